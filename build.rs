@@ -22,18 +22,16 @@ fn main() {
 
 fn scan_modules_directory(dir: &Path, modules: &mut Vec<ModuleInfo>) {
     if let Ok(entries) = fs::read_dir(dir) {
-        for entry in entries {
-            if let Ok(entry) = entry {
-                let path = entry.path();
-                if path.is_dir() {
-                    let module_name = path.file_name().unwrap().to_str().unwrap();
-                    
-                    // Look for mod.rs in the module directory
-                    let mod_file = path.join("mod.rs");
-                    if mod_file.exists() {
-                        if let Some(module_info) = extract_module_info(&mod_file, module_name) {
-                            modules.push(module_info);
-                        }
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                let module_name = path.file_name().unwrap().to_str().unwrap();
+                
+                // Look for mod.rs in the module directory
+                let mod_file = path.join("mod.rs");
+                if mod_file.exists() {
+                    if let Some(module_info) = extract_module_info(&mod_file, module_name) {
+                        modules.push(module_info);
                     }
                 }
             }
@@ -110,42 +108,5 @@ fn generate_module_code(dest_path: &Path, modules: &[ModuleInfo]) {
     
     writeln!(file, "    ]").unwrap();
     writeln!(file, "}}").unwrap();
-    
-    writeln!(file).unwrap();
-    
-    // Generate automatic command detection function
-    writeln!(file, "// Auto-generated command detection").unwrap();
-    writeln!(file, "pub fn get_all_command_ids() -> Vec<&'static str> {{").unwrap();
-    writeln!(file, "    vec![").unwrap();
-    
-    // Extract command IDs from module files
-    for module in modules {
-        if let Some(command_ids) = extract_command_ids(&format!("src/modules/{}/mod.rs", module.module_name)) {
-            for id in command_ids {
-                writeln!(file, "        \"{}\",", id).unwrap();
-            }
-        }
-    }
-    
-    writeln!(file, "    ]").unwrap();
-    writeln!(file, "}}").unwrap();
 }
 
-fn extract_command_ids(file_path: &str) -> Option<Vec<String>> {
-    let content = fs::read_to_string(file_path).ok()?;
-    let mut command_ids = Vec::new();
-    
-    for line in content.lines() {
-        let line = line.trim();
-        if line.contains(".arg(") && line.contains("Arg::new(") {
-            if let Some(start) = line.find("Arg::new(\"") {
-                if let Some(end) = line[start + 10..].find("\"") {
-                    let command_id = &line[start + 10..start + 10 + end];
-                    command_ids.push(command_id.to_string());
-                }
-            }
-        }
-    }
-    
-    Some(command_ids)
-}
\ No newline at end of file
@@ -0,0 +1,141 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+// User configuration read from $MICRO_SWISS_CONFIG or
+// ~/.config/micro-swiss/config.toml. Every section is optional.
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct Config {
+    pub run: RunConfig,
+    pub date: DateConfig,
+    pub db: DbConfig,
+    pub share: ShareConfig,
+}
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct RunConfig {
+    // Extension to command line, e.g. py = "python3" or ts = "bun run"
+    pub interpreters: HashMap<String, String>,
+}
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct DateConfig {
+    // Extra non-working days as YYYY-MM-DD
+    pub holidays: Vec<String>,
+    // Built-in holiday calendar used when --holidays is not given, e.g. "US"
+    pub country: Option<String>,
+    // IANA zones shown by --tz-now, e.g. ["Europe/Istanbul", "America/New_York"]
+    pub timezones: Vec<String>,
+}
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct DbConfig {
+    // Named connections usable as `--connect NAME`
+    pub connections: HashMap<String, DbConnectionConfig>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct DbConnectionConfig {
+    // postgres:// URL, normally without the password
+    pub url: String,
+    // "env:VAR" reads the password from an environment variable, "vault:KEY"
+    // from the --vault store, "keyring" from the OS keyring (stored with
+    // --db-save-password NAME)
+    pub password: Option<String>,
+}
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct ShareConfig {
+    // "gist" (the default) or "paste"
+    pub service: Option<String>,
+    // GitHub token with the gist scope, or "env:VAR"; $GITHUB_TOKEN otherwise
+    pub github_token: Option<String>,
+    // Pastebin that takes the raw body as a POST and answers with the URL,
+    // https://paste.rs by default
+    pub paste_url: Option<String>,
+}
+
+pub fn config_path() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var("MICRO_SWISS_CONFIG") {
+        return Some(PathBuf::from(path));
+    }
+    dirs::config_dir().map(|dir| dir.join("micro-swiss").join("config.toml"))
+}
+
+// Where modules keep state between runs (clipboard history and the like):
+// $MICRO_SWISS_DATA, or the platform data directory
+pub fn data_dir() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var("MICRO_SWISS_DATA") {
+        return Some(PathBuf::from(path));
+    }
+    dirs::data_dir().map(|dir| dir.join("micro-swiss"))
+}
+
+pub fn parse_config(content: &str) -> Result<Config, Box<dyn Error>> {
+    toml::from_str(content).map_err(|e| format!("Invalid config file: {}", e).into())
+}
+
+// A missing config file is not an error; the built-in defaults apply
+pub fn load_config() -> Result<Config, Box<dyn Error>> {
+    match config_path() {
+        Some(path) if path.exists() => {
+            let content = fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read config file {}: {}", path.display(), e))?;
+            parse_config(&content)
+        }
+        _ => Ok(Config::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_empty_config() {
+        let config = parse_config("").unwrap();
+        assert!(config.run.interpreters.is_empty());
+    }
+
+    #[test]
+    fn test_parse_run_interpreters() {
+        let config = parse_config("[run.interpreters]\npy = \"python3\"\nts = \"bun run\"\n").unwrap();
+        assert_eq!(config.run.interpreters.get("py").map(String::as_str), Some("python3"));
+        assert_eq!(config.run.interpreters.get("ts").map(String::as_str), Some("bun run"));
+    }
+
+    #[test]
+    fn test_parse_date_config() {
+        let config = parse_config("[date]\ncountry = \"DE\"\nholidays = [\"2024-12-24\", \"2024-12-31\"]\n").unwrap();
+        assert_eq!(config.date.country.as_deref(), Some("DE"));
+        assert_eq!(config.date.holidays.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_db_connections() {
+        let config = parse_config("[db.connections.prod-readonly]\nurl = \"postgres://ro@db.example.com/app\"\npassword = \"keyring\"\n").unwrap();
+        let connection = &config.db.connections["prod-readonly"];
+        assert_eq!(connection.url, "postgres://ro@db.example.com/app");
+        assert_eq!(connection.password.as_deref(), Some("keyring"));
+    }
+
+    #[test]
+    fn test_parse_share_config() {
+        let config = parse_config("[share]\nservice = \"paste\"\ngithub_token = \"env:GH_TOKEN\"\n").unwrap();
+        assert_eq!(config.share.service.as_deref(), Some("paste"));
+        assert_eq!(config.share.github_token.as_deref(), Some("env:GH_TOKEN"));
+        assert!(config.share.paste_url.is_none());
+    }
+
+    #[test]
+    fn test_parse_invalid_config() {
+        assert!(parse_config("[run\n").is_err());
+    }
+}
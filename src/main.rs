@@ -1,6 +1,8 @@
+use clap::parser::ValueSource;
 use clap::Command;
 use std::process;
 
+mod config;
 mod module_registry;
 mod tool_module;
 
@@ -48,23 +50,22 @@ fn main() {
 
     let matches = cmd.get_matches();
 
-    let mut executed = false;
     for module in registry.get_modules() {
         if let Err(e) = module.execute(&matches) {
+            if let Some(exit) = e.downcast_ref::<tool_module::ExitCodeError>() {
+                if let Some(message) = &exit.message {
+                    eprintln!("Error executing module {}: {}", module.name(), message);
+                }
+                process::exit(exit.code);
+            }
             eprintln!("Error executing module {}: {}", module.name(), e);
             process::exit(1);
         }
-
-        // Check if any command was executed using auto-discovered command IDs
-        let command_ids = module_registry::get_all_command_ids();
-        for &cmd_id in &command_ids {
-            if matches.contains_id(cmd_id) {
-                executed = true;
-                break;
-            }
-        }
     }
 
+    // Arguments with default values are always present, so only count the ones
+    // actually given on the command line
+    let executed = matches.ids().any(|id| matches.value_source(id.as_str()) == Some(ValueSource::CommandLine));
     if !executed {
         // eprintln!("Please specify a command. Use --help for usage information.");
         process::exit(1);
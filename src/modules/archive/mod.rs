@@ -0,0 +1,476 @@
+use crate::file_size::format_bytes;
+use crate::tool_module::ToolModule;
+use chrono::{DateTime, Datelike, Local, Timelike};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use colored::*;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::WalkBuilder;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+pub struct ArchiveModule;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Zip,
+    Tar,
+    TarGz,
+    TarZst,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Kind {
+    File,
+    Dir,
+    Symlink(String),
+}
+
+// Something on disk to archive, and its name inside the archive
+#[derive(Debug)]
+struct Source {
+    path: PathBuf,
+    name: String,
+    kind: Kind,
+}
+
+// An entry of an existing archive
+#[derive(Debug, PartialEq)]
+struct Member {
+    name: String,
+    kind: Kind,
+    size: u64,
+}
+
+impl Format {
+    // For new archives the name decides the format
+    fn from_name(path: &str) -> Result<Self, String> {
+        let lower = path.to_lowercase();
+        if lower.ends_with(".zip") {
+            Ok(Format::Zip)
+        } else if lower.ends_with(".tar") {
+            Ok(Format::Tar)
+        } else if lower.ends_with(".tar.gz") || lower.ends_with(".tgz") {
+            Ok(Format::TarGz)
+        } else if lower.ends_with(".tar.zst") || lower.ends_with(".tzst") {
+            Ok(Format::TarZst)
+        } else {
+            Err(format!("Can't tell the archive format from '{}'; use a .zip, .tar, .tar.gz or .tar.zst name", path))
+        }
+    }
+
+    // Existing archives are recognised by their magic bytes, whatever they are called
+    fn detect(path: &str) -> Result<Self, Box<dyn Error>> {
+        let mut magic = [0u8; 4];
+        let read = File::open(path).map_err(|e| format!("Failed to open '{}': {}", path, e))?.read(&mut magic)?;
+        Ok(match &magic[..read] {
+            [b'P', b'K', 3 | 5, 4 | 6] => Format::Zip,
+            [0x1f, 0x8b, ..] => Format::TarGz,
+            [0x28, 0xb5, 0x2f, 0xfd] => Format::TarZst,
+            _ => Format::from_name(path).unwrap_or(Format::Tar),
+        })
+    }
+}
+
+impl ToolModule for ArchiveModule {
+    fn name(&self) -> &'static str {
+        "archive"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("archive")
+                .long("archive")
+                .value_names(["ACTION", "ARCHIVE", "PATH"])
+                .num_args(2..)
+                .help("Create, extract or list zip, tar, tar.gz and tar.zst archives")
+                .long_help("One interface for zip, tar, tar.gz and tar.zst archives:\n  --archive create out.zip src/ README.md   (format from the name; skip paths with --exclude)\n  --archive extract file.tar.gz [DIR]       (into DIR, default the current directory)\n  --archive list file.zip\nExisting archives are recognised by their contents. Extraction refuses entries that would land outside DIR and never overwrites existing files.")
+        )
+        .arg(
+            Arg::new("exclude")
+                .long("exclude")
+                .value_name("PATTERN")
+                .action(ArgAction::Append)
+                .help("Leave out paths matching a .gitignore-style PATTERN from --archive create or --tree (repeatable)")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(values) = matches.get_many::<String>("archive") {
+            let values: Vec<&String> = values.collect();
+            let (action, archive, rest) = (values[0].as_str(), values[1].as_str(), &values[2..]);
+            match action {
+                "create" => {
+                    let excludes: Vec<&String> = matches.get_many::<String>("exclude").map(|values| values.collect()).unwrap_or_default();
+                    create(archive, rest, &excludes)?;
+                }
+                "extract" => {
+                    if rest.len() > 1 {
+                        return Err("--archive extract takes the archive and at most one target directory".into());
+                    }
+                    let dest = rest.first().map(|dir| dir.as_str()).unwrap_or(".");
+                    let count = extract(archive, Path::new(dest))?;
+                    println!("✅ Extracted {} entries from {} to {}", count, archive, dest);
+                }
+                "list" => {
+                    if !rest.is_empty() {
+                        return Err("--archive list takes just the archive".into());
+                    }
+                    print!("{}", render_list(&members(archive)?, fs::metadata(archive)?.len()));
+                }
+                other => return Err(format!("Unknown --archive action '{}'; use create, extract or list", other).into()),
+            }
+        }
+        Ok(())
+    }
+}
+
+fn create(archive: &str, inputs: &[&String], excludes: &[&String]) -> Result<(), Box<dyn Error>> {
+    let format = Format::from_name(archive)?;
+    if inputs.is_empty() {
+        return Err(format!("Name what to put in {}, e.g. --archive create {} src/", archive, archive).into());
+    }
+    if Path::new(archive).exists() {
+        return Err(format!("{} already exists; not overwriting it", archive).into());
+    }
+    let mut sources = Vec::new();
+    for input in inputs {
+        sources.extend(collect(Path::new(input.as_str()), excludes)?);
+    }
+    if !sources.iter().any(|source| source.kind != Kind::Dir) {
+        return Err("Nothing to archive: no files left after --exclude".into());
+    }
+    let file = File::create(archive).map_err(|e| format!("Failed to create {}: {}", archive, e))?;
+    match format {
+        Format::Zip => write_zip(file, &sources)?,
+        Format::Tar => write_tar(BufWriter::new(file), &sources)?.flush()?,
+        Format::TarGz => write_tar(GzEncoder::new(BufWriter::new(file), flate2::Compression::default()), &sources)?.finish()?.flush()?,
+        Format::TarZst => write_tar(zstd::Encoder::new(BufWriter::new(file), 0)?, &sources)?.finish()?.flush()?,
+    }
+    let files = sources.iter().filter(|source| source.kind == Kind::File).count();
+    let unpacked: u64 = sources.iter().filter(|source| source.kind == Kind::File).filter_map(|source| fs::metadata(&source.path).ok()).map(|metadata| metadata.len()).sum();
+    println!("✅ Created {} with {} files ({} → {})", archive, files, format_bytes(unpacked), format_bytes(fs::metadata(archive)?.len()));
+    Ok(())
+}
+
+// Everything under one input, named relative to the input's parent so src/ stays src/ in the archive
+fn collect(input: &Path, excludes: &[&String]) -> Result<Vec<Source>, Box<dyn Error>> {
+    fs::symlink_metadata(input).map_err(|e| format!("Can't read '{}': {}", input.display(), e))?;
+    let base = match input.file_name() {
+        Some(_) => input.parent().unwrap_or(Path::new("")).to_path_buf(),
+        None => input.to_path_buf(),
+    };
+    let mut builder = GitignoreBuilder::new(&base);
+    for pattern in excludes {
+        builder.add_line(None, pattern).map_err(|e| format!("Invalid --exclude pattern '{}': {}", pattern, e))?;
+    }
+    let ignore: Gitignore = builder.build()?;
+    let walker = WalkBuilder::new(input)
+        .standard_filters(false)
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .filter_entry(move |entry| !ignore.matched(entry.path(), entry.file_type().is_some_and(|kind| kind.is_dir())).is_ignore())
+        .build();
+    let mut sources = Vec::new();
+    for entry in walker {
+        let entry = entry?;
+        let Some(file_type) = entry.file_type() else { continue };
+        let name = archive_name(entry.path().strip_prefix(&base).unwrap_or(entry.path()));
+        if name.is_empty() {
+            continue;
+        }
+        let kind = if file_type.is_symlink() {
+            Kind::Symlink(fs::read_link(entry.path())?.to_string_lossy().into_owned())
+        } else if file_type.is_dir() {
+            Kind::Dir
+        } else if file_type.is_file() {
+            Kind::File
+        } else {
+            // Sockets, fifos and devices have no place in a portable archive
+            continue;
+        };
+        sources.push(Source { path: entry.path().to_path_buf(), name, kind });
+    }
+    Ok(sources)
+}
+
+// Archive names use / on every platform and never start with ./ or /
+fn archive_name(path: &Path) -> String {
+    path.components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn zip_options(metadata: &fs::Metadata) -> SimpleFileOptions {
+    let mut options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated).large_file(metadata.len() >= u64::from(u32::MAX));
+    if let Ok(modified) = metadata.modified() {
+        let modified: DateTime<Local> = modified.into();
+        let time = zip::DateTime::from_date_and_time(
+            modified.year().clamp(1980, 2107) as u16,
+            modified.month() as u8,
+            modified.day() as u8,
+            modified.hour() as u8,
+            modified.minute() as u8,
+            modified.second() as u8,
+        );
+        if let Ok(time) = time {
+            options = options.last_modified_time(time);
+        }
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        options = options.unix_permissions(metadata.permissions().mode() & 0o7777);
+    }
+    options
+}
+
+fn write_zip(file: File, sources: &[Source]) -> Result<(), Box<dyn Error>> {
+    let mut zip = ZipWriter::new(BufWriter::new(file));
+    for source in sources {
+        let metadata = fs::symlink_metadata(&source.path)?;
+        let options = zip_options(&metadata);
+        match &source.kind {
+            Kind::Dir => zip.add_directory(format!("{}/", source.name), options)?,
+            Kind::File => {
+                zip.start_file(source.name.as_str(), options)?;
+                io::copy(&mut File::open(&source.path)?, &mut zip)?;
+            }
+            Kind::Symlink(target) => zip.add_symlink(source.name.as_str(), target, options)?,
+        }
+    }
+    zip.finish()?.flush()?;
+    Ok(())
+}
+
+fn write_tar<W: Write>(writer: W, sources: &[Source]) -> io::Result<W> {
+    let mut builder = tar::Builder::new(writer);
+    builder.follow_symlinks(false);
+    for source in sources {
+        builder.append_path_with_name(&source.path, &source.name)?;
+    }
+    builder.into_inner()
+}
+
+fn open_tar(path: &str, format: Format) -> Result<tar::Archive<Box<dyn Read>>, Box<dyn Error>> {
+    let file = BufReader::new(File::open(path).map_err(|e| format!("Failed to open '{}': {}", path, e))?);
+    let reader: Box<dyn Read> = match format {
+        Format::TarGz => Box::new(GzDecoder::new(file)),
+        Format::TarZst => Box::new(zstd::Decoder::with_buffer(file)?),
+        _ => Box::new(file),
+    };
+    Ok(tar::Archive::new(reader))
+}
+
+fn open_zip(path: &str) -> Result<ZipArchive<BufReader<File>>, Box<dyn Error>> {
+    let file = File::open(path).map_err(|e| format!("Failed to open '{}': {}", path, e))?;
+    ZipArchive::new(BufReader::new(file)).map_err(|e| format!("'{}' is not a valid zip archive: {}", path, e).into())
+}
+
+fn members(path: &str) -> Result<Vec<Member>, Box<dyn Error>> {
+    let mut members = Vec::new();
+    match Format::detect(path)? {
+        Format::Zip => {
+            let mut archive = open_zip(path)?;
+            for index in 0..archive.len() {
+                let mut file = archive.by_index(index)?;
+                let name = file.name().trim_end_matches('/').to_string();
+                let size = file.size();
+                let kind = if file.is_dir() {
+                    Kind::Dir
+                } else if file.is_symlink() {
+                    let mut target = String::new();
+                    file.read_to_string(&mut target)?;
+                    Kind::Symlink(target)
+                } else {
+                    Kind::File
+                };
+                members.push(Member { name, kind, size });
+            }
+        }
+        format => {
+            let mut archive = open_tar(path, format)?;
+            for entry in archive.entries().map_err(|e| format!("'{}' is not a valid tar archive: {}", path, e))? {
+                let entry = entry.map_err(|e| format!("'{}' is not a valid tar archive: {}", path, e))?;
+                let header = entry.header();
+                let name = archive_name(&entry.path()?);
+                let kind = if header.entry_type().is_dir() {
+                    Kind::Dir
+                } else if header.entry_type().is_symlink() {
+                    Kind::Symlink(entry.link_name()?.map(|target| target.to_string_lossy().into_owned()).unwrap_or_default())
+                } else {
+                    Kind::File
+                };
+                members.push(Member { name, kind, size: entry.size() });
+            }
+        }
+    }
+    Ok(members)
+}
+
+fn render_list(members: &[Member], archive_size: u64) -> String {
+    let mut output = String::new();
+    for member in members {
+        let (size, name) = match &member.kind {
+            Kind::Dir => ("-".to_string(), format!("{}/", member.name).blue().to_string()),
+            Kind::Symlink(target) => ("-".to_string(), format!("{} -> {}", member.name.cyan(), target)),
+            Kind::File => (format_bytes(member.size), member.name.clone()),
+        };
+        output.push_str(&format!("{:>10}  {}\n", size, name));
+    }
+    let files: Vec<&Member> = members.iter().filter(|member| member.kind == Kind::File).collect();
+    let dirs = members.iter().filter(|member| member.kind == Kind::Dir).count();
+    let total: u64 = files.iter().map(|member| member.size).sum();
+    output.push_str(&format!(
+        "{} files, {} directories, {} unpacked ({} archive)\n",
+        files.len(),
+        dirs,
+        format_bytes(total),
+        format_bytes(archive_size)
+    ));
+    output
+}
+
+// Every entry must stay inside the target and must not replace anything already there;
+// checked up front so a refused archive leaves nothing half-extracted
+fn check_targets(members: &[Member], dest: &Path) -> Result<(), String> {
+    for member in members {
+        let relative = Path::new(&member.name);
+        if relative.components().any(|component| matches!(component, Component::ParentDir | Component::Prefix(_))) {
+            return Err(format!("Refusing to extract '{}': it points outside the target directory", member.name));
+        }
+        let target = dest.join(archive_name(relative));
+        match fs::symlink_metadata(&target) {
+            Ok(metadata) if member.kind == Kind::Dir && metadata.is_dir() => {}
+            Ok(_) => return Err(format!("{} already exists; not overwriting it", target.display())),
+            Err(_) => {}
+        }
+    }
+    Ok(())
+}
+
+fn extract(path: &str, dest: &Path) -> Result<usize, Box<dyn Error>> {
+    let members = members(path)?;
+    check_targets(&members, dest)?;
+    fs::create_dir_all(dest).map_err(|e| format!("Failed to create {}: {}", dest.display(), e))?;
+    match Format::detect(path)? {
+        Format::Zip => extract_zip(path, dest)?,
+        format => {
+            let mut archive = open_tar(path, format)?;
+            archive.set_overwrite(false);
+            for entry in archive.entries()? {
+                // unpack_in keeps every entry inside dest, also through symlinks
+                entry?.unpack_in(dest)?;
+            }
+        }
+    }
+    Ok(members.len())
+}
+
+fn extract_zip(path: &str, dest: &Path) -> Result<(), Box<dyn Error>> {
+    let mut archive = open_zip(path)?;
+    let root = dest.canonicalize()?;
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index)?;
+        let name = file.enclosed_name().ok_or_else(|| format!("Refusing to extract '{}': it points outside the target directory", file.name()))?;
+        let target = dest.join(name);
+        if file.is_dir() {
+            fs::create_dir_all(&target)?;
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+            // A symlink extracted earlier must not carry later entries elsewhere
+            if !parent.canonicalize()?.starts_with(&root) {
+                return Err(format!("Refusing to extract '{}': it points outside the target directory", file.name()).into());
+            }
+        }
+        if file.is_symlink() {
+            let mut link = String::new();
+            file.read_to_string(&mut link)?;
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(&link, &target)?;
+            #[cfg(not(unix))]
+            eprintln!("⚠️  Skipped the symlink {} -> {}", target.display(), link);
+            continue;
+        }
+        let mut out = File::create_new(&target).map_err(|e| format!("Failed to create {}: {}", target.display(), e))?;
+        io::copy(&mut file, &mut out)?;
+        #[cfg(unix)]
+        if let Some(mode) = file.unix_mode() {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&target, fs::Permissions::from_mode(mode & 0o777))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A scratch directory with src/a.txt, src/target/big.bin and src/sub/b.txt
+    fn fixture(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("ms-archive-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/sub")).unwrap();
+        fs::create_dir_all(root.join("src/target")).unwrap();
+        fs::write(root.join("src/a.txt"), "alpha").unwrap();
+        fs::write(root.join("src/sub/b.txt"), "bravo bravo").unwrap();
+        fs::write(root.join("src/target/big.bin"), [0u8; 64]).unwrap();
+        root
+    }
+
+    fn round_trip(extension: &str) {
+        let root = fixture(extension);
+        let archive = root.join(format!("out.{}", extension)).to_string_lossy().into_owned();
+        let input = root.join("src").to_string_lossy().into_owned();
+        create(&archive, &[&input], &[&"target".to_string()]).unwrap();
+        let listed: Vec<(String, Kind)> = members(&archive).unwrap().into_iter().map(|member| (member.name, member.kind)).collect();
+        assert_eq!(
+            listed,
+            [
+                ("src".to_string(), Kind::Dir),
+                ("src/a.txt".to_string(), Kind::File),
+                ("src/sub".to_string(), Kind::Dir),
+                ("src/sub/b.txt".to_string(), Kind::File)
+            ]
+        );
+        let dest = root.join("unpacked");
+        assert_eq!(extract(&archive, &dest).unwrap(), 4);
+        assert_eq!(fs::read_to_string(dest.join("src/sub/b.txt")).unwrap(), "bravo bravo");
+        // A second extraction would overwrite a.txt
+        assert!(extract(&archive, &dest).unwrap_err().to_string().contains("not overwriting"));
+        assert!(create(&archive, &[&input], &[]).is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_zip_round_trip() {
+        round_trip("zip");
+    }
+
+    #[test]
+    fn test_tar_round_trips() {
+        round_trip("tar.gz");
+        round_trip("tar.zst");
+    }
+
+    #[test]
+    fn test_names_and_formats() {
+        assert_eq!(Format::from_name("backup.TGZ").unwrap(), Format::TarGz);
+        assert_eq!(Format::from_name("a.tar.zst").unwrap(), Format::TarZst);
+        assert!(Format::from_name("a.rar").is_err());
+        assert_eq!(archive_name(Path::new("./src/main.rs")), "src/main.rs");
+        let escaping = [Member { name: "../evil".to_string(), kind: Kind::File, size: 1 }];
+        assert!(check_targets(&escaping, Path::new(".")).unwrap_err().contains("outside the target"));
+    }
+}
@@ -0,0 +1,311 @@
+use crate::color_convert::{parse_color, Color};
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use std::collections::HashMap;
+use std::error::Error;
+
+pub struct BannerModule;
+
+// FIGlet (.flf) fonts, covering printable ASCII
+const FONTS: [(&str, &str); 3] = [
+    ("standard", include_str!("fonts/standard.flf")),
+    ("big", include_str!("fonts/big.flf")),
+    ("slant", include_str!("fonts/slant.flf")),
+];
+const DEFAULT_WIDTH: usize = 80;
+const DEFAULT_GRADIENT: [&str; 2] = ["#ff5f6d", "#ffc371"];
+
+// Smushing rules from the font header's layout field
+const SMUSH_EQUAL: i32 = 1;
+const SMUSH_UNDERSCORE: i32 = 2;
+const SMUSH_HIERARCHY: i32 = 4;
+const SMUSH_PAIR: i32 = 8;
+const SMUSH_BIG_X: i32 = 16;
+const SMUSH_HARDBLANK: i32 = 32;
+
+struct Font {
+    // Drawn like a space but never smushed away
+    hardblank: char,
+    height: usize,
+    // -1 full width, 0 kerning, otherwise the smushing rules
+    layout: i32,
+    glyphs: HashMap<char, Vec<Vec<char>>>,
+}
+
+impl ToolModule for BannerModule {
+    fn name(&self) -> &'static str {
+        "banner"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("banner")
+                .long("banner")
+                .value_name("TEXT")
+                .help("Render text as a large FIGlet-style ASCII banner")
+                .long_help("Render text as large ASCII art letters, FIGlet-style, for script headers and terminal announcements. Letters are fitted together the way FIGlet smushes them, lines wrap at word boundaries to stay within --width (default: 80), and \\n in the text starts a new line. Only printable ASCII can be drawn; other characters are skipped.")
+        )
+        .arg(
+            Arg::new("font")
+                .long("font")
+                .value_name("FONT")
+                .value_parser(FONTS.map(|(name, _)| name))
+                .help("Font for --banner (default: standard)")
+                .requires("banner")
+        )
+        .arg(
+            Arg::new("gradient")
+                .long("gradient")
+                .value_names(["FROM", "TO"])
+                .num_args(0..=2)
+                .help("Color --banner with a left-to-right gradient; colors as hex, rgb or hsl (default: #ff5f6d to #ffc371)")
+                .long_help("Color --banner with a 24-bit left-to-right gradient between two colors, written as for --color-convert: hex (#ff0000), rgb (255,0,0) or hsl(0,100%,50%). With one color the banner is drawn in that color; with none, #ff5f6d to #ffc371. Needs a truecolor terminal.")
+                .requires("banner")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(text) = matches.get_one::<String>("banner") {
+            let name = matches.get_one::<String>("font").map_or("standard", String::as_str);
+            let font = load_font(name)?;
+            let width = matches.get_one::<u32>("width").map_or(DEFAULT_WIDTH, |width| *width as usize);
+            let lines = render(&text.replace("\\n", "\n"), &font, width);
+            let gradient = match matches.get_many::<String>("gradient") {
+                Some(colors) => {
+                    let colors = colors.map(|color| parse_color(color)).collect::<Result<Vec<Color>, _>>()?;
+                    Some(match colors[..] {
+                        [from, to] => (from, to),
+                        [only] => (only, only),
+                        _ => (parse_color(DEFAULT_GRADIENT[0])?, parse_color(DEFAULT_GRADIENT[1])?),
+                    })
+                }
+                None => None,
+            };
+            let columns = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+            for line in &lines {
+                match gradient {
+                    Some((from, to)) => println!("{}", paint(line, from, to, columns)),
+                    None => println!("{}", line),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn load_font(name: &str) -> Result<Font, String> {
+    let (_, data) = FONTS.iter().find(|(font, _)| *font == name).ok_or_else(|| format!("Unknown font '{}'", name))?;
+    parse_font(data)
+}
+
+// Header "flf2a$ height baseline max_length layout comment_lines", the comments,
+// then each character from space to ~ as `height` rows ending in an endmark like @
+fn parse_font(data: &str) -> Result<Font, String> {
+    let mut lines = data.lines();
+    let header = lines.next().ok_or("Empty font file")?;
+    let fields: Vec<&str> = header.split_whitespace().collect();
+    let signature = fields.first().filter(|field| field.starts_with("flf2a")).ok_or("Not a FIGlet font")?;
+    let hardblank = signature.chars().nth(5).ok_or("Font header has no hardblank")?;
+    let number = |index: usize| -> Result<i32, String> {
+        fields.get(index).and_then(|field| field.parse().ok()).ok_or_else(|| format!("Font header field {} is missing or invalid", index))
+    };
+    let height = number(1)? as usize;
+    let layout = number(4)?;
+    let mut lines = lines.skip(number(5)? as usize);
+    let mut glyphs = HashMap::new();
+    for code in 32u8..=126 {
+        let mut rows = Vec::with_capacity(height);
+        for _ in 0..height {
+            let line = lines.next().ok_or_else(|| format!("Font ends before {:?}", code as char))?;
+            let endmark = line.chars().last().unwrap_or('@');
+            rows.push(line.trim_end_matches(endmark).chars().collect::<Vec<char>>());
+        }
+        glyphs.insert(code as char, rows);
+    }
+    Ok(Font { hardblank, height, layout, glyphs })
+}
+
+// The character two overlapping ones merge into, if the font's rules allow it
+fn smush(left: char, right: char, font: &Font) -> Option<char> {
+    if left == ' ' {
+        return Some(right);
+    }
+    if right == ' ' {
+        return Some(left);
+    }
+    let rules = font.layout;
+    if rules <= 0 {
+        return None;
+    }
+    if left == font.hardblank || right == font.hardblank {
+        return (rules & SMUSH_HARDBLANK != 0 && left == right).then_some(left);
+    }
+    if rules & SMUSH_EQUAL != 0 && left == right {
+        return Some(left);
+    }
+    if rules & SMUSH_UNDERSCORE != 0 {
+        const BORDERS: &str = "|/\\[]{}()<>";
+        if left == '_' && BORDERS.contains(right) {
+            return Some(right);
+        }
+        if right == '_' && BORDERS.contains(left) {
+            return Some(left);
+        }
+    }
+    if rules & SMUSH_HIERARCHY != 0 {
+        // A character from a later class replaces one from an earlier class
+        const CLASSES: [&str; 6] = ["|", "/\\", "[]", "{}", "()", "<>"];
+        let class = |c: char| CLASSES.iter().position(|class| class.contains(c));
+        if let (Some(left_class), Some(right_class)) = (class(left), class(right)) {
+            if left_class != right_class {
+                return Some(if left_class > right_class { left } else { right });
+            }
+        }
+    }
+    if rules & SMUSH_PAIR != 0 && matches!((left, right), ('[', ']') | (']', '[') | ('{', '}') | ('}', '{') | ('(', ')') | (')', '(')) {
+        return Some('|');
+    }
+    if rules & SMUSH_BIG_X != 0 {
+        match (left, right) {
+            ('/', '\\') => return Some('|'),
+            ('\\', '/') => return Some('Y'),
+            ('>', '<') => return Some('X'),
+            _ => {}
+        }
+    }
+    None
+}
+
+// How many columns a glyph can slide left into the line drawn so far
+fn overlap(line: &[Vec<char>], glyph: &[Vec<char>], font: &Font) -> usize {
+    let mut amount = glyph.first().map_or(0, Vec::len);
+    for (drawn, row) in line.iter().zip(glyph) {
+        let trailing = drawn.iter().rev().take_while(|c| **c == ' ').count();
+        let leading = row.iter().take_while(|c| **c == ' ').count();
+        let mut fits = trailing + leading;
+        if let (Some(left), Some(right)) = (drawn.len().checked_sub(trailing + 1).map(|i| drawn[i]), row.get(leading)) {
+            if smush(left, *right, font).is_some() {
+                fits += 1;
+            }
+        }
+        amount = amount.min(fits);
+    }
+    amount
+}
+
+fn append(line: &mut [Vec<char>], glyph: &[Vec<char>], font: &Font) {
+    let amount = if font.layout < 0 { 0 } else { overlap(line, glyph, font) };
+    for (drawn, row) in line.iter_mut().zip(glyph) {
+        // At the start of a line the overlap only eats the glyph's leading blanks
+        let shared = amount.min(drawn.len());
+        let start = drawn.len() - shared;
+        for (k, c) in row.iter().skip(amount - shared).take(shared).enumerate() {
+            drawn[start + k] = smush(drawn[start + k], *c, font).unwrap_or(drawn[start + k]);
+        }
+        drawn.extend_from_slice(&row[amount.min(row.len())..]);
+    }
+}
+
+fn draw(text: &str, font: &Font) -> Vec<Vec<char>> {
+    let mut line = vec![Vec::new(); font.height];
+    for glyph in text.chars().filter_map(|c| font.glyphs.get(&c)) {
+        append(&mut line, glyph, font);
+    }
+    line
+}
+
+// Banner rows for each line of text, wrapped at spaces (or mid-word if one word is too wide)
+fn render(text: &str, font: &Font, width: usize) -> Vec<String> {
+    let too_wide = |text: &str| draw(text, font).first().map_or(0, Vec::len) > width;
+    let mut rows: Vec<String> = Vec::new();
+    for text_line in text.lines() {
+        let mut pieces: Vec<String> = Vec::new();
+        let mut current = String::new();
+        for word in text_line.split(' ') {
+            let joined = if current.is_empty() { word.to_string() } else { format!("{} {}", current, word) };
+            if !too_wide(&joined) {
+                current = joined;
+                continue;
+            }
+            if !current.is_empty() {
+                pieces.push(std::mem::take(&mut current));
+            }
+            for c in word.chars() {
+                let longer = format!("{}{}", current, c);
+                if too_wide(&longer) && !current.is_empty() {
+                    pieces.push(std::mem::replace(&mut current, c.to_string()));
+                } else {
+                    current = longer;
+                }
+            }
+        }
+        pieces.push(current);
+        for piece in pieces {
+            for row in draw(&piece, font) {
+                let row: String = row.into_iter().map(|c| if c == font.hardblank { ' ' } else { c }).collect();
+                rows.push(row.trim_end().to_string());
+            }
+        }
+    }
+    rows
+}
+
+fn paint(line: &str, from: Color, to: Color, columns: usize) -> String {
+    let blend = |a: u8, b: u8, t: f64| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+    line.chars()
+        .enumerate()
+        .map(|(column, c)| {
+            if c == ' ' {
+                return c.to_string();
+            }
+            let t = if columns > 1 { column as f64 / (columns - 1) as f64 } else { 0.0 };
+            c.to_string().truecolor(blend(from.r, to.r, t), blend(from.g, to.g, t), blend(from.b, to.b, t)).to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fonts_parse() {
+        for (name, _) in FONTS {
+            let font = load_font(name).unwrap();
+            assert_eq!(font.glyphs.len(), 95, "{}", name);
+            assert!(font.glyphs.values().all(|rows| rows.len() == font.height), "{}", name);
+        }
+        assert!(load_font("banner3").is_err());
+        assert!(parse_font("flf2a$ 6 5").is_err());
+    }
+
+    #[test]
+    fn test_render_smushes_letters() {
+        let font = load_font("standard").unwrap();
+        let lines = render("Hi", &font, DEFAULT_WIDTH);
+        assert_eq!(lines, [" _   _ _", "| | | (_)", "| |_| | |", "|  _  | |", "|_| |_|_|", ""]);
+    }
+
+    #[test]
+    fn test_smush_rules() {
+        let font = load_font("standard").unwrap();
+        assert_eq!(smush('|', '|', &font), Some('|'));
+        assert_eq!(smush('_', '/', &font), Some('/'));
+        assert_eq!(smush('|', ')', &font), Some(')'));
+        assert_eq!(smush(']', '[', &font), Some('|'));
+        // Big X is not among the standard font's rules
+        assert_eq!(smush('/', '\\', &font), None);
+        assert_eq!(smush('$', 'x', &font), None);
+    }
+
+    #[test]
+    fn test_render_wraps_at_width() {
+        let font = load_font("standard").unwrap();
+        let lines = render("RELEASE 2.0 IS OUT", &font, 40);
+        assert_eq!(lines.len() % font.height, 0);
+        assert!(lines.len() > font.height);
+        assert!(lines.iter().all(|line| line.chars().count() <= 40));
+        assert_eq!(render("a\nb", &font, 40).len(), 2 * font.height);
+    }
+}
@@ -0,0 +1,351 @@
+use crate::qr_generate::save_modules;
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgMatches, Command};
+use std::error::Error;
+
+pub struct BarcodeGenModule;
+
+// Light margin on each side, as wide as the scanners of all three symbologies need
+const QUIET_ZONE: usize = 10;
+// Bar size in pixels for --barcode-out images
+const MODULE_PIXELS: u32 = 3;
+const BAR_PIXELS: u32 = 150;
+// Terminal lines per rendered barcode
+const BAR_LINES: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Symbology {
+    Ean13,
+    Code128,
+    Code39,
+}
+
+// EAN-13 digit patterns for the left half (odd parity); the even-parity "G"
+// and right-half "R" patterns are derived from these
+const EAN_L: [&str; 10] = [
+    "0001101", "0011001", "0010011", "0111101", "0100011", "0110001", "0101111", "0111011", "0110111", "0001011",
+];
+// Which of the six left digits use G patterns, set by the first digit
+const EAN_PARITY: [&str; 10] = ["LLLLLL", "LLGLGG", "LLGGLG", "LLGGGL", "LGLLGG", "LGGLLG", "LGGGLL", "LGLGLG", "LGLGGL", "LGGLGL"];
+
+// Code 128 bar and space widths by symbol value; 103-105 are Start A/B/C, 106 is Stop
+const CODE128: [&str; 107] = [
+    "212222", "222122", "222221", "121223", "121322", "131222", "122213", "122312", "132212", "221213",
+    "221312", "231212", "112232", "122132", "122231", "113222", "123122", "123221", "223211", "221132",
+    "221231", "213212", "223112", "312131", "311222", "321122", "321221", "312212", "322112", "322211",
+    "212123", "212321", "232121", "111323", "131123", "131321", "112313", "132113", "132311", "211313",
+    "231113", "231311", "112133", "112331", "132131", "113123", "113321", "133121", "313121", "211331",
+    "231131", "213113", "213311", "213131", "311123", "311321", "331121", "312113", "312311", "332111",
+    "314111", "221411", "431111", "111224", "111422", "121124", "121421", "141122", "141221", "112214",
+    "112412", "122114", "122411", "142112", "142211", "241211", "221114", "413111", "241112", "134111",
+    "111242", "121142", "121241", "114212", "124112", "124211", "411212", "421112", "421211", "212141",
+    "214121", "412121", "111143", "111341", "131141", "114113", "114311", "411113", "411311", "113141",
+    "114131", "311141", "411131", "211412", "211214", "211232", "2331112",
+];
+const CODE128_CODE_C: usize = 99;
+const CODE128_CODE_B: usize = 100;
+const CODE128_START_B: usize = 104;
+const CODE128_START_C: usize = 105;
+const CODE128_STOP: usize = 106;
+
+// Code 39 characters and their five bars and four spaces, W marking the wide ones
+const CODE39: &[(char, &str)] = &[
+    ('0', "NNNWWNWNN"), ('1', "WNNWNNNNW"), ('2', "NNWWNNNNW"), ('3', "WNWWNNNNN"), ('4', "NNNWWNNNW"),
+    ('5', "WNNWWNNNN"), ('6', "NNWWWNNNN"), ('7', "NNNWNNWNW"), ('8', "WNNWNNWNN"), ('9', "NNWWNNWNN"),
+    ('A', "WNNNNWNNW"), ('B', "NNWNNWNNW"), ('C', "WNWNNWNNN"), ('D', "NNNNWWNNW"), ('E', "WNNNWWNNN"),
+    ('F', "NNWNWWNNN"), ('G', "NNNNNWWNW"), ('H', "WNNNNWWNN"), ('I', "NNWNNWWNN"), ('J', "NNNNWWWNN"),
+    ('K', "WNNNNNNWW"), ('L', "NNWNNNNWW"), ('M', "WNWNNNNWN"), ('N', "NNNNWNNWW"), ('O', "WNNNWNNWN"),
+    ('P', "NNWNWNNWN"), ('Q', "NNNNNNWWW"), ('R', "WNNNNNWWN"), ('S', "NNWNNNWWN"), ('T', "NNNNWNWWN"),
+    ('U', "WWNNNNNNW"), ('V', "NWWNNNNNW"), ('W', "WWWNNNNNN"), ('X', "NWNNWNNNW"), ('Y', "WWNNWNNNN"),
+    ('Z', "NWWNWNNNN"), ('-', "NWNNNNWNW"), ('.', "WWNNNNWNN"), (' ', "NWWNNNWNN"), ('$', "NWNWNWNNN"),
+    ('/', "NWNWNNNWN"), ('+', "NWNNNWNWN"), ('%', "NNNWNWNWN"), ('*', "NWNNWNWNN"),
+];
+const CODE39_WIDE: usize = 3;
+
+impl ToolModule for BarcodeGenModule {
+    fn name(&self) -> &'static str {
+        "barcode-gen"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("barcode")
+                .long("barcode")
+                .value_names(["DATA", "TYPE"])
+                .num_args(1..=2)
+                .help("Generate a barcode: ean13, code128 or code39 (default: ean13 for 12-13 digits, else code128)")
+                .long_help("Generate a 1D barcode and print it to the terminal, or save it with --barcode-out. TYPE is ean13 (12 digits, or 13 with a check digit that is verified), code128 (any printable ASCII, the most compact) or code39 (A-Z, 0-9, space and - . $ / + %). Without TYPE, 12 or 13 digits become EAN-13 and anything else Code 128. Use --invert on light-background terminals.")
+        )
+        .arg(
+            Arg::new("barcode-out")
+                .long("barcode-out")
+                .value_name("FILE")
+                .help("Save the barcode as a PNG or SVG image instead of printing it")
+                .requires("barcode")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(values) = matches.get_many::<String>("barcode") {
+            let values: Vec<&String> = values.collect();
+            let data = values[0].as_str();
+            let symbology = match values.get(1) {
+                Some(name) => parse_symbology(name)?,
+                None => default_symbology(data),
+            };
+            let (text, bars) = encode(data, symbology)?;
+            let mut modules = vec![false; QUIET_ZONE];
+            modules.extend(bars);
+            modules.extend([false; QUIET_ZONE]);
+            if let Some(out) = matches.get_one::<String>("barcode-out") {
+                save_modules(out, &[modules], MODULE_PIXELS, BAR_PIXELS)?;
+                println!("✅ Saved {} barcode for {} to {}", symbology_name(symbology), text, out);
+            } else {
+                println!("{}", render(&modules, &text, matches.get_flag("invert")));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_symbology(name: &str) -> Result<Symbology, String> {
+    match name.to_lowercase().replace(['-', '_'], "").as_str() {
+        "ean13" | "ean" => Ok(Symbology::Ean13),
+        "code128" => Ok(Symbology::Code128),
+        "code39" => Ok(Symbology::Code39),
+        _ => Err(format!("Unknown barcode type '{}'. Use ean13, code128 or code39", name)),
+    }
+}
+
+fn default_symbology(data: &str) -> Symbology {
+    if matches!(data.len(), 12 | 13) && data.bytes().all(|byte| byte.is_ascii_digit()) {
+        Symbology::Ean13
+    } else {
+        Symbology::Code128
+    }
+}
+
+fn symbology_name(symbology: Symbology) -> &'static str {
+    match symbology {
+        Symbology::Ean13 => "EAN-13",
+        Symbology::Code128 => "Code 128",
+        Symbology::Code39 => "Code 39",
+    }
+}
+
+// The human-readable text (EAN-13 gains its check digit) and the dark modules
+fn encode(data: &str, symbology: Symbology) -> Result<(String, Vec<bool>), String> {
+    match symbology {
+        Symbology::Ean13 => encode_ean13(data),
+        Symbology::Code128 => Ok((data.to_string(), encode_code128(data)?)),
+        Symbology::Code39 => Ok((data.to_string(), encode_code39(data)?)),
+    }
+}
+
+fn ean13_check_digit(digits: &[u8]) -> u8 {
+    let sum: u32 = digits.iter().enumerate().map(|(index, &digit)| digit as u32 * if index % 2 == 0 { 1 } else { 3 }).sum();
+    ((10 - sum % 10) % 10) as u8
+}
+
+fn encode_ean13(data: &str) -> Result<(String, Vec<bool>), String> {
+    if !matches!(data.len(), 12 | 13) || !data.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(format!("EAN-13 needs 12 digits, or 13 including the check digit; got '{}'", data));
+    }
+    let mut digits: Vec<u8> = data.bytes().map(|byte| byte - b'0').collect();
+    let check = ean13_check_digit(&digits[..12]);
+    match digits.get(12) {
+        Some(&given) if given != check => return Err(format!("Invalid EAN-13 check digit {} for {} (expected {})", given, &data[..12], check)),
+        Some(_) => {}
+        None => digits.push(check),
+    }
+
+    let pattern = |digit: u8, set: char| -> String {
+        let l = EAN_L[digit as usize];
+        match set {
+            'L' => l.to_string(),
+            // R inverts L; G is R reversed
+            'R' => l.chars().map(|bit| if bit == '0' { '1' } else { '0' }).collect(),
+            _ => l.chars().rev().map(|bit| if bit == '0' { '1' } else { '0' }).collect(),
+        }
+    };
+    let mut bits = String::from("101");
+    for (&digit, set) in digits[1..7].iter().zip(EAN_PARITY[digits[0] as usize].chars()) {
+        bits.push_str(&pattern(digit, set));
+    }
+    bits.push_str("01010");
+    for &digit in &digits[7..] {
+        bits.push_str(&pattern(digit, 'R'));
+    }
+    bits.push_str("101");
+    let text = digits.iter().map(|digit| (b'0' + digit) as char).collect();
+    Ok((text, bits.chars().map(|bit| bit == '1').collect()))
+}
+
+// Alternating bar and space widths, starting with a bar
+fn widths_to_modules(widths: impl Iterator<Item = usize>, modules: &mut Vec<bool>) {
+    for (index, width) in widths.enumerate() {
+        modules.extend(std::iter::repeat_n(index % 2 == 0, width));
+    }
+}
+
+// Code set B for text, switching to C (two digits per symbol) for runs of
+// digits long enough to save space
+fn code128_values(data: &str) -> Result<Vec<usize>, String> {
+    if data.is_empty() {
+        return Err("Nothing to encode".to_string());
+    }
+    if let Some(invalid) = data.chars().find(|c| !(' '..='~').contains(c)) {
+        return Err(format!("Code 128 cannot encode {:?}; use printable ASCII", invalid));
+    }
+    let bytes = data.as_bytes();
+    let mut values = Vec::new();
+    let mut in_c = None;
+    let mut index = 0;
+    while index < bytes.len() {
+        let digits = bytes[index..].iter().take_while(|byte| byte.is_ascii_digit()).count();
+        let threshold = if index == 0 || index + digits == bytes.len() { 4 } else { 6 };
+        if digits >= threshold {
+            match in_c {
+                None => values.push(CODE128_START_C),
+                Some(false) => values.push(CODE128_CODE_C),
+                Some(true) => {}
+            }
+            in_c = Some(true);
+            // An odd digit left over goes into code set B
+            for pair in bytes[index..index + digits - digits % 2].chunks(2) {
+                values.push(((pair[0] - b'0') * 10 + (pair[1] - b'0')) as usize);
+            }
+            index += digits - digits % 2;
+        } else {
+            match in_c {
+                None => values.push(CODE128_START_B),
+                Some(true) => values.push(CODE128_CODE_B),
+                Some(false) => {}
+            }
+            in_c = Some(false);
+            values.push((bytes[index] - b' ') as usize);
+            index += 1;
+        }
+    }
+    let checksum = values.iter().enumerate().map(|(position, value)| value * position.max(1)).sum::<usize>() % 103;
+    values.push(checksum);
+    values.push(CODE128_STOP);
+    Ok(values)
+}
+
+fn encode_code128(data: &str) -> Result<Vec<bool>, String> {
+    let mut modules = Vec::new();
+    for value in code128_values(data)? {
+        widths_to_modules(CODE128[value].bytes().map(|width| (width - b'0') as usize), &mut modules);
+    }
+    Ok(modules)
+}
+
+// Start and stop with '*', one narrow space between characters
+fn encode_code39(data: &str) -> Result<Vec<bool>, String> {
+    if data.is_empty() {
+        return Err("Nothing to encode".to_string());
+    }
+    let mut modules = Vec::new();
+    for (index, c) in format!("*{}*", data).chars().enumerate() {
+        let pattern = CODE39
+            .iter()
+            .find(|(known, _)| *known == c && (c != '*' || index == 0 || index == data.chars().count() + 1))
+            .map(|(_, pattern)| *pattern)
+            .ok_or_else(|| format!("Code 39 cannot encode {:?}; use A-Z, 0-9, space and - . $ / + %", c))?;
+        if index > 0 {
+            modules.push(false);
+        }
+        widths_to_modules(pattern.chars().map(|element| if element == 'W' { CODE39_WIDE } else { 1 }), &mut modules);
+    }
+    Ok(modules)
+}
+
+// Two modules per character with half blocks. Without --invert, light modules
+// are drawn as blocks, which suits dark terminals, like the QR renderer.
+fn render(modules: &[bool], text: &str, invert: bool) -> String {
+    let line: String = modules
+        .chunks(2)
+        .map(|pair| {
+            let filled = |module: Option<&bool>| module.is_some_and(|&dark| dark == invert);
+            match (filled(pair.first()), filled(pair.get(1))) {
+                (true, true) => '█',
+                (true, false) => '▌',
+                (false, true) => '▐',
+                (false, false) => ' ',
+            }
+        })
+        .collect();
+    let width = line.chars().count();
+    let mut lines = vec![line; BAR_LINES];
+    lines.push(format!("{:^width$}", text, width = width).trim_end().to_string());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bits(modules: &[bool]) -> String {
+        modules.iter().map(|&dark| if dark { '1' } else { '0' }).collect()
+    }
+
+    #[test]
+    fn test_ean13() {
+        let (text, modules) = encode_ean13("400638133393").unwrap();
+        assert_eq!(text, "4006381333931");
+        assert_eq!(modules.len(), 95);
+        // Guard, then 0 as L, 0 as G, 6 as L with first digit 4's LGLLGG parity
+        assert!(bits(&modules).starts_with(&format!("101{}{}{}", "0001101", "0100111", "0101111")));
+        assert!(bits(&modules).ends_with(&format!("{}101", "1100110")));
+        assert_eq!(encode_ean13("4006381333931").unwrap().0, "4006381333931");
+        assert!(encode_ean13("4006381333932").unwrap_err().contains("expected 1"));
+        assert!(encode_ean13("12345").is_err());
+        assert_eq!(default_symbology("012345678905"), Symbology::Ean13);
+        assert_eq!(default_symbology("ABC-123"), Symbology::Code128);
+    }
+
+    #[test]
+    fn test_code128_table() {
+        for (value, widths) in CODE128.iter().enumerate() {
+            let total: u32 = widths.bytes().map(|width| (width - b'0') as u32).sum();
+            assert_eq!(total, if value == CODE128_STOP { 13 } else { 11 }, "{}", value);
+            assert_eq!(CODE128.iter().filter(|other| *other == widths).count(), 1, "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_code128_values() {
+        // Start B, P J J 1 2 3 C, checksum, stop
+        assert_eq!(code128_values("PJJ123C").unwrap(), vec![104, 48, 42, 42, 17, 18, 19, 35, 55, 106]);
+        // Leading digits use code set C, the odd one left goes to B
+        assert_eq!(code128_values("12345").unwrap(), vec![105, 12, 34, 100, 21, 54, 106]);
+        assert_eq!(code128_values("AB123456").unwrap()[..6], [104, 33, 34, 99, 12, 34]);
+        assert!(code128_values("tab\there").is_err());
+        assert!(code128_values("naïve").is_err());
+        assert_eq!(encode_code128("Hi").unwrap().len(), 11 * 4 + 13);
+    }
+
+    #[test]
+    fn test_code39() {
+        let modules = encode_code39("A1").unwrap();
+        // A, 1 and the start and stop characters, 15 modules each with gaps between
+        assert_eq!(modules.len(), 4 * 15 + 3);
+        assert!(bits(&modules).starts_with("100010111011101"));
+        assert!(encode_code39("abc").unwrap_err().contains("'a'"));
+        assert!(encode_code39("A*B").is_err());
+        for (c, pattern) in CODE39 {
+            assert_eq!(pattern.matches('W').count(), 3, "{}", c);
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let rendered = render(&[false, false, true, false, true, true], "X", false);
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines.len(), BAR_LINES + 1);
+        assert_eq!(lines[0], "█▐ ");
+        assert_eq!(lines[BAR_LINES], " X");
+        assert!(render(&[false, false, true, false, true, true], "X", true).starts_with(" ▌█"));
+    }
+}
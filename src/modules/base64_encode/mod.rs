@@ -187,7 +187,7 @@ mod tests {
     fn test_base64_encode_long_string() {
         let long_input = "a".repeat(1000);
         let result = base64_encode(&long_input);
-        assert!(result.len() > 0);
+        assert!(!result.is_empty());
         assert!(result.ends_with('=') || !result.ends_with('='));
     }
 
@@ -196,7 +196,7 @@ mod tests {
         assert_eq!(base64_encode("\x00\x01\x02\x03"), "AAECAw==");
         let binary_data = String::from_utf8_lossy(&[0xFF, 0xFE, 0xFD]).to_string();
         let result = base64_encode(&binary_data);
-        assert!(result.len() > 0);
+        assert!(!result.is_empty());
     }
 
     #[test]
@@ -0,0 +1,282 @@
+// CBOR as described in RFC 8949
+use super::{bytes_value, float_value, key_text, timestamp_value, Reader, MAX_DEPTH};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde_json::{Map, Value};
+
+const BREAK: u8 = 0xff;
+
+pub fn decode_all(bytes: &[u8]) -> Result<Vec<Value>, String> {
+    let mut reader = Reader::new(bytes);
+    let mut values = Vec::new();
+    while !reader.at_end() {
+        values.push(decode(&mut reader, 0)?);
+    }
+    if values.is_empty() {
+        return Err("No CBOR data in the input".to_string());
+    }
+    Ok(values)
+}
+
+// The argument after the initial byte; None for indefinite length
+fn read_argument(reader: &mut Reader, info: u8, start: usize) -> Result<Option<u64>, String> {
+    match info {
+        0..=23 => Ok(Some(u64::from(info))),
+        24..=27 => Ok(Some(reader.uint(1 << (info - 24))?)),
+        31 => Ok(None),
+        _ => Err(format!("Invalid CBOR additional information {} at byte {}", info, start)),
+    }
+}
+
+fn at_break(reader: &mut Reader) -> Result<bool, String> {
+    if reader.bytes.get(reader.position) == Some(&BREAK) {
+        reader.position += 1;
+        return Ok(true);
+    }
+    if reader.at_end() {
+        return Err("Unexpected end of data inside an indefinite-length item".to_string());
+    }
+    Ok(false)
+}
+
+fn decode(reader: &mut Reader, depth: usize) -> Result<Value, String> {
+    if depth > MAX_DEPTH {
+        return Err(format!("Nesting deeper than {} levels", MAX_DEPTH));
+    }
+    let start = reader.position;
+    let initial = reader.byte()?;
+    let (major, info) = (initial >> 5, initial & 0x1f);
+    if major == 7 {
+        return decode_simple(reader, info, start);
+    }
+    let argument = read_argument(reader, info, start)?;
+    match (major, argument) {
+        (0, Some(value)) => Ok(Value::from(value)),
+        // -1 - n, which needs more than 64 bits only for the last 2^63 values
+        (1, Some(value)) => Ok(match i64::try_from(value) {
+            Ok(value) => Value::from(-1 - value),
+            Err(_) => Value::from(format!("-{}", u128::from(value) + 1)),
+        }),
+        (2, _) | (3, _) => {
+            let mut bytes = Vec::new();
+            match argument {
+                Some(count) => {
+                    let count = reader.fit(count)?;
+                    bytes.extend(reader.take(count)?);
+                }
+                // Indefinite strings are definite chunks of the same type
+                None => {
+                    while !at_break(reader)? {
+                        let chunk_start = reader.position;
+                        let chunk = reader.byte()?;
+                        let Some(count) = read_argument(reader, chunk & 0x1f, chunk_start)?.filter(|_| chunk >> 5 == major) else {
+                            return Err(format!("Invalid chunk in the indefinite-length string at byte {}", chunk_start));
+                        };
+                        let count = reader.fit(count)?;
+                        bytes.extend(reader.take(count)?);
+                    }
+                }
+            }
+            if major == 2 {
+                Ok(bytes_value(&bytes))
+            } else {
+                String::from_utf8(bytes).map(Value::from).map_err(|_| format!("Invalid UTF-8 in the text string at byte {}", start))
+            }
+        }
+        (4, _) => {
+            let mut items = Vec::new();
+            match argument {
+                Some(count) => {
+                    for _ in 0..reader.fit(count)? {
+                        items.push(decode(reader, depth + 1)?);
+                    }
+                }
+                None => {
+                    while !at_break(reader)? {
+                        items.push(decode(reader, depth + 1)?);
+                    }
+                }
+            }
+            Ok(Value::Array(items))
+        }
+        (5, _) => {
+            let mut map = Map::new();
+            let mut entry = |reader: &mut Reader| -> Result<(), String> {
+                let key = key_text(decode(reader, depth + 1)?);
+                map.insert(key, decode(reader, depth + 1)?);
+                Ok(())
+            };
+            match argument {
+                Some(count) => {
+                    for _ in 0..reader.fit(count)? {
+                        entry(reader)?;
+                    }
+                }
+                None => {
+                    while !at_break(reader)? {
+                        entry(reader)?;
+                    }
+                }
+            }
+            Ok(Value::Object(map))
+        }
+        (6, Some(tag)) => decode_tag(tag, decode(reader, depth + 1)?),
+        _ => Err(format!("Invalid CBOR item 0x{:02x} at byte {}", initial, start)),
+    }
+}
+
+fn decode_simple(reader: &mut Reader, info: u8, start: usize) -> Result<Value, String> {
+    Ok(match info {
+        20 => Value::Bool(false),
+        21 => Value::Bool(true),
+        // null and undefined
+        22 | 23 => Value::Null,
+        0..=19 => serde_json::json!({ "simple": info }),
+        24 => serde_json::json!({ "simple": reader.byte()? }),
+        25 => float_value(half_to_f64(reader.uint(2)? as u16)),
+        26 => float_value(f64::from(f32::from_bits(reader.uint(4)? as u32))),
+        27 => float_value(f64::from_bits(reader.uint(8)?)),
+        _ => return Err(format!("Unexpected CBOR break or reserved value at byte {}", start)),
+    })
+}
+
+fn half_to_f64(bits: u16) -> f64 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = i32::from((bits >> 10) & 0x1f);
+    let mantissa = f64::from(bits & 0x3ff);
+    sign * match exponent {
+        0 => mantissa * 2f64.powi(-24),
+        31 if mantissa == 0.0 => f64::INFINITY,
+        31 => f64::NAN,
+        _ => (1.0 + mantissa / 1024.0) * 2f64.powi(exponent - 15),
+    }
+}
+
+fn decode_tag(tag: u64, value: Value) -> Result<Value, String> {
+    Ok(match (tag, &value) {
+        // Date/time string
+        (0, Value::String(_)) => value,
+        // Epoch seconds
+        (1, Value::Number(number)) => {
+            let seconds = number.as_f64().unwrap_or_default();
+            timestamp_value(seconds.floor() as i64, ((seconds - seconds.floor()) * 1e9).round().min(999_999_999.0) as u32)
+        }
+        // Bignums arrive as base64 of their big-endian bytes
+        (2, Value::String(encoded)) | (3, Value::String(encoded)) => {
+            let bytes = STANDARD.decode(encoded).unwrap_or_default();
+            let magnitude = Some(bytes.as_slice())
+                .filter(|bytes| bytes.len() <= 16)
+                .map(|bytes| bytes.iter().fold(0u128, |total, byte| (total << 8) | u128::from(*byte)));
+            match (tag, magnitude) {
+                (2, Some(magnitude)) => Value::from(magnitude.to_string()),
+                (_, Some(magnitude)) if magnitude < u128::MAX => Value::from(format!("-{}", magnitude + 1)),
+                _ => serde_json::json!({ "tag": tag, "value": value }),
+            }
+        }
+        _ => serde_json::json!({ "tag": tag, "value": value }),
+    })
+}
+
+fn encode_head(output: &mut Vec<u8>, major: u8, argument: u64) {
+    let major = major << 5;
+    match argument {
+        0..=23 => output.push(major | argument as u8),
+        24..=0xff => output.extend([major | 24, argument as u8]),
+        0x100..=0xffff => {
+            output.push(major | 25);
+            output.extend((argument as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            output.push(major | 26);
+            output.extend((argument as u32).to_be_bytes());
+        }
+        _ => {
+            output.push(major | 27);
+            output.extend(argument.to_be_bytes());
+        }
+    }
+}
+
+pub fn encode(value: &Value, output: &mut Vec<u8>) {
+    match value {
+        Value::Null => output.push(0xf6),
+        Value::Bool(false) => output.push(0xf4),
+        Value::Bool(true) => output.push(0xf5),
+        Value::Number(number) => {
+            if let Some(unsigned) = number.as_u64() {
+                encode_head(output, 0, unsigned);
+            } else if let Some(signed) = number.as_i64() {
+                encode_head(output, 1, (-1 - signed) as u64);
+            } else {
+                let float = number.as_f64().unwrap_or_default();
+                if f64::from(float as f32) == float {
+                    output.push(0xfa);
+                    output.extend((float as f32).to_be_bytes());
+                } else {
+                    output.push(0xfb);
+                    output.extend(float.to_be_bytes());
+                }
+            }
+        }
+        Value::String(text) => {
+            encode_head(output, 3, text.len() as u64);
+            output.extend(text.as_bytes());
+        }
+        Value::Array(items) => {
+            encode_head(output, 4, items.len() as u64);
+            for item in items {
+                encode(item, output);
+            }
+        }
+        Value::Object(map) => {
+            encode_head(output, 5, map.len() as u64);
+            for (key, item) in map {
+                encode_head(output, 3, key.len() as u64);
+                output.extend(key.as_bytes());
+                encode(item, output);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn encoded(value: &Value) -> Vec<u8> {
+        let mut output = Vec::new();
+        encode(value, &mut output);
+        output
+    }
+
+    #[test]
+    fn test_encode() {
+        // Examples from RFC 8949 appendix A
+        assert_eq!(encoded(&json!(1000000)), b"\x1a\x00\x0f\x42\x40");
+        assert_eq!(encoded(&json!(-1000)), b"\x39\x03\xe7");
+        assert_eq!(encoded(&json!(1.1)), b"\xfb\x3f\xf1\x99\x99\x99\x99\x99\x9a");
+        assert_eq!(encoded(&json!(100000.0)), b"\xfa\x47\xc3\x50\x00");
+        assert_eq!(encoded(&json!({"a": 1, "b": [2, 3]})), b"\xa2\x61\x61\x01\x61\x62\x82\x02\x03");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let value = json!({"id": i64::MIN, "big": u64::MAX, "name": "Ada ✓", "tags": [null, true, 0.5], "nested": {"a": []}});
+        assert_eq!(decode_all(&encoded(&value)).unwrap(), [value]);
+    }
+
+    #[test]
+    fn test_decode_rfc_examples() {
+        assert_eq!(decode_all(b"\xf9\x3c\x00").unwrap(), [json!(1.0)]);
+        assert_eq!(decode_all(b"\xf9\x7c\x00").unwrap(), [json!("Infinity")]);
+        assert_eq!(decode_all(b"\x3b\xff\xff\xff\xff\xff\xff\xff\xff").unwrap(), [json!("-18446744073709551616")]);
+        assert_eq!(decode_all(b"\xc2\x49\x01\x00\x00\x00\x00\x00\x00\x00\x00").unwrap(), [json!("18446744073709551616")]);
+        assert_eq!(decode_all(b"\xc1\x1a\x51\x4b\x67\xb0").unwrap(), [json!("2013-03-21T20:04:00Z")]);
+        assert_eq!(decode_all(b"\xd8\x20\x63\x61\x62\x63").unwrap(), [json!({"tag": 32, "value": "abc"})]);
+        // Indefinite-length map, array and text string
+        assert_eq!(decode_all(b"\xbf\x61\x61\x9f\x01\xff\x61\x62\x7f\x62\x73\x74\x61\x72\xff\xff").unwrap(), [json!({"a": [1], "b": "str"})]);
+        assert_eq!(decode_all(b"\xf7\xf0\x40").unwrap(), [json!(null), json!({"simple": 16}), json!("")]);
+        assert!(decode_all(b"\x82\x01").is_err());
+        assert!(decode_all(b"\xff").is_err());
+    }
+}
@@ -0,0 +1,231 @@
+use crate::tool_module::{read_input_bytes, ToolModule};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use chrono::{DateTime, SecondsFormat};
+use clap::{Arg, ArgMatches, Command};
+use serde_json::Value;
+use std::error::Error;
+use std::io::{self, IsTerminal, Write};
+
+mod cbor;
+mod msgpack;
+
+pub struct BinaryJsonModule;
+
+// Nesting deeper than this is treated as corrupt input rather than recursed into
+const MAX_DEPTH: usize = 512;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    MessagePack,
+    Cbor,
+}
+
+impl ToolModule for BinaryJsonModule {
+    fn name(&self) -> &'static str {
+        "binary-json"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        let decode_help = "Bytes come from FILE or stdin (no file or -), as raw binary or as hex or base64 text copied from a log. Binary strings become base64 strings, maps with non-string keys get their keys as text, and several concatenated values are printed one after another.";
+        let encode_help = "Reads JSON from FILE or stdin (no file or -); several documents, as in JSON Lines, are encoded one after another. Writes the raw bytes when stdout is redirected, or hex on a terminal.";
+        cmd.arg(
+            Arg::new("msgpack-to-json")
+                .long("msgpack-to-json")
+                .value_name("FILE")
+                .num_args(0..=1)
+                .help("Decode MessagePack (binary, hex or base64) into JSON (or read from stdin)")
+                .long_help(format!("Show a MessagePack payload as pretty JSON. Timestamps (extension -1) become RFC 3339 strings and other extensions {{\"ext\": TYPE, \"data\": BASE64}}. {}", decode_help))
+        )
+        .arg(
+            Arg::new("json-to-msgpack")
+                .long("json-to-msgpack")
+                .value_name("FILE")
+                .num_args(0..=1)
+                .help("Encode JSON as MessagePack (or read from stdin)")
+                .long_help(format!("Encode JSON as MessagePack using the smallest integer and string forms; floats are 64-bit. {}", encode_help))
+        )
+        .arg(
+            Arg::new("cbor-to-json")
+                .long("cbor-to-json")
+                .value_name("FILE")
+                .num_args(0..=1)
+                .help("Decode CBOR (binary, hex or base64) into JSON (or read from stdin)")
+                .long_help(format!("Show a CBOR payload as pretty JSON. Date tags 0 and 1 become RFC 3339 strings, bignums decimal strings and other tags {{\"tag\": N, \"value\": VALUE}}; undefined becomes null. {}", decode_help))
+        )
+        .arg(
+            Arg::new("json-to-cbor")
+                .long("json-to-cbor")
+                .value_name("FILE")
+                .num_args(0..=1)
+                .help("Encode JSON as CBOR (or read from stdin)")
+                .long_help(format!("Encode JSON as CBOR with the shortest integer and length forms; floats use 32 bits when that loses nothing. {}", encode_help))
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        for (id, format) in [("msgpack-to-json", Format::MessagePack), ("cbor-to-json", Format::Cbor)] {
+            if matches.contains_id(id) {
+                let bytes = payload_bytes(read_input_bytes(matches.get_one::<String>(id))?);
+                let values = match format {
+                    Format::MessagePack => msgpack::decode_all(&bytes)?,
+                    Format::Cbor => cbor::decode_all(&bytes)?,
+                };
+                for value in values {
+                    println!("{}", serde_json::to_string_pretty(&value)?);
+                }
+            }
+        }
+        for (id, format) in [("json-to-msgpack", Format::MessagePack), ("json-to-cbor", Format::Cbor)] {
+            if matches.contains_id(id) {
+                let input = String::from_utf8(read_input_bytes(matches.get_one::<String>(id))?).map_err(|_| "The JSON input is not valid UTF-8")?;
+                let mut bytes = Vec::new();
+                for document in serde_json::Deserializer::from_str(&input).into_iter::<Value>() {
+                    let document = document.map_err(|e| format!("Invalid JSON: {}", e))?;
+                    match format {
+                        Format::MessagePack => msgpack::encode(&document, &mut bytes),
+                        Format::Cbor => cbor::encode(&document, &mut bytes),
+                    }
+                }
+                write_binary(&bytes)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Payloads pasted from logs arrive as hex or base64 text; anything else is taken as raw bytes
+pub fn payload_bytes(input: Vec<u8>) -> Vec<u8> {
+    let Ok(text) = std::str::from_utf8(&input) else {
+        return input;
+    };
+    let compact: String = text.split_whitespace().collect();
+    let hex = compact.strip_prefix("0x").unwrap_or(&compact);
+    if !hex.is_empty() && hex.len().is_multiple_of(2) && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap_or_default()).collect();
+    }
+    match STANDARD.decode(&compact) {
+        Ok(bytes) if !compact.is_empty() => bytes,
+        _ => input,
+    }
+}
+
+// Raw bytes when piped or redirected; a terminal gets hex instead
+fn write_binary(bytes: &[u8]) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    if !stdout.is_terminal() {
+        return stdout.write_all(bytes);
+    }
+    eprintln!("⚠️  Showing hex on the terminal; redirect stdout to get the {} raw bytes", bytes.len());
+    let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    writeln!(stdout, "{}", hex)
+}
+
+// Reads big-endian values from a byte slice, failing cleanly at the end
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Reader { bytes, position: 0 }
+    }
+
+    fn at_end(&self) -> bool {
+        self.position >= self.bytes.len()
+    }
+
+    fn take(&mut self, count: usize) -> Result<&'a [u8], String> {
+        let end = self.position.checked_add(count).filter(|end| *end <= self.bytes.len());
+        let end = end.ok_or_else(|| format!("Unexpected end of data at byte {} (needed {} more)", self.position, count))?;
+        let slice = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn byte(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn uint(&mut self, width: usize) -> Result<u64, String> {
+        Ok(self.take(width)?.iter().fold(0u64, |value, byte| (value << 8) | u64::from(*byte)))
+    }
+
+    // A length from the data, checked against what is left so corrupt input cannot ask for huge buffers
+    fn length(&mut self, width: usize) -> Result<usize, String> {
+        let length = self.uint(width)?;
+        self.fit(length)
+    }
+
+    fn fit(&self, length: u64) -> Result<usize, String> {
+        if length > (self.bytes.len() - self.position) as u64 {
+            return Err(format!("Length {} at byte {} runs past the end of the data", length, self.position));
+        }
+        Ok(length as usize)
+    }
+
+    fn text(&mut self, length: usize) -> Result<String, String> {
+        let start = self.position;
+        String::from_utf8(self.take(length)?.to_vec()).map_err(|_| format!("Invalid UTF-8 in the string at byte {}", start))
+    }
+}
+
+fn bytes_value(bytes: &[u8]) -> Value {
+    Value::String(STANDARD.encode(bytes))
+}
+
+// JSON has no NaN or infinities, so those are spelled out
+fn float_value(float: f64) -> Value {
+    match serde_json::Number::from_f64(float) {
+        Some(number) => Value::Number(number),
+        None if float.is_nan() => Value::from("NaN"),
+        None if float > 0.0 => Value::from("Infinity"),
+        None => Value::from("-Infinity"),
+    }
+}
+
+fn timestamp_value(seconds: i64, nanos: u32) -> Value {
+    match DateTime::from_timestamp(seconds, nanos) {
+        Some(time) => Value::from(time.to_rfc3339_opts(SecondsFormat::AutoSi, true)),
+        None => Value::from(seconds),
+    }
+}
+
+// JSON object keys must be strings
+fn key_text(key: Value) -> String {
+    match key {
+        Value::String(text) => text,
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_payload_bytes() {
+        assert_eq!(payload_bytes(b"82 a1 61 01\n".to_vec()), [0x82, 0xa1, 0x61, 0x01]);
+        assert_eq!(payload_bytes(b"0xa0".to_vec()), [0xa0]);
+        assert_eq!(payload_bytes(b"gqFhAQ==".to_vec()), [0x82, 0xa1, 0x61, 0x01]);
+        assert_eq!(payload_bytes(vec![0x81, 0xa1, 0x61, 0xc3]), [0x81, 0xa1, 0x61, 0xc3]);
+    }
+
+    #[test]
+    fn test_reader_bounds() {
+        let mut reader = Reader::new(&[0x01, 0x02, 0x03]);
+        assert_eq!(reader.uint(2).unwrap(), 0x0102);
+        assert!(reader.take(2).unwrap_err().starts_with("Unexpected end of data at byte 2"));
+        let mut reader = Reader::new(&[0xff, 0xff]);
+        assert!(reader.length(2).is_err());
+    }
+
+    #[test]
+    fn test_special_values() {
+        assert_eq!(float_value(f64::NAN), json!("NaN"));
+        assert_eq!(float_value(f64::NEG_INFINITY), json!("-Infinity"));
+        assert_eq!(timestamp_value(0, 500_000_000), json!("1970-01-01T00:00:00.500Z"));
+        assert_eq!(key_text(json!(7)), "7");
+    }
+}
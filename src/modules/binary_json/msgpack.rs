@@ -0,0 +1,219 @@
+// MessagePack as described at https://github.com/msgpack/msgpack/blob/master/spec.md
+use super::{bytes_value, float_value, key_text, timestamp_value, Reader, MAX_DEPTH};
+use serde_json::{Map, Value};
+
+pub fn decode_all(bytes: &[u8]) -> Result<Vec<Value>, String> {
+    let mut reader = Reader::new(bytes);
+    let mut values = Vec::new();
+    while !reader.at_end() {
+        values.push(decode(&mut reader, 0)?);
+    }
+    if values.is_empty() {
+        return Err("No MessagePack data in the input".to_string());
+    }
+    Ok(values)
+}
+
+fn decode(reader: &mut Reader, depth: usize) -> Result<Value, String> {
+    if depth > MAX_DEPTH {
+        return Err(format!("Nesting deeper than {} levels", MAX_DEPTH));
+    }
+    let start = reader.position;
+    let marker = reader.byte()?;
+    Ok(match marker {
+        0x00..=0x7f => Value::from(marker),
+        0x80..=0x8f => decode_map(reader, usize::from(marker & 0x0f), depth)?,
+        0x90..=0x9f => decode_array(reader, usize::from(marker & 0x0f), depth)?,
+        0xa0..=0xbf => Value::from(reader.text(usize::from(marker & 0x1f))?),
+        0xc0 => Value::Null,
+        0xc2 => Value::Bool(false),
+        0xc3 => Value::Bool(true),
+        0xc4..=0xc6 => {
+            let length = reader.length(1 << (marker - 0xc4))?;
+            bytes_value(reader.take(length)?)
+        }
+        0xc7..=0xc9 => {
+            let length = reader.length(1 << (marker - 0xc7))?;
+            decode_ext(reader, length)?
+        }
+        0xca => float_value(f64::from(f32::from_bits(reader.uint(4)? as u32))),
+        0xcb => float_value(f64::from_bits(reader.uint(8)?)),
+        0xcc..=0xcf => Value::from(reader.uint(1 << (marker - 0xcc))?),
+        0xd0 => Value::from(reader.uint(1)? as u8 as i8),
+        0xd1 => Value::from(reader.uint(2)? as u16 as i16),
+        0xd2 => Value::from(reader.uint(4)? as u32 as i32),
+        0xd3 => Value::from(reader.uint(8)? as i64),
+        0xd4..=0xd8 => decode_ext(reader, 1 << (marker - 0xd4))?,
+        0xd9..=0xdb => {
+            let length = reader.length(1 << (marker - 0xd9))?;
+            Value::from(reader.text(length)?)
+        }
+        0xdc | 0xdd => {
+            let count = reader.length(if marker == 0xdc { 2 } else { 4 })?;
+            decode_array(reader, count, depth)?
+        }
+        0xde | 0xdf => {
+            let count = reader.length(if marker == 0xde { 2 } else { 4 })?;
+            decode_map(reader, count, depth)?
+        }
+        0xe0..=0xff => Value::from(marker as i8),
+        0xc1 => return Err(format!("Invalid MessagePack marker 0xc1 at byte {}", start)),
+    })
+}
+
+fn decode_array(reader: &mut Reader, count: usize, depth: usize) -> Result<Value, String> {
+    (0..count).map(|_| decode(reader, depth + 1)).collect::<Result<Vec<Value>, String>>().map(Value::Array)
+}
+
+fn decode_map(reader: &mut Reader, count: usize, depth: usize) -> Result<Value, String> {
+    let mut map = Map::new();
+    for _ in 0..count {
+        let key = key_text(decode(reader, depth + 1)?);
+        map.insert(key, decode(reader, depth + 1)?);
+    }
+    Ok(Value::Object(map))
+}
+
+// Type -1 is the standard timestamp; other types are application-defined
+fn decode_ext(reader: &mut Reader, length: usize) -> Result<Value, String> {
+    let kind = reader.byte()? as i8;
+    let data = reader.take(length)?;
+    let mut data_reader = Reader::new(data);
+    let timestamp = match (kind, length) {
+        (-1, 4) => Some((data_reader.uint(4)? as i64, 0)),
+        (-1, 8) => {
+            let packed = data_reader.uint(8)?;
+            Some(((packed & 0x3_ffff_ffff) as i64, (packed >> 34) as u32))
+        }
+        (-1, 12) => {
+            let nanos = data_reader.uint(4)? as u32;
+            Some((data_reader.uint(8)? as i64, nanos))
+        }
+        _ => None,
+    };
+    Ok(match timestamp {
+        Some((seconds, nanos)) => timestamp_value(seconds, nanos),
+        None => serde_json::json!({ "ext": kind, "data": bytes_value(data) }),
+    })
+}
+
+// Lengths up to the 32-bit forms; larger ones cannot come from a JSON document in memory
+fn encode_length(output: &mut Vec<u8>, length: usize, short: Option<(u8, usize)>, markers: [u8; 3]) {
+    match short {
+        Some((base, limit)) if length < limit => output.push(base | length as u8),
+        _ if length <= 0xff && markers[0] != 0 => output.extend([markers[0], length as u8]),
+        _ if length <= 0xffff => {
+            output.push(markers[1]);
+            output.extend((length as u16).to_be_bytes());
+        }
+        _ => {
+            output.push(markers[2]);
+            output.extend((length as u32).to_be_bytes());
+        }
+    }
+}
+
+pub fn encode(value: &Value, output: &mut Vec<u8>) {
+    match value {
+        Value::Null => output.push(0xc0),
+        Value::Bool(false) => output.push(0xc2),
+        Value::Bool(true) => output.push(0xc3),
+        Value::Number(number) => {
+            if let Some(unsigned) = number.as_u64() {
+                match unsigned {
+                    0..=0x7f => output.push(unsigned as u8),
+                    0x80..=0xff => output.extend([0xcc, unsigned as u8]),
+                    0x100..=0xffff => {
+                        output.push(0xcd);
+                        output.extend((unsigned as u16).to_be_bytes());
+                    }
+                    0x1_0000..=0xffff_ffff => {
+                        output.push(0xce);
+                        output.extend((unsigned as u32).to_be_bytes());
+                    }
+                    _ => {
+                        output.push(0xcf);
+                        output.extend(unsigned.to_be_bytes());
+                    }
+                }
+            } else if let Some(signed) = number.as_i64() {
+                match signed {
+                    -32..=-1 => output.push(signed as i8 as u8),
+                    -128..=-33 => output.extend([0xd0, signed as i8 as u8]),
+                    -32_768..=-129 => {
+                        output.push(0xd1);
+                        output.extend((signed as i16).to_be_bytes());
+                    }
+                    -2_147_483_648..=-32_769 => {
+                        output.push(0xd2);
+                        output.extend((signed as i32).to_be_bytes());
+                    }
+                    _ => {
+                        output.push(0xd3);
+                        output.extend(signed.to_be_bytes());
+                    }
+                }
+            } else {
+                output.push(0xcb);
+                output.extend(number.as_f64().unwrap_or_default().to_be_bytes());
+            }
+        }
+        Value::String(text) => {
+            encode_length(output, text.len(), Some((0xa0, 32)), [0xd9, 0xda, 0xdb]);
+            output.extend(text.as_bytes());
+        }
+        Value::Array(items) => {
+            encode_length(output, items.len(), Some((0x90, 16)), [0, 0xdc, 0xdd]);
+            for item in items {
+                encode(item, output);
+            }
+        }
+        Value::Object(map) => {
+            encode_length(output, map.len(), Some((0x80, 16)), [0, 0xde, 0xdf]);
+            for (key, item) in map {
+                encode(&Value::from(key.as_str()), output);
+                encode(item, output);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn encoded(value: &Value) -> Vec<u8> {
+        let mut output = Vec::new();
+        encode(value, &mut output);
+        output
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encoded(&json!({"compact": true, "schema": 0})), b"\x82\xa7compact\xc3\xa6schema\x00");
+        assert_eq!(encoded(&json!([-1, -33, 200, 70000, 1.5])), b"\x95\xff\xd0\xdf\xcc\xc8\xce\x00\x01\x11\x70\xcb\x3f\xf8\x00\x00\x00\x00\x00\x00");
+        assert_eq!(encoded(&json!("x".repeat(40)))[..2], [0xd9, 40]);
+        assert_eq!(encoded(&json!(vec![0; 20]))[..3], [0xdc, 0, 20]);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let value = json!({"id": -70000, "big": u64::MAX, "name": "Ada ✓", "tags": [null, false, 0.25], "nested": {"a": []}});
+        assert_eq!(decode_all(&encoded(&value)).unwrap(), [value]);
+    }
+
+    #[test]
+    fn test_decode_special_types() {
+        // bin 8, a 32-bit timestamp extension, a custom extension and an integer key
+        let bytes = b"\x84\xa3bin\xc4\x02\x01\x02\xa2ts\xd6\xff\x00\x00\x00\x3c\xa3ext\xd4\x05\x09\x07\xc2";
+        assert_eq!(
+            decode_all(bytes).unwrap(),
+            [json!({"bin": "AQI=", "ts": "1970-01-01T00:01:00Z", "ext": {"ext": 5, "data": "CQ=="}, "7": false})]
+        );
+        assert_eq!(decode_all(b"\x01\x02").unwrap(), [json!(1), json!(2)]);
+        assert!(decode_all(b"\x92\x01").is_err());
+        assert!(decode_all(b"\xc1").is_err());
+        assert!(decode_all(b"\xdd\xff\xff\xff\xff").is_err());
+    }
+}
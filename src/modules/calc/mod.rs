@@ -0,0 +1,473 @@
+use crate::tool_module::{format_number, ToolModule};
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::{self, BufRead, IsTerminal, Write};
+
+pub struct CalcModule;
+
+// Integers beyond this lose precision as f64, so bitwise operations refuse them
+const MAX_EXACT: f64 = 9_007_199_254_740_992.0;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Name(String),
+    Operator(&'static str),
+    Open,
+    Close,
+    Comma,
+}
+
+const OPERATORS: [&str; 15] = ["**", "<<", ">>", "//", "+", "-", "*", "/", "%", "^", "&", "|", "~", "=", "!"];
+
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    position: usize,
+    variables: &'a HashMap<String, f64>,
+}
+
+impl ToolModule for CalcModule {
+    fn name(&self) -> &'static str {
+        "calc"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("calc")
+                .long("calc")
+                .value_name("EXPRESSION")
+                .num_args(0..=1)
+                .default_missing_value("")
+                .help("Evaluate an expression, e.g. \"2^10 * (3 + 4) % 5\"; without one, start an interactive calculator")
+                .long_help("Evaluate an arithmetic expression:\n- + - * / % and // (floor division), ^ or ** for powers, parentheses\n- bitwise & | xor ~ << >> on integers\n- 0x, 0o and 0b literals, 1_000_000 and 1.5e3\n- sqrt cbrt abs exp ln log log2 log10 sin cos tan asin acos atan floor ceil round trunc min max pow hypot, e.g. round(2/3, 2) or log(8, 2)\n- constants pi, e and tau\nWithout an expression, read one per line (a prompt on a terminal, or piped lines), where NAME = EXPRESSION sets a variable and ans is the previous result.")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(expression) = matches.get_one::<String>("calc") {
+            let terminal = io::stdout().is_terminal();
+            let mut variables = HashMap::new();
+            if !expression.is_empty() {
+                let (_, value) = evaluate_line(expression, &mut variables)?;
+                println!("{}", describe(value, terminal));
+                return Ok(());
+            }
+            let interactive = io::stdin().is_terminal();
+            if interactive {
+                println!("{}", "Enter expressions; NAME = EXPRESSION sets a variable, ans is the last result, Ctrl-D quits".dimmed());
+            }
+            let stdin = io::stdin();
+            let mut lines = stdin.lock().lines();
+            loop {
+                if interactive {
+                    print!("{} ", ">".green().bold());
+                    io::stdout().flush()?;
+                }
+                let Some(line) = lines.next() else { break };
+                let line = line?;
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                if matches!(line, "quit" | "exit") {
+                    break;
+                }
+                match evaluate_line(line, &mut variables) {
+                    Ok((Some(name), value)) => println!("{} = {}", name, describe(value, terminal)),
+                    Ok((None, value)) => println!("{}", describe(value, terminal)),
+                    // A typo shouldn't end the session, but it should fail a piped batch
+                    Err(e) if interactive => eprintln!("{}", e.red()),
+                    Err(e) => return Err(e.into()),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// Evaluates "EXPRESSION" or "NAME = EXPRESSION", storing the result as ans (and NAME)
+fn evaluate_line(line: &str, variables: &mut HashMap<String, f64>) -> Result<(Option<String>, f64), String> {
+    let tokens = tokenize(line)?;
+    let (target, expression) = match tokens.as_slice() {
+        [Token::Name(name), Token::Operator("="), rest @ ..] => {
+            if constant(name).is_some() || name == "ans" {
+                return Err(format!("{} can't be assigned to", name));
+            }
+            (Some(name.clone()), rest.to_vec())
+        }
+        _ => (None, tokens),
+    };
+    let mut parser = Parser { tokens: expression, position: 0, variables };
+    let value = parser.parse()?;
+    if let Some(name) = &target {
+        variables.insert(name.clone(), value);
+    }
+    variables.insert("ans".to_string(), value);
+    Ok((target, value))
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+    while index < chars.len() {
+        let c = chars[index];
+        if c.is_whitespace() {
+            index += 1;
+        } else if c.is_ascii_digit() || (c == '.' && chars.get(index + 1).is_some_and(|next| next.is_ascii_digit())) {
+            let start = index;
+            while index < chars.len() && (chars[index].is_ascii_alphanumeric() || chars[index] == '.' || chars[index] == '_' || ((chars[index] == '+' || chars[index] == '-') && matches!(chars[index - 1], 'e' | 'E') && !is_prefixed(&chars[start..index]))) {
+                index += 1;
+            }
+            let literal: String = chars[start..index].iter().filter(|c| **c != '_').collect();
+            tokens.push(Token::Number(parse_number(&literal)?));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = index;
+            while index < chars.len() && (chars[index].is_alphanumeric() || chars[index] == '_') {
+                index += 1;
+            }
+            let name: String = chars[start..index].iter().collect::<String>().to_lowercase();
+            tokens.push(if name == "xor" { Token::Operator("xor") } else { Token::Name(name) });
+        } else if c == '(' {
+            tokens.push(Token::Open);
+            index += 1;
+        } else if c == ')' {
+            tokens.push(Token::Close);
+            index += 1;
+        } else if c == ',' {
+            tokens.push(Token::Comma);
+            index += 1;
+        } else {
+            let rest: String = chars[index..(index + 2).min(chars.len())].iter().collect();
+            let operator = OPERATORS.iter().find(|operator| rest.starts_with(**operator)).ok_or_else(|| format!("Unexpected '{}'", c))?;
+            tokens.push(Token::Operator(operator));
+            index += operator.len();
+        }
+    }
+    Ok(tokens)
+}
+
+fn is_prefixed(literal: &[char]) -> bool {
+    matches!(literal, ['0', 'x' | 'X' | 'b' | 'B' | 'o' | 'O', ..])
+}
+
+fn parse_number(literal: &str) -> Result<f64, String> {
+    let invalid = || format!("Invalid number '{}'", literal);
+    let lower = literal.to_lowercase();
+    let radix = match lower.get(..2) {
+        Some("0x") => Some(16),
+        Some("0b") => Some(2),
+        Some("0o") => Some(8),
+        _ => None,
+    };
+    match radix {
+        Some(radix) => u64::from_str_radix(&lower[2..], radix).map(|value| value as f64).map_err(|_| invalid()),
+        None => lower.parse::<f64>().map_err(|_| invalid()),
+    }
+}
+
+fn constant(name: &str) -> Option<f64> {
+    match name {
+        "pi" => Some(std::f64::consts::PI),
+        "e" => Some(std::f64::consts::E),
+        "tau" => Some(std::f64::consts::TAU),
+        _ => None,
+    }
+}
+
+fn integer(value: f64, operator: &str) -> Result<i64, String> {
+    if value.fract() != 0.0 || value.abs() > MAX_EXACT {
+        return Err(format!("{} works on integers, not {}", operator, format_number(value, 12)));
+    }
+    Ok(value as i64)
+}
+
+// Binding power of each binary operator, loosest first as in Python
+fn precedence(operator: &str) -> Option<u8> {
+    match operator {
+        "|" => Some(1),
+        "xor" => Some(2),
+        "&" => Some(3),
+        "<<" | ">>" => Some(4),
+        "+" | "-" => Some(5),
+        "*" | "/" | "//" | "%" => Some(6),
+        _ => None,
+    }
+}
+
+impl Parser<'_> {
+    fn parse(&mut self) -> Result<f64, String> {
+        if self.tokens.is_empty() {
+            return Err("Nothing to calculate".to_string());
+        }
+        let value = self.binary(1)?;
+        match self.tokens.get(self.position) {
+            None => Ok(value),
+            Some(Token::Close) => Err("Unmatched ')'".to_string()),
+            Some(token) => Err(format!("Unexpected {}", describe_token(token))),
+        }
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn binary(&mut self, minimum: u8) -> Result<f64, String> {
+        let mut left = self.unary()?;
+        while let Some(Token::Operator(operator)) = self.peek() {
+            let operator: &'static str = operator;
+            let Some(power) = precedence(operator).filter(|power| *power >= minimum) else { break };
+            self.position += 1;
+            let right = self.binary(power + 1)?;
+            left = apply(operator, left, right)?;
+        }
+        Ok(left)
+    }
+
+    // Unary minus binds looser than powers, so -2^2 is -4
+    fn unary(&mut self) -> Result<f64, String> {
+        match self.peek() {
+            Some(Token::Operator("-")) => {
+                self.position += 1;
+                Ok(-self.unary()?)
+            }
+            Some(Token::Operator("+")) => {
+                self.position += 1;
+                self.unary()
+            }
+            Some(Token::Operator("~")) => {
+                self.position += 1;
+                Ok(!integer(self.unary()?, "~")? as f64)
+            }
+            _ => self.power(),
+        }
+    }
+
+    fn power(&mut self) -> Result<f64, String> {
+        let mut base = self.primary()?;
+        while let Some(Token::Operator("!")) = self.peek() {
+            self.position += 1;
+            base = factorial(base)?;
+        }
+        if let Some(Token::Operator("^" | "**")) = self.peek() {
+            self.position += 1;
+            // Right-associative: 2^3^2 is 2^9
+            let exponent = self.unary()?;
+            return Ok(base.powf(exponent));
+        }
+        Ok(base)
+    }
+
+    fn primary(&mut self) -> Result<f64, String> {
+        match self.next() {
+            Some(Token::Number(value)) => Ok(value),
+            Some(Token::Open) => {
+                let value = self.binary(1)?;
+                match self.next() {
+                    Some(Token::Close) => Ok(value),
+                    _ => Err("Missing ')'".to_string()),
+                }
+            }
+            Some(Token::Name(name)) => {
+                if let Some(Token::Open) = self.peek() {
+                    self.position += 1;
+                    let arguments = self.arguments()?;
+                    return call(&name, &arguments);
+                }
+                constant(&name)
+                    .or_else(|| self.variables.get(&name).copied())
+                    .ok_or_else(|| if name == "ans" { "No previous result for ans yet".to_string() } else { format!("Unknown variable '{}'", name) })
+            }
+            Some(token) => Err(format!("Unexpected {}", describe_token(&token))),
+            None => Err("The expression ends too early".to_string()),
+        }
+    }
+
+    fn arguments(&mut self) -> Result<Vec<f64>, String> {
+        let mut arguments = Vec::new();
+        if let Some(Token::Close) = self.peek() {
+            self.position += 1;
+            return Ok(arguments);
+        }
+        loop {
+            arguments.push(self.binary(1)?);
+            match self.next() {
+                Some(Token::Comma) => continue,
+                Some(Token::Close) => return Ok(arguments),
+                _ => return Err("Missing ')' after function arguments".to_string()),
+            }
+        }
+    }
+}
+
+fn describe_token(token: &Token) -> String {
+    match token {
+        Token::Number(value) => format!("number {}", format_number(*value, 12)),
+        Token::Name(name) => format!("'{}'", name),
+        Token::Operator(operator) => format!("'{}'", operator),
+        Token::Open => "'('".to_string(),
+        Token::Close => "')'".to_string(),
+        Token::Comma => "','".to_string(),
+    }
+}
+
+fn apply(operator: &str, left: f64, right: f64) -> Result<f64, String> {
+    Ok(match operator {
+        "+" => left + right,
+        "-" => left - right,
+        "*" => left * right,
+        "/" | "//" | "%" if right == 0.0 => return Err("Division by zero".to_string()),
+        "/" => left / right,
+        "//" => (left / right).floor(),
+        // The sign follows the divisor, as in Python
+        "%" => left - right * (left / right).floor(),
+        "&" => (integer(left, "&")? & integer(right, "&")?) as f64,
+        "|" => (integer(left, "|")? | integer(right, "|")?) as f64,
+        "xor" => (integer(left, "xor")? ^ integer(right, "xor")?) as f64,
+        "<<" | ">>" => {
+            let (value, shift) = (integer(left, operator)?, integer(right, operator)?);
+            if !(0..64).contains(&shift) {
+                return Err(format!("Shift by {} is out of range", shift));
+            }
+            let shifted = if operator == "<<" { value.checked_shl(shift as u32).filter(|result| result >> shift == value) } else { Some(value >> shift) };
+            shifted.ok_or("The shifted value overflows 64 bits")? as f64
+        }
+        _ => return Err(format!("Unknown operator '{}'", operator)),
+    })
+}
+
+fn factorial(value: f64) -> Result<f64, String> {
+    let n = integer(value, "!")?;
+    if !(0..=170).contains(&n) {
+        return Err(format!("{}! is out of range", n));
+    }
+    Ok((1..=n).map(|k| k as f64).product())
+}
+
+fn call(name: &str, arguments: &[f64]) -> Result<f64, String> {
+    let one = |f: fn(f64) -> f64| match arguments {
+        [x] => Ok(f(*x)),
+        _ => Err(format!("{}() takes one argument", name)),
+    };
+    let two = |f: fn(f64, f64) -> f64| match arguments {
+        [x, y] => Ok(f(*x, *y)),
+        _ => Err(format!("{}() takes two arguments", name)),
+    };
+    match name {
+        "sqrt" => one(f64::sqrt),
+        "cbrt" => one(f64::cbrt),
+        "abs" => one(f64::abs),
+        "exp" => one(f64::exp),
+        "ln" => one(f64::ln),
+        "log2" => one(f64::log2),
+        "log10" => one(f64::log10),
+        "log" => match arguments {
+            [x] => Ok(x.log10()),
+            [x, base] => Ok(x.log(*base)),
+            _ => Err("log() takes a value and an optional base".to_string()),
+        },
+        "sin" => one(f64::sin),
+        "cos" => one(f64::cos),
+        "tan" => one(f64::tan),
+        "asin" => one(f64::asin),
+        "acos" => one(f64::acos),
+        "atan" => one(f64::atan),
+        "floor" => one(f64::floor),
+        "ceil" => one(f64::ceil),
+        "trunc" => one(f64::trunc),
+        "round" => match arguments {
+            [x] => Ok(x.round()),
+            [x, digits] => {
+                let scale = 10f64.powi(integer(*digits, "round()")? as i32);
+                Ok((x * scale).round() / scale)
+            }
+            _ => Err("round() takes a value and optional digits".to_string()),
+        },
+        "pow" => two(f64::powf),
+        "hypot" => two(f64::hypot),
+        "min" | "max" if arguments.is_empty() => Err(format!("{}() needs at least one argument", name)),
+        "min" => Ok(arguments.iter().copied().fold(f64::INFINITY, f64::min)),
+        "max" => Ok(arguments.iter().copied().fold(f64::NEG_INFINITY, f64::max)),
+        _ => Err(format!("Unknown function '{}'", name)),
+    }
+}
+
+// On a terminal, integers also show in hex and binary
+fn describe(value: f64, terminal: bool) -> String {
+    let plain = format_number(value, 12);
+    if !terminal || value.fract() != 0.0 || value.abs() > MAX_EXACT || value.abs() < 2.0 {
+        return plain;
+    }
+    let integer = value as i64;
+    let (sign, magnitude) = if integer < 0 { ("-", integer.unsigned_abs()) } else { ("", integer as u64) };
+    format!("{}  {}", plain, format!("{}0x{:x}  {}0b{:b}", sign, magnitude, sign, magnitude).dimmed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calc(expression: &str) -> Result<String, String> {
+        evaluate_line(expression, &mut HashMap::new()).map(|(_, value)| format_number(value, 12))
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(calc("2^10 * (3 + 4) % 5").unwrap(), "3");
+        assert_eq!(calc("1 + 2 * 3").unwrap(), "7");
+        assert_eq!(calc("-2^2").unwrap(), "-4");
+        assert_eq!(calc("2^3^2").unwrap(), "512");
+        assert_eq!(calc("2**-1").unwrap(), "0.5");
+        assert_eq!(calc("7 // 2").unwrap(), "3");
+        assert_eq!(calc("-7 % 3").unwrap(), "2");
+        assert_eq!(calc("1/3").unwrap(), "0.333333333333");
+        assert_eq!(calc("1.5e3 + 1_000").unwrap(), "2500");
+        assert_eq!(calc("5!").unwrap(), "120");
+        assert!(calc("1 / 0").unwrap_err().contains("Division by zero"));
+    }
+
+    #[test]
+    fn test_bitwise_and_literals() {
+        assert_eq!(calc("0xff & 0b1010").unwrap(), "10");
+        assert_eq!(calc("0o17 | 0x10").unwrap(), "31");
+        assert_eq!(calc("6 xor 3").unwrap(), "5");
+        assert_eq!(calc("1 << 10 >> 2").unwrap(), "256");
+        assert_eq!(calc("~0").unwrap(), "-1");
+        assert_eq!(calc("1 + 2 << 1").unwrap(), "6");
+        assert!(calc("1.5 & 1").unwrap_err().contains("integers"));
+        assert!(calc("1 << 64").is_err());
+    }
+
+    #[test]
+    fn test_functions_and_errors() {
+        assert_eq!(calc("sqrt(16) + abs(-2)").unwrap(), "6");
+        assert_eq!(calc("round(2/3, 2)").unwrap(), "0.67");
+        assert_eq!(calc("log(8, 2) + log(1000) + ln(e)").unwrap(), "7");
+        assert_eq!(calc("max(1, 5, 3) - min(4, 2)").unwrap(), "3");
+        assert_eq!(calc("round(sin(pi))").unwrap(), "0");
+        assert!(calc("nope(1)").unwrap_err().contains("Unknown function"));
+        assert!(calc("(1 + 2").unwrap_err().contains("Missing ')'"));
+        assert!(calc("1 + 2)").unwrap_err().contains("Unmatched"));
+        assert!(calc("3 $ 4").is_err());
+        assert!(calc("").is_err());
+    }
+
+    #[test]
+    fn test_variables() {
+        let mut variables = HashMap::new();
+        assert_eq!(evaluate_line("rate = 0x10", &mut variables).unwrap(), (Some("rate".to_string()), 16.0));
+        assert_eq!(evaluate_line("rate * 2", &mut variables).unwrap().1, 32.0);
+        assert_eq!(evaluate_line("ans + 1", &mut variables).unwrap().1, 33.0);
+        assert!(evaluate_line("pi = 3", &mut variables).is_err());
+        assert!(evaluate_line("missing + 1", &mut variables).unwrap_err().contains("Unknown variable"));
+        assert_eq!(describe(255.0, false), "255");
+    }
+}
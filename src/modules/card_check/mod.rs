@@ -0,0 +1,244 @@
+use crate::tool_module::{ExitCodeError, ToolModule};
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use std::error::Error;
+
+pub struct CardCheckModule;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Brand {
+    Visa,
+    Mastercard,
+    Amex,
+    Discover,
+    Diners,
+    Jcb,
+    UnionPay,
+    Maestro,
+}
+
+impl Brand {
+    const ALL: [Brand; 8] = [Brand::Visa, Brand::Mastercard, Brand::Amex, Brand::Discover, Brand::Diners, Brand::Jcb, Brand::UnionPay, Brand::Maestro];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Brand::Visa => "Visa",
+            Brand::Mastercard => "Mastercard",
+            Brand::Amex => "American Express",
+            Brand::Discover => "Discover",
+            Brand::Diners => "Diners Club",
+            Brand::Jcb => "JCB",
+            Brand::UnionPay => "UnionPay",
+            Brand::Maestro => "Maestro",
+        }
+    }
+
+    fn key(&self) -> &'static str {
+        match self {
+            Brand::Visa => "visa",
+            Brand::Mastercard => "mastercard",
+            Brand::Amex => "amex",
+            Brand::Discover => "discover",
+            Brand::Diners => "diners",
+            Brand::Jcb => "jcb",
+            Brand::UnionPay => "unionpay",
+            Brand::Maestro => "maestro",
+        }
+    }
+
+    fn lengths(&self) -> &'static [usize] {
+        match self {
+            Brand::Visa => &[13, 16, 19],
+            Brand::Mastercard => &[16],
+            Brand::Amex => &[15],
+            Brand::Discover | Brand::Jcb | Brand::UnionPay => &[16, 17, 18, 19],
+            Brand::Diners => &[14, 15, 16, 17, 18, 19],
+            Brand::Maestro => &[12, 13, 14, 15, 16, 17, 18, 19],
+        }
+    }
+}
+
+// Issuer prefix ranges (inclusive, compared on as many leading digits as the
+// bounds have), most specific first so that Maestro's wide 5/6 ranges come last
+const PREFIXES: [(Brand, u32, u32); 15] = [
+    (Brand::Amex, 34, 34),
+    (Brand::Amex, 37, 37),
+    (Brand::Diners, 300, 305),
+    (Brand::Diners, 36, 36),
+    (Brand::Diners, 38, 39),
+    (Brand::Jcb, 3528, 3589),
+    (Brand::Visa, 4, 4),
+    (Brand::Mastercard, 51, 55),
+    (Brand::Mastercard, 2221, 2720),
+    (Brand::Discover, 6011, 6011),
+    (Brand::Discover, 644, 649),
+    (Brand::Discover, 65, 65),
+    (Brand::UnionPay, 62, 62),
+    (Brand::Maestro, 50, 50),
+    (Brand::Maestro, 56, 69),
+];
+
+// Sandbox numbers published by payment providers (mostly Stripe's); any future
+// expiry date and any CVC are accepted
+const TEST_CARDS: [(Brand, &str, &str); 14] = [
+    (Brand::Visa, "4242424242424242", "succeeds"),
+    (Brand::Visa, "4000056655665556", "debit, succeeds"),
+    (Brand::Visa, "4000000000000002", "declined (generic decline)"),
+    (Brand::Visa, "4000000000009995", "declined (insufficient funds)"),
+    (Brand::Visa, "4000002500003155", "requires 3D Secure authentication"),
+    (Brand::Mastercard, "5555555555554444", "succeeds"),
+    (Brand::Mastercard, "2223003122003222", "2-series, succeeds"),
+    (Brand::Mastercard, "5200828282828210", "debit, succeeds"),
+    (Brand::Amex, "378282246310005", "succeeds"),
+    (Brand::Amex, "371449635398431", "succeeds"),
+    (Brand::Discover, "6011111111111117", "succeeds"),
+    (Brand::Diners, "3056930009020004", "succeeds"),
+    (Brand::Jcb, "3566002020360505", "succeeds"),
+    (Brand::UnionPay, "6200000000000005", "succeeds"),
+];
+
+impl ToolModule for CardCheckModule {
+    fn name(&self) -> &'static str {
+        "card-check"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("card-check")
+                .long("card-check")
+                .value_name("NUMBER")
+                .help("Validate a card number (Luhn checksum, length) and detect its brand; the output is masked")
+                .long_help("Check a payment card number: the Luhn checksum, the brand from its issuer prefix (Visa, Mastercard, American Express, Discover, Diners Club, JCB, UnionPay, Maestro) and whether the length fits that brand. Spaces and dashes are ignored. Only the first six and last four digits are shown. Exits with code 1 when the number is invalid. Nothing is sent anywhere.")
+        )
+        .arg(
+            Arg::new("card-test")
+                .long("card-test")
+                .value_name("BRAND")
+                .num_args(0..=1)
+                .default_missing_value("all")
+                .help("List well-known sandbox card numbers: visa, mastercard, amex, discover, diners, jcb, unionpay or all")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(number) = matches.get_one::<String>("card-check") {
+            let digits = normalize(number)?;
+            let brand = detect_brand(&digits);
+            let luhn = luhn_valid(&digits);
+            let length_ok = brand.map(|brand| brand.lengths().contains(&digits.len())).unwrap_or((12..=19).contains(&digits.len()));
+            let mark = |ok: bool| if ok { "✅" } else { "❌" };
+            println!("{:<10} {}", "Number:".cyan(), mask(&digits));
+            println!("{:<10} {}", "Brand:".cyan(), brand.map(|brand| brand.name()).unwrap_or("unknown"));
+            println!("{:<10} {} {}", "Luhn:".cyan(), mark(luhn), if luhn { "checksum valid" } else { "checksum invalid" });
+            println!("{:<10} {} {} digits", "Length:".cyan(), mark(length_ok), digits.len());
+            if let Some((_, _, note)) = TEST_CARDS.iter().find(|(_, test, _)| *test == digits) {
+                println!("{:<10} sandbox test card ({})", "Note:".cyan(), note);
+            }
+            if !luhn || !length_ok {
+                return Err(Box::new(ExitCodeError::silent(1)));
+            }
+        }
+
+        if let Some(brand) = matches.get_one::<String>("card-test") {
+            let brands: Vec<Brand> = match brand.to_lowercase().as_str() {
+                "all" => Brand::ALL.to_vec(),
+                "mc" => vec![Brand::Mastercard],
+                "americanexpress" | "american-express" => vec![Brand::Amex],
+                key => {
+                    let with_cards: Vec<Brand> = Brand::ALL.into_iter().filter(|brand| TEST_CARDS.iter().any(|(card, _, _)| card == brand)).collect();
+                    vec![with_cards.iter().copied().find(|brand| brand.key() == key).ok_or_else(|| {
+                        let keys: Vec<&str> = with_cards.iter().map(|brand| brand.key()).collect();
+                        format!("No test cards for '{}'. Use one of: {}, all", brand, keys.join(", "))
+                    })?]
+                }
+            };
+            for (brand, number, note) in TEST_CARDS.iter().filter(|(brand, _, _)| brands.contains(brand)) {
+                println!("{:<20} {:<16} {}", group(number).green(), brand.name(), note.dimmed());
+            }
+            println!("\nUse any future expiry date and any CVC (4 digits for American Express).");
+        }
+        Ok(())
+    }
+}
+
+fn normalize(number: &str) -> Result<String, String> {
+    let digits: String = number.chars().filter(|c| !c.is_whitespace() && *c != '-').collect();
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err("A card number may only contain digits, spaces and dashes".to_string());
+    }
+    Ok(digits)
+}
+
+pub fn luhn_valid(digits: &str) -> bool {
+    let sum: u32 = digits
+        .bytes()
+        .rev()
+        .enumerate()
+        .map(|(index, byte)| {
+            let digit = (byte - b'0') as u32;
+            if index % 2 == 1 {
+                let doubled = digit * 2;
+                if doubled > 9 { doubled - 9 } else { doubled }
+            } else {
+                digit
+            }
+        })
+        .sum();
+    digits.len() > 1 && sum.is_multiple_of(10)
+}
+
+fn detect_brand(digits: &str) -> Option<Brand> {
+    PREFIXES.iter().find_map(|(brand, low, high)| {
+        let width = low.to_string().len();
+        let prefix: u32 = digits.get(..width)?.parse().ok()?;
+        (prefix >= *low && prefix <= *high).then_some(*brand)
+    })
+}
+
+// First six and last four digits, in groups of four
+fn mask(digits: &str) -> String {
+    let length = digits.len();
+    let masked: String = digits.chars().enumerate().map(|(index, c)| if index < 6 || index + 4 >= length { c } else { '•' }).collect();
+    group(&masked)
+}
+
+fn group(digits: &str) -> String {
+    let chars: Vec<char> = digits.chars().collect();
+    chars.chunks(4).map(|chunk| chunk.iter().collect::<String>()).collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_luhn_and_normalize() {
+        assert!(luhn_valid(&normalize("4242 4242-4242 4242").unwrap()));
+        assert!(!luhn_valid("4242424242424241"));
+        assert!(!luhn_valid("0"));
+        assert!(normalize("4242x").is_err());
+    }
+
+    #[test]
+    fn test_detect_brand() {
+        assert_eq!(detect_brand("4111111111111111"), Some(Brand::Visa));
+        assert_eq!(detect_brand("2720990000000000"), Some(Brand::Mastercard));
+        assert_eq!(detect_brand("6011000990139424"), Some(Brand::Discover));
+        assert_eq!(detect_brand("6500000000000002"), Some(Brand::Discover));
+        assert_eq!(detect_brand("3530111333300000"), Some(Brand::Jcb));
+        assert_eq!(detect_brand("5018000000000009"), Some(Brand::Maestro));
+        assert_eq!(detect_brand("9999999999999995"), None);
+        // Every sandbox number is valid for the brand it is listed under
+        for (brand, number, _) in TEST_CARDS {
+            assert!(luhn_valid(number), "{}", number);
+            assert_eq!(detect_brand(number), Some(brand), "{}", number);
+            assert!(brand.lengths().contains(&number.len()), "{}", number);
+        }
+    }
+
+    #[test]
+    fn test_mask() {
+        assert_eq!(mask("4242424242424242"), "4242 42•• •••• 4242");
+        assert_eq!(mask("378282246310005"), "3782 82•• •••0 005");
+    }
+}
@@ -28,18 +28,7 @@ impl ToolModule for CaseConvertModule {
                 let text = values[0];
                 let case_type = values[1];
                 
-                let converted = match case_type.to_lowercase().as_str() {
-                    "upper" => text.to_uppercase(),
-                    "lower" => text.to_lowercase(),
-                    "title" => to_title_case(text),
-                    "camel" => to_camel_case(text),
-                    "pascal" => to_pascal_case(text),
-                    "snake" => to_snake_case(text),
-                    "kebab" => to_kebab_case(text),
-                    "constant" => to_constant_case(text),
-                    _ => return Err("Invalid case type. Use: upper, lower, title, camel, pascal, snake, kebab, constant".into()),
-                };
-                
+                let converted = convert_case(text, case_type).ok_or("Invalid case type. Use: upper, lower, title, camel, pascal, snake, kebab, constant")?;
                 copy_to_clipboard_and_print(&converted);
             }
         }
@@ -47,6 +36,21 @@ impl ToolModule for CaseConvertModule {
     }
 }
 
+// None for an unknown case type
+pub fn convert_case(text: &str, case_type: &str) -> Option<String> {
+    Some(match case_type.to_lowercase().as_str() {
+        "upper" => text.to_uppercase(),
+        "lower" => text.to_lowercase(),
+        "title" => to_title_case(text),
+        "camel" => to_camel_case(text),
+        "pascal" => to_pascal_case(text),
+        "snake" => to_snake_case(text),
+        "kebab" => to_kebab_case(text),
+        "constant" => to_constant_case(text),
+        _ => return None,
+    })
+}
+
 fn to_title_case(text: &str) -> String {
     text.split_whitespace()
         .map(|word| {
@@ -128,11 +132,11 @@ fn split_into_words(text: &str) -> Vec<String> {
                 current_word.clear();
             }
             current_word.push(ch);
-        } else if ch.is_whitespace() || ch == '_' || ch == '-' || !ch.is_alphanumeric() {
-            if !current_word.is_empty() {
-                words.push(current_word.clone());
-                current_word.clear();
-            }
+        } else if (ch.is_whitespace() || ch == '_' || ch == '-' || !ch.is_alphanumeric())
+            && !current_word.is_empty()
+        {
+            words.push(current_word.clone());
+            current_word.clear();
         }
     }
     
@@ -0,0 +1,330 @@
+use crate::tool_module::ToolModule;
+use chrono::{DateTime, Utc};
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::WebPkiServerVerifier;
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{ClientConfig, ClientConnection, DigitallySignedStruct, RootCertStore, SignatureScheme, StreamOwned};
+use sha2::{Digest, Sha256};
+use std::error::Error;
+use std::fs::File;
+use std::io::BufReader;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use x509_cert::der::Decode;
+use x509_cert::ext::pkix::name::GeneralName;
+use x509_cert::ext::pkix::SubjectAltName;
+use x509_cert::Certificate;
+
+pub struct CertCheckModule;
+
+// Certificates expiring sooner than this are flagged
+const WARN_DAYS: i64 = 30;
+
+impl ToolModule for CertCheckModule {
+    fn name(&self) -> &'static str {
+        "cert-check"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("cert-check")
+                .long("cert-check")
+                .value_name("HOST[:PORT]|FILE")
+                .help("Inspect a server's TLS certificate chain, or the certificates in a PEM file")
+                .long_help("Connect to HOST (port 443 unless given, https:// URLs accepted), perform a TLS handshake and report the negotiated protocol and cipher, whether the chain is trusted, and for every certificate its subject, SANs, issuer, validity with days remaining (flagged under 30 days) and SHA-256 fingerprint. Untrusted and expired certificates are still shown. When the argument is an existing file, the PEM certificates in it are inspected instead.")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(target) = matches.get_one::<String>("cert-check") {
+            let now = Utc::now();
+            let certs = if Path::new(target).is_file() {
+                println!("{}", target.bold());
+                read_pem(target)?
+            } else {
+                let (host, port) = split_target(target)?;
+                let handshake = handshake(&host, port)?;
+                println!("{}:{} {}", host.bold(), port, format!("({})", handshake.address).dimmed());
+                println!("{:<14}{}, {}", "Protocol:".cyan(), handshake.protocol, handshake.cipher);
+                match &handshake.verification {
+                    None => println!("{:<14}{}", "Trust:".cyan(), "✅ valid chain for this host name".green()),
+                    Some(problem) => println!("{:<14}{}", "Trust:".cyan(), format!("⚠️  {}", problem).yellow()),
+                }
+                handshake.certs
+            };
+            for (index, der) in certs.iter().enumerate() {
+                let info = CertInfo::parse(der)?;
+                let role = match index {
+                    0 => "leaf",
+                    _ if info.subject == info.issuer => "root",
+                    _ => "intermediate",
+                };
+                println!();
+                println!("{}", format!("Certificate {} ({})", index, role).bold());
+                print_cert(&info, now);
+            }
+        }
+        Ok(())
+    }
+}
+
+// "example.com", "example.com:8443", "https://example.com/path", "[::1]:443"
+fn split_target(target: &str) -> Result<(String, u16), String> {
+    let rest = target.trim().split("://").last().unwrap_or("");
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or("");
+    let (host, port) = if let Some(bracketed) = authority.strip_prefix('[') {
+        match bracketed.split_once(']') {
+            Some((host, port)) => (host, port.strip_prefix(':')),
+            None => return Err(format!("Invalid address '{}'", target)),
+        }
+    } else {
+        match authority.rsplit_once(':') {
+            Some((host, port)) if !host.contains(':') => (host, Some(port)),
+            _ => (authority, None),
+        }
+    };
+    if host.is_empty() {
+        return Err(format!("'{}' is neither a host name nor an existing file", target));
+    }
+    let port = match port {
+        Some(port) => port.parse::<u16>().map_err(|_| format!("Invalid port '{}'", port))?,
+        None => 443,
+    };
+    Ok((host.to_string(), port))
+}
+
+fn read_pem(path: &str) -> Result<Vec<CertificateDer<'static>>, Box<dyn Error>> {
+    let file = File::open(path).map_err(|e| format!("Failed to open '{}': {}", path, e))?;
+    let certs = rustls_pemfile::certs(&mut BufReader::new(file)).collect::<Result<Vec<_>, _>>()?;
+    if certs.is_empty() {
+        return Err(format!("No PEM certificates found in '{}'", path).into());
+    }
+    Ok(certs)
+}
+
+struct Handshake {
+    address: String,
+    protocol: String,
+    cipher: String,
+    // Why the chain is not trusted, if it is not
+    verification: Option<String>,
+    certs: Vec<CertificateDer<'static>>,
+}
+
+// Verifies like a browser would but records the outcome instead of failing, so
+// expired, self-signed and mismatched certificates can still be inspected.
+// Handshake signatures are always verified.
+#[derive(Debug)]
+struct RecordingVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    problem: Mutex<Option<String>>,
+}
+
+impl ServerCertVerifier for RecordingVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        if let Err(e) = self.inner.verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now) {
+            *self.problem.lock().unwrap() = Some(e.to_string());
+        }
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+fn handshake(host: &str, port: u16) -> Result<Handshake, Box<dyn Error>> {
+    let mut roots = RootCertStore::empty();
+    roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let inner = WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider.clone()).build()?;
+    let verifier = Arc::new(RecordingVerifier { inner, problem: Mutex::new(None) });
+    let config = ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()?
+        .dangerous()
+        .with_custom_certificate_verifier(verifier.clone())
+        .with_no_client_auth();
+
+    let address = (host, port)
+        .to_socket_addrs()
+        .map_err(|e| format!("Failed to resolve {}: {}", host, e))?
+        .next()
+        .ok_or_else(|| format!("{} has no addresses", host))?;
+    let socket = TcpStream::connect_timeout(&address, Duration::from_secs(10)).map_err(|e| format!("Failed to connect to {}:{}: {}", host, port, e))?;
+    socket.set_read_timeout(Some(Duration::from_secs(10)))?;
+    let server_name = ServerName::try_from(host.to_string()).map_err(|_| format!("Invalid host name '{}'", host))?;
+    let connection = ClientConnection::new(Arc::new(config), server_name)?;
+    let mut stream = StreamOwned::new(connection, socket);
+    while stream.conn.is_handshaking() {
+        stream.conn.complete_io(&mut stream.sock).map_err(|e| format!("TLS handshake with {}:{} failed: {}", host, port, e))?;
+    }
+
+    let conn = &stream.conn;
+    let protocol = conn.protocol_version().map(|version| format!("{:?}", version).replace('_', ".")).unwrap_or_default();
+    let cipher = conn.negotiated_cipher_suite().map(|suite| format!("{:?}", suite.suite())).unwrap_or_default();
+    let certs = conn.peer_certificates().unwrap_or_default().iter().map(|cert| cert.clone().into_owned()).collect();
+    let verification = verifier.problem.lock().unwrap().take();
+    Ok(Handshake { address: address.ip().to_string(), protocol, cipher, verification, certs })
+}
+
+struct CertInfo {
+    subject: String,
+    issuer: String,
+    sans: Vec<String>,
+    not_before: DateTime<Utc>,
+    not_after: DateTime<Utc>,
+    serial: String,
+    fingerprint: String,
+}
+
+impl CertInfo {
+    fn parse(der: &[u8]) -> Result<CertInfo, Box<dyn Error>> {
+        let cert = Certificate::from_der(der).map_err(|e| format!("Invalid certificate: {}", e))?;
+        let tbs = &cert.tbs_certificate;
+        let sans = match tbs.get::<SubjectAltName>() {
+            Ok(Some((_, SubjectAltName(names)))) => names.iter().filter_map(general_name).collect(),
+            _ => Vec::new(),
+        };
+        let time = |time: x509_cert::time::Time| DateTime::<Utc>::from(time.to_system_time());
+        Ok(CertInfo {
+            subject: tbs.subject.to_string(),
+            issuer: tbs.issuer.to_string(),
+            sans,
+            not_before: time(tbs.validity.not_before),
+            not_after: time(tbs.validity.not_after),
+            serial: colon_hex(tbs.serial_number.as_bytes()),
+            fingerprint: colon_hex(&Sha256::digest(der)),
+        })
+    }
+}
+
+fn general_name(name: &GeneralName) -> Option<String> {
+    match name {
+        GeneralName::DnsName(name) => Some(name.to_string()),
+        GeneralName::Rfc822Name(email) => Some(format!("email:{}", email)),
+        GeneralName::UniformResourceIdentifier(uri) => Some(format!("uri:{}", uri)),
+        GeneralName::IpAddress(octets) => match octets.as_bytes().len() {
+            4 => <[u8; 4]>::try_from(octets.as_bytes()).ok().map(|ip| std::net::Ipv4Addr::from(ip).to_string()),
+            16 => <[u8; 16]>::try_from(octets.as_bytes()).ok().map(|ip| std::net::Ipv6Addr::from(ip).to_string()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn colon_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02X}", byte)).collect::<Vec<_>>().join(":")
+}
+
+// "expires in 42 days", flagged when close or past
+fn expiry_status(not_before: DateTime<Utc>, not_after: DateTime<Utc>, now: DateTime<Utc>) -> ColoredString {
+    let days = (not_after - now).num_days();
+    if now < not_before {
+        format!("⚠️  not valid until {}", not_before.format("%Y-%m-%d")).yellow()
+    } else if not_after < now {
+        format!("❌ expired {} day{} ago", -days, if days == -1 { "" } else { "s" }).red()
+    } else if days < WARN_DAYS {
+        format!("⚠️  expires in {} day{}", days, if days == 1 { "" } else { "s" }).yellow()
+    } else {
+        format!("{} days remaining", days).green()
+    }
+}
+
+fn print_cert(info: &CertInfo, now: DateTime<Utc>) {
+    let date = |time: DateTime<Utc>| time.format("%Y-%m-%d %H:%M:%S UTC").to_string();
+    println!("  {:<13}{}", "Subject:".cyan(), info.subject);
+    if !info.sans.is_empty() {
+        println!("  {:<13}{}", "SANs:".cyan(), info.sans.join(", "));
+    }
+    println!("  {:<13}{}", "Issuer:".cyan(), info.issuer);
+    println!("  {:<13}{}", "Valid from:".cyan(), date(info.not_before));
+    println!("  {:<13}{} ({})", "Valid until:".cyan(), date(info.not_after), expiry_status(info.not_before, info.not_after, now));
+    println!("  {:<13}{}", "Serial:".cyan(), info.serial);
+    println!("  {:<13}{}", "SHA-256:".cyan(), info.fingerprint);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    // Self-signed P-256 certificate for test.example, *.test.example and 127.0.0.1
+    const TEST_CERT: &str = "-----BEGIN CERTIFICATE-----
+MIIBzjCCAXSgAwIBAgICEjQwCgYIKoZIzj0EAwIwLTEVMBMGA1UEAwwMdGVzdC5l
+eGFtcGxlMRQwEgYDVQQKDAtNaWNybyBTd2lzczAeFw0yNjEwMTYxODQwMzFaFw0z
+NjEwMTMxODQwMzFaMC0xFTATBgNVBAMMDHRlc3QuZXhhbXBsZTEUMBIGA1UECgwL
+TWljcm8gU3dpc3MwWTATBgcqhkjOPQIBBggqhkjOPQMBBwNCAAQA5nbYMvKqupE8
+5O0DMoTUvJyMZbOb5KQOQk7czZ+b4lTp0s0rdolfhySt9MU5r703r0TMm7NWhB2p
+75jRiODvo4GDMIGAMB0GA1UdDgQWBBSX7KsJWwNESW/AUjmGNwfWyyy/gTAfBgNV
+HSMEGDAWgBSX7KsJWwNESW/AUjmGNwfWyyy/gTAPBgNVHRMBAf8EBTADAQH/MC0G
+A1UdEQQmMCSCDHRlc3QuZXhhbXBsZYIOKi50ZXN0LmV4YW1wbGWHBH8AAAEwCgYI
+KoZIzj0EAwIDSAAwRQIhALTx/nl0BMr/0L+WfYSIJXi4vBcg4vuMgdd4OWPyWQoR
+AiBiYVwHz2PhlYpqrZ+bgoZhHqdoZB4Pr/5P2rnqkrIHuA==
+-----END CERTIFICATE-----
+";
+
+    fn test_cert() -> CertInfo {
+        let der = rustls_pemfile::certs(&mut TEST_CERT.as_bytes()).next().unwrap().unwrap();
+        CertInfo::parse(&der).unwrap()
+    }
+
+    #[test]
+    fn test_parse_certificate() {
+        let info = test_cert();
+        assert_eq!(info.subject, "O=Micro Swiss,CN=test.example");
+        assert_eq!(info.issuer, info.subject);
+        assert_eq!(info.sans, ["test.example", "*.test.example", "127.0.0.1"]);
+        assert_eq!(info.not_after, Utc.with_ymd_and_hms(2036, 10, 13, 18, 40, 31).unwrap());
+        assert_eq!(info.serial, "12:34");
+        assert_eq!(info.fingerprint, "2B:DC:EE:EF:49:B5:0C:40:B5:9B:BC:14:E6:02:E3:0B:F4:86:30:F8:ED:40:64:FC:85:43:A9:F8:55:28:3D:FE");
+        assert!(CertInfo::parse(b"not a certificate").is_err());
+    }
+
+    #[test]
+    fn test_expiry_status() {
+        let info = test_cert();
+        let at = |y, m, d| Utc.with_ymd_and_hms(y, m, d, 0, 0, 0).unwrap();
+        assert!(expiry_status(info.not_before, info.not_after, at(2036, 1, 1)).contains("days remaining"));
+        assert!(expiry_status(info.not_before, info.not_after, at(2036, 10, 1)).contains("expires in 12 days"));
+        assert!(expiry_status(info.not_before, info.not_after, at(2036, 10, 20)).contains("expired 6 days ago"));
+        assert!(expiry_status(info.not_before, info.not_after, at(2020, 1, 1)).contains("not valid until 2026-10-16"));
+    }
+
+    #[test]
+    fn test_split_target() {
+        assert_eq!(split_target("example.com").unwrap(), ("example.com".to_string(), 443));
+        assert_eq!(split_target("https://example.com:8443/path").unwrap(), ("example.com".to_string(), 8443));
+        assert_eq!(split_target("[::1]:4433").unwrap(), ("::1".to_string(), 4433));
+        assert!(split_target("example.com:https").is_err());
+        assert!(split_target("").is_err());
+    }
+}
@@ -0,0 +1,228 @@
+use crate::tool_module::{ExitCodeError, ToolModule};
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use ipnet::IpNet;
+use std::error::Error;
+use std::net::IpAddr;
+
+pub struct CidrCalcModule;
+
+// --cidr-split prints at most this many subnets
+const MAX_LISTED_SUBNETS: usize = 256;
+
+impl ToolModule for CidrCalcModule {
+    fn name(&self) -> &'static str {
+        "cidr-calc"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("cidr")
+                .long("cidr")
+                .value_name("CIDR")
+                .help("Show network, broadcast, usable range, host count and netmask of an IPv4 or IPv6 block")
+                .long_help("Describe a CIDR block such as 10.0.0.0/22 or 2001:db8::/48: network and broadcast address, netmask and wildcard mask, first and last usable address and the number of addresses. A host address with a prefix (10.0.1.7/22) is reduced to its network. A bare address is treated as a single-host block.")
+        )
+        .arg(
+            Arg::new("cidr-contains")
+                .long("cidr-contains")
+                .value_names(["CIDR", "IP"])
+                .num_args(2)
+                .help("Check whether an IP address (or a smaller block) falls inside a CIDR block")
+                .long_help("Check whether an IP address (or a smaller block) falls inside a CIDR block. Exits with code 1 when it does not, so scripts can branch on the result.")
+        )
+        .arg(
+            Arg::new("cidr-split")
+                .long("cidr-split")
+                .value_names(["CIDR", "COUNT"])
+                .num_args(2)
+                .help("Split a CIDR block into COUNT equal subnets, or into /PREFIX subnets")
+                .long_help("Split a CIDR block into COUNT equal subnets (rounded up to a power of two), e.g. --cidr-split 10.0.0.0/22 4, or into subnets of a given size, e.g. --cidr-split 10.0.0.0/22 /24.")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(cidr) = matches.get_one::<String>("cidr") {
+            let net = parse_net(cidr)?;
+            for (label, value) in describe(&net) {
+                println!("{:<17}{}", format!("{}:", label).cyan(), value);
+            }
+        } else if let Some(values) = matches.get_many::<String>("cidr-contains") {
+            let values: Vec<&String> = values.collect();
+            let net = parse_net(values[0])?;
+            let inner = parse_net(values[1])?;
+            if net.contains(&inner) {
+                println!("✅ {} is inside {}", values[1], net.trunc());
+            } else {
+                println!("❌ {} is not inside {}", values[1], net.trunc());
+                return Err(Box::new(ExitCodeError::silent(1)));
+            }
+        } else if let Some(values) = matches.get_many::<String>("cidr-split") {
+            let values: Vec<&String> = values.collect();
+            let net = parse_net(values[0])?;
+            let (prefix, rounded) = split_prefix(&net, values[1])?;
+            let subnets = net.trunc().subnets(prefix).map_err(|e| e.to_string())?;
+            let bits = u32::from(prefix - net.prefix_len());
+            let total = 1u128.checked_shl(bits).map(|total| total.to_string()).unwrap_or_else(|| format!("2^{}", bits));
+            if let Some(requested) = rounded {
+                println!("⚠️  {} is not a power of two; splitting into {} subnets", requested, total);
+            }
+            println!("{} → {} × /{}", net.trunc().to_string().bold(), total, prefix);
+            let mut subnets = subnets.peekable();
+            for subnet in subnets.by_ref().take(MAX_LISTED_SUBNETS) {
+                println!("  {}", subnet);
+            }
+            if subnets.peek().is_some() {
+                println!("  ... {} subnets in total", total);
+            }
+        }
+        Ok(())
+    }
+}
+
+// "10.0.0.0/22", "2001:db8::/32", or a bare address as a single-host block
+fn parse_net(value: &str) -> Result<IpNet, String> {
+    let value = value.trim();
+    if let Ok(net) = value.parse::<IpNet>() {
+        return Ok(net);
+    }
+    value
+        .parse::<IpAddr>()
+        .map(IpNet::from)
+        .map_err(|_| format!("'{}' is not a CIDR block or IP address, e.g. 10.0.0.0/24 or 2001:db8::/48", value))
+}
+
+fn address_count(net: &IpNet) -> u128 {
+    let bits = net.max_prefix_len() - net.prefix_len();
+    if bits == 128 { u128::MAX } else { 1u128 << bits }
+}
+
+// Thousands separators keep IPv6 counts readable
+fn group_digits(count: u128) -> String {
+    let digits = count.to_string();
+    let mut grouped = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+fn describe(net: &IpNet) -> Vec<(&'static str, String)> {
+    let network = net.network();
+    let last = net.broadcast();
+    let total = address_count(net);
+    let mut rows = vec![("CIDR", net.trunc().to_string())];
+    if net.addr() != network {
+        rows.push(("Address", net.addr().to_string()));
+    }
+    rows.push(("Network", network.to_string()));
+    match net {
+        IpNet::V4(v4) => {
+            rows.push(("Broadcast", last.to_string()));
+            rows.push(("Netmask", v4.netmask().to_string()));
+            rows.push(("Wildcard", v4.hostmask().to_string()));
+            // /31 point-to-point links and /32 hosts have no network or broadcast address to reserve (RFC 3021)
+            let (first, last_usable, usable) = if v4.prefix_len() >= 31 {
+                (network, last, total)
+            } else {
+                (IpAddr::V4(u32::from(v4.network()).wrapping_add(1).into()), IpAddr::V4((u32::from(v4.broadcast()) - 1).into()), total - 2)
+            };
+            rows.push(("Usable range", format!("{} - {}", first, last_usable)));
+            rows.push(("Usable hosts", group_digits(usable)));
+        }
+        IpNet::V6(_) => {
+            // IPv6 has no broadcast address; every address in the block is usable
+            rows.push(("Last address", last.to_string()));
+            rows.push(("Netmask", net.netmask().to_string()));
+            rows.push(("Usable range", format!("{} - {}", network, last)));
+        }
+    }
+    rows.push(("Total addresses", if total == u128::MAX { "2^128".to_string() } else { group_digits(total) }));
+    rows.push(("Prefix length", format!("/{}", net.prefix_len())));
+    rows
+}
+
+// "/24" -> 24; "4" -> prefix + 2. Also returns the requested count when it had to be rounded up
+fn split_prefix(net: &IpNet, value: &str) -> Result<(u8, Option<u128>), String> {
+    let value = value.trim();
+    let (prefix, rounded) = if let Some(prefix) = value.strip_prefix('/') {
+        (prefix.parse::<u8>().map_err(|_| format!("Invalid prefix '{}'", value))?, None)
+    } else {
+        let count: u128 = value.parse().map_err(|_| format!("Invalid subnet count '{}'. Use a number such as 4 or a prefix such as /24", value))?;
+        if count == 0 {
+            return Err("The subnet count must be at least 1".to_string());
+        }
+        let bits = (128 - (count - 1).leading_zeros()) as u8;
+        let rounded = (!count.is_power_of_two()).then_some(count);
+        (net.prefix_len().saturating_add(bits), rounded)
+    };
+    if prefix < net.prefix_len() {
+        return Err(format!("/{} is larger than {}; pick a prefix of /{} or more", prefix, net.trunc(), net.prefix_len()));
+    }
+    if prefix > net.max_prefix_len() {
+        return Err(format!("{} cannot be split that far; the longest prefix is /{}", net.trunc(), net.max_prefix_len()));
+    }
+    Ok((prefix, rounded))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(rows: &[(&str, String)], label: &str) -> String {
+        rows.iter().find(|(name, _)| *name == label).map(|(_, value)| value.clone()).unwrap_or_default()
+    }
+
+    #[test]
+    fn test_describe_ipv4() {
+        let rows = describe(&parse_net("10.0.1.7/22").unwrap());
+        assert_eq!(row(&rows, "CIDR"), "10.0.0.0/22");
+        assert_eq!(row(&rows, "Address"), "10.0.1.7");
+        assert_eq!(row(&rows, "Broadcast"), "10.0.3.255");
+        assert_eq!(row(&rows, "Netmask"), "255.255.252.0");
+        assert_eq!(row(&rows, "Wildcard"), "0.0.3.255");
+        assert_eq!(row(&rows, "Usable range"), "10.0.0.1 - 10.0.3.254");
+        assert_eq!(row(&rows, "Usable hosts"), "1,022");
+        assert_eq!(row(&rows, "Total addresses"), "1,024");
+        let point_to_point = describe(&parse_net("192.168.0.0/31").unwrap());
+        assert_eq!(row(&point_to_point, "Usable range"), "192.168.0.0 - 192.168.0.1");
+        assert_eq!(row(&describe(&parse_net("8.8.8.8").unwrap()), "Usable hosts"), "1");
+    }
+
+    #[test]
+    fn test_describe_ipv6() {
+        let rows = describe(&parse_net("2001:db8::/48").unwrap());
+        assert_eq!(row(&rows, "Last address"), "2001:db8:0:ffff:ffff:ffff:ffff:ffff");
+        assert_eq!(row(&rows, "Netmask"), "ffff:ffff:ffff::");
+        assert_eq!(row(&rows, "Total addresses"), "1,208,925,819,614,629,174,706,176");
+        assert_eq!(row(&describe(&parse_net("::/0").unwrap()), "Total addresses"), "2^128");
+    }
+
+    #[test]
+    fn test_contains() {
+        let net = parse_net("10.0.0.0/22").unwrap();
+        assert!(net.contains(&parse_net("10.0.3.200").unwrap()));
+        assert!(!net.contains(&parse_net("10.0.4.1").unwrap()));
+        assert!(net.contains(&parse_net("10.0.2.0/23").unwrap()));
+        assert!(!net.contains(&parse_net("10.0.0.0/21").unwrap()));
+        assert!(!net.contains(&parse_net("::1").unwrap()));
+        assert!(parse_net("10.0.0.0/33").is_err());
+    }
+
+    #[test]
+    fn test_split_prefix() {
+        let net = parse_net("10.0.0.0/22").unwrap();
+        assert_eq!(split_prefix(&net, "4").unwrap(), (24, None));
+        assert_eq!(split_prefix(&net, "3").unwrap(), (24, Some(3)));
+        assert_eq!(split_prefix(&net, "1").unwrap(), (22, None));
+        assert_eq!(split_prefix(&net, "/26").unwrap(), (26, None));
+        assert!(split_prefix(&net, "/20").is_err());
+        assert!(split_prefix(&net, "2048").is_err());
+        assert!(split_prefix(&net, "0").is_err());
+        let subnets: Vec<String> = net.subnets(24).unwrap().map(|subnet| subnet.to_string()).collect();
+        assert_eq!(subnets, ["10.0.0.0/24", "10.0.1.0/24", "10.0.2.0/24", "10.0.3.0/24"]);
+    }
+}
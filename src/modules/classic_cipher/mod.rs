@@ -0,0 +1,229 @@
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use std::error::Error;
+
+pub struct ClassicCipherModule;
+
+// Letter frequencies of English text in percent, A to Z, for ranking brute-forced shifts
+const ENGLISH_FREQUENCIES: [f64; 26] = [
+    8.167, 1.492, 2.782, 4.253, 12.702, 2.228, 2.015, 6.094, 6.966, 0.153, 0.772, 4.025, 2.406,
+    6.749, 7.507, 1.929, 0.095, 5.987, 6.327, 9.056, 2.758, 0.978, 2.360, 0.150, 1.974, 0.074,
+];
+
+// The 100 most common words of the Oxford English Corpus; each one in a candidate is a
+// strong sign it is the plaintext
+const COMMON_WORDS: [&str; 100] = [
+    "the", "be", "to", "of", "and", "a", "in", "that", "have", "i", "it", "for", "not", "on", "with", "he", "as", "you", "do", "at",
+    "this", "but", "his", "by", "from", "they", "we", "say", "her", "she", "or", "an", "will", "my", "one", "all", "would", "there", "their", "what",
+    "so", "up", "out", "if", "about", "who", "get", "which", "go", "me", "when", "make", "can", "like", "time", "no", "just", "him", "know", "take",
+    "people", "into", "year", "your", "good", "some", "could", "them", "see", "other", "than", "then", "now", "look", "only", "come", "its", "over", "think", "also",
+    "back", "after", "use", "two", "how", "our", "work", "first", "well", "way", "even", "new", "want", "because", "any", "these", "give", "day", "most", "us",
+];
+
+#[derive(Debug, PartialEq)]
+enum Cipher {
+    Rot13,
+    // None tries every shift
+    Caesar(Option<u8>),
+    Vigenere(Vec<u8>),
+    Atbash,
+}
+
+impl ToolModule for ClassicCipherModule {
+    fn name(&self) -> &'static str {
+        "classic-cipher"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("cipher")
+                .long("cipher")
+                .value_names(["CIPHER", "TEXT"])
+                .num_args(2)
+                .help("Encrypt text with rot13, caesar:N, vigenere:KEY or atbash (--decrypt to reverse)")
+                .long_help("Encrypt TEXT with a classical cipher: rot13, caesar:N (shift by N), vigenere:KEY or atbash. Add --decrypt after the text to reverse it. Plain caesar without a shift tries all 25 and lists them with the most English-looking first; that ranking needs a sentence or so of text, so check the other candidates for a word or two. Only the letters A-Z change; case, digits and punctuation are kept. For puzzles and CTFs, not for secrets.")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(values) = matches.get_many::<String>("cipher") {
+            let values: Vec<&String> = values.collect();
+            let (cipher, text) = (parse_cipher(values[0])?, values[1]);
+            let decrypt = match matches.get_one::<String>("decrypt").map(String::as_str) {
+                Some("") => true,
+                Some(_) => return Err("--decrypt takes no value with --cipher; put it after the text".into()),
+                None => false,
+            };
+            match cipher {
+                Cipher::Caesar(None) => {
+                    for (rank, (shift, candidate)) in brute_force_caesar(text).into_iter().enumerate() {
+                        let label = format!("caesar:{}", shift);
+                        if rank == 0 {
+                            println!("{:<11}{}", label.cyan(), candidate.bold());
+                        } else {
+                            println!("{:<11}{}", label.cyan(), candidate);
+                        }
+                    }
+                }
+                cipher => println!("{}", apply(&cipher, text, decrypt)),
+            }
+        }
+        Ok(())
+    }
+}
+
+// "rot13", "caesar:3", "caesar" (brute force), "vigenere:lemon", "atbash"
+fn parse_cipher(spec: &str) -> Result<Cipher, String> {
+    let (name, argument) = match spec.split_once(':') {
+        Some((name, argument)) => (name, Some(argument)),
+        None => (spec, None),
+    };
+    match (name.to_lowercase().as_str(), argument) {
+        ("rot13", None) => Ok(Cipher::Rot13),
+        ("atbash", None) => Ok(Cipher::Atbash),
+        ("caesar", None) => Ok(Cipher::Caesar(None)),
+        ("caesar", Some(shift)) => {
+            let shift: i64 = shift.trim().parse().map_err(|_| format!("Invalid Caesar shift '{}'; use a number like caesar:3", shift))?;
+            Ok(Cipher::Caesar(Some(shift.rem_euclid(26) as u8)))
+        }
+        ("vigenere" | "vigenère", Some(key)) => {
+            if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(format!("Invalid Vigenère key '{}'; use letters only", key));
+            }
+            Ok(Cipher::Vigenere(key.to_ascii_uppercase().bytes().map(|byte| byte - b'A').collect()))
+        }
+        ("vigenere" | "vigenère", None) => Err("Vigenère needs a key, e.g. vigenere:LEMON".to_string()),
+        _ => Err(format!("Unknown cipher '{}'. Use rot13, caesar:N, vigenere:KEY or atbash", spec)),
+    }
+}
+
+// Shifts ASCII letters, keeping their case; `shifts` gives the shift for each successive letter
+fn shift_letters(text: &str, mut shifts: impl FnMut() -> u8) -> String {
+    text.chars()
+        .map(|c| {
+            if !c.is_ascii_alphabetic() {
+                return c;
+            }
+            let base = if c.is_ascii_uppercase() { b'A' } else { b'a' };
+            ((c as u8 - base + shifts()) % 26 + base) as char
+        })
+        .collect()
+}
+
+fn apply(cipher: &Cipher, text: &str, decrypt: bool) -> String {
+    let reverse = |shift: u8| if decrypt { (26 - shift) % 26 } else { shift };
+    match cipher {
+        Cipher::Rot13 => shift_letters(text, || 13),
+        Cipher::Caesar(shift) => {
+            let shift = reverse(shift.unwrap_or(0));
+            shift_letters(text, || shift)
+        }
+        // The key only advances on letters, as in the classic pen-and-paper method
+        Cipher::Vigenere(key) => {
+            let mut position = 0;
+            shift_letters(text, || {
+                let shift = reverse(key[position % key.len()]);
+                position += 1;
+                shift
+            })
+        }
+        Cipher::Atbash => text
+            .chars()
+            .map(|c| match c {
+                'a'..='z' => (b'z' - (c as u8 - b'a')) as char,
+                'A'..='Z' => (b'Z' - (c as u8 - b'A')) as char,
+                c => c,
+            })
+            .collect(),
+    }
+}
+
+// Log-likelihood of the letters under English frequencies, plus a bonus per
+// common word; higher is more English-like. Needs a sentence or so of text:
+// on a word or two any shift can come out on top.
+fn english_score(text: &str) -> f64 {
+    let letters: f64 = text
+        .chars()
+        .filter(char::is_ascii_alphabetic)
+        .map(|c| (ENGLISH_FREQUENCIES[(c.to_ascii_uppercase() as u8 - b'A') as usize] / 100.0).ln())
+        .sum();
+    let words: f64 = text
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .map(str::to_ascii_lowercase)
+        .filter(|word| COMMON_WORDS.contains(&word.as_str()))
+        .map(|word| 3.0 * word.len() as f64)
+        .sum();
+    letters + words
+}
+
+// Every decryption as (shift, text), the most English-looking first
+fn brute_force_caesar(text: &str) -> Vec<(u8, String)> {
+    let mut candidates: Vec<(u8, String, f64)> = (1..26)
+        .map(|shift| {
+            let candidate = apply(&Cipher::Caesar(Some(shift)), text, true);
+            let score = english_score(&candidate);
+            (shift, candidate, score)
+        })
+        .collect();
+    candidates.sort_by(|a, b| b.2.total_cmp(&a.2));
+    candidates.into_iter().map(|(shift, candidate, _)| (shift, candidate)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cipher() {
+        assert_eq!(parse_cipher("ROT13").unwrap(), Cipher::Rot13);
+        assert_eq!(parse_cipher("caesar:3").unwrap(), Cipher::Caesar(Some(3)));
+        assert_eq!(parse_cipher("caesar:-1").unwrap(), Cipher::Caesar(Some(25)));
+        assert_eq!(parse_cipher("caesar").unwrap(), Cipher::Caesar(None));
+        assert_eq!(parse_cipher("vigenere:Key").unwrap(), Cipher::Vigenere(vec![10, 4, 24]));
+        assert!(parse_cipher("vigenere:k3y").is_err());
+        assert!(parse_cipher("vigenere").is_err());
+        assert!(parse_cipher("enigma").is_err());
+    }
+
+    #[test]
+    fn test_ciphers() {
+        assert_eq!(apply(&Cipher::Rot13, "Hello, World!", false), "Uryyb, Jbeyq!");
+        assert_eq!(apply(&Cipher::Rot13, "Uryyb, Jbeyq!", true), "Hello, World!");
+        assert_eq!(apply(&Cipher::Caesar(Some(3)), "xyz ABC", false), "abc DEF");
+        assert_eq!(apply(&Cipher::Caesar(Some(3)), "abc DEF", true), "xyz ABC");
+        assert_eq!(apply(&Cipher::Atbash, "Hello", false), "Svool");
+        assert_eq!(apply(&Cipher::Atbash, "Svool", true), "Hello");
+        // Non-ASCII letters pass through untouched
+        assert_eq!(apply(&Cipher::Caesar(Some(1)), "café", false), "dbgé");
+    }
+
+    #[test]
+    fn test_vigenere() {
+        let key = parse_cipher("vigenere:LEMON").unwrap();
+        assert_eq!(apply(&key, "ATTACKATDAWN", false), "LXFOPVEFRNHR");
+        assert_eq!(apply(&key, "Attack at dawn!", false), "Lxfopv ef rnhr!");
+        assert_eq!(apply(&key, "Lxfopv ef rnhr!", true), "Attack at dawn!");
+    }
+
+    #[test]
+    fn test_brute_force_caesar() {
+        let ciphertext = apply(&Cipher::Caesar(Some(7)), "Meet me at the old harbour after sunset and bring the documents", false);
+        let candidates = brute_force_caesar(&ciphertext);
+        assert_eq!(candidates.len(), 25);
+        assert_eq!(candidates[0], (7, "Meet me at the old harbour after sunset and bring the documents".to_string()));
+    }
+
+    #[test]
+    fn test_brute_force_sentences() {
+        for (shift, plaintext) in [
+            (3, "The quick brown fox jumps over the lazy dog"),
+            (13, "Why did the chicken cross the road?"),
+            (19, "Never gonna give you up, never gonna let you down"),
+            (24, "Packing my box with five dozen liquor jugs"),
+        ] {
+            let ciphertext = apply(&Cipher::Caesar(Some(shift)), plaintext, false);
+            assert_eq!(brute_force_caesar(&ciphertext)[0], (shift, plaintext.to_string()));
+        }
+    }
+}
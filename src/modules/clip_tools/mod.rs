@@ -0,0 +1,248 @@
+use crate::config::data_dir;
+use crate::tool_module::ToolModule;
+use arboard::Clipboard;
+use chrono::{DateTime, Local, Utc};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io::{self, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+
+pub struct ClipToolsModule;
+
+// Oldest entries fall off the end; very large copies are not kept at all
+const MAX_ENTRIES: usize = 50;
+const MAX_ENTRY_BYTES: usize = 256 * 1024;
+const PREVIEW_CHARS: usize = 60;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct Entry {
+    text: String,
+    // Unix seconds
+    copied_at: i64,
+}
+
+impl ToolModule for ClipToolsModule {
+    fn name(&self) -> &'static str {
+        "clip-tools"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("clip-get")
+                .long("clip-get")
+                .action(ArgAction::SetTrue)
+                .help("Print the clipboard text to stdout")
+        )
+        .arg(
+            Arg::new("clip-set")
+                .long("clip-set")
+                .value_name("TEXT")
+                .num_args(0..=1)
+                .help("Copy TEXT, or stdin when none is given, to the clipboard")
+                .long_help("Copy TEXT to the clipboard, or everything read from stdin when no TEXT is given, e.g. git log -1 | ms --clip-set. The text is also added to --clip-history.")
+        )
+        .arg(
+            Arg::new("clip-history")
+                .long("clip-history")
+                .value_name("N|clear")
+                .num_args(0..=1)
+                .default_missing_value("")
+                .help("List recent --clip-set/--clip-get texts, copy entry N back, or clear the list")
+                .long_help(format!("Without a value, list the last {} texts that went through --clip-set or --clip-get, newest first as 1. With N, copy entry N back to the clipboard and print it. With clear, forget them all. The history is kept owner-readable only in clipboard-history.json under $MICRO_SWISS_DATA or the platform data directory (e.g. ~/.local/share/micro-swiss).", MAX_ENTRIES))
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if matches.get_flag("clip-get") {
+            let text = Clipboard::new().and_then(|mut clipboard| clipboard.get_text()).map_err(|e| format!("Failed to read the clipboard: {}", e))?;
+            remember(&text);
+            print!("{}", text);
+            // Keep the prompt on its own line without changing piped output
+            if io::stdout().is_terminal() && !text.ends_with('\n') {
+                println!();
+            }
+        }
+        if let Some(text) = clip_set_text(matches)? {
+            copy(&text)?;
+            remember(&text);
+            println!("✅ Copied {} characters to the clipboard", text.chars().count());
+        }
+        if let Some(value) = matches.get_one::<String>("clip-history") {
+            let path = history_path()?;
+            let mut history = load(&path)?;
+            match value.as_str() {
+                "" if history.is_empty() => println!("No clipboard history yet; --clip-set and --clip-get add to it"),
+                "" => {
+                    for (index, entry) in history.iter().enumerate() {
+                        let age = DateTime::from_timestamp(entry.copied_at, 0).map(|time| time.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string()).unwrap_or_default();
+                        println!("{:>3}  {}  {}", (index + 1).to_string().green().bold(), age.dimmed(), preview(&entry.text));
+                    }
+                }
+                "clear" => {
+                    save(&path, &[])?;
+                    println!("✅ Cleared {} clipboard history entries", history.len());
+                }
+                index => {
+                    let index: usize = index.parse().map_err(|_| format!("Invalid --clip-history value '{}'; use an entry number or clear", index))?;
+                    let entry = recall(&mut history, index)?;
+                    copy(&entry.text)?;
+                    save(&path, &history)?;
+                    print!("{}", entry.text);
+                    if !entry.text.ends_with('\n') {
+                        println!();
+                    }
+                    eprintln!("✅ Copied entry {} to the clipboard", index);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn clip_set_text(matches: &ArgMatches) -> Result<Option<String>, Box<dyn Error>> {
+    if !matches.contains_id("clip-set") {
+        return Ok(None);
+    }
+    match matches.get_one::<String>("clip-set") {
+        Some(text) => Ok(Some(text.clone())),
+        None => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text)?;
+            Ok(Some(text))
+        }
+    }
+}
+
+fn copy(text: &str) -> Result<(), String> {
+    Clipboard::new().and_then(|mut clipboard| clipboard.set_text(text)).map_err(|e| format!("Failed to copy to the clipboard: {}", e))
+}
+
+fn history_path() -> Result<PathBuf, String> {
+    data_dir().map(|dir| dir.join("clipboard-history.json")).ok_or_else(|| "Could not determine a data directory; set MICRO_SWISS_DATA".to_string())
+}
+
+fn load(path: &Path) -> Result<Vec<Entry>, String> {
+    match fs::read_to_string(path) {
+        Ok(text) => serde_json::from_str(&text).map_err(|e| format!("Corrupt clipboard history {}: {}", path.display(), e)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+// Written to a temporary file and renamed into place, owner-only on unix
+fn save(path: &Path, history: &[Entry]) -> Result<(), String> {
+    let fail = |e: io::Error| format!("Failed to write {}: {}", path.display(), e);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(fail)?;
+    }
+    let temporary = path.with_extension("json.tmp");
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&temporary).map_err(fail)?;
+    file.write_all(serde_json::to_string_pretty(history).map_err(|e| e.to_string())?.as_bytes()).map_err(fail)?;
+    drop(file);
+    fs::rename(&temporary, path).map_err(fail)
+}
+
+// The history is a convenience; failing to keep it never fails the copy itself
+fn remember(text: &str) {
+    if let Err(e) = record(text) {
+        eprintln!("Warning: {}", e);
+    }
+}
+
+fn record(text: &str) -> Result<(), String> {
+    let path = history_path()?;
+    let mut history = load(&path)?;
+    if push(&mut history, text, Utc::now().timestamp()) {
+        save(&path, &history)?;
+    }
+    Ok(())
+}
+
+// Newest first; an existing copy of the same text moves to the top instead of repeating.
+// Returns whether the history changed.
+fn push(history: &mut Vec<Entry>, text: &str, now: i64) -> bool {
+    if text.trim().is_empty() || text.len() > MAX_ENTRY_BYTES {
+        return false;
+    }
+    history.retain(|entry| entry.text != text);
+    history.insert(0, Entry { text: text.to_string(), copied_at: now });
+    history.truncate(MAX_ENTRIES);
+    true
+}
+
+// Entry N (1 is the newest), which becomes the newest again
+fn recall(history: &mut Vec<Entry>, index: usize) -> Result<Entry, String> {
+    if index == 0 || index > history.len() {
+        return Err(format!("No clipboard history entry {}; there are {}", index, history.len()));
+    }
+    let mut entry = history.remove(index - 1);
+    entry.copied_at = Utc::now().timestamp();
+    history.insert(0, entry.clone());
+    Ok(entry)
+}
+
+pub fn preview(text: &str) -> String {
+    let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let lines = text.trim_end().lines().count();
+    let mut shown: String = flat.chars().take(PREVIEW_CHARS).collect();
+    if flat.chars().count() > PREVIEW_CHARS {
+        shown.push('…');
+    }
+    if lines > 1 {
+        shown.push_str(&format!(" ({} lines)", lines).dimmed().to_string());
+    }
+    shown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_and_recall() {
+        let mut history = Vec::new();
+        let now = 1_700_000_000;
+        assert!(push(&mut history, "first", now));
+        assert!(push(&mut history, "second", now));
+        assert!(!push(&mut history, "  \n", now));
+        assert!(push(&mut history, "first", now));
+        assert_eq!(history.iter().map(|entry| entry.text.as_str()).collect::<Vec<_>>(), ["first", "second"]);
+        for index in 0..MAX_ENTRIES + 5 {
+            push(&mut history, &index.to_string(), now);
+        }
+        assert_eq!(history.len(), MAX_ENTRIES);
+
+        assert_eq!(recall(&mut history, 3).unwrap().text, (MAX_ENTRIES + 2).to_string());
+        assert_eq!(history[0].text, (MAX_ENTRIES + 2).to_string());
+        assert!(recall(&mut history, 0).is_err());
+        assert!(recall(&mut history, MAX_ENTRIES + 1).is_err());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir().join(format!("ms-clip-{}", std::process::id())).join("clipboard-history.json");
+        assert_eq!(load(&path).unwrap(), Vec::new());
+        let mut history = Vec::new();
+        push(&mut history, "hello\nworld", 1_700_000_000);
+        save(&path, &history).unwrap();
+        assert_eq!(load(&path).unwrap(), history);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_preview() {
+        assert_eq!(preview("short text"), "short text");
+        assert!(preview(&"x".repeat(100)).ends_with('…'));
+        assert!(preview("fn main() {\n    run();\n}\n").starts_with("fn main() { run(); } ("));
+    }
+}
@@ -0,0 +1,196 @@
+use crate::cmd_watch::shell;
+use crate::num_stats::percentile;
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use std::error::Error;
+use std::io::{self, IsTerminal, Write};
+use std::process::Stdio;
+use std::time::Instant;
+
+pub struct CmdBenchModule;
+
+const DEFAULT_RUNS: u32 = 10;
+// Runs whose modified z-score (distance from the median in median absolute deviations) exceeds this are outliers
+const OUTLIER_SCORE: f64 = 14.0;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Timing {
+    // Seconds
+    mean: f64,
+    stddev: f64,
+    min: f64,
+    max: f64,
+    median: f64,
+    outliers: usize,
+}
+
+impl ToolModule for CmdBenchModule {
+    fn name(&self) -> &'static str {
+        "cmd-bench"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("bench")
+                .long("bench")
+                .value_name("COMMAND")
+                .num_args(1..=2)
+                .help("Time a shell command over several runs, or compare two: --bench \"cmd a\" \"cmd b\"")
+                .long_help("Run each COMMAND through the shell --runs times (default: 10) after --warmup untimed runs, with its output discarded, and report the mean ± standard deviation, median, min and max wall time. Runs far slower or faster than the rest (by modified z-score) are flagged as outliers, which usually means something else was competing for the machine. With two commands, also report how many times faster the quicker one is. A failing run stops the benchmark.")
+        )
+        .arg(
+            Arg::new("runs")
+                .long("runs")
+                .value_name("N")
+                .value_parser(clap::value_parser!(u32).range(2..=100_000))
+                .help("Timed runs of each --bench command (default: 10)")
+                .requires("bench")
+        )
+        .arg(
+            Arg::new("warmup")
+                .long("warmup")
+                .value_name("N")
+                .value_parser(clap::value_parser!(u32).range(..=1000))
+                .help("Untimed runs before measuring each --bench command, to warm caches (default: 0)")
+                .requires("bench")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(commands) = matches.get_many::<String>("bench") {
+            let commands: Vec<&String> = commands.collect();
+            let runs = matches.get_one::<u32>("runs").copied().unwrap_or(DEFAULT_RUNS);
+            let warmup = matches.get_one::<u32>("warmup").copied().unwrap_or(0);
+            let live = io::stdout().is_terminal();
+            let mut results = Vec::new();
+            for command in &commands {
+                println!("{} {}", "Benchmark:".bold(), command);
+                for _ in 0..warmup {
+                    time_run(command)?;
+                }
+                let mut samples = Vec::new();
+                for run in 1..=runs {
+                    if live {
+                        print!("\r\x1B[2K  {}", format!("run {}/{}", run, runs).dimmed());
+                        io::stdout().flush()?;
+                    }
+                    samples.push(time_run(command)?);
+                }
+                if live {
+                    print!("\r\x1B[2K");
+                }
+                let timing = measure(&mut samples);
+                print!("{}", render(&timing, runs));
+                results.push(timing);
+                println!();
+            }
+            if let [first, second] = results.as_slice() {
+                println!("{}", compare(commands[0], first, commands[1], second));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Wall time of one run in seconds, with the command's output discarded
+fn time_run(command: &str) -> Result<f64, Box<dyn Error>> {
+    let started = Instant::now();
+    let status = shell(command).stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null()).status().map_err(|e| format!("Failed to start the shell: {}", e))?;
+    let elapsed = started.elapsed().as_secs_f64();
+    if !status.success() {
+        return Err(format!("'{}' failed with exit code {}; fix it or benchmark a command that succeeds", command, status.code().unwrap_or(1)).into());
+    }
+    Ok(elapsed)
+}
+
+fn measure(samples: &mut [f64]) -> Timing {
+    samples.sort_by(f64::total_cmp);
+    let count = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / count;
+    let stddev = if samples.len() > 1 { (samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (count - 1.0)).sqrt() } else { 0.0 };
+    let median = percentile(samples, 50.0);
+    let mut deviations: Vec<f64> = samples.iter().map(|s| (s - median).abs()).collect();
+    deviations.sort_by(f64::total_cmp);
+    let deviation = percentile(&deviations, 50.0);
+    Timing {
+        mean,
+        stddev,
+        min: samples[0],
+        max: samples[samples.len() - 1],
+        median,
+        outliers: if deviation > 0.0 { samples.iter().filter(|s| 0.6745 * (**s - median).abs() / deviation > OUTLIER_SCORE).count() } else { 0 },
+    }
+}
+
+// Milliseconds below a second, seconds above
+fn format_seconds(seconds: f64) -> String {
+    if seconds < 1.0 {
+        format!("{:.1} ms", seconds * 1000.0)
+    } else {
+        format!("{:.3} s", seconds)
+    }
+}
+
+fn render(timing: &Timing, runs: u32) -> String {
+    let mut output = format!(
+        "  Time (mean ± σ):   {} ± {}\n  Median:            {}\n  Range (min … max): {} … {}    {} runs\n",
+        format_seconds(timing.mean).green().bold(),
+        format_seconds(timing.stddev).green(),
+        format_seconds(timing.median),
+        format_seconds(timing.min).cyan(),
+        format_seconds(timing.max).purple(),
+        runs
+    );
+    if timing.outliers > 0 {
+        output.push_str(&format!(
+            "  ⚠️ {} outlier{} detected; close other programs or add --warmup runs for steadier numbers\n",
+            timing.outliers,
+            if timing.outliers == 1 { "" } else { "s" }
+        ));
+    }
+    output
+}
+
+// How many times faster the quicker command is, with the uncertainty carried through from both
+fn compare(first_command: &str, first: &Timing, second_command: &str, second: &Timing) -> String {
+    let ((fast_command, fast), (slow_command, slow)) = if first.mean <= second.mean { ((first_command, first), (second_command, second)) } else { ((second_command, second), (first_command, first)) };
+    let ratio = slow.mean / fast.mean;
+    let error = ratio * ((fast.stddev / fast.mean).powi(2) + (slow.stddev / slow.mean).powi(2)).sqrt();
+    format!("{}\n  '{}' ran {} ± {:.2} times faster than '{}'", "Summary".bold(), fast_command, format!("{:.2}", ratio).green().bold(), error, slow_command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(mean: f64, stddev: f64) -> Timing {
+        Timing { mean, stddev, min: mean, max: mean, median: mean, outliers: 0 }
+    }
+
+    #[test]
+    fn test_measure() {
+        let mut samples = [0.12, 0.10, 0.11, 0.10, 0.13, 0.11, 0.95];
+        let timing = measure(&mut samples);
+        assert_eq!((timing.min, timing.max, timing.median), (0.10, 0.95, 0.11));
+        assert!((timing.mean - 0.2314).abs() < 1e-3);
+        assert_eq!(timing.outliers, 1);
+        assert_eq!(measure(&mut [0.2, 0.2, 0.2]).outliers, 0);
+    }
+
+    #[test]
+    fn test_format_and_compare() {
+        assert_eq!(format_seconds(0.01234), "12.3 ms");
+        assert_eq!(format_seconds(2.5), "2.500 s");
+        let summary = compare("slow", &timing(0.3, 0.03), "fast", &timing(0.1, 0.01));
+        assert!(summary.contains("'fast' ran"));
+        assert!(summary.contains("3.00"));
+        assert!(summary.contains("± 0.42 times faster than 'slow'"));
+    }
+
+    #[test]
+    fn test_time_run() {
+        assert!(time_run("exit 0").unwrap() >= 0.0);
+        assert!(time_run("exit 3").unwrap_err().to_string().contains("exit code 3"));
+    }
+}
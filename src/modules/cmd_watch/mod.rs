@@ -0,0 +1,230 @@
+use crate::tool_module::ToolModule;
+use chrono::Local;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use colored::*;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use std::error::Error;
+use std::io::{self, Write};
+use std::path::{Component, PathBuf};
+use std::process::{self, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub struct CmdWatchModule;
+
+const DEFAULT_INTERVAL: f64 = 2.0;
+// Editors save in bursts of events; wait for quiet before re-running
+const SETTLE: Duration = Duration::from_millis(200);
+
+// The watcher has to stay alive for its events to keep arriving
+type Changes = (notify::RecommendedWatcher, mpsc::Receiver<notify::Result<Event>>);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Outcome {
+    code: i32,
+    elapsed: Duration,
+}
+
+impl Outcome {
+    fn success(&self) -> bool {
+        self.code == 0
+    }
+}
+
+impl ToolModule for CmdWatchModule {
+    fn name(&self) -> &'static str {
+        "cmd-watch"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("watch-cmd")
+                .long("watch-cmd")
+                .value_name("COMMAND")
+                .help("Re-run a shell COMMAND every 2 seconds or on file changes, showing its exit status and duration")
+                .long_help("Re-run a shell COMMAND every 2 seconds (--interval SECS) or whenever files under --path DIR change, clearing the screen and showing its exit status and duration each time, e.g. --watch-cmd \"cargo test\" --path src. Add --notify for a desktop notification when the command starts or stops failing. To re-run a file on changes, use --run FILE --watch.")
+                .conflicts_with("run")
+        )
+        .arg(
+            Arg::new("interval")
+                .long("interval")
+                .value_name("SECS")
+                .value_parser(clap::value_parser!(f64))
+                .help("Seconds between runs of a --watch-cmd COMMAND (default: 2)")
+                .requires("watch-cmd")
+        )
+        .arg(
+            Arg::new("path")
+                .long("path")
+                .value_name("DIR")
+                .action(ArgAction::Append)
+                .help("Re-run a --watch-cmd COMMAND when anything under DIR changes instead of on a timer (repeatable)")
+                .requires("watch-cmd")
+        )
+        .arg(
+            Arg::new("notify")
+                .long("notify")
+                .action(ArgAction::SetTrue)
+                .help("Send a desktop notification when a --watch-cmd COMMAND starts or stops failing, or a --timer or --pomodoro phase ends")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let Some(command) = matches.get_one::<String>("watch-cmd") else {
+            return Ok(());
+        };
+        if command.trim().is_empty() {
+            return Err("--watch-cmd needs a command to run".into());
+        }
+        let paths: Vec<PathBuf> = matches.get_many::<String>("path").unwrap_or_default().map(PathBuf::from).collect();
+        let interval = match matches.get_one::<f64>("interval") {
+            Some(_) if !paths.is_empty() => return Err("Use either --interval or --path with --watch-cmd, not both".into()),
+            Some(secs) if *secs > 0.0 && secs.is_finite() => Duration::from_secs_f64(*secs),
+            Some(_) => return Err("--interval must be a positive number of seconds".into()),
+            None => Duration::from_secs_f64(DEFAULT_INTERVAL),
+        };
+        let notify = matches.get_flag("notify");
+        let trigger = if paths.is_empty() { format!("Every {:.1}s", interval.as_secs_f64()) } else { format!("On changes in {}", paths.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join(", ")) };
+
+        let changes = if paths.is_empty() { None } else { Some(watch_paths(&paths)?) };
+        let mut previous: Option<Outcome> = None;
+        loop {
+            print!("\x1B[2J\x1B[H");
+            println!("{}", format!("{}: {}    [{}]", trigger, command, Local::now().format("%H:%M:%S")).dimmed());
+            println!();
+            io::stdout().flush()?;
+            let outcome = run(command)?;
+            println!();
+            println!("{}", status_line(&outcome));
+            if notify {
+                if let Some(message) = change_message(previous.as_ref(), &outcome, command) {
+                    notify_desktop("micro-swiss --watch-cmd", &message);
+                }
+            }
+            previous = Some(outcome);
+            match &changes {
+                Some((_watcher, events)) => {
+                    println!("{}", "Watching for changes... (Ctrl-C to stop)".dimmed());
+                    wait_for_change(events)?;
+                }
+                None => thread::sleep(interval),
+            }
+        }
+    }
+}
+
+pub fn shell(command: &str) -> process::Command {
+    if cfg!(windows) {
+        let mut shell = process::Command::new("cmd");
+        shell.arg("/C").arg(command);
+        shell
+    } else {
+        let mut shell = process::Command::new("sh");
+        shell.arg("-c").arg(command);
+        shell
+    }
+}
+
+fn run(command: &str) -> Result<Outcome, Box<dyn Error>> {
+    let started = Instant::now();
+    let status = shell(command).stdin(Stdio::null()).status().map_err(|e| format!("Failed to start the shell: {}", e))?;
+    Ok(Outcome { code: status.code().unwrap_or(1), elapsed: started.elapsed() })
+}
+
+fn status_line(outcome: &Outcome) -> String {
+    let seconds = outcome.elapsed.as_secs_f64();
+    if outcome.success() {
+        format!("✅ {} in {:.2}s", "exit 0".green().bold(), seconds)
+    } else {
+        format!("❌ {} in {:.2}s", format!("exit {}", outcome.code).red().bold(), seconds)
+    }
+}
+
+// Only flips between passing and failing are worth interrupting for
+fn change_message(previous: Option<&Outcome>, current: &Outcome, command: &str) -> Option<String> {
+    match previous {
+        Some(previous) if previous.success() == current.success() => None,
+        None if current.success() => None,
+        _ if current.success() => Some(format!("✅ {} passes again", command)),
+        _ => Some(format!("❌ {} failed with exit code {}", command, current.code)),
+    }
+}
+
+fn watch_paths(paths: &[PathBuf]) -> Result<Changes, Box<dyn Error>> {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    for path in paths {
+        watcher.watch(path, RecursiveMode::Recursive).map_err(|e| format!("Cannot watch {}: {}", path.display(), e))?;
+    }
+    Ok((watcher, rx))
+}
+
+fn wait_for_change(events: &mpsc::Receiver<notify::Result<Event>>) -> Result<(), Box<dyn Error>> {
+    // Drop whatever the last run itself caused before waiting
+    while events.try_recv().is_ok() {}
+    loop {
+        if is_relevant_change(&events.recv()?) {
+            break;
+        }
+    }
+    while events.recv_timeout(SETTLE).is_ok() {}
+    Ok(())
+}
+
+fn is_relevant_change(event: &notify::Result<Event>) -> bool {
+    let Ok(event) = event else {
+        return false;
+    };
+    !matches!(event.kind, EventKind::Access(_)) && event.paths.iter().any(|path| !path.components().any(|component| component == Component::Normal(".git".as_ref())))
+}
+
+// Best effort through the platform's notifier; falls back to the terminal bell
+pub fn notify_desktop(title: &str, message: &str) {
+    let sent = if cfg!(target_os = "macos") {
+        let script = format!("display notification {:?} with title {:?}", message, title);
+        process::Command::new("osascript").arg("-e").arg(script).stdout(Stdio::null()).stderr(Stdio::null()).status().is_ok_and(|status| status.success())
+    } else if cfg!(windows) {
+        let script = format!(
+            "[void][System.Reflection.Assembly]::LoadWithPartialName('System.Windows.Forms'); $n = New-Object System.Windows.Forms.NotifyIcon; $n.Icon = [System.Drawing.SystemIcons]::Information; $n.Visible = $true; $n.ShowBalloonTip(5000, '{}', '{}', 'None'); Start-Sleep -Seconds 5",
+            title.replace('\'', "''"),
+            message.replace('\'', "''")
+        );
+        // The balloon only shows while PowerShell runs, so don't wait for it
+        process::Command::new("powershell").args(["-NoProfile", "-Command", &script]).stdout(Stdio::null()).stderr(Stdio::null()).spawn().is_ok()
+    } else {
+        process::Command::new("notify-send").arg(title).arg(message).stdout(Stdio::null()).stderr(Stdio::null()).status().is_ok_and(|status| status.success())
+    };
+    if !sent {
+        print!("\x07");
+        let _ = io::stdout().flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(code: i32) -> Outcome {
+        Outcome { code, elapsed: Duration::from_millis(1500) }
+    }
+
+    #[test]
+    fn test_run() {
+        let passed = run("exit 0").unwrap();
+        assert!(passed.success());
+        assert_eq!(run("exit 3").unwrap().code, 3);
+        assert!(status_line(&outcome(0)).starts_with("✅"));
+        assert!(status_line(&outcome(101)).contains("exit 101"));
+        assert!(status_line(&outcome(101)).ends_with(" in 1.50s"));
+    }
+
+    #[test]
+    fn test_change_message() {
+        assert_eq!(change_message(None, &outcome(0), "make"), None);
+        assert_eq!(change_message(None, &outcome(2), "make").unwrap(), "❌ make failed with exit code 2");
+        assert_eq!(change_message(Some(&outcome(1)), &outcome(2), "make"), None);
+        assert_eq!(change_message(Some(&outcome(1)), &outcome(0), "make").unwrap(), "✅ make passes again");
+        assert_eq!(change_message(Some(&outcome(0)), &outcome(0), "make"), None);
+    }
+}
@@ -46,26 +46,26 @@ impl ToolModule for ColorConvertModule {
 }
 
 #[derive(Debug, Clone, Copy)]
-struct Color {
-    r: u8,
-    g: u8,
-    b: u8,
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
 }
 
 impl Color {
-    fn new(r: u8, g: u8, b: u8) -> Self {
+    pub fn new(r: u8, g: u8, b: u8) -> Self {
         Self { r, g, b }
     }
     
-    fn to_hex(&self) -> String {
+    pub fn to_hex(self) -> String {
         format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
     }
     
-    fn to_rgb_string(&self) -> String {
+    pub fn to_rgb_string(self) -> String {
         format!("rgb({},{},{})", self.r, self.g, self.b)
     }
     
-    fn to_hsl(&self) -> (f32, f32, f32) {
+    fn to_hsl(self) -> (f32, f32, f32) {
         let r = self.r as f32 / 255.0;
         let g = self.g as f32 / 255.0;
         let b = self.b as f32 / 255.0;
@@ -100,7 +100,7 @@ impl Color {
         (h, s * 100.0, l * 100.0)
     }
     
-    fn to_hsl_string(&self) -> String {
+    pub fn to_hsl_string(self) -> String {
         let (h, s, l) = self.to_hsl();
         format!("hsl({:.0},{:.0}%,{:.0}%)", h, s, l)
     }
@@ -136,7 +136,7 @@ impl Color {
     }
 }
 
-fn parse_color(color_str: &str) -> Result<Color, Box<dyn Error>> {
+pub fn parse_color(color_str: &str) -> Result<Color, Box<dyn Error>> {
     let color_str = color_str.trim();
     
     // Try hex format
@@ -0,0 +1,328 @@
+use crate::file_size::format_bytes;
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgMatches, Command};
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, IsTerminal, Read, Write};
+use std::path::Path;
+use std::time::Instant;
+
+pub struct CompressModule;
+
+const BUFFER: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Algorithm {
+    Gzip,
+    Zstd,
+    Brotli,
+    Xz,
+}
+
+const ALGORITHMS: [Algorithm; 4] = [Algorithm::Gzip, Algorithm::Zstd, Algorithm::Brotli, Algorithm::Xz];
+
+impl Algorithm {
+    fn name(self) -> &'static str {
+        match self {
+            Algorithm::Gzip => "gzip",
+            Algorithm::Zstd => "zstd",
+            Algorithm::Brotli => "brotli",
+            Algorithm::Xz => "xz",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Algorithm::Gzip => "gz",
+            Algorithm::Zstd => "zst",
+            Algorithm::Brotli => "br",
+            Algorithm::Xz => "xz",
+        }
+    }
+
+    // The levels each format accepts, and the one its own command-line tool uses
+    fn levels(self) -> (u32, u32, u32) {
+        match self {
+            Algorithm::Gzip => (0, 9, 6),
+            Algorithm::Zstd => (1, 22, 3),
+            Algorithm::Brotli => (0, 11, 11),
+            Algorithm::Xz => (0, 9, 6),
+        }
+    }
+
+    fn parse(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "gzip" | "gz" => Ok(Algorithm::Gzip),
+            "zstd" | "zst" => Ok(Algorithm::Zstd),
+            "brotli" | "br" => Ok(Algorithm::Brotli),
+            "xz" | "lzma" => Ok(Algorithm::Xz),
+            _ => Err(format!("Unknown algorithm '{}'; use gzip, zstd, brotli or xz", name)),
+        }
+    }
+
+    // Brotli streams have no magic number, so they are only recognised by the .br name
+    fn detect(header: &[u8], path: &str) -> Option<Self> {
+        match header {
+            [0x1f, 0x8b, ..] => Some(Algorithm::Gzip),
+            [0x28, 0xb5, 0x2f, 0xfd, ..] => Some(Algorithm::Zstd),
+            [0xfd, b'7', b'z', b'X', b'Z', 0x00, ..] => Some(Algorithm::Xz),
+            _ => ALGORITHMS.into_iter().find(|algorithm| path.to_lowercase().ends_with(&format!(".{}", algorithm.extension()))),
+        }
+    }
+}
+
+impl ToolModule for CompressModule {
+    fn name(&self) -> &'static str {
+        "compress"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("compress")
+                .long("compress")
+                .value_names(["FILE", "ALGORITHM"])
+                .num_args(1..=2)
+                .help("Compress FILE with gzip (default), zstd, brotli or xz and show the size and ratio")
+                .long_help("Compress FILE into FILE.gz, FILE.zst, FILE.br or FILE.xz, streaming so large files never sit in memory, then print the sizes, the ratio and the time taken. ALGORITHM is gzip (default), zstd, brotli or xz; pick the level with --level. With - as FILE, stdin is compressed to stdout and the summary goes to stderr.")
+        )
+        .arg(
+            Arg::new("decompress")
+                .long("decompress")
+                .value_name("FILE")
+                .help("Decompress a gzip, zstd, brotli or xz FILE next to itself (- for stdin to stdout)")
+                .long_help("Decompress FILE, recognising gzip, zstd and xz by their contents and brotli by a .br name, into FILE without its extension (or FILE.out when it has none). Existing files are never overwritten. With - as FILE, stdin is decompressed to stdout (gzip, zstd and xz only, as brotli has no header to recognise).")
+        )
+        .arg(
+            Arg::new("level")
+                .long("level")
+                .value_name("N")
+                .value_parser(clap::value_parser!(u32))
+                .help("Compression level for --compress: gzip and xz 0-9, zstd 1-22, brotli 0-11")
+                .requires("compress")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(values) = matches.get_many::<String>("compress") {
+            let values: Vec<&String> = values.collect();
+            let algorithm = values.get(1).map(|name| Algorithm::parse(name)).transpose()?.unwrap_or(Algorithm::Gzip);
+            let (min, max, default) = algorithm.levels();
+            let level = matches.get_one::<u32>("level").copied().unwrap_or(default);
+            if !(min..=max).contains(&level) {
+                return Err(format!("{} levels run from {} to {}", algorithm.name(), min, max).into());
+            }
+            let input = values[0].as_str();
+            let output = if input == "-" { input.to_string() } else { format!("{}.{}", input, algorithm.extension()) };
+            let started = Instant::now();
+            let (read, written) = if input == "-" {
+                refuse_terminal()?;
+                let mut counted = Counted::new(BufWriter::new(io::stdout().lock()));
+                let read = compress(algorithm, level, io::stdin().lock(), &mut counted)?;
+                (read, counted.count)
+            } else {
+                let source = open(input)?;
+                let mut counted = Counted::new(create(&output)?);
+                let read = compress(algorithm, level, source, &mut counted)?;
+                (read, counted.count)
+            };
+            let label = format!("{} level {}", algorithm.name(), level);
+            summary(input, &output, read, written, &label, true, started);
+        }
+        if let Some(input) = matches.get_one::<String>("decompress") {
+            let started = Instant::now();
+            if input == "-" {
+                let mut reader = BufReader::with_capacity(BUFFER, io::stdin().lock());
+                let algorithm = Algorithm::detect(reader.fill_buf()?, "").ok_or("Unrecognised input on stdin; gzip, zstd and xz can be piped, brotli needs a .br file")?;
+                refuse_terminal()?;
+                let mut counted = Counted::new(reader);
+                let written = decompress(algorithm, &mut counted, BufWriter::new(io::stdout().lock()))?;
+                summary(input, "-", counted.count, written, algorithm.name(), false, started);
+            } else {
+                let mut reader = open(input)?;
+                let algorithm = Algorithm::detect(reader.fill_buf()?, input).ok_or_else(|| format!("'{}' is not gzip, zstd or xz data and has no .br extension", input))?;
+                let output = decompressed_name(input, algorithm);
+                let read = fs::metadata(input)?.len();
+                let written = decompress(algorithm, reader, create(&output)?)?;
+                summary(input, &output, read, written, algorithm.name(), false, started);
+            }
+        }
+        Ok(())
+    }
+}
+
+// Counts the bytes that pass through, for input and output sizes of streams
+struct Counted<T> {
+    inner: T,
+    count: u64,
+}
+
+impl<T> Counted<T> {
+    fn new(inner: T) -> Self {
+        Counted { inner, count: 0 }
+    }
+}
+
+impl<W: Write> Write for Counted<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.count += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<R: Read> Read for Counted<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.count += read as u64;
+        Ok(read)
+    }
+}
+
+impl<R: BufRead> BufRead for Counted<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.count += amount as u64;
+        self.inner.consume(amount)
+    }
+}
+
+fn open(path: &str) -> Result<BufReader<File>, String> {
+    File::open(path).map(|file| BufReader::with_capacity(BUFFER, file)).map_err(|e| format!("Failed to open '{}': {}", path, e))
+}
+
+fn create(path: &str) -> Result<BufWriter<File>, String> {
+    if Path::new(path).exists() {
+        return Err(format!("{} already exists; not overwriting it", path));
+    }
+    File::create(path).map(|file| BufWriter::with_capacity(BUFFER, file)).map_err(|e| format!("Failed to create {}: {}", path, e))
+}
+
+fn refuse_terminal() -> Result<(), String> {
+    if io::stdout().is_terminal() {
+        return Err("Refusing to write binary data to the terminal; redirect stdout".to_string());
+    }
+    Ok(())
+}
+
+// Returns the number of bytes read; the writer is flushed before returning
+fn compress<R: Read, W: Write>(algorithm: Algorithm, level: u32, mut input: R, output: W) -> io::Result<u64> {
+    let read;
+    let mut output = match algorithm {
+        Algorithm::Gzip => {
+            let mut encoder = GzEncoder::new(output, flate2::Compression::new(level));
+            read = io::copy(&mut input, &mut encoder)?;
+            encoder.finish()?
+        }
+        Algorithm::Zstd => {
+            let mut encoder = zstd::Encoder::new(output, level as i32)?;
+            read = io::copy(&mut input, &mut encoder)?;
+            encoder.finish()?
+        }
+        Algorithm::Brotli => {
+            let mut encoder = brotli::CompressorWriter::new(output, BUFFER, level, 22);
+            read = io::copy(&mut input, &mut encoder)?;
+            encoder.flush()?;
+            encoder.into_inner()
+        }
+        Algorithm::Xz => {
+            let mut encoder = xz2::write::XzEncoder::new(output, level);
+            read = io::copy(&mut input, &mut encoder)?;
+            encoder.finish()?
+        }
+    };
+    output.flush()?;
+    Ok(read)
+}
+
+// Returns the number of bytes written; concatenated gzip, zstd and xz streams are all decoded
+fn decompress<R: BufRead, W: Write>(algorithm: Algorithm, input: R, mut output: W) -> io::Result<u64> {
+    let mut decoder: Box<dyn Read + '_> = match algorithm {
+        Algorithm::Gzip => Box::new(MultiGzDecoder::new(input)),
+        Algorithm::Zstd => Box::new(zstd::Decoder::with_buffer(input)?),
+        Algorithm::Brotli => Box::new(brotli::Decompressor::new(input, BUFFER)),
+        Algorithm::Xz => Box::new(xz2::bufread::XzDecoder::new_multi_decoder(input)),
+    };
+    let written = io::copy(&mut decoder, &mut output)
+        .map_err(|e| io::Error::new(e.kind(), format!("Corrupt or truncated {} data: {}", algorithm.name(), e)))?;
+    output.flush()?;
+    Ok(written)
+}
+
+fn decompressed_name(path: &str, algorithm: Algorithm) -> String {
+    let lower = path.to_lowercase();
+    // Compressed tarballs keep their .tar
+    for (suffix, replacement) in [(".tgz", ".tar"), (".tzst", ".tar"), (".txz", ".tar")] {
+        if lower.ends_with(suffix) {
+            return format!("{}{}", &path[..path.len() - suffix.len()], replacement);
+        }
+    }
+    let extension = format!(".{}", algorithm.extension());
+    match lower.strip_suffix(&extension) {
+        Some(stem) if !stem.is_empty() && !stem.ends_with('/') => path[..stem.len()].to_string(),
+        _ => format!("{}.out", path),
+    }
+}
+
+fn ratio_line(read: u64, written: u64, compressing: bool) -> String {
+    let (original, compressed) = if compressing { (read, written) } else { (written, read) };
+    let percent = if original == 0 { 100.0 } else { compressed as f64 * 100.0 / original as f64 };
+    format!("{} → {} ({:.1}% of the original)", format_bytes(read), format_bytes(written), percent)
+}
+
+fn summary(input: &str, output: &str, read: u64, written: u64, label: &str, compressing: bool, started: Instant) {
+    let line = format!("✅ {} → {} with {}: {} in {:.2}s", input, output, label, ratio_line(read, written, compressing), started.elapsed().as_secs_f64());
+    // The data itself goes to stdout in a pipe
+    if input == "-" || output == "-" {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trips() {
+        let text = "The quick brown fox jumps over the lazy dog. ".repeat(200);
+        for algorithm in ALGORITHMS {
+            let mut compressed = Vec::new();
+            let read = compress(algorithm, algorithm.levels().2, text.as_bytes(), &mut compressed).unwrap();
+            assert_eq!(read, text.len() as u64);
+            assert!(compressed.len() < text.len() / 10, "{} barely compressed", algorithm.name());
+            assert_eq!(Algorithm::detect(&compressed, "x.br"), Some(algorithm));
+            let mut restored = Vec::new();
+            assert_eq!(decompress(algorithm, compressed.as_slice(), &mut restored).unwrap(), text.len() as u64);
+            assert_eq!(restored, text.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_corrupt_input() {
+        let mut compressed = Vec::new();
+        compress(Algorithm::Gzip, 6, "hello hello".as_bytes(), &mut compressed).unwrap();
+        compressed.truncate(compressed.len() - 4);
+        assert!(decompress(Algorithm::Gzip, compressed.as_slice(), io::sink()).unwrap_err().to_string().starts_with("Corrupt or truncated gzip"));
+        assert!(Algorithm::parse("rar").is_err());
+        assert_eq!(Algorithm::detect(b"plain", "notes.txt"), None);
+    }
+
+    #[test]
+    fn test_names_and_ratio() {
+        assert_eq!(decompressed_name("logs/app.log.gz", Algorithm::Gzip), "logs/app.log");
+        assert_eq!(decompressed_name("release.tgz", Algorithm::Gzip), "release.tar");
+        assert_eq!(decompressed_name("data.bin", Algorithm::Zstd), "data.bin.out");
+        assert_eq!(ratio_line(1000, 250, true), "1000 B → 250 B (25.0% of the original)");
+        assert_eq!(ratio_line(250, 1000, false), "250 B → 1000 B (25.0% of the original)");
+    }
+}
@@ -1,7 +1,20 @@
 use crate::tool_module::ToolModule;
 use arboard::Clipboard;
-use clap::{Arg, ArgMatches, Command};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use regex::Regex;
 use std::error::Error;
+use std::io::{self, Write};
+use std::process;
+
+pub const BRANCH_TYPES: [&str; 14] = [
+    "feature", "feat", "fix", "bugfix", "hotfix", "chore", "docs", "refactor", "test", "perf", "ci", "build", "style", "release",
+];
+
+pub const DEFAULT_BRANCH_FORMAT: &str = "{type}/{ticket}-{slug}";
+
+pub const DEFAULT_STOP_WORDS: [&str; 16] = [
+    "the", "a", "an", "with", "of", "and", "or", "to", "in", "on", "for", "at", "by", "from", "is", "are",
+];
 
 pub struct ConvertToBranchModule;
 
@@ -17,36 +30,281 @@ impl ToolModule for ConvertToBranchModule {
                 .long("generate-branch")
                 .value_name("STRING")
                 .help("Convert string to Git branch-friendly format (auto-copied to clipboard)")
-                .long_help("Convert any string to a Git branch-friendly format by converting to lowercase, replacing non-alphanumeric characters with dashes, collapsing multiple dashes, and removing leading/trailing dashes. Perfect for creating branch names from issue titles or feature descriptions. Result is automatically copied to the clipboard."),
+                .long_help("Convert any string to a Git branch-friendly format by converting to lowercase, replacing non-alphanumeric characters with dashes, collapsing multiple dashes, and removing leading/trailing dashes. Perfect for creating branch names from issue titles or feature descriptions. Result is automatically copied to the clipboard. Ticket IDs (JIRA-style ABC-123 or #456) are detected and kept in front of the name, and a leading type such as \"Fix:\" becomes the branch prefix."),
+        )
+        .arg(
+            Arg::new("type")
+                .long("type")
+                .value_name("TYPE")
+                .help("Branch type prefix (feature, fix, chore, ...)")
+                .long_help("Branch type used as the prefix, e.g. --type fix produces fix/<name>. Without it, a leading type in the input such as \"Fix:\" or \"feature:\" is used.")
+                .value_parser(BRANCH_TYPES)
+        )
+        .arg(
+            Arg::new("branch-format")
+                .long("branch-format")
+                .value_name("FORMAT")
+                .help("Branch name format using {type}, {ticket} and {slug} (default: {type}/{ticket}-{slug})")
+                .long_help("Branch name format using the {type}, {ticket} and {slug} placeholders. Separators around empty placeholders are dropped, so the default {type}/{ticket}-{slug} yields plain <slug> when there is no type or ticket. Example: --branch-format \"{ticket}/{type}-{slug}\"")
+        )
+        .arg(
+            Arg::new("max-length")
+                .long("max-length")
+                .value_name("N")
+                .help("Maximum branch name length, truncating at word boundaries")
+                .value_parser(clap::value_parser!(usize))
+        )
+        .arg(
+            Arg::new("strip-stop-words")
+                .long("strip-stop-words")
+                .help("Remove filler words (the, a, with, of, ...) from the branch name")
+                .action(ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("stop-words")
+                .long("stop-words")
+                .value_name("WORDS")
+                .help("Comma-separated stop-word list to remove (implies --strip-stop-words)")
+                .value_delimiter(',')
+        )
+        .arg(
+            Arg::new("keep-unicode")
+                .long("keep-unicode")
+                .help("Keep non-ASCII characters instead of transliterating them")
+                .long_help("Keep accented and non-Latin characters in the branch name. By default they are transliterated to ASCII (café → cafe, straße → strasse, кириллица → kirillitsa) since many CI systems and git hosts reject non-ASCII branch names.")
+                .action(ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("checkout")
+                .long("checkout")
+                .help("Create and switch to the generated branch with git switch -c")
+                .long_help("After generating the name, run git switch -c <name> in the current repository. Asks for confirmation first; if the branch already exists, offers to switch to it instead.")
+                .action(ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("yes")
+                .long("yes")
+                .help("Skip the confirmation prompt for --checkout")
+                .action(ArgAction::SetTrue)
         )
     }
 
     fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
         if let Some(text) = matches.get_one::<String>("generate-branch") {
-            let branch_name = convert_to_branch_name(text);
+            let options = BranchOptions {
+                branch_type: matches.get_one::<String>("type").cloned(),
+                format: matches.get_one::<String>("branch-format").cloned().unwrap_or_else(|| DEFAULT_BRANCH_FORMAT.to_string()),
+                max_length: matches.get_one::<usize>("max-length").copied(),
+                stop_words: match matches.get_many::<String>("stop-words") {
+                    Some(words) => words.map(|w| w.trim().to_lowercase()).collect(),
+                    None if matches.get_flag("strip-stop-words") => DEFAULT_STOP_WORDS.iter().map(|w| w.to_string()).collect(),
+                    None => Vec::new(),
+                },
+                transliterate: !matches.get_flag("keep-unicode"),
+            };
+            let branch_name = generate_branch_name(text, &options);
 
             // Copy to clipboard
             match Clipboard::new() {
                 Ok(mut clipboard) => {
                     if let Err(e) = clipboard.set_text(&branch_name) {
                         eprintln!("Warning: Failed to copy to clipboard: {}", e);
+                        println!("{}", branch_name);
                     } else {
                         println!("{} (copied to clipboard)", branch_name);
-                        return Ok(());
                     }
                 }
                 Err(e) => {
+                    // Fallback: just print if clipboard fails
                     eprintln!("Warning: Failed to access clipboard: {}", e);
+                    println!("{}", branch_name);
                 }
             }
 
-            // Fallback: just print if clipboard fails
-            println!("{}", branch_name);
+            if matches.get_flag("checkout") {
+                checkout_branch(&branch_name, matches.get_flag("yes"))?;
+            }
         }
         Ok(())
     }
 }
 
+fn git(args: &[&str]) -> Result<process::Output, Box<dyn Error>> {
+    process::Command::new("git")
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e).into())
+}
+
+fn branch_exists(name: &str) -> Result<bool, Box<dyn Error>> {
+    let output = git(&["rev-parse", "--verify", "--quiet", &format!("refs/heads/{}", name)])?;
+    Ok(output.status.success())
+}
+
+fn is_affirmative(answer: &str) -> bool {
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+fn confirm(prompt: &str) -> Result<bool, Box<dyn Error>> {
+    print!("{} [y/N] ", prompt);
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(is_affirmative(&answer))
+}
+
+// Creates the branch with `git switch -c`, or switches to it when it already exists
+fn checkout_branch(name: &str, assume_yes: bool) -> Result<(), Box<dyn Error>> {
+    if name.is_empty() {
+        return Err("Cannot check out an empty branch name".into());
+    }
+    if !git(&["rev-parse", "--is-inside-work-tree"])?.status.success() {
+        return Err("Not inside a git repository".into());
+    }
+    if !git(&["check-ref-format", "--branch", name])?.status.success() {
+        return Err(format!("'{}' is not a valid git branch name", name).into());
+    }
+
+    let exists = branch_exists(name)?;
+    let (prompt, args) = if exists {
+        (format!("Branch '{}' already exists. Switch to it?", name), vec!["switch", name])
+    } else {
+        (format!("Create and switch to branch '{}'?", name), vec!["switch", "-c", name])
+    };
+
+    if !assume_yes && !confirm(&prompt)? {
+        println!("Aborted");
+        return Ok(());
+    }
+
+    let status = process::Command::new("git")
+        .args(&args)
+        .status()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if !status.success() {
+        return Err(format!("git {} failed", args.join(" ")).into());
+    }
+    Ok(())
+}
+
+pub struct BranchOptions {
+    pub branch_type: Option<String>,
+    pub format: String,
+    pub max_length: Option<usize>,
+    pub stop_words: Vec<String>,
+    pub transliterate: bool,
+}
+
+impl Default for BranchOptions {
+    fn default() -> Self {
+        Self {
+            branch_type: None,
+            format: DEFAULT_BRANCH_FORMAT.to_string(),
+            max_length: None,
+            stop_words: Vec::new(),
+            transliterate: true,
+        }
+    }
+}
+
+pub fn generate_branch_name(input: &str, options: &BranchOptions) -> String {
+    let (detected_type, rest) = split_type_prefix(input);
+    let branch_type = options.branch_type.as_deref().or(detected_type).unwrap_or("");
+    let (ticket, rest) = extract_ticket_id(&rest);
+    let rest = if options.transliterate { deunicode::deunicode(&rest) } else { rest };
+    let mut words: Vec<String> = convert_to_branch_name(&rest).split('-').map(String::from).collect();
+    words = remove_stop_words(words, &options.stop_words);
+
+    let render = |slug: &str| {
+        let formatted = options
+            .format
+            .replace("{type}", branch_type)
+            .replace("{ticket}", ticket.as_deref().unwrap_or(""))
+            .replace("{slug}", slug);
+        clean_separators(&formatted)
+    };
+
+    let mut name = render(&words.join("-"));
+    if let Some(max_length) = options.max_length {
+        // Drop trailing words first, then cut hard if a single word is still too long
+        while name.chars().count() > max_length && words.len() > 1 {
+            words.pop();
+            name = render(&words.join("-"));
+        }
+        if name.chars().count() > max_length {
+            name = name.chars().take(max_length).collect::<String>();
+            name = name.trim_end_matches(['-', '/', '_', '.']).to_string();
+        }
+    }
+    name
+}
+
+// Keeps the original words when every one of them is a stop word
+fn remove_stop_words(words: Vec<String>, stop_words: &[String]) -> Vec<String> {
+    let kept: Vec<String> = words.iter().filter(|w| !stop_words.contains(w)).cloned().collect();
+    if kept.is_empty() {
+        words
+    } else {
+        kept
+    }
+}
+
+// Detects a leading "Type:" such as "Fix: broken login" and returns the
+// lowercased type along with the remaining text
+pub fn split_type_prefix(input: &str) -> (Option<&'static str>, String) {
+    if let Some((head, rest)) = input.trim_start().split_once(':') {
+        let head = head.trim().to_lowercase();
+        if let Some(branch_type) = BRANCH_TYPES.iter().find(|t| **t == head) {
+            return (Some(branch_type), rest.to_string());
+        }
+    }
+    (None, input.to_string())
+}
+
+// Finds the first JIRA-style (ABC-123) or GitHub-style (#456) ticket ID and
+// returns it along with the input with the ticket removed
+pub fn extract_ticket_id(input: &str) -> (Option<String>, String) {
+    let jira = Regex::new(r"\b[A-Z][A-Z0-9]+-\d+\b").unwrap();
+    if let Some(m) = jira.find(input) {
+        let rest = format!("{} {}", &input[..m.start()], &input[m.end()..]);
+        return (Some(m.as_str().to_string()), rest);
+    }
+
+    let issue = Regex::new(r"#(\d+)\b").unwrap();
+    if let Some(caps) = issue.captures(input) {
+        let m = caps.get(0).unwrap();
+        let rest = format!("{} {}", &input[..m.start()], &input[m.end()..]);
+        return (Some(caps[1].to_string()), rest);
+    }
+
+    (None, input.to_string())
+}
+
+// Drops separators left dangling by empty placeholders, e.g. "/-slug" or "fix/-"
+fn clean_separators(name: &str) -> String {
+    let mut result = String::new();
+    for c in name.chars() {
+        let is_separator = matches!(c, '-' | '/' | '_' | '.');
+        match result.chars().last() {
+            None if is_separator => continue,
+            Some('/') if is_separator => continue,
+            Some(last) if is_separator && matches!(last, '-' | '_' | '.') => {
+                if c == '/' {
+                    result.pop();
+                    if result.is_empty() {
+                        continue;
+                    }
+                } else {
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        result.push(c);
+    }
+    result.trim_end_matches(['-', '/', '_', '.']).to_string()
+}
+
 pub fn convert_to_branch_name(input: &str) -> String {
     let result: String = input
         .to_lowercase()
@@ -185,6 +443,102 @@ mod tests {
         assert_eq!(convert_to_branch_name("Fix: (urgent!!!) - handle & process data!!!"), "fix-urgent-handle-process-data");
     }
 
+    #[test]
+    fn test_generate_detects_type_and_ticket() {
+        let options = BranchOptions::default();
+        assert_eq!(generate_branch_name("Fix: ABC-123 broken login", &options), "fix/ABC-123-broken-login");
+        assert_eq!(generate_branch_name("Broken login #456", &options), "456-broken-login");
+        assert_eq!(generate_branch_name("Hello World", &options), "hello-world");
+        assert_eq!(generate_branch_name("Feature: Fix bug (urgent)!", &options), "feature/fix-bug-urgent");
+    }
+
+    #[test]
+    fn test_generate_explicit_type() {
+        let options = BranchOptions { branch_type: Some("chore".to_string()), ..Default::default() };
+        assert_eq!(generate_branch_name("Update deps PLAT-9", &options), "chore/PLAT-9-update-deps");
+        // An explicit type wins over the one in the input
+        assert_eq!(generate_branch_name("fix: typo", &options), "chore/typo");
+    }
+
+    #[test]
+    fn test_generate_custom_format() {
+        let options = BranchOptions { branch_type: Some("feature".to_string()), format: "{ticket}/{type}-{slug}".to_string(), ..Default::default() };
+        assert_eq!(generate_branch_name("ABC-1 add search", &options), "ABC-1/feature-add-search");
+        assert_eq!(generate_branch_name("add search", &options), "feature-add-search");
+
+        let options = BranchOptions { format: "users/me/{type}/{slug}".to_string(), ..Default::default() };
+        assert_eq!(generate_branch_name("add search", &options), "users/me/add-search");
+    }
+
+    #[test]
+    fn test_max_length_truncates_at_word_boundary() {
+        let options = BranchOptions { max_length: Some(20), ..Default::default() };
+        let name = generate_branch_name("Fix: ABC-123 login fails when password contains unicode", &options);
+        assert_eq!(name, "fix/ABC-123-login");
+
+        let options = BranchOptions { max_length: Some(5), ..Default::default() };
+        assert_eq!(generate_branch_name("internationalization", &options), "inter");
+
+        let options = BranchOptions { max_length: Some(100), ..Default::default() };
+        assert_eq!(generate_branch_name("short title", &options), "short-title");
+    }
+
+    #[test]
+    fn test_stop_word_removal() {
+        let defaults: Vec<String> = DEFAULT_STOP_WORDS.iter().map(|w| w.to_string()).collect();
+        let options = BranchOptions { stop_words: defaults.clone(), ..Default::default() };
+        assert_eq!(generate_branch_name("Fix the login page with a broken form", &options), "fix-login-page-broken-form");
+        assert_eq!(generate_branch_name("The A", &options), "the-a");
+
+        let options = BranchOptions { stop_words: vec!["please".to_string()], ..Default::default() };
+        assert_eq!(generate_branch_name("please fix the build", &options), "fix-the-build");
+
+        let options = BranchOptions { stop_words: defaults, max_length: Some(16), ..Default::default() };
+        assert_eq!(generate_branch_name("Add a button to the settings page", &options), "add-button");
+    }
+
+    #[test]
+    fn test_transliteration() {
+        let options = BranchOptions::default();
+        assert_eq!(generate_branch_name("café", &options), "cafe");
+        assert_eq!(generate_branch_name("Straße repariert", &options), "strasse-repariert");
+        assert_eq!(generate_branch_name("кириллица", &options), "kirillitsa");
+        assert_eq!(generate_branch_name("Fix: Łódź ÆRØ", &options), "fix/lodz-aero");
+
+        let options = BranchOptions { transliterate: false, ..Default::default() };
+        assert_eq!(generate_branch_name("naïve approach", &options), "naïve-approach");
+    }
+
+    #[test]
+    fn test_is_affirmative() {
+        assert!(is_affirmative("y\n"));
+        assert!(is_affirmative(" YES "));
+        assert!(!is_affirmative("\n"));
+        assert!(!is_affirmative("no"));
+    }
+
+    #[test]
+    fn test_checkout_rejects_empty_name() {
+        assert!(checkout_branch("", true).is_err());
+    }
+
+    #[test]
+    fn test_extract_ticket_id() {
+        assert_eq!(extract_ticket_id("ABC-123 title").0.as_deref(), Some("ABC-123"));
+        assert_eq!(extract_ticket_id("title (#42)").0.as_deref(), Some("42"));
+        assert_eq!(extract_ticket_id("covid-19 notes").0, None);
+        assert_eq!(extract_ticket_id("no ticket").0, None);
+    }
+
+    #[test]
+    fn test_clean_separators() {
+        assert_eq!(clean_separators("/-slug"), "slug");
+        assert_eq!(clean_separators("fix/-slug"), "fix/slug");
+        assert_eq!(clean_separators("fix/-"), "fix");
+        assert_eq!(clean_separators("-/slug"), "slug");
+        assert_eq!(clean_separators("a-/b"), "a/b");
+    }
+
     #[test]
     fn test_convert_quotes_and_brackets() {
         assert_eq!(convert_to_branch_name("\"Feature\" [urgent] {todo}"), "feature-urgent-todo");
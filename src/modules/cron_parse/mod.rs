@@ -0,0 +1,650 @@
+use crate::tool_module::ToolModule;
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
+use clap::{Arg, ArgMatches, Command};
+use regex::Regex;
+use std::collections::BTreeSet;
+use std::error::Error;
+
+pub struct CronParseModule;
+
+const MONTHS: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June",
+    "July", "August", "September", "October", "November", "December",
+];
+
+const WEEKDAYS: [&str; 7] = ["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"];
+
+impl ToolModule for CronParseModule {
+    fn name(&self) -> &'static str {
+        "cron-parse"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("cron")
+                .long("cron")
+                .value_name("EXPRESSION")
+                .help("Explain a cron expression and show its next run times")
+                .long_help("Validate a five-field cron expression (minute hour day-of-month month day-of-week), describe it in English and list the next run times in local time and UTC. Supports *, lists, ranges, steps, month and weekday names and the @hourly, @daily, @weekly, @monthly and @yearly shortcuts. When both day fields are restricted, either one matching is enough, as in standard cron.")
+                .conflicts_with("cron-from")
+        )
+        .arg(
+            Arg::new("cron-from")
+                .long("cron-from")
+                .value_name("TEXT")
+                .help("Turn a schedule in English, e.g. \"every weekday at 2am\", into a cron expression")
+                .long_help("Build a cron expression from a short English schedule such as \"every 15 minutes\", \"every weekday at 2am\", \"every monday and friday at 9:30\", \"daily at 9am and 5pm\" or \"at midnight on the 1st of every month\", then explain it and list the next run times.")
+        )
+        .arg(
+            Arg::new("cron-next")
+                .long("cron-next")
+                .value_name("N")
+                .help("Number of upcoming runs shown by --cron and --cron-from (default: 5)")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("5")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let expression = if let Some(expression) = matches.get_one::<String>("cron") {
+            expression.clone()
+        } else if let Some(text) = matches.get_one::<String>("cron-from") {
+            from_english(text)?
+        } else {
+            return Ok(());
+        };
+        let schedule = Schedule::parse(&expression)?;
+        let count = matches.get_one::<usize>("cron-next").copied().unwrap_or(5);
+
+        println!("Expression: {}", schedule.expression);
+        println!("Meaning:    {}", schedule.describe());
+        let now = Local::now().naive_local();
+        let runs: Vec<_> = schedule.upcoming(now)
+            .filter_map(|run| Local.from_local_datetime(&run).earliest())
+            .take(count)
+            .collect();
+        if runs.is_empty() {
+            println!("\n⚠️  This schedule never runs");
+            return Ok(());
+        }
+        println!("\nNext {} run{}:", runs.len(), if runs.len() == 1 { "" } else { "s" });
+        for run in runs {
+            println!("  {}  ({} UTC)", run.format("%a %Y-%m-%d %H:%M %:z"), run.with_timezone(&Utc).format("%Y-%m-%d %H:%M"));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FieldKind {
+    Minute,
+    Hour,
+    DayOfMonth,
+    Month,
+    DayOfWeek,
+}
+
+impl FieldKind {
+    fn name(self) -> &'static str {
+        match self {
+            FieldKind::Minute => "minute",
+            FieldKind::Hour => "hour",
+            FieldKind::DayOfMonth => "day-of-month",
+            FieldKind::Month => "month",
+            FieldKind::DayOfWeek => "day-of-week",
+        }
+    }
+
+    // Day of week accepts 7 as another Sunday
+    fn bounds(self) -> (u32, u32) {
+        match self {
+            FieldKind::Minute => (0, 59),
+            FieldKind::Hour => (0, 23),
+            FieldKind::DayOfMonth => (1, 31),
+            FieldKind::Month => (1, 12),
+            FieldKind::DayOfWeek => (0, 7),
+        }
+    }
+
+    fn parse_value(self, text: &str) -> Result<u32, String> {
+        let lower = text.to_lowercase();
+        let named = match self {
+            FieldKind::Month => MONTHS.iter().position(|month| month[..3].eq_ignore_ascii_case(&lower)).map(|i| i as u32 + 1),
+            FieldKind::DayOfWeek => WEEKDAYS.iter().position(|day| day[..3].eq_ignore_ascii_case(&lower)).map(|i| i as u32),
+            _ => None,
+        };
+        let value = match named {
+            Some(value) => value,
+            None => text.parse().map_err(|_| format!("'{}' is not a valid {}", text, self.name()))?,
+        };
+        let (min, max) = self.bounds();
+        if value < min || value > max {
+            return Err(format!("{} {} is out of range {}-{}", self.name(), value, min, max));
+        }
+        Ok(value)
+    }
+
+    // How a single value reads in a description
+    fn label(self, value: u32) -> String {
+        match self {
+            FieldKind::Minute => value.to_string(),
+            FieldKind::Hour => format!("{:02}:00", value),
+            FieldKind::DayOfMonth => ordinal(value),
+            FieldKind::Month => MONTHS[(value as usize + 11) % 12].to_string(),
+            FieldKind::DayOfWeek => WEEKDAYS[value as usize % 7].to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Value(u32),
+    Range(u32, u32),
+    // */n, a/n or a-b/n; `all` marks the * form
+    Step { start: u32, end: u32, step: u32, all: bool },
+}
+
+#[derive(Debug, Clone)]
+struct Field {
+    kind: FieldKind,
+    parts: Vec<Part>,
+    // A field written as * (or */n) does not restrict the day for the OR rule
+    wildcard: bool,
+    // Bit n is set when value n matches
+    mask: u64,
+}
+
+impl Field {
+    fn parse(text: &str, kind: FieldKind) -> Result<Self, String> {
+        let (min, max) = kind.bounds();
+        let mut parts = Vec::new();
+        for item in text.split(',') {
+            let (base, step) = match item.split_once('/') {
+                Some((base, step)) => {
+                    let step: u32 = step.parse().map_err(|_| format!("invalid step '{}'", step))?;
+                    if step == 0 {
+                        return Err("the step must be at least 1".to_string());
+                    }
+                    (base, Some(step))
+                }
+                None => (item, None),
+            };
+            let (start, end, all) = match base {
+                "*" | "?" => (min, if kind == FieldKind::DayOfWeek { 6 } else { max }, true),
+                _ => match base.split_once('-') {
+                    Some((a, b)) => {
+                        let (a, b) = (kind.parse_value(a)?, kind.parse_value(b)?);
+                        if a > b {
+                            return Err(format!("range {} is backwards", base));
+                        }
+                        (a, b, false)
+                    }
+                    None if step.is_some() => (kind.parse_value(base)?, max, false),
+                    None => (kind.parse_value(base)?, kind.parse_value(base)?, false),
+                },
+            };
+            parts.push(match step {
+                Some(step) => Part::Step { start, end, step, all },
+                None if all => Part::Step { start, end, step: 1, all },
+                None if start == end => Part::Value(start),
+                None => Part::Range(start, end),
+            });
+        }
+        let mut mask = 0u64;
+        for part in &parts {
+            let (start, end, step) = match *part {
+                Part::Value(value) => (value, value, 1),
+                Part::Range(start, end) => (start, end, 1),
+                Part::Step { start, end, step, .. } => (start, end, step),
+            };
+            for value in (start..=end).step_by(step as usize) {
+                // Sunday may be written as 0 or 7
+                mask |= 1 << if kind == FieldKind::DayOfWeek { value % 7 } else { value };
+            }
+        }
+        let wildcard = text.starts_with('*') || text.starts_with('?');
+        Ok(Field { kind, parts, wildcard, mask })
+    }
+
+    fn matches(&self, value: u32) -> bool {
+        self.mask & (1 << value) != 0
+    }
+
+    fn is_every(&self) -> bool {
+        matches!(self.parts.as_slice(), [Part::Step { all: true, step: 1, .. }])
+    }
+
+    fn values(&self) -> Option<Vec<u32>> {
+        self.parts.iter().map(|part| if let Part::Value(value) = part { Some(*value) } else { None }).collect()
+    }
+
+    // "Monday through Friday", "1st and 15th", "every 3 months"
+    fn list(&self, unit: &str) -> String {
+        if let [Part::Step { start, end, step, all }] = self.parts.as_slice() {
+            let (min, max) = self.kind.bounds();
+            let mut text = format!("every {} {}s", step, unit);
+            if !*all || *start != min {
+                let last = if *all || *end == max { String::new() } else { format!(" through {}", self.kind.label(*end)) };
+                text.push_str(&format!(" from {}{}", self.kind.label(*start), last));
+            }
+            return text;
+        }
+        let items: Vec<String> = self.parts.iter().map(|part| match *part {
+            Part::Value(value) => self.kind.label(value),
+            Part::Range(start, end) => format!("{} through {}", self.kind.label(start), self.kind.label(end)),
+            Part::Step { start, end, step, .. } => format!("every {} {}s from {} through {}", step, unit, self.kind.label(start), self.kind.label(end)),
+        }).collect();
+        join_list(&items)
+    }
+}
+
+#[derive(Debug)]
+struct Schedule {
+    expression: String,
+    minute: Field,
+    hour: Field,
+    day_of_month: Field,
+    month: Field,
+    day_of_week: Field,
+}
+
+impl Schedule {
+    fn parse(expression: &str) -> Result<Self, String> {
+        let expression = match expression.trim().to_lowercase().as_str() {
+            "@yearly" | "@annually" => "0 0 1 1 *".to_string(),
+            "@monthly" => "0 0 1 * *".to_string(),
+            "@weekly" => "0 0 * * 0".to_string(),
+            "@daily" | "@midnight" => "0 0 * * *".to_string(),
+            "@hourly" => "0 * * * *".to_string(),
+            "@reboot" => return Err("@reboot runs at startup and has no schedule".to_string()),
+            _ => expression.split_whitespace().collect::<Vec<_>>().join(" "),
+        };
+        let fields: Vec<&str> = expression.split(' ').collect();
+        if fields.len() != 5 {
+            return Err(format!(
+                "Expected 5 fields (minute hour day-of-month month day-of-week), found {}",
+                fields.len()
+            ));
+        }
+        let field = |index: usize, kind: FieldKind| {
+            Field::parse(fields[index], kind).map_err(|e| format!("Invalid {} field '{}': {}", kind.name(), fields[index], e))
+        };
+        Ok(Schedule {
+            minute: field(0, FieldKind::Minute)?,
+            hour: field(1, FieldKind::Hour)?,
+            day_of_month: field(2, FieldKind::DayOfMonth)?,
+            month: field(3, FieldKind::Month)?,
+            day_of_week: field(4, FieldKind::DayOfWeek)?,
+            expression,
+        })
+    }
+
+    // Standard cron: if both day fields are restricted, matching either is enough
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let by_month = self.day_of_month.matches(date.day());
+        let by_week = self.day_of_week.matches(date.weekday().num_days_from_sunday());
+        if !self.day_of_month.wildcard && !self.day_of_week.wildcard {
+            by_month || by_week
+        } else {
+            by_month && by_week
+        }
+    }
+
+    // Matching minutes after `after`, skipping whole months, days and hours
+    // that cannot match. Gives up five years out (e.g. for 30 February).
+    fn upcoming(&self, after: NaiveDateTime) -> impl Iterator<Item = NaiveDateTime> + '_ {
+        let limit = after + Duration::days(5 * 366);
+        let mut time = after.with_second(0).and_then(|t| t.with_nanosecond(0)).unwrap_or(after) + Duration::minutes(1);
+        std::iter::from_fn(move || {
+            while time <= limit {
+                let date = time.date();
+                if !self.month.matches(date.month()) {
+                    let (year, month) = if date.month() == 12 { (date.year() + 1, 1) } else { (date.year(), date.month() + 1) };
+                    time = NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)?;
+                } else if !self.matches_day(date) {
+                    time = date.succ_opt()?.and_hms_opt(0, 0, 0)?;
+                } else if !self.hour.matches(time.hour()) {
+                    time = date.and_hms_opt(time.hour(), 0, 0)? + Duration::hours(1);
+                } else if !self.minute.matches(time.minute()) {
+                    time += Duration::minutes(1);
+                } else {
+                    let found = time;
+                    time += Duration::minutes(1);
+                    return Some(found);
+                }
+            }
+            None
+        })
+    }
+
+    fn describe(&self) -> String {
+        let mut phrases = vec![self.describe_time()];
+        let by_month = (!self.day_of_month.wildcard).then(|| match self.day_of_month.parts.as_slice() {
+            [Part::Step { .. }] => self.day_of_month.list("day"),
+            _ => format!("on the {} of the month", self.day_of_month.list("day")),
+        });
+        let by_week = (!self.day_of_week.wildcard).then(|| match self.day_of_week.parts.as_slice() {
+            [Part::Range(..)] => self.day_of_week.list("day"),
+            _ => format!("on {}", self.day_of_week.list("day")),
+        });
+        match (by_month, by_week) {
+            (Some(by_month), Some(by_week)) => phrases.push(format!("{} or {}", by_month, by_week)),
+            (Some(phrase), None) | (None, Some(phrase)) => phrases.push(phrase),
+            (None, None) => {}
+        }
+        // Steps starting from * on the day-of-month field still restrict it
+        if self.day_of_month.wildcard && !self.day_of_month.is_every() {
+            phrases.push(self.day_of_month.list("day"));
+        }
+        if !self.month.is_every() {
+            phrases.push(match self.month.parts.as_slice() {
+                [Part::Step { .. }] => self.month.list("month"),
+                [Part::Range(..)] => format!("from {}", self.month.list("month")),
+                _ => format!("in {}", self.month.list("month")),
+            });
+        }
+        let sentence = phrases.join(", ");
+        let mut chars = sentence.chars();
+        chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+    }
+
+    fn describe_time(&self) -> String {
+        // A handful of fixed times reads best as a list of clock times
+        if let (Some(minutes), Some(hours)) = (self.minute.values(), self.hour.values()) {
+            if minutes.len() * hours.len() <= 6 {
+                let times: Vec<String> = hours.iter()
+                    .flat_map(|hour| minutes.iter().map(move |minute| format!("{:02}:{:02}", hour, minute)))
+                    .collect();
+                return format!("at {}", join_list(&times));
+            }
+        }
+        let minutes = match self.minute.parts.as_slice() {
+            _ if self.minute.is_every() => "every minute".to_string(),
+            [Part::Step { .. }] => self.minute.list("minute"),
+            [Part::Value(value)] if self.hour.is_every() => return format!("at minute {} past every hour", value),
+            [Part::Value(_)] => format!("at minute {}", self.minute.list("minute")),
+            _ => format!("at minutes {}", self.minute.list("minute")),
+        };
+        let hours = match self.hour.parts.as_slice() {
+            _ if self.hour.is_every() => return minutes,
+            [Part::Value(hour)] => format!("between {:02}:00 and {:02}:59", hour, hour),
+            [Part::Range(start, end)] => format!("between {:02}:00 and {:02}:59", start, end),
+            [Part::Step { .. }] => self.hour.list("hour"),
+            _ => format!("during the {} hours", self.hour.list("hour")),
+        };
+        format!("{}, {}", minutes, hours)
+    }
+}
+
+fn ordinal(value: u32) -> String {
+    let suffix = match (value % 10, value % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", value, suffix)
+}
+
+fn join_list(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [only] => only.clone(),
+        [rest @ .., last] => format!("{} and {}", rest.join(", "), last),
+    }
+}
+
+// 1,2,3,5 -> "1-3,5"; runs of three or more become ranges
+fn compact(values: &BTreeSet<u32>) -> String {
+    let values: Vec<u32> = values.iter().copied().collect();
+    let mut groups: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < values.len() {
+        let mut j = i;
+        while j + 1 < values.len() && values[j + 1] == values[j] + 1 {
+            j += 1;
+        }
+        if j - i >= 2 {
+            groups.push(format!("{}-{}", values[i], values[j]));
+        } else {
+            groups.extend(values[i..=j].iter().map(u32::to_string));
+        }
+        i = j + 1;
+    }
+    groups.join(",")
+}
+
+// "every weekday at 2am" -> "0 2 * * 1-5"
+fn from_english(text: &str) -> Result<String, String> {
+    let text = format!(" {} ", text.to_lowercase().replace(['-', ','], " "));
+    let regex = |pattern: &str| Regex::new(pattern).map_err(|e| e.to_string());
+    let not_understood = || format!(
+        "Could not turn '{}' into a schedule. Try e.g. \"every 15 minutes\", \"every weekday at 2am\" or \"on the 1st of every month at 9:00\"",
+        text.trim()
+    );
+
+    // Clock times: 2am, 9:30, 5:15pm, at 14, midnight, noon
+    let mut times: Vec<(u32, u32)> = Vec::new();
+    let time_pattern = regex(r"\b(\d{1,2})(?::(\d{2}))?\s*(am|pm)\b|\b(\d{1,2}):(\d{2})\b|\bat (\d{1,2}) |\b(midnight|noon|midday)\b")?;
+    for capture in time_pattern.captures_iter(&text) {
+        let number = |index: usize| capture.get(index).and_then(|m| m.as_str().parse::<u32>().ok());
+        let time = if let Some(hour) = number(1) {
+            let hour = match (hour, &capture[3]) {
+                (12, "am") => 0,
+                (12, "pm") => 12,
+                (hour, "pm") => hour + 12,
+                (hour, _) => hour,
+            };
+            (hour, number(2).unwrap_or(0))
+        } else if let Some(hour) = number(4) {
+            (hour, number(5).unwrap_or(0))
+        } else if let Some(hour) = number(6) {
+            (hour, 0)
+        } else if &capture[7] == "midnight" {
+            (0, 0)
+        } else {
+            (12, 0)
+        };
+        if time.0 > 23 || time.1 > 59 {
+            return Err(format!("{:02}:{:02} is not a valid time of day", time.0, time.1));
+        }
+        times.push(time);
+    }
+
+    // Weekdays, including ranges such as "monday to friday"
+    let day_names = WEEKDAYS.iter().map(|day| format!("{}(?:{})?s?", &day[..3].to_lowercase(), &day[3..].to_lowercase())).collect::<Vec<_>>().join("|");
+    let mut weekdays = BTreeSet::new();
+    let range_pattern = regex(&format!(r"\b({0})\s+(?:to|through|thru|until)\s+({0})\b", day_names))?;
+    let day_index = |name: &str| WEEKDAYS.iter().position(|day| name.starts_with(&day[..3].to_lowercase())).unwrap_or(0) as u32;
+    for capture in range_pattern.captures_iter(&text) {
+        let (start, end) = (day_index(&capture[1]), day_index(&capture[2]));
+        let mut day = start;
+        loop {
+            weekdays.insert(day);
+            if day == end {
+                break;
+            }
+            day = (day + 1) % 7;
+        }
+    }
+    let single_pattern = regex(&format!(r"\b({})\b", day_names))?;
+    for capture in single_pattern.captures_iter(&text) {
+        weekdays.insert(day_index(&capture[1]));
+    }
+    if text.contains(" weekday") {
+        weekdays.extend(1..=5);
+    }
+    if text.contains(" weekend") {
+        weekdays.extend([0, 6]);
+    }
+
+    let mut months = BTreeSet::new();
+    for (index, month) in MONTHS.iter().enumerate() {
+        let month = month.to_lowercase();
+        if regex(&format!(r"\b(?:{}|{})\b", month, &month[..3]))?.is_match(&text) {
+            months.insert(index as u32 + 1);
+        }
+    }
+
+    let mut days = BTreeSet::new();
+    for capture in regex(r"\b(\d{1,2})(?:st|nd|rd|th)\b")?.captures_iter(&text) {
+        let day: u32 = capture[1].parse().unwrap_or(0);
+        if !(1..=31).contains(&day) {
+            return Err(format!("There is no day {} in a month", day));
+        }
+        days.insert(day);
+    }
+    if text.contains(" first day ") {
+        days.insert(1);
+    }
+
+    let interval = regex(r"\bevery (\d+) ?(minute|min|hour|hr|day|month)s?\b")?
+        .captures(&text)
+        .map(|capture| (capture[1].parse::<u32>().unwrap_or(0), capture[2].to_string()));
+    if let Some((0, _)) = interval {
+        return Err("The interval must be at least 1".to_string());
+    }
+
+    let (mut minute, mut hour) = ("0".to_string(), "0".to_string());
+    let (mut day_of_month, mut month, mut day_of_week) = ("*".to_string(), "*".to_string(), "*".to_string());
+    let every = |unit: &str| text.contains(&format!(" every {} ", unit));
+    let has_time = !times.is_empty();
+    if has_time {
+        let minutes: BTreeSet<u32> = times.iter().map(|(_, minute)| *minute).collect();
+        if minutes.len() > 1 {
+            return Err("All times need the same minutes past the hour, e.g. 9:30 and 17:30".to_string());
+        }
+        minute = minutes.iter().next().unwrap_or(&0).to_string();
+        hour = compact(&times.iter().map(|(hour, _)| *hour).collect());
+    }
+    match interval.as_ref().map(|(n, unit)| (*n, unit.as_str())) {
+        Some((n, "minute" | "min")) => {
+            minute = format!("*/{}", n);
+            hour = "*".to_string();
+        }
+        Some((n, "hour" | "hr")) => {
+            hour = format!("*/{}", n);
+            if !has_time {
+                minute = "0".to_string();
+            }
+        }
+        Some((n, "day")) => day_of_month = format!("*/{}", n),
+        Some((n, _)) => {
+            month = format!("*/{}", n);
+            day_of_month = "1".to_string();
+        }
+        None if every("minute") => {
+            minute = "*".to_string();
+            hour = "*".to_string();
+        }
+        None if every("hour") || text.contains(" hourly ") => {
+            hour = "*".to_string();
+        }
+        None => {}
+    }
+    if !days.is_empty() {
+        day_of_month = compact(&days);
+    } else if text.contains(" monthly ") || every("month") {
+        day_of_month = "1".to_string();
+    } else if text.contains(" yearly ") || text.contains(" annually ") || every("year") {
+        day_of_month = "1".to_string();
+        month = "1".to_string();
+    }
+    if !months.is_empty() {
+        month = compact(&months);
+    }
+    if !weekdays.is_empty() {
+        day_of_week = compact(&weekdays);
+    } else if text.contains(" weekly ") || every("week") {
+        day_of_week = "0".to_string();
+    }
+
+    let understood = has_time
+        || interval.is_some()
+        || !weekdays.is_empty()
+        || !days.is_empty()
+        || !months.is_empty()
+        || ["minute", "hour", "day", "week", "month", "year"].iter().any(|unit| every(unit))
+        || ["hourly", "daily", "weekly", "monthly", "yearly", "annually"].iter().any(|word| text.contains(&format!(" {} ", word)));
+    if !understood {
+        return Err(not_understood());
+    }
+    Ok(format!("{} {} {} {} {}", minute, hour, day_of_month, month, day_of_week))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(text: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn next(expression: &str, after: &str, count: usize) -> Vec<String> {
+        Schedule::parse(expression).unwrap()
+            .upcoming(at(after))
+            .take(count)
+            .map(|time| time.format("%a %Y-%m-%d %H:%M").to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_describe() {
+        let describe = |expression: &str| Schedule::parse(expression).unwrap().describe();
+        assert_eq!(describe("*/15 2 * * 1-5"), "Every 15 minutes, between 02:00 and 02:59, Monday through Friday");
+        assert_eq!(describe("0 9,17 * * *"), "At 09:00 and 17:00");
+        assert_eq!(describe("30 * * * *"), "At minute 30 past every hour");
+        assert_eq!(describe("* * * * *"), "Every minute");
+        assert_eq!(describe("0 0 1,15 * *"), "At 00:00, on the 1st and 15th of the month");
+        assert_eq!(describe("0 12 * jan-mar sat,sun"), "At 12:00, on Saturday and Sunday, from January through March");
+        assert_eq!(describe("0 */6 * * *"), "At minute 0, every 6 hours");
+        assert_eq!(describe("@monthly"), "At 00:00, on the 1st of the month");
+        assert_eq!(describe("0 8 1 * 1"), "At 08:00, on the 1st of the month or on Monday");
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        assert!(Schedule::parse("* * * *").unwrap_err().starts_with("Expected 5 fields"));
+        assert_eq!(Schedule::parse("61 * * * *").unwrap_err(), "Invalid minute field '61': minute 61 is out of range 0-59");
+        assert!(Schedule::parse("* * * foo *").unwrap_err().contains("'foo' is not a valid month"));
+        assert!(Schedule::parse("*/0 * * * *").is_err());
+        assert!(Schedule::parse("* 5-2 * * *").is_err());
+        assert!(Schedule::parse("@reboot").is_err());
+    }
+
+    #[test]
+    fn test_upcoming_runs() {
+        assert_eq!(
+            next("*/15 2 * * 1-5", "2024-05-03 02:40", 3),
+            ["Fri 2024-05-03 02:45", "Mon 2024-05-06 02:00", "Mon 2024-05-06 02:15"]
+        );
+        assert_eq!(next("0 0 29 2 *", "2024-03-01 00:00", 1), ["Tue 2028-02-29 00:00"]);
+        assert_eq!(next("0 0 * * 7", "2024-05-01 12:00", 1), ["Sun 2024-05-05 00:00"]);
+        // Either day field may match when both are restricted
+        assert_eq!(next("0 8 13 * 5", "2024-09-01 00:00", 3), ["Fri 2024-09-06 08:00", "Fri 2024-09-13 08:00", "Fri 2024-09-20 08:00"]);
+        assert!(next("0 0 30 2 *", "2024-01-01 00:00", 1).is_empty());
+    }
+
+    #[test]
+    fn test_from_english() {
+        assert_eq!(from_english("every weekday at 2am").unwrap(), "0 2 * * 1-5");
+        assert_eq!(from_english("every 15 minutes").unwrap(), "*/15 * * * *");
+        assert_eq!(from_english("every monday and friday at 9:30").unwrap(), "30 9 * * 1,5");
+        assert_eq!(from_english("daily at 9am and 5pm").unwrap(), "0 9,17 * * *");
+        assert_eq!(from_english("at midnight on the 1st of every month").unwrap(), "0 0 1 * *");
+        assert_eq!(from_english("every 2 hours").unwrap(), "0 */2 * * *");
+        assert_eq!(from_english("every saturday to monday at noon").unwrap(), "0 12 * * 0,1,6");
+        assert_eq!(from_english("12:30am on weekends in december").unwrap(), "30 0 * 12 0,6");
+        assert_eq!(from_english("hourly").unwrap(), "0 * * * *");
+        assert!(from_english("whenever you like").is_err());
+        assert!(from_english("at 25:00").is_err());
+        assert!(from_english("daily at 9:00 and 17:30").is_err());
+    }
+
+    #[test]
+    fn test_compact() {
+        assert_eq!(compact(&[1, 2, 3, 5].into_iter().collect()), "1-3,5");
+        assert_eq!(compact(&[0, 6].into_iter().collect()), "0,6");
+    }
+}
@@ -0,0 +1,511 @@
+use crate::color_convert::parse_color;
+use crate::tool_module::{read_input, ToolModule};
+use clap::{Arg, ArgMatches, Command};
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::OnceLock;
+
+pub struct CssFormatModule;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    // A selector or at-rule prelude with its block
+    Rule { prelude: String, children: Vec<Node> },
+    Declaration { property: String, value: String },
+    // An at-rule without a block, such as @import url(x.css)
+    Statement(String),
+    Comment(String),
+}
+
+impl ToolModule for CssFormatModule {
+    fn name(&self) -> &'static str {
+        "css-format"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("css-minify")
+                .long("css-minify")
+                .value_name("CSS|FILE")
+                .help("Minify CSS, shortening colors such as rgb(255,0,0) to #f00 (or read from stdin)")
+                .long_help("Remove comments (except /*! license comments), whitespace, empty rules and last semicolons, and shorten colors: rgb() and hsl() become hex and #ffcc00 becomes #fc0. Rules defined twice and repeated declarations are reported on stderr. Takes CSS text (containing {), a file path, or reads from stdin when neither is given.")
+                .num_args(0..=1)
+                .conflicts_with("css-pretty")
+        )
+        .arg(
+            Arg::new("css-pretty")
+                .long("css-pretty")
+                .value_name("CSS|FILE")
+                .help("Pretty print CSS with 2-space indentation (or read from stdin)")
+                .long_help("Put every selector and declaration on its own line, indented by two spaces per block, with rgb() and hsl() colors written as lowercase hex. Comments are kept. Rules defined twice and repeated declarations are reported on stderr. Takes CSS text (containing {), a file path, or reads from stdin when neither is given.")
+                .num_args(0..=1)
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let (input, minify) = if matches.contains_id("css-minify") {
+            (read_css(matches.get_one::<String>("css-minify"))?, true)
+        } else if matches.contains_id("css-pretty") {
+            (read_css(matches.get_one::<String>("css-pretty"))?, false)
+        } else {
+            return Ok(());
+        };
+        let nodes = parse(&input)?;
+        for warning in duplicates(&nodes) {
+            eprintln!("⚠️ {}", warning);
+        }
+        if minify {
+            println!("{}", minify_nodes(&nodes, true));
+        } else {
+            print!("{}", pretty_nodes(&nodes, 0));
+        }
+        Ok(())
+    }
+}
+
+// Inline CSS, otherwise a file path or stdin as read_input takes them
+fn read_css(value: Option<&String>) -> Result<String, Box<dyn Error>> {
+    match value {
+        Some(text) if text.contains('{') => Ok(text.clone()),
+        _ => read_input(value),
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+    line: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<char> {
+        let c = *self.chars.get(self.position)?;
+        self.position += 1;
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    // Reads the rest of a block; `nested` blocks must end with }
+    fn block(&mut self, nested: bool) -> Result<Vec<Node>, String> {
+        let mut nodes = Vec::new();
+        let mut buffer = String::new();
+        let mut depth = 0usize;
+        let start_line = self.line;
+        loop {
+            let Some(c) = self.next() else {
+                if nested {
+                    return Err(format!("Unclosed block starting at line {}", start_line));
+                }
+                push_segment(&mut nodes, &buffer, self.line)?;
+                return Ok(nodes);
+            };
+            match c {
+                '/' if self.chars.get(self.position) == Some(&'*') => {
+                    let line = self.line;
+                    let mut comment = String::from("/");
+                    loop {
+                        match self.next() {
+                            Some('/') if comment.len() > 2 && comment.ends_with('*') => break,
+                            Some(c) => comment.push(c),
+                            None => return Err(format!("Unclosed comment starting at line {}", line)),
+                        }
+                    }
+                    comment.push('/');
+                    if buffer.trim().is_empty() {
+                        nodes.push(Node::Comment(comment));
+                    } else {
+                        buffer.push(' ');
+                    }
+                }
+                '"' | '\'' => {
+                    let line = self.line;
+                    buffer.push(c);
+                    loop {
+                        match self.next() {
+                            Some('\\') => {
+                                buffer.push('\\');
+                                buffer.extend(self.next());
+                            }
+                            Some(quote) if quote == c => break,
+                            Some(other) => buffer.push(other),
+                            None => return Err(format!("Unclosed string starting at line {}", line)),
+                        }
+                    }
+                    buffer.push(c);
+                }
+                '(' => {
+                    depth += 1;
+                    buffer.push(c);
+                }
+                ')' => {
+                    depth = depth.saturating_sub(1);
+                    buffer.push(c);
+                }
+                // Inside parentheses (url(data:...;base64,...)) these are plain characters
+                _ if depth > 0 => buffer.push(c),
+                '{' => {
+                    let prelude = collapse(&buffer);
+                    let children = self.block(true)?;
+                    nodes.push(Node::Rule { prelude, children });
+                    buffer.clear();
+                }
+                ';' => {
+                    push_segment(&mut nodes, &buffer, self.line)?;
+                    buffer.clear();
+                }
+                '}' if !nested => return Err(format!("Unexpected '}}' at line {}", self.line)),
+                '}' => {
+                    push_segment(&mut nodes, &buffer, self.line)?;
+                    return Ok(nodes);
+                }
+                _ => buffer.push(c),
+            }
+        }
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<Node>, String> {
+    Parser { chars: input.chars().collect(), position: 0, line: 1 }.block(false)
+}
+
+// A declaration or at-statement ended by ; or }
+fn push_segment(nodes: &mut Vec<Node>, text: &str, line: usize) -> Result<(), String> {
+    let text = collapse(text);
+    if text.is_empty() {
+        return Ok(());
+    }
+    if text.starts_with('@') {
+        nodes.push(Node::Statement(text));
+        return Ok(());
+    }
+    let (property, value) = text.split_once(':').ok_or_else(|| format!("Expected 'property: value' near line {}, found '{}'", line, text))?;
+    nodes.push(Node::Declaration { property: property.trim().to_string(), value: value.trim().to_string() });
+    Ok(())
+}
+
+// Each character with the nesting depth of () and [] it sits at, or None
+// inside a string (quotes included) or an unquoted url(), which are kept as written
+fn scan(text: &str) -> Vec<(char, Option<usize>)> {
+    let mut scanned = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    let mut depth = 0usize;
+    for (index, c) in text.char_indices() {
+        if quote == Some(')') {
+            scanned.push((c, None));
+            if c == ')' {
+                quote = None;
+            }
+            continue;
+        }
+        if let Some(open) = quote {
+            scanned.push((c, None));
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == open {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' => {
+                quote = Some(c);
+                scanned.push((c, None));
+            }
+            '(' if text.get(index.saturating_sub(3)..index).is_some_and(|name| name.eq_ignore_ascii_case("url")) && !text[index + 1..].trim_start().starts_with(['"', '\'']) => {
+                quote = Some(')');
+                scanned.push((c, None));
+            }
+            '(' | '[' => {
+                scanned.push((c, Some(depth)));
+                depth += 1;
+            }
+            ')' | ']' => {
+                depth = depth.saturating_sub(1);
+                scanned.push((c, Some(depth)));
+            }
+            _ => scanned.push((c, Some(depth))),
+        }
+    }
+    scanned
+}
+
+// Whitespace runs outside strings become a single space
+fn collapse(text: &str) -> String {
+    let mut output = String::new();
+    for (c, depth) in scan(text) {
+        if depth.is_none() || !c.is_whitespace() {
+            output.push(c);
+        } else if !output.ends_with(' ') {
+            output.push(' ');
+        }
+    }
+    output.trim().to_string()
+}
+
+// Removes the spaces around the given characters; expects collapsed text
+fn tighten(text: &str, around: &[char]) -> String {
+    let mut output = String::new();
+    let mut after_separator = false;
+    for (c, depth) in scan(text) {
+        if depth.is_some() && c == ' ' && after_separator {
+            continue;
+        }
+        after_separator = depth.is_some() && around.contains(&c);
+        if after_separator && output.ends_with(' ') {
+            output.pop();
+        }
+        output.push(c);
+    }
+    output
+}
+
+// Splits at commas outside strings and brackets
+fn split_top_level(text: &str) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    for (c, depth) in scan(text) {
+        if c == ',' && depth == Some(0) {
+            parts.push(String::new());
+        } else if let Some(last) = parts.last_mut() {
+            last.push(c);
+        }
+    }
+    parts.iter().map(|part| part.trim().to_string()).collect()
+}
+
+// rgb()/hsl() without transparency become hex, hex digits are lowercased and,
+// when `short`, #aabbcc becomes #abc. Strings and url() are left alone.
+fn normalize_colors(value: &str, short: bool) -> String {
+    static COLOR: OnceLock<Regex> = OnceLock::new();
+    let color = COLOR.get_or_init(|| {
+        Regex::new(r#"(?i)url\([^)]*\)|"(?:[^"\\]|\\.)*"|'(?:[^'\\]|\\.)*'|\b(?:rgb|hsl)a?\([^()]*\)|#[0-9a-f]{6}\b|#[0-9a-f]{3}\b"#).unwrap()
+    });
+    color
+        .replace_all(value, |captures: &Captures| {
+            let text = &captures[0];
+            let lower = text.to_lowercase();
+            let hex = if lower.starts_with('#') {
+                lower
+            } else if lower.starts_with("rgb") || lower.starts_with("hsl") {
+                let (name, arguments) = lower.trim_end_matches(')').split_once('(').unwrap_or_default();
+                let mut arguments: Vec<&str> = arguments.split(',').map(str::trim).collect();
+                // An alpha of 1 is the same as none
+                if arguments.len() == 4 && arguments[3].parse::<f64>().ok() == Some(1.0) {
+                    arguments.pop();
+                }
+                match parse_color(&format!("{}({})", name.trim_end_matches('a'), arguments.join(","))) {
+                    Ok(color) if arguments.len() == 3 => color.to_hex(),
+                    _ => return text.to_string(),
+                }
+            } else {
+                return text.to_string();
+            };
+            let digits: Vec<char> = hex.chars().skip(1).collect();
+            if short && digits.len() == 6 && digits.chunks(2).all(|pair| pair[0] == pair[1]) {
+                format!("#{}{}{}", digits[0], digits[2], digits[4])
+            } else {
+                hex
+            }
+        })
+        .to_string()
+}
+
+fn minify_value(value: &str) -> String {
+    let value = tighten(value, &[',']).replace(" !important", "!important");
+    normalize_colors(&value, true)
+}
+
+fn minify_prelude(prelude: &str) -> String {
+    if prelude.starts_with('@') {
+        tighten(prelude, &[',', ':'])
+    } else {
+        tighten(prelude, &[',', '>', '~', '+'])
+    }
+}
+
+// Inside blocks the last semicolon is dropped; at the top level statements keep theirs
+fn minify_nodes(nodes: &[Node], top_level: bool) -> String {
+    let parts: Vec<(String, bool)> = nodes
+        .iter()
+        .filter_map(|node| match node {
+            Node::Rule { prelude, children } => {
+                let body = minify_nodes(children, false);
+                (!body.is_empty()).then(|| (format!("{}{{{}}}", minify_prelude(prelude), body), false))
+            }
+            Node::Declaration { property, value } => Some((format!("{}:{}", property, minify_value(value)), true)),
+            Node::Statement(text) => Some((tighten(text, &[',']), true)),
+            Node::Comment(text) if text.starts_with("/*!") => Some((text.clone(), false)),
+            Node::Comment(_) => None,
+        })
+        .collect();
+    let mut output = String::new();
+    for (index, (part, needs_semicolon)) in parts.iter().enumerate() {
+        output.push_str(part);
+        if *needs_semicolon && (top_level || index + 1 < parts.len()) {
+            output.push(';');
+        }
+    }
+    output
+}
+
+// One selector per line, spaces around combinators
+fn pretty_selector(selector: &str) -> Vec<String> {
+    split_top_level(selector)
+        .iter()
+        .map(|part| {
+            let mut output = String::new();
+            let mut after_combinator = false;
+            for (c, depth) in scan(part) {
+                if c == ' ' && after_combinator {
+                    continue;
+                }
+                after_combinator = depth == Some(0) && matches!(c, '>' | '~' | '+');
+                if after_combinator {
+                    output.truncate(output.trim_end().len());
+                    output.push_str(&format!(" {} ", c));
+                } else {
+                    output.push(c);
+                }
+            }
+            output.trim().to_string()
+        })
+        .collect()
+}
+
+// A space after commas and before !important
+fn pretty_value(value: &str) -> String {
+    let mut output = String::new();
+    for (c, depth) in scan(&tighten(value, &[','])) {
+        if depth.is_some() && c == '!' && !output.ends_with(' ') {
+            output.push(' ');
+        }
+        output.push(c);
+        if depth.is_some() && c == ',' {
+            output.push(' ');
+        }
+    }
+    normalize_colors(&output, false)
+}
+
+fn pretty_nodes(nodes: &[Node], level: usize) -> String {
+    let indent = "  ".repeat(level);
+    let mut output = String::new();
+    for (index, node) in nodes.iter().enumerate() {
+        // Top-level rules are separated by a blank line
+        let is_rule = |node: &Node| matches!(node, Node::Rule { .. });
+        if level == 0 && index > 0 && (is_rule(node) || is_rule(&nodes[index - 1])) {
+            output.push('\n');
+        }
+        match node {
+            Node::Rule { prelude, children } => {
+                let selectors = if prelude.starts_with('@') { vec![prelude.clone()] } else { pretty_selector(prelude) };
+                output.push_str(&format!("{}{} {{\n", indent, selectors.join(&format!(",\n{}", indent))));
+                output.push_str(&pretty_nodes(children, level + 1));
+                output.push_str(&format!("{}}}\n", indent));
+            }
+            Node::Declaration { property, value } => output.push_str(&format!("{}{}: {};\n", indent, property, pretty_value(value))),
+            Node::Statement(text) => output.push_str(&format!("{}{};\n", indent, text)),
+            Node::Comment(text) => output.push_str(&format!("{}{}\n", indent, text)),
+        }
+    }
+    output
+}
+
+// Selectors (counted one by one in selector lists) defined more than once in
+// the same context, and declarations repeated word for word inside a rule
+fn duplicates(nodes: &[Node]) -> Vec<String> {
+    fn visit(nodes: &[Node], context: &str, warnings: &mut Vec<String>) {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        let mut order = Vec::new();
+        for node in nodes {
+            let Node::Rule { prelude, children } = node else {
+                continue;
+            };
+            if prelude.starts_with('@') {
+                visit(children, prelude, warnings);
+                continue;
+            }
+            let selector = minify_prelude(prelude);
+            for single in split_top_level(&selector) {
+                let count = counts.entry(single.clone()).or_insert(0);
+                *count += 1;
+                if *count == 1 {
+                    order.push(single);
+                }
+            }
+            let mut seen = Vec::new();
+            for child in children {
+                if let Node::Declaration { property, value } = child {
+                    let declaration = format!("{}: {}", property.to_lowercase(), minify_value(value));
+                    if seen.contains(&declaration) {
+                        warnings.push(format!("Repeated declaration in {}: {}", selector, declaration));
+                    } else {
+                        seen.push(declaration);
+                    }
+                }
+            }
+            visit(children, context, warnings);
+        }
+        let place = if context.is_empty() { String::new() } else { format!(" in {}", context) };
+        for selector in order {
+            if counts[&selector] > 1 {
+                warnings.push(format!("Duplicate rule: {} is defined {} times{}", selector, counts[&selector], place));
+            }
+        }
+    }
+    let mut warnings = Vec::new();
+    visit(nodes, "", &mut warnings);
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "/* header */\n@import url(\"base.css\");\n.btn , a > b{ color : RGB(255, 0, 0); background:url(data:image/png;base64,AA==) ;}\n@media (max-width: 600px){ .btn{margin:0 auto!important} .empty{} }\n.btn { content: \"a;b\"; color: #FFCC00 }\n";
+
+    #[test]
+    fn test_parse() {
+        let nodes = parse(SAMPLE).unwrap();
+        assert_eq!(nodes.len(), 5);
+        assert_eq!(nodes[0], Node::Comment("/* header */".to_string()));
+        assert_eq!(nodes[1], Node::Statement("@import url(\"base.css\")".to_string()));
+        let Node::Rule { prelude, children } = &nodes[2] else { panic!() };
+        assert_eq!(prelude, ".btn , a > b");
+        assert_eq!(children[1], Node::Declaration { property: "background".to_string(), value: "url(data:image/png;base64,AA==)".to_string() });
+        assert_eq!(parse("a { color: red").unwrap_err(), "Unclosed block starting at line 1");
+        assert_eq!(parse("a { color: red }\n}").unwrap_err(), "Unexpected '}' at line 2");
+        assert!(parse("a { color }").unwrap_err().starts_with("Expected 'property: value'"));
+    }
+
+    #[test]
+    fn test_minify() {
+        assert_eq!(
+            minify_nodes(&parse(SAMPLE).unwrap(), true),
+            "@import url(\"base.css\");.btn,a>b{color:#f00;background:url(data:image/png;base64,AA==)}@media (max-width:600px){.btn{margin:0 auto!important}}.btn{content:\"a;b\";color:#fc0}"
+        );
+        assert_eq!(normalize_colors("1px solid rgba(0, 128, 0, 1), hsl(0, 100%, 50%) rgba(0,0,0,.5)", true), "1px solid #008000, #f00 rgba(0,0,0,.5)");
+        assert_eq!(normalize_colors("\"#FFFFFF\" #AABBCC", false), "\"#FFFFFF\" #aabbcc");
+        assert_eq!(minify_nodes(&parse("/*! keep */ a{b:c}").unwrap(), true), "/*! keep */a{b:c}");
+    }
+
+    #[test]
+    fn test_pretty_and_duplicates() {
+        let nodes = parse(SAMPLE).unwrap();
+        assert_eq!(
+            pretty_nodes(&nodes, 0),
+            "/* header */\n@import url(\"base.css\");\n\n.btn,\na > b {\n  color: #ff0000;\n  background: url(data:image/png;base64,AA==);\n}\n\n@media (max-width: 600px) {\n  .btn {\n    margin: 0 auto !important;\n  }\n  .empty {\n  }\n}\n\n.btn {\n  content: \"a;b\";\n  color: #ffcc00;\n}\n"
+        );
+        assert_eq!(duplicates(&nodes), vec!["Duplicate rule: .btn is defined 2 times"]);
+        let nodes = parse(".a{color:red;color:red} .b{} .a{x:y} @media print{.b{} .b{}}").unwrap();
+        assert_eq!(
+            duplicates(&nodes),
+            vec!["Repeated declaration in .a: color: red", "Duplicate rule: .b is defined 2 times in @media print", "Duplicate rule: .a is defined 2 times"]
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use crate::tool_module::{read_input, ToolModule};
+use clap::builder::PossibleValuesParser;
+use clap::{Arg, ArgMatches, Command};
+use csv::{QuoteStyle, ReaderBuilder, WriterBuilder};
+use serde_json::{Map, Value};
+use std::error::Error;
+
+pub struct CsvJsonModule;
+
+impl ToolModule for CsvJsonModule {
+    fn name(&self) -> &'static str {
+        "csv-json"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("csv-to-json")
+                .long("csv-to-json")
+                .value_name("FILE")
+                .help("Convert CSV to a JSON array of objects (or read from stdin)")
+                .long_help("Convert a CSV file to a JSON array with one object per row, using the header row as keys. Numbers and true/false become JSON numbers and booleans; values with leading zeros such as 00123 stay strings. If no file (or -) is given, reads from stdin.")
+                .num_args(0..=1)
+                .conflicts_with("json-to-csv")
+        )
+        .arg(
+            Arg::new("json-to-csv")
+                .long("json-to-csv")
+                .value_name("FILE")
+                .help("Convert a JSON array of flat objects to CSV (or read from stdin)")
+                .long_help("Convert a JSON array of objects to CSV. The header is every key in order of first appearance; missing keys and nulls become empty cells, and nested arrays or objects are written as compact JSON. If no file (or -) is given, reads from stdin.")
+                .num_args(0..=1)
+        )
+        .arg(
+            Arg::new("delimiter")
+                .long("delimiter")
+                .value_name("CHAR")
+                .help("Field delimiter for --csv-to-json/--json-to-csv and CSV input to --md-table (default: ',', use 'tab' for TSV)")
+        )
+        .arg(
+            Arg::new("quote")
+                .long("quote")
+                .value_name("STYLE")
+                .value_parser(PossibleValuesParser::new(["necessary", "always", "non-numeric", "never"]))
+                .requires("json-to-csv")
+                .help("When --json-to-csv quotes fields (default: necessary)")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let delimiter = match matches.get_one::<String>("delimiter") {
+            Some(delimiter) => parse_delimiter(delimiter)?,
+            None => b',',
+        };
+
+        if matches.contains_id("csv-to-json") {
+            let input = read_input(matches.get_one::<String>("csv-to-json"))?;
+            let rows = csv_to_json(&input, delimiter)?;
+            println!("{}", serde_json::to_string_pretty(&rows)?);
+        } else if matches.contains_id("json-to-csv") {
+            let input = read_input(matches.get_one::<String>("json-to-csv"))?;
+            let quote = match matches.get_one::<String>("quote").map(String::as_str) {
+                Some("always") => QuoteStyle::Always,
+                Some("non-numeric") => QuoteStyle::NonNumeric,
+                Some("never") => QuoteStyle::Never,
+                _ => QuoteStyle::Necessary,
+            };
+            print!("{}", json_to_csv(&input, delimiter, quote)?);
+        }
+        Ok(())
+    }
+}
+
+pub fn parse_delimiter(text: &str) -> Result<u8, String> {
+    match text {
+        "tab" | "\\t" | "\t" => Ok(b'\t'),
+        text if text.len() == 1 => Ok(text.as_bytes()[0]),
+        _ => Err(format!("Invalid delimiter '{}'. Use a single ASCII character or 'tab'", text)),
+    }
+}
+
+fn csv_to_json(input: &str, delimiter: u8) -> Result<Vec<Value>, Box<dyn Error>> {
+    let mut reader = ReaderBuilder::new().delimiter(delimiter).from_reader(input.as_bytes());
+    let headers = reader.headers().map_err(|e| format!("Invalid CSV: {}", e))?.clone();
+    reader.records()
+        .map(|record| {
+            let record = record.map_err(|e| format!("Invalid CSV: {}", e))?;
+            let row: Map<String, Value> = headers.iter()
+                .zip(record.iter())
+                .map(|(header, field)| (header.to_string(), infer_value(field)))
+                .collect();
+            Ok(Value::Object(row))
+        })
+        .collect()
+}
+
+// The same inference the URL parser applies to query values, except that
+// numbers with leading zeros (ZIP codes, IDs) are kept as text
+fn infer_value(field: &str) -> Value {
+    let digits = field.strip_prefix('-').unwrap_or(field);
+    let leading_zero = digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0.");
+    if field.is_empty() || leading_zero {
+        Value::String(field.to_string())
+    } else if let Ok(num) = field.parse::<i64>() {
+        Value::Number(num.into())
+    } else if let Some(num) = field.parse::<f64>().ok().and_then(serde_json::Number::from_f64) {
+        // from_f64 rejects NaN and infinity, which stay text
+        Value::Number(num)
+    } else if field.eq_ignore_ascii_case("true") {
+        Value::Bool(true)
+    } else if field.eq_ignore_ascii_case("false") {
+        Value::Bool(false)
+    } else {
+        Value::String(field.to_string())
+    }
+}
+
+fn json_to_csv(input: &str, delimiter: u8, quote: QuoteStyle) -> Result<String, Box<dyn Error>> {
+    let value: Value = serde_json::from_str(input).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let Value::Array(items) = value else {
+        return Err("Expected a JSON array of objects".into());
+    };
+    let rows = items.iter()
+        .enumerate()
+        .map(|(i, item)| item.as_object().ok_or_else(|| format!("Item {} is not an object", i + 1)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut columns: Vec<&str> = Vec::new();
+    for row in &rows {
+        for key in row.keys() {
+            if !columns.contains(&key.as_str()) {
+                columns.push(key);
+            }
+        }
+    }
+
+    let mut writer = WriterBuilder::new().delimiter(delimiter).quote_style(quote).from_writer(Vec::new());
+    if !columns.is_empty() {
+        writer.write_record(&columns)?;
+    }
+    for row in rows {
+        writer.write_record(columns.iter().map(|column| match row.get(*column) {
+            None | Some(Value::Null) => String::new(),
+            Some(Value::String(text)) => text.clone(),
+            Some(other) => other.to_string(),
+        }))?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_csv_to_json() {
+        let csv = "name,age,score,active,zip,note\nAda,36,9.5,true,02134,\nLinus,54,-1,FALSE,10001,\"says \"\"hi\"\"\"\n";
+        assert_eq!(csv_to_json(csv, b',').unwrap(), vec![
+            json!({ "name": "Ada", "age": 36, "score": 9.5, "active": true, "zip": "02134", "note": "" }),
+            json!({ "name": "Linus", "age": 54, "score": -1, "active": false, "zip": 10001, "note": "says \"hi\"" }),
+        ]);
+        assert_eq!(csv_to_json("a\tb\n1\tx\n", b'\t').unwrap(), vec![json!({ "a": 1, "b": "x" })]);
+        assert!(csv_to_json("a,b\n1,2,3\n", b',').is_err());
+    }
+
+    #[test]
+    fn test_infer_value() {
+        assert_eq!(infer_value("0"), json!(0));
+        assert_eq!(infer_value("0.5"), json!(0.5));
+        assert_eq!(infer_value("1e3"), json!(1000.0));
+        assert_eq!(infer_value("-007"), json!("-007"));
+        assert_eq!(infer_value("NaN"), json!("NaN"));
+        assert_eq!(infer_value("inf"), json!("inf"));
+    }
+
+    #[test]
+    fn test_json_to_csv() {
+        let json = r#"[{"id": 1, "name": "Ada, Countess"}, {"id": 2, "tags": ["a", "b"], "name": null}]"#;
+        assert_eq!(
+            json_to_csv(json, b',', QuoteStyle::Necessary).unwrap(),
+            "id,name,tags\n1,\"Ada, Countess\",\n2,,\"[\"\"a\"\",\"\"b\"\"]\"\n"
+        );
+        assert_eq!(json_to_csv(r#"[{"a": 1, "b": "x"}]"#, b';', QuoteStyle::Always).unwrap(), "\"a\";\"b\"\n\"1\";\"x\"\n");
+        assert_eq!(json_to_csv("[]", b',', QuoteStyle::Necessary).unwrap(), "");
+        assert!(json_to_csv(r#"{"a": 1}"#, b',', QuoteStyle::Necessary).is_err());
+        assert!(json_to_csv("[1, 2]", b',', QuoteStyle::Necessary).is_err());
+    }
+
+    #[test]
+    fn test_parse_delimiter() {
+        assert_eq!(parse_delimiter(";").unwrap(), b';');
+        assert_eq!(parse_delimiter("tab").unwrap(), b'\t');
+        assert!(parse_delimiter("::").is_err());
+    }
+}
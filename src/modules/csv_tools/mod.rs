@@ -0,0 +1,427 @@
+use crate::csv_json::parse_delimiter;
+use crate::tool_module::{format_number, ToolModule};
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+use csv::{Reader, ReaderBuilder, StringRecord, Writer, WriterBuilder};
+use std::collections::{HashSet, VecDeque};
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufReader, Read, Write};
+
+pub struct CsvToolsModule;
+
+const OPERATIONS: [&str; 6] = ["view", "select", "head", "tail", "filter", "stats"];
+const DEFAULT_ROWS: usize = 10;
+// Rows read before the view fixes its column widths; later cells are cut to fit
+const VIEW_SAMPLE_ROWS: usize = 1000;
+const MAX_CELL_WIDTH: usize = 40;
+// Distinct values remembered per column before the count becomes a lower bound
+const MAX_DISTINCT: usize = 100_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operator {
+    Equal,
+    NotEqual,
+    Contains,
+    NotContains,
+    Greater,
+    GreaterEqual,
+    Less,
+    LessEqual,
+}
+
+// Longer operators first so ">=" is not read as ">"
+const OPERATORS: [(&str, Operator); 8] = [
+    ("!=", Operator::NotEqual),
+    ("!~", Operator::NotContains),
+    (">=", Operator::GreaterEqual),
+    ("<=", Operator::LessEqual),
+    ("=", Operator::Equal),
+    ("~", Operator::Contains),
+    (">", Operator::Greater),
+    ("<", Operator::Less),
+];
+
+#[derive(Debug, PartialEq)]
+struct Condition {
+    column: usize,
+    operator: Operator,
+    value: String,
+}
+
+#[derive(Debug, Default)]
+struct ColumnStats {
+    name: String,
+    count: usize,
+    empty: usize,
+    distinct: HashSet<String>,
+    // True until a non-empty value fails to parse as a number
+    numeric: bool,
+    sum: f64,
+    min: Option<f64>,
+    max: Option<f64>,
+    min_text: Option<String>,
+    max_text: Option<String>,
+}
+
+impl ToolModule for CsvToolsModule {
+    fn name(&self) -> &'static str {
+        "csv-tools"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("csv")
+                .long("csv")
+                .value_names(["OPERATION", "FILE"])
+                .num_args(1..=2)
+                .help("CSV toolkit: view, select, head, tail, filter or stats (FILE or stdin)")
+                .long_help("Work with the CSV in FILE, or stdin when no file (or -) is given. Rows are streamed, so large files are never loaded whole:\n- view: print as an aligned table (widths come from the first 1000 rows; longer cells are cut)\n- select: keep the --columns given, in that order\n- head: the first --rows rows (default: 10)\n- tail: the last --rows rows (default: 10)\n- filter: rows matching every --where condition\n- stats: per column, the number of values, empty cells, distinct values, min, max and mean\n\nselect, head, tail and filter write CSV with the header row, so they can be piped into each other. Use --delimiter for other separators; .tsv files default to tabs.")
+        )
+        .arg(
+            Arg::new("columns")
+                .long("columns")
+                .value_name("LIST")
+                .help("Columns for --csv select, by header or 1-based number, comma-separated, in output order")
+                .requires("csv")
+        )
+        .arg(
+            Arg::new("rows")
+                .long("rows")
+                .value_name("N")
+                .value_parser(value_parser!(usize))
+                .help("Number of rows for --csv head and tail (default: 10)")
+                .requires("csv")
+        )
+        .arg(
+            Arg::new("where")
+                .long("where")
+                .value_name("CONDITION")
+                .action(ArgAction::Append)
+                .help("Condition for --csv filter, like status=active, age>=30 or name~smith (repeatable, all must match)")
+                .long_help("Condition for --csv filter, written COLUMN OPERATOR VALUE. = and != compare text exactly, ~ and !~ test whether the cell contains the value (ignoring case), and >, >=, < and <= compare numerically when both sides are numbers, as text otherwise. Repeat --where to require several conditions.")
+                .requires("csv")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(values) = matches.get_many::<String>("csv") {
+            let values: Vec<&String> = values.collect();
+            let operation = values[0].to_lowercase();
+            if !OPERATIONS.contains(&operation.as_str()) {
+                return Err(format!("Unknown operation '{}'. Use one of: {}", operation, OPERATIONS.join(", ")).into());
+            }
+            let path = values.get(1).map(|path| path.as_str()).filter(|path| *path != "-");
+            let delimiter = match matches.get_one::<String>("delimiter") {
+                Some(delimiter) => parse_delimiter(delimiter)?,
+                None if path.is_some_and(|path| path.to_lowercase().ends_with(".tsv")) => b'\t',
+                None => b',',
+            };
+            let input: Box<dyn Read> = match path {
+                Some(path) => Box::new(BufReader::new(File::open(path).map_err(|e| format!("Failed to read '{}': {}", path, e))?)),
+                None => Box::new(io::stdin().lock()),
+            };
+            let mut reader = ReaderBuilder::new().delimiter(delimiter).flexible(true).from_reader(input);
+            let rows = matches.get_one::<usize>("rows").copied().unwrap_or(DEFAULT_ROWS);
+            let stdout = io::stdout().lock();
+            match operation.as_str() {
+                "view" => view(&mut reader, stdout)?,
+                "stats" => print_stats(&column_stats(&mut reader)?, stdout)?,
+                operation => {
+                    let mut writer = WriterBuilder::new().delimiter(delimiter).from_writer(stdout);
+                    match operation {
+                        "select" => {
+                            let columns = matches.get_one::<String>("columns").ok_or("--csv select needs --columns, e.g. --columns name,email")?;
+                            select(&mut reader, &mut writer, columns)?
+                        }
+                        "head" => head(&mut reader, &mut writer, rows)?,
+                        "tail" => tail(&mut reader, &mut writer, rows)?,
+                        _ => {
+                            let conditions: Vec<&String> = matches.get_many::<String>("where").ok_or("--csv filter needs --where, e.g. --where status=active")?.collect();
+                            filter(&mut reader, &mut writer, &conditions)?
+                        }
+                    }
+                    writer.flush()?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn csv_error(e: csv::Error) -> String {
+    format!("Invalid CSV: {}", e)
+}
+
+// By header (ignoring case) or 1-based column number
+fn column_index(headers: &StringRecord, column: &str) -> Result<usize, String> {
+    let column = column.trim();
+    if let Some(index) = headers.iter().position(|header| header.trim().eq_ignore_ascii_case(column)) {
+        return Ok(index);
+    }
+    match column.parse::<usize>() {
+        Ok(number) if (1..=headers.len()).contains(&number) => Ok(number - 1),
+        _ => Err(format!("No column '{}'; the columns are: {}", column, headers.iter().collect::<Vec<_>>().join(", "))),
+    }
+}
+
+fn select<R: Read, W: Write>(reader: &mut Reader<R>, writer: &mut Writer<W>, columns: &str) -> Result<(), Box<dyn Error>> {
+    let headers = reader.headers().map_err(csv_error)?.clone();
+    let indices = columns.split(',').map(|column| column_index(&headers, column)).collect::<Result<Vec<usize>, String>>()?;
+    let pick = |record: &StringRecord| -> StringRecord { indices.iter().map(|i| record.get(*i).unwrap_or("")).collect() };
+    writer.write_record(&pick(&headers))?;
+    for record in reader.records() {
+        writer.write_record(&pick(&record.map_err(csv_error)?))?;
+    }
+    Ok(())
+}
+
+fn head<R: Read, W: Write>(reader: &mut Reader<R>, writer: &mut Writer<W>, rows: usize) -> Result<(), Box<dyn Error>> {
+    writer.write_record(reader.headers().map_err(csv_error)?)?;
+    for record in reader.records().take(rows) {
+        writer.write_record(&record.map_err(csv_error)?)?;
+    }
+    Ok(())
+}
+
+// Only the last `rows` records are held in memory
+fn tail<R: Read, W: Write>(reader: &mut Reader<R>, writer: &mut Writer<W>, rows: usize) -> Result<(), Box<dyn Error>> {
+    writer.write_record(reader.headers().map_err(csv_error)?)?;
+    let mut last = VecDeque::with_capacity(rows);
+    for record in reader.records() {
+        if last.len() == rows {
+            last.pop_front();
+        }
+        if rows > 0 {
+            last.push_back(record.map_err(csv_error)?);
+        }
+    }
+    for record in last {
+        writer.write_record(&record)?;
+    }
+    Ok(())
+}
+
+fn parse_condition(headers: &StringRecord, text: &str) -> Result<Condition, String> {
+    // The earliest operator in the text, so values may contain operator characters
+    let (position, symbol, operator) = OPERATORS
+        .iter()
+        .filter_map(|(symbol, operator)| text.find(symbol).map(|position| (position, *symbol, *operator)))
+        .min_by_key(|(position, symbol, _)| (*position, usize::MAX - symbol.len()))
+        .ok_or_else(|| format!("Invalid condition '{}'. Use COLUMN=VALUE, or one of != ~ !~ > >= < <=", text))?;
+    Ok(Condition {
+        column: column_index(headers, &text[..position])?,
+        operator,
+        value: text[position + symbol.len()..].trim().to_string(),
+    })
+}
+
+fn matches_condition(record: &StringRecord, condition: &Condition) -> bool {
+    let cell = record.get(condition.column).unwrap_or("").trim();
+    let value = condition.value.as_str();
+    let contains = || cell.to_lowercase().contains(&value.to_lowercase());
+    let ordering = || match (cell.parse::<f64>(), value.parse::<f64>()) {
+        (Ok(cell), Ok(value)) => cell.partial_cmp(&value),
+        _ => Some(cell.cmp(value)),
+    };
+    match condition.operator {
+        Operator::Equal => cell == value,
+        Operator::NotEqual => cell != value,
+        Operator::Contains => contains(),
+        Operator::NotContains => !contains(),
+        Operator::Greater => ordering().is_some_and(|order| order.is_gt()),
+        Operator::GreaterEqual => ordering().is_some_and(|order| order.is_ge()),
+        Operator::Less => ordering().is_some_and(|order| order.is_lt()),
+        Operator::LessEqual => ordering().is_some_and(|order| order.is_le()),
+    }
+}
+
+fn filter<R: Read, W: Write>(reader: &mut Reader<R>, writer: &mut Writer<W>, conditions: &[&String]) -> Result<(), Box<dyn Error>> {
+    let headers = reader.headers().map_err(csv_error)?.clone();
+    let conditions = conditions.iter().map(|text| parse_condition(&headers, text)).collect::<Result<Vec<Condition>, String>>()?;
+    writer.write_record(&headers)?;
+    for record in reader.records() {
+        let record = record.map_err(csv_error)?;
+        if conditions.iter().all(|condition| matches_condition(&record, condition)) {
+            writer.write_record(&record)?;
+        }
+    }
+    Ok(())
+}
+
+// A finite number, except that leading zeros (ZIP codes, IDs) mark text, as in --csv-to-json
+fn stat_number(cell: &str) -> Option<f64> {
+    let digits = cell.strip_prefix('-').unwrap_or(cell);
+    if digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0.") {
+        return None;
+    }
+    cell.parse::<f64>().ok().filter(|number| number.is_finite())
+}
+
+fn column_stats<R: Read>(reader: &mut Reader<R>) -> Result<Vec<ColumnStats>, Box<dyn Error>> {
+    let headers = reader.headers().map_err(csv_error)?.clone();
+    let mut stats: Vec<ColumnStats> = headers.iter().map(|name| ColumnStats { name: name.to_string(), numeric: true, ..Default::default() }).collect();
+    for record in reader.records() {
+        let record = record.map_err(csv_error)?;
+        for (column, stat) in stats.iter_mut().enumerate() {
+            let cell = record.get(column).unwrap_or("").trim();
+            if cell.is_empty() {
+                stat.empty += 1;
+                continue;
+            }
+            stat.count += 1;
+            if stat.distinct.len() < MAX_DISTINCT {
+                stat.distinct.insert(cell.to_string());
+            }
+            match stat_number(cell) {
+                Some(number) if stat.numeric => {
+                    stat.sum += number;
+                    stat.min = Some(stat.min.map_or(number, |min| min.min(number)));
+                    stat.max = Some(stat.max.map_or(number, |max| max.max(number)));
+                }
+                _ => stat.numeric = false,
+            }
+            if stat.min_text.as_deref().is_none_or(|min| cell < min) {
+                stat.min_text = Some(cell.to_string());
+            }
+            if stat.max_text.as_deref().is_none_or(|max| cell > max) {
+                stat.max_text = Some(cell.to_string());
+            }
+        }
+    }
+    Ok(stats)
+}
+
+fn print_stats(stats: &[ColumnStats], out: impl Write) -> Result<(), Box<dyn Error>> {
+    let header: Vec<String> = ["column", "count", "empty", "distinct", "min", "max", "mean"].map(String::from).to_vec();
+    let rows: Vec<Vec<String>> = stats
+        .iter()
+        .map(|stat| {
+            let numeric = stat.numeric && stat.count > 0;
+            let distinct = if stat.distinct.len() >= MAX_DISTINCT { format!("{}+", MAX_DISTINCT) } else { stat.distinct.len().to_string() };
+            let (min, max) = if numeric {
+                (stat.min.map(|min| format_number(min, 4)), stat.max.map(|max| format_number(max, 4)))
+            } else {
+                (stat.min_text.clone(), stat.max_text.clone())
+            };
+            let mean = if numeric { format_number(stat.sum / stat.count as f64, 4) } else { String::new() };
+            vec![stat.name.clone(), stat.count.to_string(), stat.empty.to_string(), distinct, min.unwrap_or_default(), max.unwrap_or_default(), mean]
+        })
+        .collect();
+    let widths = column_widths(&header, &rows);
+    print_table(out, &header, rows.into_iter().map(Ok), &widths)
+}
+
+fn column_widths(header: &[String], rows: &[Vec<String>]) -> Vec<usize> {
+    (0..header.len())
+        .map(|i| rows.iter().filter_map(|row| row.get(i)).chain([&header[i]]).map(|cell| cell.chars().count()).max().unwrap_or(0).min(MAX_CELL_WIDTH))
+        .collect()
+}
+
+// Cells cut to the column width, numbers right-aligned
+fn table_line(cells: &[String], widths: &[usize]) -> String {
+    let cells: Vec<String> = widths
+        .iter()
+        .enumerate()
+        .map(|(i, &width)| {
+            let cell = cells.get(i).map_or("", String::as_str).replace('\n', "\\n");
+            let cell = if cell.chars().count() > width { format!("{}…", cell.chars().take(width.saturating_sub(1)).collect::<String>()) } else { cell };
+            if cell.parse::<f64>().is_ok() { format!("{:>width$}", cell) } else { format!("{:<width$}", cell) }
+        })
+        .collect();
+    format!("| {} |\n", cells.join(" | "))
+}
+
+fn print_table(mut out: impl Write, header: &[String], rows: impl Iterator<Item = Result<Vec<String>, String>>, widths: &[usize]) -> Result<(), Box<dyn Error>> {
+    let border = format!("+{}+\n", widths.iter().map(|w| "-".repeat(w + 2)).collect::<Vec<_>>().join("+"));
+    out.write_all(border.as_bytes())?;
+    out.write_all(table_line(header, widths).as_bytes())?;
+    out.write_all(border.as_bytes())?;
+    for row in rows {
+        out.write_all(table_line(&row?, widths).as_bytes())?;
+    }
+    out.write_all(border.as_bytes())?;
+    Ok(())
+}
+
+fn view<R: Read>(reader: &mut Reader<R>, out: impl Write) -> Result<(), Box<dyn Error>> {
+    let header: Vec<String> = reader.headers().map_err(csv_error)?.iter().map(String::from).collect();
+    let mut records = reader.records().map(|record| record.map(|record| record.iter().map(String::from).collect::<Vec<String>>()).map_err(csv_error));
+    let sample = records.by_ref().take(VIEW_SAMPLE_ROWS).collect::<Result<Vec<_>, String>>()?;
+    let widths = column_widths(&header, &sample);
+    print_table(out, &header, sample.into_iter().map(Ok).chain(records), &widths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PEOPLE: &str = "name,age,city\nAda,36,London\nGrace,85,New York\nAlan,41,\nLinus,,Helsinki\n";
+
+    fn run(operation: impl Fn(&mut Reader<&[u8]>, &mut Writer<Vec<u8>>) -> Result<(), Box<dyn Error>>) -> String {
+        let mut reader = ReaderBuilder::new().flexible(true).from_reader(PEOPLE.as_bytes());
+        let mut writer = Writer::from_writer(Vec::new());
+        operation(&mut reader, &mut writer).unwrap();
+        String::from_utf8(writer.into_inner().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_select_head_tail() {
+        assert_eq!(run(|r, w| select(r, w, "city, 1")), "city,name\nLondon,Ada\nNew York,Grace\n,Alan\nHelsinki,Linus\n");
+        assert_eq!(run(|r, w| head(r, w, 1)), "name,age,city\nAda,36,London\n");
+        assert_eq!(run(|r, w| tail(r, w, 2)), "name,age,city\nAlan,41,\nLinus,,Helsinki\n");
+        assert_eq!(run(|r, w| tail(r, w, 0)), "name,age,city\n");
+    }
+
+    #[test]
+    fn test_filter() {
+        let conditions = |texts: &[&str]| texts.iter().map(|text| text.to_string()).collect::<Vec<String>>();
+        let older = conditions(&["age>=41"]);
+        assert_eq!(run(|r, w| filter(r, w, &older.iter().collect::<Vec<_>>())), "name,age,city\nGrace,85,New York\nAlan,41,\n");
+        let both = conditions(&["city~LON", "age!=85"]);
+        assert_eq!(run(|r, w| filter(r, w, &both.iter().collect::<Vec<_>>())), "name,age,city\nAda,36,London\n");
+        let headers = StringRecord::from(vec!["name", "url"]);
+        assert_eq!(parse_condition(&headers, "url=https://x.io/?a=b").unwrap().value, "https://x.io/?a=b");
+        assert_eq!(parse_condition(&headers, "name>=B").unwrap().operator, Operator::GreaterEqual);
+        assert!(parse_condition(&headers, "name").is_err());
+        assert!(parse_condition(&headers, "email=x").is_err());
+    }
+
+    #[test]
+    fn test_column_stats() {
+        let mut reader = ReaderBuilder::new().flexible(true).from_reader(PEOPLE.as_bytes());
+        let stats = column_stats(&mut reader).unwrap();
+        let age = &stats[1];
+        assert_eq!((age.count, age.empty, age.distinct.len()), (3, 1, 3));
+        assert!(age.numeric);
+        assert_eq!((age.min, age.max, format_number(age.sum / age.count as f64, 4).as_str()), (Some(36.0), Some(85.0), "54"));
+        let city = &stats[2];
+        assert!(!city.numeric);
+        assert_eq!((city.min_text.as_deref(), city.max_text.as_deref()), (Some("Helsinki"), Some("New York")));
+        assert_eq!(format_number(2.0 / 3.0, 4), "0.6667");
+    }
+
+    #[test]
+    fn test_stats_keep_leading_zeros() {
+        let mut reader = ReaderBuilder::new().from_reader("zip,price\n00123,0.5\n02134,0\n10001,-0.25\n".as_bytes());
+        let stats = column_stats(&mut reader).unwrap();
+        let mut out = Vec::new();
+        print_stats(&stats, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(!stats[0].numeric);
+        assert_eq!(out.lines().nth(3), Some("| zip    |     3 |     0 |        3 | 00123 | 10001 |        |"));
+        assert!(stats[1].numeric);
+        assert_eq!((stats[1].min, stats[1].max), (Some(-0.25), Some(0.5)));
+    }
+
+    #[test]
+    fn test_view() {
+        let mut reader = ReaderBuilder::new().from_reader("id,note\n7,a very long note\n".as_bytes());
+        let mut out = Vec::new();
+        view(&mut reader, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "+----+------------------+\n| id | note             |\n+----+------------------+\n|  7 | a very long note |\n+----+------------------+\n"
+        );
+        assert_eq!(table_line(&["abcdef".to_string()], &[4]), "| abc… |\n");
+    }
+}
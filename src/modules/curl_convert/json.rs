@@ -0,0 +1,143 @@
+// A JSON body kept in its original key order, printed as a JSON or Python literal
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    // As serde_json prints it
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+pub struct Syntax {
+    pub indent: &'static str,
+    pub null: &'static str,
+    pub true_: &'static str,
+    pub false_: &'static str,
+    pub trailing_comma: bool,
+}
+
+pub const JSON_RUST: Syntax = Syntax { indent: "    ", null: "null", true_: "true", false_: "false", trailing_comma: false };
+pub const JSON_JS: Syntax = Syntax { indent: "  ", ..JSON_RUST };
+pub const PYTHON: Syntax = Syntax { indent: "    ", null: "None", true_: "True", false_: "False", trailing_comma: true };
+
+// A double-quoted literal; JSON string syntax is valid Python and JavaScript too
+pub fn quote(text: &str) -> String {
+    serde_json::to_string(text).unwrap_or_default()
+}
+
+impl Json {
+    pub fn parse(text: &str) -> Option<Json> {
+        serde_json::from_str(text).ok()
+    }
+
+    // Multi-line from the second line on, indented as if the first line sat at depth
+    pub fn render(&self, syntax: &Syntax, depth: usize) -> String {
+        let inner = syntax.indent.repeat(depth + 1);
+        let outer = syntax.indent.repeat(depth);
+        let block = |open: &str, items: Vec<String>, close: &str| {
+            if items.is_empty() {
+                return format!("{}{}", open, close);
+            }
+            let last = if syntax.trailing_comma { "," } else { "" };
+            format!("{}\n{}{}{}\n{}{}", open, inner, items.join(&format!(",\n{}", inner)), last, outer, close)
+        };
+        match self {
+            Json::Null => syntax.null.to_string(),
+            Json::Bool(true) => syntax.true_.to_string(),
+            Json::Bool(false) => syntax.false_.to_string(),
+            Json::Number(number) => number.clone(),
+            Json::String(text) => quote(text),
+            Json::Array(items) => block("[", items.iter().map(|item| item.render(syntax, depth + 1)).collect(), "]"),
+            Json::Object(entries) => block(
+                "{",
+                entries.iter().map(|(key, item)| format!("{}: {}", quote(key), item.render(syntax, depth + 1))).collect(),
+                "}",
+            ),
+        }
+    }
+}
+
+struct JsonVisitor;
+
+impl<'de> Visitor<'de> for JsonVisitor {
+    type Value = Json;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a JSON value")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Json, E> {
+        Ok(Json::Null)
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<Json, E> {
+        Ok(Json::Bool(value))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Json, E> {
+        Ok(Json::Number(value.to_string()))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Json, E> {
+        Ok(Json::Number(value.to_string()))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Json, E> {
+        Ok(Json::Number(serde_json::Number::from_f64(value).map(|number| number.to_string()).unwrap_or_else(|| "null".to_string())))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Json, E> {
+        Ok(Json::String(value.to_string()))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Json, A::Error> {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(Json::Array(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Json, A::Error> {
+        let mut entries = Vec::new();
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(Json::Object(entries))
+    }
+}
+
+impl<'de> Deserialize<'de> for Json {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Json, D::Error> {
+        deserializer.deserialize_any(JsonVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_keeps_key_order() {
+        let json = Json::parse("{\"z\": 1, \"a\": [true, null, 2.5], \"m\": {}}").unwrap();
+        let Json::Object(entries) = &json else { panic!("expected an object") };
+        let keys: Vec<&str> = entries.iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(keys, ["z", "a", "m"]);
+        assert!(Json::parse("{\"a\": }").is_none());
+    }
+
+    #[test]
+    fn test_render() {
+        let json = Json::parse("{\"ok\": false, \"tags\": [\"a\", null], \"empty\": []}").unwrap();
+        assert_eq!(json.render(&JSON_JS, 0), "{\n  \"ok\": false,\n  \"tags\": [\n    \"a\",\n    null\n  ],\n  \"empty\": []\n}");
+        assert_eq!(
+            json.render(&PYTHON, 1),
+            "{\n        \"ok\": False,\n        \"tags\": [\n            \"a\",\n            None,\n        ],\n        \"empty\": [],\n    }"
+        );
+    }
+}
@@ -0,0 +1,576 @@
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use std::error::Error;
+use std::io::{self, Read};
+
+mod json;
+use json::{quote, Json, JSON_JS, JSON_RUST, PYTHON};
+
+pub struct CurlConvertModule;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Language {
+    Rust,
+    Python,
+    JavaScript,
+}
+
+#[derive(Debug, PartialEq)]
+enum Body {
+    Json(Json),
+    // Decoded key/value pairs of an urlencoded body
+    Form(Vec<(String, String)>),
+    Text(String),
+    // -d @path
+    File(String),
+    Multipart(Vec<Part>),
+}
+
+#[derive(Debug, PartialEq)]
+struct Part {
+    name: String,
+    // A path when file is set
+    value: String,
+    file: bool,
+}
+
+#[derive(Debug, PartialEq)]
+struct CurlRequest {
+    method: String,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Option<Body>,
+    basic_auth: Option<(String, String)>,
+    insecure: bool,
+    timeout: Option<f64>,
+    // Options with no equivalent in the generated code
+    ignored: Vec<String>,
+}
+
+// Options that read the next word as their value; anything else is a flag
+const VALUE_OPTIONS: [&str; 44] = [
+    "-X", "--request", "-H", "--header", "-d", "--data", "--data-ascii", "--data-binary", "--data-raw", "--data-urlencode",
+    "--json", "-F", "--form", "--form-string", "-u", "--user", "-A", "--user-agent", "-e", "--referer", "-b", "--cookie",
+    "-c", "--cookie-jar", "-m", "--max-time", "--connect-timeout", "--url", "-o", "--output", "-w", "--write-out", "-x",
+    "--proxy", "-T", "--upload-file", "-E", "--cert", "--key", "--cacert", "--retry", "-r", "--range", "--url-query",
+];
+
+// Flags that change nothing about the request the snippets send
+const NEUTRAL_FLAGS: [&str; 20] = [
+    "-s", "--silent", "-S", "--show-error", "-v", "--verbose", "-i", "--include", "-L", "--location", "-f", "--fail",
+    "--fail-with-body", "--compressed", "-#", "--progress-bar", "--no-progress-meter", "-N", "--no-buffer", "--globoff",
+];
+
+impl ToolModule for CurlConvertModule {
+    fn name(&self) -> &'static str {
+        "curl-convert"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("curl-to")
+                .long("curl-to")
+                .value_names(["LANGUAGE", "COMMAND"])
+                .num_args(1..=2)
+                .help("Convert a curl command into a rust (reqwest), python (requests) or js (fetch) snippet (COMMAND or stdin)")
+                .long_help("Turn a curl command copied from API docs or the browser into code: rust prints an async reqwest program, python a requests call and js a fetch call. Understands -X, -H, -d/--data-raw/--data-binary/--data-urlencode, --json, -F, -u, -A, -e, -b, -G, -I, -k and -m; JSON bodies become native objects and urlencoded ones key/value pairs. Quote the whole command, or leave it out (or use -) to read it from stdin, which keeps multi-line commands with backslash continuations intact. Options without an equivalent are listed on stderr.")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(values) = matches.get_many::<String>("curl-to") {
+            let values: Vec<&String> = values.collect();
+            let language = parse_language(values[0])?;
+            let command = match values.get(1).map(|command| command.as_str()) {
+                Some(command) if command != "-" => command.to_string(),
+                _ => {
+                    let mut buffer = String::new();
+                    io::stdin().read_to_string(&mut buffer)?;
+                    buffer
+                }
+            };
+            let request = parse_curl(&command)?;
+            if !request.ignored.is_empty() {
+                eprintln!("{}", format!("⚠️  Not converted: {}", request.ignored.join(", ")).yellow());
+            }
+            print!("{}", generate(&request, language));
+        }
+        Ok(())
+    }
+}
+
+fn parse_language(name: &str) -> Result<Language, String> {
+    match name.to_lowercase().as_str() {
+        "rust" | "rs" | "reqwest" => Ok(Language::Rust),
+        "python" | "py" | "requests" => Ok(Language::Python),
+        "js" | "javascript" | "node" | "fetch" => Ok(Language::JavaScript),
+        _ => Err(format!("Unknown language '{}'. Use rust, python or js", name)),
+    }
+}
+
+// One word can hold several short flags (-sSL) or a flag and its value (-XPOST, --data=x)
+fn split_option(word: &str) -> Vec<(String, Option<String>)> {
+    if let Some(long) = word.strip_prefix("--") {
+        return match long.split_once('=') {
+            Some((name, value)) if VALUE_OPTIONS.contains(&format!("--{}", name).as_str()) => {
+                vec![(format!("--{}", name), Some(value.to_string()))]
+            }
+            _ => vec![(word.to_string(), None)],
+        };
+    }
+    let mut options = Vec::new();
+    for (index, c) in word.char_indices().skip(1) {
+        let option = format!("-{}", c);
+        if VALUE_OPTIONS.contains(&option.as_str()) {
+            let rest = &word[index + c.len_utf8()..];
+            options.push((option, (!rest.is_empty()).then(|| rest.to_string())));
+            break;
+        }
+        options.push((option, None));
+    }
+    options
+}
+
+fn parse_curl(command: &str) -> Result<CurlRequest, String> {
+    // Backslash continuations would otherwise leave empty words behind
+    let joined = command.replace("\\\r\n", " ").replace("\\\n", " ");
+    let mut words = shlex::split(&joined).ok_or("Unbalanced quotes in the curl command")?.into_iter();
+    let mut url = None;
+    let mut method = None;
+    let mut headers: Vec<(String, String)> = Vec::new();
+    let mut data = Vec::new();
+    let mut data_file = None;
+    let mut parts = Vec::new();
+    let mut basic_auth = None;
+    let (mut json, mut get, mut head, mut insecure) = (false, false, false, false);
+    let mut timeout = None;
+    let mut ignored = Vec::new();
+    let mut first = true;
+    while let Some(word) = words.next() {
+        if std::mem::take(&mut first) && word == "curl" {
+            continue;
+        }
+        if !word.starts_with('-') || word == "-" {
+            url = Some(word);
+            continue;
+        }
+        for (option, inline) in split_option(&word) {
+            let value = if VALUE_OPTIONS.contains(&option.as_str()) {
+                Some(inline.or_else(|| words.next()).ok_or_else(|| format!("{} needs a value", option))?)
+            } else {
+                None
+            };
+            let value = value.unwrap_or_default();
+            match option.as_str() {
+                "--url" => url = Some(value),
+                "-X" | "--request" => method = Some(value.to_uppercase()),
+                "-H" | "--header" => {
+                    let (name, value) = value.split_once(':').ok_or_else(|| format!("Invalid header '{}', expected Name: value", value))?;
+                    headers.push((name.trim().to_string(), value.trim().to_string()));
+                }
+                "-d" | "--data" | "--data-ascii" | "--data-binary" if value.starts_with('@') => data_file = Some(value[1..].to_string()),
+                "-d" | "--data" | "--data-ascii" | "--data-binary" | "--data-raw" => data.push(value),
+                "--data-urlencode" => data.push(urlencode_data(&value)),
+                "--json" => {
+                    json = true;
+                    data.push(value);
+                }
+                "-F" | "--form" | "--form-string" => {
+                    let (name, value) = value.split_once('=').ok_or_else(|| format!("Invalid form field '{}', expected name=value", value))?;
+                    let path = value.strip_prefix('@').filter(|_| option != "--form-string");
+                    parts.push(match path {
+                        // Drop ;type=... and ;filename=... modifiers
+                        Some(path) => Part { name: name.to_string(), value: path.split(';').next().unwrap_or(path).to_string(), file: true },
+                        None => Part { name: name.to_string(), value: value.to_string(), file: false },
+                    });
+                }
+                "-u" | "--user" => {
+                    let (user, password) = value.split_once(':').unwrap_or((&value, ""));
+                    basic_auth = Some((user.to_string(), password.to_string()));
+                }
+                "-A" | "--user-agent" => headers.push(("User-Agent".to_string(), value)),
+                "-e" | "--referer" => headers.push(("Referer".to_string(), value)),
+                // Without an = the value names a cookie file
+                "-b" | "--cookie" if value.contains('=') => headers.push(("Cookie".to_string(), value)),
+                "-G" | "--get" => get = true,
+                "-I" | "--head" => head = true,
+                "-k" | "--insecure" => insecure = true,
+                "-m" | "--max-time" => timeout = Some(value.parse::<f64>().map_err(|_| format!("Invalid --max-time '{}'", value))?),
+                option if NEUTRAL_FLAGS.contains(&option) => {}
+                option if VALUE_OPTIONS.contains(&option) => ignored.push(format!("{} {}", option, value)),
+                option => ignored.push(option.to_string()),
+            }
+        }
+    }
+    let mut url = url.ok_or("No URL found in the curl command")?;
+    // curl assumes http:// for a bare host
+    if !url.contains("://") {
+        url = format!("http://{}", url);
+    }
+    if json {
+        for name in ["Content-Type", "Accept"] {
+            if header(&headers, name).is_none() {
+                headers.push((name.to_string(), "application/json".to_string()));
+            }
+        }
+    }
+    let mut body = None;
+    if get {
+        if !data.is_empty() {
+            url.push(if url.contains('?') { '&' } else { '?' });
+            url.push_str(&data.join("&"));
+        }
+    } else if !parts.is_empty() {
+        body = Some(Body::Multipart(parts));
+    } else if data_file.is_some() || !data.is_empty() {
+        let content_type = header(&headers, "Content-Type").map(str::to_lowercase);
+        let text = data.join("&");
+        let parsed = match (data_file, content_type.clone()) {
+            (Some(path), _) => Body::File(path),
+            (None, Some(content_type)) if content_type.contains("json") => match Json::parse(&text) {
+                Some(value) => Body::Json(value),
+                None => Body::Text(text),
+            },
+            (None, content_type) if content_type.as_deref().is_none_or(|content_type| content_type.contains("x-www-form-urlencoded")) => {
+                match parse_form(&text) {
+                    Some(pairs) => Body::Form(pairs),
+                    None => Body::Text(text),
+                }
+            }
+            _ => Body::Text(text),
+        };
+        // curl labels -d bodies as a form unless told otherwise; the clients only do so for key/value pairs
+        if content_type.is_none() && matches!(parsed, Body::Text(_) | Body::File(_)) {
+            headers.push(("Content-Type".to_string(), "application/x-www-form-urlencoded".to_string()));
+        }
+        body = Some(parsed);
+    }
+    let method = method.unwrap_or_else(|| {
+        match (head, &body) {
+            (true, _) => "HEAD",
+            (false, Some(_)) => "POST",
+            (false, None) => "GET",
+        }
+        .to_string()
+    });
+    Ok(CurlRequest { method, url, headers, body, basic_auth, insecure, timeout, ignored })
+}
+
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers.iter().find(|(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, value)| value.as_str())
+}
+
+// --data-urlencode takes content, =content or name=content and encodes only the content
+fn urlencode_data(value: &str) -> String {
+    let encode = |content: &str| url::form_urlencoded::byte_serialize(content.as_bytes()).collect::<String>();
+    match value.split_once('=') {
+        Some(("", content)) => encode(content),
+        Some((name, content)) => format!("{}={}", name, encode(content)),
+        None => encode(value),
+    }
+}
+
+// Some only when every piece is name=value, so arbitrary text stays a raw body
+fn parse_form(text: &str) -> Option<Vec<(String, String)>> {
+    if text.split('&').any(|piece| !piece.contains('=') || piece.contains(char::is_whitespace)) {
+        return None;
+    }
+    Some(url::form_urlencoded::parse(text.as_bytes()).into_owned().collect())
+}
+
+fn has_duplicate_keys(pairs: &[(String, String)]) -> bool {
+    pairs.iter().enumerate().any(|(index, (key, _))| pairs[..index].iter().any(|(other, _)| other == key))
+}
+
+fn generate(request: &CurlRequest, language: Language) -> String {
+    match language {
+        Language::Rust => generate_rust(request),
+        Language::Python => generate_python(request),
+        Language::JavaScript => generate_javascript(request),
+    }
+}
+
+// reqwest and requests set this header themselves for JSON bodies
+fn sent_headers(request: &CurlRequest, implied_json: bool) -> Vec<&(String, String)> {
+    let json_body = implied_json && matches!(request.body, Some(Body::Json(_)));
+    request
+        .headers
+        .iter()
+        .filter(|(name, value)| !(json_body && name.eq_ignore_ascii_case("Content-Type") && value.eq_ignore_ascii_case("application/json")))
+        .collect()
+}
+
+fn generate_rust(request: &CurlRequest) -> String {
+    let mut code = String::from("use std::error::Error;\n");
+    if matches!(request.body, Some(Body::Json(_))) {
+        code.push_str("use serde_json::json;\n");
+    }
+    if request.timeout.is_some() {
+        code.push_str("use std::time::Duration;\n");
+    }
+    code.push_str("\n#[tokio::main]\nasync fn main() -> Result<(), Box<dyn Error>> {\n");
+    if request.insecure || request.timeout.is_some() {
+        code.push_str("    let client = reqwest::Client::builder()\n");
+        if request.insecure {
+            code.push_str("        .danger_accept_invalid_certs(true)\n");
+        }
+        if let Some(seconds) = request.timeout {
+            code.push_str(&format!("        .timeout(Duration::from_secs_f64({:?}))\n", seconds));
+        }
+        code.push_str("        .build()?;\n");
+    } else {
+        code.push_str("    let client = reqwest::Client::new();\n");
+    }
+    if let Some(Body::Multipart(parts)) = &request.body {
+        code.push_str("    let form = reqwest::multipart::Form::new()");
+        for part in parts {
+            if part.file {
+                code.push_str(&format!(
+                    "\n        .part({:?}, reqwest::multipart::Part::bytes(std::fs::read({:?})?).file_name({:?}))",
+                    part.name, part.value, part.value
+                ));
+            } else {
+                code.push_str(&format!("\n        .text({:?}, {:?})", part.name, part.value));
+            }
+        }
+        code.push_str(";\n");
+    }
+    code.push_str("    let response = client\n");
+    match request.method.as_str() {
+        "GET" | "POST" | "PUT" | "PATCH" | "DELETE" | "HEAD" => {
+            code.push_str(&format!("        .{}({:?})\n", request.method.to_lowercase(), request.url));
+        }
+        method => code.push_str(&format!("        .request(reqwest::Method::from_bytes(b{:?})?, {:?})\n", method, request.url)),
+    }
+    for (name, value) in sent_headers(request, true) {
+        code.push_str(&format!("        .header({:?}, {:?})\n", name, value));
+    }
+    if let Some((user, password)) = &request.basic_auth {
+        code.push_str(&format!("        .basic_auth({:?}, Some({:?}))\n", user, password));
+    }
+    match &request.body {
+        Some(Body::Json(value)) => code.push_str(&format!("        .json(&json!({}))\n", value.render(&JSON_RUST, 2))),
+        Some(Body::Form(pairs)) => {
+            let pairs: Vec<String> = pairs.iter().map(|(key, value)| format!("({:?}, {:?})", key, value)).collect();
+            code.push_str(&format!("        .form(&[{}])\n", pairs.join(", ")));
+        }
+        Some(Body::Text(text)) => code.push_str(&format!("        .body({:?})\n", text)),
+        Some(Body::File(path)) => code.push_str(&format!("        .body(std::fs::read({:?})?)\n", path)),
+        Some(Body::Multipart(_)) => code.push_str("        .multipart(form)\n"),
+        None => {}
+    }
+    code.push_str("        .send()\n        .await?;\n");
+    code.push_str("    println!(\"{}\", response.status());\n    println!(\"{}\", response.text().await?);\n    Ok(())\n}\n");
+    code
+}
+
+fn generate_python(request: &CurlRequest) -> String {
+    let mut code = String::from("import requests\n\n");
+    let mut arguments = vec![quote(&request.url)];
+    let headers = sent_headers(request, true);
+    if !headers.is_empty() {
+        code.push_str("headers = {\n");
+        for (name, value) in headers {
+            code.push_str(&format!("    {}: {},\n", quote(name), quote(value)));
+        }
+        code.push_str("}\n");
+        arguments.push("headers=headers".to_string());
+    }
+    match &request.body {
+        Some(Body::Json(value)) => {
+            code.push_str(&format!("json_data = {}\n", value.render(&PYTHON, 0)));
+            arguments.push("json=json_data".to_string());
+        }
+        Some(Body::Form(pairs)) => {
+            // A list of tuples keeps repeated keys
+            if has_duplicate_keys(pairs) {
+                let pairs: Vec<String> = pairs.iter().map(|(key, value)| format!("    ({}, {}),\n", quote(key), quote(value))).collect();
+                code.push_str(&format!("data = [\n{}]\n", pairs.concat()));
+            } else {
+                let pairs: Vec<String> = pairs.iter().map(|(key, value)| format!("    {}: {},\n", quote(key), quote(value))).collect();
+                code.push_str(&format!("data = {{\n{}}}\n", pairs.concat()));
+            }
+            arguments.push("data=data".to_string());
+        }
+        Some(Body::Text(text)) => {
+            code.push_str(&format!("data = {}\n", quote(text)));
+            arguments.push("data=data".to_string());
+        }
+        Some(Body::File(path)) => {
+            code.push_str(&format!("data = open({}, \"rb\")\n", quote(path)));
+            arguments.push("data=data".to_string());
+        }
+        Some(Body::Multipart(parts)) => {
+            code.push_str("files = {\n");
+            for part in parts {
+                if part.file {
+                    code.push_str(&format!("    {}: open({}, \"rb\"),\n", quote(&part.name), quote(&part.value)));
+                } else {
+                    // A None filename sends a plain text field
+                    code.push_str(&format!("    {}: (None, {}),\n", quote(&part.name), quote(&part.value)));
+                }
+            }
+            code.push_str("}\n");
+            arguments.push("files=files".to_string());
+        }
+        None => {}
+    }
+    if let Some((user, password)) = &request.basic_auth {
+        arguments.push(format!("auth=({}, {})", quote(user), quote(password)));
+    }
+    if request.insecure {
+        arguments.push("verify=False".to_string());
+    }
+    if let Some(seconds) = request.timeout {
+        arguments.push(format!("timeout={:?}", seconds));
+    }
+    let call = match request.method.as_str() {
+        "GET" | "POST" | "PUT" | "PATCH" | "DELETE" | "HEAD" | "OPTIONS" => format!("requests.{}", request.method.to_lowercase()),
+        method => {
+            arguments.insert(0, quote(method));
+            "requests.request".to_string()
+        }
+    };
+    if code.ends_with("}\n") || code.ends_with("]\n") || code.ends_with(")\n") {
+        code.push('\n');
+    }
+    if arguments.len() == 1 {
+        code.push_str(&format!("response = {}({})\n", call, arguments[0]));
+    } else {
+        code.push_str(&format!("response = {}(\n", call));
+        for argument in arguments {
+            code.push_str(&format!("    {},\n", argument));
+        }
+        code.push_str(")\n");
+    }
+    code.push_str("print(response.status_code)\nprint(response.text)\n");
+    code
+}
+
+fn generate_javascript(request: &CurlRequest) -> String {
+    let mut code = String::new();
+    let reads_files = match &request.body {
+        Some(Body::File(_)) => true,
+        Some(Body::Multipart(parts)) => parts.iter().any(|part| part.file),
+        _ => false,
+    };
+    if reads_files {
+        code.push_str("import fs from \"node:fs\";\n\n");
+    }
+    if request.insecure {
+        code.push_str("// curl -k skipped certificate checks; fetch has no per-request switch (NODE_TLS_REJECT_UNAUTHORIZED=0 in Node)\n");
+    }
+    if let Some(Body::Multipart(parts)) = &request.body {
+        code.push_str("const form = new FormData();\n");
+        for part in parts {
+            if part.file {
+                code.push_str(&format!("form.append({}, await fs.openAsBlob({}), {});\n", quote(&part.name), quote(&part.value), quote(&part.value)));
+            } else {
+                code.push_str(&format!("form.append({}, {});\n", quote(&part.name), quote(&part.value)));
+            }
+        }
+        code.push('\n');
+    }
+    let mut options = Vec::new();
+    if request.method != "GET" {
+        options.push(format!("method: {}", quote(&request.method)));
+    }
+    let mut headers: Vec<String> = sent_headers(request, false).iter().map(|(name, value)| format!("{}: {}", quote(name), quote(value))).collect();
+    if let Some((user, password)) = &request.basic_auth {
+        headers.push(format!("\"Authorization\": \"Basic \" + btoa({})", quote(&format!("{}:{}", user, password))));
+    }
+    if !headers.is_empty() {
+        options.push(format!("headers: {{\n    {},\n  }}", headers.join(",\n    ")));
+    }
+    match &request.body {
+        Some(Body::Json(value)) => options.push(format!("body: JSON.stringify({})", value.render(&JSON_JS, 1))),
+        Some(Body::Form(pairs)) if has_duplicate_keys(pairs) => {
+            let pairs: Vec<String> = pairs.iter().map(|(key, value)| format!("[{}, {}]", quote(key), quote(value))).collect();
+            options.push(format!("body: new URLSearchParams([{}])", pairs.join(", ")));
+        }
+        Some(Body::Form(pairs)) => {
+            let pairs: Vec<String> = pairs.iter().map(|(key, value)| format!("{}: {}", quote(key), quote(value))).collect();
+            options.push(format!("body: new URLSearchParams({{ {} }})", pairs.join(", ")));
+        }
+        Some(Body::Text(text)) => options.push(format!("body: {}", quote(text))),
+        Some(Body::File(path)) => options.push(format!("body: fs.readFileSync({})", quote(path))),
+        Some(Body::Multipart(_)) => options.push("body: form".to_string()),
+        None => {}
+    }
+    if let Some(seconds) = request.timeout {
+        options.push(format!("signal: AbortSignal.timeout({})", (seconds * 1000.0).round()));
+    }
+    if options.is_empty() {
+        code.push_str(&format!("const response = await fetch({});\n", quote(&request.url)));
+    } else {
+        code.push_str(&format!("const response = await fetch({}, {{\n  {},\n}});\n", quote(&request.url), options.join(",\n  ")));
+    }
+    code.push_str("console.log(response.status);\nconsole.log(await response.text());\n");
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_curl() {
+        let request = parse_curl(
+            "curl -sSL -X PUT 'https://api.example.com/users/1' \\\n  -H 'Authorization: Bearer abc' \\\n  -H \"Content-Type: application/json\" \\\n  -d '{\"name\": \"Ada\", \"admin\": true}' -o out.json",
+        )
+        .unwrap();
+        assert_eq!(request.method, "PUT");
+        assert_eq!(request.url, "https://api.example.com/users/1");
+        assert_eq!(request.headers[0], ("Authorization".to_string(), "Bearer abc".to_string()));
+        assert_eq!(request.body, Json::parse("{\"name\": \"Ada\", \"admin\": true}").map(Body::Json));
+        assert_eq!(request.ignored, ["-o out.json"]);
+        assert_eq!(parse_curl("curl -XDELETE example.com/x").unwrap().url, "http://example.com/x");
+        assert!(parse_curl("curl -H 'X: 1'").is_err());
+        assert!(parse_curl("curl 'https://a.example").is_err());
+    }
+
+    #[test]
+    fn test_bodies_and_methods() {
+        let request = parse_curl("curl https://a.example/login -d user=ada -d 'note=a+b' --data-urlencode 'q=x&y' -u ada:pw").unwrap();
+        assert_eq!(request.method, "POST");
+        let expected = [("user", "ada"), ("note", "a b"), ("q", "x&y")].map(|(key, value)| (key.to_string(), value.to_string()));
+        assert_eq!(request.body, Some(Body::Form(expected.to_vec())));
+        assert_eq!(request.basic_auth, Some(("ada".to_string(), "pw".to_string())));
+        let request = parse_curl("curl -G https://a.example/search?x=1 -d q=rust").unwrap();
+        assert_eq!((request.method.as_str(), request.url.as_str(), &request.body), ("GET", "https://a.example/search?x=1&q=rust", &None));
+        assert_eq!(parse_curl("curl -I https://a.example").unwrap().method, "HEAD");
+        let request = parse_curl("curl https://a.example -F name=doc -F 'file=@report.pdf;type=application/pdf'").unwrap();
+        let Some(Body::Multipart(parts)) = request.body else { panic!("expected multipart") };
+        assert_eq!((parts[1].value.as_str(), parts[1].file), ("report.pdf", true));
+        assert_eq!(parse_curl("curl https://a.example --data-binary @body.bin").unwrap().body, Some(Body::File("body.bin".to_string())));
+    }
+
+    #[test]
+    fn test_generate_rust() {
+        let request = parse_curl("curl --json '{\"a\": [1, null]}' -k https://a.example/items").unwrap();
+        let code = generate(&request, Language::Rust);
+        assert!(code.contains("use serde_json::json;\n"));
+        assert!(code.contains("        .danger_accept_invalid_certs(true)\n"));
+        assert!(code.contains("        .post(\"https://a.example/items\")\n        .header(\"Accept\", \"application/json\")\n"));
+        assert!(code.contains("        .json(&json!({\n            \"a\": [\n                1,\n                null\n            ]\n        }))\n"));
+        assert!(!code.contains("Content-Type"));
+    }
+
+    #[test]
+    fn test_generate_python_and_javascript() {
+        let request = parse_curl("curl -X POST https://a.example/x -H 'Content-Type: application/json' -d '{\"ok\": false, \"n\": null}' -m 2.5").unwrap();
+        assert_eq!(
+            generate(&request, Language::Python),
+            "import requests\n\njson_data = {\n    \"ok\": False,\n    \"n\": None,\n}\n\nresponse = requests.post(\n    \"https://a.example/x\",\n    json=json_data,\n    timeout=2.5,\n)\nprint(response.status_code)\nprint(response.text)\n"
+        );
+        assert_eq!(
+            generate(&request, Language::JavaScript),
+            "const response = await fetch(\"https://a.example/x\", {\n  method: \"POST\",\n  headers: {\n    \"Content-Type\": \"application/json\",\n  },\n  body: JSON.stringify({\n    \"ok\": false,\n    \"n\": null\n  }),\n  signal: AbortSignal.timeout(2500),\n});\nconsole.log(response.status);\nconsole.log(await response.text());\n"
+        );
+        let request = parse_curl("curl https://a.example").unwrap();
+        assert!(generate(&request, Language::JavaScript).starts_with("const response = await fetch(\"https://a.example\");\n"));
+        assert!(generate(&request, Language::Python).contains("response = requests.get(\"https://a.example\")\n"));
+        assert!(parse_language("go").is_err());
+    }
+}
@@ -0,0 +1,161 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+pub const SUPPORTED_COUNTRIES: [&str; 4] = ["US", "GB", "DE", "FR"];
+
+// Easter Sunday using the anonymous Gregorian algorithm (Meeus/Jones/Butcher)
+pub fn easter_sunday(year: i32) -> NaiveDate {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32).unwrap()
+}
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn nth_weekday(year: i32, month: u32, weekday: Weekday, n: u8) -> NaiveDate {
+    NaiveDate::from_weekday_of_month_opt(year, month, weekday, n).unwrap()
+}
+
+fn last_weekday(year: i32, month: u32, weekday: Weekday) -> NaiveDate {
+    let next_month = if month == 12 { date(year + 1, 1, 1) } else { date(year, month + 1, 1) };
+    let mut day = next_month - Duration::days(1);
+    while day.weekday() != weekday {
+        day -= Duration::days(1);
+    }
+    day
+}
+
+// US federal rule: Saturday holidays are observed on Friday, Sunday ones on Monday
+fn us_observed(day: NaiveDate) -> NaiveDate {
+    match day.weekday() {
+        Weekday::Sat => day - Duration::days(1),
+        Weekday::Sun => day + Duration::days(1),
+        _ => day,
+    }
+}
+
+// UK substitute days move weekend holidays to the next free weekday
+fn gb_substitute(day: NaiveDate, taken: &[NaiveDate]) -> NaiveDate {
+    let mut observed = day;
+    while matches!(observed.weekday(), Weekday::Sat | Weekday::Sun) || taken.contains(&observed) {
+        observed += Duration::days(1);
+    }
+    observed
+}
+
+// Public holidays for a country and year. Only nationwide holidays are
+// included; regional ones (German states, Scottish bank holidays) are not.
+pub fn country_holidays(country: &str, year: i32) -> Option<Vec<NaiveDate>> {
+    let easter = easter_sunday(year);
+    let holidays = match country.to_uppercase().as_str() {
+        "US" => vec![
+            us_observed(date(year, 1, 1)),
+            nth_weekday(year, 1, Weekday::Mon, 3),
+            nth_weekday(year, 2, Weekday::Mon, 3),
+            last_weekday(year, 5, Weekday::Mon),
+            us_observed(date(year, 6, 19)),
+            us_observed(date(year, 7, 4)),
+            nth_weekday(year, 9, Weekday::Mon, 1),
+            nth_weekday(year, 10, Weekday::Mon, 2),
+            us_observed(date(year, 11, 11)),
+            nth_weekday(year, 11, Weekday::Thu, 4),
+            us_observed(date(year, 12, 25)),
+        ],
+        "GB" | "UK" => {
+            let new_year = gb_substitute(date(year, 1, 1), &[]);
+            let christmas = gb_substitute(date(year, 12, 25), &[]);
+            let boxing_day = gb_substitute(date(year, 12, 26), &[christmas]);
+            vec![
+                new_year,
+                easter - Duration::days(2),
+                easter + Duration::days(1),
+                nth_weekday(year, 5, Weekday::Mon, 1),
+                last_weekday(year, 5, Weekday::Mon),
+                last_weekday(year, 8, Weekday::Mon),
+                christmas,
+                boxing_day,
+            ]
+        }
+        "DE" => vec![
+            date(year, 1, 1),
+            easter - Duration::days(2),
+            easter + Duration::days(1),
+            date(year, 5, 1),
+            easter + Duration::days(39),
+            easter + Duration::days(50),
+            date(year, 10, 3),
+            date(year, 12, 25),
+            date(year, 12, 26),
+        ],
+        "FR" => vec![
+            date(year, 1, 1),
+            easter + Duration::days(1),
+            date(year, 5, 1),
+            date(year, 5, 8),
+            easter + Duration::days(39),
+            easter + Duration::days(50),
+            date(year, 7, 14),
+            date(year, 8, 15),
+            date(year, 11, 1),
+            date(year, 11, 11),
+            date(year, 12, 25),
+        ],
+        _ => return None,
+    };
+    Some(holidays)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_easter_sunday() {
+        assert_eq!(easter_sunday(2024), date(2024, 3, 31));
+        assert_eq!(easter_sunday(2025), date(2025, 4, 20));
+        assert_eq!(easter_sunday(2019), date(2019, 4, 21));
+        assert_eq!(easter_sunday(2000), date(2000, 4, 23));
+    }
+
+    #[test]
+    fn test_us_holidays() {
+        let holidays = country_holidays("us", 2024).unwrap();
+        assert!(holidays.contains(&date(2024, 11, 28))); // Thanksgiving
+        assert!(holidays.contains(&date(2024, 5, 27))); // Memorial Day
+        assert!(holidays.contains(&date(2024, 1, 15))); // MLK Day
+        // 4 July 2026 is a Saturday, observed on Friday 3 July
+        assert!(country_holidays("US", 2026).unwrap().contains(&date(2026, 7, 3)));
+    }
+
+    #[test]
+    fn test_gb_substitute_days() {
+        // Christmas 2021 fell on Saturday and Boxing Day on Sunday
+        let holidays = country_holidays("GB", 2021).unwrap();
+        assert!(holidays.contains(&date(2021, 12, 27)));
+        assert!(holidays.contains(&date(2021, 12, 28)));
+        assert!(holidays.contains(&date(2021, 4, 2))); // Good Friday
+    }
+
+    #[test]
+    fn test_de_and_fr_movable_holidays() {
+        let de = country_holidays("DE", 2024).unwrap();
+        assert!(de.contains(&date(2024, 5, 9))); // Ascension
+        assert!(de.contains(&date(2024, 5, 20))); // Whit Monday
+        let fr = country_holidays("FR", 2024).unwrap();
+        assert!(fr.contains(&date(2024, 7, 14)));
+        assert!(country_holidays("XX", 2024).is_none());
+    }
+}
@@ -1,7 +1,20 @@
+use crate::config::load_config;
 use crate::tool_module::ToolModule;
 use clap::{Arg, ArgMatches, Command};
+use std::collections::HashSet;
 use std::error::Error;
-use chrono::{NaiveDate, Datelike, Weekday};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, Datelike, LocalResult, Months, TimeZone, Utc, Weekday};
+use chrono_tz::{OffsetComponents, Tz};
+
+mod holidays;
+mod recurrence;
+
+use holidays::{country_holidays, SUPPORTED_COUNTRIES};
+use recurrence::Schedule;
+
+const DEFAULT_OCCURRENCES: usize = 5;
+// More dates than anyone reads, and a bound on the work --next does
+const MAX_OCCURRENCES: usize = 1000;
 
 pub struct DateCalcModule;
 
@@ -16,20 +29,110 @@ impl ToolModule for DateCalcModule {
                 .long("date-add")
                 .value_names(["DATE", "DAYS"])
                 .num_args(2)
-                .help("Add days to a date (format: DDMMYYYY, DD/MM/YYYY, or DD-MM-YYYY)")
-                .long_help("Add specified number of days to the given date. Date can be in DDMMYYYY, DD/MM/YYYY, or DD-MM-YYYY format. Returns the new date with day of the week.")
+                .help("Add days to a date (format: DDMMYYYY, DD/MM/YYYY, DD-MM-YYYY, YYYY-MM-DD, RFC 3339, or words like tomorrow)")
+                .long_help("Add specified number of days to the given date. Date can be in DDMMYYYY, DD/MM/YYYY, DD-MM-YYYY, YYYY-MM-DD or RFC 3339 format, or relative like \"tomorrow\", \"next friday\" or \"in 3 weeks\". Returns the new date with day of the week.")
         )
         .arg(
             Arg::new("date-sub")
                 .long("date-sub")
                 .value_names(["DATE", "DAYS"])
                 .num_args(2)
-                .help("Subtract days from a date (format: DDMMYYYY, DD/MM/YYYY, or DD-MM-YYYY)")
-                .long_help("Subtract specified number of days from the given date. Date can be in DDMMYYYY, DD/MM/YYYY, or DD-MM-YYYY format. Returns the new date with day of the week.")
+                .help("Subtract days from a date (format: DDMMYYYY, DD/MM/YYYY, DD-MM-YYYY, YYYY-MM-DD, RFC 3339, or words like tomorrow)")
+                .long_help("Subtract specified number of days from the given date. Date can be in DDMMYYYY, DD/MM/YYYY, DD-MM-YYYY, YYYY-MM-DD or RFC 3339 format, or relative like \"tomorrow\", \"next friday\" or \"in 3 weeks\". Returns the new date with day of the week.")
+        )
+        .arg(
+            Arg::new("date-diff")
+                .long("date-diff")
+                .value_names(["DATE1", "DATE2"])
+                .num_args(2)
+                .help("Difference between two dates in days and years/months/days")
+                .long_help("Calculate the difference between two dates. Prints the total number of days (negative when DATE2 is before DATE1), a years/months/days breakdown and the weekday of each date.")
+        )
+        .arg(
+            Arg::new("date-add-business")
+                .long("date-add-business")
+                .value_names(["DATE", "DAYS"])
+                .num_args(2)
+                .allow_negative_numbers(true)
+                .help("Add business days to a date, skipping weekends and holidays")
+                .long_help("Add business days to a date, skipping weekends and holidays (negative DAYS counts backwards). Holidays come from --holidays COUNTRY (US, GB, DE, FR) or the config file:\n\n[date]\ncountry = \"US\"\nholidays = [\"2024-12-24\", \"2024-12-31\"]")
+        )
+        .arg(
+            Arg::new("holidays")
+                .long("holidays")
+                .value_name("COUNTRY")
+                .help("Built-in holiday calendar for business days: US, GB, DE or FR")
+                .value_parser(clap::builder::PossibleValuesParser::new(["US", "GB", "UK", "DE", "FR", "us", "gb", "uk", "de", "fr"]))
+        )
+        .arg(
+            Arg::new("week-of")
+                .long("week-of")
+                .value_name("DATE")
+                .help("Show the ISO week number and day of year for a date")
+        )
+        .arg(
+            Arg::new("age")
+                .long("age")
+                .value_name("DOB")
+                .help("Exact age from a date of birth, plus days until the next birthday")
+        )
+        .arg(
+            Arg::new("until")
+                .long("until")
+                .value_name("DATE[ TIME]")
+                .help("Countdown to a date/time in weeks, days, hours and minutes")
+                .long_help("Show the time remaining until a local date and optional time (midnight when omitted), e.g. --until \"31/12/2026 23:59\" or --until \"next friday 17:00\". Prints a weeks/days/hours/minutes breakdown and the total number of days.")
+        )
+        .arg(
+            Arg::new("live")
+                .long("live")
+                .action(clap::ArgAction::SetTrue)
+                .requires("until")
+                .help("Keep refreshing the --until countdown every second")
+        )
+        .arg(
+            Arg::new("next")
+                .long("next")
+                .value_names(["SCHEDULE", "N"])
+                .num_args(1..=2)
+                .help("List the next N dates of a recurring schedule (default 5, at most 1000)")
+                .long_help("List the next N (default 5, at most 1000) dates of a recurring schedule, starting after today. Schedules: \"every tuesday\", \"every other friday\", \"every 3 weeks on monday\", \"every 2nd tuesday\" (of the month), \"every last friday\", \"every 15th\", \"every day\", \"every 3 days\" and \"every weekday\".")
+        )
+        .arg(
+            Arg::new("next-weekday")
+                .long("next-weekday")
+                .value_names(["WEEKDAY", "N"])
+                .num_args(1..=2)
+                .help("List the next N occurrences of a weekday (default 5)")
+        )
+        .arg(
+            Arg::new("out-format")
+                .long("out-format")
+                .value_name("FORMAT")
+                .help("Output format for dates: dmy (default), iso, mdy, unix or rfc2822")
+                .value_parser(clap::builder::PossibleValuesParser::new(["dmy", "iso", "mdy", "unix", "rfc2822"]))
+        )
+        .arg(
+            Arg::new("tz-convert")
+                .long("tz-convert")
+                .value_names(["DATETIME", "FROM_TZ", "TO_TZ"])
+                .num_args(3)
+                .help("Convert a date/time between IANA time zones (DST-aware)")
+                .long_help("Convert a date and time from one IANA time zone to another, e.g. --tz-convert \"2024-05-01 14:30\" Europe/Istanbul America/New_York. DATETIME may be YYYY-MM-DD HH:MM[:SS], DD/MM/YYYY HH:MM, a bare HH:MM (today) or \"now\". Zones also accept UTC and local. Output shows the UTC offset and whether daylight saving time applies; times skipped by a DST change are rejected.")
+        )
+        .arg(
+            Arg::new("tz-now")
+                .long("tz-now")
+                .value_name("ZONES")
+                .num_args(0..=1)
+                .default_missing_value("")
+                .help("Show the current time in several zones (comma-separated or from config)")
+                .long_help("Show the current time in several IANA time zones at once. Pass them comma-separated, or configure a default list in the config file:\n\n[date]\ntimezones = [\"Europe/Istanbul\", \"America/New_York\", \"Asia/Tokyo\"]")
         )
     }
 
     fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let format = matches.get_one::<String>("out-format").map(|f| DateFormat::parse(f)).unwrap_or_default();
         if let Some(values) = matches.get_many::<String>("date-add") {
             let values: Vec<&String> = values.collect();
             if values.len() == 2 {
@@ -41,7 +144,7 @@ impl ToolModule for DateCalcModule {
                 let new_date = date + chrono::Duration::days(days);
                 let weekday = format_weekday(new_date.weekday());
                 
-                println!("{} ({})", format_date_output(new_date), weekday);
+                println!("{} ({})", format_date_output(new_date, format), weekday);
             }
         } else if let Some(values) = matches.get_many::<String>("date-sub") {
             let values: Vec<&String> = values.collect();
@@ -54,7 +157,138 @@ impl ToolModule for DateCalcModule {
                 let new_date = date - chrono::Duration::days(days);
                 let weekday = format_weekday(new_date.weekday());
                 
-                println!("{} ({})", format_date_output(new_date), weekday);
+                println!("{} ({})", format_date_output(new_date, format), weekday);
+            }
+        } else if let Some(values) = matches.get_many::<String>("date-diff") {
+            let values: Vec<&String> = values.collect();
+            if values.len() == 2 {
+                let from = parse_date(values[0])?;
+                let to = parse_date(values[1])?;
+                let days = (to - from).num_days();
+                let (years, months, rem_days) = if from <= to { ymd_between(from, to) } else { ymd_between(to, from) };
+
+                println!("Difference: {} days", days);
+                println!("Breakdown: {}", format_ymd(years, months, rem_days));
+                println!("From: {} ({})", format_date_output(from, format), format_weekday(from.weekday()));
+                println!("To:   {} ({})", format_date_output(to, format), format_weekday(to.weekday()));
+            }
+        } else if let Some(values) = matches.get_many::<String>("date-add-business") {
+            let values: Vec<&String> = values.collect();
+            if values.len() == 2 {
+                let date = parse_date(values[0])?;
+                let days: i64 = values[1].parse()?;
+                let config = load_config()?.date;
+
+                let mut calendar = HolidayCalendar::default();
+                if let Some(country) = matches.get_one::<String>("holidays").or(config.country.as_ref()) {
+                    if country_holidays(country, date.year()).is_none() {
+                        return Err(format!("Unsupported holiday calendar '{}'. Use one of: {}", country, SUPPORTED_COUNTRIES.join(", ")).into());
+                    }
+                    calendar.country = Some(country.to_uppercase());
+                }
+                for holiday in &config.holidays {
+                    let parsed = NaiveDate::parse_from_str(holiday, "%Y-%m-%d")
+                        .map_err(|_| format!("Invalid holiday '{}' in config file, expected YYYY-MM-DD", holiday))?;
+                    calendar.extra.insert(parsed);
+                }
+
+                let new_date = add_business_days(date, days, &calendar);
+                println!("{} ({})", format_date_output(new_date, format), format_weekday(new_date.weekday()));
+            }
+        } else if let Some(date_str) = matches.get_one::<String>("week-of") {
+            let date = parse_date(date_str)?;
+            let week = date.iso_week();
+            let days_in_year = if NaiveDate::from_ymd_opt(date.year(), 2, 29).is_some() { 366 } else { 365 };
+            println!("Date: {} ({})", format_date_output(date, format), format_weekday(date.weekday()));
+            println!("ISO week: {}-W{:02}", week.year(), week.week());
+            println!("Day of year: {} of {}", date.ordinal(), days_in_year);
+        } else if let Some(dob_str) = matches.get_one::<String>("age") {
+            let dob = parse_date(dob_str)?;
+            let today = chrono::Local::now().date_naive();
+            if dob > today {
+                return Err("Date of birth is in the future".into());
+            }
+            let (years, months, days) = ymd_between(dob, today);
+            let (birthday, turning) = next_birthday(dob, today);
+            let until = (birthday - today).num_days();
+
+            println!("Age: {}", format_ymd(years, months, days));
+            println!("Born: {} ({})", format_date_output(dob, format), format_weekday(dob.weekday()));
+            if until == 0 {
+                println!("Birthday: today, turning {}", turning);
+            } else {
+                println!("Next birthday: {} ({}), in {} day{} (turning {})",
+                    format_date_output(birthday, format), format_weekday(birthday.weekday()),
+                    until, if until == 1 { "" } else { "s" }, turning);
+            }
+        } else if let Some(target_str) = matches.get_one::<String>("until") {
+            let tz = local_timezone()?;
+            let naive = parse_datetime(target_str, tz)
+                .or_else(|_| parse_date(target_str).map(|date| date.and_time(NaiveTime::MIN)))?;
+            let target = resolve_local(tz, naive)?;
+
+            println!("Target: {} {} ({})", format_date_output(naive.date(), format), naive.format("%H:%M"), format_weekday(naive.weekday()));
+            if matches.get_flag("live") {
+                loop {
+                    let remaining = (target.with_timezone(&Utc) - Utc::now()).num_seconds();
+                    print!("\r\x1b[2K{}", format_countdown(remaining.max(0), true));
+                    std::io::Write::flush(&mut std::io::stdout())?;
+                    if remaining <= 0 {
+                        println!();
+                        break;
+                    }
+                    std::thread::sleep(std::time::Duration::from_secs(1));
+                }
+            } else {
+                let remaining = (target.with_timezone(&Utc) - Utc::now()).num_seconds();
+                if remaining < 0 {
+                    println!("Passed: {} ago", format_countdown(-remaining, false));
+                } else {
+                    println!("Remaining: {}", format_countdown(remaining, false));
+                }
+                println!("Total: {:.2} days", remaining.abs() as f64 / 86_400.0);
+            }
+        } else if let Some(values) = matches.get_many::<String>("next").or_else(|| matches.get_many::<String>("next-weekday")) {
+            let values: Vec<&String> = values.collect();
+            let schedule = if matches.contains_id("next") {
+                Schedule::parse(values[0])?
+            } else {
+                let weekday = parse_weekday(&values[0].to_lowercase())
+                    .ok_or_else(|| format!("Invalid weekday '{}'", values[0]))?;
+                Schedule::Weekly { weekday, interval: 1 }
+            };
+            let count = match values.get(1) {
+                Some(count) => count.parse().map_err(|_| format!("Invalid count '{}'", count))?,
+                None => DEFAULT_OCCURRENCES,
+            };
+            if count > MAX_OCCURRENCES {
+                return Err(format!("Count {} is too large; list at most {} dates", count, MAX_OCCURRENCES).into());
+            }
+            for date in schedule.next_occurrences(chrono::Local::now().date_naive(), count) {
+                println!("{} ({})", format_date_output(date, format), format_weekday(date.weekday()));
+            }
+        } else if let Some(values) = matches.get_many::<String>("tz-convert") {
+            let values: Vec<&String> = values.collect();
+            if values.len() == 3 {
+                let from = parse_timezone(values[1])?;
+                let to = parse_timezone(values[2])?;
+                let naive = parse_datetime(values[0], from)?;
+                let source = resolve_local(from, naive)?;
+                let target = source.with_timezone(&to);
+                println!("{}", format_zoned(&source));
+                println!("{}", format_zoned(&target));
+            }
+        } else if let Some(zones) = matches.get_one::<String>("tz-now") {
+            let zones: Vec<String> = if zones.is_empty() {
+                let configured = load_config()?.date.timezones;
+                if configured.is_empty() { vec!["local".to_string(), "UTC".to_string()] } else { configured }
+            } else {
+                zones.split(',').map(|z| z.trim().to_string()).filter(|z| !z.is_empty()).collect()
+            };
+            let now = Utc::now();
+            for zone in &zones {
+                let tz = parse_timezone(zone)?;
+                println!("{}", format_zoned(&now.with_timezone(&tz)));
             }
         }
         Ok(())
@@ -62,7 +296,12 @@ impl ToolModule for DateCalcModule {
 }
 
 fn parse_date(date_str: &str) -> Result<NaiveDate, Box<dyn Error>> {
-    // Try DDMMYYYY format first
+    // Natural language ("tomorrow", "next friday", "in 3 weeks") relative to today
+    if let Some(date) = parse_relative_date(date_str, chrono::Local::now().date_naive()) {
+        return Ok(date);
+    }
+
+    // Try DDMMYYYY format
     if date_str.len() == 8 && date_str.chars().all(|c| c.is_numeric()) {
         let day: u32 = date_str[0..2].parse()?;
         let month: u32 = date_str[2..4].parse()?;
@@ -70,6 +309,14 @@ fn parse_date(date_str: &str) -> Result<NaiveDate, Box<dyn Error>> {
         return Ok(NaiveDate::from_ymd_opt(year, month, day).ok_or("Invalid date")?);
     }
     
+    // Try ISO 8601 YYYY-MM-DD and full RFC 3339 timestamps (date as written)
+    if let Ok(date) = NaiveDate::parse_from_str(date_str, "%Y-%m-%d") {
+        return Ok(date);
+    }
+    if let Ok(datetime) = chrono::DateTime::parse_from_rfc3339(date_str) {
+        return Ok(datetime.date_naive());
+    }
+
     // Try DD/MM/YYYY format
     if let Some(date) = try_parse_with_separator(date_str, '/') {
         return Ok(date);
@@ -80,7 +327,82 @@ fn parse_date(date_str: &str) -> Result<NaiveDate, Box<dyn Error>> {
         return Ok(date);
     }
     
-    Err("Invalid date format. Use DDMMYYYY, DD/MM/YYYY, or DD-MM-YYYY".into())
+    Err("Invalid date format. Use DDMMYYYY, DD/MM/YYYY, DD-MM-YYYY, YYYY-MM-DD, or words like today, next friday, in 3 weeks".into())
+}
+
+// Understands today/tomorrow/yesterday, [this|next|last] WEEKDAY,
+// next/last week|month|year, "in N UNITS" and "N UNITS ago"
+fn parse_relative_date(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let input = input.trim().to_lowercase();
+    let words: Vec<&str> = input.split_whitespace().collect();
+    match words.as_slice() {
+        ["today"] | ["now"] => Some(today),
+        ["tomorrow"] => today.succ_opt(),
+        ["yesterday"] => today.pred_opt(),
+        [day] | ["this", day] => {
+            let weekday = parse_weekday(day)?;
+            Some(today + chrono::Duration::days(days_until(today.weekday(), weekday) as i64))
+        }
+        ["next", unit] => match parse_weekday(unit) {
+            Some(weekday) => {
+                let days = match days_until(today.weekday(), weekday) { 0 => 7, days => days };
+                Some(today + chrono::Duration::days(days as i64))
+            }
+            None => shift_date(today, 1, unit),
+        },
+        ["last", unit] => match parse_weekday(unit) {
+            Some(weekday) => {
+                let days = match days_until(weekday, today.weekday()) { 0 => 7, days => days };
+                Some(today - chrono::Duration::days(days as i64))
+            }
+            None => shift_date(today, -1, unit),
+        },
+        ["in", amount, unit] => shift_date(today, parse_amount(amount)?, unit),
+        [amount, unit, "ago"] => shift_date(today, -parse_amount(amount)?, unit),
+        [amount, unit, "from", "now"] => shift_date(today, parse_amount(amount)?, unit),
+        _ => None,
+    }
+}
+
+fn parse_weekday(name: &str) -> Option<Weekday> {
+    match name {
+        "monday" | "mon" => Some(Weekday::Mon),
+        "tuesday" | "tue" | "tues" => Some(Weekday::Tue),
+        "wednesday" | "wed" => Some(Weekday::Wed),
+        "thursday" | "thu" | "thurs" => Some(Weekday::Thu),
+        "friday" | "fri" => Some(Weekday::Fri),
+        "saturday" | "sat" => Some(Weekday::Sat),
+        "sunday" | "sun" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+// Days forward from one weekday to the next occurrence of another (0-6)
+fn days_until(from: Weekday, to: Weekday) -> u32 {
+    (to.num_days_from_monday() + 7 - from.num_days_from_monday()) % 7
+}
+
+fn parse_amount(amount: &str) -> Option<i64> {
+    match amount {
+        "a" | "an" | "one" => Some(1),
+        "two" => Some(2),
+        "three" => Some(3),
+        _ => amount.parse().ok(),
+    }
+}
+
+fn shift_date(date: NaiveDate, amount: i64, unit: &str) -> Option<NaiveDate> {
+    let months = |n: i64| {
+        let shift = Months::new(u32::try_from(n.unsigned_abs()).ok()?);
+        if n < 0 { date.checked_sub_months(shift) } else { date.checked_add_months(shift) }
+    };
+    match unit.trim_end_matches('s') {
+        "day" => date.checked_add_signed(chrono::Duration::try_days(amount)?),
+        "week" => date.checked_add_signed(chrono::Duration::try_weeks(amount)?),
+        "month" => months(amount),
+        "year" => months(amount.checked_mul(12)?),
+        _ => None,
+    }
 }
 
 fn try_parse_with_separator(date_str: &str, separator: char) -> Option<NaiveDate> {
@@ -97,8 +419,199 @@ fn try_parse_with_separator(date_str: &str, separator: char) -> Option<NaiveDate
     None
 }
 
-fn format_date_output(date: NaiveDate) -> String {
-    format!("{:02}/{:02}/{}", date.day(), date.month(), date.year())
+// Calendar difference between two dates (start <= end) as whole years,
+// months and remaining days. Months are added to `start` with end-of-month
+// clamping, so 31 Jan to 1 Mar is 1 month and 1 day.
+fn ymd_between(start: NaiveDate, end: NaiveDate) -> (i32, u32, u32) {
+    let mut total_months = (end.year() - start.year()) * 12 + end.month() as i32 - start.month() as i32;
+    let anchor = |months: i32| start.checked_add_months(Months::new(months.max(0) as u32)).unwrap_or(start);
+    if total_months > 0 && anchor(total_months) > end {
+        total_months -= 1;
+    }
+    let total_months = total_months.max(0);
+    let days = (end - anchor(total_months)).num_days().max(0) as u32;
+    (total_months / 12, (total_months % 12) as u32, days)
+}
+
+// The first birthday on or after `today` and the age reached on it.
+// 29 February birthdays fall on 28 February in common years.
+fn next_birthday(dob: NaiveDate, today: NaiveDate) -> (NaiveDate, i32) {
+    let birthday_in = |years: i32| dob.checked_add_months(Months::new(years as u32 * 12)).unwrap_or(dob);
+    let mut turning = (today.year() - dob.year()).max(0);
+    if birthday_in(turning) < today {
+        turning += 1;
+    }
+    (birthday_in(turning), turning)
+}
+
+fn local_timezone() -> Result<Tz, Box<dyn Error>> {
+    let name = std::env::var("TZ")
+        .ok()
+        .filter(|tz| !tz.is_empty())
+        .or_else(|| std::fs::read_link("/etc/localtime").ok().and_then(|path| {
+            let path = path.to_string_lossy().to_string();
+            path.split_once("zoneinfo/").map(|(_, zone)| zone.to_string())
+        }))
+        .unwrap_or_else(|| "UTC".to_string());
+    name.parse::<Tz>().map_err(|_| format!("Could not determine local time zone from '{}'", name).into())
+}
+
+pub fn parse_timezone(name: &str) -> Result<Tz, Box<dyn Error>> {
+    if name.eq_ignore_ascii_case("local") {
+        return local_timezone();
+    }
+    if name.eq_ignore_ascii_case("utc") || name.eq_ignore_ascii_case("z") {
+        return Ok(Tz::UTC);
+    }
+    name.parse::<Tz>()
+        .ok()
+        .or_else(|| chrono_tz::TZ_VARIANTS.iter().copied().find(|tz| tz.name().eq_ignore_ascii_case(name)))
+        .ok_or_else(|| format!("Unknown time zone '{}'. Use an IANA name such as Europe/Istanbul", name).into())
+}
+
+fn parse_time(time_str: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(time_str, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(time_str, "%H:%M"))
+        .ok()
+}
+
+// Parses "YYYY-MM-DD HH:MM[:SS]" (space or T), "<DATE> HH:MM" with any
+// format parse_date accepts, a bare "HH:MM" for today, or "now". RFC 3339
+// timestamps carry their own offset and are converted into `tz`.
+fn parse_datetime(input: &str, tz: Tz) -> Result<NaiveDateTime, Box<dyn Error>> {
+    let input = input.trim();
+    let now = Utc::now().with_timezone(&tz).naive_local();
+    if input.eq_ignore_ascii_case("now") {
+        return Ok(now);
+    }
+    if let Ok(datetime) = chrono::DateTime::parse_from_rfc3339(input) {
+        return Ok(datetime.with_timezone(&tz).naive_local());
+    }
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M"] {
+        if let Ok(datetime) = NaiveDateTime::parse_from_str(input, format) {
+            return Ok(datetime);
+        }
+    }
+    if let Some(time) = parse_time(input) {
+        return Ok(now.date().and_time(time));
+    }
+    if let Some((date_str, time_str)) = input.rsplit_once(' ') {
+        if let Some(time) = parse_time(time_str) {
+            let date = NaiveDate::parse_from_str(date_str, "%Y-%m-%d").or_else(|_| parse_date(date_str))?;
+            return Ok(date.and_time(time));
+        }
+    }
+    Err(format!("Invalid date/time '{}'. Use YYYY-MM-DD HH:MM, DD/MM/YYYY HH:MM, HH:MM or now", input).into())
+}
+
+// Attaches a zone to a wall-clock time. Ambiguous times (clocks going back)
+// take the earlier instant; times skipped by DST do not exist.
+fn resolve_local(tz: Tz, naive: NaiveDateTime) -> Result<chrono::DateTime<Tz>, Box<dyn Error>> {
+    match tz.from_local_datetime(&naive) {
+        LocalResult::Single(datetime) => Ok(datetime),
+        LocalResult::Ambiguous(earliest, _) => Ok(earliest),
+        LocalResult::None => Err(format!("{} does not exist in {} (skipped by a daylight saving change)", naive.format("%Y-%m-%d %H:%M"), tz.name()).into()),
+    }
+}
+
+fn format_zoned(datetime: &chrono::DateTime<Tz>) -> String {
+    let offset = datetime.offset();
+    let dst = if offset.dst_offset().num_seconds() != 0 { ", DST" } else { "" };
+    format!(
+        "{} {} {} (UTC{}{}, {})",
+        datetime.format("%Y-%m-%d %H:%M"),
+        datetime.format("%Z"),
+        &format_weekday(datetime.weekday())[..3],
+        datetime.format("%:z"),
+        dst,
+        datetime.timezone().name()
+    )
+}
+
+#[derive(Default)]
+struct HolidayCalendar {
+    country: Option<String>,
+    extra: HashSet<NaiveDate>,
+}
+
+impl HolidayCalendar {
+    fn is_business_day(&self, date: NaiveDate) -> bool {
+        if matches!(date.weekday(), Weekday::Sat | Weekday::Sun) || self.extra.contains(&date) {
+            return false;
+        }
+        match &self.country {
+            Some(country) => !country_holidays(country, date.year()).unwrap_or_default().contains(&date),
+            None => true,
+        }
+    }
+}
+
+// Steps one day at a time in the direction of `days`, counting only business days
+fn add_business_days(date: NaiveDate, days: i64, calendar: &HolidayCalendar) -> NaiveDate {
+    let step = chrono::Duration::days(days.signum());
+    let mut current = date;
+    let mut remaining = days.abs();
+    while remaining > 0 {
+        current += step;
+        if calendar.is_business_day(current) {
+            remaining -= 1;
+        }
+    }
+    current
+}
+
+// "2 weeks, 3 days, 4 hours, 5 minutes", optionally with seconds
+fn format_countdown(total_seconds: i64, with_seconds: bool) -> String {
+    let plural = |n: i64, unit: &str| format!("{} {}{}", n, unit, if n == 1 { "" } else { "s" });
+    let mut parts = vec![
+        plural(total_seconds / 604_800, "week"),
+        plural(total_seconds % 604_800 / 86_400, "day"),
+        plural(total_seconds % 86_400 / 3_600, "hour"),
+        plural(total_seconds % 3_600 / 60, "minute"),
+    ];
+    if with_seconds {
+        parts.push(plural(total_seconds % 60, "second"));
+    }
+    parts.join(", ")
+}
+
+fn format_ymd(years: i32, months: u32, days: u32) -> String {
+    let plural = |n: i64, unit: &str| format!("{} {}{}", n, unit, if n == 1 { "" } else { "s" });
+    format!("{}, {}, {}", plural(years as i64, "year"), plural(months as i64, "month"), plural(days as i64, "day"))
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+enum DateFormat {
+    #[default]
+    Dmy,
+    Iso,
+    Mdy,
+    Unix,
+    Rfc2822,
+}
+
+impl DateFormat {
+    fn parse(name: &str) -> Self {
+        match name {
+            "iso" => DateFormat::Iso,
+            "mdy" => DateFormat::Mdy,
+            "unix" => DateFormat::Unix,
+            "rfc2822" => DateFormat::Rfc2822,
+            _ => DateFormat::Dmy,
+        }
+    }
+}
+
+// unix and rfc2822 describe midnight UTC of the date
+fn format_date_output(date: NaiveDate, format: DateFormat) -> String {
+    let midnight = date.and_time(NaiveTime::MIN).and_utc();
+    match format {
+        DateFormat::Dmy => format!("{:02}/{:02}/{}", date.day(), date.month(), date.year()),
+        DateFormat::Iso => date.format("%Y-%m-%d").to_string(),
+        DateFormat::Mdy => format!("{:02}/{:02}/{}", date.month(), date.day(), date.year()),
+        DateFormat::Unix => midnight.timestamp().to_string(),
+        DateFormat::Rfc2822 => midnight.to_rfc2822(),
+    }
 }
 
 fn format_weekday(weekday: Weekday) -> String {
@@ -138,19 +651,155 @@ mod tests {
 
     #[test]
     fn test_invalid_date_formats() {
-        assert!(parse_date("2023-01-01").is_err());
+        assert!(parse_date("2023-13-01").is_err());
         assert!(parse_date("invalid").is_err());
         assert!(parse_date("32/01/2023").is_err());
         assert!(parse_date("01/13/2023").is_err());
     }
 
+    #[test]
+    fn test_parse_relative_date() {
+        // 2024-05-15 is a Wednesday
+        let today = NaiveDate::from_ymd_opt(2024, 5, 15).unwrap();
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d);
+        assert_eq!(parse_relative_date("today", today), date(2024, 5, 15));
+        assert_eq!(parse_relative_date("Tomorrow", today), date(2024, 5, 16));
+        assert_eq!(parse_relative_date("yesterday", today), date(2024, 5, 14));
+        assert_eq!(parse_relative_date("next friday", today), date(2024, 5, 17));
+        assert_eq!(parse_relative_date("next wednesday", today), date(2024, 5, 22));
+        assert_eq!(parse_relative_date("wednesday", today), date(2024, 5, 15));
+        assert_eq!(parse_relative_date("last monday", today), date(2024, 5, 13));
+        assert_eq!(parse_relative_date("last wed", today), date(2024, 5, 8));
+        assert_eq!(parse_relative_date("in 3 weeks", today), date(2024, 6, 5));
+        assert_eq!(parse_relative_date("in a month", today), date(2024, 6, 15));
+        assert_eq!(parse_relative_date("10 days ago", today), date(2024, 5, 5));
+        assert_eq!(parse_relative_date("2 years from now", today), date(2026, 5, 15));
+        assert_eq!(parse_relative_date("next week", today), date(2024, 5, 22));
+        assert_eq!(parse_relative_date("last year", today), date(2023, 5, 15));
+        assert_eq!(parse_relative_date("in 3 fortnights", today), None);
+        assert_eq!(parse_relative_date("someday", today), None);
+    }
+
+    #[test]
+    fn test_parse_date_iso_and_rfc3339() {
+        let expected = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+        assert_eq!(parse_date("2023-01-01").unwrap(), expected);
+        assert_eq!(parse_date("2023-01-01T23:30:00+03:00").unwrap(), expected);
+        assert_eq!(parse_date("2023-01-01T10:00:00Z").unwrap(), expected);
+    }
+
     #[test]
     fn test_format_date_output() {
         let date = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
-        assert_eq!(format_date_output(date), "01/01/2023");
+        assert_eq!(format_date_output(date, DateFormat::Dmy), "01/01/2023");
         
         let date = NaiveDate::from_ymd_opt(2023, 12, 31).unwrap();
-        assert_eq!(format_date_output(date), "31/12/2023");
+        assert_eq!(format_date_output(date, DateFormat::Dmy), "31/12/2023");
+        assert_eq!(format_date_output(date, DateFormat::Iso), "2023-12-31");
+        assert_eq!(format_date_output(date, DateFormat::Mdy), "12/31/2023");
+        assert_eq!(format_date_output(date, DateFormat::Unix), "1703980800");
+        assert_eq!(format_date_output(date, DateFormat::Rfc2822), "Sun, 31 Dec 2023 00:00:00 +0000");
+    }
+
+    #[test]
+    fn test_ymd_between() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(ymd_between(date(2023, 1, 1), date(2024, 1, 1)), (1, 0, 0));
+        assert_eq!(ymd_between(date(2023, 1, 31), date(2023, 3, 1)), (0, 1, 1));
+        assert_eq!(ymd_between(date(2020, 2, 29), date(2021, 2, 28)), (1, 0, 0));
+        assert_eq!(ymd_between(date(2020, 3, 31), date(2020, 4, 30)), (0, 1, 0));
+        assert_eq!(ymd_between(date(2023, 5, 15), date(2023, 5, 15)), (0, 0, 0));
+        assert_eq!(ymd_between(date(1990, 12, 25), date(2024, 3, 10)), (33, 2, 14));
+    }
+
+    #[test]
+    fn test_next_birthday() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(next_birthday(date(1990, 8, 11), date(2024, 5, 15)), (date(2024, 8, 11), 34));
+        assert_eq!(next_birthday(date(1990, 3, 1), date(2024, 5, 15)), (date(2025, 3, 1), 35));
+        assert_eq!(next_birthday(date(1990, 5, 15), date(2024, 5, 15)), (date(2024, 5, 15), 34));
+        assert_eq!(next_birthday(date(2000, 2, 29), date(2023, 1, 10)), (date(2023, 2, 28), 23));
+        assert_eq!(next_birthday(date(2000, 2, 29), date(2024, 1, 10)), (date(2024, 2, 29), 24));
+    }
+
+    #[test]
+    fn test_add_business_days() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let calendar = HolidayCalendar::default();
+        // Friday + 1 business day is Monday
+        assert_eq!(add_business_days(date(2024, 5, 3), 1, &calendar), date(2024, 5, 6));
+        assert_eq!(add_business_days(date(2024, 5, 1), 5, &calendar), date(2024, 5, 8));
+        assert_eq!(add_business_days(date(2024, 5, 6), -1, &calendar), date(2024, 5, 3));
+        assert_eq!(add_business_days(date(2024, 5, 4), 0, &calendar), date(2024, 5, 4));
+    }
+
+    #[test]
+    fn test_add_business_days_with_holidays() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let calendar = HolidayCalendar { country: Some("US".to_string()), extra: HashSet::new() };
+        // Wednesday before Thanksgiving 2024: Thursday and the weekend are skipped
+        assert_eq!(add_business_days(date(2024, 11, 27), 2, &calendar), date(2024, 12, 2));
+
+        let mut extra = HashSet::new();
+        extra.insert(date(2024, 12, 24));
+        let calendar = HolidayCalendar { country: Some("DE".to_string()), extra };
+        // 23 Dec 2024 + 1: Christmas Eve (config), Christmas and Boxing Day are skipped
+        assert_eq!(add_business_days(date(2024, 12, 23), 1, &calendar), date(2024, 12, 27));
+    }
+
+    #[test]
+    fn test_tz_convert_with_dst() {
+        let istanbul = parse_timezone("Europe/Istanbul").unwrap();
+        let new_york = parse_timezone("america/new_york").unwrap();
+        let naive = parse_datetime("2024-05-01 14:30", istanbul).unwrap();
+        let converted = resolve_local(istanbul, naive).unwrap().with_timezone(&new_york);
+        assert_eq!(converted.format("%Y-%m-%d %H:%M").to_string(), "2024-05-01 07:30");
+        assert!(format_zoned(&converted).contains("EDT"));
+        assert!(format_zoned(&converted).contains("UTC-04:00, DST"));
+
+        // Same wall time in January is standard time
+        let naive = parse_datetime("2024-01-15T14:30", istanbul).unwrap();
+        let converted = resolve_local(istanbul, naive).unwrap().with_timezone(&new_york);
+        assert_eq!(converted.format("%H:%M").to_string(), "06:30");
+        assert!(!format_zoned(&converted).contains("DST"));
+    }
+
+    #[test]
+    fn test_resolve_local_dst_gap_and_overlap() {
+        let new_york = parse_timezone("America/New_York").unwrap();
+        let gap = parse_datetime("2024-03-10 02:30", new_york).unwrap();
+        assert!(resolve_local(new_york, gap).is_err());
+        let overlap = parse_datetime("2024-11-03 01:30", new_york).unwrap();
+        assert_eq!(resolve_local(new_york, overlap).unwrap().format("%:z").to_string(), "-04:00");
+    }
+
+    #[test]
+    fn test_parse_datetime_formats() {
+        let utc = Tz::UTC;
+        let expected = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap().and_hms_opt(14, 30, 0).unwrap();
+        assert_eq!(parse_datetime("2024-05-01 14:30", utc).unwrap(), expected);
+        assert_eq!(parse_datetime("01/05/2024 14:30", utc).unwrap(), expected);
+        assert_eq!(parse_datetime("2024-05-01 14:30:00", utc).unwrap(), expected);
+        assert_eq!(parse_datetime("14:30", utc).unwrap().time(), expected.time());
+        assert!(parse_datetime("now", utc).is_ok());
+        assert_eq!(parse_datetime("2024-05-01T17:30:00+03:00", utc).unwrap(), expected);
+        assert!(parse_datetime("tomorrowish", utc).is_err());
+        assert!(parse_timezone("Mars/Olympus").is_err());
+        assert_eq!(parse_timezone("utc").unwrap(), Tz::UTC);
+    }
+
+    #[test]
+    fn test_format_countdown() {
+        let seconds = 2 * 604_800 + 3 * 86_400 + 4 * 3_600 + 60 + 9;
+        assert_eq!(format_countdown(seconds, false), "2 weeks, 3 days, 4 hours, 1 minute");
+        assert_eq!(format_countdown(seconds, true), "2 weeks, 3 days, 4 hours, 1 minute, 9 seconds");
+        assert_eq!(format_countdown(0, false), "0 weeks, 0 days, 0 hours, 0 minutes");
+    }
+
+    #[test]
+    fn test_format_ymd() {
+        assert_eq!(format_ymd(1, 0, 1), "1 year, 0 months, 1 day");
+        assert_eq!(format_ymd(2, 3, 4), "2 years, 3 months, 4 days");
     }
 
     #[test]
@@ -0,0 +1,167 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+use super::parse_weekday;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Schedule {
+    // Every `interval` days
+    Daily(u32),
+    // Monday to Friday
+    Weekdays,
+    // A weekday every `interval` weeks
+    Weekly { weekday: Weekday, interval: u32 },
+    // The nth weekday of each month; nth = -1 means the last one
+    MonthlyWeekday { nth: i8, weekday: Weekday },
+    // A fixed day of each month; months without it are skipped
+    MonthlyDay(u32),
+}
+
+impl Schedule {
+    // Accepts "every tuesday", "every other friday", "every 2 weeks on monday",
+    // "every 2nd tuesday", "every last friday", "every 15th", "every day",
+    // "every 3 days" and "every weekday". The leading "every" is optional.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim().to_lowercase();
+        let words: Vec<&str> = input.split_whitespace().collect();
+        let words = match words.as_slice() {
+            ["every", rest @ ..] => rest,
+            rest => rest,
+        };
+        let schedule = match words {
+            ["day"] => Some(Schedule::Daily(1)),
+            ["weekday"] | ["business", "day"] => Some(Schedule::Weekdays),
+            [count, "days"] => count.parse().ok().filter(|&n| n > 0).map(Schedule::Daily),
+            ["other", day] => weekday(day).map(|weekday| Schedule::Weekly { weekday, interval: 2 }),
+            [count, "weeks", "on", day] => match (count.parse().ok().filter(|&n: &u32| n > 0), weekday(day)) {
+                (Some(interval), Some(weekday)) => Some(Schedule::Weekly { weekday, interval }),
+                _ => None,
+            },
+            ["last", day] => weekday(day).map(|weekday| Schedule::MonthlyWeekday { nth: -1, weekday }),
+            [nth, day] => match (parse_ordinal(nth).filter(|n| (1..=5).contains(n)), weekday(day)) {
+                (Some(nth), Some(weekday)) => Some(Schedule::MonthlyWeekday { nth: nth as i8, weekday }),
+                _ => None,
+            },
+            [word] => match weekday(word) {
+                Some(weekday) => Some(Schedule::Weekly { weekday, interval: 1 }),
+                None => parse_ordinal(word).filter(|n| (1..=31).contains(n)).map(Schedule::MonthlyDay),
+            },
+            _ => None,
+        };
+        schedule.ok_or_else(|| format!(
+            "Unrecognized schedule '{}'. Try \"every tuesday\", \"every other friday\", \"every 2nd tuesday\", \"every last friday\", \"every 15th\" or \"every 3 days\"",
+            input
+        ))
+    }
+
+    pub fn matches(&self, date: NaiveDate) -> bool {
+        match *self {
+            Schedule::Daily(_) => true,
+            Schedule::Weekdays => !matches!(date.weekday(), Weekday::Sat | Weekday::Sun),
+            Schedule::Weekly { weekday, .. } => date.weekday() == weekday,
+            Schedule::MonthlyWeekday { nth, weekday } => {
+                date.weekday() == weekday
+                    && if nth < 0 {
+                        (date + Duration::days(7)).month() != date.month()
+                    } else {
+                        (date.day() - 1) / 7 + 1 == nth as u32
+                    }
+            }
+            Schedule::MonthlyDay(day) => date.day() == day,
+        }
+    }
+
+    // The next `count` dates strictly after `after`. Interval schedules are
+    // anchored on their first occurrence.
+    pub fn next_occurrences(&self, after: NaiveDate, count: usize) -> Vec<NaiveDate> {
+        let step = match *self {
+            Schedule::Daily(interval) => Some(interval as i64),
+            Schedule::Weekly { interval, .. } => Some(7 * interval as i64),
+            _ => None,
+        };
+        let mut dates = Vec::new();
+        let mut date = after;
+        while dates.len() < count {
+            date = match (step, dates.last()) {
+                (Some(step), Some(&last)) => last + Duration::days(step),
+                _ => match date.succ_opt() {
+                    Some(next) => next,
+                    None => break,
+                },
+            };
+            if self.matches(date) {
+                dates.push(date);
+            }
+        }
+        dates
+    }
+}
+
+fn weekday(word: &str) -> Option<Weekday> {
+    parse_weekday(word).or_else(|| word.strip_suffix('s').and_then(parse_weekday))
+}
+
+// "2nd", "15th", "first" ... "fifth"
+fn parse_ordinal(word: &str) -> Option<u32> {
+    match word {
+        "first" => Some(1),
+        "second" => Some(2),
+        "third" => Some(3),
+        "fourth" => Some(4),
+        "fifth" => Some(5),
+        _ => ["st", "nd", "rd", "th"]
+            .iter()
+            .find_map(|suffix| word.strip_suffix(suffix))
+            .and_then(|number| number.parse().ok()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_parse_schedule() {
+        assert_eq!(Schedule::parse("every tuesday").unwrap(), Schedule::Weekly { weekday: Weekday::Tue, interval: 1 });
+        assert_eq!(Schedule::parse("Every Other Friday").unwrap(), Schedule::Weekly { weekday: Weekday::Fri, interval: 2 });
+        assert_eq!(Schedule::parse("every 3 weeks on mondays").unwrap(), Schedule::Weekly { weekday: Weekday::Mon, interval: 3 });
+        assert_eq!(Schedule::parse("every 2nd tuesday").unwrap(), Schedule::MonthlyWeekday { nth: 2, weekday: Weekday::Tue });
+        assert_eq!(Schedule::parse("last friday").unwrap(), Schedule::MonthlyWeekday { nth: -1, weekday: Weekday::Fri });
+        assert_eq!(Schedule::parse("every 15th").unwrap(), Schedule::MonthlyDay(15));
+        assert_eq!(Schedule::parse("every 3 days").unwrap(), Schedule::Daily(3));
+        assert_eq!(Schedule::parse("every weekday").unwrap(), Schedule::Weekdays);
+        assert!(Schedule::parse("every 6th monday").is_err());
+        assert!(Schedule::parse("every 32nd").is_err());
+        assert!(Schedule::parse("every 0 days").is_err());
+        assert!(Schedule::parse("sometimes").is_err());
+    }
+
+    #[test]
+    fn test_next_occurrences_monthly() {
+        let schedule = Schedule::parse("every 2nd tuesday").unwrap();
+        assert_eq!(
+            schedule.next_occurrences(date(2024, 5, 1), 3),
+            vec![date(2024, 5, 14), date(2024, 6, 11), date(2024, 7, 9)]
+        );
+        let schedule = Schedule::parse("every last friday").unwrap();
+        assert_eq!(schedule.next_occurrences(date(2024, 5, 31), 2), vec![date(2024, 6, 28), date(2024, 7, 26)]);
+        let schedule = Schedule::parse("every 31st").unwrap();
+        assert_eq!(schedule.next_occurrences(date(2024, 1, 31), 2), vec![date(2024, 3, 31), date(2024, 5, 31)]);
+    }
+
+    #[test]
+    fn test_next_occurrences_intervals() {
+        // 2024-05-15 is a Wednesday
+        let schedule = Schedule::parse("every other friday").unwrap();
+        assert_eq!(schedule.next_occurrences(date(2024, 5, 15), 3), vec![date(2024, 5, 17), date(2024, 5, 31), date(2024, 6, 14)]);
+        let schedule = Schedule::parse("every wednesday").unwrap();
+        assert_eq!(schedule.next_occurrences(date(2024, 5, 15), 1), vec![date(2024, 5, 22)]);
+        let schedule = Schedule::parse("every weekday").unwrap();
+        assert_eq!(schedule.next_occurrences(date(2024, 5, 17), 2), vec![date(2024, 5, 20), date(2024, 5, 21)]);
+        let schedule = Schedule::parse("every 10 days").unwrap();
+        assert_eq!(schedule.next_occurrences(date(2024, 5, 15), 2), vec![date(2024, 5, 16), date(2024, 5, 26)]);
+    }
+}
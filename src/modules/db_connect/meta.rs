@@ -0,0 +1,131 @@
+// psql-style backslash commands, answered with catalog queries
+
+pub const META_HELP: &str = "\
+\\dt                     list tables
+\\d TABLE                describe a table's columns and indexes
+\\l                      list databases
+\\dn                     list schemas
+\\format [NAME]          show or set the output format (table, csv, json, markdown)
+\\export [FORMAT] FILE   save the last result as csv, json, jsonl or markdown
+\\r                      reset the query buffer
+\\begin                  start a transaction
+\\commit                 commit the current transaction
+\\rollback               roll back the current transaction
+\\?                      show this help
+
+Statements can span several lines and run when a line ends with ;";
+
+const LIST_TABLES: &str = "\
+SELECT n.nspname::text AS schema, c.relname::text AS name,
+       CASE c.relkind WHEN 'p' THEN 'partitioned table' ELSE 'table' END AS type,
+       pg_get_userbyid(c.relowner)::text AS owner
+FROM pg_class c JOIN pg_namespace n ON n.oid = c.relnamespace
+WHERE c.relkind IN ('r', 'p')
+  AND n.nspname NOT IN ('pg_catalog', 'information_schema') AND n.nspname NOT LIKE 'pg\\_toast%'
+ORDER BY 1, 2";
+
+const DESCRIBE_COLUMNS: &str = "\
+SELECT a.attname::text AS column, format_type(a.atttypid, a.atttypmod) AS type,
+       CASE WHEN a.attnotnull THEN 'not null' ELSE '' END AS nullable,
+       COALESCE(pg_get_expr(d.adbin, d.adrelid), '') AS default
+FROM pg_attribute a LEFT JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum
+WHERE a.attrelid = $1::text::regclass AND a.attnum > 0 AND NOT a.attisdropped
+ORDER BY a.attnum";
+
+const DESCRIBE_INDEXES: &str = "\
+SELECT i.relname::text AS index, pg_get_indexdef(ix.indexrelid) AS definition
+FROM pg_index ix JOIN pg_class i ON i.oid = ix.indexrelid
+WHERE ix.indrelid = $1::text::regclass
+ORDER BY ix.indisprimary DESC, 1";
+
+const LIST_DATABASES: &str = "\
+SELECT datname::text AS name, pg_get_userbyid(datdba)::text AS owner,
+       pg_encoding_to_char(encoding)::text AS encoding
+FROM pg_database WHERE NOT datistemplate
+ORDER BY 1";
+
+const LIST_SCHEMAS: &str = "\
+SELECT nspname::text AS name, pg_get_userbyid(nspowner)::text AS owner
+FROM pg_namespace
+WHERE nspname NOT LIKE 'pg\\_%' AND nspname <> 'information_schema'
+ORDER BY 1";
+
+#[derive(Debug, PartialEq)]
+pub enum MetaCommand {
+    ListTables,
+    Describe(String),
+    ListDatabases,
+    ListSchemas,
+    Help,
+}
+
+// A titled catalog query; `$1` (if any) is bound to the table name
+pub struct MetaQuery {
+    pub title: Option<String>,
+    pub sql: &'static str,
+    pub empty_message: &'static str,
+}
+
+impl MetaCommand {
+    // `input` starts with a backslash; \format and \export are handled by the session
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut parts = input.split_whitespace();
+        let command = parts.next().unwrap_or_default();
+        let argument = parts.next();
+        match (command, argument) {
+            ("\\dt", None) => Ok(MetaCommand::ListTables),
+            ("\\d", Some(table)) => Ok(MetaCommand::Describe(table.trim_end_matches(';').to_string())),
+            ("\\d", None) => Ok(MetaCommand::ListTables),
+            ("\\l", None) => Ok(MetaCommand::ListDatabases),
+            ("\\dn", None) => Ok(MetaCommand::ListSchemas),
+            ("\\?", _) => Ok(MetaCommand::Help),
+            _ => Err(format!("Unknown command '{}'. Type \\? for the list of commands", input)),
+        }
+    }
+
+    pub fn queries(&self) -> Vec<MetaQuery> {
+        match self {
+            MetaCommand::ListTables => vec![MetaQuery { title: None, sql: LIST_TABLES, empty_message: "No tables found." }],
+            MetaCommand::Describe(table) => vec![
+                MetaQuery { title: Some(format!("Table \"{}\"", table)), sql: DESCRIBE_COLUMNS, empty_message: "No columns." },
+                MetaQuery { title: Some("Indexes:".to_string()), sql: DESCRIBE_INDEXES, empty_message: "No indexes." },
+            ],
+            MetaCommand::ListDatabases => vec![MetaQuery { title: None, sql: LIST_DATABASES, empty_message: "No databases found." }],
+            MetaCommand::ListSchemas => vec![MetaQuery { title: None, sql: LIST_SCHEMAS, empty_message: "No schemas found." }],
+            MetaCommand::Help => Vec::new(),
+        }
+    }
+
+    pub fn table(&self) -> Option<&str> {
+        match self {
+            MetaCommand::Describe(table) => Some(table),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_meta_commands() {
+        assert_eq!(MetaCommand::parse("\\dt"), Ok(MetaCommand::ListTables));
+        assert_eq!(MetaCommand::parse("\\d  public.users;"), Ok(MetaCommand::Describe("public.users".to_string())));
+        assert_eq!(MetaCommand::parse("\\l"), Ok(MetaCommand::ListDatabases));
+        assert_eq!(MetaCommand::parse("\\dn"), Ok(MetaCommand::ListSchemas));
+        assert_eq!(MetaCommand::parse("\\?"), Ok(MetaCommand::Help));
+        assert!(MetaCommand::parse("\\dx").is_err());
+        assert!(MetaCommand::parse("\\l extra").is_err());
+    }
+
+    #[test]
+    fn test_describe_queries_bind_table() {
+        let command = MetaCommand::Describe("users".to_string());
+        assert_eq!(command.table(), Some("users"));
+        let queries = command.queries();
+        assert_eq!(queries.len(), 2);
+        assert!(queries.iter().all(|query| query.sql.contains("$1")));
+        assert!(MetaCommand::ListTables.queries().iter().all(|query| !query.sql.contains("$1")));
+    }
+}
@@ -0,0 +1,253 @@
+// MongoDB sessions: a small subset of mongosh syntax for looking at documents,
+// printed as pretty relaxed extended JSON
+use futures_util::TryStreamExt;
+use mongodb::bson::{Bson, Document};
+use mongodb::options::ClientOptions;
+use mongodb::{Client, Database};
+use serde_json::Value;
+use std::error::Error;
+use std::io::{self, Write};
+
+// Documents shown per find when no limit is given
+const DEFAULT_FIND_LIMIT: i64 = 20;
+
+const MONGO_HELP: &str = "\
+db.COLLECTION.find(FILTER).limit(N)   list matching documents (20 unless limited)
+db.COLLECTION.findOne(FILTER)         show the first matching document
+db.COLLECTION.countDocuments(FILTER)  count matching documents
+show collections                      list collections in the current database
+show dbs                              list databases
+use NAME                              switch database
+help                                  show this help
+
+FILTER is JSON, e.g. {\"active\": true, \"_id\": {\"$oid\": \"65f1c0ffee...\"}}";
+
+#[derive(Debug, PartialEq)]
+enum MongoCommand {
+    Find { collection: String, filter: Document, limit: Option<i64> },
+    FindOne { collection: String, filter: Document },
+    Count { collection: String, filter: Document },
+    ShowCollections,
+    ShowDatabases,
+    Use(String),
+    Help,
+}
+
+// Where a query comes from: mongosh-style text, or --collection/--filter
+pub enum MongoQuery<'a> {
+    Shell(&'a str),
+    Collection { name: &'a str, filter: Option<&'a str>, limit: Option<usize> },
+}
+
+pub async fn run(connection_string: &str, query: Option<MongoQuery<'_>>) -> Result<(), Box<dyn Error>> {
+    let options = ClientOptions::parse(connection_string)
+        .await
+        .map_err(|e| format!("Invalid MongoDB connection string: {}", e))?;
+    let mut database = options.default_database.clone().unwrap_or_else(|| "test".to_string());
+    let client = Client::with_options(options)?;
+
+    if let Some(query) = query {
+        let command = match query {
+            MongoQuery::Shell(text) => parse_command(text)?,
+            MongoQuery::Collection { name, filter, limit } => MongoCommand::Find {
+                collection: name.to_string(),
+                filter: parse_filter(filter.unwrap_or("{}"))?,
+                limit: limit.map(|limit| limit as i64),
+            },
+        };
+        println!("{}", run_command(&client, &mut database, command).await.map_err(|e| describe_mongo_error(&e))?);
+        return Ok(());
+    }
+
+    println!("Connecting to MongoDB...");
+    // The driver connects lazily; ping so bad hosts or credentials show up now
+    if let Err(e) = client.database(&database).run_command(mongodb::bson::doc! { "ping": 1 }).await {
+        eprintln!("❌ Failed to connect to MongoDB: {}", describe_mongo_error(&e));
+        return Err(describe_mongo_error(&e).into());
+    }
+    println!("✅ Connected successfully to MongoDB database '{}'!", database);
+    println!("Interactive MongoDB session started. Try db.users.find({{\"active\": true}}) or show collections.");
+    println!("Type 'help' for commands, 'exit' or 'quit' to end the session.\n");
+
+    loop {
+        print!("{}> ", database);
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input)? == 0 {
+            println!();
+            break;
+        }
+        let line = input.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line.eq_ignore_ascii_case("exit") || line.eq_ignore_ascii_case("quit") {
+            break;
+        }
+        let output = match parse_command(line) {
+            Ok(command) => run_command(&client, &mut database, command).await.map_err(|e| describe_mongo_error(&e)),
+            Err(e) => Err(e),
+        };
+        match output {
+            Ok(output) => println!("{}", output),
+            Err(e) => eprintln!("❌ {}", e),
+        }
+    }
+    println!("Goodbye! 👋");
+    Ok(())
+}
+
+async fn run_command(client: &Client, database: &mut String, command: MongoCommand) -> Result<String, mongodb::error::Error> {
+    let db: Database = client.database(database);
+    match command {
+        MongoCommand::Find { collection, filter, limit } => {
+            let cursor = db.collection::<Document>(&collection)
+                .find(filter)
+                .limit(limit.unwrap_or(DEFAULT_FIND_LIMIT))
+                .await?;
+            let documents: Vec<Document> = cursor.try_collect().await?;
+            if documents.is_empty() {
+                return Ok("No documents found.".to_string());
+            }
+            let shown = documents.len() as i64;
+            let mut output = documents.into_iter().map(format_document).collect::<Vec<_>>().join("\n");
+            if limit.is_none() && shown == DEFAULT_FIND_LIMIT {
+                output.push_str(&format!("\n(showing the first {}; add .limit(N) or --limit for more)", DEFAULT_FIND_LIMIT));
+            }
+            Ok(output)
+        }
+        MongoCommand::FindOne { collection, filter } => {
+            match db.collection::<Document>(&collection).find_one(filter).await? {
+                Some(document) => Ok(format_document(document)),
+                None => Ok("null".to_string()),
+            }
+        }
+        MongoCommand::Count { collection, filter } => {
+            Ok(db.collection::<Document>(&collection).count_documents(filter).await?.to_string())
+        }
+        MongoCommand::ShowCollections => {
+            let mut names = db.list_collection_names().await?;
+            names.sort();
+            Ok(if names.is_empty() { "No collections found.".to_string() } else { names.join("\n") })
+        }
+        MongoCommand::ShowDatabases => Ok(client.list_database_names().await?.join("\n")),
+        MongoCommand::Use(name) => {
+            *database = name;
+            Ok(format!("switched to db {}", database))
+        }
+        MongoCommand::Help => Ok(MONGO_HELP.to_string()),
+    }
+}
+
+fn format_document(document: Document) -> String {
+    format!("{:#}", Bson::Document(document).into_relaxed_extjson())
+}
+
+// The driver's Display adds labels and empty source fields; the kind alone reads better
+fn describe_mongo_error(error: &mongodb::error::Error) -> String {
+    error.kind.to_string()
+}
+
+// Extended JSON such as {"$oid": "..."} or {"$date": "..."} becomes the matching BSON type
+fn parse_filter(text: &str) -> Result<Document, String> {
+    let value: Value = serde_json::from_str(text).map_err(|e| format!("Invalid filter JSON: {}", e))?;
+    match Bson::try_from(value).map_err(|e| format!("Invalid filter: {}", e))? {
+        Bson::Document(document) => Ok(document),
+        _ => Err("The filter must be a JSON object".to_string()),
+    }
+}
+
+fn parse_command(input: &str) -> Result<MongoCommand, String> {
+    let input = input.trim().trim_end_matches(';').trim();
+    let words: Vec<&str> = input.split_whitespace().collect();
+    match words.as_slice() {
+        ["show", "collections" | "tables"] => return Ok(MongoCommand::ShowCollections),
+        ["show", "dbs" | "databases"] => return Ok(MongoCommand::ShowDatabases),
+        ["use", name] => return Ok(MongoCommand::Use(name.to_string())),
+        ["help"] => return Ok(MongoCommand::Help),
+        _ => {}
+    }
+
+    let unknown = || format!("Unknown command '{}'. Type 'help' for the supported commands", input);
+    let call = input.strip_prefix("db.").ok_or_else(unknown)?;
+    // Collection names may contain dots, so look for the method instead
+    let (position, method) = ["find", "findOne", "countDocuments", "count"].iter()
+        .filter_map(|method| call.find(&format!(".{}(", method)).map(|position| (position, *method)))
+        .min()
+        .ok_or_else(unknown)?;
+    let collection = &call[..position];
+    if collection.is_empty() {
+        return Err(unknown());
+    }
+    let (filter, rest) = call_argument(&call[position + method.len() + 2..])?;
+    let filter = filter.map(parse_filter).transpose()?.unwrap_or_default();
+
+    let collection = collection.to_string();
+    match (method, rest.trim()) {
+        ("find", "") => Ok(MongoCommand::Find { collection, filter, limit: None }),
+        ("find", rest) => {
+            let limit = rest.strip_prefix(".limit(")
+                .and_then(|rest| rest.strip_suffix(')'))
+                .and_then(|limit| limit.trim().parse::<i64>().ok())
+                .ok_or_else(|| format!("Unsupported cursor method '{}'. Only .limit(N) is supported", rest))?;
+            Ok(MongoCommand::Find { collection, filter, limit: Some(limit) })
+        }
+        ("findOne", "") => Ok(MongoCommand::FindOne { collection, filter }),
+        ("countDocuments" | "count", "") => Ok(MongoCommand::Count { collection, filter }),
+        (_, rest) => Err(format!("Unexpected '{}' after {}()", rest, method)),
+    }
+}
+
+// Reads the optional JSON argument of a call up to its closing parenthesis
+// and returns it along with whatever follows
+fn call_argument(text: &str) -> Result<(Option<&str>, &str), String> {
+    if let Some(rest) = text.trim_start().strip_prefix(')') {
+        return Ok((None, rest));
+    }
+    let mut values = serde_json::Deserializer::from_str(text).into_iter::<Value>();
+    values.next().ok_or("Missing closing parenthesis")?.map_err(|e| format!("Invalid filter JSON: {}", e))?;
+    let end = values.byte_offset();
+    let rest = text[end..].trim_start().strip_prefix(')').ok_or("Expected ) after the filter")?;
+    Ok((Some(&text[..end]), rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::bson::{doc, oid::ObjectId};
+
+    #[test]
+    fn test_parse_find() {
+        assert_eq!(
+            parse_command("db.users.find({\"active\": true})").unwrap(),
+            MongoCommand::Find { collection: "users".to_string(), filter: doc! { "active": true }, limit: None }
+        );
+        assert_eq!(
+            parse_command("db.system.profile.find().limit(5);").unwrap(),
+            MongoCommand::Find { collection: "system.profile".to_string(), filter: Document::new(), limit: Some(5) }
+        );
+        assert_eq!(
+            parse_command("db.orders.countDocuments({\"total\": {\"$gt\": 100}})").unwrap(),
+            MongoCommand::Count { collection: "orders".to_string(), filter: doc! { "total": { "$gt": 100 } } }
+        );
+    }
+
+    #[test]
+    fn test_parse_shell_commands() {
+        assert_eq!(parse_command("show collections").unwrap(), MongoCommand::ShowCollections);
+        assert_eq!(parse_command("show dbs").unwrap(), MongoCommand::ShowDatabases);
+        assert_eq!(parse_command("use analytics").unwrap(), MongoCommand::Use("analytics".to_string()));
+        assert!(parse_command("db.users.drop()").is_err());
+        assert!(parse_command("db.users.find({active: true})").is_err());
+        assert!(parse_command("db.users.find({}).sort({\"a\": 1})").is_err());
+        assert!(parse_command("db.users.find({}").is_err());
+    }
+
+    #[test]
+    fn test_parse_filter_extended_json() {
+        let filter = parse_filter("{\"_id\": {\"$oid\": \"65f1c0ffee0000000000abcd\"}}").unwrap();
+        assert_eq!(filter, doc! { "_id": ObjectId::parse_str("65f1c0ffee0000000000abcd").unwrap() });
+        assert!(parse_filter("[1, 2]").is_err());
+    }
+}
@@ -0,0 +1,314 @@
+use csv::Writer;
+use serde_json::Value;
+use std::error::Error;
+use std::io::Write;
+use std::path::Path;
+
+pub const OUTPUT_FORMATS: [&str; 4] = ["table", "csv", "json", "markdown"];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum OutputFormat {
+    Table,
+    #[default]
+    Csv,
+    Json,
+    Markdown,
+}
+
+impl OutputFormat {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "table" => Some(OutputFormat::Table),
+            "csv" => Some(OutputFormat::Csv),
+            "json" => Some(OutputFormat::Json),
+            "markdown" | "md" => Some(OutputFormat::Markdown),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            OutputFormat::Table => "table",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Json => "json",
+            OutputFormat::Markdown => "markdown",
+        }
+    }
+}
+
+pub const EXPORT_FORMATS: [&str; 4] = ["csv", "json", "jsonl", "markdown"];
+
+// File formats for exported results; JSON files hold a single array
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    JsonLines,
+    Markdown,
+}
+
+impl ExportFormat {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "csv" => Some(ExportFormat::Csv),
+            "json" => Some(ExportFormat::Json),
+            "jsonl" | "ndjson" => Some(ExportFormat::JsonLines),
+            "markdown" | "md" => Some(ExportFormat::Markdown),
+            _ => None,
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension().and_then(|ext| ext.to_str()).and_then(Self::parse)
+    }
+}
+
+// Query results with typed cells so JSON output keeps numbers, booleans and nulls
+#[derive(Debug, Default)]
+pub struct ResultSet {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+pub fn export(result: &ResultSet, format: ExportFormat) -> Result<String, Box<dyn Error>> {
+    write_all(RowWriter::for_export(Vec::new(), format), result)
+}
+
+pub fn render(result: &ResultSet, format: OutputFormat) -> Result<String, Box<dyn Error>> {
+    write_all(RowWriter::for_output(Vec::new(), format), result)
+}
+
+fn write_all(mut writer: RowWriter<Vec<u8>>, result: &ResultSet) -> Result<String, Box<dyn Error>> {
+    writer.write_batch(&result.columns, &result.rows)?;
+    Ok(String::from_utf8(writer.finish()?)?)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Style {
+    Table,
+    Csv,
+    JsonLines,
+    JsonArray,
+    Markdown,
+}
+
+// Writes rows as they arrive so large results never have to be held in
+// memory. Headers are written with the first batch; aligned tables are laid
+// out per batch, like psql with FETCH_COUNT set.
+pub struct RowWriter<W: Write> {
+    out: W,
+    style: Style,
+    rows: usize,
+}
+
+impl<W: Write> RowWriter<W> {
+    pub fn for_output(out: W, format: OutputFormat) -> Self {
+        let style = match format {
+            OutputFormat::Table => Style::Table,
+            OutputFormat::Csv => Style::Csv,
+            OutputFormat::Json => Style::JsonLines,
+            OutputFormat::Markdown => Style::Markdown,
+        };
+        RowWriter { out, style, rows: 0 }
+    }
+
+    pub fn for_export(out: W, format: ExportFormat) -> Self {
+        let style = match format {
+            ExportFormat::Csv => Style::Csv,
+            ExportFormat::Json => Style::JsonArray,
+            ExportFormat::JsonLines => Style::JsonLines,
+            ExportFormat::Markdown => Style::Markdown,
+        };
+        RowWriter { out, style, rows: 0 }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn write_batch(&mut self, columns: &[String], rows: &[Vec<Value>]) -> Result<(), Box<dyn Error>> {
+        let first = self.rows == 0;
+        match self.style {
+            Style::Table => self.out.write_all(table_batch(columns, rows).as_bytes())?,
+            Style::Csv => {
+                let mut writer = Writer::from_writer(&mut self.out);
+                if first {
+                    writer.write_record(columns)?;
+                }
+                for row in rows {
+                    writer.write_record(row.iter().map(cell_text))?;
+                }
+                writer.flush()?;
+            }
+            Style::JsonLines => {
+                for row in rows {
+                    writeln!(self.out, "{}", row_json(columns, row)?)?;
+                }
+            }
+            Style::JsonArray => {
+                for (i, row) in rows.iter().enumerate() {
+                    let separator = if first && i == 0 { "[\n" } else { ",\n" };
+                    write!(self.out, "{}  {}", separator, row_json(columns, row)?)?;
+                }
+            }
+            Style::Markdown => {
+                let escape = |text: String| text.replace('|', "\\|").replace('\n', "<br>");
+                let line = |values: Vec<String>| format!("| {} |\n", values.join(" | "));
+                if first {
+                    self.out.write_all(line(columns.iter().map(|c| escape(c.clone())).collect()).as_bytes())?;
+                    self.out.write_all(line(columns.iter().map(|_| "---".to_string()).collect()).as_bytes())?;
+                }
+                for row in rows {
+                    self.out.write_all(line(row.iter().map(|value| escape(cell_text(value))).collect()).as_bytes())?;
+                }
+            }
+        }
+        self.rows += rows.len();
+        self.out.flush()?;
+        Ok(())
+    }
+
+    // Closes the JSON array or prints the table's row count
+    pub fn finish(mut self) -> Result<W, Box<dyn Error>> {
+        match self.style {
+            Style::JsonArray if self.rows == 0 => self.out.write_all(b"[]\n")?,
+            Style::JsonArray => self.out.write_all(b"\n]\n")?,
+            Style::Table if self.rows > 0 => {
+                writeln!(self.out, "({} row{})", self.rows, if self.rows == 1 { "" } else { "s" })?
+            }
+            _ => {}
+        }
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+// Plain text for a cell; NULL renders as an empty string
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+// A row as a JSON object with keys in column order
+fn row_json(columns: &[String], row: &[Value]) -> Result<String, serde_json::Error> {
+    let fields = columns.iter().zip(row)
+        .map(|(column, value)| Ok(format!("{}:{}", serde_json::to_string(column)?, serde_json::to_string(value)?)))
+        .collect::<Result<Vec<_>, serde_json::Error>>()?;
+    Ok(format!("{{{}}}", fields.join(",")))
+}
+
+fn table_batch(columns: &[String], rows: &[Vec<Value>]) -> String {
+    let cells: Vec<Vec<String>> = rows.iter()
+        .map(|row| row.iter().map(|value| cell_text(value).replace('\n', "\\n")).collect())
+        .collect();
+    let widths: Vec<usize> = columns.iter().enumerate()
+        .map(|(i, column)| {
+            cells.iter().map(|row| row[i].chars().count()).chain([column.chars().count()]).max().unwrap_or(0)
+        })
+        .collect();
+    let border = format!("+{}+\n", widths.iter().map(|w| "-".repeat(w + 2)).collect::<Vec<_>>().join("+"));
+    let line = |values: Vec<String>| format!("| {} |\n", values.join(" | "));
+
+    let mut output = border.clone();
+    output.push_str(&line(columns.iter().zip(&widths).map(|(c, &w)| format!("{:<w$}", c)).collect()));
+    output.push_str(&border);
+    for (row, values) in cells.iter().zip(rows) {
+        output.push_str(&line(row.iter().zip(&widths).zip(values)
+            .map(|((cell, &w), value)| if value.is_number() { format!("{:>w$}", cell) } else { format!("{:<w$}", cell) })
+            .collect()));
+    }
+    output.push_str(&border);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample() -> ResultSet {
+        ResultSet {
+            columns: vec!["id".to_string(), "name".to_string(), "active".to_string()],
+            rows: vec![
+                vec![json!(1), json!("Ada"), json!(true)],
+                vec![json!(20), json!("Grace, H|B"), Value::Null],
+            ],
+        }
+    }
+
+    #[test]
+    fn test_parse_output_format() {
+        assert_eq!(OutputFormat::parse("TABLE"), Some(OutputFormat::Table));
+        assert_eq!(OutputFormat::parse("md"), Some(OutputFormat::Markdown));
+        assert_eq!(OutputFormat::parse("xml"), None);
+        for name in OUTPUT_FORMATS {
+            assert_eq!(OutputFormat::parse(name).unwrap().name(), name);
+        }
+    }
+
+    #[test]
+    fn test_render_csv() {
+        assert_eq!(render(&sample(), OutputFormat::Csv).unwrap(), "id,name,active\n1,Ada,true\n20,\"Grace, H|B\",\n");
+    }
+
+    #[test]
+    fn test_render_json_lines() {
+        assert_eq!(
+            render(&sample(), OutputFormat::Json).unwrap(),
+            "{\"id\":1,\"name\":\"Ada\",\"active\":true}\n{\"id\":20,\"name\":\"Grace, H|B\",\"active\":null}\n"
+        );
+    }
+
+    #[test]
+    fn test_export_formats() {
+        assert_eq!(ExportFormat::from_path(Path::new("out/results.CSV")), Some(ExportFormat::Csv));
+        assert_eq!(ExportFormat::from_path(Path::new("rows.ndjson")), Some(ExportFormat::JsonLines));
+        assert_eq!(ExportFormat::from_path(Path::new("rows.parquet")), None);
+        assert_eq!(ExportFormat::from_path(Path::new("rows")), None);
+        assert_eq!(
+            export(&sample(), ExportFormat::Json).unwrap(),
+            "[\n  {\"id\":1,\"name\":\"Ada\",\"active\":true},\n  {\"id\":20,\"name\":\"Grace, H|B\",\"active\":null}\n]\n"
+        );
+        assert_eq!(export(&ResultSet::default(), ExportFormat::Json).unwrap(), "[]\n");
+    }
+
+    #[test]
+    fn test_render_table() {
+        let expected = "\
++----+------------+--------+
+| id | name       | active |
++----+------------+--------+
+|  1 | Ada        | true   |
+| 20 | Grace, H|B |        |
++----+------------+--------+
+(2 rows)
+";
+        assert_eq!(render(&sample(), OutputFormat::Table).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        assert_eq!(
+            render(&sample(), OutputFormat::Markdown).unwrap(),
+            "| id | name | active |\n| --- | --- | --- |\n| 1 | Ada | true |\n| 20 | Grace, H\\|B |  |\n"
+        );
+    }
+
+    #[test]
+    fn test_row_writer_batches() {
+        let result = sample();
+        let mut writer = RowWriter::for_export(Vec::new(), ExportFormat::Csv);
+        writer.write_batch(&result.columns, &result.rows[..1]).unwrap();
+        writer.write_batch(&result.columns, &result.rows[1..]).unwrap();
+        assert_eq!(writer.rows(), 2);
+        assert_eq!(String::from_utf8(writer.finish().unwrap()).unwrap(), render(&result, OutputFormat::Csv).unwrap());
+
+        let mut writer = RowWriter::for_export(Vec::new(), ExportFormat::Json);
+        writer.write_batch(&result.columns, &result.rows[..1]).unwrap();
+        writer.write_batch(&result.columns, &result.rows[1..]).unwrap();
+        assert_eq!(String::from_utf8(writer.finish().unwrap()).unwrap(), export(&result, ExportFormat::Json).unwrap());
+    }
+}
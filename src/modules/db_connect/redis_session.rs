@@ -0,0 +1,241 @@
+// Redis sessions: commands are sent as typed and replies are printed the way
+// redis-cli shows them, with a few readability tweaks for common commands
+use redis::{Connection, Value};
+use std::error::Error;
+use std::io::{self, Write};
+
+// Runs each line of `query` as a command, or starts an interactive prompt
+pub fn run(connection_string: &str, query: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let client = redis::Client::open(connection_string)
+        .map_err(|e| format!("Invalid Redis connection string: {}", e))?;
+
+    if let Some(query) = query {
+        let mut connection = client.get_connection().map_err(|e| format!("Failed to connect to Redis: {}", e))?;
+        for line in query.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            println!("{}", run_command(&mut connection, line)?);
+        }
+        return Ok(());
+    }
+
+    println!("Connecting to Redis...");
+    let mut connection = match client.get_connection() {
+        Ok(connection) => connection,
+        Err(e) => {
+            eprintln!("❌ Failed to connect to Redis: {}", e);
+            return Err(e.into());
+        }
+    };
+    println!("✅ Connected successfully to Redis!");
+    println!("Interactive Redis session started. Type commands like GET, SET, KEYS, SCAN, TTL or HGETALL.");
+    println!("Type 'exit' or 'quit' to end the session.\n");
+
+    loop {
+        print!("redis> ");
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input)? == 0 {
+            println!();
+            break;
+        }
+        let line = input.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line.eq_ignore_ascii_case("exit") || line.eq_ignore_ascii_case("quit") {
+            break;
+        }
+        match run_command(&mut connection, line) {
+            Ok(output) => println!("{}", output),
+            Err(e) => eprintln!("❌ {}", e),
+        }
+    }
+    println!("Goodbye! 👋");
+    Ok(())
+}
+
+fn run_command(connection: &mut Connection, line: &str) -> Result<String, Box<dyn Error>> {
+    let args = split_args(line)?;
+    let Some((name, rest)) = args.split_first() else {
+        return Err("Empty command".into());
+    };
+    let reply: Value = redis::cmd(name).arg(rest).query(connection).map_err(|e| describe_redis_error(&e))?;
+    Ok(format_reply(&name.to_uppercase(), &reply))
+}
+
+// Server errors as redis-cli shows them, e.g. "(error) WRONGTYPE Operation against a key..."
+fn describe_redis_error(error: &redis::RedisError) -> String {
+    match (error.code(), error.detail()) {
+        (Some(code), Some(detail)) => format!("(error) {} {}", code, detail),
+        _ => format!("(error) {}", error),
+    }
+}
+
+// Splits a command line like redis-cli: whitespace separated, with single or
+// double quotes around arguments that contain spaces. Double quotes accept
+// \n, \r, \t, \" and \\ escapes.
+fn split_args(line: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let Some(&first) = chars.peek() else {
+            return Ok(args);
+        };
+        let mut arg = String::new();
+        if first == '"' || first == '\'' {
+            chars.next();
+            loop {
+                match chars.next() {
+                    Some(c) if c == first => break,
+                    Some('\\') if first == '"' => match chars.next() {
+                        Some('n') => arg.push('\n'),
+                        Some('r') => arg.push('\r'),
+                        Some('t') => arg.push('\t'),
+                        Some(other) => arg.push(other),
+                        None => return Err("Unbalanced quotes in command".to_string()),
+                    },
+                    Some(c) => arg.push(c),
+                    None => return Err("Unbalanced quotes in command".to_string()),
+                }
+            }
+            if chars.peek().is_some_and(|c| !c.is_whitespace()) {
+                return Err("Closing quote must be followed by a space".to_string());
+            }
+        } else {
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                arg.push(c);
+            }
+        }
+        args.push(arg);
+    }
+}
+
+fn format_reply(command: &str, reply: &Value) -> String {
+    match (command, reply) {
+        ("HGETALL" | "CONFIG", Value::Array(items)) if items.len() % 2 == 0 => format_pairs(items),
+        ("SCAN" | "HSCAN" | "SSCAN" | "ZSCAN", Value::Array(items)) if items.len() == 2 => {
+            let entries = match (command, &items[1]) {
+                ("HSCAN" | "ZSCAN", Value::Array(pairs)) if pairs.len() % 2 == 0 => format_pairs(pairs),
+                (_, entries) => format_value(entries, 0),
+            };
+            format!("Next cursor: {}\n{}", format_value(&items[0], 0).trim_matches('"'), entries)
+        }
+        ("TTL" | "PTTL", Value::Int(ttl)) => match ttl {
+            -2 => "(integer) -2 (key does not exist)".to_string(),
+            -1 => "(integer) -1 (no expiry)".to_string(),
+            ttl => format!("(integer) {} ({})", ttl, if command == "TTL" { "seconds" } else { "milliseconds" }),
+        },
+        _ => format_value(reply, 0),
+    }
+}
+
+// Field/value lists such as HGETALL replies, one `field => value` per line
+fn format_pairs(items: &[Value]) -> String {
+    if items.is_empty() {
+        return "(empty hash)".to_string();
+    }
+    items.chunks(2)
+        .enumerate()
+        .map(|(i, pair)| format!("{}) {} => {}", i + 1, format_value(&pair[0], 0), format_value(&pair[1], 0)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// redis-cli style: quoted strings, (integer)/(nil) markers and numbered,
+// indented arrays
+fn format_value(value: &Value, indent: usize) -> String {
+    match value {
+        Value::Nil => "(nil)".to_string(),
+        Value::Int(n) => format!("(integer) {}", n),
+        Value::Double(n) => format!("(double) {}", n),
+        Value::Boolean(b) => format!("(boolean) {}", b),
+        Value::Okay => "OK".to_string(),
+        Value::SimpleString(text) => text.clone(),
+        Value::BulkString(bytes) => quote_bytes(bytes),
+        Value::VerbatimString { text, .. } => quote_bytes(text.as_bytes()),
+        Value::ServerError(e) => format!("(error) {:?}", e),
+        Value::Array(items) | Value::Set(items) => format_list(items.iter(), indent),
+        Value::Map(pairs) => {
+            format_pairs(&pairs.iter().flat_map(|(k, v)| [k.clone(), v.clone()]).collect::<Vec<_>>())
+        }
+        other => format!("{:?}", other),
+    }
+}
+
+fn format_list<'a>(items: impl ExactSizeIterator<Item = &'a Value>, indent: usize) -> String {
+    if items.len() == 0 {
+        return "(empty array)".to_string();
+    }
+    let width = items.len().to_string().len();
+    items.enumerate()
+        .map(|(i, item)| {
+            let prefix = format!("{:>width$}) ", i + 1);
+            let body = format_value(item, indent + prefix.len());
+            if i == 0 { format!("{}{}", prefix, body) } else { format!("{}{}{}", " ".repeat(indent), prefix, body) }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Valid UTF-8 is shown as text; other bytes as \xNN escapes
+fn quote_bytes(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => format!("{:?}", text),
+        Err(_) => {
+            let escaped: String = bytes.iter().map(|&b| match b {
+                b'"' => "\\\"".to_string(),
+                b'\\' => "\\\\".to_string(),
+                0x20..=0x7e => (b as char).to_string(),
+                _ => format!("\\x{:02x}", b),
+            }).collect();
+            format!("\"{}\"", escaped)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(text: &str) -> Value {
+        Value::BulkString(text.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_split_args() {
+        assert_eq!(split_args("SET greeting \"hello world\"").unwrap(), vec!["SET", "greeting", "hello world"]);
+        assert_eq!(split_args("  GET   'user:1 name'  ").unwrap(), vec!["GET", "user:1 name"]);
+        assert_eq!(split_args("SET k \"a\\nb\\\"c\"").unwrap(), vec!["SET", "k", "a\nb\"c"]);
+        assert_eq!(split_args("SET k ''").unwrap(), vec!["SET", "k", ""]);
+        assert!(split_args("SET k \"open").is_err());
+        assert!(split_args("SET k \"a\"b").is_err());
+    }
+
+    #[test]
+    fn test_format_scalars() {
+        assert_eq!(format_reply("GET", &Value::Nil), "(nil)");
+        assert_eq!(format_reply("GET", &bulk("Ada")), "\"Ada\"");
+        assert_eq!(format_reply("GET", &Value::BulkString(vec![0xff, b'a'])), "\"\\xffa\"");
+        assert_eq!(format_reply("SET", &Value::Okay), "OK");
+        assert_eq!(format_reply("INCR", &Value::Int(3)), "(integer) 3");
+        assert_eq!(format_reply("TTL", &Value::Int(-1)), "(integer) -1 (no expiry)");
+        assert_eq!(format_reply("TTL", &Value::Int(42)), "(integer) 42 (seconds)");
+    }
+
+    #[test]
+    fn test_format_arrays() {
+        let keys = Value::Array(vec![bulk("user:1"), bulk("user:2")]);
+        assert_eq!(format_reply("KEYS", &keys), "1) \"user:1\"\n2) \"user:2\"");
+        assert_eq!(format_reply("KEYS", &Value::Array(vec![])), "(empty array)");
+
+        let nested = Value::Array(vec![bulk("a"), Value::Array(vec![bulk("b"), bulk("c")])]);
+        assert_eq!(format_reply("LRANGE", &nested), "1) \"a\"\n2) 1) \"b\"\n   2) \"c\"");
+
+        let hash = Value::Array(vec![bulk("name"), bulk("Ada"), bulk("role"), bulk("admin")]);
+        assert_eq!(format_reply("HGETALL", &hash), "1) \"name\" => \"Ada\"\n2) \"role\" => \"admin\"");
+
+        let scan = Value::Array(vec![bulk("17"), Value::Array(vec![bulk("session:9")])]);
+        assert_eq!(format_reply("SCAN", &scan), "Next cursor: 17\n1) \"session:9\"");
+    }
+}
@@ -0,0 +1,317 @@
+// The interactive PostgreSQL session. The prompt loop owns a PgSession, which
+// owns the client and replaces it when the server goes away.
+use super::meta::MetaCommand;
+use super::output::{ExportFormat, OutputFormat, ResultSet, RowWriter, EXPORT_FORMATS, OUTPUT_FORMATS};
+use super::statements::{split_statements, transaction_control, TransactionControl};
+use super::{
+    cancel_running_query, describe_pg_error, establish_connection, export_to_file, finish_output, report_truncation,
+    run_meta_command, stream_query, ConnectParams, KEEP_ROWS,
+};
+use std::error::Error;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Child, Command as ProcessCommand, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+use tokio_postgres::Client;
+
+// A connection unused for this long is pinged before the next statement
+const IDLE_CHECK_AFTER: Duration = Duration::from_secs(60);
+const PING_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TxState {
+    Idle,
+    Open,
+    // A statement failed; only ROLLBACK is accepted until the transaction ends
+    Failed,
+}
+
+pub struct SessionOptions {
+    pub autocommit: bool,
+    pub limit: Option<usize>,
+    pub pager: bool,
+}
+
+pub struct PgSession {
+    params: ConnectParams,
+    client: Client,
+    tx: TxState,
+    last_used: Instant,
+}
+
+impl PgSession {
+    pub async fn connect(params: ConnectParams) -> Result<Self, Box<dyn Error>> {
+        let client = establish_connection(&params).await?;
+        Ok(PgSession { params, client, tx: TxState::Idle, last_used: Instant::now() })
+    }
+
+    // Called before talking to the server. A closed connection is replaced,
+    // and one that sat idle is pinged first because servers and proxies
+    // drop idle connections without the client noticing.
+    async fn ensure_connected(&mut self) -> Result<(), Box<dyn Error>> {
+        let healthy = !self.client.is_closed() && (self.last_used.elapsed() < IDLE_CHECK_AFTER || self.ping().await);
+        if !healthy {
+            if self.tx == TxState::Idle {
+                eprintln!("⚠️  Connection lost, reconnecting...");
+            } else {
+                eprintln!("⚠️  Connection lost; the open transaction was rolled back. Reconnecting...");
+                self.tx = TxState::Idle;
+            }
+            self.client = establish_connection(&self.params).await?;
+            eprintln!("✅ Reconnected");
+        }
+        self.last_used = Instant::now();
+        Ok(())
+    }
+
+    async fn ping(&self) -> bool {
+        matches!(tokio::time::timeout(PING_TIMEOUT, self.client.simple_query("")).await, Ok(Ok(_)))
+    }
+
+    async fn batch_execute(&mut self, sql: &str) -> Result<(), Box<dyn Error>> {
+        self.ensure_connected().await?;
+        self.client.batch_execute(sql).await.map_err(|e| describe_pg_error(&e).into())
+    }
+
+    // An open transaction is rolled back explicitly so the user is told about it
+    async fn end(self) {
+        if self.tx != TxState::Idle && !self.client.is_closed() && self.client.batch_execute("ROLLBACK").await.is_ok() {
+            println!("Rolled back the open transaction.");
+        }
+        println!("Goodbye! 👋");
+    }
+}
+
+// Reads a line on a blocking thread so the runtime keeps driving the
+// connection (and noticing when it drops) while the prompt waits
+async fn read_line() -> io::Result<Option<String>> {
+    tokio::task::spawn_blocking(|| {
+        let mut input = String::new();
+        match io::stdin().read_line(&mut input)? {
+            0 => Ok(None),
+            _ => Ok(Some(input)),
+        }
+    })
+    .await
+    .map_err(io::Error::other)?
+}
+
+pub async fn run(mut session: PgSession, mut format: OutputFormat, options: &SessionOptions) -> Result<(), Box<dyn Error>> {
+    let mut last_result: Option<ResultSet> = None;
+
+    // Ctrl-C cancels the running query instead of ending the session
+    let interrupt = Arc::new(Notify::new());
+    let query_running = Arc::new(AtomicBool::new(false));
+    {
+        let interrupt = interrupt.clone();
+        let query_running = query_running.clone();
+        tokio::spawn(async move {
+            while tokio::signal::ctrl_c().await.is_ok() {
+                if query_running.load(Ordering::SeqCst) {
+                    interrupt.notify_waiters();
+                } else {
+                    eprintln!("\n(Type 'exit' or press Ctrl-D to end the session)");
+                }
+            }
+        });
+    }
+    // SQL typed so far; statements run once a line ends them with `;`
+    let mut buffer = String::new();
+
+    loop {
+        // psql-style markers: * inside a transaction, ! after an error in one
+        let marker = match session.tx {
+            TxState::Idle => "",
+            TxState::Open => "*",
+            TxState::Failed => "!",
+        };
+        print!("sql{}{} ", marker, if buffer.is_empty() { ">" } else { "->" });
+        io::stdout().flush()?;
+
+        let Some(input) = read_line().await? else {
+            // End of input (Ctrl-D or a closed pipe)
+            println!();
+            break;
+        };
+        let line = input.trim();
+
+        if line == "\\r" {
+            buffer.clear();
+            println!("Query buffer reset.");
+            continue;
+        }
+
+        // Commands are only recognised at the start of a statement
+        if buffer.is_empty() {
+            if line.is_empty() {
+                continue;
+            }
+
+            let command = line.trim_end_matches(';');
+            if command.eq_ignore_ascii_case("exit") || command.eq_ignore_ascii_case("quit") {
+                break;
+            }
+
+            if let Some(argument) = line.strip_prefix("\\format") {
+                match argument.trim() {
+                    "" => println!("Output format is {}", format.name()),
+                    name => match OutputFormat::parse(name) {
+                        Some(new_format) => {
+                            format = new_format;
+                            println!("Output format is now {}", format.name());
+                        }
+                        None => eprintln!("❌ Unknown format '{}'. Use one of: {}", name, OUTPUT_FORMATS.join(", ")),
+                    },
+                }
+                continue;
+            }
+
+            if let Some(arguments) = line.strip_prefix("\\export") {
+                let arguments: Vec<&str> = arguments.split_whitespace().collect();
+                let target = match arguments.as_slice() {
+                    [path] => Ok((None, *path)),
+                    [name, path] => ExportFormat::parse(name)
+                        .map(|format| (Some(format), *path))
+                        .ok_or_else(|| format!("Unknown export format '{}'. Use one of: {}", name, EXPORT_FORMATS.join(", "))),
+                    _ => Err("Usage: \\export [FORMAT] FILE".to_string()),
+                };
+                let outcome = target.map_err(Box::<dyn Error>::from).and_then(|(export_format, path)| match &last_result {
+                    Some(result) => export_to_file(result, Path::new(path), export_format),
+                    None => Err(format!(
+                        "Nothing to export. Run a query first; results over {} rows are not kept, so export those with --query and --export",
+                        KEEP_ROWS
+                    ).into()),
+                });
+                match outcome {
+                    Ok(summary) => println!("{}", summary),
+                    Err(e) => eprintln!("❌ {}", e),
+                }
+                continue;
+            }
+
+            let control = match command {
+                "\\begin" => Some(("BEGIN", TransactionControl::Begin)),
+                "\\commit" => Some(("COMMIT", TransactionControl::Commit)),
+                "\\rollback" => Some(("ROLLBACK", TransactionControl::Rollback)),
+                _ => None,
+            };
+            if let Some((sql, control)) = control {
+                match session.batch_execute(sql).await {
+                    Ok(()) => {
+                        session.tx = if control == TransactionControl::Begin { TxState::Open } else { TxState::Idle };
+                        println!("{}", sql);
+                    }
+                    Err(e) => eprintln!("❌ {}", e),
+                }
+                continue;
+            }
+
+            if line.starts_with('\\') {
+                let output = match MetaCommand::parse(line) {
+                    Ok(command) => match session.ensure_connected().await {
+                        Ok(()) => run_meta_command(&session.client, &command, format).await,
+                        Err(e) => Err(e),
+                    },
+                    Err(e) => Err(e.into()),
+                };
+                match output {
+                    Ok(output) => println!("{}", output),
+                    Err(e) => eprintln!("❌ {}", e),
+                }
+                continue;
+            }
+        }
+
+        buffer.push_str(input.trim_end_matches(['\n', '\r']));
+        buffer.push('\n');
+        let (statements, rest) = split_statements(&buffer);
+        buffer = if rest.trim().is_empty() { String::new() } else { rest };
+
+        for statement in statements {
+            if let Err(e) = session.ensure_connected().await {
+                eprintln!("❌ {}", e);
+                break;
+            }
+            let control = transaction_control(&statement);
+            if !options.autocommit && session.tx == TxState::Idle && control.is_none() {
+                if let Err(e) = session.batch_execute("BEGIN").await {
+                    eprintln!("❌ Query error: {}", e);
+                    continue;
+                }
+                session.tx = TxState::Open;
+            }
+
+            // Stream the rows in the session's format, through the pager if enabled
+            let cancel_token = session.client.cancel_token();
+            let (mut writer, pager) = open_output(format, options.pager);
+            query_running.store(true, Ordering::SeqCst);
+            let result = {
+                let query = stream_query(&session.client, &statement, &mut writer, options.limit, KEEP_ROWS);
+                tokio::pin!(query);
+                tokio::select! {
+                    result = &mut query => result,
+                    _ = interrupt.notified() => {
+                        if let Err(e) = cancel_running_query(&cancel_token, &session.params.tls).await {
+                            eprintln!("❌ Failed to cancel query: {}", e);
+                        }
+                        query.await
+                    }
+                }
+            };
+            query_running.store(false, Ordering::SeqCst);
+            let result = result.and_then(|streamed| finish_output(writer).map(|_| streamed));
+            if let Some(mut pager) = pager {
+                let _ = pager.wait();
+            }
+            match result {
+                Ok(streamed) => {
+                    session.tx = match control {
+                        Some(TransactionControl::Begin) => TxState::Open,
+                        Some(_) => TxState::Idle,
+                        None => session.tx,
+                    };
+                    if streamed.rows == 0 {
+                        println!("Query executed successfully. No results returned.");
+                    } else {
+                        println!();
+                    }
+                    report_truncation(&streamed, options.limit);
+                    last_result = streamed.kept;
+                }
+                Err(e) => {
+                    if session.tx != TxState::Idle {
+                        session.tx = TxState::Failed;
+                    }
+                    eprintln!("❌ Query error: {}", e);
+                    if session.client.is_closed() {
+                        eprintln!("   The connection was closed; it will be reopened for the next statement.");
+                    } else if session.tx == TxState::Failed {
+                        eprintln!("   The transaction is aborted; use \\rollback to continue.");
+                    }
+                }
+            }
+        }
+    }
+
+    session.end().await;
+    Ok(())
+}
+
+// Where interactive results go: $PAGER when enabled and it starts, otherwise stdout
+fn open_output(format: OutputFormat, use_pager: bool) -> (RowWriter<Box<dyn Write>>, Option<Child>) {
+    if use_pager {
+        let pager = std::env::var("PAGER").unwrap_or_else(|_| "less -FRX".to_string());
+        match ProcessCommand::new("sh").arg("-c").arg(&pager).stdin(Stdio::piped()).spawn() {
+            Ok(mut child) => {
+                if let Some(stdin) = child.stdin.take() {
+                    return (RowWriter::for_output(Box::new(stdin), format), Some(child));
+                }
+            }
+            Err(e) => eprintln!("⚠️  Could not start pager '{}': {}", pager, e),
+        }
+    }
+    (RowWriter::for_output(Box::new(io::stdout()), format), None)
+}
@@ -0,0 +1,135 @@
+// Splits buffered SQL on semicolons that are outside string literals, quoted
+// identifiers, dollar-quoted bodies and comments. Returns the complete
+// statements (without their `;`) and whatever is left after the last one.
+pub fn split_statements(input: &str) -> (Vec<String>, String) {
+    let chars: Vec<char> = input.chars().collect();
+    let mut statements = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            quote @ ('\'' | '"') => {
+                // A doubled quote inside the literal is just two toggles
+                i += 1;
+                while i < chars.len() && chars[i] != quote {
+                    i += 1;
+                }
+            }
+            '-' if chars.get(i + 1) == Some(&'-') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 1;
+            }
+            '$' => {
+                if let Some(tag) = dollar_tag(&chars[i..]) {
+                    let body_start = i + tag.len();
+                    i = find_sequence(&chars, body_start, &tag).map_or(chars.len(), |end| end + tag.len() - 1);
+                }
+            }
+            ';' => {
+                let statement: String = chars[start..i].iter().collect();
+                if !statement.trim().is_empty() {
+                    statements.push(statement.trim().to_string());
+                }
+                start = i + 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    let rest: String = chars[start.min(chars.len())..].iter().collect();
+    (statements, rest)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransactionControl {
+    Begin,
+    Commit,
+    Rollback,
+}
+
+// Recognises statements that open or close a transaction so the session can
+// track its state. ROLLBACK TO SAVEPOINT keeps the transaction open.
+pub fn transaction_control(statement: &str) -> Option<TransactionControl> {
+    let words: Vec<String> = statement.split_whitespace().take(2).map(|w| w.trim_end_matches(';').to_uppercase()).collect();
+    match words.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["BEGIN", ..] | ["START", "TRANSACTION"] => Some(TransactionControl::Begin),
+        ["COMMIT" | "END", ..] => Some(TransactionControl::Commit),
+        ["ROLLBACK" | "ABORT", "TO"] => None,
+        ["ROLLBACK" | "ABORT", ..] => Some(TransactionControl::Rollback),
+        _ => None,
+    }
+}
+
+// `$$` or `$tag$` at the start of `chars`
+fn dollar_tag(chars: &[char]) -> Option<Vec<char>> {
+    let end = chars.iter().skip(1).position(|&c| c == '$')? + 1;
+    let name = &chars[1..end];
+    let valid = name.first().is_none_or(|c| c.is_alphabetic() || *c == '_')
+        && name.iter().all(|c| c.is_alphanumeric() || *c == '_');
+    valid.then(|| chars[..=end].to_vec())
+}
+
+fn find_sequence(chars: &[char], from: usize, sequence: &[char]) -> Option<usize> {
+    (from..chars.len()).find(|&i| chars[i..].starts_with(sequence))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_complete_statements() {
+        let (statements, rest) = split_statements("SELECT 1;\nSELECT 2;\n");
+        assert_eq!(statements, vec!["SELECT 1", "SELECT 2"]);
+        assert_eq!(rest.trim(), "");
+    }
+
+    #[test]
+    fn test_split_keeps_incomplete_statement() {
+        let (statements, rest) = split_statements("SELECT id,\n  name\nFROM users\n");
+        assert!(statements.is_empty());
+        assert_eq!(rest, "SELECT id,\n  name\nFROM users\n");
+    }
+
+    #[test]
+    fn test_split_ignores_quoted_semicolons() {
+        let (statements, rest) = split_statements("SELECT 'a;b', \"odd;name\" FROM t -- trailing; comment\nWHERE x = 'it''s;';");
+        assert_eq!(statements, vec!["SELECT 'a;b', \"odd;name\" FROM t -- trailing; comment\nWHERE x = 'it''s;'"]);
+        assert_eq!(rest, "");
+
+        let (statements, rest) = split_statements("SELECT 'unterminated;\n");
+        assert!(statements.is_empty());
+        assert_eq!(rest, "SELECT 'unterminated;\n");
+    }
+
+    #[test]
+    fn test_transaction_control() {
+        assert_eq!(transaction_control("begin"), Some(TransactionControl::Begin));
+        assert_eq!(transaction_control("START TRANSACTION ISOLATION LEVEL SERIALIZABLE"), Some(TransactionControl::Begin));
+        assert_eq!(transaction_control("commit;"), Some(TransactionControl::Commit));
+        assert_eq!(transaction_control("END"), Some(TransactionControl::Commit));
+        assert_eq!(transaction_control("rollback"), Some(TransactionControl::Rollback));
+        assert_eq!(transaction_control("ROLLBACK TO SAVEPOINT before_delete"), None);
+        assert_eq!(transaction_control("SELECT 1"), None);
+    }
+
+    #[test]
+    fn test_split_ignores_dollar_quotes_and_block_comments() {
+        let body = "CREATE FUNCTION f() RETURNS int AS $fn$ BEGIN RETURN 1; END; $fn$ LANGUAGE plpgsql";
+        let (statements, _) = split_statements(&format!("{};", body));
+        assert_eq!(statements, vec![body]);
+
+        let (statements, _) = split_statements("SELECT /* ; */ $$a;b$$, $1;");
+        assert_eq!(statements, vec!["SELECT /* ; */ $$a;b$$, $1"]);
+    }
+}
@@ -0,0 +1,212 @@
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::WebPkiServerVerifier;
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{CertificateError, ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
+use std::error::Error;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use url::Url;
+
+// Connection string parameters handled here rather than by tokio-postgres,
+// which rejects verify-ca/verify-full and the certificate file options
+const TLS_PARAMS: [&str; 4] = ["sslmode", "sslrootcert", "sslcert", "sslkey"];
+
+// libpq's sslmode values; `allow` is treated like `prefer`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum SslMode {
+    Disable,
+    #[default]
+    Prefer,
+    Require,
+    VerifyCa,
+    VerifyFull,
+}
+
+impl SslMode {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "disable" => Ok(SslMode::Disable),
+            "allow" | "prefer" => Ok(SslMode::Prefer),
+            "require" => Ok(SslMode::Require),
+            "verify-ca" => Ok(SslMode::VerifyCa),
+            "verify-full" => Ok(SslMode::VerifyFull),
+            _ => Err(format!("Invalid sslmode '{}'. Use disable, prefer, require, verify-ca or verify-full", name)),
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct TlsSettings {
+    pub mode: SslMode,
+    pub root_cert: Option<PathBuf>,
+    pub client_cert: Option<PathBuf>,
+    pub client_key: Option<PathBuf>,
+}
+
+// Removes the TLS parameters from a postgres:// URL and returns them separately
+pub fn split_tls_params(connection_string: &str) -> Result<(String, TlsSettings), Box<dyn Error>> {
+    let mut url = Url::parse(connection_string)?;
+    let mut settings = TlsSettings::default();
+    let mut remaining = Vec::new();
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "sslmode" => settings.mode = SslMode::parse(&value)?,
+            "sslrootcert" => settings.root_cert = Some(PathBuf::from(value.as_ref())),
+            "sslcert" => settings.client_cert = Some(PathBuf::from(value.as_ref())),
+            "sslkey" => settings.client_key = Some(PathBuf::from(value.as_ref())),
+            _ => remaining.push((key.into_owned(), value.into_owned())),
+        }
+    }
+    if url.query_pairs().any(|(key, _)| TLS_PARAMS.contains(&key.as_ref())) {
+        if remaining.is_empty() {
+            url.set_query(None);
+        } else {
+            url.query_pairs_mut().clear().extend_pairs(remaining);
+        }
+    }
+    Ok((url.to_string(), settings))
+}
+
+impl TlsSettings {
+    // Like libpq, `require` with a root certificate behaves as `verify-ca`
+    pub fn effective_mode(&self) -> SslMode {
+        match self.mode {
+            SslMode::Require if self.root_cert.is_some() => SslMode::VerifyCa,
+            mode => mode,
+        }
+    }
+
+    pub fn client_config(&self) -> Result<ClientConfig, Box<dyn Error>> {
+        let mut roots = RootCertStore::empty();
+        match &self.root_cert {
+            Some(path) => {
+                for cert in read_certs(path)? {
+                    roots.add(cert).map_err(|e| format!("Invalid certificate in '{}': {}", path.display(), e))?;
+                }
+            }
+            None => roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned()),
+        }
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let inner = WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider.clone()).build()?;
+        let verifier = Arc::new(ModeVerifier { inner, mode: self.effective_mode() });
+
+        let builder = ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()?
+            .dangerous()
+            .with_custom_certificate_verifier(verifier);
+        match (&self.client_cert, &self.client_key) {
+            (Some(cert_path), Some(key_path)) => {
+                let key = rustls_pemfile::private_key(&mut BufReader::new(File::open(key_path)?))?
+                    .ok_or_else(|| format!("No private key found in '{}'", key_path.display()))?;
+                Ok(builder.with_client_auth_cert(read_certs(cert_path)?, key)?)
+            }
+            (None, None) => Ok(builder.with_no_client_auth()),
+            _ => Err("A client certificate needs both sslcert and sslkey".into()),
+        }
+    }
+}
+
+fn read_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>, Box<dyn Error>> {
+    let file = File::open(path).map_err(|e| format!("Failed to open '{}': {}", path.display(), e))?;
+    let certs = rustls_pemfile::certs(&mut BufReader::new(file)).collect::<Result<Vec<_>, _>>()?;
+    if certs.is_empty() {
+        return Err(format!("No PEM certificates found in '{}'", path.display()).into());
+    }
+    Ok(certs)
+}
+
+// prefer/require only encrypt, verify-ca checks the chain, verify-full also
+// checks the host name. Handshake signatures are always verified.
+#[derive(Debug)]
+struct ModeVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    mode: SslMode,
+}
+
+impl ServerCertVerifier for ModeVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let result = self.inner.verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now);
+        match (self.mode, result) {
+            (SslMode::VerifyFull, result) => result,
+            (SslMode::VerifyCa, Err(rustls::Error::InvalidCertificate(
+                CertificateError::NotValidForName | CertificateError::NotValidForNameContext { .. },
+            ))) => Ok(ServerCertVerified::assertion()),
+            (SslMode::VerifyCa, result) => result,
+            _ => Ok(ServerCertVerified::assertion()),
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_tls_params() {
+        let (url, settings) = split_tls_params(
+            "postgres://u:p@db.example.com:5432/app?sslmode=verify-full&sslrootcert=/etc/ca.pem&application_name=ms",
+        ).unwrap();
+        assert_eq!(url, "postgres://u:p@db.example.com:5432/app?application_name=ms");
+        assert_eq!(settings.mode, SslMode::VerifyFull);
+        assert_eq!(settings.root_cert, Some(PathBuf::from("/etc/ca.pem")));
+        assert_eq!(settings.client_cert, None);
+
+        let (url, settings) = split_tls_params("postgres://u@localhost/app?sslmode=require").unwrap();
+        assert_eq!(url, "postgres://u@localhost/app");
+        assert_eq!(settings.mode, SslMode::Require);
+
+        let (url, settings) = split_tls_params("postgres://u@localhost/app").unwrap();
+        assert_eq!(url, "postgres://u@localhost/app");
+        assert_eq!(settings, TlsSettings::default());
+
+        assert!(split_tls_params("postgres://u@localhost/app?sslmode=always").is_err());
+    }
+
+    #[test]
+    fn test_effective_mode() {
+        let mut settings = TlsSettings { mode: SslMode::Require, ..Default::default() };
+        assert_eq!(settings.effective_mode(), SslMode::Require);
+        settings.root_cert = Some(PathBuf::from("ca.pem"));
+        assert_eq!(settings.effective_mode(), SslMode::VerifyCa);
+        settings.mode = SslMode::VerifyFull;
+        assert_eq!(settings.effective_mode(), SslMode::VerifyFull);
+    }
+
+    #[test]
+    fn test_client_config_requires_cert_and_key() {
+        let settings = TlsSettings { client_cert: Some(PathBuf::from("client.pem")), ..Default::default() };
+        assert!(settings.client_config().is_err());
+        assert!(TlsSettings::default().client_config().is_ok());
+    }
+}
@@ -0,0 +1,309 @@
+use crate::file_size::format_bytes;
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use colored::*;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::WalkBuilder;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+
+pub struct DirTreeModule;
+
+type Style = fn(&str) -> ColoredString;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Kind {
+    Dir,
+    File,
+    Symlink(String),
+}
+
+#[derive(Debug)]
+struct Node {
+    kind: Kind,
+    size: u64,
+    children: BTreeMap<String, Node>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Totals {
+    dirs: usize,
+    files: usize,
+}
+
+struct Options<'a> {
+    depth: Option<usize>,
+    sizes: bool,
+    gitignore: bool,
+    globs: &'a [&'a String],
+    excludes: &'a [&'a String],
+}
+
+impl Node {
+    fn new(kind: Kind, size: u64) -> Self {
+        Node { kind, size, children: BTreeMap::new() }
+    }
+}
+
+impl ToolModule for DirTreeModule {
+    fn name(&self) -> &'static str {
+        "dir-tree"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("tree")
+                .long("tree")
+                .value_name("PATH")
+                .num_args(0..=1)
+                .default_missing_value(".")
+                .help("Print the directory tree under PATH (default: .) with file counts")
+                .long_help("Print the files and directories under PATH (default: the current directory) as a tree, like the tree command, followed by the number of directories and files shown. Limit the depth with --depth, add sizes with --size (directories show the total beneath them), show only files matching --glob, leave paths out with --exclude, and hide what .gitignore ignores with --gitignore. The .git directory is never shown.")
+        )
+        .arg(
+            Arg::new("depth")
+                .long("depth")
+                .value_name("N")
+                .value_parser(clap::value_parser!(u32).range(1..))
+                .help("How many levels deep --tree descends (default: no limit)")
+                .requires("tree")
+        )
+        .arg(
+            Arg::new("size")
+                .long("size")
+                .action(ArgAction::SetTrue)
+                .help("Show file sizes and directory totals in --tree")
+                .requires("tree")
+        )
+        .arg(
+            Arg::new("glob")
+                .long("glob")
+                .value_name("PATTERN")
+                .action(ArgAction::Append)
+                .help("Only show files matching a .gitignore-style PATTERN in --tree, e.g. '*.rs' (repeatable)")
+                .requires("tree")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(path) = matches.get_one::<String>("tree") {
+            let globs: Vec<&String> = matches.get_many::<String>("glob").map(|values| values.collect()).unwrap_or_default();
+            // --exclude is declared by the archive module, --gitignore by the gitignore-gen module
+            let excludes: Vec<&String> = matches.get_many::<String>("exclude").map(|values| values.collect()).unwrap_or_default();
+            let options = Options {
+                depth: matches.get_one::<u32>("depth").map(|&depth| depth as usize),
+                sizes: matches.get_flag("size"),
+                gitignore: matches.contains_id("gitignore"),
+                globs: &globs,
+                excludes: &excludes,
+            };
+            let root = build(Path::new(path), &options)?;
+            print!("{}", render(path, &root, options.depth, options.sizes, io::stdout().is_terminal()));
+        }
+        Ok(())
+    }
+}
+
+fn matcher(root: &Path, patterns: &[&String], flag: &str) -> Result<Gitignore, Box<dyn Error>> {
+    let mut builder = GitignoreBuilder::new(root);
+    for pattern in patterns {
+        builder.add_line(None, pattern).map_err(|e| format!("Invalid {} pattern '{}': {}", flag, pattern, e))?;
+    }
+    Ok(builder.build()?)
+}
+
+fn build(root: &Path, options: &Options) -> Result<Node, Box<dyn Error>> {
+    let metadata = fs::metadata(root).map_err(|e| format!("Can't read '{}': {}", root.display(), e))?;
+    if !metadata.is_dir() {
+        return Err(format!("'{}' is not a directory", root.display()).into());
+    }
+    let globs = matcher(root, options.globs, "--glob")?;
+    let excludes = matcher(root, options.excludes, "--exclude")?;
+    let mut walker = WalkBuilder::new(root);
+    walker
+        .standard_filters(false)
+        .git_ignore(options.gitignore)
+        .git_exclude(options.gitignore)
+        .git_global(options.gitignore)
+        .parents(options.gitignore)
+        .require_git(false)
+        .filter_entry(move |entry| entry.file_name() != ".git" && !excludes.matched(entry.path(), entry.file_type().is_some_and(|kind| kind.is_dir())).is_ignore());
+    // Directory totals and --glob need everything beneath the cut-off
+    if !options.sizes && options.globs.is_empty() {
+        walker.max_depth(options.depth);
+    }
+    let mut tree = Node::new(Kind::Dir, 0);
+    for entry in walker.build() {
+        let entry = entry?;
+        let Some(file_type) = entry.file_type() else { continue };
+        let Ok(relative) = entry.path().strip_prefix(root) else { continue };
+        let names: Vec<String> = relative.components().map(|component| component.as_os_str().to_string_lossy().into_owned()).collect();
+        if names.is_empty() {
+            continue;
+        }
+        let node = if file_type.is_dir() {
+            Node::new(Kind::Dir, 0)
+        } else {
+            if !options.globs.is_empty() && !globs.matched(entry.path(), false).is_ignore() {
+                continue;
+            }
+            if file_type.is_symlink() {
+                Node::new(Kind::Symlink(fs::read_link(entry.path())?.to_string_lossy().into_owned()), 0)
+            } else {
+                Node::new(Kind::File, entry.metadata().map(|metadata| metadata.len()).unwrap_or(0))
+            }
+        };
+        insert(&mut tree, &names, node);
+    }
+    if !options.globs.is_empty() {
+        prune(&mut tree);
+    }
+    total(&mut tree);
+    Ok(tree)
+}
+
+// The walk yields parents before their children, so every parent already exists
+fn insert(tree: &mut Node, names: &[String], node: Node) {
+    let mut current = tree;
+    for name in &names[..names.len() - 1] {
+        current = current.children.entry(name.clone()).or_insert_with(|| Node::new(Kind::Dir, 0));
+    }
+    current.children.insert(names[names.len() - 1].clone(), node);
+}
+
+// Drops directories left empty by --glob; returns whether anything matched beneath
+fn prune(node: &mut Node) -> bool {
+    node.children.retain(|_, child| child.kind != Kind::Dir || prune(child));
+    !node.children.is_empty()
+}
+
+fn total(node: &mut Node) -> u64 {
+    if node.kind == Kind::Dir {
+        node.size = node.children.values_mut().map(total).sum();
+    }
+    node.size
+}
+
+fn render(label: &str, root: &Node, depth: Option<usize>, sizes: bool, color: bool) -> String {
+    let paint = |text: &str, style: Style| if color { style(text).to_string() } else { text.to_string() };
+    let mut output = format!("{}{}\n", paint(label, |t| t.blue().bold()), size_note(root, sizes));
+    let mut totals = Totals::default();
+    render_children(root, "", 1, depth, sizes, &paint, &mut output, &mut totals);
+    output.push_str(&format!(
+        "\n{} director{}, {} file{}",
+        totals.dirs,
+        if totals.dirs == 1 { "y" } else { "ies" },
+        totals.files,
+        if totals.files == 1 { "" } else { "s" }
+    ));
+    if sizes {
+        output.push_str(&format!(", {}", format_bytes(root.size)));
+    }
+    output.push('\n');
+    output
+}
+
+#[allow(clippy::too_many_arguments)]
+fn render_children(
+    node: &Node,
+    prefix: &str,
+    level: usize,
+    depth: Option<usize>,
+    sizes: bool,
+    paint: &dyn Fn(&str, Style) -> String,
+    output: &mut String,
+    totals: &mut Totals,
+) {
+    let count = node.children.len();
+    for (index, (name, child)) in node.children.iter().enumerate() {
+        let last = index + 1 == count;
+        let name = match &child.kind {
+            Kind::Dir => {
+                totals.dirs += 1;
+                paint(name, |t| t.blue().bold())
+            }
+            Kind::File => {
+                totals.files += 1;
+                name.clone()
+            }
+            Kind::Symlink(target) => {
+                totals.files += 1;
+                format!("{} -> {}", paint(name, |t| t.cyan()), target)
+            }
+        };
+        output.push_str(&format!("{}{}{}{}\n", prefix, if last { "└── " } else { "├── " }, name, size_note(child, sizes)));
+        if child.kind == Kind::Dir && depth.is_none_or(|depth| level < depth) {
+            let prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+            render_children(child, &prefix, level + 1, depth, sizes, paint, output, totals);
+        }
+    }
+}
+
+fn size_note(node: &Node, sizes: bool) -> String {
+    if sizes && !matches!(node.kind, Kind::Symlink(_)) {
+        format!(" ({})", format_bytes(node.size))
+    } else {
+        String::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> std::path::PathBuf {
+        let root = std::env::temp_dir().join(format!("ms-dir-tree-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/bin")).unwrap();
+        fs::create_dir_all(root.join("target/debug")).unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        fs::write(root.join("README.md"), "# hi\n").unwrap();
+        fs::write(root.join("src/main.rs"), "fn main() {}\n").unwrap();
+        fs::write(root.join("src/bin/tool.rs"), "fn main() {}\n").unwrap();
+        fs::write(root.join("target/debug/app"), vec![0u8; 2048]).unwrap();
+        fs::write(root.join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+        root
+    }
+
+    fn options<'a>(depth: Option<usize>, gitignore: bool, globs: &'a [&'a String]) -> Options<'a> {
+        Options { depth, sizes: true, gitignore, globs, excludes: &[] }
+    }
+
+    #[test]
+    fn test_render_tree() {
+        let root = fixture("render");
+        let tree = build(&root, &options(None, true, &[])).unwrap();
+        assert_eq!(
+            render("demo", &tree, None, false, false),
+            "demo\n├── .gitignore\n├── README.md\n└── src\n    ├── bin\n    │   └── tool.rs\n    └── main.rs\n\n2 directories, 4 files\n"
+        );
+        assert_eq!(render("demo", &tree, Some(1), true, false), "demo (39 B)\n├── .gitignore (8 B)\n├── README.md (5 B)\n└── src (26 B)\n\n1 directory, 2 files, 39 B\n");
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_filters() {
+        let root = fixture("filters");
+        let everything = build(&root, &options(None, false, &[])).unwrap();
+        assert!(everything.children.contains_key("target"));
+        assert!(!everything.children.contains_key(".git"));
+        assert_eq!(everything.size, 2048 + 39);
+
+        let pattern = "*.rs".to_string();
+        let globs = [&pattern];
+        let rust = build(&root, &options(Some(1), false, &globs)).unwrap();
+        assert_eq!(rust.children.keys().collect::<Vec<_>>(), ["src"]);
+        assert_eq!(render("demo", &rust, Some(1), false, false), "demo\n└── src\n\n1 directory, 0 files\n");
+
+        let exclude = "bin".to_string();
+        let excludes = [&exclude];
+        let trimmed = build(&root, &Options { depth: None, sizes: false, gitignore: true, globs: &[], excludes: &excludes }).unwrap();
+        assert_eq!(trimmed.children["src"].children.keys().collect::<Vec<_>>(), ["main.rs"]);
+        assert!(build(&root.join("README.md"), &options(None, false, &[])).is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
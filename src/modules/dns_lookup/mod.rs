@@ -0,0 +1,218 @@
+use crate::tool_module::{block_on, ToolModule};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use colored::*;
+use hickory_resolver::config::{NameServerConfig, ResolverConfig};
+use hickory_resolver::net::runtime::TokioRuntimeProvider;
+use hickory_resolver::proto::rr::{Record, RecordType};
+use hickory_resolver::{Resolver, TokioResolver};
+use serde_json::{json, Value};
+use std::error::Error;
+use std::net::{IpAddr, SocketAddr};
+use std::time::Instant;
+
+pub struct DnsLookupModule;
+
+const RECORD_TYPES: [&str; 7] = ["A", "AAAA", "CNAME", "MX", "NS", "TXT", "SOA"];
+
+// Well-known public resolvers accepted by name after @
+const PUBLIC_RESOLVERS: [(&str, &str); 3] = [("cloudflare", "1.1.1.1"), ("google", "8.8.8.8"), ("quad9", "9.9.9.9")];
+
+impl ToolModule for DnsLookupModule {
+    fn name(&self) -> &'static str {
+        "dns-lookup"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("dns")
+                .long("dns")
+                .value_names(["DOMAIN", "TYPE"])
+                .num_args(1..=3)
+                .help("Look up DNS records: A, AAAA, CNAME, MX, NS, TXT, SOA or ALL, optionally @SERVER")
+                .long_help("Query DNS records for DOMAIN and print each answer with its TTL. TYPE is A (default), AAAA, CNAME, MX, NS, TXT, SOA or ALL. Add @SERVER to ask a specific resolver instead of the system one, e.g. @1.1.1.1, @8.8.8.8:53 or @cloudflare, @google, @quad9. Use --dns-json for JSON output.")
+        )
+        .arg(
+            Arg::new("dns-json")
+                .long("dns-json")
+                .help("Print --dns results as JSON")
+                .action(ArgAction::SetTrue)
+                .requires("dns")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(values) = matches.get_many::<String>("dns") {
+            let values: Vec<&str> = values.map(String::as_str).collect();
+            let query = parse_query(&values)?;
+            let resolver = build_resolver(query.server)?;
+            let started = Instant::now();
+            let mut answers = Vec::new();
+            for record_type in &query.record_types {
+                let mut records = lookup(&resolver, &query.domain, *record_type)?;
+                // A single type keeps the CNAME chain that led to it; ALL lists each record once
+                if query.record_types.len() > 1 {
+                    records.retain(|record| record.record_type() == *record_type);
+                }
+                answers.push((*record_type, records));
+            }
+            let elapsed = started.elapsed();
+            let server = query.server.map(|server| server.to_string()).unwrap_or_else(|| "system resolver".to_string());
+
+            if matches.get_flag("dns-json") {
+                let records: Vec<Value> = answers.iter().flat_map(|(_, records)| records.iter().map(record_json)).collect();
+                let output = json!({
+                    "domain": query.domain,
+                    "server": server,
+                    "time_ms": elapsed.as_millis() as u64,
+                    "records": records,
+                });
+                println!("{}", serde_json::to_string_pretty(&output)?);
+                return Ok(());
+            }
+
+            println!("{} via {} ({} ms)", query.domain.bold(), server, elapsed.as_millis());
+            for (record_type, records) in &answers {
+                if records.is_empty() {
+                    if query.record_types.len() == 1 {
+                        println!("⚠️  No {} records found", record_type);
+                    }
+                    continue;
+                }
+                for record in records {
+                    println!(
+                        "  {:<6} {:<10} {}",
+                        record.record_type().to_string().cyan(),
+                        format!("TTL {}", record.ttl).dimmed(),
+                        record.data
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Query {
+    domain: String,
+    record_types: Vec<RecordType>,
+    server: Option<SocketAddr>,
+}
+
+// DOMAIN, then TYPE and @SERVER in either order
+fn parse_query(values: &[&str]) -> Result<Query, String> {
+    let domain = values[0].trim_end_matches('.').to_string();
+    if domain.is_empty() || domain.starts_with('@') {
+        return Err("Give the domain to look up first, e.g. --dns example.com MX".to_string());
+    }
+    let mut query = Query { domain, record_types: vec![RecordType::A], server: None };
+    for value in &values[1..] {
+        if let Some(server) = value.strip_prefix('@') {
+            query.server = Some(parse_server(server)?);
+        } else {
+            query.record_types = parse_record_types(value)?;
+        }
+    }
+    Ok(query)
+}
+
+fn parse_record_types(value: &str) -> Result<Vec<RecordType>, String> {
+    let upper = value.to_uppercase();
+    let names: Vec<&str> = if upper == "ALL" {
+        RECORD_TYPES.to_vec()
+    } else if RECORD_TYPES.contains(&upper.as_str()) {
+        vec![upper.as_str()]
+    } else {
+        return Err(format!("Unsupported record type '{}'. Use one of: {}, ALL", value, RECORD_TYPES.join(", ")));
+    };
+    names.into_iter().map(|name| name.parse::<RecordType>().map_err(|e| e.to_string())).collect()
+}
+
+// "1.1.1.1", "1.1.1.1:5353", "[2606:4700::1111]:53", "2606:4700::1111" or "cloudflare"
+fn parse_server(server: &str) -> Result<SocketAddr, String> {
+    let server = PUBLIC_RESOLVERS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(server))
+        .map(|(_, ip)| *ip)
+        .unwrap_or(server);
+    if let Ok(address) = server.parse::<SocketAddr>() {
+        return Ok(address);
+    }
+    server
+        .parse::<IpAddr>()
+        .map(|ip| SocketAddr::new(ip, 53))
+        .map_err(|_| format!("Invalid DNS server '{}'. Use an IP address such as @1.1.1.1 or @8.8.8.8:53", server))
+}
+
+pub fn build_resolver(server: Option<SocketAddr>) -> Result<TokioResolver, Box<dyn Error>> {
+    let builder = match server {
+        Some(address) => {
+            let mut name_server = NameServerConfig::udp_and_tcp(address.ip());
+            for connection in &mut name_server.connections {
+                connection.port = address.port();
+            }
+            Resolver::builder_with_config(ResolverConfig::from_name_servers(vec![name_server]), TokioRuntimeProvider::default())
+        }
+        None => Resolver::builder_tokio().map_err(|e| format!("Failed to read the system DNS configuration: {}", e))?,
+    };
+    Ok(builder.build()?)
+}
+
+// An empty answer is not an error: ALL asks for types most domains do not have
+fn lookup(resolver: &TokioResolver, domain: &str, record_type: RecordType) -> Result<Vec<Record>, Box<dyn Error>> {
+    // The trailing dot skips the search domains from resolv.conf
+    match block_on(resolver.lookup(format!("{}.", domain), record_type)) {
+        Ok(lookup) => Ok(lookup.answers().to_vec()),
+        Err(e) if e.is_nx_domain() => Err(format!("{} does not exist (NXDOMAIN)", domain).into()),
+        Err(e) if e.is_no_records_found() => Ok(Vec::new()),
+        Err(e) => Err(format!("{} lookup for {} failed: {}", record_type, domain, e).into()),
+    }
+}
+
+fn record_json(record: &Record) -> Value {
+    json!({
+        "name": record.name.to_string(),
+        "type": record.record_type().to_string(),
+        "ttl": record.ttl,
+        "value": record.data.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hickory_resolver::proto::rr::rdata::{A, MX};
+    use hickory_resolver::proto::rr::{Name, RData};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_parse_query() {
+        let query = parse_query(&["example.com."]).unwrap();
+        assert_eq!(query.domain, "example.com");
+        assert_eq!(query.record_types, vec![RecordType::A]);
+        assert_eq!(query.server, None);
+
+        let query = parse_query(&["example.com", "@1.1.1.1", "mx"]).unwrap();
+        assert_eq!(query.record_types, vec![RecordType::MX]);
+        assert_eq!(query.server, Some("1.1.1.1:53".parse().unwrap()));
+        assert_eq!(parse_query(&["example.com", "all"]).unwrap().record_types.len(), RECORD_TYPES.len());
+        assert!(parse_query(&["example.com", "SRVX"]).is_err());
+        assert!(parse_query(&["@1.1.1.1", "example.com"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_server() {
+        assert_eq!(parse_server("8.8.8.8:5353").unwrap(), "8.8.8.8:5353".parse().unwrap());
+        assert_eq!(parse_server("2606:4700::1111").unwrap(), "[2606:4700::1111]:53".parse().unwrap());
+        assert_eq!(parse_server("Cloudflare").unwrap(), "1.1.1.1:53".parse().unwrap());
+        assert!(parse_server("dns.example").is_err());
+    }
+
+    #[test]
+    fn test_record_json() {
+        let name = Name::from_str("example.com.").unwrap();
+        let a = Record::from_rdata(name.clone(), 300, RData::A(A::new(93, 184, 216, 34)));
+        assert_eq!(record_json(&a), json!({ "name": "example.com.", "type": "A", "ttl": 300, "value": "93.184.216.34" }));
+        let mx = Record::from_rdata(name, 3600, RData::MX(MX::new(10, Name::from_str("mail.example.com.").unwrap())));
+        assert_eq!(record_json(&mx)["value"], "10 mail.example.com.");
+    }
+}
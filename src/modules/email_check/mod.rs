@@ -0,0 +1,338 @@
+use crate::dns_lookup::build_resolver;
+use crate::punycode::domain_to_ascii;
+use crate::tool_module::{block_on, ExitCodeError, ToolModule};
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use hickory_resolver::proto::rr::{RData, RecordType};
+use hickory_resolver::TokioResolver;
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::{self, BufRead, IsTerminal};
+
+pub struct EmailCheckModule;
+
+// RFC 5321 limits
+const MAX_LOCAL_LENGTH: usize = 64;
+const MAX_ADDRESS_LENGTH: usize = 254;
+
+// Throwaway inbox providers
+const DISPOSABLE_DOMAINS: [&str; 40] = [
+    "10minutemail.com", "20minutemail.com", "33mail.com", "anonaddy.me", "burnermail.io", "discard.email", "dispostable.com",
+    "emailondeck.com", "fakeinbox.com", "getairmail.com", "getnada.com", "guerrillamail.biz", "guerrillamail.com",
+    "guerrillamail.de", "guerrillamail.net", "guerrillamail.org", "harakirimail.com", "inboxkitten.com", "mailcatch.com",
+    "maildrop.cc", "mailinator.com", "mailnesia.com", "mailsac.com", "mintemail.com", "moakt.com", "mohmal.com",
+    "mytemp.email", "nada.email", "sharklasers.com", "spambox.us", "spamgourmet.com", "temp-mail.io", "temp-mail.org",
+    "tempail.com", "tempmail.dev", "tempmailo.com", "throwawaymail.com", "trashmail.com", "yopmail.com", "tempr.email",
+];
+
+// Shared mailboxes that rarely belong to a person
+const ROLE_ACCOUNTS: [&str; 14] = [
+    "abuse", "admin", "billing", "contact", "help", "hostmaster", "info", "marketing", "noreply", "no-reply", "postmaster",
+    "sales", "support", "webmaster",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Level {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum DomainStatus {
+    // Mail servers by preference
+    Mx(Vec<String>),
+    // No MX, so mail goes to the A/AAAA address (RFC 5321 implicit MX)
+    AddressOnly,
+    // "MX 0 ." declares that the domain accepts no mail (RFC 7505)
+    NullMx,
+    NoMailHost,
+    NotFound,
+    LookupFailed(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Report {
+    address: String,
+    // (check, level, detail), in the order they ran
+    checks: Vec<(&'static str, Level, String)>,
+}
+
+impl Report {
+    fn level(&self) -> Level {
+        self.checks.iter().map(|(_, level, _)| *level).max().unwrap_or(Level::Pass)
+    }
+
+    // The first failure or warning, for one-line batch output
+    fn reason(&self) -> Option<&str> {
+        let level = self.level();
+        self.checks.iter().find(|(_, check_level, _)| *check_level == level && level != Level::Pass).map(|(_, _, detail)| detail.as_str())
+    }
+}
+
+impl ToolModule for EmailCheckModule {
+    fn name(&self) -> &'static str {
+        "email-check"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("email-check")
+                .long("email-check")
+                .value_name("EMAIL")
+                .num_args(0..=1)
+                .default_missing_value("")
+                .help("Validate an email address: syntax, domain, MX records and disposable providers; stdin for a list")
+                .long_help("Check an email address: RFC 5321/5322 syntax (including quoted local parts and internationalized domains), that the domain exists and has MX records (or an address to fall back to), whether it is a known disposable inbox provider, and whether it is a role account such as info@ or support@. No mail is sent.\n\nWithout EMAIL, addresses are read from stdin one per line for cleaning mailing lists: usable addresses go to stdout and rejected ones, with the reason, to stderr, e.g.\n  ms --email-check < list.txt > clean.txt")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(address) = matches.get_one::<String>("email-check") {
+            let resolver = build_resolver(None)?;
+            let mut cache = HashMap::new();
+            if !address.is_empty() {
+                let report = check(address, &mut |domain| lookup_domain(&resolver, &mut cache, domain));
+                print!("{}", render(&report, io::stdout().is_terminal()));
+                if report.level() == Level::Fail {
+                    return Err(Box::new(ExitCodeError::silent(1)));
+                }
+                return Ok(());
+            }
+            let (mut kept, mut rejected) = (0, 0);
+            for line in io::stdin().lock().lines() {
+                let line = line?;
+                let address = line.trim();
+                if address.is_empty() || address.starts_with('#') {
+                    continue;
+                }
+                let report = check(address, &mut |domain| lookup_domain(&resolver, &mut cache, domain));
+                match report.level() {
+                    Level::Fail => {
+                        rejected += 1;
+                        eprintln!("❌ {}  {}", address, report.reason().unwrap_or_default());
+                    }
+                    _ => {
+                        kept += 1;
+                        println!("{}", address);
+                    }
+                }
+            }
+            eprintln!("✅ {} usable, {} rejected", kept, rejected);
+        }
+        Ok(())
+    }
+}
+
+fn check(address: &str, lookup: &mut dyn FnMut(&str) -> DomainStatus) -> Report {
+    let mut report = Report { address: address.to_string(), checks: Vec::new() };
+    let (local, domain) = match parse_address(address) {
+        Ok(parts) => parts,
+        Err(e) => {
+            report.checks.push(("Syntax", Level::Fail, e));
+            return report;
+        }
+    };
+    report.checks.push(("Syntax", Level::Pass, "valid".to_string()));
+
+    let domain_lower = domain.to_lowercase();
+    if DISPOSABLE_DOMAINS.iter().any(|disposable| domain_lower == *disposable || domain_lower.ends_with(&format!(".{}", disposable))) {
+        report.checks.push(("Disposable", Level::Fail, format!("{} is a disposable inbox provider", domain)));
+    }
+
+    if domain.starts_with('[') {
+        report.checks.push(("Domain", Level::Warn, "address literal; mail servers often refuse these".to_string()));
+    } else {
+        let (level, detail) = match lookup(&domain_lower) {
+            DomainStatus::Mx(hosts) => (Level::Pass, format!("MX {}", hosts.join(", "))),
+            DomainStatus::AddressOnly => (Level::Warn, "no MX records; mail falls back to the domain's A/AAAA address".to_string()),
+            DomainStatus::NullMx => (Level::Fail, format!("{} does not accept mail (null MX)", domain)),
+            DomainStatus::NoMailHost => (Level::Fail, format!("{} has no MX or address records", domain)),
+            DomainStatus::NotFound => (Level::Fail, format!("{} does not exist", domain)),
+            DomainStatus::LookupFailed(e) => (Level::Warn, format!("could not look up {}: {}", domain, e)),
+        };
+        report.checks.push(("Domain", level, detail));
+    }
+
+    let mailbox = local.trim_matches('"').to_lowercase();
+    let mailbox = mailbox.split('+').next().unwrap_or("");
+    if ROLE_ACCOUNTS.contains(&mailbox) {
+        report.checks.push(("Role account", Level::Warn, format!("{}@ is usually a shared mailbox", mailbox)));
+    }
+    report
+}
+
+// Splits at the last @ and validates both halves; the domain is returned in
+// ASCII (punycode) form
+fn parse_address(address: &str) -> Result<(String, String), String> {
+    if address.chars().count() > MAX_ADDRESS_LENGTH {
+        return Err(format!("longer than {} characters", MAX_ADDRESS_LENGTH));
+    }
+    let (local, domain) = address.rsplit_once('@').ok_or("missing @")?;
+    validate_local(local)?;
+    let domain = validate_domain(domain)?;
+    Ok((local.to_string(), domain))
+}
+
+fn validate_local(local: &str) -> Result<(), String> {
+    if local.is_empty() {
+        return Err("nothing before the @".to_string());
+    }
+    if local.len() > MAX_LOCAL_LENGTH {
+        return Err(format!("the part before the @ is longer than {} bytes", MAX_LOCAL_LENGTH));
+    }
+    // "john doe"@example.com: anything printable, with \ escapes
+    if let Some(quoted) = local.strip_prefix('"').and_then(|rest| rest.strip_suffix('"')) {
+        let mut chars = quoted.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' if chars.next().is_none() => return Err("dangling \\ in the quoted part".to_string()),
+                '\\' => {}
+                '"' => return Err("unescaped \" in the quoted part".to_string()),
+                c if c.is_control() => return Err("control character in the quoted part".to_string()),
+                _ => {}
+            }
+        }
+        return Ok(());
+    }
+    if local.starts_with('.') || local.ends_with('.') || local.contains("..") {
+        return Err("dots cannot start, end or repeat in the part before the @".to_string());
+    }
+    // Dot-atom text, plus UTF-8 for internationalized addresses (RFC 6531)
+    match local.chars().find(|c| !(c.is_ascii_alphanumeric() || "!#$%&'*+/=?^_`{|}~-.".contains(*c) || (!c.is_ascii() && !c.is_control() && !c.is_whitespace()))) {
+        Some(c) => Err(format!("'{}' is not allowed before the @ unless quoted", c)),
+        None => Ok(()),
+    }
+}
+
+fn validate_domain(domain: &str) -> Result<String, String> {
+    if domain.is_empty() {
+        return Err("nothing after the @".to_string());
+    }
+    if let Some(literal) = domain.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+        let ip = literal.strip_prefix("IPv6:").unwrap_or(literal);
+        return ip.parse::<std::net::IpAddr>().map(|_| domain.to_string()).map_err(|_| format!("invalid address literal {}", domain));
+    }
+    let ascii = domain_to_ascii(domain).map_err(|e| format!("invalid domain {}: {}", domain, e))?;
+    let labels: Vec<&str> = ascii.split('.').collect();
+    if labels.len() < 2 {
+        return Err(format!("{} has no top-level domain", domain));
+    }
+    for label in &labels {
+        if label.is_empty() || label.len() > 63 {
+            return Err(format!("invalid domain {}", domain));
+        }
+        if label.starts_with('-') || label.ends_with('-') || !label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(format!("invalid domain label '{}'", label));
+        }
+    }
+    if labels.last().is_some_and(|tld| tld.chars().all(|c| c.is_ascii_digit())) {
+        return Err(format!("{} has a numeric top-level domain", domain));
+    }
+    Ok(ascii)
+}
+
+// Cached per domain, since mailing lists repeat the same few domains
+fn lookup_domain(resolver: &TokioResolver, cache: &mut HashMap<String, DomainStatus>, domain: &str) -> DomainStatus {
+    cache.entry(domain.to_string()).or_insert_with(|| resolve_domain(resolver, domain)).clone()
+}
+
+fn resolve_domain(resolver: &TokioResolver, domain: &str) -> DomainStatus {
+    // The trailing dot skips the search domains from resolv.conf
+    let name = format!("{}.", domain);
+    match block_on(resolver.lookup(name.clone(), RecordType::MX)) {
+        Ok(lookup) => {
+            let mut hosts: Vec<(u16, String)> = lookup
+                .answers()
+                .iter()
+                .filter_map(|record| match &record.data {
+                    RData::MX(mx) => Some((mx.preference, mx.exchange.to_string().trim_end_matches('.').to_string())),
+                    _ => None,
+                })
+                .collect();
+            hosts.sort();
+            if hosts.len() == 1 && hosts[0].1.is_empty() {
+                return DomainStatus::NullMx;
+            }
+            if !hosts.is_empty() {
+                return DomainStatus::Mx(hosts.into_iter().map(|(_, host)| host).collect());
+            }
+        }
+        Err(e) if e.is_nx_domain() => return DomainStatus::NotFound,
+        Err(e) if e.is_no_records_found() => {}
+        Err(e) => return DomainStatus::LookupFailed(e.to_string()),
+    }
+    match block_on(resolver.lookup_ip(name)) {
+        Ok(lookup) if lookup.iter().next().is_some() => DomainStatus::AddressOnly,
+        Ok(_) => DomainStatus::NoMailHost,
+        Err(e) if e.is_nx_domain() => DomainStatus::NotFound,
+        Err(e) if e.is_no_records_found() => DomainStatus::NoMailHost,
+        Err(e) => DomainStatus::LookupFailed(e.to_string()),
+    }
+}
+
+fn render(report: &Report, color: bool) -> String {
+    let mut output = format!("{}\n", if color { report.address.bold().to_string() } else { report.address.clone() });
+    for (check, level, detail) in &report.checks {
+        let icon = match level {
+            Level::Pass => "✅",
+            Level::Warn => "⚠️",
+            Level::Fail => "❌",
+        };
+        output.push_str(&format!("{} {:<13} {}\n", icon, check, detail));
+    }
+    let verdict = match report.level() {
+        Level::Pass => "deliverable as far as DNS can tell".green(),
+        Level::Warn => "usable, with warnings".yellow(),
+        Level::Fail => "not usable".red(),
+    };
+    let verdict = if color { verdict.bold().to_string() } else { verdict.clear().to_string() };
+    output.push_str(&format!("\nVerdict: {}\n", verdict));
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_address() {
+        assert_eq!(parse_address("john.doe+news@Example.com").unwrap(), ("john.doe+news".to_string(), "example.com".to_string()));
+        assert_eq!(parse_address("\"john doe\"@example.com").unwrap().0, "\"john doe\"");
+        assert_eq!(parse_address("user@bücher.de").unwrap().1, "xn--bcher-kva.de");
+        assert_eq!(parse_address("admin@[192.168.0.1]").unwrap().1, "[192.168.0.1]");
+        assert_eq!(parse_address("no-at-sign").unwrap_err(), "missing @");
+        assert!(parse_address("john..doe@example.com").is_err());
+        assert!(parse_address("john doe@example.com").is_err());
+        assert!(parse_address("john@localhost").is_err());
+        assert!(parse_address("john@-bad.com").is_err());
+        assert!(parse_address("john@example.123").is_err());
+        assert!(parse_address(&format!("{}@example.com", "a".repeat(65))).is_err());
+    }
+
+    #[test]
+    fn test_check_levels() {
+        let mut mx = |_: &str| DomainStatus::Mx(vec!["mx1.example.com".to_string()]);
+        let report = check("jane@example.com", &mut mx);
+        assert_eq!(report.level(), Level::Pass);
+        assert_eq!(report.reason(), None);
+
+        let report = check("Support+eu@example.com", &mut mx);
+        assert_eq!(report.level(), Level::Warn);
+        assert_eq!(report.reason(), Some("support@ is usually a shared mailbox"));
+
+        let report = check("someone@mailinator.com", &mut mx);
+        assert_eq!(report.reason(), Some("mailinator.com is a disposable inbox provider"));
+
+        let report = check("jane@nope.example", &mut |_: &str| DomainStatus::NotFound);
+        assert_eq!(report.reason(), Some("nope.example does not exist"));
+        assert_eq!(check("jane@example.com", &mut |_: &str| DomainStatus::NullMx).level(), Level::Fail);
+        assert_eq!(check("jane@example.com", &mut |_: &str| DomainStatus::AddressOnly).level(), Level::Warn);
+    }
+
+    #[test]
+    fn test_render() {
+        let report = check("bad address@example.com", &mut |_: &str| unreachable!());
+        assert_eq!(render(&report, false), "bad address@example.com\n❌ Syntax        ' ' is not allowed before the @ unless quoted\n\nVerdict: not usable\n");
+    }
+}
@@ -0,0 +1,179 @@
+use crate::secret_scan::{entropy, ENTROPY_THRESHOLD};
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use std::error::Error;
+use std::fs;
+
+pub struct EntropyModule;
+
+// Below this even random strings are guessable, so no verdict is given
+const MIN_JUDGED_LENGTH: usize = 8;
+// Random keys and tokens are at least this long
+const MIN_SECRET_LENGTH: usize = 16;
+// Share of the highest entropy possible for the charset and length above which a string looks random
+const RANDOM_RATIO: f64 = 0.85;
+
+#[derive(Debug, PartialEq)]
+struct Charset {
+    classes: Vec<&'static str>,
+    // Number of symbols an attacker would have to try per character
+    size: usize,
+}
+
+#[derive(Debug, PartialEq)]
+enum Verdict {
+    TooShort,
+    Secret,
+    RandomButShort,
+    Human,
+}
+
+impl ToolModule for EntropyModule {
+    fn name(&self) -> &'static str {
+        "entropy"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("entropy")
+                .long("entropy")
+                .value_name("STRING|@FILE")
+                .help("Measure the Shannon entropy of a string (or @file) and judge whether it looks like a secret")
+                .long_help("Measure the Shannon entropy of STRING, or of a file's contents with @FILE: bits per character and in total, the character classes used and how many bits a random string of that charset and length would have. The verdict says whether it looks like a random secret or key, using the same entropy threshold as --scan-secrets. The string itself is never printed.")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(value) = matches.get_one::<String>("entropy") {
+            let text = match value.strip_prefix('@') {
+                Some(path) => {
+                    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read '{}': {}", path, e))?;
+                    content.strip_suffix('\n').map(|text| text.strip_suffix('\r').unwrap_or(text).to_string()).unwrap_or(content)
+                }
+                None => value.clone(),
+            };
+            if text.is_empty() {
+                return Err("Nothing to measure".into());
+            }
+            for (label, value) in summary(&text) {
+                println!("{:<14}{}", format!("{}:", label).cyan(), value);
+            }
+            match verdict(&text) {
+                Verdict::TooShort => println!("⚠️  Too short to judge; anything under {} characters is easy to brute-force", MIN_JUDGED_LENGTH),
+                Verdict::Secret => println!("❌ Looks like a secret: random-looking and long enough to be a key or token"),
+                Verdict::RandomButShort => println!("⚠️  Random-looking, but short for a key or token"),
+                Verdict::Human => println!("✅ Looks human-written rather than random"),
+            }
+            let mixed = text.chars().any(|c| c.is_ascii_digit()) && text.chars().any(|c| c.is_ascii_alphabetic());
+            if mixed && entropy(&text) >= ENTROPY_THRESHOLD {
+                println!("   --scan-secrets would flag it when assigned to a name like token or password");
+            }
+        }
+        Ok(())
+    }
+}
+
+// The labelled lines printed above the verdict
+fn summary(text: &str) -> Vec<(&'static str, String)> {
+    let length = text.chars().count();
+    let unique = text.chars().collect::<std::collections::HashSet<char>>().len();
+    let bits_per_char = entropy(text);
+    let charset = charset(text);
+    vec![
+        ("Length", format!("{} characters, {} unique", length, unique)),
+        ("Entropy", format!("{:.2} bits/char, {:.1} bits total", bits_per_char, bits_per_char * length as f64)),
+        ("Charset", format!("{} ({} symbols, {:.1} bits if random)", charset.classes.join(", "), charset.size, length as f64 * (charset.size as f64).log2())),
+        ("Randomness", format!("{:.0}% of the maximum for this charset and length", randomness(text) * 100.0)),
+    ]
+}
+
+fn charset(text: &str) -> Charset {
+    let has = |test: fn(&char) -> bool| text.chars().any(|c| test(&c));
+    // Hex keys and hashes only draw from 16 symbols, lowercase or uppercase
+    if text.chars().all(|c| c.is_ascii_hexdigit()) && !(has(char::is_ascii_lowercase) && has(char::is_ascii_uppercase)) {
+        return Charset { classes: vec!["hex"], size: 16 };
+    }
+    let mut classes = Vec::new();
+    let mut size = 0;
+    for (name, test, class_size) in [
+        ("lowercase", char::is_ascii_lowercase as fn(&char) -> bool, 26),
+        ("uppercase", char::is_ascii_uppercase, 26),
+        ("digits", char::is_ascii_digit, 10),
+        // Printable ASCII punctuation and space
+        ("symbols", |c: &char| c.is_ascii_punctuation() || *c == ' ', 33),
+    ] {
+        if has(test) {
+            classes.push(name);
+            size += class_size;
+        }
+    }
+    let other: std::collections::HashSet<char> = text.chars().filter(|c| !c.is_ascii_graphic() && *c != ' ').collect();
+    if !other.is_empty() {
+        classes.push("other");
+        size += other.len();
+    }
+    Charset { classes, size }
+}
+
+// Entropy as a share of the most a string of this length and charset can have
+fn randomness(text: &str) -> f64 {
+    let length = text.chars().count();
+    let most = (charset(text).size.min(length) as f64).log2();
+    if most == 0.0 {
+        return 0.0;
+    }
+    (entropy(text) / most).min(1.0)
+}
+
+fn verdict(text: &str) -> Verdict {
+    let length = text.chars().count();
+    if length < MIN_JUDGED_LENGTH {
+        Verdict::TooShort
+    } else if randomness(text) < RANDOM_RATIO {
+        Verdict::Human
+    } else if length >= MIN_SECRET_LENGTH {
+        Verdict::Secret
+    } else {
+        Verdict::RandomButShort
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_charset() {
+        assert_eq!(charset("3f786850e387550f"), Charset { classes: vec!["hex"], size: 16 });
+        assert_eq!(charset("DEADBEEF"), Charset { classes: vec!["hex"], size: 16 });
+        assert_eq!(charset("q8Xz2LmP").size, 62);
+        assert_eq!(charset("Tr0ub4dor&3").classes, ["lowercase", "uppercase", "digits", "symbols"]);
+        assert_eq!(charset("héllo").classes, ["lowercase", "other"]);
+    }
+
+    #[test]
+    fn test_verdict() {
+        assert_eq!(verdict("q8Xz2LmP9vR4tY7wK1nB5cJ3"), Verdict::Secret);
+        assert_eq!(verdict("3f786850e387550fdab836ed7e6dc881de23001b"), Verdict::Secret);
+        assert_eq!(verdict("wJalrXUtnFEMI/K7MDENG/bPxRfiCYEXAMPLEKEY"), Verdict::Secret);
+        assert_eq!(verdict("correct horse battery staple"), Verdict::Human);
+        assert_eq!(verdict("changeme_changeme_changeme"), Verdict::Human);
+        assert_eq!(verdict("x9K2mQ7v"), Verdict::RandomButShort);
+        assert_eq!(verdict("abc"), Verdict::TooShort);
+    }
+
+    #[test]
+    fn test_randomness() {
+        assert_eq!(randomness("aaaaaaaaaa"), 0.0);
+        assert!((randomness("abcdefgh") - 1.0).abs() < 1e-9);
+        assert!(randomness("hello world") < RANDOM_RATIO);
+    }
+
+    #[test]
+    fn test_summary_of_repeated_character() {
+        let summary = summary("aaaa");
+        assert_eq!(summary[1], ("Entropy", "0.00 bits/char, 0.0 bits total".to_string()));
+        assert_eq!(summary[3], ("Randomness", "0% of the maximum for this charset and length".to_string()));
+    }
+}
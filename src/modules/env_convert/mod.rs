@@ -0,0 +1,183 @@
+use crate::run_file::parse_env_file;
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgMatches, Command};
+use serde_json::Value;
+use serde_yaml::Mapping;
+use std::error::Error;
+use std::fs;
+
+pub struct EnvConvertModule;
+
+const FORMATS: [&str; 6] = ["dotenv", "shell", "docker", "compose", "json", "github-actions"];
+
+impl ToolModule for EnvConvertModule {
+    fn name(&self) -> &'static str {
+        "env-convert"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("env-convert")
+                .long("env-convert")
+                .value_name("FILE")
+                .help("Convert a .env (or flat JSON) file to another format, chosen with --to")
+                .long_help("Convert environment variables from a .env file (KEY=VALUE, optionally with export) or a flat JSON object into the format given by --to, quoting values as the target requires.")
+                .requires("to")
+        )
+        .arg(
+            Arg::new("to")
+                .long("to")
+                .value_name("FORMAT")
+                .help("Target format for --env-convert (dotenv, shell, docker, compose, json or github-actions) --line-endings (lf or crlf) or --indent-convert (spaces:N or tabs)")
+                .long_help("Target format for --env-convert:\n- dotenv: KEY=VALUE lines\n- shell: export KEY='VALUE' lines, safe to source\n- docker: -e KEY=VALUE flags for docker run\n- compose: an environment: mapping for docker-compose.yml\n- json: a JSON object\n- github-actions: lines for $GITHUB_ENV, with multiline values as heredocs\n\nLine endings for --line-endings: lf or crlf.\n\nIndentation for --indent-convert: spaces:N (e.g. spaces:2) or tabs.")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(file) = matches.get_one::<String>("env-convert") {
+            let content = fs::read_to_string(file).map_err(|e| format!("Failed to read '{}': {}", file, e))?;
+            let vars = parse_vars(&content).map_err(|e| format!("{}: {}", file, e))?;
+            let format = matches.get_one::<String>("to").map(String::as_str).unwrap_or("dotenv");
+            println!("{}", convert(&vars, format)?);
+        }
+        Ok(())
+    }
+}
+
+// A JSON object when the file holds one, .env lines otherwise
+fn parse_vars(content: &str) -> Result<Vec<(String, String)>, String> {
+    if !content.trim_start().starts_with('{') {
+        return parse_env_file(content);
+    }
+    let value: Value = serde_json::from_str(content).map_err(|e| format!("invalid JSON: {}", e))?;
+    let object = value.as_object().ok_or("expected a JSON object")?;
+    object
+        .iter()
+        .map(|(key, value)| {
+            let value = match value {
+                Value::String(text) => text.clone(),
+                Value::Number(_) | Value::Bool(_) => value.to_string(),
+                Value::Null => String::new(),
+                _ => return Err(format!("{} is not a string, number or boolean", key)),
+            };
+            Ok((key.clone(), value))
+        })
+        .collect()
+}
+
+fn convert(vars: &[(String, String)], format: &str) -> Result<String, Box<dyn Error>> {
+    let lines: Vec<String> = match format {
+        "dotenv" => vars.iter().map(|(key, value)| format!("{}={}", key, dotenv_quote(value))).collect(),
+        "shell" => vars.iter().map(|(key, value)| format!("export {}={}", key, shell_quote(value))).collect(),
+        "docker" => vec![vars.iter().map(|(key, value)| format!("-e {}", shell_quote(&format!("{}={}", key, value)))).collect::<Vec<_>>().join(" ")],
+        "compose" => {
+            let mut environment = Mapping::new();
+            for (key, value) in vars {
+                environment.insert(key.clone().into(), value.clone().into());
+            }
+            let mut root = Mapping::new();
+            root.insert("environment".into(), environment.into());
+            vec![serde_yaml::to_string(&root)?.trim_end().to_string()]
+        }
+        // Built by hand so keys keep the file's order
+        "json" => {
+            let mut entries = Vec::new();
+            for (key, value) in vars {
+                entries.push(format!("  {}: {}", serde_json::to_string(key)?, serde_json::to_string(value)?));
+            }
+            if entries.is_empty() { vec!["{}".to_string()] } else { vec![format!("{{\n{}\n}}", entries.join(",\n"))] }
+        }
+        "github-actions" => vars.iter().map(|(key, value)| github_env_line(key, value)).collect(),
+        other => return Err(format!("Unknown format '{}'. Use one of: {}", other, FORMATS.join(", ")).into()),
+    };
+    Ok(lines.join("\n"))
+}
+
+// Bare when safe, otherwise double-quoted with the escapes .env parsers understand
+fn dotenv_quote(value: &str) -> String {
+    let safe = value.chars().all(|c| c.is_ascii_alphanumeric() || "_-.,/:@+%".contains(c));
+    if safe {
+        return value.to_string();
+    }
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+// Single quotes keep $, backticks and backslashes literal; an embedded ' becomes '\''
+fn shell_quote(value: &str) -> String {
+    let safe = !value.is_empty() && value.chars().all(|c| c.is_ascii_alphanumeric() || "_-.,/:@+%=".contains(c));
+    if safe {
+        return value.to_string();
+    }
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+// $GITHUB_ENV takes KEY=value, or KEY<<DELIMITER ... DELIMITER for multiline values
+fn github_env_line(key: &str, value: &str) -> String {
+    if !value.contains('\n') {
+        return format!("{}={}", key, value);
+    }
+    let mut delimiter = "EOF".to_string();
+    while value.lines().any(|line| line == delimiter) {
+        delimiter.push('_');
+    }
+    format!("{}<<{}\n{}\n{}", key, delimiter, value, delimiter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Vec<(String, String)> {
+        parse_vars("PORT=8080\nexport GREETING=\"it's a \\\"test\\\"\"\nDEBUG=true\nCERT=\"line one\\nline two\"\n").unwrap()
+    }
+
+    #[test]
+    fn test_shell_and_docker_quoting() {
+        assert_eq!(
+            convert(&sample(), "shell").unwrap(),
+            "export PORT=8080\nexport GREETING='it'\\''s a \"test\"'\nexport DEBUG=true\nexport CERT='line one\nline two'"
+        );
+        assert_eq!(convert(&sample()[..2], "docker").unwrap(), "-e PORT=8080 -e 'GREETING=it'\\''s a \"test\"'");
+        assert_eq!(shell_quote(""), "''");
+        assert_eq!(shell_quote("$HOME"), "'$HOME'");
+    }
+
+    #[test]
+    fn test_dotenv_round_trip() {
+        let dotenv = convert(&sample(), "dotenv").unwrap();
+        assert_eq!(dotenv, "PORT=8080\nGREETING=\"it's a \\\"test\\\"\"\nDEBUG=true\nCERT=\"line one\\nline two\"");
+        assert_eq!(parse_vars(&dotenv).unwrap(), sample());
+        let json = convert(&sample(), "json").unwrap();
+        assert!(json.starts_with("{\n  \"PORT\": \"8080\",\n  \"GREETING\""));
+        assert_eq!(parse_vars(&json).unwrap(), sample());
+    }
+
+    #[test]
+    fn test_compose_quotes_ambiguous_values() {
+        let compose = convert(&sample(), "compose").unwrap();
+        assert!(compose.starts_with("environment:\n  PORT: '8080'\n"));
+        assert!(compose.contains("  DEBUG: 'true'\n"));
+        let parsed: serde_yaml::Value = serde_yaml::from_str(&compose).unwrap();
+        assert_eq!(parsed["environment"]["CERT"].as_str(), Some("line one\nline two"));
+    }
+
+    #[test]
+    fn test_github_actions_and_json_input() {
+        let vars = parse_vars(r#"{"PORT": 8080, "DEBUG": false, "CERT": "a\nEOF\nb", "EMPTY": null}"#).unwrap();
+        assert_eq!(convert(&vars, "github-actions").unwrap(), "PORT=8080\nDEBUG=false\nCERT<<EOF_\na\nEOF\nb\nEOF_\nEMPTY=");
+        assert!(parse_vars(r#"{"NESTED": {"a": 1}}"#).is_err());
+        assert!(parse_vars("[1, 2]").is_err());
+    }
+}
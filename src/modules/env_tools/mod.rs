@@ -0,0 +1,215 @@
+use crate::run_file::{parse_env_file, parse_env_line};
+use crate::tool_module::{ExitCodeError, ToolModule};
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fs;
+
+pub struct EnvToolsModule;
+
+// Masked values all look the same, so their length is not revealed either
+const MASK: &str = "********";
+
+impl ToolModule for EnvToolsModule {
+    fn name(&self) -> &'static str {
+        "env-tools"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("env-diff")
+                .long("env-diff")
+                .value_names(["A", "B"])
+                .num_args(2)
+                .help("Compare two .env files: keys missing, extra or with different values")
+                .long_help("Compare two .env files, e.g. staging and production: keys only in A, keys only in B, and keys whose values differ. Values are never printed, so the output is safe to share.")
+        )
+        .arg(
+            Arg::new("env-validate")
+                .long("env-validate")
+                .value_name("FILE")
+                .help("Check a .env file for malformed lines, duplicate keys and risky values")
+                .long_help("Check a .env file for malformed lines and duplicate keys (errors, exit code 1), and for empty values and unquoted values with spaces, which break `source .env` (warnings).")
+        )
+        .arg(
+            Arg::new("env-mask")
+                .long("env-mask")
+                .value_name("FILE")
+                .help("Print a .env file with every value redacted")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(files) = matches.get_many::<String>("env-diff") {
+            let files: Vec<&String> = files.collect();
+            let a = read_vars(files[0])?;
+            let b = read_vars(files[1])?;
+            let diff = diff_vars(&a, &b);
+            if diff.is_empty() {
+                println!("✅ {} and {} define the same {} keys with the same values", files[0], files[1], a.len());
+            } else {
+                print_keys(&format!("Only in {}", files[0]), &diff.only_in_a, "-".red());
+                print_keys(&format!("Only in {}", files[1]), &diff.only_in_b, "+".green());
+                print_keys("Different values", &diff.changed, "~".yellow());
+                println!("{} identical", diff.same);
+            }
+        } else if let Some(file) = matches.get_one::<String>("env-validate") {
+            let content = read_file(file)?;
+            let issues = validate(&content);
+            for issue in &issues {
+                let prefix = if issue.error { "❌" } else { "⚠️ " };
+                println!("{} line {}: {}", prefix, issue.line, issue.message);
+            }
+            let errors = issues.iter().filter(|issue| issue.error).count();
+            if errors > 0 {
+                return Err(Box::new(ExitCodeError::silent(1)));
+            }
+            if issues.is_empty() {
+                let count = content.lines().filter_map(|line| parse_env_line(line).ok().flatten()).count();
+                println!("✅ {}: {} variables, no problems found", file, count);
+            }
+        } else if let Some(file) = matches.get_one::<String>("env-mask") {
+            print!("{}", mask(&read_file(file)?).map_err(|e| format!("{}: {}", file, e))?);
+        }
+        Ok(())
+    }
+}
+
+fn read_file(path: &str) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("Failed to read '{}': {}", path, e))
+}
+
+// Later definitions win, as when the file is sourced
+fn read_vars(path: &str) -> Result<BTreeMap<String, String>, String> {
+    let content = read_file(path)?;
+    Ok(parse_env_file(&content).map_err(|e| format!("{}: {}", path, e))?.into_iter().collect())
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct EnvDiff {
+    only_in_a: Vec<String>,
+    only_in_b: Vec<String>,
+    changed: Vec<String>,
+    same: usize,
+}
+
+impl EnvDiff {
+    fn is_empty(&self) -> bool {
+        self.only_in_a.is_empty() && self.only_in_b.is_empty() && self.changed.is_empty()
+    }
+}
+
+fn diff_vars(a: &BTreeMap<String, String>, b: &BTreeMap<String, String>) -> EnvDiff {
+    let mut diff = EnvDiff::default();
+    for (key, value) in a {
+        match b.get(key) {
+            None => diff.only_in_a.push(key.clone()),
+            Some(other) if other != value => diff.changed.push(key.clone()),
+            Some(_) => diff.same += 1,
+        }
+    }
+    diff.only_in_b = b.keys().filter(|key| !a.contains_key(*key)).cloned().collect();
+    diff
+}
+
+fn print_keys(title: &str, keys: &[String], marker: ColoredString) {
+    if keys.is_empty() {
+        return;
+    }
+    println!("{} ({}):", title.bold(), keys.len());
+    for key in keys {
+        println!("  {} {}", marker, key);
+    }
+    println!();
+}
+
+#[derive(Debug, PartialEq)]
+struct Issue {
+    line: usize,
+    error: bool,
+    message: String,
+}
+
+fn validate(content: &str) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let mut first_seen: HashMap<String, usize> = HashMap::new();
+    for (index, line) in content.lines().enumerate() {
+        let number = index + 1;
+        let (key, value) = match parse_env_line(line) {
+            Ok(Some(var)) => var,
+            Ok(None) => continue,
+            Err(e) => {
+                issues.push(Issue { line: number, error: true, message: e });
+                continue;
+            }
+        };
+        if let Some(first) = first_seen.get(&key) {
+            issues.push(Issue { line: number, error: true, message: format!("duplicate key {} (first defined on line {})", key, first) });
+        } else {
+            first_seen.insert(key.clone(), number);
+        }
+        let raw_value = line.split_once('=').map(|(_, raw)| raw.trim()).unwrap_or("");
+        if value.is_empty() {
+            issues.push(Issue { line: number, error: false, message: format!("{} has an empty value", key) });
+        } else if !raw_value.starts_with(['"', '\'']) && value.contains(char::is_whitespace) {
+            issues.push(Issue { line: number, error: false, message: format!("{} has an unquoted value with spaces; quote it so shells can source the file", key) });
+        }
+    }
+    issues
+}
+
+// Keeps comments and layout so the masked file reads like the original
+fn mask(content: &str) -> Result<String, String> {
+    let mut masked = String::new();
+    for (index, line) in content.lines().enumerate() {
+        match parse_env_line(line).map_err(|e| format!("line {}: {}", index + 1, e))? {
+            Some((key, value)) => {
+                let export = if line.trim_start().starts_with("export ") { "export " } else { "" };
+                masked.push_str(&format!("{}{}={}\n", export, key, if value.is_empty() { "" } else { MASK }));
+            }
+            None => masked.push_str(&format!("{}\n", line)),
+        }
+    }
+    Ok(masked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(content: &str) -> BTreeMap<String, String> {
+        parse_env_file(content).unwrap().into_iter().collect()
+    }
+
+    #[test]
+    fn test_diff_vars() {
+        let staging = vars("API_URL=https://staging.example.com\nDEBUG=true\nPORT=8080\nDEBUG=false\n");
+        let production = vars("API_URL=https://example.com\nPORT=8080\nSENTRY_DSN=https://sentry\n");
+        let diff = diff_vars(&staging, &production);
+        assert_eq!(diff, EnvDiff {
+            only_in_a: vec!["DEBUG".to_string()],
+            only_in_b: vec!["SENTRY_DSN".to_string()],
+            changed: vec!["API_URL".to_string()],
+            same: 1,
+        });
+        assert!(diff_vars(&staging, &staging).is_empty());
+    }
+
+    #[test]
+    fn test_validate() {
+        let issues = validate("# config\nAPI_URL=https://example.com\nBROKEN LINE\nexport API_URL=other\nEMPTY=\nGREETING=hello world\nQUOTED=\"hello world\"\n1BAD=x\n");
+        let summary: Vec<(usize, bool)> = issues.iter().map(|issue| (issue.line, issue.error)).collect();
+        assert_eq!(summary, [(3, true), (4, true), (5, false), (6, false), (8, true)]);
+        assert_eq!(issues[1].message, "duplicate key API_URL (first defined on line 2)");
+        assert_eq!(issues[0].message, "expected KEY=VALUE");
+        assert!(validate("A=1\nB='two words'\n").is_empty());
+    }
+
+    #[test]
+    fn test_mask() {
+        let masked = mask("# database\nexport DB_PASSWORD=\"hunter2\"\nEMPTY=\n\nTOKEN=abc # inline\n").unwrap();
+        assert_eq!(masked, "# database\nexport DB_PASSWORD=********\nEMPTY=\n\nTOKEN=********\n");
+        assert_eq!(mask("OK=1\nNOPE\n").unwrap_err(), "line 2: expected KEY=VALUE");
+    }
+}
@@ -0,0 +1,325 @@
+use crate::tool_module::ToolModule;
+use chrono::{Duration, NaiveDate, Utc};
+use clap::{Arg, ArgMatches, Command};
+use fake::faker::address::raw::{BuildingNumber, CityName, CountryName, PostCode, StateAbbr, StreetName, ZipCode};
+use fake::faker::company::raw::CompanyName;
+use fake::faker::impls::address::CityNameGenFn;
+use fake::faker::internet::raw::{IPv4, SafeEmail, Username};
+use fake::faker::job::raw::Title as JobTitle;
+use fake::faker::lorem::raw::{Sentence, Word};
+use fake::faker::name::raw::{FirstName, LastName, Name};
+use fake::faker::phone_number::raw::PhoneNumber;
+use fake::locales::{DE_DE, EN, FR_FR, IT_IT, JA_JP, PT_BR, PT_PT, ZH_CN};
+use fake::Fake;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde_json::{Map, Value};
+use std::error::Error;
+use std::fs;
+
+pub struct FakeDataModule;
+
+const KINDS: &[&str] = &[
+    "name", "first_name", "last_name", "email", "username", "address", "city", "country", "phone",
+    "company", "job", "uuid", "iban", "ip", "word", "sentence", "bool",
+];
+
+const LOCALES: [&str; 8] = ["en", "de", "fr", "it", "pt_br", "pt_pt", "ja", "zh_cn"];
+
+impl ToolModule for FakeDataModule {
+    fn name(&self) -> &'static str {
+        "fake-data"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("fake")
+                .long("fake")
+                .value_names(["KIND", "COUNT"])
+                .num_args(1..=2)
+                .help("Generate fake test data: name, email, address, phone, company, uuid, iban, ...")
+                .long_help(format!("Generate COUNT (default 1) fake values, one per line. Kinds: {}. Use --locale for localized names, addresses and phone numbers.", KINDS.join(", ")))
+                .conflicts_with("fake-json")
+        )
+        .arg(
+            Arg::new("fake-json")
+                .long("fake-json")
+                .value_names(["TEMPLATE", "COUNT"])
+                .num_args(1..=2)
+                .help("Generate JSON fixtures from a template such as '{\"name\":\"name\",\"age\":\"number(18..80)\"}'")
+                .long_help(format!("Fill a JSON template (inline or a file path) with fake data. Every string value names a generator: {}, number(MIN..MAX), float(MIN..MAX), date(FROM..TO), one_of(a,b,c). Objects and arrays are filled recursively and other values are kept as they are. With a COUNT above 1 the result is an array.", KINDS.join(", ")))
+        )
+        .arg(
+            Arg::new("locale")
+                .long("locale")
+                .value_name("LOCALE")
+                .help("Locale for --fake and --fake-json")
+                .value_parser(LOCALES)
+                .default_value("en")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let locale = matches.get_one::<String>("locale").map(String::as_str).unwrap_or("en");
+        if let Some(values) = matches.get_many::<String>("fake") {
+            let values: Vec<&String> = values.collect();
+            let count = parse_count(values.get(1).map(|s| s.as_str()))?;
+            for _ in 0..count {
+                match generate(&values[0].replace('-', "_"), locale)? {
+                    Value::String(text) => println!("{}", text),
+                    other => println!("{}", other),
+                }
+            }
+        } else if let Some(values) = matches.get_many::<String>("fake-json") {
+            let values: Vec<&String> = values.collect();
+            let template = read_template(values[0])?;
+            let count = parse_count(values.get(1).map(|s| s.as_str()))?;
+            let output = if count == 1 {
+                fill_template(&template, locale)?
+            } else {
+                Value::Array((0..count).map(|_| fill_template(&template, locale)).collect::<Result<_, _>>()?)
+            };
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        Ok(())
+    }
+}
+
+fn parse_count(value: Option<&str>) -> Result<usize, String> {
+    match value {
+        None => Ok(1),
+        Some(text) => match text.parse::<usize>() {
+            Ok(count) if count > 0 => Ok(count),
+            _ => Err(format!("Invalid count '{}'. Use a positive number", text)),
+        },
+    }
+}
+
+// Inline JSON, or a path to a JSON file
+fn read_template(value: &str) -> Result<Value, String> {
+    let text = if value.trim_start().starts_with(['{', '[']) {
+        value.to_string()
+    } else {
+        fs::read_to_string(value).map_err(|e| format!("Failed to read '{}': {}", value, e))?
+    };
+    serde_json::from_str(&text).map_err(|e| format!("Invalid JSON template: {}", e))
+}
+
+fn fill_template(template: &Value, locale: &str) -> Result<Value, String> {
+    Ok(match template {
+        Value::String(spec) => generate(spec, locale)?,
+        Value::Array(items) => Value::Array(items.iter().map(|item| fill_template(item, locale)).collect::<Result<_, _>>()?),
+        Value::Object(fields) => Value::Object(
+            fields.iter()
+                .map(|(key, value)| Ok((key.clone(), fill_template(value, locale)?)))
+                .collect::<Result<Map<_, _>, String>>()?,
+        ),
+        other => other.clone(),
+    })
+}
+
+// A generator name, optionally with arguments: number(18..80), one_of(a,b)
+fn generate(spec: &str, locale: &str) -> Result<Value, String> {
+    let spec = spec.trim();
+    let (kind, arguments) = match spec.split_once('(') {
+        Some((kind, rest)) => {
+            let arguments = rest.strip_suffix(')').ok_or_else(|| format!("Missing ) in '{}'", spec))?;
+            (kind.trim(), Some(arguments.trim()))
+        }
+        None => (spec, None),
+    };
+    let mut rng = rand::thread_rng();
+    match (kind, arguments) {
+        ("number", range) => {
+            let (min, max) = parse_range::<i64>(range.unwrap_or("0..100"), spec)?;
+            Ok(Value::from(rng.gen_range(min..=max)))
+        }
+        ("float", range) => {
+            let (min, max) = parse_range::<f64>(range.unwrap_or("0..1"), spec)?;
+            let value: f64 = if min == max { min } else { rng.gen_range(min..max) };
+            Ok(Value::from((value * 100.0).round() / 100.0))
+        }
+        ("date", range) => {
+            let today = Utc::now().date_naive();
+            let (from, to) = match range {
+                Some(range) => parse_range::<NaiveDate>(range, spec)?,
+                None => (today - Duration::days(365), today),
+            };
+            let day = from + Duration::days(rng.gen_range(0..=(to - from).num_days()));
+            Ok(Value::String(day.format("%Y-%m-%d").to_string()))
+        }
+        ("one_of", Some(options)) => {
+            let options: Vec<&str> = options.split(',').map(str::trim).collect();
+            Ok(Value::String(options.choose(&mut rng).copied().unwrap_or_default().to_string()))
+        }
+        ("bool", None) => Ok(Value::Bool(rng.gen())),
+        ("uuid", None) => Ok(Value::String(uuid::Uuid::new_v4().to_string())),
+        ("iban", None) => Ok(Value::String(iban(&mut rng, iban_country(locale)))),
+        (kind, None) => match locale {
+            "de" => localized(kind, DE_DE, false),
+            "fr" => localized(kind, FR_FR, false),
+            "it" => localized(kind, IT_IT, false),
+            "pt_br" => localized(kind, PT_BR, false),
+            "pt_pt" => localized(kind, PT_PT, false),
+            "ja" => localized(kind, JA_JP, false),
+            "zh_cn" => localized(kind, ZH_CN, false),
+            _ => localized(kind, EN, true),
+        }
+        .map(Value::String),
+        (kind, Some(_)) => Err(format!("'{}' takes no arguments", kind)),
+    }
+}
+
+fn localized<L: CityNameGenFn>(kind: &str, locale: L, us_address: bool) -> Result<String, String> {
+    Ok(match kind {
+        "name" => Name(locale).fake(),
+        "first_name" => FirstName(locale).fake(),
+        "last_name" => LastName(locale).fake(),
+        "email" => SafeEmail(locale).fake(),
+        "username" => Username(locale).fake(),
+        "address" => address(locale, us_address),
+        "city" => CityName(locale).fake(),
+        "country" => CountryName(locale).fake(),
+        "phone" => PhoneNumber(locale).fake(),
+        "company" => CompanyName(locale).fake(),
+        "job" => JobTitle(locale).fake(),
+        "ip" => IPv4(locale).fake(),
+        "word" => Word(locale).fake(),
+        "sentence" => Sentence(locale, 5..12).fake(),
+        _ => {
+            return Err(format!(
+                "Unknown generator '{}'. Use one of: {}, number(MIN..MAX), float(MIN..MAX), date(FROM..TO), one_of(a,b,c)",
+                kind,
+                KINDS.join(", ")
+            ))
+        }
+    })
+}
+
+// US style "123 Main Street, Springfield, IL 62704"; elsewhere the number
+// follows the street and the postcode comes before the city
+fn address<L: CityNameGenFn>(locale: L, us_style: bool) -> String {
+    let street: String = StreetName(locale).fake();
+    let number: String = BuildingNumber(locale).fake();
+    let city: String = CityName(locale).fake();
+    if us_style {
+        let state: String = StateAbbr(locale).fake();
+        let zip: String = PostCode(locale).fake();
+        format!("{} {}, {}, {} {}", number, street, city, state, zip)
+    } else {
+        let postcode: String = ZipCode(locale).fake();
+        format!("{} {}, {} {}", street, number, postcode, city)
+    }
+}
+
+fn parse_range<T: std::str::FromStr + PartialOrd>(range: &str, spec: &str) -> Result<(T, T), String> {
+    let invalid = || format!("Invalid range in '{}'. Use MIN..MAX", spec);
+    let (min, max) = range.split_once("..").ok_or_else(invalid)?;
+    let min: T = min.trim().parse().map_err(|_| invalid())?;
+    let max: T = max.trim().trim_start_matches('=').parse().map_err(|_| invalid())?;
+    if min > max {
+        return Err(format!("The range in '{}' is empty", spec));
+    }
+    Ok((min, max))
+}
+
+// Locales without IBANs (ja, zh_cn) fall back to Germany
+fn iban_country(locale: &str) -> &'static str {
+    match locale {
+        "en" => "GB",
+        "fr" => "FR",
+        "it" => "IT",
+        "pt_br" => "BR",
+        "pt_pt" => "PT",
+        _ => "DE",
+    }
+}
+
+// A random BBAN in the country's layout (A = letter, 9 = digit) with valid
+// ISO 13616 check digits
+fn iban<R: Rng>(rng: &mut R, country: &str) -> String {
+    let layout = match country {
+        "GB" => "AAAA99999999999999",
+        "FR" => "99999999999999999999999",
+        "IT" => "A9999999999999999999999",
+        "BR" => "99999999999999999999999AA",
+        "PT" => "999999999999999999999",
+        _ => "999999999999999999",
+    };
+    let bban: String = layout
+        .chars()
+        .map(|c| if c == 'A' { rng.gen_range(b'A'..=b'Z') as char } else { char::from_digit(rng.gen_range(0..10), 10).unwrap_or('0') })
+        .collect();
+    let check = 98 - iban_remainder(&format!("{}{}00", bban, country));
+    format!("{}{:02}{}", country, check, bban)
+}
+
+// Letters count as 10..35; the number is reduced mod 97 digit by digit
+pub fn iban_remainder(text: &str) -> u32 {
+    text.chars()
+        .filter_map(|c| c.to_digit(36))
+        .fold(0, |remainder, value| if value < 10 { (remainder * 10 + value) % 97 } else { (remainder * 100 + value) % 97 })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_valid_iban(iban: &str) -> bool {
+        iban_remainder(&format!("{}{}", &iban[4..], &iban[..4])) == 1
+    }
+
+    #[test]
+    fn test_iban_check_digits() {
+        assert!(is_valid_iban("GB82WEST12345698765432"));
+        assert!(is_valid_iban("DE89370400440532013000"));
+        let mut rng = rand::thread_rng();
+        for country in ["GB", "DE", "FR", "IT", "BR", "PT"] {
+            let iban = iban(&mut rng, country);
+            assert!(iban.starts_with(country));
+            assert!(is_valid_iban(&iban), "{}", iban);
+        }
+        assert_eq!(iban(&mut rng, "GB").len(), 22);
+        assert_eq!(iban(&mut rng, "BR").len(), 29);
+    }
+
+    #[test]
+    fn test_generators() {
+        assert!(generate("email", "en").unwrap().as_str().unwrap().contains('@'));
+        assert_eq!(generate("uuid", "en").unwrap().as_str().unwrap().len(), 36);
+        assert!(generate("iban", "de").unwrap().as_str().unwrap().starts_with("DE"));
+        assert!(generate("address", "fr").unwrap().as_str().unwrap().contains(", "));
+        for locale in LOCALES {
+            assert!(!generate("name", locale).unwrap().as_str().unwrap().is_empty());
+        }
+        assert!(generate("nickname", "en").unwrap_err().starts_with("Unknown generator 'nickname'"));
+        assert!(generate("email(1..2)", "en").is_err());
+    }
+
+    #[test]
+    fn test_ranges() {
+        for _ in 0..50 {
+            let age = generate("number(18..80)", "en").unwrap().as_i64().unwrap();
+            assert!((18..=80).contains(&age));
+            let price = generate("float(1.5..2.5)", "en").unwrap().as_f64().unwrap();
+            assert!((1.5..=2.5).contains(&price));
+            let day = generate("date(2024-02-27..2024-03-02)", "en").unwrap();
+            assert!(day.as_str().unwrap() >= "2024-02-27" && day.as_str().unwrap() <= "2024-03-02");
+        }
+        assert_eq!(generate("number(5..5)", "en").unwrap(), Value::from(5));
+        assert!(generate("number(9..1)", "en").is_err());
+        assert!(generate("number(a..b)", "en").is_err());
+        assert!(["red", "green"].contains(&generate("one_of(red, green)", "en").unwrap().as_str().unwrap()));
+    }
+
+    #[test]
+    fn test_fill_template() {
+        let template = read_template(r#"{"id":"uuid","active":"bool","score":7,"tags":["word","word"],"owner":{"name":"name"}}"#).unwrap();
+        let filled = fill_template(&template, "en").unwrap();
+        assert_eq!(filled["score"], 7);
+        assert!(filled["active"].is_boolean());
+        assert_eq!(filled["tags"].as_array().unwrap().len(), 2);
+        assert!(filled["owner"]["name"].is_string());
+        assert!(fill_template(&Value::String("oops".into()), "en").is_err());
+    }
+}
@@ -0,0 +1,177 @@
+use crate::file_size::format_bytes;
+use crate::img_convert::{flatten_onto_white, open_upright};
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use image::codecs::ico::{IcoEncoder, IcoFrame};
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat, RgbaImage};
+use serde_json::{json, Value};
+use std::error::Error;
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+
+pub struct FaviconGenModule;
+
+// Sizes packed into favicon.ico for browser tabs, bookmarks and the Windows taskbar
+const ICO_SIZES: &[u32] = &[16, 32, 48];
+// File name, size, and whether transparency is flattened (iOS draws it black)
+const PNG_ICONS: &[(&str, u32, bool)] = &[
+    ("favicon-16x16.png", 16, false),
+    ("favicon-32x32.png", 32, false),
+    ("apple-touch-icon.png", 180, true),
+    ("android-chrome-192x192.png", 192, false),
+    ("android-chrome-512x512.png", 512, false),
+];
+const MANIFEST: &str = "site.webmanifest";
+const DEFAULT_OUT_DIR: &str = "favicons";
+
+impl ToolModule for FaviconGenModule {
+    fn name(&self) -> &'static str {
+        "favicon-gen"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("favicon")
+                .long("favicon")
+                .value_name("IMAGE")
+                .help("Generate favicon.ico, the standard favicon PNGs and a site.webmanifest from a logo")
+                .long_help("Generate the standard favicon set from a logo: favicon.ico (16, 32 and 48 px), favicon-16x16.png, favicon-32x32.png, apple-touch-icon.png (180 px, on white), android-chrome-192x192.png, android-chrome-512x512.png and site.webmanifest. Non-square logos are centered on a transparent square; use a logo of at least 512×512 for sharp results. The <head> tags to paste are printed afterwards.")
+        )
+        .arg(
+            Arg::new("favicon-out")
+                .long("favicon-out")
+                .value_name("DIR")
+                .help("Directory for the --favicon files (default: favicons)")
+                .requires("favicon")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(path) = matches.get_one::<String>("favicon") {
+            let out_dir = Path::new(matches.get_one::<String>("favicon-out").map(String::as_str).unwrap_or(DEFAULT_OUT_DIR));
+            let names = std::iter::once("favicon.ico").chain(PNG_ICONS.iter().map(|(name, _, _)| *name)).chain([MANIFEST]);
+            for name in names {
+                let existing = out_dir.join(name);
+                if existing.exists() {
+                    return Err(format!("{} already exists; not overwriting it", existing.display()).into());
+                }
+            }
+            let logo = open_upright(path)?;
+            if logo.width().min(logo.height()) < 512 {
+                eprintln!("⚠️  {} is {}×{}; the larger icons will look blurry below 512×512", path, logo.width(), logo.height());
+            }
+            let logo = square(&logo);
+
+            let mut files = vec![("favicon.ico".to_string(), encode_ico(&logo).map_err(|e| format!("Failed to encode favicon.ico: {}", e))?)];
+            for (name, size, opaque) in PNG_ICONS {
+                let icon = logo.resize_exact(*size, *size, FilterType::Lanczos3);
+                let icon = if *opaque { flatten_onto_white(&icon) } else { icon };
+                let mut bytes = Cursor::new(Vec::new());
+                icon.write_to(&mut bytes, ImageFormat::Png).map_err(|e| format!("Failed to encode {}: {}", name, e))?;
+                files.push((name.to_string(), bytes.into_inner()));
+            }
+            files.push((MANIFEST.to_string(), format!("{}\n", serde_json::to_string_pretty(&manifest())?).into_bytes()));
+
+            fs::create_dir_all(out_dir).map_err(|e| format!("Failed to create {}: {}", out_dir.display(), e))?;
+            for (name, bytes) in &files {
+                let file = out_dir.join(name);
+                fs::write(&file, bytes).map_err(|e| format!("Failed to write {}: {}", file.display(), e))?;
+                println!("✅ {:<28}{}", name, format_bytes(bytes.len() as u64).dimmed());
+            }
+            println!();
+            println!("{}", "Copy the files to your site root and add to <head>:".cyan());
+            println!("{}", head_tags());
+        }
+        Ok(())
+    }
+}
+
+// Centers a non-square logo on a transparent square, so icons are not stretched
+fn square(image: &DynamicImage) -> DynamicImage {
+    if image.width() == image.height() {
+        return image.clone();
+    }
+    let side = image.width().max(image.height());
+    let mut canvas = RgbaImage::new(side, side);
+    let x = (side - image.width()) / 2;
+    let y = (side - image.height()) / 2;
+    image::imageops::overlay(&mut canvas, &image.to_rgba8(), x as i64, y as i64);
+    DynamicImage::ImageRgba8(canvas)
+}
+
+fn encode_ico(logo: &DynamicImage) -> Result<Vec<u8>, image::ImageError> {
+    let mut frames = Vec::new();
+    for size in ICO_SIZES {
+        let icon = logo.resize_exact(*size, *size, FilterType::Lanczos3).to_rgba8();
+        frames.push(IcoFrame::as_png(icon.as_raw(), *size, *size, image::ExtendedColorType::Rgba8)?);
+    }
+    let mut bytes = Vec::new();
+    IcoEncoder::new(&mut bytes).encode_images(&frames)?;
+    Ok(bytes)
+}
+
+// Name and colors are left for the site to fill in
+fn manifest() -> Value {
+    json!({
+        "name": "",
+        "short_name": "",
+        "icons": [
+            { "src": "/android-chrome-192x192.png", "sizes": "192x192", "type": "image/png" },
+            { "src": "/android-chrome-512x512.png", "sizes": "512x512", "type": "image/png" },
+        ],
+        "theme_color": "#ffffff",
+        "background_color": "#ffffff",
+        "display": "standalone",
+    })
+}
+
+fn head_tags() -> String {
+    [
+        r#"<link rel="icon" href="/favicon.ico" sizes="16x16 32x32 48x48">"#,
+        r#"<link rel="icon" type="image/png" sizes="32x32" href="/favicon-32x32.png">"#,
+        r#"<link rel="icon" type="image/png" sizes="16x16" href="/favicon-16x16.png">"#,
+        r#"<link rel="apple-touch-icon" sizes="180x180" href="/apple-touch-icon.png">"#,
+        r#"<link rel="manifest" href="/site.webmanifest">"#,
+    ]
+    .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn test_square_centers_logo() {
+        let wide = DynamicImage::ImageRgba8(RgbaImage::from_pixel(40, 20, Rgba([255, 0, 0, 255])));
+        let squared = square(&wide).to_rgba8();
+        assert_eq!(squared.dimensions(), (40, 40));
+        assert_eq!(squared.get_pixel(0, 0).0[3], 0);
+        assert_eq!(squared.get_pixel(20, 20).0, [255, 0, 0, 255]);
+        assert_eq!(squared.get_pixel(20, 39).0[3], 0);
+    }
+
+    #[test]
+    fn test_encode_ico_has_every_size() {
+        let logo = DynamicImage::ImageRgba8(RgbaImage::from_pixel(64, 64, Rgba([0, 128, 255, 255])));
+        let ico = encode_ico(&logo).unwrap();
+        assert_eq!(image::guess_format(&ico).unwrap(), ImageFormat::Ico);
+        // ICONDIR header: reserved, type 1 (icon), image count, then 16-byte entries starting with width
+        assert_eq!(u16::from_le_bytes([ico[4], ico[5]]), ICO_SIZES.len() as u16);
+        let widths: Vec<u32> = (0..ICO_SIZES.len()).map(|index| ico[6 + index * 16] as u32).collect();
+        assert_eq!(widths, ICO_SIZES);
+    }
+
+    #[test]
+    fn test_manifest_lists_android_icons() {
+        let manifest = manifest();
+        let icons: Vec<&str> = manifest["icons"].as_array().unwrap().iter().map(|icon| icon["src"].as_str().unwrap()).collect();
+        assert_eq!(icons, ["/android-chrome-192x192.png", "/android-chrome-512x512.png"]);
+        for src in icons {
+            assert!(PNG_ICONS.iter().any(|(name, _, _)| src == format!("/{}", name)));
+        }
+    }
+}
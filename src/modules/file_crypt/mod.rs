@@ -0,0 +1,233 @@
+use crate::tool_module::ToolModule;
+use age::secrecy::SecretString;
+use age::{scrypt, DecryptError, Decryptor, Encryptor};
+use clap::{Arg, ArgMatches, Command};
+use std::error::Error;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::iter;
+use std::path::PathBuf;
+
+pub struct FileCryptModule;
+
+// Read instead of prompting, for scripts and CI
+pub const PASSPHRASE_ENV: &str = "MICRO_SWISS_PASSPHRASE";
+
+impl ToolModule for FileCryptModule {
+    fn name(&self) -> &'static str {
+        "file-crypt"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("encrypt")
+                .long("encrypt")
+                .value_name("FILE")
+                .help("Encrypt a file with a passphrase, writing FILE.enc")
+                .long_help("Encrypt FILE with a prompted passphrase and write FILE.enc. The output is a standard age file (scrypt passphrase mode, ChaCha20-Poly1305), so it can also be opened with `age -d`. Files are streamed, so dumps of any size work. Set MICRO_SWISS_PASSPHRASE to skip the prompt in scripts.")
+                .conflicts_with("decrypt")
+        )
+        .arg(
+            Arg::new("decrypt")
+                .long("decrypt")
+                .value_name("FILE")
+                .num_args(0..=1)
+                .default_missing_value("")
+                .help("Decrypt a file written by --encrypt (or age -p), dropping the .enc suffix; without FILE, decrypt the --cipher text")
+                .long_help("Decrypt FILE, written by --encrypt or by `age -p`, with a prompted passphrase. The output drops the .enc or .age suffix (FILE.dec otherwise) and is readable only by you. Set MICRO_SWISS_PASSPHRASE to skip the prompt in scripts. Without FILE, after --cipher, the cipher text is decrypted instead.")
+        )
+        .arg(
+            Arg::new("crypt-out")
+                .long("crypt-out")
+                .value_name("FILE")
+                .help("Output file for --encrypt or --decrypt (- for stdout)")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let out = matches.get_one::<String>("crypt-out");
+        if let Some(input) = matches.get_one::<String>("encrypt") {
+            let output = output_path(input, out, encrypted_path);
+            ensure_absent(&output)?;
+            let passphrase = read_passphrase("Passphrase: ", true)?;
+            let reader = open_input(input)?;
+            write_output(&output, |writer| encrypt(reader, writer, &scrypt::Recipient::new(passphrase)))?;
+            report(input, &output);
+        } else if let Some(input) = matches.get_one::<String>("decrypt") {
+            // A bare --decrypt belongs to --cipher
+            if input.is_empty() {
+                if matches.contains_id("cipher") {
+                    return Ok(());
+                }
+                return Err("--decrypt needs a FILE".into());
+            }
+            let output = output_path(input, out, decrypted_path);
+            ensure_absent(&output)?;
+            let reader = open_input(input)?;
+            let passphrase = read_passphrase("Passphrase: ", false)?;
+            write_output(&output, |writer| decrypt(reader, writer, &scrypt::Identity::new(passphrase)))?;
+            report(input, &output);
+        }
+        Ok(())
+    }
+}
+
+// None means stdout
+fn output_path(input: &str, out: Option<&String>, default: fn(&str) -> PathBuf) -> Option<PathBuf> {
+    match out.map(String::as_str) {
+        Some("-") => None,
+        Some(path) => Some(PathBuf::from(path)),
+        None => Some(default(input)),
+    }
+}
+
+fn encrypted_path(input: &str) -> PathBuf {
+    PathBuf::from(format!("{}.enc", input))
+}
+
+// "dump.sql.enc" -> "dump.sql"; anything else gets ".dec" so the input is never overwritten
+fn decrypted_path(input: &str) -> PathBuf {
+    match input.strip_suffix(".enc").or_else(|| input.strip_suffix(".age")) {
+        Some(stem) if !stem.is_empty() => PathBuf::from(stem),
+        _ => PathBuf::from(format!("{}.dec", input)),
+    }
+}
+
+fn ensure_absent(output: &Option<PathBuf>) -> Result<(), String> {
+    match output {
+        Some(path) if path.exists() => Err(format!("{} already exists; not overwriting it (choose another with --crypt-out)", path.display())),
+        _ => Ok(()),
+    }
+}
+
+fn open_input(path: &str) -> Result<BufReader<File>, String> {
+    File::open(path).map(BufReader::new).map_err(|e| format!("Failed to open '{}': {}", path, e))
+}
+
+// MICRO_SWISS_PASSPHRASE, otherwise a hidden prompt; also used by the secret vault
+pub fn read_passphrase(prompt: &str, confirm: bool) -> Result<SecretString, Box<dyn Error>> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(SecretString::from(passphrase));
+    }
+    let passphrase = rpassword::prompt_password(prompt)?;
+    if passphrase.is_empty() {
+        return Err("The passphrase must not be empty".into());
+    }
+    if confirm && rpassword::prompt_password("Confirm passphrase: ")? != passphrase {
+        return Err("Passphrases do not match".into());
+    }
+    Ok(SecretString::from(passphrase))
+}
+
+// The output is created fresh (owner-only on unix) and removed again if anything fails,
+// so a wrong passphrase never leaves a half-written file behind
+fn write_output(output: &Option<PathBuf>, write: impl FnOnce(&mut dyn Write) -> Result<(), String>) -> Result<(), String> {
+    let Some(path) = output else {
+        return write(&mut io::stdout().lock());
+    };
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let file = options.open(path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    let mut writer = BufWriter::new(file);
+    let result = write(&mut writer).and_then(|_| writer.flush().map_err(|e| e.to_string()));
+    if result.is_err() {
+        drop(writer);
+        let _ = fs::remove_file(path);
+    }
+    result
+}
+
+fn encrypt(mut reader: impl Read, writer: &mut dyn Write, recipient: &scrypt::Recipient) -> Result<(), String> {
+    let encryptor = Encryptor::with_recipients(iter::once(recipient as &dyn age::Recipient)).map_err(|e| e.to_string())?;
+    let mut stream = encryptor.wrap_output(writer).map_err(|e| format!("Encryption failed: {}", e))?;
+    io::copy(&mut reader, &mut stream).map_err(|e| format!("Encryption failed: {}", e))?;
+    stream.finish().map_err(|e| format!("Encryption failed: {}", e))?;
+    Ok(())
+}
+
+fn decrypt(reader: impl io::BufRead, writer: &mut dyn Write, identity: &scrypt::Identity) -> Result<(), String> {
+    let decryptor = Decryptor::new_buffered(reader).map_err(|e| match e {
+        DecryptError::InvalidHeader | DecryptError::UnknownFormat => "Not an encrypted file (expected an age file written by --encrypt)".to_string(),
+        e => format!("Decryption failed: {}", e),
+    })?;
+    if !decryptor.is_scrypt() {
+        return Err("This file is encrypted to age keys, not a passphrase; decrypt it with age -d -i KEYFILE".to_string());
+    }
+    let mut stream = decryptor.decrypt(iter::once(identity as &dyn age::Identity)).map_err(|e| match e {
+        DecryptError::NoMatchingKeys | DecryptError::DecryptionFailed | DecryptError::KeyDecryptionFailed => "Wrong passphrase".to_string(),
+        e => format!("Decryption failed: {}", e),
+    })?;
+    io::copy(&mut stream, writer).map_err(|e| format!("Decryption failed, the file is corrupted or truncated: {}", e))?;
+    Ok(())
+}
+
+fn report(input: &str, output: &Option<PathBuf>) {
+    if let Some(path) = output {
+        let size = fs::metadata(path).map(|metadata| metadata.len()).unwrap_or(0);
+        println!("✅ {} → {} ({} bytes)", input, path.display(), size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A low scrypt work factor keeps the tests fast
+    fn recipient(passphrase: &str) -> scrypt::Recipient {
+        let mut recipient = scrypt::Recipient::new(SecretString::from(passphrase.to_string()));
+        recipient.set_work_factor(4);
+        recipient
+    }
+
+    fn identity(passphrase: &str) -> scrypt::Identity {
+        scrypt::Identity::new(SecretString::from(passphrase.to_string()))
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let plaintext: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        let mut ciphertext = Vec::new();
+        encrypt(&plaintext[..], &mut ciphertext, &recipient("correct horse")).unwrap();
+        assert!(ciphertext.starts_with(b"age-encryption.org/v1\n"));
+        let mut decrypted = Vec::new();
+        decrypt(&ciphertext[..], &mut decrypted, &identity("correct horse")).unwrap();
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn test_decrypt_errors() {
+        let mut ciphertext = Vec::new();
+        encrypt(&b"DATABASE_URL=postgres://"[..], &mut ciphertext, &recipient("right")).unwrap();
+        assert_eq!(decrypt(&ciphertext[..], &mut Vec::new(), &identity("wrong")).unwrap_err(), "Wrong passphrase");
+        assert!(decrypt(&b"plain text"[..], &mut Vec::new(), &identity("right")).unwrap_err().starts_with("Not an encrypted file"));
+        ciphertext.truncate(ciphertext.len() - 4);
+        assert!(decrypt(&ciphertext[..], &mut Vec::new(), &identity("right")).is_err());
+    }
+
+    #[test]
+    fn test_output_paths() {
+        assert_eq!(encrypted_path("dump.sql"), PathBuf::from("dump.sql.enc"));
+        assert_eq!(decrypted_path("dump.sql.enc"), PathBuf::from("dump.sql"));
+        assert_eq!(decrypted_path(".env.age"), PathBuf::from(".env"));
+        assert_eq!(decrypted_path("secrets.bin"), PathBuf::from("secrets.bin.dec"));
+        assert_eq!(output_path("a", Some(&"-".to_string()), encrypted_path), None);
+        assert_eq!(output_path("a", Some(&"b".to_string()), encrypted_path), Some(PathBuf::from("b")));
+    }
+
+    #[test]
+    fn test_failed_write_removes_output() {
+        let path = std::env::temp_dir().join(format!("micro-swiss-crypt-{}", std::process::id()));
+        let output = Some(path.clone());
+        assert!(write_output(&output, |writer| {
+            writer.write_all(b"partial").unwrap();
+            Err("Wrong passphrase".to_string())
+        })
+        .is_err());
+        assert!(!path.exists());
+    }
+}
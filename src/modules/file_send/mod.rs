@@ -0,0 +1,222 @@
+use crate::file_size::format_bytes;
+use crate::qr_generate::{generate_qr_ascii, QrOptions};
+use crate::tool_module::{ExitCodeError, ToolModule};
+use crate::webhook_listen::read_request;
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufReader, Write};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener, TcpStream, UdpSocket};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+use url::Url;
+
+pub struct FileSendModule;
+
+const DEFAULT_TIMEOUT_SECS: f64 = 600.0;
+const TOKEN_LENGTH: usize = 16;
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+impl ToolModule for FileSendModule {
+    fn name(&self) -> &'static str {
+        "file-send"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("send")
+                .long("send")
+                .value_name("FILE")
+                .help("Serve FILE on the local network at a random URL, shown with a QR code")
+                .long_help("Start a temporary HTTP server on the local network that offers FILE at a random, unguessable path. The URL is printed along with a QR code so a phone on the same network can download it straight away. The server stops after --downloads completed downloads (default 1) or after --timeout seconds (default 600), whichever comes first; exits with code 1 when it timed out before any download.")
+        )
+        .arg(
+            Arg::new("downloads")
+                .long("downloads")
+                .value_name("N")
+                .help("With --send, stop after N completed downloads (default: 1)")
+                .value_parser(clap::value_parser!(u32).range(1..))
+                .default_value("1")
+                .requires("send")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(path) = matches.get_one::<String>("send") {
+            let file = Path::new(path);
+            let size = file.metadata().map_err(|e| format!("Failed to open '{}': {}", path, e))?.len();
+            if !file.is_file() {
+                return Err(format!("'{}' is not a file", path).into());
+            }
+            let file_name = file.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_else(|| "download".to_string());
+            let downloads = matches.get_one::<u32>("downloads").copied().unwrap_or(1);
+            // --timeout is declared by the run-file module
+            let timeout = Duration::from_secs_f64(matches.get_one::<f64>("timeout").copied().unwrap_or(DEFAULT_TIMEOUT_SECS).max(0.0));
+
+            let ip = lan_ip().unwrap_or_else(|| {
+                eprintln!("⚠️ Could not determine the LAN address; the URL only works on this machine");
+                IpAddr::V4(Ipv4Addr::LOCALHOST)
+            });
+            let listener = TcpListener::bind((ip, 0)).map_err(|e| format!("Failed to start the server: {}", e))?;
+            let url = share_url(listener.local_addr()?, &random_token(), &file_name)?;
+
+            println!("✅ Sending {} ({}) at {}", file_name.bold(), format_bytes(size), url.as_str().bold());
+            println!("   Stops after {} or {}s (Ctrl+C to stop now)\n", plural(downloads, "download"), timeout.as_secs());
+            println!("{}", generate_qr_ascii(url.as_str(), &QrOptions::default())?);
+
+            let served = serve(&listener, file, &file_name, url.path(), downloads, timeout)?;
+            if served == 0 {
+                return Err(Box::new(ExitCodeError::new(1, format!("Timed out after {}s without a download", timeout.as_secs()))));
+            }
+            println!("✅ Done: {}", plural(served, "download"));
+        }
+        Ok(())
+    }
+}
+
+fn plural(count: u32, noun: &str) -> String {
+    format!("{} {}{}", count, noun, if count == 1 { "" } else { "s" })
+}
+
+// The address of the interface that routes to the internet. Connecting a UDP
+// socket sends no packets; it only selects the route.
+fn lan_ip() -> Option<IpAddr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("192.0.2.1:80").ok()?;
+    Some(socket.local_addr().ok()?.ip()).filter(|ip| !ip.is_unspecified())
+}
+
+fn random_token() -> String {
+    rand::thread_rng().sample_iter(&Alphanumeric).take(TOKEN_LENGTH).map(char::from).collect()
+}
+
+// http://ADDR/TOKEN/FILE_NAME, with the file name percent-encoded
+fn share_url(addr: SocketAddr, token: &str, file_name: &str) -> Result<Url, Box<dyn Error>> {
+    let mut url = Url::parse(&format!("http://{}/", addr))?;
+    url.path_segments_mut().map_err(|_| "Invalid server URL")?.push(token).push(file_name);
+    Ok(url)
+}
+
+// Serves connections one at a time until enough downloads have completed or
+// the deadline passes, and returns the number of completed downloads
+fn serve(listener: &TcpListener, file: &Path, file_name: &str, path: &str, downloads: u32, timeout: Duration) -> Result<u32, Box<dyn Error>> {
+    listener.set_nonblocking(true)?;
+    let deadline = Instant::now() + timeout;
+    let mut served = 0;
+    while served < downloads && Instant::now() < deadline {
+        let (stream, peer) = match listener.accept() {
+            Ok(connection) => connection,
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                thread::sleep(POLL_INTERVAL);
+                continue;
+            }
+            Err(e) => {
+                eprintln!("⚠️ Connection failed: {}", e);
+                continue;
+            }
+        };
+        match handle_connection(stream, file, file_name, path) {
+            Ok(Some(bytes)) => {
+                served += 1;
+                println!("⬇️  Download {}/{} by {} ({})", served, downloads, peer.ip(), format_bytes(bytes));
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("⚠️ {}: {}", peer.ip(), e),
+        }
+    }
+    Ok(served)
+}
+
+// Returns the bytes sent for a completed GET of the file; other requests get
+// a 404 (or 405) and count as nothing
+fn handle_connection(mut stream: TcpStream, file: &Path, file_name: &str, path: &str) -> Result<Option<u64>, String> {
+    stream.set_nonblocking(false).map_err(|e| e.to_string())?;
+    stream.set_read_timeout(Some(Duration::from_secs(10))).map_err(|e| e.to_string())?;
+    let request = read_request(&mut BufReader::new(&stream))?;
+    let target = request.target.split('?').next().unwrap_or("");
+    if target != path {
+        stream.write_all(&status_response(404, "Not Found")).map_err(|e| e.to_string())?;
+        return Ok(None);
+    }
+    if request.method != "GET" && request.method != "HEAD" {
+        stream.write_all(&status_response(405, "Method Not Allowed")).map_err(|e| e.to_string())?;
+        return Ok(None);
+    }
+    let mut source = File::open(file).map_err(|e| format!("Failed to open '{}': {}", file.display(), e))?;
+    let size = source.metadata().map_err(|e| e.to_string())?.len();
+    let encoded_name = path.rsplit('/').next().unwrap_or("");
+    stream.write_all(file_headers(size, file_name, encoded_name).as_bytes()).map_err(|e| format!("Failed to send response: {}", e))?;
+    if request.method == "HEAD" {
+        return Ok(None);
+    }
+    let sent = io::copy(&mut source, &mut stream).map_err(|e| format!("Download interrupted: {}", e))?;
+    Ok((sent == size).then_some(sent))
+}
+
+fn status_response(status: u16, reason: &str) -> Vec<u8> {
+    format!("HTTP/1.1 {} {}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, reason, reason.len(), reason).into_bytes()
+}
+
+// The quoted filename is an ASCII fallback; filename* carries the real name
+fn file_headers(size: u64, file_name: &str, encoded_name: &str) -> String {
+    let fallback: String = file_name.chars().map(|c| if (c.is_ascii_graphic() && c != '"' && c != '\\') || c == ' ' { c } else { '_' }).collect();
+    format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/octet-stream\r\nContent-Length: {}\r\nContent-Disposition: attachment; filename=\"{}\"; filename*=UTF-8''{}\r\nConnection: close\r\n\r\n",
+        size, fallback, encoded_name
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::Read;
+
+    #[test]
+    fn test_share_url() {
+        let addr: SocketAddr = "192.168.1.20:8123".parse().unwrap();
+        let url = share_url(addr, "AbC123", "my report.pdf").unwrap();
+        assert_eq!(url.as_str(), "http://192.168.1.20:8123/AbC123/my%20report.pdf");
+        assert_eq!(random_token().len(), TOKEN_LENGTH);
+    }
+
+    #[test]
+    fn test_file_headers() {
+        let headers = file_headers(42, "résumé \"v2\".txt", "r%C3%A9sum%C3%A9%20%22v2%22.txt");
+        assert!(headers.contains("Content-Length: 42\r\n"));
+        assert!(headers.contains("filename=\"r_sum_ _v2_.txt\"; filename*=UTF-8''r%C3%A9sum%C3%A9%20%22v2%22.txt\r\n"));
+    }
+
+    #[test]
+    fn test_serve_until_download_limit() {
+        let dir = std::env::temp_dir().join(format!("ms-file-send-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("hello.txt");
+        fs::write(&file, "hello over the LAN").unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = share_url(listener.local_addr().unwrap(), "token", "hello.txt").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            let fetch = |path: &str| {
+                let mut stream = TcpStream::connect(addr).unwrap();
+                write!(stream, "GET {} HTTP/1.1\r\nHost: test\r\n\r\n", path).unwrap();
+                let mut response = String::new();
+                stream.read_to_string(&mut response).unwrap();
+                response
+            };
+            (fetch("/wrong/hello.txt"), fetch("/token/hello.txt"))
+        });
+        let served = serve(&listener, &file, "hello.txt", url.path(), 1, Duration::from_secs(10)).unwrap();
+        let (missing, found) = client.join().unwrap();
+        assert_eq!(served, 1);
+        assert!(missing.starts_with("HTTP/1.1 404"));
+        assert!(found.starts_with("HTTP/1.1 200 OK"));
+        assert!(found.ends_with("\r\n\r\nhello over the LAN"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -53,7 +53,7 @@ impl ToolModule for FileSizeModule {
     }
 }
 
-fn format_bytes(bytes: u64) -> String {
+pub fn format_bytes(bytes: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB", "PB"];
     const THRESHOLD: f64 = 1024.0;
     
@@ -0,0 +1,297 @@
+use crate::file_size::format_bytes;
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgMatches, Command};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+pub struct FileSplitModule;
+
+const BUFFER: usize = 64 * 1024;
+
+impl ToolModule for FileSplitModule {
+    fn name(&self) -> &'static str {
+        "file-split"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("split")
+                .long("split")
+                .value_names(["FILE", "SIZE"])
+                .num_args(2)
+                .help("Split FILE into numbered SIZE parts (e.g. 100MB) plus a SHA-256 manifest")
+                .long_help("Split FILE into FILE.part001, FILE.part002, ... of at most SIZE each, streaming so the file never sits in memory. SIZE is a byte count or a number with K, M, G or T (KB, MiB and so on are accepted too; all are powers of 1024), e.g. 100MB or 1.5G. FILE.sha256 lists the SHA-256 of every part and of the whole file, in the format sha256sum -c reads; --join checks it before reassembling.")
+        )
+        .arg(
+            Arg::new("join")
+                .long("join")
+                .value_name("PARTS")
+                .num_args(1..)
+                .help("Verify and reassemble the parts made by --split, e.g. --join big.bin.part*")
+                .long_help("Reassemble FILE from FILE.part001, FILE.part002, ... in numeric order, whatever order the shell lists them in. When FILE.sha256 from --split is next to the parts, every part is checked against it before anything is written and the result is checked again afterwards; a missing or corrupt part is an error. The output is never overwritten if it already exists.")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(values) = matches.get_many::<String>("split") {
+            let values: Vec<&String> = values.collect();
+            let size = parse_size(values[1])?;
+            let parts = split(values[0], size)?;
+            for part in &parts {
+                println!("  {}  {}", part.name, format_bytes(part.size));
+            }
+            println!("✅ Split {} into {} parts of up to {}; checksums in {}", values[0], parts.len(), format_bytes(size), manifest_name(values[0]));
+        }
+        if let Some(parts) = matches.get_many::<String>("join") {
+            let parts: Vec<&str> = parts.map(String::as_str).collect();
+            let (output, size, verified) = join(&parts)?;
+            if verified {
+                println!("✅ Joined {} parts into {} ({}), SHA-256 verified", parts.len(), output, format_bytes(size));
+            } else {
+                println!("✅ Joined {} parts into {} ({})", parts.len(), output, format_bytes(size));
+                eprintln!("⚠️  No {} found, so the parts could not be verified", manifest_name(&output));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct Part {
+    name: String,
+    size: u64,
+}
+
+// Sizes are binary, like the ones format_bytes prints
+fn parse_size(text: &str) -> Result<u64, String> {
+    let text = text.trim();
+    let split = text.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: f64 = number.parse().map_err(|_| format!("Invalid size '{}'; use e.g. 100MB, 1.5G or 4096", text))?;
+    let power = match unit.trim().to_uppercase().as_str() {
+        "" | "B" => 0,
+        "K" | "KB" | "KIB" => 1,
+        "M" | "MB" | "MIB" => 2,
+        "G" | "GB" | "GIB" => 3,
+        "T" | "TB" | "TIB" => 4,
+        other => return Err(format!("Unknown size unit '{}'; use B, K, M, G or T", other)),
+    };
+    let bytes = (number * 1024f64.powi(power)).floor();
+    if bytes < 1.0 {
+        return Err("The part size must be at least one byte".to_string());
+    }
+    Ok(bytes as u64)
+}
+
+fn manifest_name(path: &str) -> String {
+    format!("{}.sha256", path)
+}
+
+fn part_name(path: &str, index: usize, width: usize) -> String {
+    format!("{}.part{:0width$}", path, index, width = width)
+}
+
+// Splits a part name into the original file and the part number
+fn parse_part_name(path: &str) -> Option<(&str, usize)> {
+    let (original, number) = path.rsplit_once(".part")?;
+    if original.is_empty() || number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some((original, number.parse().ok()?))
+}
+
+fn file_name(path: &str) -> &str {
+    Path::new(path).file_name().and_then(|name| name.to_str()).unwrap_or(path)
+}
+
+fn hex(digest: impl AsRef<[u8]>) -> String {
+    digest.as_ref().iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn create(path: &str) -> Result<BufWriter<File>, String> {
+    if Path::new(path).exists() {
+        return Err(format!("{} already exists; not overwriting it", path));
+    }
+    File::create(path).map(|file| BufWriter::with_capacity(BUFFER, file)).map_err(|e| format!("Failed to create {}: {}", path, e))
+}
+
+// Reads up to `limit` bytes into `output`, hashing them into every hasher given
+fn copy_hashed<R: Read, W: Write>(input: &mut R, output: &mut W, limit: u64, hashers: &mut [&mut Sha256]) -> std::io::Result<u64> {
+    let mut buffer = vec![0u8; BUFFER];
+    let mut copied = 0;
+    while copied < limit {
+        let wanted = (limit - copied).min(BUFFER as u64) as usize;
+        let read = input.read(&mut buffer[..wanted])?;
+        if read == 0 {
+            break;
+        }
+        for hasher in hashers.iter_mut() {
+            hasher.update(&buffer[..read]);
+        }
+        output.write_all(&buffer[..read])?;
+        copied += read as u64;
+    }
+    Ok(copied)
+}
+
+fn split(path: &str, size: u64) -> Result<Vec<Part>, Box<dyn Error>> {
+    let total = fs::metadata(path).map_err(|e| format!("Failed to open '{}': {}", path, e))?.len();
+    let count = total.div_ceil(size).max(1);
+    if count > 99_999 {
+        return Err(format!("{} parts of {} is too many; pick a larger size", count, format_bytes(size)).into());
+    }
+    let width = count.to_string().len().max(3);
+    let manifest = manifest_name(path);
+    if Path::new(&manifest).exists() {
+        return Err(format!("{} already exists; not overwriting it", manifest).into());
+    }
+    let mut input = BufReader::with_capacity(BUFFER, File::open(path)?);
+    let mut whole = Sha256::new();
+    let mut parts = Vec::new();
+    let mut lines = Vec::new();
+    for index in 1..=count as usize {
+        let name = part_name(path, index, width);
+        let mut output = create(&name)?;
+        let mut hasher = Sha256::new();
+        let written = copy_hashed(&mut input, &mut output, size, &mut [&mut hasher, &mut whole]).map_err(|e| format!("Failed to write {}: {}", name, e))?;
+        output.flush().map_err(|e| format!("Failed to write {}: {}", name, e))?;
+        lines.push(format!("{}  {}", hex(hasher.finalize()), file_name(&name)));
+        parts.push(Part { name, size: written });
+    }
+    lines.push(format!("{}  {}", hex(whole.finalize()), file_name(path)));
+    fs::write(&manifest, lines.join("\n") + "\n").map_err(|e| format!("Failed to write {}: {}", manifest, e))?;
+    Ok(parts)
+}
+
+// Reads a sha256sum-style manifest into file name → digest
+fn read_manifest(path: &str) -> Result<Option<HashMap<String, String>>, String> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("Failed to read {}: {}", path, e)),
+    };
+    let mut digests = HashMap::new();
+    for line in text.lines().filter(|line| !line.trim().is_empty()) {
+        let (digest, name) = line.split_once(' ').ok_or_else(|| format!("Malformed line in {}: {}", path, line))?;
+        digests.insert(name.trim_start_matches([' ', '*']).to_string(), digest.to_lowercase());
+    }
+    Ok(Some(digests))
+}
+
+fn hash_file(path: &str) -> Result<String, Box<dyn Error>> {
+    let mut input = BufReader::with_capacity(BUFFER, File::open(path).map_err(|e| format!("Failed to open '{}': {}", path, e))?);
+    let mut hasher = Sha256::new();
+    copy_hashed(&mut input, &mut std::io::sink(), u64::MAX, &mut [&mut hasher])?;
+    Ok(hex(hasher.finalize()))
+}
+
+// Returns the output path, its size and whether a manifest verified it
+fn join(paths: &[&str]) -> Result<(String, u64, bool), Box<dyn Error>> {
+    let mut numbered = Vec::new();
+    for path in paths {
+        let (original, number) = parse_part_name(path).ok_or_else(|| format!("'{}' is not a part made by --split (NAME.partNNN)", path))?;
+        numbered.push((original, number, *path));
+    }
+    let original = numbered[0].0.to_string();
+    if let Some((other, _, _)) = numbered.iter().find(|(other, _, _)| *other != original) {
+        return Err(format!("Parts of different files given: {} and {}", original, other).into());
+    }
+    numbered.sort_by_key(|(_, number, _)| *number);
+    for (expected, (_, number, path)) in numbered.iter().enumerate() {
+        if *number == expected {
+            return Err(format!("Part {} is given twice ({})", number, path).into());
+        }
+        if *number != expected + 1 {
+            return Err(format!("Part {} is missing (next is {})", expected + 1, path).into());
+        }
+    }
+    let manifest = read_manifest(&manifest_name(&original))?;
+    if let Some(digests) = &manifest {
+        let listed = digests.keys().filter(|name| parse_part_name(name).is_some()).count();
+        if listed != numbered.len() {
+            return Err(format!("{} lists {} parts but {} were given", manifest_name(&original), listed, numbered.len()).into());
+        }
+        for (_, _, path) in &numbered {
+            let expected = digests.get(file_name(path)).ok_or_else(|| format!("{} is not listed in {}", path, manifest_name(&original)))?;
+            if hash_file(path)? != *expected {
+                return Err(format!("{} is corrupt: its SHA-256 does not match {}", path, manifest_name(&original)).into());
+            }
+        }
+    }
+    let mut output = create(&original)?;
+    let mut whole = Sha256::new();
+    let mut size = 0;
+    let result = (|| -> Result<(), Box<dyn Error>> {
+        for (_, _, path) in &numbered {
+            let mut input = BufReader::with_capacity(BUFFER, File::open(path).map_err(|e| format!("Failed to open '{}': {}", path, e))?);
+            size += copy_hashed(&mut input, &mut output, u64::MAX, &mut [&mut whole])?;
+        }
+        output.flush()?;
+        if let Some(expected) = manifest.as_ref().and_then(|digests| digests.get(file_name(&original))) {
+            if hex(whole.finalize()) != *expected {
+                return Err(format!("The joined file does not match the SHA-256 of {} in the manifest", file_name(&original)).into());
+            }
+        }
+        Ok(())
+    })();
+    if let Err(e) = result {
+        drop(output);
+        let _ = fs::remove_file(&original);
+        return Err(e);
+    }
+    Ok((original, size, manifest.is_some()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("4096").unwrap(), 4096);
+        assert_eq!(parse_size("100MB").unwrap(), 100 * 1024 * 1024);
+        assert_eq!(parse_size("1.5k").unwrap(), 1536);
+        assert_eq!(parse_size("2 GiB").unwrap(), 2 << 30);
+        assert!(parse_size("0").is_err());
+        assert!(parse_size("10 parsecs").is_err());
+        assert!(parse_size("MB").is_err());
+    }
+
+    #[test]
+    fn test_part_names() {
+        assert_eq!(part_name("dir/big.bin", 7, 3), "dir/big.bin.part007");
+        assert_eq!(parse_part_name("dir/big.bin.part007"), Some(("dir/big.bin", 7)));
+        assert_eq!(parse_part_name("big.bin.part"), None);
+        assert_eq!(parse_part_name("big.bin.sha256"), None);
+    }
+
+    #[test]
+    fn test_split_and_join() {
+        let dir = std::env::temp_dir().join(format!("ms-file-split-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("data.bin").to_str().unwrap().to_string();
+        let data: Vec<u8> = (0..2500u32).map(|i| (i * 7) as u8).collect();
+        fs::write(&path, &data).unwrap();
+
+        let parts = split(&path, 1000).unwrap();
+        assert_eq!(parts.iter().map(|part| part.size).collect::<Vec<_>>(), [1000, 1000, 500]);
+        fs::rename(&path, dir.join("original.bin")).unwrap();
+        // Shell globs may list the parts in any order
+        let names: Vec<&str> = parts.iter().rev().map(|part| part.name.as_str()).collect();
+        assert_eq!(join(&names).unwrap(), (path.clone(), 2500, true));
+        assert_eq!(fs::read(&path).unwrap(), data);
+        assert!(join(&names).unwrap_err().to_string().contains("not overwriting"));
+
+        fs::remove_file(&path).unwrap();
+        assert!(join(&names[..2]).unwrap_err().to_string().contains("is missing"));
+        fs::write(&parts[1].name, vec![0u8; 1000]).unwrap();
+        assert!(join(&names).unwrap_err().to_string().contains("is corrupt"));
+        assert!(!Path::new(&path).exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
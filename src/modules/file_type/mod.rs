@@ -0,0 +1,260 @@
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use infer::MatcherType;
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+pub struct FileTypeModule;
+
+// Every signature infer knows sits in the first 8 KB
+const SNIFF_BYTES: u64 = 8192;
+
+const DESCRIPTIONS: &[(&str, &str)] = &[
+    ("jpg", "JPEG image"),
+    ("png", "PNG image"),
+    ("gif", "GIF image"),
+    ("webp", "WebP image"),
+    ("bmp", "BMP image"),
+    ("tif", "TIFF image"),
+    ("ico", "Windows icon"),
+    ("heif", "HEIF image"),
+    ("avif", "AVIF image"),
+    ("psd", "Photoshop document"),
+    ("mp4", "MPEG-4 video"),
+    ("mkv", "Matroska video"),
+    ("webm", "WebM video"),
+    ("mov", "QuickTime video"),
+    ("avi", "AVI video"),
+    ("mp3", "MP3 audio"),
+    ("m4a", "MPEG-4 audio"),
+    ("ogg", "Ogg audio"),
+    ("flac", "FLAC audio"),
+    ("wav", "WAVE audio"),
+    ("pdf", "PDF document"),
+    ("doc", "Word 97-2003 document"),
+    ("docx", "Word document"),
+    ("xls", "Excel 97-2003 spreadsheet"),
+    ("xlsx", "Excel spreadsheet"),
+    ("ppt", "PowerPoint 97-2003 presentation"),
+    ("pptx", "PowerPoint presentation"),
+    ("epub", "EPUB e-book"),
+    ("zip", "ZIP archive"),
+    ("tar", "tar archive"),
+    ("gz", "gzip compressed data"),
+    ("bz2", "bzip2 compressed data"),
+    ("xz", "XZ compressed data"),
+    ("zst", "Zstandard compressed data"),
+    ("7z", "7-Zip archive"),
+    ("rar", "RAR archive"),
+    ("deb", "Debian package"),
+    ("rpm", "RPM package"),
+    ("sqlite", "SQLite database"),
+    ("wasm", "WebAssembly module"),
+    ("elf", "ELF executable"),
+    ("exe", "Windows executable"),
+    ("mach", "Mach-O executable"),
+    ("class", "Java class file"),
+    ("woff", "WOFF font"),
+    ("woff2", "WOFF2 font"),
+    ("ttf", "TrueType font"),
+    ("html", "HTML document"),
+    ("xml", "XML document"),
+    ("sh", "Shell script"),
+];
+
+// Extensions that name the same kind of content
+const EQUIVALENT_EXTENSIONS: &[&[&str]] = &[
+    &["jpg", "jpeg", "jpe", "jfif"],
+    &["tif", "tiff"],
+    &["heif", "heic"],
+    &["midi", "mid"],
+    &["mpg", "mpeg"],
+    &["aiff", "aif"],
+    &["ogg", "oga", "ogv", "opus"],
+    &["exe", "dll", "sys", "efi"],
+    &["elf", "so", "o", "out", "bin"],
+    &["ttf", "otf"],
+    &["gz", "tgz"],
+    &["bz2", "tbz2"],
+    &["xz", "txz"],
+    &["zip", "jar", "apk", "aab", "whl", "nupkg", "ipa", "xpi", "vsix", "war"],
+    &["sqlite", "sqlite3", "db"],
+    &["html", "htm", "xhtml"],
+    &["xml", "svg", "xsd", "xsl", "xslt", "plist", "rss", "atom", "csproj"],
+    &["sh", "bash", "zsh", "ksh"],
+    &["pem", "crt", "cer", "key"],
+    &["der", "crt", "cer"],
+];
+
+#[derive(Debug, PartialEq)]
+struct Detected {
+    mime: String,
+    description: String,
+    // None for text and JSON, which go by many extensions, and unknown binary data
+    extension: Option<String>,
+}
+
+impl ToolModule for FileTypeModule {
+    fn name(&self) -> &'static str {
+        "file-type"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("file-type")
+                .long("file-type")
+                .value_name("PATH")
+                .help("Identify a file's real type from its magic bytes and check its extension (- for stdin)")
+                .long_help("Identify what a file really contains from its magic bytes, whatever its extension says: images, audio and video, archives, documents, executables, fonts, HTML/XML and plain text or JSON. Reports the MIME type and a description, and warns when the extension does not match, e.g. an \"image\" download that is really an HTML error page. Use - to read stdin.")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(path) = matches.get_one::<String>("file-type") {
+            let mut sample = Vec::new();
+            let complete = if path == "-" {
+                let mut stdin = io::stdin().lock();
+                stdin.by_ref().take(SNIFF_BYTES).read_to_end(&mut sample)?;
+                // Drain the rest so the writer does not see a broken pipe
+                io::copy(&mut stdin, &mut io::sink())? == 0
+            } else {
+                let file = File::open(path).map_err(|e| format!("Failed to open '{}': {}", path, e))?;
+                let size = file.metadata()?.len();
+                file.take(SNIFF_BYTES).read_to_end(&mut sample)?;
+                size <= SNIFF_BYTES
+            };
+            let detected = detect(&sample, complete);
+            println!("{:<14}{}", "Type:".cyan(), detected.description.bold());
+            println!("{:<14}{}", "MIME:".cyan(), detected.mime);
+            if let Some(extension) = &detected.extension {
+                println!("{:<14}.{}", "Extension:".cyan(), extension);
+            }
+            if path != "-" {
+                match Path::new(path).extension().and_then(|extension| extension.to_str()) {
+                    None => println!("⚠️  {} has no extension", path),
+                    Some(actual) => match extension_matches(&detected, actual) {
+                        Some(true) => println!("✅ .{} matches the content", actual),
+                        Some(false) => {
+                            let expected = detected.extension.as_deref().map(|extension| format!(" (expected .{})", extension)).unwrap_or_default();
+                            println!("❌ .{} does not match the content{}", actual, expected);
+                        }
+                        None => println!("⚠️  Cannot tell whether .{} matches unrecognized binary content", actual),
+                    },
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// `complete` says whether `sample` holds the whole file, so JSON can be parsed rather than guessed
+fn detect(sample: &[u8], complete: bool) -> Detected {
+    if sample.is_empty() {
+        return Detected { mime: "inode/x-empty".to_string(), description: "Empty file".to_string(), extension: None };
+    }
+    if let Some(kind) = infer::get(sample) {
+        let extension = kind.extension();
+        let description = DESCRIPTIONS
+            .iter()
+            .find(|(known, _)| *known == extension)
+            .map(|(_, description)| description.to_string())
+            .unwrap_or_else(|| format!("{} {}", extension.to_uppercase(), category(kind.matcher_type())));
+        return Detected { mime: kind.mime_type().to_string(), description, extension: Some(extension.to_string()) };
+    }
+    if !is_text(sample) {
+        return Detected { mime: "application/octet-stream".to_string(), description: "Binary data".to_string(), extension: None };
+    }
+    if complete && serde_json::from_slice::<serde_json::Value>(sample).is_ok_and(|value| value.is_object() || value.is_array()) {
+        return Detected { mime: "application/json".to_string(), description: "JSON data".to_string(), extension: None };
+    }
+    Detected { mime: "text/plain".to_string(), description: "Plain text".to_string(), extension: None }
+}
+
+fn category(matcher: MatcherType) -> &'static str {
+    match matcher {
+        MatcherType::App => "executable",
+        MatcherType::Archive => "archive",
+        MatcherType::Audio => "audio",
+        MatcherType::Book => "e-book",
+        MatcherType::Doc => "document",
+        MatcherType::Font => "font",
+        MatcherType::Image => "image",
+        MatcherType::Text => "text",
+        MatcherType::Video => "video",
+        MatcherType::Custom => "file",
+    }
+}
+
+// UTF-8 without NUL bytes; a character cut off by the sample's end still counts
+fn is_text(sample: &[u8]) -> bool {
+    if sample.contains(&0) {
+        return false;
+    }
+    match std::str::from_utf8(sample) {
+        Ok(_) => true,
+        Err(e) => e.error_len().is_none(),
+    }
+}
+
+// None when the content has no signature to check the extension against
+fn extension_matches(detected: &Detected, actual: &str) -> Option<bool> {
+    let actual = actual.to_lowercase();
+    match &detected.extension {
+        Some(expected) => Some(
+            *expected == actual
+                || EQUIVALENT_EXTENSIONS.iter().any(|group| group.contains(&expected.as_str()) && group.contains(&actual.as_str())),
+        ),
+        // An extension naming a format with a signature is wrong, anything else can't be told apart
+        None if infer::is_supported(&actual) => Some(false),
+        None if detected.mime != "application/octet-stream" => Some(true),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\0\x01\0\0\0\x01\x08\x06\0\0\0\x1f\x15\xc4\x89";
+
+    #[test]
+    fn test_detect_by_magic_bytes() {
+        let png = detect(PNG, true);
+        assert_eq!(png, Detected { mime: "image/png".to_string(), description: "PNG image".to_string(), extension: Some("png".to_string()) });
+        assert_eq!(detect(b"%PDF-1.7\n%\xe2\xe3\xcf\xd3\n", false).description, "PDF document");
+        assert_eq!(detect(b"\x1f\x8b\x08\0\0\0\0\0\0\x03", true).mime, "application/gzip");
+        assert_eq!(detect(b"\0\x01\x02\xff\xfe", true).description, "Binary data");
+        assert_eq!(detect(b"", true).description, "Empty file");
+    }
+
+    #[test]
+    fn test_detect_text() {
+        let html = detect(b"\n<!DOCTYPE html>\n<html><body>404 Not Found</body></html>\n", true);
+        assert_eq!((html.mime.as_str(), html.extension.as_deref()), ("text/html", Some("html")));
+        assert_eq!(detect(br#"{"name": "micro-swiss"}"#, true).mime, "application/json");
+        // A truncated sample cannot be parsed, so it stays plain text
+        assert_eq!(detect(br#"{"name": "micro-swiss"}"#, false).mime, "text/plain");
+        assert_eq!(detect("héllo wörld".as_bytes(), true).mime, "text/plain");
+        assert!(is_text(&"ü".as_bytes()[..1]));
+    }
+
+    #[test]
+    fn test_extension_matches() {
+        let jpeg = Detected { mime: "image/jpeg".to_string(), description: "JPEG image".to_string(), extension: Some("jpg".to_string()) };
+        assert_eq!(extension_matches(&jpeg, "jpg"), Some(true));
+        assert_eq!(extension_matches(&jpeg, "JPEG"), Some(true));
+        assert_eq!(extension_matches(&jpeg, "png"), Some(false));
+        let html = detect(b"<html><body>Access denied</body></html>", true);
+        assert_eq!(extension_matches(&html, "jpg"), Some(false));
+        assert_eq!(extension_matches(&html, "htm"), Some(true));
+        let text = detect(b"just some notes\n", true);
+        assert_eq!(extension_matches(&text, "md"), Some(true));
+        assert_eq!(extension_matches(&text, "png"), Some(false));
+        let binary = detect(b"\0\x01\x02", true);
+        assert_eq!(extension_matches(&binary, "dat"), None);
+        assert_eq!(extension_matches(&binary, "pdf"), Some(false));
+    }
+}
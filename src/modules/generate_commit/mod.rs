@@ -0,0 +1,293 @@
+use crate::convert_to_branch::{extract_ticket_id, split_type_prefix};
+use crate::tool_module::ToolModule;
+use arboard::Clipboard;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use std::error::Error;
+
+pub const COMMIT_TYPES: [&str; 11] = [
+    "feat", "fix", "docs", "style", "refactor", "perf", "test", "build", "ci", "chore", "revert",
+];
+
+const MAX_HEADER_LENGTH: usize = 72;
+
+pub struct GenerateCommitModule;
+
+impl ToolModule for GenerateCommitModule {
+    fn name(&self) -> &'static str {
+        "generate-commit"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("generate-commit")
+                .long("generate-commit")
+                .value_name("STRING")
+                .help("Generate a conventional commit message (auto-copied to clipboard)")
+                .long_help("Generate a Conventional Commits message from a short description: the type is inferred from the leading verb (Fix → fix, Add → feat, Update → refactor, ...), the subject is kept under 72 characters, ticket IDs (ABC-123, #456) become a Refs footer and a body template is appended. Result is automatically copied to the clipboard.")
+        )
+        .arg(
+            Arg::new("commit-type")
+                .long("commit-type")
+                .value_name("TYPE")
+                .help("Conventional commit type (feat, fix, docs, refactor, chore, ...)")
+                .value_parser(COMMIT_TYPES)
+        )
+        .arg(
+            Arg::new("scope")
+                .long("scope")
+                .value_name("SCOPE")
+                .help("Conventional commit scope, e.g. --scope checkout")
+        )
+        .arg(
+            Arg::new("breaking")
+                .long("breaking")
+                .help("Mark the commit as a breaking change")
+                .action(ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("no-body")
+                .long("no-body")
+                .help("Only output the commit header, without the body template")
+                .action(ArgAction::SetTrue)
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(text) = matches.get_one::<String>("generate-commit") {
+            let options = CommitOptions {
+                commit_type: matches.get_one::<String>("commit-type").cloned(),
+                scope: matches.get_one::<String>("scope").cloned(),
+                breaking: matches.get_flag("breaking"),
+                body: !matches.get_flag("no-body"),
+            };
+            let message = generate_commit_message(text, &options)?;
+
+            match Clipboard::new() {
+                Ok(mut clipboard) => {
+                    if let Err(e) = clipboard.set_text(&message) {
+                        eprintln!("Warning: Failed to copy to clipboard: {}", e);
+                        println!("{}", message);
+                    } else {
+                        println!("{}", message);
+                        println!("(copied to clipboard)");
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Warning: Failed to access clipboard: {}", e);
+                    println!("{}", message);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct CommitOptions {
+    pub commit_type: Option<String>,
+    pub scope: Option<String>,
+    pub breaking: bool,
+    pub body: bool,
+}
+
+impl Default for CommitOptions {
+    fn default() -> Self {
+        Self {
+            commit_type: None,
+            scope: None,
+            breaking: false,
+            body: true,
+        }
+    }
+}
+
+// Maps the leading verb of a description to a commit type. The verb is
+// dropped from the subject when it just repeats the type ("Fix bug" → "fix: bug").
+fn infer_commit_type(first_word: &str) -> Option<(&'static str, bool)> {
+    let word = first_word.to_lowercase();
+    let inferred = match word.as_str() {
+        "fix" | "fixes" | "fixed" => ("fix", true),
+        "resolve" | "resolves" | "resolved" | "correct" | "corrects" | "corrected" | "patch" | "repair" => ("fix", false),
+        "add" | "adds" | "added" | "implement" | "implements" | "implemented" | "introduce" | "introduces" | "support" | "create" | "allow" | "enable" => ("feat", false),
+        "refactor" | "refactors" | "refactored" => ("refactor", true),
+        "update" | "updates" | "updated" | "rename" | "renames" | "move" | "moves" | "restructure" | "simplify" | "extract" | "clean" | "cleanup" | "remove" | "removes" | "delete" | "drop" => ("refactor", false),
+        "doc" | "docs" | "document" | "documents" | "documented" => ("docs", true),
+        "test" | "tests" | "tested" => ("test", true),
+        "optimize" | "optimise" | "speed" | "improve" => ("perf", false),
+        "perf" => ("perf", true),
+        "bump" | "upgrade" | "downgrade" => ("chore", false),
+        "chore" => ("chore", true),
+        "revert" | "reverts" | "reverted" => ("revert", false),
+        "style" | "format" | "lint" => ("style", false),
+        _ => return None,
+    };
+    Some(inferred)
+}
+
+fn normalize_type(branch_type: &str) -> &str {
+    match branch_type {
+        "feature" => "feat",
+        "bugfix" | "hotfix" => "fix",
+        "release" => "chore",
+        other => other,
+    }
+}
+
+// Cuts text at the last word boundary that fits within max_length characters
+fn truncate_at_word(text: &str, max_length: usize) -> String {
+    if text.chars().count() <= max_length {
+        return text.to_string();
+    }
+    let mut result = String::new();
+    for word in text.split_whitespace() {
+        let candidate_length = result.chars().count() + usize::from(!result.is_empty()) + word.chars().count();
+        if candidate_length > max_length {
+            break;
+        }
+        if !result.is_empty() {
+            result.push(' ');
+        }
+        result.push_str(word);
+    }
+    if result.is_empty() {
+        result = text.chars().take(max_length).collect();
+    }
+    result
+}
+
+pub fn generate_commit_message(input: &str, options: &CommitOptions) -> Result<String, Box<dyn Error>> {
+    let (detected_type, rest) = split_type_prefix(input);
+    let (ticket, rest) = extract_ticket_id(&rest);
+    let mut words: Vec<&str> = rest.split_whitespace().collect();
+
+    let mut commit_type = options.commit_type.clone().or(detected_type.map(|t| normalize_type(t).to_string()));
+    if let Some(first) = words.first() {
+        if let Some((inferred, drop_verb)) = infer_commit_type(first) {
+            if commit_type.is_none() {
+                commit_type = Some(inferred.to_string());
+            }
+            if drop_verb && commit_type.as_deref() == Some(inferred) && words.len() > 1 {
+                words.remove(0);
+            }
+        }
+    }
+    let commit_type = commit_type.unwrap_or_else(|| "chore".to_string());
+
+    let mut subject = words.join(" ").trim_end_matches(['.', '!', ' ']).to_string();
+    if subject.is_empty() {
+        return Err("Commit description cannot be empty".into());
+    }
+    // Conventional subjects start lowercase unless the first word is an acronym
+    let first_word = subject.split_whitespace().next().unwrap_or("");
+    if !first_word.chars().skip(1).any(|c| c.is_uppercase()) {
+        let mut chars = subject.chars();
+        if let Some(first) = chars.next() {
+            subject = first.to_lowercase().collect::<String>() + chars.as_str();
+        }
+    }
+
+    let scope = options.scope.as_deref().map(str::trim).filter(|s| !s.is_empty());
+    let prefix = format!(
+        "{}{}{}: ",
+        commit_type,
+        scope.map(|s| format!("({})", s)).unwrap_or_default(),
+        if options.breaking { "!" } else { "" }
+    );
+    let available = MAX_HEADER_LENGTH.saturating_sub(prefix.chars().count());
+    let header = format!("{}{}", prefix, truncate_at_word(&subject, available));
+
+    if !options.body {
+        return Ok(header);
+    }
+
+    let mut lines = vec![
+        header,
+        String::new(),
+        "# Why is this change needed?".to_string(),
+        String::new(),
+        "# What does it change?".to_string(),
+    ];
+    if options.breaking {
+        lines.push(String::new());
+        lines.push("BREAKING CHANGE: <describe the breaking change>".to_string());
+    }
+    if let Some(ticket) = ticket {
+        let reference = if ticket.chars().all(|c| c.is_ascii_digit()) { format!("#{}", ticket) } else { ticket };
+        lines.push(String::new());
+        lines.push(format!("Refs: {}", reference));
+    }
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(input: &str, options: &CommitOptions) -> String {
+        generate_commit_message(input, options).unwrap().lines().next().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_infers_type_from_verb() {
+        let options = CommitOptions::default();
+        assert_eq!(header("Fix urgent bug in checkout flow", &options), "fix: urgent bug in checkout flow");
+        assert_eq!(header("Add dark mode toggle", &options), "feat: add dark mode toggle");
+        assert_eq!(header("Update README examples.", &options), "refactor: update README examples");
+        assert_eq!(header("Bump serde to 1.0.200", &options), "chore: bump serde to 1.0.200");
+        assert_eq!(header("Something vague", &options), "chore: something vague");
+    }
+
+    #[test]
+    fn test_explicit_type_scope_and_breaking() {
+        let options = CommitOptions {
+            commit_type: Some("feat".to_string()),
+            scope: Some("api".to_string()),
+            breaking: true,
+            body: true,
+        };
+        let message = generate_commit_message("Drop v1 endpoints", &options).unwrap();
+        assert!(message.starts_with("feat(api)!: drop v1 endpoints\n"));
+        assert!(message.contains("BREAKING CHANGE:"));
+    }
+
+    #[test]
+    fn test_type_prefix_in_input() {
+        let options = CommitOptions::default();
+        assert_eq!(header("Feature: user avatars", &options), "feat: user avatars");
+        assert_eq!(header("docs: Document the CLI flags", &options), "docs: the CLI flags");
+    }
+
+    #[test]
+    fn test_ticket_becomes_footer() {
+        let options = CommitOptions::default();
+        let message = generate_commit_message("Fix ABC-123 broken login", &options).unwrap();
+        assert!(message.starts_with("fix: broken login\n"));
+        assert!(message.ends_with("Refs: ABC-123"));
+
+        let message = generate_commit_message("Fix crash on start #42", &options).unwrap();
+        assert!(message.ends_with("Refs: #42"));
+    }
+
+    #[test]
+    fn test_header_length_limit() {
+        let options = CommitOptions { scope: Some("checkout".to_string()), ..Default::default() };
+        let long = "Fix the extremely long and winding description of a bug that goes on and on without end";
+        let header = header(long, &options);
+        assert!(header.chars().count() <= MAX_HEADER_LENGTH);
+        assert!(header.starts_with("fix(checkout): the extremely long"));
+        assert!(!header.ends_with(' '));
+    }
+
+    #[test]
+    fn test_no_body_and_empty_input() {
+        let options = CommitOptions { body: false, ..Default::default() };
+        assert_eq!(generate_commit_message("Fix typo", &options).unwrap(), "fix: typo");
+        assert!(generate_commit_message("   ", &options).is_err());
+    }
+
+    #[test]
+    fn test_truncate_at_word() {
+        assert_eq!(truncate_at_word("short", 10), "short");
+        assert_eq!(truncate_at_word("one two three", 9), "one two");
+        assert_eq!(truncate_at_word("supercalifragilistic", 5), "super");
+    }
+}
@@ -0,0 +1,250 @@
+use crate::file_size::format_bytes;
+use crate::tool_module::ToolModule;
+use chrono::DateTime;
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::Write;
+use std::process::{self, Stdio};
+
+pub struct GitStatsModule;
+
+const TOP_AUTHORS: usize = 10;
+const LARGEST_FILES: usize = 10;
+const BAR_WIDTH: usize = 30;
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+impl ToolModule for GitStatsModule {
+    fn name(&self) -> &'static str {
+        "git-stats"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("git-stats")
+                .long("git-stats")
+                .value_name("PATH")
+                .num_args(0..=1)
+                .default_missing_value(".")
+                .help("Summarize a git repository: commits per author, activity by weekday and hour, largest files, branches")
+                .long_help("Print a health snapshot of the git repository at PATH (default: current directory): commits and authors on the current branch, activity by weekday and hour of day (in each author's own time zone), branch and tag counts, and the largest files ever committed on any branch.")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(path) = matches.get_one::<String>("git-stats") {
+            git(path, &["rev-parse", "--git-dir"], None).map_err(|_| format!("{} is not a git repository", path))?;
+            let log = git(path, &["log", "--format=%aN%x1f%ad%x1f%at", "--date=format:%u %H"], None)
+                .map_err(|_| format!("{} has no commits yet", path))?;
+            let stats = parse_log(&log);
+            let refs = count_refs(&git(path, &["for-each-ref", "--format=%(refname)", "refs/heads", "refs/remotes", "refs/tags"], None)?);
+            let objects = git(path, &["rev-list", "--objects", "--all"], None)?;
+            let sizes = git(path, &["cat-file", "--batch-check=%(objecttype) %(objectsize) %(rest)"], Some(&objects))?;
+            let branch = git(path, &["branch", "--show-current"], None).unwrap_or_default();
+            print!("{}", report(&stats, &refs, &largest_blobs(&sizes, LARGEST_FILES), branch.trim()));
+        }
+        Ok(())
+    }
+}
+
+fn git(path: &str, args: &[&str], stdin: Option<&str>) -> Result<String, String> {
+    let mut child = process::Command::new("git")
+        .arg("-C")
+        .arg(path)
+        .args(args)
+        .stdin(if stdin.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if let Some(input) = stdin {
+        // Written from a thread so git can drain stdout while we feed stdin
+        let mut pipe = child.stdin.take().expect("stdin is piped");
+        let input = input.to_string();
+        std::thread::spawn(move || pipe.write_all(input.as_bytes()));
+    }
+    let output = child.wait_with_output().map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("git {} failed: {}", args[0], String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[derive(Debug, Default)]
+struct LogStats {
+    commits: usize,
+    // Most commits first
+    authors: Vec<(String, usize)>,
+    weekdays: [usize; 7],
+    hours: [usize; 24],
+    first: Option<i64>,
+    last: Option<i64>,
+}
+
+// Lines of "name \x1f weekday(1-7) hour \x1f unix time", as written by the git log call above
+fn parse_log(log: &str) -> LogStats {
+    let mut stats = LogStats::default();
+    let mut authors: HashMap<&str, usize> = HashMap::new();
+    for line in log.lines() {
+        let mut fields = line.split('\x1f');
+        let (Some(author), Some(when), Some(timestamp)) = (fields.next(), fields.next(), fields.next()) else {
+            continue;
+        };
+        stats.commits += 1;
+        *authors.entry(author).or_default() += 1;
+        if let Some((weekday, hour)) = when.split_once(' ') {
+            if let (Ok(weekday), Ok(hour)) = (weekday.parse::<usize>(), hour.parse::<usize>()) {
+                if (1..=7).contains(&weekday) && hour < 24 {
+                    stats.weekdays[weekday - 1] += 1;
+                    stats.hours[hour] += 1;
+                }
+            }
+        }
+        if let Ok(timestamp) = timestamp.parse::<i64>() {
+            stats.first = Some(stats.first.map_or(timestamp, |first| first.min(timestamp)));
+            stats.last = Some(stats.last.map_or(timestamp, |last| last.max(timestamp)));
+        }
+    }
+    stats.authors = authors.into_iter().map(|(name, count)| (name.to_string(), count)).collect();
+    stats.authors.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    stats
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct RefCounts {
+    local: usize,
+    remote: usize,
+    tags: usize,
+}
+
+fn count_refs(refs: &str) -> RefCounts {
+    let mut counts = RefCounts::default();
+    for name in refs.lines() {
+        if name.starts_with("refs/heads/") {
+            counts.local += 1;
+        } else if name.starts_with("refs/remotes/") && !name.ends_with("/HEAD") {
+            counts.remote += 1;
+        } else if name.starts_with("refs/tags/") {
+            counts.tags += 1;
+        }
+    }
+    counts
+}
+
+// From "blob SIZE PATH" lines: the biggest version of each path, largest first
+fn largest_blobs(batch: &str, limit: usize) -> Vec<(String, u64)> {
+    let mut largest: HashMap<&str, u64> = HashMap::new();
+    for line in batch.lines() {
+        let mut fields = line.splitn(3, ' ');
+        let (Some("blob"), Some(size), Some(path)) = (fields.next(), fields.next(), fields.next()) else {
+            continue;
+        };
+        let Ok(size) = size.parse::<u64>() else { continue };
+        let entry = largest.entry(path).or_default();
+        *entry = (*entry).max(size);
+    }
+    let mut blobs: Vec<(String, u64)> = largest.into_iter().map(|(path, size)| (path.to_string(), size)).collect();
+    blobs.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    blobs.truncate(limit);
+    blobs
+}
+
+fn bar(value: usize, max: usize) -> String {
+    if max == 0 {
+        return String::new();
+    }
+    "█".repeat((value * BAR_WIDTH).div_ceil(max))
+}
+
+fn sparkline(values: &[usize]) -> String {
+    let max = values.iter().copied().max().unwrap_or(0);
+    values
+        .iter()
+        .map(|&value| match value {
+            0 => ' ',
+            _ => SPARKS[(value * (SPARKS.len() - 1)).div_ceil(max.max(1))],
+        })
+        .collect()
+}
+
+fn format_date(timestamp: Option<i64>) -> String {
+    timestamp.and_then(|timestamp| DateTime::from_timestamp(timestamp, 0)).map(|date| date.format("%Y-%m-%d").to_string()).unwrap_or_default()
+}
+
+fn report(stats: &LogStats, refs: &RefCounts, largest: &[(String, u64)], branch: &str) -> String {
+    let mut output = String::new();
+    let on_branch = if branch.is_empty() { String::new() } else { format!(" on {}", branch) };
+    let authors = if stats.authors.len() == 1 { "author" } else { "authors" };
+    output.push_str(&format!("{:<14}{} by {} {}{}\n", "Commits:".cyan(), stats.commits, stats.authors.len(), authors, on_branch));
+    output.push_str(&format!("{:<14}{} → {}\n", "History:".cyan(), format_date(stats.first), format_date(stats.last)));
+    output.push_str(&format!("{:<14}{} local, {} remote\n", "Branches:".cyan(), refs.local, refs.remote));
+    output.push_str(&format!("{:<14}{}\n", "Tags:".cyan(), refs.tags));
+
+    output.push_str(&format!("\n{}\n", "Top authors".bold()));
+    let width = stats.authors.iter().take(TOP_AUTHORS).map(|(name, _)| name.chars().count()).max().unwrap_or(0);
+    let top = stats.authors.first().map_or(0, |(_, count)| *count);
+    for (name, count) in stats.authors.iter().take(TOP_AUTHORS) {
+        let share = *count as f64 * 100.0 / stats.commits.max(1) as f64;
+        output.push_str(&format!("  {:<width$}  {:>6}  {:>5.1}%  {}\n", name, count, share, bar(*count, top).green(), width = width));
+    }
+    if stats.authors.len() > TOP_AUTHORS {
+        output.push_str(&format!("  ... and {} more\n", stats.authors.len() - TOP_AUTHORS));
+    }
+
+    output.push_str(&format!("\n{}\n", "Commits by weekday".bold()));
+    let busiest = stats.weekdays.iter().copied().max().unwrap_or(0);
+    for (day, count) in WEEKDAYS.iter().zip(stats.weekdays) {
+        output.push_str(format!("  {}  {:>6}  {}", day, count, bar(count, busiest).blue()).trim_end());
+        output.push('\n');
+    }
+
+    output.push_str(&format!("\n{}\n", "Commits by hour".bold()));
+    output.push_str(&format!("  {}\n", sparkline(&stats.hours).yellow()));
+    output.push_str(&format!("  {}\n", "0     6     12    18   23".dimmed()));
+
+    if !largest.is_empty() {
+        output.push_str(&format!("\n{}\n", "Largest files in history".bold()));
+        for (path, size) in largest {
+            output.push_str(&format!("  {:>9}  {}\n", format_bytes(*size), path));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str = "Jane Doe\x1f1 09\x1f1700000000\nBob\x1f1 10\x1f1690000000\nJane Doe\x1f5 23\x1f1710000000\nmalformed line\n";
+
+    #[test]
+    fn test_parse_log() {
+        let stats = parse_log(LOG);
+        assert_eq!(stats.commits, 3);
+        assert_eq!(stats.authors, [("Jane Doe".to_string(), 2), ("Bob".to_string(), 1)]);
+        assert_eq!(stats.weekdays, [2, 0, 0, 0, 1, 0, 0]);
+        assert_eq!((stats.hours[9], stats.hours[10], stats.hours[23]), (1, 1, 1));
+        assert_eq!((stats.first, stats.last), (Some(1690000000), Some(1710000000)));
+        assert_eq!(format_date(stats.first), "2023-07-22");
+    }
+
+    #[test]
+    fn test_refs_and_largest_blobs() {
+        let refs = count_refs("refs/heads/main\nrefs/heads/dev\nrefs/remotes/origin/HEAD\nrefs/remotes/origin/main\nrefs/tags/v1.0.0\n");
+        assert_eq!(refs, RefCounts { local: 2, remote: 1, tags: 1 });
+        let batch = "commit 250\ntree 120\nblob 10 README.md\nblob 5000 assets/logo.png\nblob 40 README.md\nblob 7 docs/my notes.txt\n";
+        assert_eq!(largest_blobs(batch, 2), [("assets/logo.png".to_string(), 5000), ("README.md".to_string(), 40)]);
+        assert_eq!(largest_blobs(batch, 10).len(), 3);
+    }
+
+    #[test]
+    fn test_sparkline_and_bar() {
+        assert_eq!(sparkline(&[0, 1, 4, 8]), " ▂▅█");
+        assert_eq!(sparkline(&[0, 0]), "  ");
+        assert_eq!(bar(15, 30).chars().count(), 15);
+        assert_eq!(bar(1, 1000).chars().count(), 1);
+        assert_eq!(bar(0, 0), "");
+    }
+}
@@ -0,0 +1,174 @@
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use colored::*;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+mod templates;
+
+use templates::{Template, TEMPLATES};
+
+pub struct GitignoreGenModule;
+
+impl ToolModule for GitignoreGenModule {
+    fn name(&self) -> &'static str {
+        "gitignore-gen"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("gitignore")
+                .long("gitignore")
+                .value_name("TEMPLATES")
+                .num_args(0..=1)
+                .default_missing_value("")
+                .help("Print a .gitignore combined from built-in templates, e.g. rust,node,macos; with --tree, hide ignored files")
+                .long_help("Combine built-in .gitignore templates (comma-separated, e.g. rust,node,macos) into one file, dropping patterns repeated across templates. Prints to stdout unless --append is given. See --gitignore --list for the templates and their aliases. Given without templates alongside --tree, it instead hides the files .gitignore ignores from the tree.")
+        )
+        .arg(
+            Arg::new("list")
+                .long("list")
+                .help("List the available --gitignore templates or --license texts")
+                .action(ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("append")
+                .long("append")
+                .help("Append the --gitignore templates to ./.gitignore, skipping patterns it already has")
+                .action(ArgAction::SetTrue)
+                .requires("gitignore")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        // A bare --gitignore next to --tree is a filter for the tree, not a template request
+        if let Some(names) = matches.get_one::<String>("gitignore").filter(|_| !matches.contains_id("tree")) {
+            if matches.get_flag("list") {
+                for template in TEMPLATES {
+                    let aliases = if template.aliases.is_empty() { String::new() } else { format!(" (also {})", template.aliases.join(", ")) };
+                    println!("  {:<11}{}{}", template.name.green(), template.description, aliases.dimmed());
+                }
+                return Ok(());
+            }
+            let templates = resolve(names)?;
+            if matches.get_flag("append") {
+                let path = Path::new(".gitignore");
+                let existing = if path.exists() { fs::read_to_string(path)? } else { String::new() };
+                let addition = combine(&templates, &existing);
+                let names = templates.iter().map(|template| template.name).collect::<Vec<_>>().join(", ");
+                if addition.added == 0 {
+                    println!("✅ .gitignore already covers {}", names);
+                    return Ok(());
+                }
+                let separator = if existing.is_empty() { "" } else if existing.ends_with('\n') { "\n" } else { "\n\n" };
+                fs::write(path, format!("{}{}{}", existing, separator, addition.text))?;
+                println!("✅ Added {} patterns for {} to .gitignore", addition.added, names);
+            } else {
+                print!("{}", combine(&templates, "").text);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn find(name: &str) -> Option<&'static Template> {
+    let name = name.trim().to_lowercase();
+    TEMPLATES.iter().find(|template| template.name == name || template.aliases.contains(&name.as_str()))
+}
+
+// "rust, Node,osx" -> [rust, node, macos] in the order given, without repeats
+fn resolve(names: &str) -> Result<Vec<&'static Template>, String> {
+    let mut templates: Vec<&'static Template> = Vec::new();
+    for name in names.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        let template = find(name).ok_or_else(|| {
+            let known = TEMPLATES.iter().map(|template| template.name).collect::<Vec<_>>().join(", ");
+            format!("Unknown template '{}'. Available: {}", name, known)
+        })?;
+        if !templates.iter().any(|known| known.name == template.name) {
+            templates.push(template);
+        }
+    }
+    if templates.is_empty() {
+        return Err("Name one or more templates, e.g. --gitignore rust,macos (see --gitignore --list)".to_string());
+    }
+    Ok(templates)
+}
+
+struct Combined {
+    text: String,
+    // Patterns written, not counting comments
+    added: usize,
+}
+
+// One "### Name ###" section per template. Patterns already in `existing` or in an
+// earlier section are left out, as are sections left with nothing to add.
+fn combine(templates: &[&Template], existing: &str) -> Combined {
+    let mut seen: HashSet<&str> = existing.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')).collect();
+    let mut sections = Vec::new();
+    let mut added = 0;
+    for template in templates {
+        let mut lines = Vec::new();
+        let mut patterns = 0;
+        for line in template.body.lines() {
+            if line.starts_with('#') {
+                lines.push(line);
+            } else if seen.insert(line) {
+                lines.push(line);
+                patterns += 1;
+            }
+        }
+        if patterns == 0 {
+            continue;
+        }
+        // Drop comments that no longer head any pattern
+        let mut kept: Vec<&str> = Vec::new();
+        for (index, line) in lines.iter().enumerate() {
+            let heads_pattern = lines.get(index + 1).is_some_and(|next| !next.starts_with('#'));
+            if !line.starts_with('#') || heads_pattern {
+                kept.push(line);
+            }
+        }
+        sections.push(format!("### {} ###\n{}\n", template.title, kept.join("\n")));
+        added += patterns;
+    }
+    Combined { text: sections.join("\n"), added }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let names: Vec<&str> = resolve("rust, Node,osx,nodejs").unwrap().iter().map(|template| template.name).collect();
+        assert_eq!(names, ["rust", "node", "macos"]);
+        assert!(resolve("rust,cobol").err().unwrap().starts_with("Unknown template 'cobol'"));
+        assert!(resolve(" , ").is_err());
+    }
+
+    #[test]
+    fn test_combine_drops_repeated_patterns() {
+        let combined = combine(&resolve("node,python").unwrap(), "");
+        assert!(combined.text.starts_with("### Node ###\n# Dependencies\nnode_modules/\n"));
+        assert!(combined.text.contains("\n### Python ###\n# Bytecode\n__pycache__/\n"));
+        // build/, dist/ and .env come from the node section only
+        assert_eq!(combined.text.matches("\nbuild/\n").count(), 1);
+        assert_eq!(combined.text.matches("\n.env\n").count(), 1);
+        assert!(!combined.text.contains("# Environment\n### Python"));
+        assert!(!combined.text.ends_with("# Environment\n"));
+    }
+
+    #[test]
+    fn test_combine_against_existing_file() {
+        let macos = resolve("macos").unwrap();
+        let everything: String = find("macos").unwrap().body.lines().filter(|line| !line.starts_with('#')).map(|line| format!("{}\n", line)).collect();
+        assert_eq!(combine(&macos, &everything).added, 0);
+        let partial = combine(&resolve("rust,macos").unwrap(), "/target/\n.DS_Store\n");
+        assert!(!partial.text.contains("/target/"));
+        assert!(!partial.text.contains(".DS_Store"));
+        assert!(partial.text.contains("**/*.rs.bk"));
+        assert!(partial.text.starts_with("### Rust ###\n# Build output\ndebug/\n"));
+    }
+}
@@ -0,0 +1,325 @@
+// Condensed from github/gitignore
+pub struct Template {
+    pub name: &'static str,
+    // Section heading in the generated file
+    pub title: &'static str,
+    pub aliases: &'static [&'static str],
+    pub description: &'static str,
+    pub body: &'static str,
+}
+
+pub const TEMPLATES: &[Template] = &[
+    Template {
+        name: "rust",
+        title: "Rust",
+        aliases: &["cargo"],
+        description: "Cargo build output",
+        body: "\
+# Build output
+/target/
+debug/
+**/*.rs.bk
+*.pdb",
+    },
+    Template {
+        name: "node",
+        title: "Node",
+        aliases: &["nodejs", "javascript", "js", "typescript", "ts"],
+        description: "npm, yarn and pnpm dependencies, logs and build output",
+        body: "\
+# Dependencies
+node_modules/
+jspm_packages/
+.pnp.*
+.yarn/*
+!.yarn/patches
+!.yarn/plugins
+!.yarn/releases
+!.yarn/versions
+# Logs
+logs/
+*.log
+npm-debug.log*
+yarn-debug.log*
+yarn-error.log*
+pnpm-debug.log*
+# Build output and caches
+dist/
+build/
+coverage/
+.nyc_output/
+.next/
+.nuxt/
+.cache/
+.parcel-cache/
+.turbo/
+*.tsbuildinfo
+.eslintcache
+# Environment
+.env
+.env.*
+!.env.example",
+    },
+    Template {
+        name: "python",
+        title: "Python",
+        aliases: &["py"],
+        description: "Bytecode, virtualenvs, packaging and tool caches",
+        body: "\
+# Bytecode
+__pycache__/
+*.py[cod]
+*$py.class
+# Packaging
+build/
+dist/
+*.egg-info/
+.eggs/
+wheels/
+# Virtual environments
+.venv/
+venv/
+env/
+# Tool caches
+.pytest_cache/
+.mypy_cache/
+.ruff_cache/
+.tox/
+.coverage
+htmlcov/
+.ipynb_checkpoints/
+# Environment
+.env",
+    },
+    Template {
+        name: "go",
+        title: "Go",
+        aliases: &["golang"],
+        description: "Binaries, test output and vendored modules",
+        body: "\
+# Binaries
+*.exe
+*.exe~
+*.dll
+*.so
+*.dylib
+# Test output
+*.test
+*.out
+coverage.*
+# Workspaces
+go.work
+go.work.sum
+vendor/",
+    },
+    Template {
+        name: "java",
+        title: "Java",
+        aliases: &["maven", "gradle", "kotlin"],
+        description: "Class files, archives, Maven and Gradle output",
+        body: "\
+# Compiled classes and archives
+*.class
+*.jar
+*.war
+*.ear
+hs_err_pid*
+# Maven
+target/
+# Gradle
+.gradle/
+build/
+!gradle/wrapper/gradle-wrapper.jar
+# Kotlin
+.kotlin/",
+    },
+    Template {
+        name: "dotnet",
+        title: ".NET",
+        aliases: &["csharp", "visualstudio"],
+        description: "bin/obj output, NuGet packages and Visual Studio state",
+        body: "\
+# Build output
+[Bb]in/
+[Oo]bj/
+[Dd]ebug/
+[Rr]elease/
+*.user
+*.suo
+# NuGet
+*.nupkg
+packages/
+# Visual Studio
+.vs/
+TestResults/",
+    },
+    Template {
+        name: "ruby",
+        title: "Ruby",
+        aliases: &["rails"],
+        description: "Bundler, gems and Rails runtime files",
+        body: "\
+# Bundler and gems
+.bundle/
+vendor/bundle/
+*.gem
+# Rails
+log/*
+tmp/*
+storage/*
+public/assets/
+.byebug_history
+# Environment
+.env",
+    },
+    Template {
+        name: "c",
+        title: "C/C++",
+        aliases: &["cpp", "c++", "cmake"],
+        description: "Object files, libraries, executables and CMake output",
+        body: "\
+# Object files and libraries
+*.o
+*.obj
+*.a
+*.lib
+*.so
+*.dylib
+*.dll
+# Executables
+*.exe
+*.out
+*.app
+# CMake
+CMakeFiles/
+CMakeCache.txt
+cmake-build-*/
+compile_commands.json",
+    },
+    Template {
+        name: "swift",
+        title: "Swift",
+        aliases: &["xcode", "ios"],
+        description: "Xcode user data, SwiftPM and CocoaPods",
+        body: "\
+# Xcode
+xcuserdata/
+DerivedData/
+*.xcscmblueprint
+*.xccheckout
+# Swift Package Manager
+.build/
+.swiftpm/
+# CocoaPods and Carthage
+Pods/
+Carthage/Build/",
+    },
+    Template {
+        name: "terraform",
+        title: "Terraform",
+        aliases: &["tf"],
+        description: "Provider cache, state files and local overrides",
+        body: "\
+# Provider cache
+.terraform/
+# State, which can contain secrets
+*.tfstate
+*.tfstate.*
+crash.log
+crash.*.log
+# Variable files and local overrides
+*.tfvars
+*.tfvars.json
+override.tf
+override.tf.json
+*_override.tf
+*_override.tf.json
+.terraformrc
+terraform.rc",
+    },
+    Template {
+        name: "macos",
+        title: "macOS",
+        aliases: &["osx", "mac"],
+        description: "Finder metadata and system files",
+        body: "\
+.DS_Store
+.AppleDouble
+.LSOverride
+._*
+.Spotlight-V100
+.Trashes
+.fseventsd
+.DocumentRevisions-V100
+.TemporaryItems",
+    },
+    Template {
+        name: "windows",
+        title: "Windows",
+        aliases: &["win"],
+        description: "Thumbnail caches, desktop.ini and shortcuts",
+        body: "\
+Thumbs.db
+Thumbs.db:encryptable
+ehthumbs.db
+ehthumbs_vista.db
+[Dd]esktop.ini
+$RECYCLE.BIN/
+*.lnk
+*.stackdump",
+    },
+    Template {
+        name: "linux",
+        title: "Linux",
+        aliases: &[],
+        description: "Editor backups and temporary files",
+        body: "\
+*~
+.fuse_hidden*
+.directory
+.Trash-*
+.nfs*",
+    },
+    Template {
+        name: "vscode",
+        title: "VS Code",
+        aliases: &["visualstudiocode", "code"],
+        description: "VS Code settings, keeping shared ones",
+        body: "\
+.vscode/*
+!.vscode/settings.json
+!.vscode/tasks.json
+!.vscode/launch.json
+!.vscode/extensions.json
+*.code-workspace
+.history/",
+    },
+    Template {
+        name: "jetbrains",
+        title: "JetBrains",
+        aliases: &["idea", "intellij", "pycharm", "webstorm", "goland", "rustrover"],
+        description: "IntelliJ-based IDE project files",
+        body: "\
+.idea/
+*.iml
+*.ipr
+*.iws
+out/",
+    },
+    Template {
+        name: "vim",
+        title: "Vim",
+        aliases: &["neovim"],
+        description: "Swap, undo and session files",
+        body: "\
+[._]*.s[a-v][a-z]
+[._]*.sw[a-p]
+[._]s[a-rt-v][a-z]
+[._]ss[a-gi-z]
+[._]sw[a-p]
+Session.vim
+Sessionx.vim
+.netrwhist
+tags",
+    },
+];
@@ -0,0 +1,150 @@
+// GraphQL tokens as written in the source; commas and whitespace carry no meaning and are dropped
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    Punctuator(&'static str),
+    Name(String),
+    Number(String),
+    // Quotes and escapes included, block strings too
+    Str(String),
+    // Including the leading #
+    Comment(String),
+}
+
+impl Token {
+    pub fn text(&self) -> &str {
+        match self {
+            Token::Punctuator(symbol) => symbol,
+            Token::Name(text) | Token::Number(text) | Token::Str(text) | Token::Comment(text) => text,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lexed {
+    pub token: Token,
+    // 1-based line the token starts on
+    pub line: usize,
+}
+
+const PUNCTUATORS: [&str; 14] = ["...", "!", "$", "&", "(", ")", ":", "=", "@", "[", "]", "{", "|", "}"];
+
+pub fn tokenize(source: &str) -> Result<Vec<Lexed>, String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        let token = match c {
+            '\n' => {
+                line += 1;
+                i += 1;
+                continue;
+            }
+            c if c.is_whitespace() || c == ',' || c == '\u{FEFF}' => {
+                i += 1;
+                continue;
+            }
+            '#' => {
+                while i < chars.len() && chars[i] != '\n' && chars[i] != '\r' {
+                    i += 1;
+                }
+                Token::Comment(chars[start..i].iter().collect::<String>().trim_end().to_string())
+            }
+            '"' if chars[i..].starts_with(&['"', '"', '"']) => {
+                i += 3;
+                let start_line = line;
+                loop {
+                    match chars.get(i) {
+                        None => return Err(format!("Unterminated block string starting on line {}", start_line)),
+                        Some('\\') if chars[i + 1..].starts_with(&['"', '"', '"']) => i += 4,
+                        Some('"') if chars[i..].starts_with(&['"', '"', '"']) => {
+                            i += 3;
+                            break;
+                        }
+                        Some(c) => {
+                            if *c == '\n' {
+                                line += 1;
+                            }
+                            i += 1;
+                        }
+                    }
+                }
+                tokens.push(Lexed { token: Token::Str(chars[start..i].iter().collect()), line: start_line });
+                continue;
+            }
+            '"' => {
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None | Some('\n') | Some('\r') => return Err(format!("Unterminated string on line {}", line)),
+                        Some('\\') => i += 2,
+                        Some('"') => {
+                            i += 1;
+                            break;
+                        }
+                        Some(_) => i += 1,
+                    }
+                }
+                Token::Str(chars[start..i].iter().collect())
+            }
+            c if c == '_' || c.is_ascii_alphabetic() => {
+                while i < chars.len() && (chars[i] == '_' || chars[i].is_ascii_alphanumeric()) {
+                    i += 1;
+                }
+                Token::Name(chars[start..i].iter().collect())
+            }
+            c if c == '-' || c.is_ascii_digit() => {
+                i += 1;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || matches!(chars[i], '.' | '+' | '-')) {
+                    // A sign only belongs to the number right after an exponent
+                    if matches!(chars[i], '+' | '-') && !matches!(chars[i - 1], 'e' | 'E') {
+                        break;
+                    }
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                if text.parse::<f64>().is_err() {
+                    return Err(format!("Invalid number '{}' on line {}", text, line));
+                }
+                Token::Number(text)
+            }
+            _ => {
+                let symbol = PUNCTUATORS
+                    .iter()
+                    .find(|symbol| chars[i..].iter().take(symbol.len()).copied().eq(symbol.chars()))
+                    .ok_or_else(|| format!("Unexpected character '{}' on line {}", c, line))?;
+                i += symbol.len();
+                Token::Punctuator(symbol)
+            }
+        };
+        tokens.push(Lexed { token, line });
+    }
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        let tokens = tokenize("query {\n  a(x: -1.5e3, y: \"q\\\"\") # note\n  ...F\n}").unwrap();
+        let texts: Vec<&str> = tokens.iter().map(|lexed| lexed.token.text()).collect();
+        assert_eq!(texts, ["query", "{", "a", "(", "x", ":", "-1.5e3", "y", ":", "\"q\\\"\"", ")", "# note", "...", "F", "}"]);
+        assert_eq!(tokens[11].line, 2);
+        assert_eq!(tokens[12].line, 3);
+    }
+
+    #[test]
+    fn test_tokenize_errors() {
+        assert_eq!(tokenize("{ a(x: \"open) }").unwrap_err(), "Unterminated string on line 1");
+        assert_eq!(tokenize("\n{ a % b }").unwrap_err(), "Unexpected character '%' on line 2");
+        assert!(tokenize("\"\"\"never closed").is_err());
+        assert!(tokenize("{ a(x: 1.2.3) }").is_err());
+        let block = tokenize("\"\"\"\nMulti \\\"\"\" line\n\"\"\" type A").unwrap();
+        assert_eq!(block[1].line, 3);
+    }
+}
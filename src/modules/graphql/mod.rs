@@ -0,0 +1,527 @@
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use colored::*;
+use reqwest::blocking::Client;
+use reqwest::header::{HeaderName, HeaderValue, CONTENT_TYPE};
+use serde::Deserialize;
+use std::error::Error;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::time::Duration;
+
+mod lexer;
+use lexer::{tokenize, Lexed, Token};
+
+pub struct GraphqlModule;
+
+// Keywords that start a new top-level definition, in queries and in schema files
+const DEFINITION_KEYWORDS: [&str; 13] = [
+    "query", "mutation", "subscription", "fragment", "type", "interface", "union", "enum", "input", "scalar", "schema", "directive", "extend",
+];
+const BUILT_IN_SCALARS: [&str; 5] = ["String", "Int", "Float", "Boolean", "ID"];
+
+const INTROSPECTION_QUERY: &str = "query IntrospectionQuery {
+  __schema {
+    queryType { name }
+    mutationType { name }
+    subscriptionType { name }
+    types {
+      kind
+      name
+      fields(includeDeprecated: true) {
+        name
+        args { name type { ...TypeRef } defaultValue }
+        type { ...TypeRef }
+        isDeprecated
+      }
+      inputFields { name type { ...TypeRef } defaultValue }
+      enumValues(includeDeprecated: true) { name }
+      possibleTypes { name }
+    }
+  }
+}
+
+fragment TypeRef on __Type {
+  kind name ofType { kind name ofType { kind name ofType { kind name ofType { kind name ofType { kind name } } } } }
+}";
+
+#[derive(Deserialize)]
+struct IntrospectionResponse {
+    data: Option<Introspection>,
+    #[serde(default)]
+    errors: Vec<GraphqlError>,
+}
+
+#[derive(Deserialize)]
+struct GraphqlError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct Introspection {
+    #[serde(rename = "__schema")]
+    schema: Schema,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Schema {
+    query_type: Option<Named>,
+    mutation_type: Option<Named>,
+    subscription_type: Option<Named>,
+    types: Vec<FullType>,
+}
+
+#[derive(Deserialize)]
+struct Named {
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FullType {
+    kind: String,
+    name: String,
+    fields: Option<Vec<Field>>,
+    input_fields: Option<Vec<InputValue>>,
+    enum_values: Option<Vec<Named>>,
+    possible_types: Option<Vec<Named>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Field {
+    name: String,
+    #[serde(default)]
+    args: Vec<InputValue>,
+    #[serde(rename = "type")]
+    field_type: TypeRef,
+    #[serde(default)]
+    is_deprecated: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InputValue {
+    name: String,
+    #[serde(rename = "type")]
+    value_type: TypeRef,
+    default_value: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TypeRef {
+    kind: String,
+    name: Option<String>,
+    of_type: Option<Box<TypeRef>>,
+}
+
+impl ToolModule for GraphqlModule {
+    fn name(&self) -> &'static str {
+        "graphql"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("graphql-format")
+                .long("graphql-format")
+                .value_name("QUERY")
+                .help("Pretty-print a GraphQL query or schema (text, a file, or - for stdin)")
+                .long_help("Pretty-print a GraphQL document: queries, mutations, fragments or schema definitions. Selections go one per line with two-space indents, arguments stay inline, and comments are kept. Add --minify to squeeze it onto one line instead, dropping comments and optional commas. QUERY can be the text itself, a file path, or - for stdin.")
+        )
+        .arg(
+            Arg::new("minify")
+                .long("minify")
+                .help("Minify the --graphql-format document instead of pretty-printing it")
+                .action(ArgAction::SetTrue)
+                .requires("graphql-format")
+        )
+        .arg(
+            Arg::new("graphql-introspect")
+                .long("graphql-introspect")
+                .value_name("URL")
+                .help("Fetch a GraphQL endpoint's schema and summarize its queries, mutations and types")
+                .long_help("Send the standard introspection query to a GraphQL endpoint and print a compact summary: every query, mutation and subscription with its arguments and return type, then each type with its fields, enum values or union members. Instead of a URL you can pass a file holding a saved introspection result (JSON). Use --graphql-header for authentication.")
+        )
+        .arg(
+            Arg::new("graphql-header")
+                .long("graphql-header")
+                .value_name("NAME: VALUE")
+                .help("Add a request header to --graphql-introspect, e.g. 'Authorization: Bearer TOKEN' (repeatable)")
+                .action(ArgAction::Append)
+                .requires("graphql-introspect")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(value) = matches.get_one::<String>("graphql-format") {
+            let document = read_document(value)?;
+            let tokens = tokenize(&document)?;
+            if matches.get_flag("minify") {
+                println!("{}", minify(&tokens)?);
+            } else {
+                println!("{}", pretty(&tokens)?);
+            }
+        }
+        if let Some(target) = matches.get_one::<String>("graphql-introspect") {
+            let body = if Path::new(target).is_file() {
+                fs::read_to_string(target)?
+            } else {
+                let headers: Vec<&String> = matches.get_many::<String>("graphql-header").into_iter().flatten().collect();
+                fetch_schema(target, &headers)?
+            };
+            let schema = parse_introspection(&body)?;
+            print_summary(&schema);
+        }
+        Ok(())
+    }
+}
+
+// The text itself, a file, or "-" for stdin
+fn read_document(value: &str) -> Result<String, Box<dyn Error>> {
+    if value == "-" {
+        let mut buffer = String::new();
+        io::stdin().read_to_string(&mut buffer)?;
+        return Ok(buffer);
+    }
+    if Path::new(value).is_file() {
+        return Ok(fs::read_to_string(value)?);
+    }
+    Ok(value.to_string())
+}
+
+fn is_punctuator(token: Option<&Token>, symbols: &[&str]) -> bool {
+    matches!(token, Some(Token::Punctuator(symbol)) if symbols.contains(symbol))
+}
+
+fn is_name(token: Option<&Token>, name: &str) -> bool {
+    matches!(token, Some(Token::Name(text)) if text == name)
+}
+
+// Only names and numbers need a space between them
+fn minify(tokens: &[Lexed]) -> Result<String, String> {
+    check_brackets(tokens)?;
+    let mut out = String::new();
+    let mut previous: Option<&Token> = None;
+    for lexed in tokens.iter().filter(|lexed| !matches!(lexed.token, Token::Comment(_))) {
+        let word = |token: &Token| matches!(token, Token::Name(_) | Token::Number(_));
+        if previous.is_some_and(word) && word(&lexed.token) {
+            out.push(' ');
+        }
+        out.push_str(lexed.token.text());
+        previous = Some(&lexed.token);
+    }
+    Ok(out)
+}
+
+fn check_brackets(tokens: &[Lexed]) -> Result<(), String> {
+    let mut open: Vec<(&str, usize)> = Vec::new();
+    for lexed in tokens {
+        match lexed.token {
+            Token::Punctuator(symbol @ ("{" | "(" | "[")) => open.push((symbol, lexed.line)),
+            Token::Punctuator(symbol @ ("}" | ")" | "]")) => {
+                let expected = match open.pop() {
+                    Some(("{", _)) => "}",
+                    Some(("(", _)) => ")",
+                    Some(_) => "]",
+                    None => return Err(format!("Unexpected '{}' on line {}", symbol, lexed.line)),
+                };
+                if symbol != expected {
+                    return Err(format!("Expected '{}' but found '{}' on line {}", expected, symbol, lexed.line));
+                }
+            }
+            _ => {}
+        }
+    }
+    match open.last() {
+        Some((symbol, line)) => Err(format!("'{}' on line {} is never closed", symbol, line)),
+        None => Ok(()),
+    }
+}
+
+enum Separator {
+    None,
+    Space,
+    Comma,
+    Newline,
+    BlankLine,
+}
+
+// Starts a fresh (or blank-line separated) indented line, unless the output is already on one
+fn newline(out: &mut String, indent: usize, blank: bool) {
+    out.truncate(out.trim_end_matches(' ').len());
+    if !out.is_empty() {
+        let wanted: usize = if blank { 2 } else { 1 };
+        let present = out.len() - out.trim_end_matches('\n').len();
+        out.push_str(&"\n".repeat(wanted.saturating_sub(present)));
+    }
+    out.push_str(&"  ".repeat(indent));
+}
+
+// Whether a space goes between two tokens on the same line
+fn needs_space(previous: Option<&Token>, token: &Token) -> bool {
+    if previous.is_none() || is_punctuator(Some(token), &[")", "]", ":", "!", "("]) {
+        return false;
+    }
+    if is_punctuator(previous, &["..."]) {
+        return is_name(Some(token), "on");
+    }
+    !is_punctuator(previous, &["(", "[", "$", "@"])
+}
+
+// Selections and schema fields go one per line, arguments and values stay inline
+fn pretty(tokens: &[Lexed]) -> Result<String, String> {
+    check_brackets(tokens)?;
+    let mut out = String::new();
+    // '{' for selection sets and type bodies, '(' and '[' for arguments and lists, 'o' for input objects
+    let mut stack: Vec<char> = Vec::new();
+    let mut previous: Option<&Lexed> = None;
+    let mut before_previous: Option<&Token> = None;
+    let mut after_comment = false;
+    for lexed in tokens {
+        let token = &lexed.token;
+        let prev = previous.map(|previous| &previous.token);
+        let context = stack.last().copied();
+        if let Token::Comment(text) = token {
+            match previous {
+                Some(previous) if !after_comment && previous.line == lexed.line => out.push_str("  "),
+                Some(_) if context.is_none() && !after_comment => newline(&mut out, stack.len(), true),
+                _ => newline(&mut out, stack.len(), false),
+            }
+            out.push_str(text);
+            after_comment = true;
+            continue;
+        }
+        let starts_value = matches!(token, Token::Name(_) | Token::Number(_) | Token::Str(_)) || is_punctuator(Some(token), &["$", "[", "{"]);
+        let ends_value = matches!(prev, Some(Token::Name(_) | Token::Number(_) | Token::Str(_))) || is_punctuator(prev, &[")", "]", "}", "!"]);
+        let starts_definition = match token {
+            Token::Name(name) => DEFINITION_KEYWORDS.contains(&name.as_str()),
+            Token::Str(_) => true,
+            Token::Punctuator(symbol) => *symbol == "{" && is_punctuator(prev, &["}"]),
+            _ => false,
+        };
+        let starts_selection = matches!(token, Token::Name(_) | Token::Str(_)) || is_punctuator(Some(token), &["..."]);
+        let separator = match context {
+            _ if after_comment => Separator::Newline,
+            _ if prev.is_none() => Separator::None,
+            None if starts_definition => {
+                if matches!(prev, Some(Token::Str(_))) {
+                    Separator::Newline
+                } else if is_name(prev, "extend") {
+                    Separator::Space
+                } else {
+                    Separator::BlankLine
+                }
+            }
+            Some('{') if starts_selection
+                && !is_punctuator(prev, &["{", ":", "@", "...", "=", "|", "&", "$"])
+                && !(is_name(prev, "on") && is_punctuator(before_previous, &["..."])) =>
+            {
+                Separator::Newline
+            }
+            Some('(' | '[' | 'o') if starts_value && ends_value => Separator::Comma,
+            _ => Separator::Space,
+        };
+        let indent = stack.len();
+        match separator {
+            Separator::None => {}
+            Separator::Newline => newline(&mut out, indent, false),
+            Separator::BlankLine => newline(&mut out, indent, true),
+            Separator::Comma => out.push_str(", "),
+            Separator::Space => {
+                if needs_space(prev, token) && !out.ends_with(' ') && !out.ends_with('\n') {
+                    out.push(' ');
+                }
+            }
+        }
+        match token {
+            Token::Punctuator("{") if matches!(context, Some('(' | '[' | 'o')) => {
+                stack.push('o');
+                out.push_str("{ ");
+            }
+            Token::Punctuator("{") => {
+                if !out.is_empty() && !out.ends_with(' ') && !out.ends_with('\n') {
+                    out.push(' ');
+                }
+                out.push('{');
+                stack.push('{');
+                newline(&mut out, stack.len(), false);
+            }
+            Token::Punctuator("}") => {
+                if stack.pop() == Some('o') {
+                    out.truncate(out.trim_end_matches(' ').len());
+                    out.push_str(if out.ends_with('{') { "}" } else { " }" });
+                } else {
+                    newline(&mut out, stack.len(), false);
+                    out.push('}');
+                }
+            }
+            Token::Punctuator(symbol @ ("(" | "[")) => {
+                stack.push(if *symbol == "(" { '(' } else { '[' });
+                out.push_str(symbol);
+            }
+            Token::Punctuator(symbol @ (")" | "]")) => {
+                stack.pop();
+                out.push_str(symbol);
+            }
+            token => out.push_str(token.text()),
+        }
+        before_previous = prev;
+        previous = Some(lexed);
+        after_comment = false;
+    }
+    Ok(out.trim_end().to_string())
+}
+
+fn fetch_schema(url: &str, headers: &[&String]) -> Result<String, Box<dyn Error>> {
+    let client = Client::builder()
+        .user_agent(concat!("micro-swiss/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(30))
+        .build()?;
+    let mut request = client
+        .post(url)
+        .header(CONTENT_TYPE, "application/json")
+        .body(serde_json::json!({ "query": INTROSPECTION_QUERY }).to_string());
+    for header in headers {
+        let (name, value) = header.split_once(':').ok_or_else(|| format!("Invalid header '{}'. Use the form 'Name: value'", header))?;
+        let name = HeaderName::from_bytes(name.trim().as_bytes()).map_err(|_| format!("Invalid header name '{}'", name.trim()))?;
+        let value = HeaderValue::from_str(value.trim()).map_err(|_| format!("Invalid value for header '{}'", name))?;
+        request = request.header(name, value);
+    }
+    let response = request.send().map_err(|e| format!("Request to {} failed: {}", url, e))?;
+    let status = response.status();
+    let body = response.text()?;
+    // GraphQL servers often explain failures in a JSON body, even with an error status
+    if !status.is_success() && !body.trim_start().starts_with('{') {
+        return Err(format!("{} returned HTTP {}", url, status).into());
+    }
+    Ok(body)
+}
+
+// A server response, or a saved result with or without the "data" wrapper
+fn parse_introspection(body: &str) -> Result<Schema, String> {
+    if let Ok(introspection) = serde_json::from_str::<Introspection>(body) {
+        return Ok(introspection.schema);
+    }
+    let response: IntrospectionResponse = serde_json::from_str(body).map_err(|e| format!("Not an introspection result: {}", e))?;
+    if let Some(error) = response.errors.first() {
+        return Err(format!("The server refused introspection: {}", error.message));
+    }
+    response.data.map(|data| data.schema).ok_or_else(|| "The response has no schema data".to_string())
+}
+
+// "[User!]!" from the nested NON_NULL/LIST wrappers
+fn type_name(type_ref: &TypeRef) -> String {
+    match (type_ref.kind.as_str(), &type_ref.of_type) {
+        ("NON_NULL", Some(inner)) => format!("{}!", type_name(inner)),
+        ("LIST", Some(inner)) => format!("[{}]", type_name(inner)),
+        _ => type_ref.name.clone().unwrap_or_else(|| "?".to_string()),
+    }
+}
+
+fn field_signature(field: &Field) -> String {
+    let args: Vec<String> = field
+        .args
+        .iter()
+        .map(|arg| match &arg.default_value {
+            Some(default) => format!("{}: {} = {}", arg.name, type_name(&arg.value_type), default),
+            None => format!("{}: {}", arg.name, type_name(&arg.value_type)),
+        })
+        .collect();
+    let args = if args.is_empty() { String::new() } else { format!("({})", args.join(", ")) };
+    format!("{}{}: {}", field.name, args, type_name(&field.field_type))
+}
+
+// Members of a type in one line: fields, input fields, enum values or union members
+fn type_members(full_type: &FullType) -> String {
+    let names: Vec<&str> = match full_type.kind.as_str() {
+        "INPUT_OBJECT" => full_type.input_fields.iter().flatten().map(|field| field.name.as_str()).collect(),
+        "ENUM" => full_type.enum_values.iter().flatten().map(|value| value.name.as_str()).collect(),
+        "UNION" => return full_type.possible_types.iter().flatten().map(|member| member.name.as_str()).collect::<Vec<_>>().join(" | "),
+        _ => full_type.fields.iter().flatten().map(|field| field.name.as_str()).collect(),
+    };
+    names.join(", ")
+}
+
+fn print_summary(schema: &Schema) {
+    let types: Vec<&FullType> = schema
+        .types
+        .iter()
+        .filter(|full_type| !full_type.name.starts_with("__") && !BUILT_IN_SCALARS.contains(&full_type.name.as_str()))
+        .collect();
+    let roots = [("Queries", &schema.query_type), ("Mutations", &schema.mutation_type), ("Subscriptions", &schema.subscription_type)];
+    let root_names: Vec<&str> = roots.iter().filter_map(|(_, root)| root.as_ref().map(|root| root.name.as_str())).collect();
+    for (label, root) in roots {
+        let Some(root_type) = root.as_ref().and_then(|root| types.iter().find(|full_type| full_type.name == root.name)) else {
+            continue;
+        };
+        let fields = root_type.fields.as_deref().unwrap_or_default();
+        println!("{} ({})", label.cyan().bold(), fields.len());
+        for field in fields {
+            let deprecated = if field.is_deprecated { " (deprecated)".dimmed().to_string() } else { String::new() };
+            println!("  {}{}", field_signature(field), deprecated);
+        }
+        println!();
+    }
+    let others: Vec<&&FullType> = types.iter().filter(|full_type| !root_names.contains(&full_type.name.as_str())).collect();
+    println!("{} ({})", "Types".cyan().bold(), others.len());
+    let width = others.iter().map(|full_type| full_type.name.len()).max().unwrap_or(0);
+    for full_type in others {
+        let kind = full_type.kind.to_lowercase().replace("input_object", "input");
+        println!("  {:<width$}  {:<9}  {}", full_type.name, kind, type_members(full_type), width = width);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(document: &str) -> String {
+        pretty(&tokenize(document).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_pretty_query() {
+        let query = "query User($id: ID!, $n: Int = 10) { user(id: $id) { id name ...Avatar posts(first: $n, filter: {tags: [\"a\" \"b\"]}) @include(if: true) { title } } } fragment Avatar on User { avatar(size: 64) }";
+        assert_eq!(
+            format(query),
+            "query User($id: ID!, $n: Int = 10) {\n  user(id: $id) {\n    id\n    name\n    ...Avatar\n    posts(first: $n, filter: { tags: [\"a\", \"b\"] }) @include(if: true) {\n      title\n    }\n  }\n}\n\nfragment Avatar on User {\n  avatar(size: 64)\n}"
+        );
+        assert_eq!(format("{me{...on User{a:name}}}"), "{\n  me {\n    ... on User {\n      a: name\n    }\n  }\n}");
+    }
+
+    #[test]
+    fn test_pretty_schema_and_comments() {
+        let schema = "# Users\ntype User implements Node & Entity { id: ID! # primary key\n \"The name\" name: String @deprecated(reason: \"no\") tags: [String!]! }\nenum Role { ADMIN USER }\nunion Result = User | Post";
+        assert_eq!(
+            format(schema),
+            "# Users\ntype User implements Node & Entity {\n  id: ID!  # primary key\n  \"The name\"\n  name: String @deprecated(reason: \"no\")\n  tags: [String!]!\n}\n\nenum Role {\n  ADMIN\n  USER\n}\n\nunion Result = User | Post"
+        );
+    }
+
+    #[test]
+    fn test_minify() {
+        let query = "query Q($id: ID!) {\n  # who\n  user(id: $id) { id, name }\n  ... on Query { version }\n}";
+        assert_eq!(minify(&tokenize(query).unwrap()).unwrap(), "query Q($id:ID!){user(id:$id){id name}...on Query{version}}");
+        assert_eq!(minify(&tokenize("{ a(x: \"b c\") }").unwrap()).unwrap(), "{a(x:\"b c\")}");
+        assert!(minify(&tokenize("{ a { b }").unwrap()).is_err());
+        assert!(pretty(&tokenize("{ a ) }").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_introspection_summary() {
+        let body = r#"{"data":{"__schema":{"queryType":{"name":"Query"},"mutationType":null,"subscriptionType":null,"types":[
+            {"kind":"OBJECT","name":"Query","fields":[{"name":"users","args":[{"name":"first","type":{"kind":"SCALAR","name":"Int","ofType":null},"defaultValue":"10"}],
+             "type":{"kind":"NON_NULL","name":null,"ofType":{"kind":"LIST","name":null,"ofType":{"kind":"NON_NULL","name":null,"ofType":{"kind":"OBJECT","name":"User","ofType":null}}}},"isDeprecated":false}]},
+            {"kind":"ENUM","name":"Role","enumValues":[{"name":"ADMIN"},{"name":"USER"}]},
+            {"kind":"SCALAR","name":"String"}]}}}"#;
+        let schema = parse_introspection(body).unwrap();
+        let query = schema.types.iter().find(|full_type| full_type.name == "Query").unwrap();
+        assert_eq!(field_signature(&query.fields.as_ref().unwrap()[0]), "users(first: Int = 10): [User!]!");
+        assert_eq!(type_members(&schema.types[1]), "ADMIN, USER");
+        let refused = parse_introspection(r#"{"errors":[{"message":"introspection is disabled"}]}"#);
+        assert_eq!(refused.err().unwrap(), "The server refused introspection: introspection is disabled");
+    }
+}
@@ -0,0 +1,380 @@
+use crate::tool_module::ToolModule;
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+use std::error::Error;
+
+pub struct HashidModule;
+
+const HASHIDS_ALPHABET: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890";
+// Letters that keep curse words out of hashes; they separate the numbers instead
+const HASHIDS_SEPARATORS: &str = "cfhistuCFHISTU";
+const HASHIDS_MIN_ALPHABET: usize = 16;
+const SEPARATOR_RATIO: f64 = 3.5;
+const GUARD_RATIO: f64 = 12.0;
+const SQIDS_ALPHABET: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+struct Hashids {
+    salt: Vec<u8>,
+    alphabet: Vec<u8>,
+    separators: Vec<u8>,
+    // Padding characters added in front of and behind short hashes
+    guards: Vec<u8>,
+    min_length: usize,
+}
+
+struct Sqids {
+    alphabet: Vec<u8>,
+    min_length: usize,
+}
+
+impl ToolModule for HashidModule {
+    fn name(&self) -> &'static str {
+        "hashid"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("hashid-encode")
+                .long("hashid-encode")
+                .value_name("NUMBER")
+                .num_args(1..)
+                .help("Encode numbers as a Hashids ID (--salt, --alphabet, --min-length), or a Sqids ID with --sqids")
+                .long_help("Encode one or more non-negative numbers (space or comma separated) as a short obfuscated ID, as used in URLs. Hashids is the default; pass the same --salt, --alphabet and --min-length as the application. With --sqids, uses the Sqids scheme instead (no salt). Sqids libraries also skip IDs containing words from a profanity blocklist, which is not applied here, so in rare cases their IDs differ.")
+        )
+        .arg(
+            Arg::new("hashid-decode")
+                .long("hashid-decode")
+                .value_name("ID")
+                .help("Decode a Hashids ID (or Sqids ID with --sqids) back to its numbers")
+        )
+        .arg(
+            Arg::new("salt")
+                .long("salt")
+                .value_name("SALT")
+                .help("Salt for --hashid-encode and --hashid-decode (Hashids only)")
+        )
+        .arg(
+            Arg::new("min-length")
+                .long("min-length")
+                .value_name("N")
+                .value_parser(value_parser!(u32).range(0..=255))
+                .help("Pad --hashid-encode IDs to at least N characters (default: 0)")
+        )
+        .arg(
+            Arg::new("sqids")
+                .long("sqids")
+                .help("Use Sqids instead of Hashids for --hashid-encode and --hashid-decode")
+                .action(ArgAction::SetTrue)
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let encode = matches.get_many::<String>("hashid-encode");
+        let decode = matches.get_one::<String>("hashid-decode");
+        if encode.is_none() && decode.is_none() {
+            return Ok(());
+        }
+        let min_length = matches.get_one::<u32>("min-length").map_or(0, |length| *length as usize);
+        let salt = matches.get_one::<String>("salt");
+        let alphabet = matches.get_one::<String>("alphabet").map(String::as_str);
+        let sqids = matches.get_flag("sqids");
+        if sqids && salt.is_some() {
+            return Err("Sqids has no salt; shuffle the --alphabet instead".into());
+        }
+        if let Some(values) = encode {
+            let mut numbers = Vec::new();
+            for value in values.flat_map(|value| value.split(',')).map(str::trim).filter(|value| !value.is_empty()) {
+                numbers.push(value.parse::<u64>().map_err(|_| format!("Invalid number '{}'; IDs encode non-negative whole numbers", value))?);
+            }
+            if numbers.is_empty() {
+                return Err("Nothing to encode".into());
+            }
+            let id = if sqids {
+                Sqids::new(alphabet.unwrap_or(SQIDS_ALPHABET), min_length)?.encode(&numbers)
+            } else {
+                Hashids::new(salt.map_or("", String::as_str), alphabet.unwrap_or(HASHIDS_ALPHABET), min_length)?.encode(&numbers)
+            };
+            println!("{}", id);
+        } else if let Some(id) = decode {
+            let numbers = if sqids {
+                Sqids::new(alphabet.unwrap_or(SQIDS_ALPHABET), min_length)?.decode(id)?
+            } else {
+                Hashids::new(salt.map_or("", String::as_str), alphabet.unwrap_or(HASHIDS_ALPHABET), min_length)?.decode(id)?
+            };
+            println!("{}", numbers.iter().map(u64::to_string).collect::<Vec<String>>().join(", "));
+        }
+        Ok(())
+    }
+}
+
+// Unique ASCII characters of an alphabet, in order
+fn check_alphabet(alphabet: &str, min_length: usize) -> Result<Vec<u8>, String> {
+    if !alphabet.is_ascii() || alphabet.contains(' ') {
+        return Err("The alphabet must be ASCII characters without spaces".to_string());
+    }
+    let mut unique = Vec::new();
+    for byte in alphabet.bytes() {
+        if unique.contains(&byte) {
+            return Err(format!("The alphabet repeats '{}'; every character must be unique", byte as char));
+        }
+        unique.push(byte);
+    }
+    if unique.len() < min_length {
+        return Err(format!("The alphabet needs at least {} characters", min_length));
+    }
+    Ok(unique)
+}
+
+// The number written in the alphabet's digits, most significant first
+fn to_alphabet(mut number: u64, alphabet: &[u8]) -> Vec<u8> {
+    let base = alphabet.len() as u64;
+    let mut digits = Vec::new();
+    loop {
+        digits.insert(0, alphabet[(number % base) as usize]);
+        number /= base;
+        if number == 0 {
+            return digits;
+        }
+    }
+}
+
+fn from_alphabet(digits: &[u8], alphabet: &[u8]) -> Result<u64, String> {
+    digits.iter().try_fold(0u64, |number, digit| {
+        let value = alphabet.iter().position(|c| c == digit).ok_or_else(|| format!("'{}' is not in the alphabet", *digit as char))?;
+        number
+            .checked_mul(alphabet.len() as u64)
+            .and_then(|number| number.checked_add(value as u64))
+            .ok_or_else(|| "The ID decodes to a number larger than 64 bits".to_string())
+    })
+}
+
+// Hashids' salted shuffle; the same salt always gives the same order
+fn hashids_shuffle(alphabet: &mut [u8], salt: &[u8]) {
+    if salt.is_empty() {
+        return;
+    }
+    let (mut v, mut p) = (0, 0);
+    for i in (1..alphabet.len()).rev() {
+        v %= salt.len();
+        let integer = salt[v] as usize;
+        p += integer;
+        alphabet.swap(i, (integer + v + p) % i);
+        v += 1;
+    }
+}
+
+impl Hashids {
+    fn new(salt: &str, alphabet: &str, min_length: usize) -> Result<Self, String> {
+        let salt = salt.as_bytes().to_vec();
+        let all = check_alphabet(alphabet, HASHIDS_MIN_ALPHABET)?;
+        let mut separators: Vec<u8> = HASHIDS_SEPARATORS.bytes().filter(|c| all.contains(c)).collect();
+        let mut alphabet: Vec<u8> = all.into_iter().filter(|c| !separators.contains(c)).collect();
+        hashids_shuffle(&mut separators, &salt);
+        if separators.is_empty() || alphabet.len() as f64 / separators.len() as f64 > SEPARATOR_RATIO {
+            let wanted = ((alphabet.len() as f64 / SEPARATOR_RATIO).ceil() as usize).max(2);
+            if wanted > separators.len() {
+                let borrowed = wanted - separators.len();
+                separators.extend(alphabet.drain(..borrowed));
+            } else {
+                separators.truncate(wanted);
+            }
+        }
+        hashids_shuffle(&mut alphabet, &salt);
+        let guard_count = (alphabet.len() as f64 / GUARD_RATIO).ceil() as usize;
+        let guards = if alphabet.len() < 3 {
+            separators.drain(..guard_count).collect()
+        } else {
+            alphabet.drain(..guard_count).collect()
+        };
+        Ok(Hashids { salt, alphabet, separators, guards, min_length })
+    }
+
+    // The alphabet reshuffled for each number, seeded by the lottery character
+    fn next_alphabet(&self, alphabet: &mut [u8], lottery: u8) {
+        let mut buffer = vec![lottery];
+        buffer.extend(&self.salt);
+        buffer.extend(alphabet.iter());
+        buffer.truncate(alphabet.len());
+        hashids_shuffle(alphabet, &buffer);
+    }
+
+    fn encode(&self, numbers: &[u64]) -> String {
+        let mut alphabet = self.alphabet.clone();
+        let numbers_hash: u64 = numbers.iter().enumerate().map(|(i, number)| number % (i as u64 + 100)).sum();
+        let lottery = alphabet[(numbers_hash % alphabet.len() as u64) as usize];
+        let mut id = vec![lottery];
+        for (i, &number) in numbers.iter().enumerate() {
+            self.next_alphabet(&mut alphabet, lottery);
+            let last = to_alphabet(number, &alphabet);
+            id.extend(&last);
+            if i + 1 < numbers.len() {
+                let number = number % (last[0] as u64 + i as u64);
+                id.push(self.separators[(number % self.separators.len() as u64) as usize]);
+            }
+        }
+        if id.len() < self.min_length {
+            let guard = |c: u8| self.guards[((numbers_hash + c as u64) % self.guards.len() as u64) as usize];
+            id.insert(0, guard(id[0]));
+            if id.len() < self.min_length {
+                id.push(guard(id[2]));
+            }
+        }
+        let half = alphabet.len() / 2;
+        while id.len() < self.min_length {
+            let salt = alphabet.clone();
+            hashids_shuffle(&mut alphabet, &salt);
+            let mut padded = alphabet[half..].to_vec();
+            padded.extend(&id);
+            padded.extend(&alphabet[..half]);
+            // Trim evenly from both ends once long enough
+            let excess = padded.len().saturating_sub(self.min_length);
+            id = if excess > 0 { padded[excess / 2..excess / 2 + self.min_length].to_vec() } else { padded };
+        }
+        String::from_utf8(id).unwrap_or_default()
+    }
+
+    fn decode(&self, id: &str) -> Result<Vec<u64>, String> {
+        let invalid = || "Not a valid ID for this salt, alphabet and minimum length".to_string();
+        let parts: Vec<&[u8]> = id.as_bytes().split(|c| self.guards.contains(c)).collect();
+        let core = parts[if parts.len() == 2 || parts.len() == 3 { 1 } else { 0 }];
+        let (&lottery, rest) = core.split_first().ok_or_else(invalid)?;
+        let mut alphabet = self.alphabet.clone();
+        let mut numbers = Vec::new();
+        for part in rest.split(|c| self.separators.contains(c)) {
+            self.next_alphabet(&mut alphabet, lottery);
+            numbers.push(from_alphabet(part, &alphabet).map_err(|_| invalid())?);
+        }
+        // Anything that does not re-encode to the same ID was not made with these settings
+        if self.encode(&numbers) != id {
+            return Err(invalid());
+        }
+        Ok(numbers)
+    }
+}
+
+// Sqids' shuffle, which needs no salt
+fn sqids_shuffle(alphabet: &mut [u8]) {
+    let length = alphabet.len();
+    let (mut i, mut j) = (0, length - 1);
+    while j > 0 {
+        let r = (i * j + alphabet[i] as usize + alphabet[j] as usize) % length;
+        alphabet.swap(i, r);
+        i += 1;
+        j -= 1;
+    }
+}
+
+impl Sqids {
+    fn new(alphabet: &str, min_length: usize) -> Result<Self, String> {
+        let mut alphabet = check_alphabet(alphabet, 3)?;
+        sqids_shuffle(&mut alphabet);
+        Ok(Sqids { alphabet, min_length })
+    }
+
+    fn encode(&self, numbers: &[u64]) -> String {
+        let length = self.alphabet.len();
+        let offset = numbers
+            .iter()
+            .enumerate()
+            .fold(numbers.len(), |total, (i, &number)| total + self.alphabet[(number % length as u64) as usize] as usize + i)
+            % length;
+        let mut alphabet = [&self.alphabet[offset..], &self.alphabet[..offset]].concat();
+        let prefix = alphabet[0];
+        alphabet.reverse();
+        let mut id = vec![prefix];
+        for (i, &number) in numbers.iter().enumerate() {
+            id.extend(to_alphabet(number, &alphabet[1..]));
+            if i + 1 < numbers.len() {
+                id.push(alphabet[0]);
+                sqids_shuffle(&mut alphabet);
+            }
+        }
+        if id.len() < self.min_length {
+            id.push(alphabet[0]);
+            while id.len() < self.min_length {
+                sqids_shuffle(&mut alphabet);
+                let wanted = (self.min_length - id.len()).min(length);
+                id.extend(&alphabet[..wanted]);
+            }
+        }
+        String::from_utf8(id).unwrap_or_default()
+    }
+
+    fn decode(&self, id: &str) -> Result<Vec<u64>, String> {
+        let bytes = id.as_bytes();
+        let (&prefix, mut rest) = bytes.split_first().ok_or("Nothing to decode")?;
+        if let Some(c) = id.chars().find(|c| !c.is_ascii() || !self.alphabet.contains(&(*c as u8))) {
+            return Err(format!("'{}' is not in the alphabet", c));
+        }
+        let offset = self.alphabet.iter().position(|&c| c == prefix).unwrap_or_default();
+        let mut alphabet = [&self.alphabet[offset..], &self.alphabet[..offset]].concat();
+        alphabet.reverse();
+        let mut numbers = Vec::new();
+        while !rest.is_empty() {
+            let separator = alphabet[0];
+            let (chunk, remainder) = match rest.iter().position(|&c| c == separator) {
+                Some(index) => (&rest[..index], Some(&rest[index + 1..])),
+                None => (rest, None),
+            };
+            // Padding starts with a separator, so an empty chunk ends the numbers
+            if chunk.is_empty() {
+                break;
+            }
+            numbers.push(from_alphabet(chunk, &alphabet[1..])?);
+            match remainder {
+                Some(remainder) => {
+                    sqids_shuffle(&mut alphabet);
+                    rest = remainder;
+                }
+                None => break,
+            }
+        }
+        Ok(numbers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hashids() {
+        let hashids = Hashids::new("this is my salt", HASHIDS_ALPHABET, 0).unwrap();
+        assert_eq!(hashids.encode(&[12345]), "NkK9");
+        assert_eq!(hashids.encode(&[1, 2, 3]), "laHquq");
+        assert_eq!(hashids.decode("NkK9").unwrap(), [12345]);
+        assert_eq!(hashids.decode("laHquq").unwrap(), [1, 2, 3]);
+        // A different salt gives a different ID that does not decode with the first
+        let other = Hashids::new("another salt", HASHIDS_ALPHABET, 0).unwrap();
+        assert_ne!(other.encode(&[12345]), "NkK9");
+        assert!(other.decode("NkK9").is_err());
+    }
+
+    #[test]
+    fn test_hashids_options() {
+        let padded = Hashids::new("this is my salt", HASHIDS_ALPHABET, 8).unwrap();
+        assert_eq!(padded.encode(&[1]), "gB0NV05e");
+        assert_eq!(padded.decode("gB0NV05e").unwrap(), [1]);
+        let hex = Hashids::new("this is my salt", "0123456789abcdef", 0).unwrap();
+        assert_eq!(hex.encode(&[1234567]), "b332db5");
+        assert!(Hashids::new("", "abc", 0).is_err());
+        assert!(Hashids::new("", "aabcdefghijklmnopq", 0).is_err());
+    }
+
+    #[test]
+    fn test_sqids() {
+        let sqids = Sqids::new(SQIDS_ALPHABET, 0).unwrap();
+        assert_eq!(sqids.encode(&[1, 2, 3]), "86Rf07");
+        assert_eq!(sqids.decode("86Rf07").unwrap(), [1, 2, 3]);
+        assert_eq!(sqids.decode(&sqids.encode(&[0, u64::MAX])).unwrap(), [0, u64::MAX]);
+        assert!(sqids.decode("86Rf07*").is_err());
+    }
+
+    #[test]
+    fn test_sqids_min_length() {
+        let sqids = Sqids::new(SQIDS_ALPHABET, 10).unwrap();
+        let id = sqids.encode(&[1, 2, 3]);
+        assert_eq!(id.len(), 10);
+        assert!(id.starts_with("86Rf07"));
+        assert_eq!(sqids.decode(&id).unwrap(), [1, 2, 3]);
+    }
+}
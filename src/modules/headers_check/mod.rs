@@ -0,0 +1,336 @@
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use reqwest::blocking::Client;
+use std::error::Error;
+use std::io::{self, IsTerminal};
+use std::time::Duration;
+
+pub struct HeadersCheckModule;
+
+type Headers = Vec<(String, String)>;
+
+// HSTS preload lists ask for at least a year; less than six months is weak
+const HSTS_MIN_SECONDS: u64 = 180 * 24 * 3600;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Level {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Finding {
+    check: String,
+    level: Level,
+    detail: String,
+    hint: Option<String>,
+}
+
+impl Finding {
+    fn new(check: &str, level: Level, detail: impl Into<String>, hint: Option<&str>) -> Self {
+        Self { check: check.to_string(), level, detail: detail.into(), hint: hint.map(str::to_string) }
+    }
+}
+
+impl ToolModule for HeadersCheckModule {
+    fn name(&self) -> &'static str {
+        "headers-check"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("headers-check")
+                .long("headers-check")
+                .value_name("URL")
+                .help("Grade a site's security headers (HSTS, CSP, framing, nosniff, referrer, cookies) with fixes")
+                .long_help("Fetch URL (following redirects) and check its response headers: Strict-Transport-Security, Content-Security-Policy, X-Frame-Options or frame-ancestors, X-Content-Type-Options, Referrer-Policy, the Secure, HttpOnly and SameSite flags of every cookie, and version numbers leaked by Server or X-Powered-By. Each check passes, warns or fails with a concrete remediation hint, and the result is summarized as a grade from A to F.")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(url) = matches.get_one::<String>("headers-check") {
+            let url = if url.contains("://") { url.clone() } else { format!("https://{}", url) };
+            let (final_url, status, headers) = fetch_headers(&url)?;
+            let findings = analyze(final_url.starts_with("https://"), &headers);
+            print!("{}", render(&final_url, status, &findings, io::stdout().is_terminal()));
+        }
+        Ok(())
+    }
+}
+
+// Header names are lowercased; repeated headers such as Set-Cookie stay separate
+fn fetch_headers(url: &str) -> Result<(String, u16, Headers), Box<dyn Error>> {
+    let client = Client::builder()
+        .user_agent(concat!("micro-swiss/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(15))
+        .build()?;
+    // Error pages are still served with the site's headers, so any status is fine here
+    let response = client.get(url).send().map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
+    let mut headers: Headers = response
+        .headers()
+        .iter()
+        .map(|(name, value)| (name.as_str().to_string(), String::from_utf8_lossy(value.as_bytes()).to_string()))
+        .collect();
+    headers.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok((response.url().to_string(), response.status().as_u16(), headers))
+}
+
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers.iter().find(|(key, _)| key == name).map(|(_, value)| value.trim())
+}
+
+fn analyze(https: bool, headers: &[(String, String)]) -> Vec<Finding> {
+    let csp = header(headers, "content-security-policy");
+    let mut findings = vec![
+        check_hsts(https, header(headers, "strict-transport-security")),
+        check_csp(csp, header(headers, "content-security-policy-report-only")),
+        check_framing(header(headers, "x-frame-options"), csp),
+        check_nosniff(header(headers, "x-content-type-options")),
+        check_referrer(header(headers, "referrer-policy")),
+    ];
+    for (_, cookie) in headers.iter().filter(|(key, _)| key == "set-cookie") {
+        findings.push(check_cookie(https, cookie));
+    }
+    for name in ["server", "x-powered-by", "x-aspnet-version"] {
+        if let Some(value) = header(headers, name).filter(|value| value.chars().any(|c| c.is_ascii_digit())) {
+            findings.push(Finding::new(
+                &display_name(name),
+                Level::Warn,
+                format!("discloses {}", value),
+                Some("Remove the version number so scanners cannot match it against known vulnerabilities"),
+            ));
+        }
+    }
+    findings
+}
+
+// "x-powered-by" -> "X-Powered-By"
+fn display_name(name: &str) -> String {
+    name.split('-')
+        .map(|part| {
+            let mut chars = part.chars();
+            chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+        })
+        .collect::<Vec<String>>()
+        .join("-")
+}
+
+fn check_hsts(https: bool, value: Option<&str>) -> Finding {
+    const NAME: &str = "Strict-Transport-Security";
+    if !https {
+        return Finding::new(NAME, Level::Fail, "site is served over plain HTTP", Some("Redirect all HTTP traffic to HTTPS, then add Strict-Transport-Security"));
+    }
+    let Some(value) = value else {
+        return Finding::new(NAME, Level::Fail, "missing", Some("Add Strict-Transport-Security: max-age=31536000; includeSubDomains"));
+    };
+    let directives: Vec<String> = value.split(';').map(|directive| directive.trim().to_lowercase()).collect();
+    let max_age = directives.iter().find_map(|directive| directive.strip_prefix("max-age=")).and_then(|age| age.trim_matches('"').parse::<u64>().ok());
+    match max_age {
+        None => Finding::new(NAME, Level::Fail, format!("{} (no valid max-age)", value), Some("Set max-age=31536000")),
+        Some(age) if age < HSTS_MIN_SECONDS => Finding::new(NAME, Level::Warn, format!("{} (max-age under 6 months)", value), Some("Raise max-age to 31536000 (one year)")),
+        Some(_) if !directives.iter().any(|directive| directive == "includesubdomains") => {
+            Finding::new(NAME, Level::Warn, value, Some("Add includeSubDomains once every subdomain serves HTTPS"))
+        }
+        Some(_) => Finding::new(NAME, Level::Pass, value, None),
+    }
+}
+
+fn check_csp(value: Option<&str>, report_only: Option<&str>) -> Finding {
+    const NAME: &str = "Content-Security-Policy";
+    let Some(value) = value else {
+        return match report_only {
+            Some(_) => Finding::new(NAME, Level::Warn, "report-only, not enforced", Some("Switch Content-Security-Policy-Report-Only to Content-Security-Policy once the reports are clean")),
+            None => Finding::new(NAME, Level::Fail, "missing", Some("Add a Content-Security-Policy, starting from default-src 'self'; object-src 'none'; base-uri 'self'")),
+        };
+    };
+    let policy = value.to_lowercase();
+    // script-src falls back to default-src when it is not given
+    let scripts = policy
+        .split(';')
+        .map(str::trim)
+        .find(|directive| directive.starts_with("script-src "))
+        .or_else(|| policy.split(';').map(str::trim).find(|directive| directive.starts_with("default-src ")));
+    let mut problems = Vec::new();
+    match scripts {
+        None => problems.push("no script-src or default-src"),
+        Some(scripts) => {
+            // 'unsafe-inline' is ignored by browsers when a nonce or hash is present
+            if scripts.contains("'unsafe-inline'") && !scripts.contains("'nonce-") && !scripts.contains("'sha") {
+                problems.push("scripts allow 'unsafe-inline'");
+            }
+            if scripts.contains("'unsafe-eval'") {
+                problems.push("scripts allow 'unsafe-eval'");
+            }
+            if scripts.split_whitespace().skip(1).any(|source| source == "*" || source == "http:" || source == "https:" || source == "data:") {
+                problems.push("scripts load from any host");
+            }
+        }
+    }
+    if problems.is_empty() {
+        Finding::new(NAME, Level::Pass, value, None)
+    } else {
+        Finding::new(NAME, Level::Warn, problems.join(", "), Some("Replace 'unsafe-inline' and 'unsafe-eval' with nonces or hashes, and list script hosts explicitly"))
+    }
+}
+
+fn check_framing(value: Option<&str>, csp: Option<&str>) -> Finding {
+    const NAME: &str = "X-Frame-Options";
+    if csp.is_some_and(|csp| csp.to_lowercase().contains("frame-ancestors")) {
+        return Finding::new(NAME, Level::Pass, "covered by CSP frame-ancestors", None);
+    }
+    match value.map(str::to_uppercase).as_deref() {
+        Some("DENY") | Some("SAMEORIGIN") => Finding::new(NAME, Level::Pass, value.unwrap_or_default(), None),
+        Some(other) if other.starts_with("ALLOW-FROM") => {
+            Finding::new(NAME, Level::Warn, format!("{} (ignored by modern browsers)", value.unwrap_or_default()), Some("Use CSP frame-ancestors to allow specific origins"))
+        }
+        Some(_) => Finding::new(NAME, Level::Fail, format!("{} (invalid)", value.unwrap_or_default()), Some("Use X-Frame-Options: DENY, or SAMEORIGIN if the site frames itself")),
+        None => Finding::new(NAME, Level::Fail, "missing", Some("Add X-Frame-Options: DENY (or CSP frame-ancestors 'none') to prevent clickjacking")),
+    }
+}
+
+fn check_nosniff(value: Option<&str>) -> Finding {
+    const NAME: &str = "X-Content-Type-Options";
+    match value {
+        Some(value) if value.eq_ignore_ascii_case("nosniff") => Finding::new(NAME, Level::Pass, value, None),
+        Some(value) => Finding::new(NAME, Level::Fail, format!("{} (invalid)", value), Some("Set X-Content-Type-Options: nosniff")),
+        None => Finding::new(NAME, Level::Fail, "missing", Some("Add X-Content-Type-Options: nosniff")),
+    }
+}
+
+fn check_referrer(value: Option<&str>) -> Finding {
+    const NAME: &str = "Referrer-Policy";
+    let Some(value) = value else {
+        return Finding::new(NAME, Level::Warn, "missing (browser default applies)", Some("Add Referrer-Policy: strict-origin-when-cross-origin"));
+    };
+    // With a list, browsers use the last value they understand
+    let policy = value.rsplit(',').next().unwrap_or("").trim().to_lowercase();
+    match policy.as_str() {
+        "unsafe-url" | "no-referrer-when-downgrade" | "origin-when-cross-origin" => {
+            Finding::new(NAME, Level::Warn, format!("{} (leaks full URLs to other sites)", value), Some("Use strict-origin-when-cross-origin or no-referrer"))
+        }
+        _ => Finding::new(NAME, Level::Pass, value, None),
+    }
+}
+
+fn check_cookie(https: bool, cookie: &str) -> Finding {
+    let mut parts = cookie.split(';').map(str::trim);
+    let name = parts.next().and_then(|pair| pair.split('=').next()).unwrap_or("").to_string();
+    let attributes: Vec<String> = parts.map(str::to_lowercase).collect();
+    let has = |flag: &str| attributes.iter().any(|attribute| attribute == flag);
+    let same_site = attributes.iter().find_map(|attribute| attribute.strip_prefix("samesite=")).map(str::trim);
+    let check = format!("Cookie {}", name);
+
+    let mut missing = Vec::new();
+    if https && !has("secure") {
+        missing.push("Secure");
+    }
+    if !has("httponly") {
+        missing.push("HttpOnly");
+    }
+    if same_site.is_none() {
+        missing.push("SameSite");
+    }
+    let detail = if missing.is_empty() { "Secure, HttpOnly, SameSite".to_string() } else { format!("no {}", missing.join(", no ")) };
+    if same_site == Some("none") && !has("secure") {
+        Finding::new(&check, Level::Fail, format!("{} (SameSite=None requires Secure)", detail), Some("Add Secure, or use SameSite=Lax"))
+    } else if missing.contains(&"Secure") {
+        Finding::new(&check, Level::Fail, detail, Some("Add Secure so the cookie is never sent over plain HTTP; add HttpOnly and SameSite=Lax too unless scripts or cross-site requests need them"))
+    } else if missing == ["SameSite"] {
+        Finding::new(&check, Level::Warn, detail, Some("Add SameSite=Lax (or Strict) to limit cross-site requests"))
+    } else if !missing.is_empty() {
+        Finding::new(&check, Level::Warn, detail, Some("Add HttpOnly unless scripts read this cookie, and SameSite=Lax (or Strict)"))
+    } else {
+        Finding::new(&check, Level::Pass, detail, None)
+    }
+}
+
+fn grade(findings: &[Finding]) -> char {
+    let fails = findings.iter().filter(|finding| finding.level == Level::Fail).count();
+    let warns = findings.iter().filter(|finding| finding.level == Level::Warn).count();
+    match (fails, warns) {
+        (0, 0..=1) => 'A',
+        (0, _) => 'B',
+        (1, _) => 'C',
+        (2, _) => 'D',
+        _ => 'F',
+    }
+}
+
+fn render(url: &str, status: u16, findings: &[Finding], color: bool) -> String {
+    let width = findings.iter().map(|finding| finding.check.chars().count()).max().unwrap_or(0);
+    let mut output = format!("{} → {}\n\n", url, status);
+    for finding in findings {
+        let icon = match finding.level {
+            Level::Pass => "✅",
+            Level::Warn => "⚠️",
+            Level::Fail => "❌",
+        };
+        let check = format!("{:<width$}", finding.check, width = width);
+        output.push_str(&format!("{} {}  {}\n", icon, if color { check.bold().to_string() } else { check }, finding.detail));
+        if let Some(hint) = &finding.hint {
+            let hint = format!("→ {}", hint);
+            output.push_str(&format!("   {}\n", if color { hint.dimmed().to_string() } else { hint }));
+        }
+    }
+    let grade = grade(findings);
+    let fails = findings.iter().filter(|finding| finding.level == Level::Fail).count();
+    let warns = findings.iter().filter(|finding| finding.level == Level::Warn).count();
+    let letter = match grade {
+        _ if !color => grade.to_string(),
+        'A' | 'B' => grade.to_string().green().bold().to_string(),
+        'C' => grade.to_string().yellow().bold().to_string(),
+        _ => grade.to_string().red().bold().to_string(),
+    };
+    output.push_str(&format!("\nGrade: {} ({} failing, {} warnings)\n", letter, fails, warns));
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&str, &str)]) -> Headers {
+        pairs.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect()
+    }
+
+    #[test]
+    fn test_hardened_site_grades_a() {
+        let findings = analyze(true, &headers(&[
+            ("strict-transport-security", "max-age=63072000; includeSubDomains; preload"),
+            ("content-security-policy", "default-src 'self'; frame-ancestors 'none'"),
+            ("x-content-type-options", "nosniff"),
+            ("referrer-policy", "no-referrer"),
+            ("set-cookie", "session=abc; Path=/; Secure; HttpOnly; SameSite=Lax"),
+        ]));
+        assert!(findings.iter().all(|finding| finding.level == Level::Pass), "{:?}", findings);
+        assert_eq!(grade(&findings), 'A');
+    }
+
+    #[test]
+    fn test_bare_site_fails() {
+        let findings = analyze(true, &headers(&[("server", "nginx/1.18.0"), ("set-cookie", "id=1; SameSite=None")]));
+        let level = |check: &str| findings.iter().find(|finding| finding.check == check).map(|finding| finding.level);
+        assert_eq!(level("Strict-Transport-Security"), Some(Level::Fail));
+        assert_eq!(level("Content-Security-Policy"), Some(Level::Fail));
+        assert_eq!(level("X-Frame-Options"), Some(Level::Fail));
+        assert_eq!(level("Referrer-Policy"), Some(Level::Warn));
+        assert_eq!(level("Cookie id"), Some(Level::Fail));
+        assert_eq!(level("Server"), Some(Level::Warn));
+        assert_eq!(grade(&findings), 'F');
+    }
+
+    #[test]
+    fn test_individual_checks() {
+        assert_eq!(check_hsts(true, Some("max-age=600")).level, Level::Warn);
+        assert_eq!(check_hsts(false, None).level, Level::Fail);
+        assert_eq!(check_csp(Some("script-src 'self' 'unsafe-inline' 'unsafe-eval'"), None).detail, "scripts allow 'unsafe-inline', scripts allow 'unsafe-eval'");
+        assert_eq!(check_csp(Some("script-src 'self' 'nonce-abc' 'unsafe-inline'"), None).level, Level::Pass);
+        assert_eq!(check_csp(None, Some("default-src 'self'")).level, Level::Warn);
+        assert_eq!(check_framing(Some("ALLOW-FROM https://a.example"), None).level, Level::Warn);
+        assert_eq!(check_referrer(Some("no-referrer, unsafe-url")).level, Level::Warn);
+        assert_eq!(check_cookie(true, "theme=dark; Secure").detail, "no HttpOnly, no SameSite");
+        assert_eq!(display_name("x-powered-by"), "X-Powered-By");
+    }
+}
@@ -0,0 +1,203 @@
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgMatches, Command};
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, IsTerminal, Read, Write};
+use std::path::Path;
+
+pub struct HexDumpModule;
+
+const DEFAULT_BYTES_PER_LINE: usize = 16;
+
+impl ToolModule for HexDumpModule {
+    fn name(&self) -> &'static str {
+        "hex-dump"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("hexdump")
+                .long("hexdump")
+                .value_name("FILE")
+                .help("Show a file as an xxd-style hex dump (- for stdin); --width sets bytes per line")
+                .long_help("Show FILE as an xxd-style hex dump: the offset, the bytes in hex grouped in pairs and the printable ASCII characters. Use - to read stdin and --width to change the bytes per line (default: 16). The output can be edited and turned back into binary with --hexdump-reverse, or with xxd -r.")
+        )
+        .arg(
+            Arg::new("hexdump-reverse")
+                .long("hexdump-reverse")
+                .value_names(["DUMP", "OUT"])
+                .num_args(1..=2)
+                .help("Rebuild binary from a --hexdump or xxd dump (- for stdin), writing OUT or stdout")
+                .long_help("Turn a hex dump back into binary: xxd-style lines with offsets, as written by --hexdump or xxd, or plain hex as written by xxd -p. Bytes are placed at each line's offset, so an edited dump patches exactly the bytes changed. Writes OUT (never overwritten) or stdout when it is redirected.")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(path) = matches.get_one::<String>("hexdump") {
+            let width = matches.get_one::<u32>("width").map_or(DEFAULT_BYTES_PER_LINE, |width| *width as usize);
+            let reader: Box<dyn Read> = if path == "-" {
+                Box::new(io::stdin().lock())
+            } else {
+                Box::new(File::open(path).map_err(|e| format!("Failed to open '{}': {}", path, e))?)
+            };
+            dump(BufReader::new(reader), &mut BufWriter::new(io::stdout().lock()), width)?;
+        } else if let Some(values) = matches.get_many::<String>("hexdump-reverse") {
+            let values: Vec<&String> = values.collect();
+            let dump = if values[0] == "-" {
+                let mut buffer = String::new();
+                io::stdin().read_to_string(&mut buffer)?;
+                buffer
+            } else {
+                fs::read_to_string(values[0]).map_err(|e| format!("Failed to read '{}': {}", values[0], e))?
+            };
+            let bytes = reverse(&dump)?;
+            match values.get(1) {
+                Some(out) => {
+                    if Path::new(out.as_str()).exists() {
+                        return Err(format!("{} already exists; not overwriting it", out).into());
+                    }
+                    fs::write(out.as_str(), &bytes).map_err(|e| format!("Failed to write {}: {}", out, e))?;
+                    println!("✅ Wrote {} bytes to {}", bytes.len(), out);
+                }
+                None if io::stdout().is_terminal() => {
+                    return Err("Not writing binary to the terminal; pass an OUT file or redirect stdout".into());
+                }
+                None => io::stdout().lock().write_all(&bytes)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// "00000000: 4865 6c6c 6f0a                           Hello." with the hex
+// column padded on short lines so the ASCII column stays aligned
+fn format_line(offset: usize, bytes: &[u8], width: usize) -> String {
+    let mut line = format!("{:08x}: ", offset);
+    for index in 0..width {
+        match bytes.get(index) {
+            Some(byte) => line.push_str(&format!("{:02x}", byte)),
+            None => line.push_str("  "),
+        }
+        if index % 2 == 1 || index == width - 1 {
+            line.push(' ');
+        }
+    }
+    line.push(' ');
+    line.extend(bytes.iter().map(|&byte| if (0x20..0x7f).contains(&byte) { byte as char } else { '.' }));
+    line
+}
+
+fn dump(mut reader: impl Read, writer: &mut impl Write, width: usize) -> io::Result<()> {
+    let mut buffer = vec![0u8; width];
+    let mut offset = 0;
+    loop {
+        // Fill the whole line even when reads come back short, as they do from pipes
+        let mut filled = 0;
+        while filled < width {
+            match reader.read(&mut buffer[filled..])? {
+                0 => break,
+                read => filled += read,
+            }
+        }
+        if filled == 0 {
+            break;
+        }
+        writeln!(writer, "{}", format_line(offset, &buffer[..filled], width))?;
+        offset += filled;
+        if filled < width {
+            break;
+        }
+    }
+    writer.flush()
+}
+
+fn hex_value(digit: u8) -> Option<u8> {
+    (digit as char).to_digit(16).map(|value| value as u8)
+}
+
+// Hex pairs up to the ASCII column, which starts after two spaces in a row
+fn parse_hex_column(column: &str) -> Result<Vec<u8>, String> {
+    let column = column.split("  ").next().unwrap_or_default();
+    let digits: Vec<u8> = column.bytes().filter(|byte| !byte.is_ascii_whitespace()).collect();
+    if digits.len() % 2 == 1 {
+        return Err(format!("Odd number of hex digits in '{}'", column.trim()));
+    }
+    digits
+        .chunks(2)
+        .map(|pair| match (hex_value(pair[0]), hex_value(pair[1])) {
+            (Some(high), Some(low)) => Ok(high << 4 | low),
+            _ => Err(format!("Invalid hex '{}{}'", pair[0] as char, pair[1] as char)),
+        })
+        .collect()
+}
+
+fn reverse(dump: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    let mut position = 0;
+    for (number, line) in dump.lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fail = |e: String| format!("Line {}: {}", number + 1, e);
+        // "offset: hex  ascii" or plain hex as from xxd -p
+        let column = match line.split_once(':') {
+            Some((offset, column)) if !offset.trim().is_empty() && offset.trim().bytes().all(|byte| byte.is_ascii_hexdigit()) => {
+                position = usize::from_str_radix(offset.trim(), 16).map_err(|e| fail(e.to_string()))?;
+                column.strip_prefix(' ').unwrap_or(column)
+            }
+            _ => line,
+        };
+        for byte in parse_hex_column(column).map_err(fail)? {
+            if position < bytes.len() {
+                bytes[position] = byte;
+            } else {
+                bytes.resize(position, 0);
+                bytes.push(byte);
+            }
+            position += 1;
+        }
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dump_string(data: &[u8], width: usize) -> String {
+        let mut output = Vec::new();
+        dump(data, &mut output, width).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_dump_matches_xxd() {
+        // As printed by `printf 'Hello, hex world!\n\x00\xff' | xxd`
+        assert_eq!(
+            dump_string(b"Hello, hex world!\n\x00\xff", 16),
+            "00000000: 4865 6c6c 6f2c 2068 6578 2077 6f72 6c64  Hello, hex world\n\
+             00000010: 210a 00ff                                !...\n"
+        );
+        assert_eq!(dump_string(b"abc", 3), "00000000: 6162 63  abc\n");
+        assert_eq!(dump_string(b"", 16), "");
+    }
+
+    #[test]
+    fn test_reverse_round_trip() {
+        let data: Vec<u8> = (0..=255).chain(b"  two  spaces  ".iter().copied()).collect();
+        for width in [1, 7, 16, 32] {
+            assert_eq!(reverse(&dump_string(&data, width)).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_reverse_plain_hex_and_patches() {
+        assert_eq!(reverse("48656c6c6f\n0a\n").unwrap(), b"Hello\n");
+        // An edited dump patches bytes at the given offsets, filling gaps with zeros
+        let patched = reverse("00000000: 4865 6c6c 6f0a  Hello.\n00000001: 61\n00000008: ff\n").unwrap();
+        assert_eq!(patched, b"Hallo\n\0\0\xff");
+        assert!(reverse("00000000: 486").unwrap_err().contains("Line 1"));
+        assert!(reverse("00000000: zz").is_err());
+    }
+}
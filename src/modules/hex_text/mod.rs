@@ -0,0 +1,213 @@
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use std::error::Error;
+use std::io::{self, IsTerminal, Read, Write};
+
+pub struct HexTextModule;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Radix {
+    Hex,
+    Binary,
+}
+
+impl ToolModule for HexTextModule {
+    fn name(&self) -> &'static str {
+        "hex-text"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("hex-encode")
+                .long("hex-encode")
+                .value_name("TEXT")
+                .help("Convert text to hex, e.g. Hello → 48656c6c6f (- for stdin); --upper for A-F")
+        )
+        .arg(
+            Arg::new("hex-decode")
+                .long("hex-decode")
+                .value_name("HEX")
+                .help("Convert hex back to text; spaces, colons, 0x and \\x prefixes are ignored (- for stdin)")
+        )
+        .arg(
+            Arg::new("bin-encode")
+                .long("bin-encode")
+                .value_name("TEXT")
+                .help("Convert text to binary, e.g. Hi → 01001000 01101001 (- for stdin)")
+        )
+        .arg(
+            Arg::new("bin-decode")
+                .long("bin-decode")
+                .value_name("BITS")
+                .help("Convert binary back to text; separated groups may be shorter than 8 bits (- for stdin)")
+        )
+        .arg(
+            Arg::new("byte-sep")
+                .long("byte-sep")
+                .value_name("SEP")
+                .help("Separator between bytes for --hex-encode (default: none, or a space with --prefix) and --bin-encode (default: space)")
+        )
+        .arg(
+            Arg::new("prefix")
+                .long("prefix")
+                .help("Prefix every byte with 0x (--hex-encode) or 0b (--bin-encode)")
+                .action(ArgAction::SetTrue)
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let separator = matches.get_one::<String>("byte-sep").map(String::as_str);
+        let prefix = matches.get_flag("prefix");
+        if let Some(text) = matches.get_one::<String>("hex-encode") {
+            println!("{}", encode(&read_hex_source(text)?, Radix::Hex, separator.unwrap_or(default_separator(Radix::Hex, prefix)), prefix, matches.get_flag("upper")));
+        } else if let Some(text) = matches.get_one::<String>("bin-encode") {
+            println!("{}", encode(&read_hex_source(text)?, Radix::Binary, separator.unwrap_or(default_separator(Radix::Binary, prefix)), prefix, false));
+        } else if let Some(hex) = matches.get_one::<String>("hex-decode") {
+            print_bytes(&decode(&String::from_utf8_lossy(&read_hex_source(hex)?), Radix::Hex)?)?;
+        } else if let Some(bits) = matches.get_one::<String>("bin-decode") {
+            print_bytes(&decode(&String::from_utf8_lossy(&read_hex_source(bits)?), Radix::Binary)?)?;
+        }
+        Ok(())
+    }
+}
+
+// "-" reads stdin as bytes, minus the newline that echo and heredocs add
+fn read_hex_source(value: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    if value != "-" {
+        return Ok(value.as_bytes().to_vec());
+    }
+    let mut buffer = Vec::new();
+    io::stdin().read_to_end(&mut buffer)?;
+    if buffer.ends_with(b"\n") {
+        buffer.pop();
+        if buffer.ends_with(b"\r") {
+            buffer.pop();
+        }
+    }
+    Ok(buffer)
+}
+
+// Hex runs together unless every byte gets a prefix, which would give 0x480x69
+fn default_separator(radix: Radix, prefix: bool) -> &'static str {
+    match radix {
+        Radix::Hex if !prefix => "",
+        _ => " ",
+    }
+}
+
+fn encode(bytes: &[u8], radix: Radix, separator: &str, prefix: bool, upper: bool) -> String {
+    bytes
+        .iter()
+        .map(|byte| {
+            let digits = match radix {
+                Radix::Hex if upper => format!("{:02X}", byte),
+                Radix::Hex => format!("{:02x}", byte),
+                Radix::Binary => format!("{:08b}", byte),
+            };
+            match (radix, prefix) {
+                (_, false) => digits,
+                (Radix::Hex, true) => format!("0x{}", digits),
+                (Radix::Binary, true) => format!("0b{}", digits),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(separator)
+}
+
+// Separators split the input into bytes; without any, hex is read two digits
+// and binary eight bits at a time
+fn decode(input: &str, radix: Radix) -> Result<Vec<u8>, String> {
+    let (name, base, width, prefixes): (&str, u32, usize, &[&str]) = match radix {
+        Radix::Hex => ("hex", 16, 2, &["0x", "0X"]),
+        Radix::Binary => ("binary", 2, 8, &["0b", "0B"]),
+    };
+    let mut groups = Vec::new();
+    for token in input.split(|c: char| c.is_whitespace() || matches!(c, ',' | ':' | ';' | '-' | '_' | '|')) {
+        // \x48\x65 arrives as one token
+        for group in token.split("\\x").filter(|group| !group.is_empty()) {
+            let group = prefixes.iter().find_map(|prefix| group.strip_prefix(prefix)).unwrap_or(group);
+            if let Some(invalid) = group.chars().find(|c| !c.is_digit(base)) {
+                return Err(format!("Invalid {} digit {:?} in '{}'", name, invalid, group));
+            }
+            groups.push(group.to_string());
+        }
+    }
+    if groups.is_empty() {
+        return Err("Nothing to decode".to_string());
+    }
+    let separated = groups.len() > 1;
+    let mut bytes = Vec::new();
+    for group in &groups {
+        let chunks: Vec<String> = if separated && group.len() <= width {
+            vec![group.clone()]
+        } else if group.len() % width == 0 {
+            group.as_bytes().chunks(width).map(|chunk| String::from_utf8_lossy(chunk).into_owned()).collect()
+        } else {
+            return Err(format!("'{}' is not a whole number of bytes ({} digits each)", group, width));
+        };
+        for chunk in chunks {
+            bytes.push(u8::from_str_radix(&chunk, base).map_err(|e| format!("Invalid {} '{}': {}", name, chunk, e))?);
+        }
+    }
+    Ok(bytes)
+}
+
+// Raw bytes when piped, so binary survives; on a terminal, invalid UTF-8 is shown replaced
+fn print_bytes(bytes: &[u8]) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    if !stdout.is_terminal() {
+        return stdout.write_all(bytes);
+    }
+    let text = String::from_utf8_lossy(bytes);
+    if std::str::from_utf8(bytes).is_err() {
+        eprintln!("⚠️  Not valid UTF-8; invalid bytes are shown as �. Redirect the output to keep them.");
+    }
+    writeln!(stdout, "{}", text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode(b"Hello", Radix::Hex, "", false, false), "48656c6c6f");
+        assert_eq!(encode(b"Hi", Radix::Hex, " ", true, false), "0x48 0x69");
+        assert_eq!(encode(b"\xab\xcd", Radix::Hex, ", ", true, true), "0xAB, 0xCD");
+        assert_eq!(encode("é".as_bytes(), Radix::Hex, ":", false, false), "c3:a9");
+        assert_eq!(encode(b"Hi", Radix::Binary, " ", false, false), "01001000 01101001");
+        assert_eq!(encode(b"\n", Radix::Binary, "", true, false), "0b00001010");
+    }
+
+    #[test]
+    fn test_default_separator() {
+        assert_eq!(encode(b"Hi", Radix::Hex, default_separator(Radix::Hex, true), true, false), "0x48 0x69");
+        assert_eq!(encode(b"Hi", Radix::Hex, default_separator(Radix::Hex, false), false, false), "4869");
+        assert_eq!(default_separator(Radix::Binary, false), " ");
+    }
+
+    #[test]
+    fn test_decode_hex() {
+        assert_eq!(decode("48656c6c6f", Radix::Hex).unwrap(), b"Hello");
+        assert_eq!(decode("48 65 6C 6c 6F", Radix::Hex).unwrap(), b"Hello");
+        assert_eq!(decode("0x48, 0x69", Radix::Hex).unwrap(), b"Hi");
+        assert_eq!(decode("\\x48\\x69", Radix::Hex).unwrap(), b"Hi");
+        assert_eq!(decode("de:ad:be:ef", Radix::Hex).unwrap(), [0xde, 0xad, 0xbe, 0xef]);
+        // Separated single digits are whole bytes
+        assert_eq!(decode("a 0f", Radix::Hex).unwrap(), [0x0a, 0x0f]);
+        assert!(decode("486", Radix::Hex).unwrap_err().contains("whole number"));
+        assert!(decode("48zz", Radix::Hex).unwrap_err().contains("'z'"));
+        assert!(decode("  ", Radix::Hex).is_err());
+    }
+
+    #[test]
+    fn test_decode_binary() {
+        assert_eq!(decode("0100100001101001", Radix::Binary).unwrap(), b"Hi");
+        assert_eq!(decode("01001000 01101001", Radix::Binary).unwrap(), b"Hi");
+        // 7-bit groups as often seen in puzzles
+        assert_eq!(decode("1001000 1101001", Radix::Binary).unwrap(), b"Hi");
+        assert_eq!(decode("0b01001000,0b01101001", Radix::Binary).unwrap(), b"Hi");
+        assert!(decode("010010", Radix::Binary).is_err());
+        assert!(decode("01001002", Radix::Binary).unwrap_err().contains("'2'"));
+    }
+}
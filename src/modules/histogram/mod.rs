@@ -0,0 +1,192 @@
+use crate::num_stats::extract_value;
+use crate::tool_module::{format_number, ToolModule};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use colored::*;
+use regex::Regex;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, IsTerminal};
+
+pub struct HistogramModule;
+
+const DEFAULT_BINS: usize = 10;
+const DEFAULT_CATEGORIES: usize = 20;
+const DEFAULT_WIDTH: usize = 40;
+// Eighth blocks let bars end between whole characters
+const PARTIAL_BLOCKS: [&str; 8] = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+#[derive(Debug, Clone, PartialEq)]
+struct Bucket {
+    label: String,
+    count: usize,
+}
+
+impl ToolModule for HistogramModule {
+    fn name(&self) -> &'static str {
+        "histogram"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("histogram")
+                .long("histogram")
+                .value_name("FILE")
+                .num_args(0..=1)
+                .default_missing_value("")
+                .help("Draw a bar chart of the values in FILE or stdin, one per line: numbers in equal-width bins, anything else counted per label")
+                .long_help("Draw a bar chart of the values in FILE (or stdin), one per line or pulled out with --extract. When every value is a number they are grouped into --bins equal-width ranges (default: 10); otherwise each distinct label gets a bar, most frequent first, with the rest beyond --bins (default: 20) rolled into one. Use --categorical to count numbers such as status codes as labels, and --width for the longest bar (default: 40).")
+        )
+        .arg(
+            Arg::new("bins")
+                .long("bins")
+                .value_name("N")
+                .value_parser(clap::value_parser!(u32).range(1..=1000))
+                .help("Number of --histogram ranges for numbers, or labels shown before the rest are grouped")
+                .requires("histogram")
+        )
+        .arg(
+            Arg::new("categorical")
+                .long("categorical")
+                .action(ArgAction::SetTrue)
+                .help("Count --histogram values as labels even when they are numbers")
+                .requires("histogram")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(source) = matches.get_one::<String>("histogram") {
+            // --extract is declared by the num-stats module and --width by the img-ascii module
+            let extract = matches.get_one::<String>("extract").map(|pattern| Regex::new(pattern).map_err(|e| format!("Invalid --extract regex: {}", e))).transpose()?;
+            let width = matches.get_one::<u32>("width").map_or(DEFAULT_WIDTH, |width| *width as usize);
+            let bins = matches.get_one::<u32>("bins").map(|bins| *bins as usize);
+            let reader: Box<dyn BufRead> = if source.is_empty() || source == "-" {
+                Box::new(BufReader::new(io::stdin()))
+            } else {
+                Box::new(BufReader::new(File::open(source).map_err(|e| format!("Failed to open '{}': {}", source, e))?))
+            };
+            let values = read_values(reader, extract.as_ref())?;
+            if values.is_empty() {
+                return Err("No values found in the input".into());
+            }
+            let numbers: Option<Vec<f64>> = if matches.get_flag("categorical") {
+                None
+            } else {
+                values.iter().map(|value| value.parse::<f64>().ok().filter(|number| number.is_finite())).collect()
+            };
+            let buckets = match numbers {
+                Some(numbers) => numeric_buckets(&numbers, bins.unwrap_or(DEFAULT_BINS)),
+                None => label_buckets(&values, bins.unwrap_or(DEFAULT_CATEGORIES)),
+            };
+            print!("{}", render(&buckets, values.len(), width, io::stdout().is_terminal()));
+        }
+        Ok(())
+    }
+}
+
+fn read_values(reader: impl BufRead, extract: Option<&Regex>) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut values = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if let Some(value) = extract_value(&line, extract).filter(|value| !value.is_empty()) {
+            values.push(value.to_string());
+        }
+    }
+    Ok(values)
+}
+
+// Equal-width ranges from the smallest to the largest value; the last one includes its upper edge
+fn numeric_buckets(numbers: &[f64], bins: usize) -> Vec<Bucket> {
+    let min = numbers.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = numbers.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if min == max {
+        return vec![Bucket { label: format_number(min, 4), count: numbers.len() }];
+    }
+    let step = (max - min) / bins as f64;
+    let mut counts = vec![0; bins];
+    for number in numbers {
+        counts[(((number - min) / step) as usize).min(bins - 1)] += 1;
+    }
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(index, count)| {
+            let (low, high) = (min + step * index as f64, if index + 1 == bins { max } else { min + step * (index + 1) as f64 });
+            let close = if index + 1 == bins { ']' } else { ')' };
+            Bucket { label: format!("[{}, {}{}", format_number(low, 4), format_number(high, 4), close), count }
+        })
+        .collect()
+}
+
+// Most frequent labels first (ties alphabetically), the rest beyond `limit` grouped together
+fn label_buckets(values: &[String], limit: usize) -> Vec<Bucket> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for value in values {
+        *counts.entry(value).or_default() += 1;
+    }
+    let mut buckets: Vec<Bucket> = counts.into_iter().map(|(label, count)| Bucket { label: label.to_string(), count }).collect();
+    buckets.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.label.cmp(&b.label)));
+    if buckets.len() > limit {
+        let rest = buckets.split_off(limit);
+        let count = rest.iter().map(|bucket| bucket.count).sum();
+        buckets.push(Bucket { label: format!("({} others)", rest.len()), count });
+    }
+    buckets
+}
+
+fn bar(count: usize, largest: usize, width: usize) -> String {
+    let eighths = if largest == 0 { 0 } else { (count as f64 / largest as f64 * (width * 8) as f64).round() as usize };
+    format!("{}{}", "█".repeat(eighths / 8), PARTIAL_BLOCKS[eighths % 8])
+}
+
+fn render(buckets: &[Bucket], total: usize, width: usize, color: bool) -> String {
+    let largest = buckets.iter().map(|bucket| bucket.count).max().unwrap_or(0);
+    let label_width = buckets.iter().map(|bucket| bucket.label.chars().count()).max().unwrap_or(0);
+    let count_width = largest.to_string().len();
+    buckets
+        .iter()
+        .map(|bucket| {
+            let bar = format!("{:<width$}", bar(bucket.count, largest, width), width = width);
+            let percent = format!("{:5.1}%", bucket.count as f64 / total as f64 * 100.0);
+            let (bar, percent) = if color { (bar.cyan().to_string(), percent.dimmed().to_string()) } else { (bar, percent) };
+            format!("{:<label_width$}  {} {:>count_width$} {}\n", bucket.label, bar, bucket.count, percent, label_width = label_width, count_width = count_width)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(buckets: &[Bucket]) -> Vec<usize> {
+        buckets.iter().map(|bucket| bucket.count).collect()
+    }
+
+    #[test]
+    fn test_numeric_buckets() {
+        let numbers: Vec<f64> = (0..=10).map(f64::from).collect();
+        let buckets = numeric_buckets(&numbers, 5);
+        assert_eq!(counts(&buckets), [2, 2, 2, 2, 3]);
+        assert_eq!(buckets[0].label, "[0, 2)");
+        assert_eq!(buckets[4].label, "[8, 10]");
+        assert_eq!(numeric_buckets(&[1.5, 1.5], 10), [Bucket { label: "1.5".to_string(), count: 2 }]);
+    }
+
+    #[test]
+    fn test_label_buckets() {
+        let values: Vec<String> = ["200", "404", "200", "500", "200", "404", "301"].iter().map(|value| value.to_string()).collect();
+        let buckets = label_buckets(&values, 2);
+        assert_eq!(buckets.iter().map(|bucket| (bucket.label.as_str(), bucket.count)).collect::<Vec<_>>(), [("200", 3), ("404", 2), ("(2 others)", 2)]);
+        let regex = Regex::new(r"status=(\d+)").unwrap();
+        assert_eq!(read_values("status=200\nnoise\nstatus=404\n".as_bytes(), Some(&regex)).unwrap(), ["200", "404"]);
+    }
+
+    #[test]
+    fn test_render() {
+        assert_eq!(bar(4, 4, 4), "████");
+        assert_eq!(bar(1, 4, 5), "█▎");
+        assert_eq!(bar(0, 4, 5), "");
+        let buckets = [Bucket { label: "a".to_string(), count: 3 }, Bucket { label: "bb".to_string(), count: 1 }];
+        assert_eq!(render(&buckets, 4, 6, false), "a   ██████ 3  75.0%\nbb  ██     1  25.0%\n");
+    }
+}
@@ -0,0 +1,295 @@
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io;
+use std::net::IpAddr;
+use std::path::PathBuf;
+
+pub struct HostsFileModule;
+
+const ACTIONS: [&str; 4] = ["list", "add", "remove", "toggle"];
+
+#[derive(Debug, Clone, PartialEq)]
+struct Entry {
+    enabled: bool,
+    ip: IpAddr,
+    names: Vec<String>,
+    comment: Option<String>,
+}
+
+// Lines that aren't entries, and entries nobody touched, are written back exactly as they were
+#[derive(Debug, Clone, PartialEq)]
+struct Line {
+    raw: String,
+    entry: Option<Entry>,
+}
+
+impl Entry {
+    // "127.0.0.1 api.local # note", or the same commented out to disable it
+    fn parse(line: &str) -> Option<Entry> {
+        let trimmed = line.trim();
+        let (enabled, rest) = match trimmed.strip_prefix('#') {
+            Some(rest) => (false, rest.trim_start()),
+            None => (true, trimmed),
+        };
+        let (content, comment) = match rest.split_once('#') {
+            Some((content, comment)) => (content, Some(comment.to_string())),
+            None => (rest, None),
+        };
+        let mut fields = content.split_whitespace();
+        // Plain comments don't start with an address
+        let ip = fields.next()?.parse().ok()?;
+        let names: Vec<String> = fields.map(str::to_string).collect();
+        if names.is_empty() || (!enabled && !names.iter().all(|name| valid_name(name))) {
+            return None;
+        }
+        Some(Entry { enabled, ip, names, comment })
+    }
+
+    fn render(&self) -> String {
+        let comment = self.comment.as_ref().map(|comment| format!(" #{}", comment)).unwrap_or_default();
+        format!("{}{}\t{}{}", if self.enabled { "" } else { "# " }, self.ip, self.names.join(" "), comment)
+    }
+}
+
+impl ToolModule for HostsFileModule {
+    fn name(&self) -> &'static str {
+        "hosts-file"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("hosts")
+                .long("hosts")
+                .value_name("ACTION")
+                .num_args(1..)
+                .help("Manage hosts file entries: list [PATTERN], add IP NAME..., remove NAME, toggle NAME")
+                .long_help("Manage entries in /etc/hosts (or the Windows hosts file, or $MICRO_SWISS_HOSTS):\n- list [PATTERN]: show entries, disabled ones too, optionally only those matching PATTERN\n- add IP NAME...: map the names to IP, re-enabling a disabled entry when there is one\n- remove NAME: drop NAME, and its line once no names are left\n- toggle NAME: comment out the line mapping NAME, or bring it back\nChanges need write access, usually sudo; the previous file is kept as hosts.micro-swiss.bak next to it.")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(values) = matches.get_many::<String>("hosts") {
+            let values: Vec<&str> = values.map(String::as_str).collect();
+            let path = hosts_path();
+            let text = fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            let mut lines = parse(&text);
+            let message = match values.as_slice() {
+                ["list"] => return list(&lines, None),
+                ["list", pattern] => return list(&lines, Some(pattern)),
+                ["add", ip, names @ ..] if !names.is_empty() => {
+                    let ip: IpAddr = ip.parse().map_err(|_| format!("'{}' is not an IP address", ip))?;
+                    add(&mut lines, ip, names)?
+                }
+                ["remove", name] => remove(&mut lines, name)?,
+                ["toggle", name] => toggle(&mut lines, name)?,
+                [action, ..] if ACTIONS.contains(action) => return Err(format!("Usage: --hosts list [PATTERN] | add IP NAME... | remove NAME | toggle NAME (got '{}')", values.join(" ")).into()),
+                [action, ..] => return Err(format!("Unknown --hosts action '{}'; use list, add, remove or toggle", action).into()),
+                [] => unreachable!("clap requires at least one value"),
+            };
+            let Some(message) = message else {
+                println!("Nothing to change in {}", path.display());
+                return Ok(());
+            };
+            let backup = save(&path, &text, &render(&lines, &text))?;
+            println!("✅ {} (previous file saved as {})", message, backup.display());
+        }
+        Ok(())
+    }
+}
+
+fn hosts_path() -> PathBuf {
+    if let Some(path) = std::env::var_os("MICRO_SWISS_HOSTS") {
+        return PathBuf::from(path);
+    }
+    if cfg!(windows) {
+        let root = std::env::var_os("SystemRoot").unwrap_or_else(|| "C:\\Windows".into());
+        PathBuf::from(root).join("System32").join("drivers").join("etc").join("hosts")
+    } else {
+        PathBuf::from("/etc/hosts")
+    }
+}
+
+fn valid_name(name: &str) -> bool {
+    !name.is_empty() && name.len() <= 253 && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+}
+
+fn parse(text: &str) -> Vec<Line> {
+    text.lines().map(|raw| Line { raw: raw.to_string(), entry: Entry::parse(raw) }).collect()
+}
+
+fn render(lines: &[Line], original: &str) -> String {
+    let mut text = lines.iter().map(|line| line.raw.as_str()).collect::<Vec<_>>().join(if original.contains("\r\n") { "\r\n" } else { "\n" });
+    if !lines.is_empty() && (original.ends_with('\n') || original.is_empty()) {
+        text.push_str(if original.contains("\r\n") { "\r\n" } else { "\n" });
+    }
+    text
+}
+
+fn list(lines: &[Line], pattern: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let pattern = pattern.map(str::to_lowercase);
+    let entries: Vec<&Entry> = lines
+        .iter()
+        .filter_map(|line| line.entry.as_ref())
+        .filter(|entry| pattern.as_ref().is_none_or(|pattern| entry.ip.to_string().contains(pattern.as_str()) || entry.names.iter().any(|name| name.to_lowercase().contains(pattern.as_str()))))
+        .collect();
+    if entries.is_empty() {
+        println!("No matching hosts entries");
+        return Ok(());
+    }
+    let width = entries.iter().map(|entry| entry.ip.to_string().len()).max().unwrap_or(0);
+    for entry in entries {
+        let ip = format!("{:<width$}", entry.ip.to_string(), width = width);
+        if entry.enabled {
+            println!("{} {}  {}", "●".green(), ip, entry.names.join(" ").bold());
+        } else {
+            println!("{} {}", "○".dimmed(), format!("{}  {}  (disabled)", ip, entry.names.join(" ")).dimmed());
+        }
+    }
+    Ok(())
+}
+
+fn entries_with<'a>(lines: &'a mut [Line], name: &str) -> impl Iterator<Item = &'a mut Line> {
+    let name = name.to_lowercase();
+    lines.iter_mut().filter(move |line| line.entry.as_ref().is_some_and(|entry| entry.names.iter().any(|n| n.to_lowercase() == name)))
+}
+
+// Returns what changed, or None when the entry is already there
+fn add(lines: &mut Vec<Line>, ip: IpAddr, names: &[&str]) -> Result<Option<String>, String> {
+    if let Some(name) = names.iter().find(|name| !valid_name(name)) {
+        return Err(format!("'{}' is not a valid host name", name));
+    }
+    let mut missing = Vec::new();
+    let mut enabled = Vec::new();
+    for name in names {
+        let mut found = false;
+        for line in entries_with(lines, name) {
+            let entry = line.entry.as_mut().expect("filtered on entries");
+            if entry.ip != ip {
+                if entry.enabled && entry.ip.is_ipv4() == ip.is_ipv4() {
+                    return Err(format!("{} already points to {}; remove it first with --hosts remove {}", name, entry.ip, name));
+                }
+                continue;
+            }
+            found = true;
+            if !entry.enabled {
+                entry.enabled = true;
+                line.raw = entry.render();
+                enabled.push(name.to_string());
+            }
+        }
+        if !found {
+            missing.push(name.to_string());
+        }
+    }
+    if !missing.is_empty() {
+        let entry = Entry { enabled: true, ip, names: missing.clone(), comment: None };
+        lines.push(Line { raw: entry.render(), entry: Some(entry) });
+    }
+    let mut changes = Vec::new();
+    if !missing.is_empty() {
+        changes.push(format!("Added {} {}", ip, missing.join(" ")));
+    }
+    if !enabled.is_empty() {
+        changes.push(format!("Re-enabled {} {}", ip, enabled.join(" ")));
+    }
+    Ok((!changes.is_empty()).then(|| changes.join("; ")))
+}
+
+fn remove(lines: &mut Vec<Line>, name: &str) -> Result<Option<String>, String> {
+    let mut removed = 0;
+    for line in entries_with(lines, name) {
+        let entry = line.entry.as_mut().expect("filtered on entries");
+        entry.names.retain(|n| !n.eq_ignore_ascii_case(name));
+        line.raw = entry.render();
+        removed += 1;
+    }
+    if removed == 0 {
+        return Err(format!("No hosts entry for {}", name));
+    }
+    lines.retain(|line| line.entry.as_ref().is_none_or(|entry| !entry.names.is_empty()));
+    Ok(Some(format!("Removed {} from {} entr{}", name, removed, if removed == 1 { "y" } else { "ies" })))
+}
+
+fn toggle(lines: &mut [Line], name: &str) -> Result<Option<String>, String> {
+    let mut changes = Vec::new();
+    for line in entries_with(lines, name) {
+        let entry = line.entry.as_mut().expect("filtered on entries");
+        entry.enabled = !entry.enabled;
+        line.raw = entry.render();
+        changes.push(format!("{} {} {}", if entry.enabled { "Enabled" } else { "Disabled" }, entry.ip, entry.names.join(" ")));
+    }
+    if changes.is_empty() {
+        return Err(format!("No hosts entry for {}", name));
+    }
+    Ok(Some(changes.join("; ")))
+}
+
+// Backs up the current file next to it, then rewrites it in place (it may be a bind mount)
+fn save(path: &PathBuf, original: &str, text: &str) -> Result<PathBuf, String> {
+    let denied = || {
+        let command: Vec<String> = std::env::args().collect();
+        let hint = if cfg!(windows) { "run it from an administrator terminal".to_string() } else { format!("re-run with sudo: sudo {}", command.join(" ")) };
+        format!("Editing {} needs administrator rights; {}", path.display(), hint)
+    };
+    let fail = |e: io::Error| if e.kind() == io::ErrorKind::PermissionDenied { denied() } else { format!("Failed to write {}: {}", path.display(), e) };
+    // Check before touching anything, so a normal user gets the hint rather than a half-done change
+    OpenOptions::new().append(true).open(path).map_err(fail)?;
+    let file_name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_else(|| "hosts".to_string());
+    let backup = path.with_file_name(format!("{}.micro-swiss.bak", file_name));
+    fs::write(&backup, original).map_err(|e| if e.kind() == io::ErrorKind::PermissionDenied { denied() } else { format!("Failed to write backup {}: {}", backup.display(), e) })?;
+    fs::write(path, text).map_err(fail)?;
+    Ok(backup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOSTS: &str = "# Static table lookup for hostnames.\n127.0.0.1\tlocalhost\n::1 localhost ip6-localhost\n# 10.0.0.5 staging.local\n192.168.1.9 nas.local printer.local # office\n";
+
+    #[test]
+    fn test_parse_entries() {
+        let lines = parse(HOSTS);
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0].entry, None);
+        assert_eq!(lines[3].entry, Some(Entry { enabled: false, ip: "10.0.0.5".parse().unwrap(), names: vec!["staging.local".to_string()], comment: None }));
+        assert_eq!(lines[4].entry.as_ref().unwrap().comment.as_deref(), Some(" office"));
+        assert_eq!(lines[4].entry.as_ref().unwrap().render(), "192.168.1.9\tnas.local printer.local # office");
+        assert_eq!(Entry::parse("# 2 of these are comments"), None);
+        assert_eq!(render(&lines, HOSTS), HOSTS);
+    }
+
+    #[test]
+    fn test_add_remove_toggle() {
+        let mut lines = parse(HOSTS);
+        let localhost: IpAddr = "127.0.0.1".parse().unwrap();
+        assert_eq!(add(&mut lines, localhost, &["api.local"]).unwrap().unwrap(), "Added 127.0.0.1 api.local");
+        assert_eq!(add(&mut lines, localhost, &["api.local"]).unwrap(), None);
+        assert!(add(&mut lines, "10.1.1.1".parse().unwrap(), &["api.local"]).unwrap_err().contains("already points to 127.0.0.1"));
+        assert!(add(&mut lines, localhost, &["bad name!"]).is_err());
+        assert_eq!(add(&mut lines, "10.0.0.5".parse().unwrap(), &["staging.local"]).unwrap().unwrap(), "Re-enabled 10.0.0.5 staging.local");
+
+        assert_eq!(toggle(&mut lines, "printer.local").unwrap().unwrap(), "Disabled 192.168.1.9 nas.local printer.local");
+        assert!(render(&lines, HOSTS).contains("# 192.168.1.9\tnas.local printer.local # office\n"));
+
+        assert_eq!(remove(&mut lines, "nas.local").unwrap().unwrap(), "Removed nas.local from 1 entry");
+        assert_eq!(remove(&mut lines, "printer.local").unwrap().unwrap(), "Removed printer.local from 1 entry");
+        assert!(remove(&mut lines, "printer.local").is_err());
+        assert_eq!(render(&lines, HOSTS), "# Static table lookup for hostnames.\n127.0.0.1\tlocalhost\n::1 localhost ip6-localhost\n10.0.0.5\tstaging.local\n127.0.0.1\tapi.local\n");
+    }
+
+    #[test]
+    fn test_save_with_backup() {
+        let dir = std::env::temp_dir().join(format!("ms-hosts-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("hosts");
+        fs::write(&path, HOSTS).unwrap();
+        let backup = save(&path, HOSTS, "127.0.0.1 localhost\n").unwrap();
+        assert_eq!(fs::read_to_string(&backup).unwrap(), HOSTS);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "127.0.0.1 localhost\n");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,388 @@
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgMatches, Command};
+use reqwest::blocking::Client;
+use scraper::{ElementRef, Html, Node, Selector};
+use std::error::Error;
+use std::fs;
+use std::io::{self, Read};
+use std::time::Duration;
+use url::Url;
+
+pub struct HtmlToMdModule;
+
+// Elements whose content never belongs in the Markdown
+const SKIPPED_ELEMENTS: [&str; 9] = ["head", "script", "style", "noscript", "template", "svg", "iframe", "canvas", "button"];
+
+impl ToolModule for HtmlToMdModule {
+    fn name(&self) -> &'static str {
+        "html-to-md"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("html-to-md")
+                .long("html-to-md")
+                .value_name("HTML|FILE|URL")
+                .help("Convert HTML to Markdown from text, a file, a URL or stdin")
+                .long_help("Convert HTML to Markdown: headings, paragraphs, emphasis, links, images, lists, blockquotes, tables and fenced code blocks. Takes HTML text (starting with <), a file path, an http(s) URL to fetch, or reads stdin when nothing is given. For fetched pages only the <article> or <main> content is converted when the page has one, and relative links are made absolute.")
+                .num_args(0..=1)
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if matches.contains_id("html-to-md") {
+            let source = matches.get_one::<String>("html-to-md").map(String::as_str);
+            let markdown = match source {
+                Some(url) if url.starts_with("http://") || url.starts_with("https://") => {
+                    let (html, final_url) = fetch_page(url)?;
+                    html_to_markdown(&html, Some(&final_url), true)
+                }
+                Some(html) if html.trim_start().starts_with('<') => html_to_markdown(html, None, false),
+                Some(path) if path != "-" => {
+                    let html = fs::read_to_string(path).map_err(|e| format!("Failed to read '{}': {}", path, e))?;
+                    html_to_markdown(&html, None, false)
+                }
+                _ => {
+                    let mut html = String::new();
+                    io::stdin().read_to_string(&mut html)?;
+                    html_to_markdown(&html, None, false)
+                }
+            };
+            println!("{}", markdown);
+        }
+        Ok(())
+    }
+}
+
+// Returns the page and the URL it was finally served from, for resolving links
+fn fetch_page(url: &str) -> Result<(String, Url), Box<dyn Error>> {
+    let client = Client::builder()
+        .user_agent(concat!("micro-swiss/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(30))
+        .build()?;
+    let response = client.get(url).send().map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to fetch {}: HTTP {}", url, response.status()).into());
+    }
+    let final_url = response.url().clone();
+    Ok((response.text()?, final_url))
+}
+
+fn html_to_markdown(html: &str, base_url: Option<&Url>, main_content_only: bool) -> String {
+    let document = Html::parse_document(html);
+    let mut root = document.root_element();
+    if main_content_only {
+        for name in ["article", "main"] {
+            if let Some(main) = Selector::parse(name).ok().and_then(|selector| document.select(&selector).next()) {
+                root = main;
+                break;
+            }
+        }
+    }
+    let converter = Converter { base_url };
+    let mut out = String::new();
+    converter.children(root, &mut out);
+    tidy(&out)
+}
+
+struct Converter<'a> {
+    base_url: Option<&'a Url>,
+}
+
+impl Converter<'_> {
+    fn children(&self, element: ElementRef, out: &mut String) {
+        for child in element.children() {
+            match child.value() {
+                Node::Text(text) => push_text(out, text),
+                Node::Element(_) => {
+                    if let Some(child) = ElementRef::wrap(child) {
+                        self.element(child, out);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Renders the children on their own, for content that gets wrapped or prefixed
+    fn inner(&self, element: ElementRef) -> String {
+        let mut out = String::new();
+        self.children(element, &mut out);
+        tidy(&out)
+    }
+
+    fn element(&self, element: ElementRef, out: &mut String) {
+        let name = element.value().name();
+        match name {
+            _ if SKIPPED_ELEMENTS.contains(&name) => {}
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = name[1..].parse().unwrap_or(1);
+                let text = self.inner(element).replace('\n', " ");
+                if !text.is_empty() {
+                    push_block(out, &format!("{} {}", "#".repeat(level), text));
+                }
+            }
+            "p" | "div" | "section" | "article" | "main" | "header" | "footer" | "nav" | "aside" | "figure"
+            | "figcaption" | "details" | "summary" | "address" | "dl" | "dt" | "dd" | "form" | "fieldset" => {
+                block_break(out);
+                self.children(element, out);
+                block_break(out);
+            }
+            "br" => {
+                trim_trailing_spaces(out);
+                out.push_str("  \n");
+            }
+            "hr" => push_block(out, "---"),
+            "strong" | "b" => wrap_inline(out, &self.inner(element), "**"),
+            "em" | "i" => wrap_inline(out, &self.inner(element), "*"),
+            "del" | "s" | "strike" => wrap_inline(out, &self.inner(element), "~~"),
+            "code" | "kbd" | "samp" => {
+                let code: String = element.text().collect();
+                let code = collapse_whitespace(&code);
+                let fence = if code.contains('`') { "``" } else { "`" };
+                push_inline(out, &format!("{}{}{}", fence, code.trim(), fence));
+            }
+            "a" => {
+                let text = self.inner(element).replace('\n', " ");
+                match element.value().attr("href").map(|href| self.resolve(href)) {
+                    Some(href) if !href.is_empty() && !href.starts_with("javascript:") && !text.is_empty() => {
+                        push_inline(out, &format!("[{}]({})", text, href));
+                    }
+                    _ => push_inline(out, &text),
+                }
+            }
+            "img" => {
+                if let Some(src) = element.value().attr("src") {
+                    let alt = element.value().attr("alt").unwrap_or("");
+                    push_inline(out, &format!("![{}]({})", escape_markdown(alt), self.resolve(src)));
+                }
+            }
+            "pre" => push_block(out, &code_block(element)),
+            "blockquote" => {
+                let quoted = self.inner(element)
+                    .lines()
+                    .map(|line| if line.is_empty() { ">".to_string() } else { format!("> {}", line) })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                push_block(out, &quoted);
+            }
+            "ul" | "ol" => push_block(out, &self.list(element, name == "ol")),
+            "table" => push_block(out, &self.table(element)),
+            _ => self.children(element, out),
+        }
+    }
+
+    fn list(&self, list: ElementRef, ordered: bool) -> String {
+        let start: usize = list.value().attr("start").and_then(|start| start.parse().ok()).unwrap_or(1);
+        list.child_elements()
+            .filter(|item| item.value().name() == "li")
+            .enumerate()
+            .map(|(i, item)| {
+                let marker = if ordered { format!("{}. ", start + i) } else { "- ".to_string() };
+                // Tight list: paragraphs inside an item stay on consecutive lines
+                let content = self.inner(item).replace("\n\n", "\n");
+                let indent = " ".repeat(marker.len());
+                let mut lines = content.lines();
+                let mut rendered = format!("{}{}", marker, lines.next().unwrap_or(""));
+                for line in lines {
+                    rendered.push('\n');
+                    if !line.is_empty() {
+                        rendered.push_str(&indent);
+                        rendered.push_str(line);
+                    }
+                }
+                rendered
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // The first row becomes the header; cells are flattened to one line
+    fn table(&self, table: ElementRef) -> String {
+        let Ok(row_selector) = Selector::parse("tr") else {
+            return String::new();
+        };
+        let rows: Vec<Vec<String>> = table.select(&row_selector)
+            .map(|row| {
+                row.child_elements()
+                    .filter(|cell| matches!(cell.value().name(), "td" | "th"))
+                    .map(|cell| self.inner(cell).replace('\n', " ").replace('|', "\\|"))
+                    .collect()
+            })
+            .filter(|cells: &Vec<String>| !cells.is_empty())
+            .collect();
+        let Some(columns) = rows.iter().map(Vec::len).max() else {
+            return String::new();
+        };
+        let line = |cells: &[String]| {
+            let padded: Vec<&str> = (0..columns).map(|i| cells.get(i).map_or("", String::as_str)).collect();
+            format!("| {} |", padded.join(" | "))
+        };
+        let mut lines = vec![line(&rows[0]), format!("|{}", " --- |".repeat(columns))];
+        lines.extend(rows[1..].iter().map(|row| line(row)));
+        lines.join("\n")
+    }
+
+    fn resolve(&self, href: &str) -> String {
+        match self.base_url.and_then(|base| base.join(href).ok()) {
+            Some(url) => url.to_string(),
+            None => href.to_string(),
+        }
+    }
+}
+
+// A fenced block, tagged with the language from a language-xxx class
+fn code_block(pre: ElementRef) -> String {
+    let code: String = pre.text().collect();
+    let language = std::iter::once(pre)
+        .chain(pre.child_elements().filter(|child| child.value().name() == "code"))
+        .flat_map(|element| element.value().classes())
+        .find_map(|class| class.strip_prefix("language-").or_else(|| class.strip_prefix("lang-")))
+        .unwrap_or("");
+    let fence = if code.contains("```") { "````" } else { "```" };
+    format!("{}{}\n{}\n{}", fence, language, code.trim_end_matches('\n'), fence)
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut in_space = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !in_space {
+                collapsed.push(' ');
+            }
+            in_space = true;
+        } else {
+            collapsed.push(c);
+            in_space = false;
+        }
+    }
+    collapsed
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '`' | '[' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+// Text is whitespace-collapsed; a space is dropped at the start of a line
+fn push_text(out: &mut String, text: &str) {
+    let text = escape_markdown(&collapse_whitespace(text));
+    let text = if out.is_empty() || out.ends_with([' ', '\n']) { text.trim_start() } else { &text };
+    out.push_str(text);
+}
+
+fn push_inline(out: &mut String, markdown: &str) {
+    if !markdown.is_empty() {
+        out.push_str(markdown);
+    }
+}
+
+// Emphasis markers must hug the text, so surrounding spaces move outside them
+fn wrap_inline(out: &mut String, text: &str, marker: &str) {
+    if !text.trim().is_empty() {
+        out.push_str(&format!("{}{}{}", marker, text.trim(), marker));
+    }
+}
+
+fn push_block(out: &mut String, block: &str) {
+    block_break(out);
+    out.push_str(block);
+    block_break(out);
+}
+
+fn block_break(out: &mut String) {
+    trim_trailing_spaces(out);
+    if !out.is_empty() {
+        while !out.ends_with("\n\n") {
+            out.push('\n');
+        }
+    }
+}
+
+fn trim_trailing_spaces(out: &mut String) {
+    let trimmed = out.trim_end_matches([' ', '\t']).len();
+    out.truncate(trimmed);
+}
+
+// Trailing spaces go (except hard breaks) and blank lines collapse to one
+fn tidy(markdown: &str) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    let mut in_fence = false;
+    for line in markdown.lines() {
+        if line.starts_with("```") {
+            in_fence = !in_fence;
+        }
+        let line = if in_fence || line.ends_with("  ") && !line.trim().is_empty() { line } else { line.trim_end() };
+        if !in_fence && line.is_empty() && lines.last().is_some_and(|last| last.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    lines.join("\n").trim_matches('\n').trim_end_matches(' ').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert(html: &str) -> String {
+        html_to_markdown(html, None, false)
+    }
+
+    #[test]
+    fn test_headings_and_inline() {
+        assert_eq!(
+            convert("<h1>Title</h1><p>Some <strong>bold</strong>, <em> emphasis </em> and <code>a*b</code>.</p><h3>Next</h3>"),
+            "# Title\n\nSome **bold**, *emphasis* and `a*b`.\n\n### Next"
+        );
+        assert_eq!(convert("<p>1 * 2 [x]\n   wrapped</p>"), "1 \\* 2 \\[x\\] wrapped");
+    }
+
+    #[test]
+    fn test_links_and_images() {
+        assert_eq!(
+            convert("<p><a href=\"https://example.com\">Example</a> <img src=\"a.png\" alt=\"Alt\"></p>"),
+            "[Example](https://example.com) ![Alt](a.png)"
+        );
+        let base = Url::parse("https://example.com/docs/page").unwrap();
+        assert_eq!(html_to_markdown("<a href=\"../guide\">Guide</a>", Some(&base), false), "[Guide](https://example.com/guide)");
+    }
+
+    #[test]
+    fn test_lists() {
+        assert_eq!(
+            convert("<ul><li>One</li><li>Two<ul><li>Nested</li></ul></li></ul><ol start=\"3\"><li>Three</li><li><p>Four</p></li></ol>"),
+            "- One\n- Two\n  - Nested\n\n3. Three\n4. Four"
+        );
+    }
+
+    #[test]
+    fn test_code_blocks_and_quotes() {
+        assert_eq!(
+            convert("<pre><code class=\"language-rust\">fn main() {\n    println!(\"hi\");\n}\n</code></pre><blockquote><p>Quoted</p><p>Twice</p></blockquote>"),
+            "```rust\nfn main() {\n    println!(\"hi\");\n}\n```\n\n> Quoted\n>\n> Twice"
+        );
+    }
+
+    #[test]
+    fn test_tables() {
+        assert_eq!(
+            convert("<table><thead><tr><th>Name</th><th>Role</th></tr></thead><tbody><tr><td>Ada</td><td>a|b</td></tr><tr><td>Linus</td></tr></tbody></table>"),
+            "| Name | Role |\n| --- | --- |\n| Ada | a\\|b |\n| Linus |  |"
+        );
+    }
+
+    #[test]
+    fn test_main_content_and_skipped_elements() {
+        let page = "<html><head><title>T</title><style>p{}</style></head><body><nav>Menu</nav><main><h2>Docs</h2><script>x()</script><p>Body</p></main></body></html>";
+        assert_eq!(html_to_markdown(page, None, true), "## Docs\n\nBody");
+        assert_eq!(convert(page), "Menu\n\n## Docs\n\nBody");
+    }
+}
@@ -0,0 +1,225 @@
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use reqwest::blocking::Client;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub struct HttpBenchModule;
+
+impl ToolModule for HttpBenchModule {
+    fn name(&self) -> &'static str {
+        "http-bench"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("http-bench")
+                .long("http-bench")
+                .value_name("URL")
+                .help("Load test a URL with GET requests and report latency percentiles and throughput")
+                .long_help("Send GET requests to URL from several workers at once and report throughput, latency percentiles (p50, p90, p95, p99) and errors. Responses outside 2xx and failed connections count as errors. Use --requests and --concurrency to size the run; each request times out after 30 seconds.")
+        )
+        .arg(
+            Arg::new("requests")
+                .long("requests")
+                .value_name("N")
+                .help("Total number of requests sent by --http-bench (default: 200)")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("200")
+                .requires("http-bench")
+        )
+        .arg(
+            Arg::new("concurrency")
+                .long("concurrency")
+                .value_name("N")
+                .help("Requests --http-bench keeps in flight (default: 10), or connections for --port-scan (default: 200)")
+                .value_parser(clap::value_parser!(usize))
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(url) = matches.get_one::<String>("http-bench") {
+            let requests = matches.get_one::<usize>("requests").copied().unwrap_or(200);
+            let concurrency = matches.get_one::<usize>("concurrency").copied().unwrap_or(10);
+            if requests == 0 || concurrency == 0 {
+                return Err("--requests and --concurrency must be at least 1".into());
+            }
+            let url = if url.contains("://") { url.clone() } else { format!("http://{}", url) };
+            println!("Benchmarking {} with {} requests, {} at a time...", url.bold(), requests, concurrency.min(requests));
+            let started = Instant::now();
+            let outcomes = run(&url, requests, concurrency)?;
+            print!("{}", report(&outcomes, started.elapsed()));
+        }
+        Ok(())
+    }
+}
+
+// One request: how long it took, and the status code or the transport error
+struct Outcome {
+    latency: Duration,
+    result: Result<u16, String>,
+}
+
+fn run(url: &str, requests: usize, concurrency: usize) -> Result<Vec<Outcome>, Box<dyn Error>> {
+    let client = Client::builder()
+        .user_agent(concat!("micro-swiss/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(30))
+        .build()?;
+    // Workers take the next request number until all have been sent
+    let next = Arc::new(AtomicUsize::new(0));
+    let workers: Vec<_> = (0..concurrency.min(requests))
+        .map(|_| {
+            let (client, next, url) = (client.clone(), Arc::clone(&next), url.to_string());
+            thread::spawn(move || {
+                let mut outcomes = Vec::new();
+                while next.fetch_add(1, Ordering::Relaxed) < requests {
+                    let started = Instant::now();
+                    let result = client
+                        .get(&url)
+                        .send()
+                        // Read the whole body so the timing covers the full response
+                        .and_then(|response| {
+                            let status = response.status().as_u16();
+                            response.bytes().map(|_| status)
+                        })
+                        .map_err(|e| error_kind(&e));
+                    outcomes.push(Outcome { latency: started.elapsed(), result });
+                }
+                outcomes
+            })
+        })
+        .collect();
+    let mut outcomes = Vec::with_capacity(requests);
+    for worker in workers {
+        outcomes.extend(worker.join().map_err(|_| "A benchmark worker panicked")?);
+    }
+    Ok(outcomes)
+}
+
+// Group transport errors by kind so a run of refused connections reads as one line
+fn error_kind(error: &reqwest::Error) -> String {
+    if error.is_timeout() {
+        "timeout".to_string()
+    } else if error.is_connect() {
+        "connection failed".to_string()
+    } else if error.is_body() || error.is_decode() {
+        "body read failed".to_string()
+    } else {
+        error.to_string()
+    }
+}
+
+// Nearest-rank percentile of sorted latencies
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn format_ms(duration: Duration) -> String {
+    format!("{:.2} ms", duration.as_secs_f64() * 1000.0)
+}
+
+fn report(outcomes: &[Outcome], elapsed: Duration) -> String {
+    let mut latencies: Vec<Duration> = outcomes.iter().map(|outcome| outcome.latency).collect();
+    latencies.sort();
+    let mut statuses: BTreeMap<u16, usize> = BTreeMap::new();
+    let mut errors: BTreeMap<&str, usize> = BTreeMap::new();
+    for outcome in outcomes {
+        match &outcome.result {
+            Ok(status) => *statuses.entry(*status).or_default() += 1,
+            Err(kind) => *errors.entry(kind.as_str()).or_default() += 1,
+        }
+    }
+    let succeeded: usize = statuses.iter().filter(|(status, _)| (200..300).contains(*status)).map(|(_, count)| count).sum();
+    let failed = outcomes.len() - succeeded;
+    let mean = if latencies.is_empty() { Duration::ZERO } else { latencies.iter().sum::<Duration>() / latencies.len() as u32 };
+
+    let mut output = String::new();
+    output.push_str(&format!("\nRequests:    {} in {:.2} s\n", outcomes.len(), elapsed.as_secs_f64()));
+    output.push_str(&format!("Throughput:  {:.1} req/s\n", outcomes.len() as f64 / elapsed.as_secs_f64().max(f64::EPSILON)));
+    output.push_str(&format!("Succeeded:   {}\n", succeeded.to_string().green()));
+    let failed = failed.to_string();
+    output.push_str(&format!("Errors:      {}\n", if failed == "0" { failed.normal() } else { failed.red() }));
+
+    output.push_str("\nLatency:\n");
+    output.push_str(&format!("  min   {}\n", format_ms(latencies.first().copied().unwrap_or_default())));
+    output.push_str(&format!("  mean  {}\n", format_ms(mean)));
+    for p in [50, 90, 95, 99] {
+        output.push_str(&format!("  p{:<5}{}\n", p, format_ms(percentile(&latencies, p as f64))));
+    }
+    output.push_str(&format!("  max   {}\n", format_ms(latencies.last().copied().unwrap_or_default())));
+
+    output.push_str("\nResponses:\n");
+    for (status, count) in &statuses {
+        output.push_str(&format!("  {}  {}\n", status, count));
+    }
+    for (kind, count) in &errors {
+        output.push_str(&format!("  {}  {}\n", kind, count));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(value: u64) -> Duration {
+        Duration::from_millis(value)
+    }
+
+    #[test]
+    fn test_percentile() {
+        let sorted: Vec<Duration> = (1..=100).map(ms).collect();
+        assert_eq!(percentile(&sorted, 50.0), ms(50));
+        assert_eq!(percentile(&sorted, 99.0), ms(99));
+        assert_eq!(percentile(&sorted, 0.0), ms(1));
+        assert_eq!(percentile(&[ms(7)], 95.0), ms(7));
+        assert_eq!(percentile(&[], 50.0), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_report() {
+        let outcomes = vec![
+            Outcome { latency: ms(10), result: Ok(200) },
+            Outcome { latency: ms(30), result: Ok(200) },
+            Outcome { latency: ms(20), result: Ok(503) },
+            Outcome { latency: ms(40), result: Err("timeout".to_string()) },
+        ];
+        let output = report(&outcomes, Duration::from_secs(2));
+        assert!(output.contains("Requests:    4 in 2.00 s"));
+        assert!(output.contains("Throughput:  2.0 req/s"));
+        assert!(output.contains("Succeeded:   2"));
+        assert!(output.contains("Errors:      2"));
+        assert!(output.contains("  mean  25.00 ms"));
+        assert!(output.contains("  p50   20.00 ms"));
+        assert!(output.contains("  200  2\n  503  1\n  timeout  1"));
+    }
+
+    #[test]
+    fn test_run_against_local_server() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            for stream in listener.incoming().take(6) {
+                let mut stream = stream.unwrap();
+                let mut buffer = [0; 1024];
+                let _ = stream.read(&mut buffer);
+                let _ = stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok");
+            }
+        });
+        let outcomes = run(&url, 6, 3).unwrap();
+        assert_eq!(outcomes.len(), 6);
+        assert!(outcomes.iter().all(|outcome| outcome.result == Ok(200)));
+    }
+}
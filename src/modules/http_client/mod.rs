@@ -0,0 +1,192 @@
+use crate::json_format::format_json_pretty;
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use colored::*;
+use reqwest::blocking::{Client, Response};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, CONTENT_TYPE};
+use reqwest::{Method, StatusCode};
+use std::error::Error;
+use std::time::{Duration, Instant};
+
+pub struct HttpClientModule;
+
+const METHODS: [&str; 7] = ["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"];
+
+impl ToolModule for HttpClientModule {
+    fn name(&self) -> &'static str {
+        "http-client"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("http")
+                .long("http")
+                .value_names(["METHOD", "URL"])
+                .num_args(2)
+                .help("Send an HTTP request and show the status, timing, headers and body")
+                .long_help("Send an HTTP request (GET, POST, PUT, PATCH, DELETE, HEAD or OPTIONS) and print the status line, how long it took, the response headers and the body. JSON bodies are pretty-printed. Redirects are followed and the request times out after 30 seconds. The URL scheme defaults to https:// when left out.")
+        )
+        .arg(
+            Arg::new("header")
+                .long("header")
+                .short('H')
+                .value_name("NAME: VALUE")
+                .help("Add a request header to --http (repeatable)")
+                .action(ArgAction::Append)
+                .requires("http")
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .value_name("BODY")
+                .help("Send BODY as JSON with --http, setting Content-Type and Accept")
+                .requires("http")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(values) = matches.get_many::<String>("http") {
+            let values: Vec<&String> = values.collect();
+            let method = parse_method(values[0])?;
+            let url = normalize_url(values[1]);
+            let mut headers = HeaderMap::new();
+            if let Some(body) = matches.get_one::<String>("json") {
+                serde_json::from_str::<serde_json::Value>(body).map_err(|e| format!("Invalid JSON body: {}", e))?;
+                headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+                headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+            }
+            // Explicit --header values win over the defaults set for --json
+            for header in matches.get_many::<String>("header").into_iter().flatten() {
+                let (name, value) = parse_header(header)?;
+                headers.insert(name, value);
+            }
+
+            let client = Client::builder()
+                .user_agent(concat!("micro-swiss/", env!("CARGO_PKG_VERSION")))
+                .timeout(Duration::from_secs(30))
+                .build()?;
+            let mut request = client.request(method.clone(), &url).headers(headers);
+            if let Some(body) = matches.get_one::<String>("json") {
+                request = request.body(body.clone());
+            }
+
+            let started = Instant::now();
+            let response = request.send().map_err(|e| format!("Request to {} failed: {}", url, e))?;
+            let elapsed = started.elapsed();
+            print_response(response, &method, elapsed)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_method(method: &str) -> Result<Method, String> {
+    let upper = method.to_uppercase();
+    if !METHODS.contains(&upper.as_str()) {
+        return Err(format!("Unsupported method '{}'. Use one of: {}", method, METHODS.join(", ")));
+    }
+    Method::from_bytes(upper.as_bytes()).map_err(|e| e.to_string())
+}
+
+// "example.com/api" -> "https://example.com/api"; localhost defaults to plain http
+fn normalize_url(url: &str) -> String {
+    if url.contains("://") {
+        url.to_string()
+    } else if url.starts_with("localhost") || url.starts_with("127.0.0.1") {
+        format!("http://{}", url)
+    } else {
+        format!("https://{}", url)
+    }
+}
+
+// "Authorization: Bearer abc" -> (authorization, "Bearer abc")
+fn parse_header(header: &str) -> Result<(HeaderName, HeaderValue), String> {
+    let (name, value) = header
+        .split_once(':')
+        .ok_or_else(|| format!("Invalid header '{}'. Use the form 'Name: value'", header))?;
+    let name = HeaderName::from_bytes(name.trim().as_bytes()).map_err(|_| format!("Invalid header name '{}'", name.trim()))?;
+    let value = HeaderValue::from_str(value.trim()).map_err(|_| format!("Invalid value for header '{}'", name))?;
+    Ok((name, value))
+}
+
+fn status_line(status: StatusCode) -> ColoredString {
+    let text = format!("{} {}", status.as_u16(), status.canonical_reason().unwrap_or(""));
+    match status.as_u16() {
+        200..=299 => text.green().bold(),
+        300..=399 => text.yellow().bold(),
+        _ => text.red().bold(),
+    }
+}
+
+// Pretty-print JSON bodies, whether or not the server labelled them as JSON
+fn format_body(body: &str, content_type: Option<&str>) -> String {
+    let looks_like_json = content_type.is_some_and(|value| value.contains("json"))
+        || matches!(body.trim_start().chars().next(), Some('{') | Some('['));
+    if looks_like_json {
+        if let Ok(pretty) = format_json_pretty(body) {
+            return pretty;
+        }
+    }
+    body.to_string()
+}
+
+fn print_response(response: Response, method: &Method, elapsed: Duration) -> Result<(), Box<dyn Error>> {
+    println!(
+        "{} {} {}",
+        status_line(response.status()),
+        format!("{:?}", response.version()).dimmed(),
+        format!("({} ms)", elapsed.as_millis()).dimmed()
+    );
+    for (name, value) in response.headers() {
+        println!("{}: {}", name.as_str().cyan(), value.to_str().unwrap_or("<binary>"));
+    }
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    if method == Method::HEAD {
+        return Ok(());
+    }
+    let body = response.text()?;
+    if !body.is_empty() {
+        println!();
+        println!("{}", format_body(&body, content_type.as_deref()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_method() {
+        assert_eq!(parse_method("get").unwrap(), Method::GET);
+        assert_eq!(parse_method("POST").unwrap(), Method::POST);
+        assert!(parse_method("FETCH").is_err());
+    }
+
+    #[test]
+    fn test_parse_header() {
+        let (name, value) = parse_header("Authorization: Bearer a:b").unwrap();
+        assert_eq!(name, "authorization");
+        assert_eq!(value, "Bearer a:b");
+        assert!(parse_header("no colon").is_err());
+        assert!(parse_header("bad name: x").is_err());
+    }
+
+    #[test]
+    fn test_normalize_url() {
+        assert_eq!(normalize_url("example.com/api"), "https://example.com/api");
+        assert_eq!(normalize_url("localhost:8080/health"), "http://localhost:8080/health");
+        assert_eq!(normalize_url("http://example.com"), "http://example.com");
+    }
+
+    #[test]
+    fn test_format_body() {
+        assert_eq!(format_body(r#"{"ok":true}"#, Some("application/json")), "{\n  \"ok\": true\n}");
+        assert_eq!(format_body("[1]", None), "[\n  1\n]");
+        assert_eq!(format_body("{not json", Some("application/json")), "{not json");
+        assert_eq!(format_body("<p>hi</p>", Some("text/html")), "<p>hi</p>");
+    }
+}
@@ -0,0 +1,191 @@
+use crate::timestamp::relative_time;
+use crate::tool_module::ToolModule;
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use std::error::Error;
+
+pub struct IdDecodeModule;
+
+// Epochs in unix milliseconds and the names each platform gives the bits after the timestamp
+const SNOWFLAKE_EPOCHS: [(&str, &str, i64, [&str; 3]); 2] = [
+    ("twitter", "Twitter/X", 1_288_834_974_657, ["Datacenter:", "Worker:", "Sequence:"]),
+    ("discord", "Discord", 1_420_070_400_000, ["Worker:", "Process:", "Increment:"]),
+];
+// Clock skew allowed before a decoded time counts as in the future
+const FUTURE_SLACK_MS: i64 = 86_400_000;
+
+#[derive(Debug, PartialEq)]
+struct Snowflake {
+    // Unix milliseconds
+    timestamp: i64,
+    // The two 5-bit machine fields and the 12-bit per-millisecond counter
+    fields: [u64; 3],
+}
+
+#[derive(Debug, PartialEq)]
+struct ObjectId {
+    // Unix seconds
+    timestamp: u32,
+    // Fixed per machine and process
+    random: [u8; 5],
+    counter: u32,
+}
+
+impl ToolModule for IdDecodeModule {
+    fn name(&self) -> &'static str {
+        "id-decode"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("id-decode")
+                .long("id-decode")
+                .value_name("ID")
+                .help("Decode a Twitter/Discord snowflake or MongoDB ObjectId: creation time, machine bits and sequence")
+                .long_help("Decode the parts of a time-based ID. Snowflakes (64-bit numbers used by Twitter/X and Discord) hold a millisecond timestamp, two 5-bit machine fields and a 12-bit sequence; the epoch differs per platform, so both are tried and any reading that lands in the future is dropped. MongoDB ObjectIds (24 hex characters, ObjectId(\"...\") works too) hold a creation time in seconds, a per-process random value and a counter.")
+        )
+        .arg(
+            Arg::new("snowflake-epoch")
+                .long("snowflake-epoch")
+                .value_name("EPOCH")
+                .help("Epoch for --id-decode snowflakes: twitter, discord or unix milliseconds (default: try both)")
+                .requires("id-decode")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(value) = matches.get_one::<String>("id-decode") {
+            let value = value.trim();
+            let inner = value.strip_prefix("ObjectId(").and_then(|rest| rest.strip_suffix(')')).map_or(value, |inner| inner.trim_matches(['"', '\'']));
+            if let Some(object_id) = parse_object_id(inner) {
+                print_object_id(&object_id);
+                return Ok(());
+            }
+            let id: u64 = match value.parse() {
+                Ok(id) => id,
+                Err(_) if !value.is_empty() && value.bytes().all(|byte| byte.is_ascii_digit()) => {
+                    return Err(format!("{} is too large for a 64-bit snowflake", value).into())
+                }
+                Err(_) => return Err(format!("'{}' is neither a snowflake (a 64-bit number) nor a MongoDB ObjectId (24 hex characters)", value).into()),
+            };
+            let now = Utc::now().timestamp_millis();
+            let readings: Vec<(String, Snowflake, [&str; 3])> = match matches.get_one::<String>("snowflake-epoch") {
+                Some(epoch) => {
+                    let (name, epoch, labels) = parse_epoch(epoch)?;
+                    vec![(name, decode_snowflake(id, epoch), labels)]
+                }
+                None => SNOWFLAKE_EPOCHS
+                    .iter()
+                    .map(|(_, name, epoch, labels)| (name.to_string(), decode_snowflake(id, *epoch), *labels))
+                    .filter(|(_, snowflake, _)| snowflake.timestamp <= now + FUTURE_SLACK_MS)
+                    .collect(),
+            };
+            if readings.is_empty() {
+                return Err(format!("{} decodes to a future time with every known epoch; pass --snowflake-epoch", id).into());
+            }
+            if readings.len() > 1 {
+                println!("⚠️  Valid with either epoch; pass --snowflake-epoch twitter or discord to pick one\n");
+            }
+            for (index, (name, snowflake, labels)) in readings.iter().enumerate() {
+                if index > 0 {
+                    println!();
+                }
+                println!("{:<14}Snowflake, {} epoch", "Type:".cyan(), name);
+                print_time(snowflake.timestamp);
+                for (label, value) in labels.iter().zip(snowflake.fields) {
+                    println!("{:<14}{}", label.cyan(), value);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// "twitter", "discord" or a custom epoch in unix milliseconds
+fn parse_epoch(value: &str) -> Result<(String, i64, [&'static str; 3]), String> {
+    if let Some((_, name, epoch, labels)) = SNOWFLAKE_EPOCHS.iter().find(|(key, ..)| key.eq_ignore_ascii_case(value)) {
+        return Ok((name.to_string(), *epoch, *labels));
+    }
+    let epoch: i64 = value.parse().map_err(|_| format!("Invalid epoch '{}'; use twitter, discord or unix milliseconds", value))?;
+    Ok((format!("custom {}", epoch), epoch, SNOWFLAKE_EPOCHS[0].3))
+}
+
+// 41 bits of milliseconds since the epoch, 5 + 5 machine bits, 12 sequence bits
+fn decode_snowflake(id: u64, epoch: i64) -> Snowflake {
+    Snowflake { timestamp: (id >> 22) as i64 + epoch, fields: [(id >> 17) & 0x1f, (id >> 12) & 0x1f, id & 0xfff] }
+}
+
+fn parse_object_id(value: &str) -> Option<ObjectId> {
+    if value.len() != 24 || !value.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+    let bytes: Vec<u8> = (0..24).step_by(2).map(|i| u8::from_str_radix(&value[i..i + 2], 16).unwrap()).collect();
+    Some(ObjectId {
+        timestamp: u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        random: [bytes[4], bytes[5], bytes[6], bytes[7], bytes[8]],
+        counter: u32::from_be_bytes([0, bytes[9], bytes[10], bytes[11]]),
+    })
+}
+
+fn print_object_id(object_id: &ObjectId) {
+    println!("{:<14}MongoDB ObjectId", "Type:".cyan());
+    print_time(object_id.timestamp as i64 * 1000);
+    let random: String = object_id.random.iter().map(|byte| format!("{:02x}", byte)).collect();
+    println!("{:<14}{} (fixed per machine and process)", "Random:".cyan(), random);
+    println!("{:<14}{} (0x{:06x})", "Counter:".cyan(), object_id.counter, object_id.counter);
+}
+
+fn print_time(millis: i64) {
+    match DateTime::from_timestamp_millis(millis) {
+        Some(datetime) => {
+            let relative = relative_time(datetime.timestamp() - Utc::now().timestamp());
+            println!("{:<14}{} ({})", "Created:".cyan(), datetime.to_rfc3339_opts(SecondsFormat::Millis, true), relative);
+            println!("{:<14}{}", "Local:".cyan(), datetime.with_timezone(&Local).to_rfc3339_opts(SecondsFormat::Millis, false));
+        }
+        None => println!("{:<14}out of range ({} ms)", "Created:".cyan(), millis),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn created(millis: i64) -> String {
+        DateTime::from_timestamp_millis(millis).unwrap().to_rfc3339_opts(SecondsFormat::Millis, true)
+    }
+
+    #[test]
+    fn test_decode_twitter_snowflake() {
+        let snowflake = decode_snowflake(1_212_092_628_029_698_048, SNOWFLAKE_EPOCHS[0].2);
+        assert_eq!(created(snowflake.timestamp), "2019-12-31T19:26:16.771Z");
+        assert_eq!(snowflake.fields, [10, 7, 0]);
+        // The timestamp alone, with every other bit clear
+        assert_eq!(decode_snowflake(1 << 22, 0), Snowflake { timestamp: 1, fields: [0, 0, 0] });
+    }
+
+    #[test]
+    fn test_decode_discord_snowflake() {
+        // The example from Discord's API reference
+        let snowflake = decode_snowflake(175_928_847_299_117_063, SNOWFLAKE_EPOCHS[1].2);
+        assert_eq!(created(snowflake.timestamp), "2016-04-30T11:18:25.796Z");
+        assert_eq!(snowflake.fields, [1, 0, 7]);
+    }
+
+    #[test]
+    fn test_parse_object_id() {
+        let object_id = parse_object_id("507f1f77bcf86cd799439011").unwrap();
+        assert_eq!(object_id.timestamp, 1_350_508_407);
+        assert_eq!(object_id.random, [0xbc, 0xf8, 0x6c, 0xd7, 0x99]);
+        assert_eq!(object_id.counter, 0x439011);
+        assert!(parse_object_id("507f1f77bcf86cd79943901").is_none());
+        assert!(parse_object_id("507f1f77bcf86cd79943901g").is_none());
+    }
+
+    #[test]
+    fn test_parse_epoch() {
+        assert_eq!(parse_epoch("Discord").unwrap().1, 1_420_070_400_000);
+        assert_eq!(parse_epoch("1288834974657").unwrap().0, "custom 1288834974657");
+        assert!(parse_epoch("instagram").is_err());
+    }
+}
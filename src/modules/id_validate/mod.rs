@@ -0,0 +1,546 @@
+use crate::card_check::luhn_valid;
+use crate::fake_data::iban_remainder;
+use crate::tool_module::{ExitCodeError, ToolModule};
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use regex::Regex;
+use std::error::Error;
+use std::io::{self, BufRead, IsTerminal};
+
+pub struct IdValidateModule;
+
+const KINDS: [&str; 4] = ["iban", "phone", "isbn", "vat"];
+
+// IBAN lengths from the SWIFT IBAN registry
+const IBAN_COUNTRIES: [(&str, &str, usize); 79] = [
+    ("AD", "Andorra", 24), ("AE", "United Arab Emirates", 23), ("AL", "Albania", 28), ("AT", "Austria", 20),
+    ("AZ", "Azerbaijan", 28), ("BA", "Bosnia and Herzegovina", 20), ("BE", "Belgium", 16), ("BG", "Bulgaria", 22),
+    ("BH", "Bahrain", 22), ("BR", "Brazil", 29), ("BY", "Belarus", 28), ("CH", "Switzerland", 21),
+    ("CR", "Costa Rica", 22), ("CY", "Cyprus", 28), ("CZ", "Czech Republic", 24), ("DE", "Germany", 22),
+    ("DK", "Denmark", 18), ("DO", "Dominican Republic", 28), ("EE", "Estonia", 20), ("EG", "Egypt", 29),
+    ("ES", "Spain", 24), ("FI", "Finland", 18), ("FO", "Faroe Islands", 18), ("FR", "France", 27),
+    ("GB", "United Kingdom", 22), ("GE", "Georgia", 22), ("GI", "Gibraltar", 23), ("GL", "Greenland", 18),
+    ("GR", "Greece", 27), ("GT", "Guatemala", 28), ("HR", "Croatia", 21), ("HU", "Hungary", 28),
+    ("IE", "Ireland", 22), ("IL", "Israel", 23), ("IQ", "Iraq", 23), ("IS", "Iceland", 26),
+    ("IT", "Italy", 27), ("JO", "Jordan", 30), ("KW", "Kuwait", 30), ("KZ", "Kazakhstan", 20),
+    ("LB", "Lebanon", 28), ("LC", "Saint Lucia", 32), ("LI", "Liechtenstein", 21), ("LT", "Lithuania", 20),
+    ("LU", "Luxembourg", 20), ("LV", "Latvia", 21), ("MC", "Monaco", 27), ("MD", "Moldova", 24),
+    ("ME", "Montenegro", 22), ("MK", "North Macedonia", 19), ("MR", "Mauritania", 27), ("MT", "Malta", 31),
+    ("MU", "Mauritius", 30), ("NL", "Netherlands", 18), ("NO", "Norway", 15), ("PK", "Pakistan", 24),
+    ("PL", "Poland", 28), ("PS", "Palestine", 29), ("PT", "Portugal", 25), ("QA", "Qatar", 29),
+    ("RO", "Romania", 24), ("RS", "Serbia", 22), ("SA", "Saudi Arabia", 24), ("SC", "Seychelles", 31),
+    ("SE", "Sweden", 24), ("SI", "Slovenia", 19), ("SK", "Slovakia", 24), ("SM", "San Marino", 27),
+    ("ST", "São Tomé and Príncipe", 25), ("SV", "El Salvador", 28), ("TL", "Timor-Leste", 23), ("TN", "Tunisia", 24),
+    ("TR", "Turkey", 26), ("UA", "Ukraine", 29), ("VA", "Vatican City", 22), ("VG", "British Virgin Islands", 24),
+    ("XK", "Kosovo", 20), ("LY", "Libya", 25), ("SD", "Sudan", 18),
+];
+
+// A country's numbering plan: calling code, ISO code, name, the trunk prefix
+// dialled before national numbers (dropped in E.164) and the length range of
+// the national significant number
+struct PhonePlan {
+    code: &'static str,
+    iso: &'static str,
+    name: &'static str,
+    trunk: &'static str,
+    min: usize,
+    max: usize,
+}
+
+const fn plan(code: &'static str, iso: &'static str, name: &'static str, trunk: &'static str, min: usize, max: usize) -> PhonePlan {
+    PhonePlan { code, iso, name, trunk, min, max }
+}
+
+// The first plan with a calling code wins when looking numbers up, so +1 is
+// reported as the North American Numbering Plan rather than Canada
+const PHONE_PLANS: [PhonePlan; 56] = [
+    plan("1", "US", "United States / Canada (NANP)", "1", 10, 10),
+    plan("1", "CA", "Canada", "1", 10, 10),
+    plan("7", "RU", "Russia", "8", 10, 10),
+    plan("20", "EG", "Egypt", "0", 8, 10),
+    plan("27", "ZA", "South Africa", "0", 9, 9),
+    plan("30", "GR", "Greece", "", 10, 10),
+    plan("31", "NL", "Netherlands", "0", 9, 9),
+    plan("32", "BE", "Belgium", "0", 8, 9),
+    plan("33", "FR", "France", "0", 9, 9),
+    plan("34", "ES", "Spain", "", 9, 9),
+    plan("36", "HU", "Hungary", "06", 8, 9),
+    plan("39", "IT", "Italy", "", 6, 11),
+    plan("40", "RO", "Romania", "0", 9, 9),
+    plan("41", "CH", "Switzerland", "0", 9, 9),
+    plan("43", "AT", "Austria", "0", 4, 13),
+    plan("44", "GB", "United Kingdom", "0", 7, 10),
+    plan("45", "DK", "Denmark", "", 8, 8),
+    plan("46", "SE", "Sweden", "0", 6, 12),
+    plan("47", "NO", "Norway", "", 8, 8),
+    plan("48", "PL", "Poland", "", 9, 9),
+    plan("49", "DE", "Germany", "0", 5, 13),
+    plan("51", "PE", "Peru", "0", 8, 9),
+    plan("52", "MX", "Mexico", "", 10, 10),
+    plan("54", "AR", "Argentina", "0", 10, 11),
+    plan("55", "BR", "Brazil", "0", 10, 11),
+    plan("56", "CL", "Chile", "", 9, 9),
+    plan("57", "CO", "Colombia", "0", 8, 10),
+    plan("60", "MY", "Malaysia", "0", 8, 10),
+    plan("61", "AU", "Australia", "0", 9, 9),
+    plan("62", "ID", "Indonesia", "0", 8, 12),
+    plan("63", "PH", "Philippines", "0", 8, 10),
+    plan("64", "NZ", "New Zealand", "0", 8, 10),
+    plan("65", "SG", "Singapore", "", 8, 8),
+    plan("66", "TH", "Thailand", "0", 8, 9),
+    plan("81", "JP", "Japan", "0", 9, 10),
+    plan("82", "KR", "South Korea", "0", 8, 10),
+    plan("84", "VN", "Vietnam", "0", 9, 10),
+    plan("86", "CN", "China", "0", 9, 11),
+    plan("90", "TR", "Turkey", "0", 10, 10),
+    plan("91", "IN", "India", "0", 10, 10),
+    plan("92", "PK", "Pakistan", "0", 9, 10),
+    plan("98", "IR", "Iran", "0", 10, 10),
+    plan("212", "MA", "Morocco", "0", 9, 9),
+    plan("234", "NG", "Nigeria", "0", 8, 10),
+    plan("254", "KE", "Kenya", "0", 9, 9),
+    plan("351", "PT", "Portugal", "", 9, 9),
+    plan("352", "LU", "Luxembourg", "", 4, 11),
+    plan("353", "IE", "Ireland", "0", 7, 9),
+    plan("358", "FI", "Finland", "0", 5, 12),
+    plan("380", "UA", "Ukraine", "0", 9, 9),
+    plan("420", "CZ", "Czech Republic", "", 9, 9),
+    plan("421", "SK", "Slovakia", "0", 9, 9),
+    plan("966", "SA", "Saudi Arabia", "0", 8, 9),
+    plan("971", "AE", "United Arab Emirates", "0", 8, 9),
+    plan("972", "IL", "Israel", "0", 8, 9),
+    plan("974", "QA", "Qatar", "", 8, 8),
+];
+
+// ISBN registration groups (prefix + group), longest match wins
+const ISBN_GROUPS: [(&str, &str); 31] = [
+    ("9780", "English language"), ("9781", "English language"), ("9782", "French language"), ("9783", "German language"),
+    ("9784", "Japan"), ("9785", "Russian language (former USSR)"), ("9787", "China"), ("97880", "Czech Republic / Slovakia"),
+    ("97881", "India"), ("97882", "Norway"), ("97883", "Poland"), ("97884", "Spain"), ("97885", "Brazil"),
+    ("97886", "Former Yugoslavia"), ("97887", "Denmark"), ("97888", "Italy"), ("97889", "South Korea"),
+    ("97890", "Netherlands / Flanders"), ("97891", "Sweden"), ("97892", "International organizations"), ("97893", "India"),
+    ("97894", "Netherlands"), ("978600", "Iran"), ("978605", "Turkey"), ("978607", "Mexico"), ("97865", "Brazil"),
+    ("978951", "Finland"), ("97910", "France"), ("97911", "South Korea"), ("97912", "Italy"), ("9798", "United States"),
+];
+
+// EU VAT identifiers (Greece uses EL) plus the UK, Northern Ireland, Switzerland and Norway
+const VAT_COUNTRIES: [(&str, &str, &str); 31] = [
+    ("AT", "Austria", r"U\d{8}"),
+    ("BE", "Belgium", r"[01]\d{9}"),
+    ("BG", "Bulgaria", r"\d{9,10}"),
+    ("CY", "Cyprus", r"\d{8}[A-Z]"),
+    ("CZ", "Czech Republic", r"\d{8,10}"),
+    ("DE", "Germany", r"\d{9}"),
+    ("DK", "Denmark", r"\d{8}"),
+    ("EE", "Estonia", r"\d{9}"),
+    ("EL", "Greece", r"\d{9}"),
+    ("ES", "Spain", r"[A-Z0-9]\d{7}[A-Z0-9]"),
+    ("FI", "Finland", r"\d{8}"),
+    ("FR", "France", r"[A-HJ-NP-Z0-9]{2}\d{9}"),
+    ("HR", "Croatia", r"\d{11}"),
+    ("HU", "Hungary", r"\d{8}"),
+    ("IE", "Ireland", r"\d{7}[A-W][A-I]?|\d[A-Z+*]\d{5}[A-W]"),
+    ("IT", "Italy", r"\d{11}"),
+    ("LT", "Lithuania", r"\d{9}|\d{12}"),
+    ("LU", "Luxembourg", r"\d{8}"),
+    ("LV", "Latvia", r"\d{11}"),
+    ("MT", "Malta", r"\d{8}"),
+    ("NL", "Netherlands", r"\d{9}B\d{2}"),
+    ("PL", "Poland", r"\d{10}"),
+    ("PT", "Portugal", r"\d{9}"),
+    ("RO", "Romania", r"\d{2,10}"),
+    ("SE", "Sweden", r"\d{10}01"),
+    ("SI", "Slovenia", r"\d{8}"),
+    ("SK", "Slovakia", r"\d{10}"),
+    ("GB", "United Kingdom", r"\d{9}|\d{12}|GD[0-4]\d{2}|HA[5-9]\d{2}"),
+    ("XI", "Northern Ireland", r"\d{9}|\d{12}|GD[0-4]\d{2}|HA[5-9]\d{2}"),
+    ("CH", "Switzerland", r"E\d{9}(?:MWST|TVA|IVA|TPV)?"),
+    ("NO", "Norway", r"\d{9}(?:MVA)?"),
+];
+
+#[derive(Debug, PartialEq)]
+struct Validated {
+    // The canonical form, printed alone in batch mode
+    normalized: String,
+    fields: Vec<(&'static str, String)>,
+}
+
+impl ToolModule for IdValidateModule {
+    fn name(&self) -> &'static str {
+        "id-validate"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("validate")
+                .long("validate")
+                .value_names(["KIND", "VALUE"])
+                .num_args(1..=2)
+                .help("Validate and normalize an identifier: iban, phone, isbn or vat; stdin for a list")
+                .long_help("Verify an identifier's checksum and format, detect its country and print it normalized:\n\n  iban   ISO 13616 mod-97 check digits and the country's IBAN length\n  phone  numbering plan length, printed in E.164 (+4930123456); numbers without a country code need --region\n  isbn   ISBN-10 or ISBN-13 check digit, converted to ISBN-13 and the registration group shown\n  vat    EU (Greece as EL), UK, Swiss and Norwegian VAT formats; check digits where the rule is public\n\nWithout VALUE, values are read from stdin one per line for data cleaning: valid ones are printed normalized to stdout and invalid ones, with the reason, to stderr, e.g.\n  ms --validate phone --region GB < numbers.txt > e164.txt")
+        )
+        .arg(
+            Arg::new("region")
+                .long("region")
+                .value_name("COUNTRY")
+                .help("Country (ISO code such as DE or GB) for --validate phone numbers written without a country code")
+                .requires("validate")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(values) = matches.get_many::<String>("validate") {
+            let values: Vec<&str> = values.map(String::as_str).collect();
+            let kind = values[0].to_lowercase();
+            if !KINDS.contains(&kind.as_str()) {
+                return Err(format!("Unknown identifier kind '{}'. Use one of: {}", values[0], KINDS.join(", ")).into());
+            }
+            let region = matches.get_one::<String>("region").map(|region| region.to_uppercase());
+            if let Some(region) = &region {
+                if !PHONE_PLANS.iter().any(|plan| plan.iso == region) {
+                    return Err(format!("Unknown region '{}' for phone numbers", region).into());
+                }
+            }
+            let validate = |value: &str| match kind.as_str() {
+                "iban" => validate_iban(value),
+                "phone" => validate_phone(value, region.as_deref()),
+                "isbn" => validate_isbn(value),
+                _ => validate_vat(value),
+            };
+
+            if let Some(value) = values.get(1) {
+                match validate(value) {
+                    Ok(validated) => print!("{}", render(&kind, &validated, io::stdout().is_terminal())),
+                    Err(e) => {
+                        eprintln!("❌ Invalid {}: {}", label(&kind), e);
+                        return Err(Box::new(ExitCodeError::silent(1)));
+                    }
+                }
+                return Ok(());
+            }
+
+            let (mut valid, mut invalid) = (0, 0);
+            for line in io::stdin().lock().lines() {
+                let line = line?;
+                let value = line.trim();
+                if value.is_empty() || value.starts_with('#') {
+                    continue;
+                }
+                match validate(value) {
+                    Ok(validated) => {
+                        valid += 1;
+                        println!("{}", validated.normalized);
+                    }
+                    Err(e) => {
+                        invalid += 1;
+                        eprintln!("❌ {}  {}", value, e);
+                    }
+                }
+            }
+            eprintln!("✅ {} valid, {} invalid", valid, invalid);
+        }
+        Ok(())
+    }
+}
+
+fn label(kind: &str) -> &'static str {
+    match kind {
+        "iban" => "IBAN",
+        "phone" => "phone number",
+        "isbn" => "ISBN",
+        _ => "VAT number",
+    }
+}
+
+// Uppercased, with spaces and common separators removed
+fn compact(value: &str, separators: &[char]) -> String {
+    value.chars().filter(|c| !c.is_whitespace() && !separators.contains(c)).collect::<String>().to_uppercase()
+}
+
+fn validate_iban(value: &str) -> Result<Validated, String> {
+    let iban = compact(value, &['-', '.']);
+    let iban = iban.strip_prefix("IBAN").unwrap_or(&iban).trim_start_matches(':').to_string();
+    if !iban.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err("an IBAN only contains letters and digits".to_string());
+    }
+    let country = iban.get(..2).unwrap_or_default();
+    let (_, name, length) = IBAN_COUNTRIES
+        .iter()
+        .find(|(code, _, _)| *code == country)
+        .ok_or_else(|| format!("'{}' is not a country that uses IBANs", country))?;
+    if iban.len() != *length {
+        return Err(format!("{} IBANs have {} characters, this one has {}", name, length, iban.len()));
+    }
+    if !iban[2..4].chars().all(|c| c.is_ascii_digit()) || iban_remainder(&format!("{}{}", &iban[4..], &iban[..4])) != 1 {
+        return Err("check digits do not match (mod-97)".to_string());
+    }
+    let grouped: Vec<String> = iban.as_bytes().chunks(4).map(|chunk| String::from_utf8_lossy(chunk).to_string()).collect();
+    Ok(Validated {
+        fields: vec![
+            ("Formatted", grouped.join(" ")),
+            ("Country", format!("{} ({})", name, country)),
+            ("Check digits", iban[2..4].to_string()),
+            ("BBAN", iban[4..].to_string()),
+        ],
+        normalized: iban,
+    })
+}
+
+fn validate_phone(value: &str, region: Option<&str>) -> Result<Validated, String> {
+    let value = value.trim();
+    if let Some(c) = value.chars().find(|c| !c.is_ascii_digit() && !" -./()+".contains(*c)) {
+        return Err(format!("unexpected character '{}'", c));
+    }
+    let digits: String = value.chars().filter(|c| c.is_ascii_digit()).collect();
+    let international = if value.starts_with('+') {
+        Some(digits.as_str())
+    } else {
+        digits.strip_prefix("00")
+    };
+    let (plan, national) = match international {
+        Some(number) => {
+            let plan = (1..=3)
+                .rev()
+                .find_map(|length| number.get(..length).and_then(|code| PHONE_PLANS.iter().find(|plan| plan.code == code)))
+                .ok_or_else(|| format!("unknown country calling code in '{}'", value))?;
+            (plan, number[plan.code.len()..].to_string())
+        }
+        None => {
+            let region = region.ok_or("no country code; write it as +49... or pass --region")?;
+            let plan = PHONE_PLANS.iter().find(|plan| plan.iso == region).ok_or_else(|| format!("unknown region '{}'", region))?;
+            let national = if plan.trunk.is_empty() { digits.as_str() } else { digits.strip_prefix(plan.trunk).unwrap_or(&digits) };
+            (plan, national.to_string())
+        }
+    };
+    if national.len() < plan.min || national.len() > plan.max {
+        let expected = if plan.min == plan.max { plan.min.to_string() } else { format!("{} to {}", plan.min, plan.max) };
+        return Err(format!("{} numbers have {} digits after +{}, this one has {}", plan.name, expected, plan.code, national.len()));
+    }
+    // NANP area codes and exchanges never start with 0 or 1
+    if plan.code == "1" && (national.starts_with(['0', '1']) || national[3..].starts_with(['0', '1'])) {
+        return Err("North American area codes and exchanges start with 2-9".to_string());
+    }
+    Ok(Validated {
+        normalized: format!("+{}{}", plan.code, national),
+        fields: vec![
+            ("International", format!("+{} {}", plan.code, national)),
+            ("Country", format!("{} ({})", plan.name, plan.iso)),
+            ("National", format!("{}{}", plan.trunk, national)),
+        ],
+    })
+}
+
+fn validate_isbn(value: &str) -> Result<Validated, String> {
+    let isbn = compact(value, &['-']);
+    let isbn = isbn.strip_prefix("ISBN").unwrap_or(&isbn);
+    let isbn = isbn.strip_prefix("13:").or_else(|| isbn.strip_prefix("10:")).unwrap_or(isbn).trim_start_matches(':');
+    let digits: Vec<u32> = isbn
+        .chars()
+        .enumerate()
+        .map(|(index, c)| if c == 'X' && index == 9 && isbn.len() == 10 { Some(10) } else { c.to_digit(10) })
+        .collect::<Option<_>>()
+        .ok_or("an ISBN only contains digits (and a final X for ISBN-10)")?;
+    let isbn13 = match digits.len() {
+        10 => {
+            let sum: u32 = digits.iter().enumerate().map(|(index, digit)| (10 - index as u32) * digit).sum();
+            if !sum.is_multiple_of(11) {
+                return Err("ISBN-10 check digit does not match".to_string());
+            }
+            let body = format!("978{}", &isbn[..9]);
+            format!("{}{}", body, isbn13_check(&body))
+        }
+        13 => {
+            if !isbn.starts_with("978") && !isbn.starts_with("979") {
+                return Err("ISBN-13 starts with 978 or 979".to_string());
+            }
+            if isbn13_check(&isbn[..12]) != digits[12] {
+                return Err("ISBN-13 check digit does not match".to_string());
+            }
+            isbn.to_string()
+        }
+        length => return Err(format!("an ISBN has 10 or 13 digits, this one has {}", length)),
+    };
+    let mut fields = Vec::new();
+    if let Some(body) = isbn13.strip_prefix("978") {
+        let sum: u32 = body[..9].chars().filter_map(|c| c.to_digit(10)).enumerate().map(|(index, digit)| (10 - index as u32) * digit).sum();
+        let check = (11 - sum % 11) % 11;
+        let check = if check == 10 { "X".to_string() } else { check.to_string() };
+        fields.push(("ISBN-10", format!("{}{}", &body[..9], check)));
+    }
+    let group = ISBN_GROUPS.iter().filter(|(prefix, _)| isbn13.starts_with(prefix)).max_by_key(|(prefix, _)| prefix.len());
+    fields.push(("Group", group.map(|(_, name)| name.to_string()).unwrap_or_else(|| "unknown registration group".to_string())));
+    Ok(Validated { normalized: isbn13, fields })
+}
+
+// Weights alternate 1 and 3 over the first twelve digits
+fn isbn13_check(body: &str) -> u32 {
+    let sum: u32 = body.chars().filter_map(|c| c.to_digit(10)).enumerate().map(|(index, digit)| if index % 2 == 0 { digit } else { digit * 3 }).sum();
+    (10 - sum % 10) % 10
+}
+
+fn validate_vat(value: &str) -> Result<Validated, String> {
+    let vat = compact(value, &['-', '.', '/']);
+    let (prefix, number) = vat.split_at(vat.char_indices().nth(2).map(|(index, _)| index).unwrap_or(vat.len()));
+    // Greek VAT numbers use EL rather than the ISO code
+    let prefix = if prefix == "GR" { "EL" } else { prefix };
+    let (country, name, pattern) = VAT_COUNTRIES
+        .iter()
+        .find(|(code, _, _)| *code == prefix)
+        .ok_or_else(|| format!("'{}' is not a known VAT country prefix (e.g. DE, FR, EL, GB)", prefix))?;
+    let format = Regex::new(&format!("^(?:{})$", pattern)).map_err(|e| e.to_string())?;
+    if !format.is_match(number) {
+        return Err(format!("does not match the {} VAT number format", name));
+    }
+    let checksum = match vat_checksum(country, number) {
+        Some(true) => "verified",
+        Some(false) => return Err(format!("{} VAT check digit does not match", name)),
+        None => "format only (no public check digit rule)",
+    };
+    let normalized = format!("{}{}", country, number);
+    Ok(Validated {
+        fields: vec![("Country", format!("{} ({})", name, country)), ("Checksum", checksum.to_string())],
+        normalized,
+    })
+}
+
+fn digits_of(number: &str) -> Vec<u32> {
+    number.chars().filter_map(|c| c.to_digit(10)).collect()
+}
+
+fn weighted(digits: &[u32], weights: &[u32]) -> u32 {
+    digits.iter().zip(weights).map(|(digit, weight)| digit * weight).sum()
+}
+
+// ISO 7064 MOD 11,10 over all but the last digit
+fn mod_11_10_valid(digits: &[u32]) -> bool {
+    let (check, body) = digits.split_last().unwrap_or((&0, &[]));
+    let product = body.iter().fold(10, |product, digit| {
+        let sum = (digit + product) % 10;
+        (if sum == 0 { 10 } else { sum }) * 2 % 11
+    });
+    (11 - product) % 10 == *check
+}
+
+// 11 - (weighted sum mod 11), where 11 becomes 0 and 10 is never issued
+fn mod_11_check(digits: &[u32], weights: &[u32]) -> Option<u32> {
+    match 11 - weighted(digits, weights) % 11 {
+        11 => Some(0),
+        10 => None,
+        check => Some(check),
+    }
+}
+
+// Some(valid) for countries with a published check digit rule
+fn vat_checksum(country: &str, number: &str) -> Option<bool> {
+    let digits = digits_of(number);
+    let valid = match country {
+        "AT" => {
+            let sum: u32 = digits[..7].iter().enumerate().map(|(index, digit)| if index % 2 == 1 { digit * 2 / 10 + digit * 2 % 10 } else { *digit }).sum();
+            (10 - (sum + 4) % 10) % 10 == digits[7]
+        }
+        "BE" => 97 - number[..8].parse::<u32>().ok()? % 97 == number[8..].parse::<u32>().ok()?,
+        "DE" | "HR" => mod_11_10_valid(&digits),
+        "DK" => weighted(&digits, &[2, 7, 6, 5, 4, 3, 2, 1]).is_multiple_of(11),
+        "EE" => (10 - weighted(&digits, &[3, 7, 1, 3, 7, 1, 3, 7]) % 10) % 10 == digits[8],
+        "EL" => weighted(&digits, &[256, 128, 64, 32, 16, 8, 4, 2]) % 11 % 10 == digits[8],
+        "FI" => mod_11_check(&digits, &[7, 9, 10, 5, 8, 4, 2]) == Some(digits[7]),
+        "FR" => {
+            let key = number[..2].parse::<u64>().ok()?;
+            (12 + 3 * (number[2..].parse::<u64>().ok()? % 97)) % 97 == key
+        }
+        "IT" | "SE" => luhn_valid(&number[..if country == "SE" { 10 } else { 11 }]),
+        "LU" => number[..6].parse::<u32>().ok()? % 89 == number[6..].parse::<u32>().ok()?,
+        // Sole proprietors' numbers since 2020 pass mod-97 over the whole
+        // identifier instead of the classic weighted check
+        "NL" => weighted(&digits, &[9, 8, 7, 6, 5, 4, 3, 2]) % 11 == digits[8] || iban_remainder(&format!("NL{}", number)) == 1,
+        "PL" => (weighted(&digits, &[6, 5, 7, 2, 3, 4, 5, 6, 7]) % 11) == digits[9],
+        "PT" => (11 - weighted(&digits, &[9, 8, 7, 6, 5, 4, 3, 2]) % 11) % 11 % 10 == digits[8],
+        "SI" => match 11 - weighted(&digits, &[8, 7, 6, 5, 4, 3, 2]) % 11 {
+            11 => false,
+            check => check % 10 == digits[7],
+        },
+        "GB" | "XI" if digits.len() == 9 => {
+            let total = weighted(&digits, &[8, 7, 6, 5, 4, 3, 2]) + digits[7] * 10 + digits[8];
+            total.is_multiple_of(97) || (total + 55).is_multiple_of(97)
+        }
+        "CH" => mod_11_check(&digits, &[5, 4, 3, 2, 7, 6, 5, 4]) == Some(digits[8]),
+        "NO" => mod_11_check(&digits, &[3, 2, 7, 6, 5, 4, 3, 2]) == Some(digits[8]),
+        _ => return None,
+    };
+    Some(valid)
+}
+
+fn render(kind: &str, validated: &Validated, color: bool) -> String {
+    let paint = |text: &str| if color { text.cyan().to_string() } else { text.to_string() };
+    let width = validated.fields.iter().map(|(name, _)| name.len() + 1).max().unwrap_or(0).max("Normalized:".len());
+    let mut output = format!("✅ Valid {}\n", label(kind));
+    output.push_str(&format!("{}{} {}\n", paint("Normalized:"), " ".repeat(width - "Normalized:".len()), validated.normalized));
+    for (name, value) in &validated.fields {
+        let name = format!("{}:", name);
+        output.push_str(&format!("{}{} {}\n", paint(&name), " ".repeat(width - name.len()), value));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iban() {
+        let validated = validate_iban("de89 3704 0044 0532 0130 00").unwrap();
+        assert_eq!(validated.normalized, "DE89370400440532013000");
+        assert_eq!(validated.fields[0], ("Formatted", "DE89 3704 0044 0532 0130 00".to_string()));
+        assert_eq!(validated.fields[1], ("Country", "Germany (DE)".to_string()));
+        assert!(validate_iban("IBAN GB82 WEST 1234 5698 7654 32").is_ok());
+        assert_eq!(validate_iban("GB82WEST12345698765431").unwrap_err(), "check digits do not match (mod-97)");
+        assert_eq!(validate_iban("DE8937040044053201300").unwrap_err(), "Germany IBANs have 22 characters, this one has 21");
+        assert!(validate_iban("ZZ12").is_err());
+    }
+
+    #[test]
+    fn test_phone() {
+        assert_eq!(validate_phone("+49 (30) 1234-5678", None).unwrap().normalized, "+493012345678");
+        assert_eq!(validate_phone("0044 20 7946 0958", None).unwrap().normalized, "+442079460958");
+        assert_eq!(validate_phone("020 7946 0958", Some("GB")).unwrap().normalized, "+442079460958");
+        assert_eq!(validate_phone("06 12 34 56 78", Some("FR")).unwrap().normalized, "+33612345678");
+        // Italian numbers keep their leading 0
+        assert_eq!(validate_phone("06 6982 1234", Some("IT")).unwrap().normalized, "+390669821234");
+        assert_eq!(validate_phone("(212) 555-0123", Some("US")).unwrap().fields[1].1, "United States / Canada (NANP) (US)");
+        assert!(validate_phone("(212) 055-0123", Some("US")).is_err());
+        assert!(validate_phone("+33 6 12 34", None).unwrap_err().starts_with("France numbers have 9 digits"));
+        assert!(validate_phone("030 1234567", None).unwrap_err().starts_with("no country code"));
+        assert!(validate_phone("+49 30 CALL-ME", None).is_err());
+    }
+
+    #[test]
+    fn test_isbn() {
+        let validated = validate_isbn("ISBN 0-306-40615-2").unwrap();
+        assert_eq!(validated.normalized, "9780306406157");
+        assert_eq!(validated.fields, vec![("ISBN-10", "0306406152".to_string()), ("Group", "English language".to_string())]);
+        assert_eq!(validate_isbn("978-3-16-148410-0").unwrap().fields[1].1, "German language");
+        assert_eq!(validate_isbn("080442957X").unwrap().normalized, "9780804429573");
+        assert!(validate_isbn("979-10-90636-07-1").unwrap().fields.iter().all(|(name, _)| *name != "ISBN-10"));
+        assert_eq!(validate_isbn("978-3-16-148410-1").unwrap_err(), "ISBN-13 check digit does not match");
+        assert!(validate_isbn("12345").is_err());
+    }
+
+    #[test]
+    fn test_vat() {
+        for valid in ["DE136695976", "ATU13585627", "BE0403170701", "IT00743110157", "FR40303265045", "NL004495445B01", "GB980780684", "DK13585628", "FI20774740", "PL5260250274", "CHE-116.281.710 MWST", "EL094259216"] {
+            let validated = validate_vat(valid).unwrap_or_else(|e| panic!("{}: {}", valid, e));
+            assert_eq!(validated.fields[1].1, "verified", "{}", valid);
+        }
+        assert_eq!(validate_vat("GR094259216").unwrap().normalized, "EL094259216");
+        assert_eq!(validate_vat("DE136695977").unwrap_err(), "Germany VAT check digit does not match");
+        assert_eq!(validate_vat("DE13669597").unwrap_err(), "does not match the Germany VAT number format");
+        assert_eq!(validate_vat("CZ25123891").unwrap().fields[1].1, "format only (no public check digit rule)");
+        assert!(validate_vat("US123456789").is_err());
+    }
+
+    #[test]
+    fn test_render() {
+        let validated = validate_isbn("0306406152").unwrap();
+        assert_eq!(render("isbn", &validated, false), "✅ Valid ISBN\nNormalized: 9780306406157\nISBN-10:    0306406152\nGroup:      English language\n");
+    }
+}
@@ -0,0 +1,158 @@
+use crate::img_convert::open_upright;
+use crate::tool_module::ToolModule;
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+use image::imageops::FilterType;
+use image::{Rgba, RgbaImage};
+use std::error::Error;
+
+pub struct ImgAsciiModule;
+
+// From empty to dense, so bright pixels get dense characters on a dark terminal
+const RAMP: &[u8] = b" .:-=+*#%@";
+const DEFAULT_WIDTH: u32 = 80;
+// Terminal cells are roughly twice as tall as they are wide
+const CELL_ASPECT: f64 = 2.0;
+
+impl ToolModule for ImgAsciiModule {
+    fn name(&self) -> &'static str {
+        "img-ascii"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("img-ascii")
+                .long("img-ascii")
+                .value_name("FILE")
+                .help("Render an image as ASCII art, or as 24-bit color half-block art with --color")
+                .long_help("Render an image in the terminal: as ASCII characters by brightness, or with --color as 24-bit ANSI half blocks (two pixels per character cell), which needs a truecolor terminal. Handy for previewing images over SSH. Use --width to set the size and --invert on light-background terminals.")
+        )
+        .arg(
+            Arg::new("width")
+                .long("width")
+                .value_name("N")
+                .value_parser(value_parser!(u32).range(1..=1000))
+                .help("Width of --img-ascii output in characters (default: 80), bytes per --hexdump line (default: 16), where --banner wraps (default: 80), or the longest --histogram bar (default: 40)")
+        )
+        .arg(
+            Arg::new("color")
+                .long("color")
+                .help("Render --img-ascii with 24-bit ANSI colors and half blocks")
+                .action(ArgAction::SetTrue)
+                .requires("img-ascii")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(path) = matches.get_one::<String>("img-ascii") {
+            let image = open_upright(path)?;
+            let width = matches.get_one::<u32>("width").copied().unwrap_or(DEFAULT_WIDTH);
+            let color = matches.get_flag("color");
+            // Half blocks fit two pixel rows into each line
+            let rows_per_line = if color { 2.0 } else { 1.0 };
+            let (columns, rows) = grid_size(image.width(), image.height(), width, rows_per_line);
+            let pixels = image.resize_exact(columns, rows, FilterType::Triangle).to_rgba8();
+            if color {
+                println!("{}", render_color(&pixels));
+            } else {
+                println!("{}", render_ascii(&pixels, matches.get_flag("invert")));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Pixel grid for `width` columns, keeping the aspect ratio once drawn in terminal cells
+fn grid_size(image_width: u32, image_height: u32, width: u32, rows_per_line: f64) -> (u32, u32) {
+    let lines = (image_height as f64 / image_width as f64 * width as f64 / CELL_ASPECT).round().max(1.0);
+    (width, (lines * rows_per_line) as u32)
+}
+
+// Relative luminance (0.0-1.0) with transparency blended towards black
+fn brightness(pixel: &Rgba<u8>) -> f64 {
+    let [red, green, blue, alpha] = pixel.0.map(|channel| channel as f64 / 255.0);
+    (0.2126 * red + 0.7152 * green + 0.0722 * blue) * alpha
+}
+
+fn render_ascii(pixels: &RgbaImage, invert: bool) -> String {
+    pixels
+        .rows()
+        .map(|row| {
+            let line: String = row
+                .map(|pixel| {
+                    let level = if invert { 1.0 - brightness(pixel) } else { brightness(pixel) };
+                    // Fully transparent pixels stay blank either way
+                    let level = if pixel.0[3] == 0 { 0.0 } else { level };
+                    RAMP[(level * (RAMP.len() - 1) as f64).round() as usize] as char
+                })
+                .collect();
+            line.trim_end().to_string()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// '▀' in the top pixel's color over the bottom pixel's color; transparent pixels show the terminal background
+fn render_color(pixels: &RgbaImage) -> String {
+    let transparent = Rgba([0, 0, 0, 0]);
+    let mut lines = Vec::new();
+    for y in (0..pixels.height()).step_by(2) {
+        let mut line = String::new();
+        for x in 0..pixels.width() {
+            let top = pixels.get_pixel(x, y);
+            let bottom = if y + 1 < pixels.height() { pixels.get_pixel(x, y + 1) } else { &transparent };
+            let [tr, tg, tb, ta] = top.0;
+            let [br, bg, bb, ba] = bottom.0;
+            line.push_str(&match (ta >= 128, ba >= 128) {
+                (true, true) => format!("\x1b[38;2;{};{};{}m\x1b[48;2;{};{};{}m▀", tr, tg, tb, br, bg, bb),
+                (true, false) => format!("\x1b[0m\x1b[38;2;{};{};{}m▀", tr, tg, tb),
+                (false, true) => format!("\x1b[0m\x1b[38;2;{};{};{}m▄", br, bg, bb),
+                (false, false) => "\x1b[0m ".to_string(),
+            });
+        }
+        line.push_str("\x1b[0m");
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_size() {
+        assert_eq!(grid_size(1920, 1080, 80, 1.0), (80, 23));
+        assert_eq!(grid_size(1920, 1080, 80, 2.0), (80, 46));
+        assert_eq!(grid_size(100, 100, 40, 1.0), (40, 20));
+        // Very wide images still get one line
+        assert_eq!(grid_size(10_000, 10, 80, 2.0), (80, 2));
+    }
+
+    #[test]
+    fn test_render_ascii() {
+        let pixels = RgbaImage::from_fn(4, 2, |x, y| match (x, y) {
+            (0, _) => Rgba([255, 255, 255, 255]),
+            (1, _) => Rgba([128, 128, 128, 255]),
+            (2, 0) => Rgba([255, 255, 255, 0]),
+            _ => Rgba([0, 0, 0, 255]),
+        });
+        assert_eq!(render_ascii(&pixels, false), "@+\n@+");
+        // Transparent pixels stay blank when inverted, black ones become dense
+        assert_eq!(render_ascii(&pixels, true), " = @\n =@@");
+    }
+
+    #[test]
+    fn test_render_color() {
+        let pixels = RgbaImage::from_fn(2, 3, |x, y| match (x, y) {
+            (1, 1) => Rgba([0, 0, 0, 0]),
+            (_, 0) => Rgba([255, 0, 0, 255]),
+            _ => Rgba([0, 0, 255, 255]),
+        });
+        let rendered = render_color(&pixels);
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], "\x1b[38;2;255;0;0m\x1b[48;2;0;0;255m▀\x1b[0m\x1b[38;2;255;0;0m▀\x1b[0m");
+        // The last line has no bottom pixels, so only the top half is drawn
+        assert_eq!(lines[1], "\x1b[0m\x1b[38;2;0;0;255m▀\x1b[0m\x1b[38;2;0;0;255m▀\x1b[0m");
+    }
+}
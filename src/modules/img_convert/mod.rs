@@ -0,0 +1,184 @@
+use crate::file_size::format_bytes;
+use crate::tool_module::ToolModule;
+use clap::{value_parser, Arg, ArgMatches, Command};
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, Rgba, RgbaImage};
+use std::error::Error;
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+
+pub struct ImgConvertModule;
+
+const DEFAULT_JPEG_QUALITY: u8 = 85;
+
+impl ToolModule for ImgConvertModule {
+    fn name(&self) -> &'static str {
+        "img-convert"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("img-convert")
+                .long("img-convert")
+                .value_names(["IN", "OUT"])
+                .num_args(2)
+                .help("Convert an image to the format of OUT's extension (png, jpg, webp, gif, bmp, tiff, ico)")
+                .long_help("Convert an image to the format given by OUT's extension: png, jpg/jpeg, webp (lossless), gif, bmp, tiff or ico. Phone photos are turned upright using their EXIF orientation, and transparency is flattened onto white for JPEG. Combine with --resize and --quality; OUT is never overwritten.")
+        )
+        .arg(
+            Arg::new("resize")
+                .long("resize")
+                .value_name("SIZE")
+                .help("Resize for --img-convert: 800x (width), x600 (height), 800x600 (fit inside), 800x600! (exact) or 50%")
+                .requires("img-convert")
+        )
+        .arg(
+            Arg::new("quality")
+                .long("quality")
+                .value_name("1-100")
+                .value_parser(value_parser!(u8).range(1..=100))
+                .help("JPEG quality for --img-convert (default: 85)")
+                .requires("img-convert")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(paths) = matches.get_many::<String>("img-convert") {
+            let paths: Vec<&String> = paths.collect();
+            let (input, output) = (paths[0], paths[1]);
+            let format = ImageFormat::from_path(output)
+                .ok()
+                .filter(|format| format.writing_enabled())
+                .ok_or_else(|| format!("Cannot write '{}': use a .png, .jpg, .webp, .gif, .bmp, .tiff or .ico file name", output))?;
+            if Path::new(output).exists() {
+                return Err(format!("{} already exists; not overwriting it", output).into());
+            }
+            let image = open_upright(input)?;
+            let (width, height) = (image.width(), image.height());
+            let image = match matches.get_one::<String>("resize") {
+                Some(spec) => {
+                    let (target_width, target_height) = target_size(spec, width, height)?;
+                    image.resize_exact(target_width, target_height, FilterType::Lanczos3)
+                }
+                None => image,
+            };
+            let quality = matches.get_one::<u8>("quality").copied();
+            if quality.is_some() && format != ImageFormat::Jpeg {
+                eprintln!("⚠️  --quality only applies to JPEG output; {} is written losslessly", output);
+            }
+            let bytes = encode(&image, format, quality.unwrap_or(DEFAULT_JPEG_QUALITY)).map_err(|e| format!("Failed to encode {}: {}", output, e))?;
+            fs::write(output, &bytes).map_err(|e| format!("Failed to write {}: {}", output, e))?;
+            let input_size = fs::metadata(input).map(|metadata| format_bytes(metadata.len())).unwrap_or_default();
+            println!(
+                "✅ {} ({}×{}, {}) → {} ({}×{}, {})",
+                input, width, height, input_size, output, image.width(), image.height(), format_bytes(bytes.len() as u64)
+            );
+        }
+        Ok(())
+    }
+}
+
+// Decodes the image and applies its EXIF orientation, so phone photos are not sideways
+pub fn open_upright(path: &str) -> Result<DynamicImage, String> {
+    let fail = |e: &dyn std::fmt::Display| format!("Failed to open image '{}': {}", path, e);
+    let mut decoder = ImageReader::open(path).map_err(|e| fail(&e))?.with_guessed_format().map_err(|e| fail(&e))?.into_decoder().map_err(|e| fail(&e))?;
+    let orientation = decoder.orientation().map_err(|e| fail(&e))?;
+    let mut image = DynamicImage::from_decoder(decoder).map_err(|e| fail(&e))?;
+    image.apply_orientation(orientation);
+    Ok(image)
+}
+
+// "800x", "x600", "800x600" (fit inside, keeping the aspect ratio), "800x600!" (exact) or "50%"
+fn target_size(spec: &str, width: u32, height: u32) -> Result<(u32, u32), String> {
+    let invalid = || format!("Invalid size '{}'. Use 800x, x600, 800x600, 800x600! or 50%", spec);
+    let scaled = |factor: f64| ((width as f64 * factor).round().max(1.0) as u32, (height as f64 * factor).round().max(1.0) as u32);
+    let spec = spec.trim().to_lowercase();
+    if let Some(percent) = spec.strip_suffix('%') {
+        let percent: f64 = percent.trim().parse().map_err(|_| invalid())?;
+        if percent <= 0.0 {
+            return Err(invalid());
+        }
+        return Ok(scaled(percent / 100.0));
+    }
+    let (exact, spec) = match spec.strip_suffix('!') {
+        Some(spec) => (true, spec),
+        None => (false, spec.as_str()),
+    };
+    let (target_width, target_height) = spec.split_once('x').ok_or_else(invalid)?;
+    let parse = |value: &str| -> Result<Option<u32>, String> {
+        match value.trim() {
+            "" => Ok(None),
+            value => value.parse::<u32>().ok().filter(|&value| value > 0).map(Some).ok_or_else(invalid),
+        }
+    };
+    match (parse(target_width)?, parse(target_height)?) {
+        (Some(target_width), Some(target_height)) if exact => Ok((target_width, target_height)),
+        (Some(target_width), Some(target_height)) => {
+            Ok(scaled((target_width as f64 / width as f64).min(target_height as f64 / height as f64)))
+        }
+        (Some(target_width), None) => Ok(scaled(target_width as f64 / width as f64)),
+        (None, Some(target_height)) => Ok(scaled(target_height as f64 / height as f64)),
+        (None, None) => Err(invalid()),
+    }
+}
+
+// JPEG has no alpha channel, so transparent areas become white instead of black
+pub fn flatten_onto_white(image: &DynamicImage) -> DynamicImage {
+    let mut flattened = RgbaImage::from_pixel(image.width(), image.height(), Rgba([255, 255, 255, 255]));
+    image::imageops::overlay(&mut flattened, &image.to_rgba8(), 0, 0);
+    DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(flattened).to_rgb8())
+}
+
+fn encode(image: &DynamicImage, format: ImageFormat, quality: u8) -> Result<Vec<u8>, image::ImageError> {
+    let mut bytes = Cursor::new(Vec::new());
+    if format == ImageFormat::Jpeg {
+        let image = if image.color().has_alpha() { flatten_onto_white(image) } else { DynamicImage::ImageRgb8(image.to_rgb8()) };
+        image.write_with_encoder(JpegEncoder::new_with_quality(&mut bytes, quality))?;
+    } else {
+        image.write_to(&mut bytes, format)?;
+    }
+    Ok(bytes.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_size() {
+        assert_eq!(target_size("800x", 1920, 1080).unwrap(), (800, 450));
+        assert_eq!(target_size("x540", 1920, 1080).unwrap(), (960, 540));
+        assert_eq!(target_size("800x800", 1920, 1080).unwrap(), (800, 450));
+        assert_eq!(target_size("800X800!", 1920, 1080).unwrap(), (800, 800));
+        assert_eq!(target_size("50%", 1920, 1080).unwrap(), (960, 540));
+        assert_eq!(target_size("1%", 10, 10).unwrap(), (1, 1));
+        for invalid in ["800", "x", "0x100", "-5%", "axb"] {
+            assert!(target_size(invalid, 1920, 1080).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_jpeg_flattens_transparency() {
+        let mut image = RgbaImage::from_pixel(4, 4, Rgba([0, 0, 0, 0]));
+        image.put_pixel(0, 0, Rgba([255, 0, 0, 255]));
+        let jpeg = encode(&DynamicImage::ImageRgba8(image), ImageFormat::Jpeg, 95).unwrap();
+        let decoded = image::load_from_memory(&jpeg).unwrap().to_rgb8();
+        assert!(decoded.get_pixel(3, 3).0.iter().all(|&channel| channel > 240));
+    }
+
+    #[test]
+    fn test_encode_formats_round_trip() {
+        let image = DynamicImage::ImageRgba8(RgbaImage::from_fn(16, 8, |x, y| Rgba([(x * 16) as u8, (y * 32) as u8, 128, 255])));
+        for format in [ImageFormat::Png, ImageFormat::WebP, ImageFormat::Bmp, ImageFormat::Tiff, ImageFormat::Ico, ImageFormat::Gif] {
+            let bytes = encode(&image, format, DEFAULT_JPEG_QUALITY).unwrap();
+            assert_eq!(image::guess_format(&bytes).unwrap(), format);
+            let decoded = image::load_from_memory(&bytes).unwrap();
+            assert_eq!((decoded.width(), decoded.height()), (16, 8));
+        }
+        let small = encode(&image, ImageFormat::Jpeg, 10).unwrap();
+        let large = encode(&image, ImageFormat::Jpeg, 100).unwrap();
+        assert!(small.len() < large.len());
+    }
+}
@@ -0,0 +1,185 @@
+use crate::color_convert::Color;
+use crate::img_convert::open_upright;
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgMatches, Command};
+use image::imageops::FilterType;
+use image::RgbaImage;
+use rand::distributions::WeightedIndex;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use std::error::Error;
+use std::io::{self, IsTerminal};
+
+pub struct ImgPaletteModule;
+
+const DEFAULT_COUNT: u32 = 6;
+// Images are shrunk to fit this many pixels a side before clustering
+const SAMPLE_SIZE: u32 = 200;
+const MAX_ITERATIONS: usize = 30;
+
+#[derive(Debug, Clone, Copy)]
+struct Swatch {
+    color: Color,
+    // Fraction of the opaque pixels closest to this color
+    share: f64,
+}
+
+impl ToolModule for ImgPaletteModule {
+    fn name(&self) -> &'static str {
+        "img-palette"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("img-palette")
+                .long("img-palette")
+                .value_name("FILE")
+                .help("Extract the dominant colors of an image as swatches, hex/rgb/hsl values and CSS variables")
+                .long_help("Find the dominant colors of an image with k-means clustering (on a downscaled copy, ignoring transparent pixels). Each color is printed with a swatch, its hex, rgb and hsl values and its share of the image, most common first, followed by a :root block of CSS custom properties ready to paste. Set the number of colors with --count (default: 6). The result is the same on every run.")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(path) = matches.get_one::<String>("img-palette") {
+            // --count is declared by the ping module
+            let count = matches.get_one::<u32>("count").copied().unwrap_or(DEFAULT_COUNT);
+            let image = open_upright(path)?;
+            let pixels = image.resize(SAMPLE_SIZE, SAMPLE_SIZE, FilterType::Triangle).to_rgba8();
+            let palette = extract_palette(&pixels, count as usize);
+            if palette.is_empty() {
+                return Err(format!("'{}' has no opaque pixels", path).into());
+            }
+            print!("{}", render(&palette, io::stdout().is_terminal()));
+        }
+        Ok(())
+    }
+}
+
+fn distance(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum()
+}
+
+fn nearest(point: [f64; 3], centers: &[[f64; 3]]) -> usize {
+    (0..centers.len()).min_by(|&a, &b| distance(point, centers[a]).total_cmp(&distance(point, centers[b]))).unwrap_or(0)
+}
+
+// k-means with k-means++ seeding from a fixed seed, so a given image always
+// gives the same palette. Returns at most `count` colors, largest share first.
+fn extract_palette(pixels: &RgbaImage, count: usize) -> Vec<Swatch> {
+    let points: Vec<[f64; 3]> = pixels.pixels().filter(|pixel| pixel.0[3] >= 128).map(|pixel| [pixel.0[0] as f64, pixel.0[1] as f64, pixel.0[2] as f64]).collect();
+    if points.is_empty() || count == 0 {
+        return Vec::new();
+    }
+
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut centers = vec![points[rng.gen_range(0..points.len())]];
+    while centers.len() < count {
+        let weights: Vec<f64> = points.iter().map(|point| distance(*point, centers[nearest(*point, &centers)])).collect();
+        // Every pixel already sits on a center: the image has fewer colors than asked for
+        let Ok(index) = WeightedIndex::new(&weights) else {
+            break;
+        };
+        centers.push(points[index.sample(&mut rng)]);
+    }
+
+    let mut assignment = vec![usize::MAX; points.len()];
+    for _ in 0..MAX_ITERATIONS {
+        let mut changed = false;
+        for (point, cluster) in points.iter().zip(assignment.iter_mut()) {
+            let closest = nearest(*point, &centers);
+            if closest != *cluster {
+                *cluster = closest;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+        let mut sums = vec![([0.0; 3], 0usize); centers.len()];
+        for (point, cluster) in points.iter().zip(&assignment) {
+            let (sum, size) = &mut sums[*cluster];
+            sum.iter_mut().zip(point).for_each(|(total, value)| *total += value);
+            *size += 1;
+        }
+        for (center, (sum, size)) in centers.iter_mut().zip(sums) {
+            if size > 0 {
+                *center = sum.map(|total| total / size as f64);
+            }
+        }
+    }
+
+    let mut sizes = vec![0usize; centers.len()];
+    assignment.iter().for_each(|cluster| sizes[*cluster] += 1);
+    let mut palette: Vec<Swatch> = centers
+        .iter()
+        .zip(sizes)
+        .filter(|(_, size)| *size > 0)
+        .map(|(center, size)| {
+            let [r, g, b] = center.map(|channel| channel.round().clamp(0.0, 255.0) as u8);
+            Swatch { color: Color::new(r, g, b), share: size as f64 / points.len() as f64 }
+        })
+        .collect();
+    palette.sort_by(|a, b| b.share.total_cmp(&a.share));
+    palette
+}
+
+fn render(palette: &[Swatch], color: bool) -> String {
+    let mut output = String::new();
+    for swatch in palette {
+        let block = if color { format!("\x1b[38;2;{};{};{}m██████\x1b[0m  ", swatch.color.r, swatch.color.g, swatch.color.b) } else { String::new() };
+        output.push_str(&format!(
+            "{}{}  {:<16}  {:<18}  {:>5.1}%\n",
+            block,
+            swatch.color.to_hex(),
+            swatch.color.to_rgb_string(),
+            swatch.color.to_hsl_string(),
+            swatch.share * 100.0
+        ));
+    }
+    output.push_str("\n:root {\n");
+    for (index, swatch) in palette.iter().enumerate() {
+        output.push_str(&format!("  --color-{}: {};\n", index + 1, swatch.color.to_hex()));
+    }
+    output.push_str("}\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    // Left three quarters red, right quarter blue
+    fn two_tone() -> RgbaImage {
+        RgbaImage::from_fn(40, 20, |x, _| if x < 30 { Rgba([220, 20, 60, 255]) } else { Rgba([0, 0, 255, 255]) })
+    }
+
+    #[test]
+    fn test_extract_palette() {
+        let palette = extract_palette(&two_tone(), 2);
+        assert_eq!(palette.len(), 2);
+        assert_eq!(palette[0].color.to_hex(), "#dc143c");
+        assert_eq!(palette[1].color.to_hex(), "#0000ff");
+        assert!((palette[0].share - 0.75).abs() < 1e-9);
+        // Asking for more colors than the image has gives only the real ones
+        assert_eq!(extract_palette(&two_tone(), 6).len(), 2);
+    }
+
+    #[test]
+    fn test_transparent_pixels_ignored() {
+        let image = RgbaImage::from_fn(10, 10, |x, _| if x < 5 { Rgba([255, 255, 255, 0]) } else { Rgba([10, 20, 30, 255]) });
+        let palette = extract_palette(&image, 3);
+        assert_eq!(palette.len(), 1);
+        assert_eq!(palette[0].color.to_hex(), "#0a141e");
+        assert!(extract_palette(&RgbaImage::new(4, 4), 3).is_empty());
+    }
+
+    #[test]
+    fn test_render() {
+        let palette = extract_palette(&two_tone(), 2);
+        assert_eq!(
+            render(&palette, false),
+            "#dc143c  rgb(220,20,60)    hsl(348,83%,47%)     75.0%\n#0000ff  rgb(0,0,255)      hsl(240,100%,50%)    25.0%\n\n:root {\n  --color-1: #dc143c;\n  --color-2: #0000ff;\n}\n"
+        );
+    }
+}
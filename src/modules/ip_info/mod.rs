@@ -0,0 +1,240 @@
+use crate::tool_module::{block_on, ToolModule};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use colored::*;
+use hickory_resolver::proto::rr::RData;
+use hickory_resolver::Resolver;
+use reqwest::blocking::Client;
+use serde_json::{json, Map, Value};
+use std::error::Error;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::time::Duration;
+
+pub struct IpInfoModule;
+
+// Tried in order until one answers with a valid address
+const IPV4_SERVICES: [&str; 3] = ["https://api.ipify.org", "https://ipv4.icanhazip.com", "https://ifconfig.me/ip"];
+const IPV6_SERVICES: [&str; 3] = ["https://api6.ipify.org", "https://ipv6.icanhazip.com", "https://ifconfig.co/ip"];
+
+impl ToolModule for IpInfoModule {
+    fn name(&self) -> &'static str {
+        "ip-info"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("myip")
+                .long("myip")
+                .help("Show your public IPv4 and IPv6 addresses")
+                .long_help("Ask public echo services (ipify, icanhazip, ifconfig) for the address your traffic comes from, once over IPv4 and once over IPv6, falling back to the next service when one does not answer.")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("ip-info")
+        )
+        .arg(
+            Arg::new("ip-info")
+                .long("ip-info")
+                .value_name("IP")
+                .num_args(0..=1)
+                .default_missing_value("")
+                .help("Show reverse DNS, ASN and location for an IP address (default: your public IP)")
+                .long_help("Look up an IP address: its reverse DNS name (PTR record), the network (ASN) it belongs to and its approximate location, from ipinfo.io. Without an address, your public IP is looked up.")
+        )
+        .arg(
+            Arg::new("ip-json")
+                .long("ip-json")
+                .help("Print --myip and --ip-info results as JSON")
+                .action(ArgAction::SetTrue)
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let as_json = matches.get_flag("ip-json");
+        if matches.get_flag("myip") {
+            let ipv4 = public_ip(false);
+            let ipv6 = public_ip(true);
+            if ipv4.is_none() && ipv6.is_none() {
+                return Err("Could not determine your public IP; are you online?".into());
+            }
+            if as_json {
+                println!("{}", serde_json::to_string_pretty(&json!({ "ipv4": ipv4, "ipv6": ipv6 }))?);
+            } else {
+                let show = |label: &str, ip: Option<IpAddr>| match ip {
+                    Some(ip) => println!("{} {}", label.cyan(), ip.to_string().bold()),
+                    None => println!("{} {}", label.cyan(), "not available".dimmed()),
+                };
+                show("IPv4:", ipv4);
+                show("IPv6:", ipv6);
+            }
+        } else if let Some(input) = matches.get_one::<String>("ip-info") {
+            let ip = if input.is_empty() {
+                public_ip(false).or_else(|| public_ip(true)).ok_or("Could not determine your public IP; are you online?")?
+            } else {
+                input.trim().parse::<IpAddr>().map_err(|_| format!("'{}' is not an IP address", input))?
+            };
+            let info = ip_info(ip)?;
+            if as_json {
+                println!("{}", serde_json::to_string_pretty(&info)?);
+            } else {
+                print_info(&info);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn client(ipv6: bool) -> Result<Client, reqwest::Error> {
+    // Binding to the unspecified address of one family forces the request over it
+    let local: IpAddr = if ipv6 { Ipv6Addr::UNSPECIFIED.into() } else { Ipv4Addr::UNSPECIFIED.into() };
+    Client::builder()
+        .user_agent(concat!("micro-swiss/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(5))
+        .local_address(local)
+        .build()
+}
+
+fn public_ip(ipv6: bool) -> Option<IpAddr> {
+    let client = client(ipv6).ok()?;
+    let services = if ipv6 { IPV6_SERVICES } else { IPV4_SERVICES };
+    services.iter().find_map(|url| {
+        let body = client.get(*url).send().ok()?.error_for_status().ok()?.text().ok()?;
+        parse_ip(&body).filter(|ip| ip.is_ipv6() == ipv6)
+    })
+}
+
+// Echo services answer with the bare address, sometimes followed by a newline
+fn parse_ip(body: &str) -> Option<IpAddr> {
+    body.trim().parse().ok()
+}
+
+fn reverse_dns(ip: IpAddr) -> Option<String> {
+    let resolver = Resolver::builder_tokio().ok()?.build().ok()?;
+    let lookup = block_on(resolver.reverse_lookup(ip)).ok()?;
+    lookup.answers().iter().find_map(|record| match &record.data {
+        RData::PTR(name) => Some(name.to_string().trim_end_matches('.').to_string()),
+        _ => None,
+    })
+}
+
+fn ip_info(ip: IpAddr) -> Result<Value, Box<dyn Error>> {
+    let mut info = Map::new();
+    info.insert("ip".to_string(), json!(ip.to_string()));
+    info.insert("reverse_dns".to_string(), json!(reverse_dns(ip)));
+    if let Some(scope) = special_scope(ip) {
+        // Registries know nothing about private and reserved ranges
+        info.insert("scope".to_string(), json!(scope));
+        return Ok(Value::Object(info));
+    }
+    let body = Client::builder()
+        .user_agent(concat!("micro-swiss/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(5))
+        .build()?
+        .get(format!("https://ipinfo.io/{}/json", ip))
+        .send()
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.text())
+        .map_err(|e| format!("IP lookup failed: {}", e))?;
+    let response: Value = serde_json::from_str(&body).map_err(|e| format!("Unexpected response from ipinfo.io: {}", e))?;
+    info.extend(location_fields(&response));
+    Ok(Value::Object(info))
+}
+
+// ipinfo.io reports the network as "org": "AS15169 Google LLC"
+fn location_fields(response: &Value) -> Map<String, Value> {
+    let text = |key: &str| response.get(key).and_then(Value::as_str).map(str::to_string);
+    let mut fields = Map::new();
+    if let Some(org) = text("org") {
+        match org.split_once(' ').filter(|(asn, _)| asn.starts_with("AS")) {
+            Some((asn, name)) => {
+                fields.insert("asn".to_string(), json!(asn));
+                fields.insert("organization".to_string(), json!(name));
+            }
+            None => {
+                fields.insert("organization".to_string(), json!(org));
+            }
+        }
+    }
+    for (key, field) in [("city", "city"), ("region", "region"), ("country", "country"), ("loc", "coordinates"), ("timezone", "timezone")] {
+        if let Some(value) = text(key) {
+            fields.insert(field.to_string(), json!(value));
+        }
+    }
+    fields
+}
+
+fn special_scope(ip: IpAddr) -> Option<&'static str> {
+    match ip {
+        IpAddr::V4(ip) if ip.is_loopback() => Some("loopback"),
+        IpAddr::V4(ip) if ip.is_private() => Some("private"),
+        IpAddr::V4(ip) if ip.is_link_local() => Some("link-local"),
+        // 100.64.0.0/10, used by carrier-grade NAT
+        IpAddr::V4(ip) if ip.octets()[0] == 100 && (64..128).contains(&ip.octets()[1]) => Some("shared (carrier-grade NAT)"),
+        IpAddr::V4(ip) if ip.is_unspecified() || ip.is_broadcast() || ip.is_multicast() || ip.is_documentation() => Some("reserved"),
+        IpAddr::V6(ip) if ip.is_loopback() => Some("loopback"),
+        IpAddr::V6(ip) if (ip.segments()[0] & 0xfe00) == 0xfc00 => Some("private (unique local)"),
+        IpAddr::V6(ip) if (ip.segments()[0] & 0xffc0) == 0xfe80 => Some("link-local"),
+        IpAddr::V6(ip) if ip.is_unspecified() || ip.is_multicast() || ip.segments()[0] == 0x2001 && ip.segments()[1] == 0xdb8 => Some("reserved"),
+        _ => None,
+    }
+}
+
+fn print_info(info: &Value) {
+    let labels = [
+        ("ip", "IP"),
+        ("reverse_dns", "Reverse DNS"),
+        ("scope", "Scope"),
+        ("asn", "ASN"),
+        ("organization", "Organization"),
+        ("city", "City"),
+        ("region", "Region"),
+        ("country", "Country"),
+        ("coordinates", "Coordinates"),
+        ("timezone", "Timezone"),
+    ];
+    for (key, label) in labels {
+        if let Some(value) = info.get(key).and_then(Value::as_str) {
+            println!("{:<14}{}", format!("{}:", label).cyan(), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ip() {
+        assert_eq!(parse_ip("203.0.113.7\n"), Some("203.0.113.7".parse().unwrap()));
+        assert_eq!(parse_ip(" 2001:db8::1 "), Some("2001:db8::1".parse().unwrap()));
+        assert_eq!(parse_ip("<html>rate limited</html>"), None);
+    }
+
+    #[test]
+    fn test_special_scope() {
+        assert_eq!(special_scope("192.168.1.10".parse().unwrap()), Some("private"));
+        assert_eq!(special_scope("127.0.0.1".parse().unwrap()), Some("loopback"));
+        assert_eq!(special_scope("100.100.1.1".parse().unwrap()), Some("shared (carrier-grade NAT)"));
+        assert_eq!(special_scope("fd12:3456::1".parse().unwrap()), Some("private (unique local)"));
+        assert_eq!(special_scope("fe80::1".parse().unwrap()), Some("link-local"));
+        assert_eq!(special_scope("8.8.8.8".parse().unwrap()), None);
+        assert_eq!(special_scope("2606:4700::1111".parse().unwrap()), None);
+    }
+
+    #[test]
+    fn test_location_fields() {
+        let response = json!({
+            "ip": "8.8.8.8",
+            "hostname": "dns.google",
+            "city": "Mountain View",
+            "region": "California",
+            "country": "US",
+            "loc": "37.4056,-122.0775",
+            "org": "AS15169 Google LLC",
+            "timezone": "America/Los_Angeles"
+        });
+        let fields = location_fields(&response);
+        assert_eq!(fields["asn"], "AS15169");
+        assert_eq!(fields["organization"], "Google LLC");
+        assert_eq!(fields["coordinates"], "37.4056,-122.0775");
+        assert_eq!(fields.keys().next().map(String::as_str), Some("asn"));
+        assert_eq!(location_fields(&json!({ "org": "Example Corp" }))["organization"], "Example Corp");
+    }
+}
@@ -40,7 +40,7 @@ impl ToolModule for JsonFormatModule {
     }
 }
 
-fn format_json_pretty(json_str: &str) -> Result<String, Box<dyn Error>> {
+pub(crate) fn format_json_pretty(json_str: &str) -> Result<String, Box<dyn Error>> {
     let value: Value = serde_json::from_str(json_str)?;
     let pretty = serde_json::to_string_pretty(&value)?;
     Ok(pretty)
@@ -0,0 +1,331 @@
+use crate::tool_module::ToolModule;
+use chrono::{DateTime, NaiveDate};
+use clap::{Arg, ArgMatches, Command};
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::io::{self, Read};
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+pub struct JsonSchemaModule;
+
+const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+// A string field becomes an enum when it repeats at most this many distinct values
+const ENUM_MAX_VALUES: usize = 5;
+// and has been seen at least this often
+const ENUM_MIN_SAMPLES: usize = 3;
+
+// Everything seen at one position across all samples
+#[derive(Debug, Default)]
+struct Shape {
+    null: bool,
+    boolean: bool,
+    integer: bool,
+    number: bool,
+    strings: usize,
+    // Distinct strings while there are few enough to be an enum
+    string_values: Option<Vec<String>>,
+    // The format every string so far matches; Some(None) once they disagree
+    format: Option<Option<&'static str>>,
+    // Element shape of every array, merged
+    items: Option<Box<Shape>>,
+    arrays: usize,
+    objects: usize,
+    properties: BTreeMap<String, (usize, Shape)>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct Schema {
+    #[serde(rename = "$schema", skip_serializing_if = "Option::is_none")]
+    dialect: Option<&'static str>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    kind: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<&'static str>,
+    #[serde(rename = "enum", skip_serializing_if = "Option::is_none")]
+    values: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    properties: Option<Properties>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    required: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    items: Option<Box<Schema>>,
+}
+
+#[derive(Debug)]
+struct Properties(Vec<(String, Schema)>);
+
+impl Serialize for Properties {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (name, schema) in &self.0 {
+            map.serialize_entry(name, schema)?;
+        }
+        map.end()
+    }
+}
+
+impl ToolModule for JsonSchemaModule {
+    fn name(&self) -> &'static str {
+        "json-schema"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("json-to-schema")
+                .long("json-to-schema")
+                .value_name("FILE")
+                .num_args(0..)
+                .help("Infer a JSON Schema from one or more example JSON documents (FILEs or stdin)")
+                .long_help("Generate a JSON Schema (draft 2020-12) that fits every example document: types (a list when samples disagree, e.g. [\"string\", \"null\"]), object properties with required listing those present in every sample, array item schemas, enums for strings that repeat few distinct values, and formats (date-time, date, uuid, email, uri, ipv4, ipv6) when every value matches. Each FILE may hold several documents, one after another as in JSON Lines. If no file (or -) is given, reads from stdin.")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if matches.contains_id("json-to-schema") {
+            let paths: Vec<&String> = matches.get_many::<String>("json-to-schema").into_iter().flatten().collect();
+            let mut samples = Vec::new();
+            if paths.is_empty() {
+                let mut buffer = String::new();
+                io::stdin().read_to_string(&mut buffer)?;
+                samples.extend(parse_samples(&buffer, "stdin")?);
+            }
+            for path in paths {
+                let input = if path == "-" {
+                    let mut buffer = String::new();
+                    io::stdin().read_to_string(&mut buffer)?;
+                    buffer
+                } else {
+                    fs::read_to_string(path).map_err(|e| format!("Failed to read '{}': {}", path, e))?
+                };
+                samples.extend(parse_samples(&input, path)?);
+            }
+            if samples.is_empty() {
+                return Err("No JSON documents found in the input".into());
+            }
+            println!("{}", serde_json::to_string_pretty(&infer_schema(&samples))?);
+        }
+        Ok(())
+    }
+}
+
+// Every document in the input, so JSON Lines and concatenated JSON both work
+fn parse_samples(input: &str, source: &str) -> Result<Vec<Value>, String> {
+    serde_json::Deserializer::from_str(input)
+        .into_iter::<Value>()
+        .map(|document| document.map_err(|e| format!("Invalid JSON in {}: {}", source, e)))
+        .collect()
+}
+
+fn infer_schema(samples: &[Value]) -> Schema {
+    let mut shape = Shape::default();
+    for sample in samples {
+        shape.add(sample);
+    }
+    let mut schema = shape.to_schema();
+    schema.dialect = Some(DIALECT);
+    schema
+}
+
+fn detect_format(text: &str) -> Option<&'static str> {
+    if DateTime::parse_from_rfc3339(text).is_ok() {
+        Some("date-time")
+    } else if text.len() == 10 && NaiveDate::parse_from_str(text, "%Y-%m-%d").is_ok() {
+        Some("date")
+    } else if text.len() == 36 && uuid::Uuid::try_parse(text).is_ok() {
+        Some("uuid")
+    } else if text.parse::<Ipv4Addr>().is_ok() {
+        Some("ipv4")
+    } else if text.parse::<Ipv6Addr>().is_ok() {
+        Some("ipv6")
+    } else if is_email(text) {
+        Some("email")
+    } else if text.contains("://") && url::Url::parse(text).is_ok() {
+        Some("uri")
+    } else {
+        None
+    }
+}
+
+fn is_email(text: &str) -> bool {
+    match text.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !text.contains(char::is_whitespace)
+                && !domain.contains('@')
+                && domain.split('.').count() >= 2
+                && domain.split('.').all(|label| !label.is_empty())
+        }
+        None => false,
+    }
+}
+
+impl Shape {
+    fn add(&mut self, value: &Value) {
+        match value {
+            Value::Null => self.null = true,
+            Value::Bool(_) => self.boolean = true,
+            Value::Number(number) if number.is_i64() || number.is_u64() => self.integer = true,
+            Value::Number(_) => self.number = true,
+            Value::String(text) => {
+                if self.strings == 0 {
+                    self.string_values = Some(Vec::new());
+                }
+                self.strings += 1;
+                if let Some(values) = &mut self.string_values {
+                    if !values.contains(text) {
+                        values.push(text.clone());
+                    }
+                    if values.len() > ENUM_MAX_VALUES {
+                        self.string_values = None;
+                    }
+                }
+                let format = detect_format(text);
+                self.format = Some(match self.format {
+                    None => format,
+                    Some(previous) if previous == format => format,
+                    Some(_) => None,
+                });
+            }
+            Value::Array(items) => {
+                self.arrays += 1;
+                let shape = self.items.get_or_insert_with(Box::default);
+                for item in items {
+                    shape.add(item);
+                }
+            }
+            Value::Object(map) => {
+                self.objects += 1;
+                for (name, item) in map {
+                    let (seen, shape) = self.properties.entry(name.clone()).or_default();
+                    *seen += 1;
+                    shape.add(item);
+                }
+            }
+        }
+    }
+
+    // Empty only for an array that never held anything
+    fn types(&self) -> Vec<&'static str> {
+        let mut types = Vec::new();
+        if self.objects > 0 {
+            types.push("object");
+        }
+        if self.arrays > 0 {
+            types.push("array");
+        }
+        if self.strings > 0 {
+            types.push("string");
+        }
+        // Integers are numbers too, so one type covers both
+        if self.number {
+            types.push("number");
+        } else if self.integer {
+            types.push("integer");
+        }
+        if self.boolean {
+            types.push("boolean");
+        }
+        if self.null {
+            types.push("null");
+        }
+        types
+    }
+
+    fn to_schema(&self) -> Schema {
+        let types = self.types();
+        let mut schema = Schema {
+            kind: match types.as_slice() {
+                [] => None,
+                [single] => Some(Value::from(*single)),
+                _ => Some(Value::from(types.clone())),
+            },
+            ..Schema::default()
+        };
+        if self.strings > 0 {
+            schema.format = self.format.flatten();
+            if schema.format.is_none() && self.strings >= ENUM_MIN_SAMPLES {
+                // Only when values repeat; a handful of unique names is not an enum
+                schema.values = self.string_values.clone().filter(|values| values.len() < self.strings);
+            }
+        }
+        if self.objects > 0 {
+            schema.properties = Some(Properties(self.properties.iter().map(|(name, (_, shape))| (name.clone(), shape.to_schema())).collect()));
+            let required: Vec<String> =
+                self.properties.iter().filter(|(_, (seen, _))| *seen == self.objects).map(|(name, _)| name.clone()).collect();
+            schema.required = (!required.is_empty()).then_some(required);
+        }
+        if let Some(items) = &self.items {
+            if !items.types().is_empty() {
+                schema.items = Some(Box::new(items.to_schema()));
+            }
+        }
+        schema
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema_of(samples: &str) -> Value {
+        serde_json::to_value(infer_schema(&parse_samples(samples, "test").unwrap())).unwrap()
+    }
+
+    #[test]
+    fn test_objects_and_required() {
+        let schema = schema_of("{\"id\": 1, \"name\": \"Ada\", \"score\": 2}\n{\"id\": 2, \"score\": 2.5, \"tags\": null}");
+        assert_eq!(
+            schema,
+            json!({
+                "$schema": DIALECT,
+                "type": "object",
+                "properties": {
+                    "id": {"type": "integer"},
+                    "name": {"type": "string"},
+                    "score": {"type": "number"},
+                    "tags": {"type": "null"}
+                },
+                "required": ["id", "score"]
+            })
+        );
+    }
+
+    #[test]
+    fn test_arrays_and_mixed_types() {
+        let schema = schema_of("[{\"a\": [1, \"x\"]}, {\"a\": []}, {\"a\": null}]");
+        assert_eq!(schema["type"], "array");
+        assert_eq!(schema["items"]["properties"]["a"], json!({"type": ["array", "null"], "items": {"type": ["string", "integer"]}}));
+        assert_eq!(schema["items"]["required"], json!(["a"]));
+        assert_eq!(schema_of("[]"), json!({"$schema": DIALECT, "type": "array"}));
+    }
+
+    #[test]
+    fn test_enums_and_formats() {
+        let samples = "{\"status\": \"active\", \"id\": \"0b8e7a6c-3c1f-4b8e-9f3e-2a1d5c6b7e8f\", \"at\": \"2024-05-01T10:00:00Z\"}\n\
+                       {\"status\": \"inactive\", \"id\": \"5f2c1d9e-8a7b-4c6d-9e0f-1a2b3c4d5e6f\", \"at\": \"2024-05-02\"}\n\
+                       {\"status\": \"active\", \"email\": \"ada@example.com\", \"at\": \"2024-05-03T00:00:00+02:00\"}";
+        let schema = schema_of(samples);
+        assert_eq!(schema["properties"]["status"], json!({"type": "string", "enum": ["active", "inactive"]}));
+        assert_eq!(schema["properties"]["id"], json!({"type": "string", "format": "uuid"}));
+        assert_eq!(schema["properties"]["email"], json!({"type": "string", "format": "email"}));
+        // A date and date-times disagree, so no format
+        assert_eq!(schema["properties"]["at"], json!({"type": "string"}));
+        let names = schema_of("{\"n\": \"a\"} {\"n\": \"b\"} {\"n\": \"c\"}");
+        assert_eq!(names["properties"]["n"], json!({"type": "string"}));
+    }
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(detect_format("2024-02-30"), None);
+        assert_eq!(detect_format("10.0.0.1"), Some("ipv4"));
+        assert_eq!(detect_format("::1"), Some("ipv6"));
+        assert_eq!(detect_format("https://example.com/a?b=1"), Some("uri"));
+        assert_eq!(detect_format("not an@email"), None);
+        assert!(parse_samples("{\"a\": }", "x.json").unwrap_err().starts_with("Invalid JSON in x.json"));
+    }
+}
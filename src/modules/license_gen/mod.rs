@@ -0,0 +1,156 @@
+use crate::tool_module::ToolModule;
+use chrono::{Datelike, Local};
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::process;
+
+pub struct LicenseGenModule;
+
+struct License {
+    id: &'static str,
+    name: &'static str,
+    aliases: &'static [&'static str],
+    text: &'static str,
+}
+
+// Texts are verbatim; only MIT and BSD-3-Clause carry a copyright line to fill in
+const LICENSES: [License; 5] = [
+    License { id: "MIT", name: "MIT License", aliases: &["mit"], text: include_str!("texts/mit.txt") },
+    License { id: "Apache-2.0", name: "Apache License 2.0", aliases: &["apache", "apache2"], text: include_str!("texts/apache-2.0.txt") },
+    License { id: "GPL-3.0", name: "GNU General Public License v3.0", aliases: &["gpl", "gpl3", "gpl-3.0-or-later", "gpl-3.0-only"], text: include_str!("texts/gpl-3.0.txt") },
+    License { id: "BSD-3-Clause", name: "BSD 3-Clause License", aliases: &["bsd", "bsd3", "bsd-3"], text: include_str!("texts/bsd-3-clause.txt") },
+    License { id: "MPL-2.0", name: "Mozilla Public License 2.0", aliases: &["mpl", "mpl2"], text: include_str!("texts/mpl-2.0.txt") },
+];
+
+impl ToolModule for LicenseGenModule {
+    fn name(&self) -> &'static str {
+        "license-gen"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("license")
+                .long("license")
+                .value_name("LICENSE")
+                .num_args(0..=1)
+                .default_missing_value("")
+                .help("Print a standard license text: mit, apache-2.0, gpl-3.0, bsd-3 or mpl-2.0")
+                .long_help("Print the full text of a standard license (MIT, Apache-2.0, GPL-3.0, BSD-3-Clause or MPL-2.0; SPDX ids and short names such as bsd or gpl work too) with the copyright year and holder filled in. The holder comes from --author, or from `git config user.name`. Write it to a file with --license-out; see --license --list for all licenses.")
+        )
+        .arg(
+            Arg::new("author")
+                .long("author")
+                .value_name("NAME")
+                .help("Copyright holder for --license (default: git config user.name)")
+                .requires("license")
+        )
+        .arg(
+            Arg::new("year")
+                .long("year")
+                .value_name("YEAR")
+                .help("Copyright year for --license, e.g. 2025 or 2019-2025 (default: this year)")
+                .requires("license")
+        )
+        .arg(
+            Arg::new("license-out")
+                .long("license-out")
+                .value_name("FILE")
+                .num_args(0..=1)
+                .default_missing_value("LICENSE")
+                .help("Write --license to FILE instead of stdout (default: LICENSE)")
+                .requires("license")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(id) = matches.get_one::<String>("license") {
+            // --list is declared by the gitignore-gen module
+            if matches.get_flag("list") {
+                for license in &LICENSES {
+                    println!("  {:<14}{}", license.id.green(), license.name);
+                }
+                return Ok(());
+            }
+            let license = find(id)?;
+            let year = matches.get_one::<String>("year").cloned().unwrap_or_else(|| Local::now().year().to_string());
+            let author = matches.get_one::<String>("author").cloned().or_else(git_user_name);
+            let text = fill(license, &year, author.as_deref())?;
+            match matches.get_one::<String>("license-out") {
+                Some(path) => {
+                    if Path::new(path).exists() {
+                        return Err(format!("{} already exists; not overwriting it", path).into());
+                    }
+                    fs::write(path, &text).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+                    println!("✅ Wrote the {} to {}", license.name, path);
+                }
+                None => print!("{}", text),
+            }
+        }
+        Ok(())
+    }
+}
+
+fn find(id: &str) -> Result<&'static License, String> {
+    let wanted = id.trim().to_lowercase();
+    if wanted.is_empty() {
+        return Err("Name a license, e.g. --license mit (see --license --list)".to_string());
+    }
+    LICENSES
+        .iter()
+        .find(|license| license.id.to_lowercase() == wanted || license.aliases.contains(&wanted.as_str()))
+        .ok_or_else(|| format!("Unknown license '{}'. Available: {}", id, LICENSES.iter().map(|license| license.id).collect::<Vec<_>>().join(", ")))
+}
+
+fn git_user_name() -> Option<String> {
+    let output = process::Command::new("git").args(["config", "user.name"]).output().ok()?;
+    let name = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (output.status.success() && !name.is_empty()).then_some(name)
+}
+
+fn fill(license: &License, year: &str, author: Option<&str>) -> Result<String, String> {
+    if !license.text.contains("[fullname]") {
+        return Ok(license.text.to_string());
+    }
+    let author = author.ok_or_else(|| format!("The {} names a copyright holder; pass --author \"Your Name\"", license.id))?;
+    Ok(license.text.replace("[year]", year).replace("[fullname]", author))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find("mit").unwrap().id, "MIT");
+        assert_eq!(find("Apache-2.0").unwrap().id, "Apache-2.0");
+        assert_eq!(find("bsd-3").unwrap().id, "BSD-3-Clause");
+        assert_eq!(find("GPL").unwrap().id, "GPL-3.0");
+        assert!(find("wtfpl").err().unwrap().contains("MPL-2.0"));
+        assert!(find("").is_err());
+    }
+
+    #[test]
+    fn test_fill() {
+        let mit = fill(find("mit").unwrap(), "2025", Some("Jane Doe")).unwrap();
+        assert!(mit.starts_with("MIT License\n\nCopyright (c) 2025 Jane Doe\n"));
+        let bsd = fill(find("bsd").unwrap(), "2019-2025", Some("ACME Inc.")).unwrap();
+        assert!(bsd.contains("Copyright (c) 2019-2025, ACME Inc.\n"));
+        assert!(fill(find("mit").unwrap(), "2025", None).is_err());
+        // Licenses without a copyright line do not need an author
+        let apache = fill(find("apache").unwrap(), "2025", None).unwrap();
+        assert!(apache.contains("Apache License\n                           Version 2.0, January 2004"));
+    }
+
+    #[test]
+    fn test_texts_are_complete() {
+        for license in &LICENSES {
+            assert!(license.text.len() > 1000, "{} looks truncated", license.id);
+            assert!(license.text.ends_with('\n'));
+        }
+        assert!(find("gpl").unwrap().text.contains("END OF TERMS AND CONDITIONS"));
+        assert!(find("mpl").unwrap().text.contains("Exhibit B - \"Incompatible With Secondary Licenses\" Notice"));
+    }
+}
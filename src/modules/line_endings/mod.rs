@@ -0,0 +1,245 @@
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use colored::*;
+use ignore::overrides::OverrideBuilder;
+use ignore::WalkBuilder;
+use std::error::Error;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+pub struct LineEndingsModule;
+
+const BOM: &[u8] = b"\xEF\xBB\xBF";
+
+#[derive(Debug, Default, PartialEq)]
+struct Report {
+    lf: usize,
+    crlf: usize,
+    // Classic Mac line endings: a CR not followed by LF
+    cr: usize,
+    bom: bool,
+    final_newline: bool,
+}
+
+impl Report {
+    fn endings(&self) -> String {
+        let counts: Vec<String> = [("LF", self.lf), ("CRLF", self.crlf), ("CR", self.cr)]
+            .iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(name, count)| format!("{} {}", name, count))
+            .collect();
+        match counts.len() {
+            0 => "no line breaks".to_string(),
+            1 => counts[0].split(' ').next().unwrap_or_default().to_string(),
+            _ => format!("mixed ({})", counts.join(", ")),
+        }
+    }
+}
+
+impl ToolModule for LineEndingsModule {
+    fn name(&self) -> &'static str {
+        "line-endings"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("line-endings")
+                .long("line-endings")
+                .value_name("FILE|GLOB")
+                .num_args(1..)
+                .help("Report line endings, BOM and final newline of files, or convert them with --to lf|crlf")
+                .long_help("Without --to or --strip-bom, report for every file its line endings (LF, CRLF, old Mac CR, or a mix with counts), whether it starts with a UTF-8 byte order mark and whether it ends with a newline. Quoted globs such as 'src/**/*.rs' are expanded here, skipping what .gitignore ignores; binary files are skipped.\n\nWith --to lf or --to crlf every line break is rewritten, and --strip-bom removes the byte order mark. A single file is written to stdout unless --in-place is given; several files need --in-place.")
+        )
+        .arg(
+            Arg::new("strip-bom")
+                .long("strip-bom")
+                .help("Remove a UTF-8 byte order mark in --line-endings")
+                .action(ArgAction::SetTrue)
+                .requires("line-endings")
+        )
+        .arg(
+            Arg::new("in-place")
+                .long("in-place")
+                .help("Rewrite the files given to --line-endings or --indent-convert instead of printing the result")
+                .action(ArgAction::SetTrue)
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(patterns) = matches.get_many::<String>("line-endings") {
+            let mut files = Vec::new();
+            for pattern in patterns {
+                files.extend(expand(pattern)?);
+            }
+            // --to is declared by the env-convert module
+            let target = match matches.get_one::<String>("to").map(|to| to.to_lowercase()) {
+                Some(to) if to == "lf" => Some(&b"\n"[..]),
+                Some(to) if to == "crlf" => Some(&b"\r\n"[..]),
+                Some(to) => return Err(format!("Unknown line ending '{}'. Use lf or crlf", to).into()),
+                None => None,
+            };
+            let strip_bom = matches.get_flag("strip-bom");
+            if target.is_none() && !strip_bom {
+                report(&files);
+                return Ok(());
+            }
+
+            let in_place = matches.get_flag("in-place");
+            if !in_place && files.len() > 1 {
+                return Err(format!("{} files matched; converting several files needs --in-place", files.len()).into());
+            }
+            for file in &files {
+                let content = fs::read(file).map_err(|e| format!("Failed to read '{}': {}", file.display(), e))?;
+                if is_binary(&content) {
+                    eprintln!("⚠️ Skipping binary file {}", file.display());
+                    continue;
+                }
+                let converted = convert(&content, target, strip_bom);
+                if !in_place {
+                    io::stdout().write_all(&converted)?;
+                } else if converted != content {
+                    fs::write(file, &converted).map_err(|e| format!("Failed to write '{}': {}", file.display(), e))?;
+                    let after = inspect(&converted);
+                    println!("✅ {}  {} → {}", file.display(), inspect(&content).endings(), after.endings());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// A path as given, or the files matching a glob; globs are matched below the
+// directory part before the first wildcard and respect .gitignore
+fn expand(pattern: &str) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let path = Path::new(pattern);
+    if path.exists() || !pattern.contains(['*', '?', '[']) {
+        if path.is_dir() {
+            return Err(format!("'{}' is a directory; use a glob such as '{}/**/*'", pattern, pattern.trim_end_matches('/')).into());
+        }
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let wildcard = pattern.find(['*', '?', '[']).unwrap_or(0);
+    let split = pattern[..wildcard].rfind('/').map(|index| index + 1).unwrap_or(0);
+    let root = if split == 0 { Path::new(".") } else { Path::new(&pattern[..split]) };
+    let mut overrides = OverrideBuilder::new(root);
+    overrides.add(&format!("/{}", &pattern[split..])).map_err(|e| format!("Invalid glob '{}': {}", pattern, e))?;
+    let mut files: Vec<PathBuf> = WalkBuilder::new(root)
+        .require_git(false)
+        .overrides(overrides.build()?)
+        .build()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_some_and(|kind| kind.is_file()))
+        .map(|entry| if split == 0 { entry.path().strip_prefix(".").unwrap_or(entry.path()).to_path_buf() } else { entry.into_path() })
+        .collect();
+    if files.is_empty() {
+        return Err(format!("No files match '{}'", pattern).into());
+    }
+    files.sort();
+    Ok(files)
+}
+
+// Same heuristic as git: a NUL byte in the first 8 KB
+fn is_binary(content: &[u8]) -> bool {
+    content.iter().take(8000).any(|byte| *byte == 0)
+}
+
+fn inspect(content: &[u8]) -> Report {
+    let mut report = Report { bom: content.starts_with(BOM), final_newline: content.ends_with(b"\n") || content.ends_with(b"\r"), ..Report::default() };
+    let mut bytes = content.iter().peekable();
+    while let Some(byte) = bytes.next() {
+        match byte {
+            b'\r' if bytes.peek() == Some(&&b'\n') => {
+                bytes.next();
+                report.crlf += 1;
+            }
+            b'\r' => report.cr += 1,
+            b'\n' => report.lf += 1,
+            _ => {}
+        }
+    }
+    report
+}
+
+// Every CRLF, CR and LF becomes `ending`; the BOM is dropped when asked
+fn convert(content: &[u8], ending: Option<&[u8]>, strip_bom: bool) -> Vec<u8> {
+    let content = if strip_bom { content.strip_prefix(BOM).unwrap_or(content) } else { content };
+    let Some(ending) = ending else {
+        return content.to_vec();
+    };
+    let mut converted = Vec::with_capacity(content.len());
+    let mut bytes = content.iter().peekable();
+    while let Some(byte) = bytes.next() {
+        match byte {
+            b'\r' => {
+                if bytes.peek() == Some(&&b'\n') {
+                    bytes.next();
+                }
+                converted.extend_from_slice(ending);
+            }
+            b'\n' => converted.extend_from_slice(ending),
+            _ => converted.push(*byte),
+        }
+    }
+    converted
+}
+
+fn report(files: &[PathBuf]) {
+    let width = files.iter().map(|file| file.display().to_string().chars().count()).max().unwrap_or(0);
+    for file in files {
+        let name = format!("{:<width$}", file.display(), width = width);
+        let content = match fs::read(file) {
+            Ok(content) => content,
+            Err(e) => {
+                eprintln!("⚠️ Failed to read '{}': {}", file.display(), e);
+                continue;
+            }
+        };
+        if is_binary(&content) {
+            println!("{}  {}", name, "binary, skipped".dimmed());
+            continue;
+        }
+        let report = inspect(&content);
+        let endings = if report.endings().starts_with("mixed") { report.endings().yellow().to_string() } else { report.endings() };
+        let bom = if report.bom { "BOM".yellow().to_string() } else { "no BOM".to_string() };
+        let newline = if report.final_newline || content.is_empty() { "final newline".to_string() } else { "no final newline".yellow().to_string() };
+        println!("{}  {}  {}  {}", name, endings, bom, newline);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inspect() {
+        let report = inspect(b"\xEF\xBB\xBFa\r\nb\nc\rd");
+        assert_eq!(report, Report { lf: 1, crlf: 1, cr: 1, bom: true, final_newline: false });
+        assert_eq!(report.endings(), "mixed (LF 1, CRLF 1, CR 1)");
+        assert_eq!(inspect(b"a\r\nb\r\n").endings(), "CRLF");
+        assert_eq!(inspect(b"single line").endings(), "no line breaks");
+    }
+
+    #[test]
+    fn test_convert() {
+        let content = b"\xEF\xBB\xBFa\r\nb\nc\rd\n";
+        assert_eq!(convert(content, Some(b"\n"), true), b"a\nb\nc\nd\n");
+        assert_eq!(convert(content, Some(b"\r\n"), false), b"\xEF\xBB\xBFa\r\nb\r\nc\r\nd\r\n");
+        assert_eq!(convert(content, None, true), &content[3..]);
+        assert!(is_binary(b"PNG\0\x01"));
+    }
+
+    #[test]
+    fn test_expand() {
+        let dir = std::env::temp_dir().join(format!("ms-line-endings-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src/nested")).unwrap();
+        for file in ["src/a.rs", "src/nested/b.rs", "src/c.txt"] {
+            fs::write(dir.join(file), "x\n").unwrap();
+        }
+        let pattern = format!("{}/src/**/*.rs", dir.display());
+        assert_eq!(expand(&pattern).unwrap(), vec![dir.join("src/a.rs"), dir.join("src/nested/b.rs")]);
+        assert_eq!(expand(&format!("{}/src/*.txt", dir.display())).unwrap(), vec![dir.join("src/c.txt")]);
+        assert!(expand(&format!("{}/src/*.md", dir.display())).unwrap_err().to_string().starts_with("No files match"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
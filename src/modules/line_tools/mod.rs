@@ -0,0 +1,184 @@
+use crate::tool_module::{read_input, ToolModule};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use rand::seq::SliceRandom;
+use serde_json::{Map, Value};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+pub struct LineToolsModule;
+
+const OPERATIONS: [&str; 6] = ["sort", "uniq", "dedupe", "shuffle", "reverse", "count"];
+
+impl ToolModule for LineToolsModule {
+    fn name(&self) -> &'static str {
+        "line-tools"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("lines")
+                .long("lines")
+                .value_names(["OPERATION", "FILE"])
+                .num_args(1..=2)
+                .help("Process lines: sort, uniq, dedupe, shuffle, reverse or count (FILE or stdin)")
+                .long_help("Process the lines of FILE, or stdin when no file (or -) is given:\n- sort: sort lines (--numeric for numbers, --ignore-case)\n- uniq: sort and drop duplicate lines, like sort -u\n- dedupe: drop repeated lines, keeping the first of each in input order\n- shuffle: random order\n- reverse: last line first\n- count: how often each line occurs, as JSON, most frequent first")
+        )
+        .arg(
+            Arg::new("numeric")
+                .long("numeric")
+                .help("Compare lines by their leading number in --lines sort and uniq")
+                .action(ArgAction::SetTrue)
+                .requires("lines")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(values) = matches.get_many::<String>("lines") {
+            let values: Vec<&String> = values.collect();
+            let operation = values[0].to_lowercase();
+            if !OPERATIONS.contains(&operation.as_str()) {
+                return Err(format!("Unknown operation '{}'. Use one of: {}", operation, OPERATIONS.join(", ")).into());
+            }
+            let input = read_input(values.get(1).copied())?;
+            let lines: Vec<&str> = input.lines().collect();
+            let options = Options {
+                numeric: matches.get_flag("numeric"),
+                // --ignore-case is declared by the similarity module
+                ignore_case: matches.get_flag("ignore-case"),
+            };
+            match operation.as_str() {
+                "count" => println!("{}", serde_json::to_string_pretty(&count_lines(&lines, options))?),
+                operation => {
+                    for line in process(operation, lines, options)? {
+                        println!("{}", line);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Options {
+    numeric: bool,
+    ignore_case: bool,
+}
+
+impl Options {
+    fn key(self, line: &str) -> String {
+        if self.ignore_case { line.to_lowercase() } else { line.to_string() }
+    }
+
+    // Numeric order puts lines without a leading number last, in text order
+    fn compare(self, a: &str, b: &str) -> Ordering {
+        if self.numeric {
+            match (leading_number(a), leading_number(b)) {
+                (Some(x), Some(y)) => return x.total_cmp(&y),
+                (Some(_), None) => return Ordering::Less,
+                (None, Some(_)) => return Ordering::Greater,
+                (None, None) => {}
+            }
+        }
+        self.key(a).cmp(&self.key(b))
+    }
+}
+
+// "42 apples" -> 42, "-1.5e3" -> -1500, "apples" -> None
+fn leading_number(line: &str) -> Option<f64> {
+    let line = line.trim_start();
+    let end = line
+        .char_indices()
+        .take_while(|(i, c)| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E') || (*i == 0 && matches!(c, '-' | '+')))
+        .map(|(i, c)| i + c.len_utf8())
+        .last()?;
+    // Back off trailing characters such as the "e" in "12e" until it parses
+    (1..=end).rev().find_map(|length| line[..length].parse().ok())
+}
+
+fn process<'a>(operation: &str, mut lines: Vec<&'a str>, options: Options) -> Result<Vec<&'a str>, String> {
+    match operation {
+        "sort" => lines.sort_by(|a, b| options.compare(a, b)),
+        "uniq" => {
+            lines.sort_by(|a, b| options.compare(a, b));
+            lines.dedup_by(|a, b| options.compare(a, b) == Ordering::Equal);
+        }
+        "dedupe" => {
+            let mut seen = HashSet::new();
+            lines.retain(|line| seen.insert(options.key(line)));
+        }
+        "shuffle" => lines.shuffle(&mut rand::thread_rng()),
+        "reverse" => lines.reverse(),
+        other => return Err(format!("Unknown operation '{}'. Use one of: {}", other, OPERATIONS.join(", "))),
+    }
+    Ok(lines)
+}
+
+// Most frequent first; ties keep the order in which lines first appeared.
+// With --ignore-case the first spelling seen is the one reported.
+fn count_lines(lines: &[&str], options: Options) -> Value {
+    let mut counts: Vec<(&str, u64)> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for line in lines.iter().filter(|line| !line.trim().is_empty()) {
+        match positions.get(&options.key(line)) {
+            Some(&index) => counts[index].1 += 1,
+            None => {
+                positions.insert(options.key(line), counts.len());
+                counts.push((line, 1));
+            }
+        }
+    }
+    counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    Value::Object(counts.into_iter().map(|(line, count)| (line.to_string(), Value::from(count))).collect::<Map<_, _>>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const FRUIT: &str = "pear\nApple\nbanana\napple\npear\nbanana\npear";
+
+    #[test]
+    fn test_sort_and_uniq() {
+        let lines: Vec<&str> = FRUIT.lines().collect();
+        assert_eq!(process("sort", lines.clone(), Options::default()).unwrap(), ["Apple", "apple", "banana", "banana", "pear", "pear", "pear"]);
+        assert_eq!(process("uniq", lines.clone(), Options::default()).unwrap(), ["Apple", "apple", "banana", "pear"]);
+        let ignore_case = Options { ignore_case: true, ..Options::default() };
+        assert_eq!(process("uniq", lines, ignore_case).unwrap(), ["Apple", "banana", "pear"]);
+    }
+
+    #[test]
+    fn test_numeric_sort() {
+        let lines = vec!["10 items", "9 items", "n/a", "-2.5", "100", "1e3"];
+        let numeric = Options { numeric: true, ..Options::default() };
+        assert_eq!(process("sort", lines.clone(), numeric).unwrap(), ["-2.5", "9 items", "10 items", "100", "1e3", "n/a"]);
+        assert_eq!(process("sort", lines, Options::default()).unwrap(), ["-2.5", "10 items", "100", "1e3", "9 items", "n/a"]);
+        assert_eq!(leading_number("12e"), Some(12.0));
+        assert_eq!(leading_number("-"), None);
+    }
+
+    #[test]
+    fn test_dedupe_shuffle_reverse() {
+        let lines: Vec<&str> = FRUIT.lines().collect();
+        assert_eq!(process("dedupe", lines.clone(), Options::default()).unwrap(), ["pear", "Apple", "banana", "apple"]);
+        assert_eq!(process("reverse", vec!["a", "b", "c"], Options::default()).unwrap(), ["c", "b", "a"]);
+        let mut shuffled = process("shuffle", lines.clone(), Options::default()).unwrap();
+        shuffled.sort();
+        let mut sorted = lines;
+        sorted.sort();
+        assert_eq!(shuffled, sorted);
+        assert!(process("rotate", vec![], Options::default()).is_err());
+    }
+
+    #[test]
+    fn test_count() {
+        let lines: Vec<&str> = FRUIT.lines().collect();
+        let counts = count_lines(&lines, Options::default());
+        assert_eq!(counts, json!({ "pear": 3, "banana": 2, "Apple": 1, "apple": 1 }));
+        assert_eq!(serde_json::to_string(&counts).unwrap(), r#"{"pear":3,"banana":2,"Apple":1,"apple":1}"#);
+        let ignore_case = count_lines(&lines, Options { ignore_case: true, ..Options::default() });
+        assert_eq!(serde_json::to_string(&ignore_case).unwrap(), r#"{"pear":3,"Apple":2,"banana":2}"#);
+    }
+}
@@ -0,0 +1,461 @@
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use colored::*;
+use regex::{Regex, RegexBuilder};
+use serde_json::{Map, Value};
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, IsTerminal, Read, Seek, SeekFrom, Write};
+use std::thread;
+use std::time::Duration;
+
+pub struct LogViewModule;
+
+type Style = fn(&str) -> ColoredString;
+
+// Lines shown before --follow starts waiting for new ones, as with tail -f
+const TAIL_LINES: usize = 10;
+const POLL: Duration = Duration::from_millis(250);
+
+const TIME_KEYS: [&str; 4] = ["timestamp", "time", "ts", "@timestamp"];
+const LEVEL_KEYS: [&str; 4] = ["level", "lvl", "severity", "log.level"];
+const MESSAGE_KEYS: [&str; 3] = ["msg", "message", "event"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Level {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Fatal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+#[derive(Debug, PartialEq)]
+struct LevelFilter {
+    comparison: Comparison,
+    level: Level,
+}
+
+struct Viewer {
+    filter: Option<LevelFilter>,
+    grep: Option<Regex>,
+    color: bool,
+    timestamp: Regex,
+    level_word: Regex,
+    // Continuation lines such as stack traces carry the level of the line they follow
+    last_level: Option<Level>,
+}
+
+impl Level {
+    fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "trace" | "trc" => Some(Level::Trace),
+            "debug" | "dbg" => Some(Level::Debug),
+            "info" | "inf" | "information" | "notice" => Some(Level::Info),
+            "warn" | "wrn" | "warning" => Some(Level::Warn),
+            "error" | "err" => Some(Level::Error),
+            "fatal" | "critical" | "crit" | "panic" | "emerg" | "alert" => Some(Level::Fatal),
+            _ => None,
+        }
+    }
+
+    // pino and bunyan write levels as numbers
+    fn from_number(number: i64) -> Self {
+        match number {
+            ..=10 => Level::Trace,
+            11..=20 => Level::Debug,
+            21..=30 => Level::Info,
+            31..=40 => Level::Warn,
+            41..=50 => Level::Error,
+            _ => Level::Fatal,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Level::Trace => "trace",
+            Level::Debug => "debug",
+            Level::Info => "info",
+            Level::Warn => "warn",
+            Level::Error => "error",
+            Level::Fatal => "fatal",
+        }
+    }
+
+    fn style(self) -> Style {
+        match self {
+            Level::Trace => |t| t.dimmed(),
+            Level::Debug => |t| t.blue(),
+            Level::Info => |t| t.green(),
+            Level::Warn => |t| t.yellow().bold(),
+            Level::Error => |t| t.red().bold(),
+            Level::Fatal => |t| t.white().on_red().bold(),
+        }
+    }
+}
+
+impl LevelFilter {
+    // level>=warn, level=error, level!=debug, or a bare >=warn
+    fn parse(text: &str) -> Result<Self, String> {
+        let invalid = || format!("Invalid --logs filter '{}'; use e.g. level>=warn, level=error or level!=debug", text);
+        let rest = text.trim().strip_prefix("level").unwrap_or(text.trim()).trim_start();
+        let operators = [
+            (">=", Comparison::GreaterOrEqual),
+            ("<=", Comparison::LessOrEqual),
+            ("!=", Comparison::NotEqual),
+            ("==", Comparison::Equal),
+            ("=", Comparison::Equal),
+            (">", Comparison::Greater),
+            ("<", Comparison::Less),
+        ];
+        let (comparison, name) = operators
+            .iter()
+            .find_map(|(operator, comparison)| rest.strip_prefix(operator).map(|name| (*comparison, name)))
+            .ok_or_else(invalid)?;
+        let level = Level::parse(name).ok_or_else(invalid)?;
+        Ok(LevelFilter { comparison, level })
+    }
+
+    fn allows(&self, level: Level) -> bool {
+        match self.comparison {
+            Comparison::Equal => level == self.level,
+            Comparison::NotEqual => level != self.level,
+            Comparison::Less => level < self.level,
+            Comparison::LessOrEqual => level <= self.level,
+            Comparison::Greater => level > self.level,
+            Comparison::GreaterOrEqual => level >= self.level,
+        }
+    }
+}
+
+impl ToolModule for LogViewModule {
+    fn name(&self) -> &'static str {
+        "log-view"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("logs")
+                .long("logs")
+                .value_name("FILE")
+                .num_args(0..=1)
+                .default_missing_value("-")
+                .help("View a log FILE (or stdin) with colored levels, highlighted timestamps and pretty JSON")
+                .long_help("Print a log FILE, or stdin when no file (or -) is given, with levels colored, timestamps highlighted and JSON pretty-printed: structured JSON lines become a time, level and message line followed by their other fields, and JSON embedded after a text prefix is indented below it. Narrow the output with --filter level>=warn and --grep REGEX, and keep watching the file for new lines with --follow. Lines without a level, such as stack traces, are kept or dropped along with the line above them.")
+        )
+        .arg(
+            Arg::new("follow")
+                .long("follow")
+                .action(ArgAction::SetTrue)
+                .help("Keep printing lines as they are appended to the --logs file, like tail -f")
+                .requires("logs")
+        )
+        .arg(
+            Arg::new("grep")
+                .long("grep")
+                .value_name("REGEX")
+                .help("Only show --logs lines matching REGEX (case-insensitive with --ignore-case)")
+                .requires("logs")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(path) = matches.get_one::<String>("logs") {
+            // --filter is declared by the db-connect module, --ignore-case by the similarity module
+            let filter = matches.get_one::<String>("filter").map(|text| LevelFilter::parse(text)).transpose()?;
+            let grep = matches
+                .get_one::<String>("grep")
+                .map(|pattern| RegexBuilder::new(pattern).case_insensitive(matches.get_flag("ignore-case")).build())
+                .transpose()
+                .map_err(|e| format!("Invalid --grep pattern: {}", e))?;
+            let mut viewer = Viewer::new(filter, grep, io::stdout().is_terminal());
+            if matches.get_flag("follow") {
+                if path == "-" {
+                    return Err("--follow needs a file; piped input is already shown as it arrives".into());
+                }
+                follow(path, &mut viewer)?;
+            } else if path == "-" {
+                show(io::stdin().lock(), &mut viewer)?;
+            } else {
+                let file = File::open(path).map_err(|e| format!("Failed to open '{}': {}", path, e))?;
+                show(BufReader::new(file), &mut viewer)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Viewer {
+    fn new(filter: Option<LevelFilter>, grep: Option<Regex>, color: bool) -> Self {
+        Viewer {
+            filter,
+            grep,
+            color,
+            timestamp: Regex::new(r"\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}:\d{2}(?:[.,]\d+)?(?:Z|[+-]\d{2}:?\d{2})?|\b[A-Z][a-z]{2} [ \d]\d \d{2}:\d{2}:\d{2}\b").unwrap(),
+            level_word: Regex::new(r"\b(TRACE|DEBUG|INFO|NOTICE|WARN|WARNING|ERROR|ERR|FATAL|CRITICAL|CRIT|PANIC)\b|(?i:level|lvl|severity)[=:]\s*\x22?([A-Za-z]+)|\[(?i:(trace|debug|info|warn|warning|error|fatal))\]").unwrap(),
+            last_level: None,
+        }
+    }
+
+    fn paint(&self, text: &str, style: Style) -> String {
+        if self.color {
+            style(text).to_string()
+        } else {
+            text.to_string()
+        }
+    }
+
+    // The rendered line, or None when --filter or --grep drops it
+    fn render(&mut self, line: &str) -> Option<String> {
+        let json = embedded_json(line);
+        let level = match &json {
+            Some((_, object)) => json_level(object),
+            None => None,
+        }
+        .or_else(|| self.text_level(line).map(|(level, _, _)| level));
+        match level {
+            Some(level) => self.last_level = Some(level),
+            None if line.trim().is_empty() || !line.starts_with(char::is_whitespace) => self.last_level = None,
+            None => {}
+        }
+        if let Some(filter) = &self.filter {
+            if !self.last_level.is_some_and(|level| filter.allows(level)) {
+                return None;
+            }
+        }
+        if self.grep.as_ref().is_some_and(|grep| !grep.is_match(line)) {
+            return None;
+        }
+        Some(match json {
+            Some((prefix, object)) if prefix.trim().is_empty() => self.render_structured(object),
+            Some((prefix, object)) => format!("{}\n{}", self.render_text(prefix.trim_end()), indent(&pretty(&Value::Object(object)), "  ")),
+            None => self.render_text(line),
+        })
+    }
+
+    // Where the level word is, so it can be colored in place
+    fn text_level(&self, line: &str) -> Option<(Level, usize, usize)> {
+        self.level_word.captures_iter(line).find_map(|captures| {
+            let word = (1..=3).find_map(|group| captures.get(group))?;
+            Some((Level::parse(word.as_str())?, word.start(), word.end()))
+        })
+    }
+
+    fn render_text(&self, line: &str) -> String {
+        let level = self.text_level(line);
+        let mut output = String::new();
+        let mut position = 0;
+        let mut spans: Vec<(usize, usize, Style)> = self.timestamp.find_iter(line).map(|found| (found.start(), found.end(), (|t| t.cyan()) as Style)).collect();
+        if let Some((level, start, end)) = level {
+            spans.push((start, end, level.style()));
+        }
+        spans.sort_by_key(|span| span.0);
+        for (start, end, style) in spans {
+            if start < position {
+                continue;
+            }
+            output.push_str(&line[position..start]);
+            output.push_str(&self.paint(&line[start..end], style));
+            position = end;
+        }
+        output.push_str(&line[position..]);
+        output
+    }
+
+    fn render_structured(&self, mut object: Map<String, Value>) -> String {
+        let mut head = Vec::new();
+        if let Some(time) = take_first(&mut object, &TIME_KEYS) {
+            head.push(self.paint(&plain(&time), |t| t.cyan()));
+        }
+        if let Some(level) = take_first(&mut object, &LEVEL_KEYS) {
+            let (name, style) = match value_level(&level) {
+                Some(known) => (known.name().to_string(), known.style()),
+                None => (plain(&level), (|t| t.normal()) as Style),
+            };
+            head.push(self.paint(&format!("{:<5}", name.to_uppercase()), style));
+        }
+        if let Some(message) = take_first(&mut object, &MESSAGE_KEYS) {
+            head.push(self.paint(&plain(&message), |t| t.bold()));
+        }
+        if head.is_empty() {
+            return pretty(&Value::Object(object));
+        }
+        let mut output = head.join(" ");
+        if !object.is_empty() {
+            output.push('\n');
+            output.push_str(&indent(&pretty(&Value::Object(object)), "  "));
+        }
+        output
+    }
+}
+
+// A JSON object at the end of the line, and the text in front of it
+fn embedded_json(line: &str) -> Option<(&str, Map<String, Value>)> {
+    let start = line.find('{')?;
+    match serde_json::from_str::<Value>(line[start..].trim_end()) {
+        Ok(Value::Object(object)) => Some((&line[..start], object)),
+        _ => None,
+    }
+}
+
+fn json_level(object: &Map<String, Value>) -> Option<Level> {
+    LEVEL_KEYS.iter().find_map(|key| object.get(*key)).and_then(value_level)
+}
+
+fn value_level(value: &Value) -> Option<Level> {
+    match value {
+        Value::String(name) => Level::parse(name),
+        Value::Number(number) => number.as_i64().map(Level::from_number),
+        _ => None,
+    }
+}
+
+fn take_first(object: &mut Map<String, Value>, keys: &[&str]) -> Option<Value> {
+    keys.iter().find_map(|key| object.remove(*key))
+}
+
+fn plain(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+}
+
+fn indent(text: &str, prefix: &str) -> String {
+    text.lines().map(|line| format!("{}{}", prefix, line)).collect::<Vec<_>>().join("\n")
+}
+
+fn show<R: BufRead>(mut input: R, viewer: &mut Viewer) -> Result<(), Box<dyn Error>> {
+    let mut stdout = io::stdout().lock();
+    let mut buffer = Vec::new();
+    loop {
+        buffer.clear();
+        if input.read_until(b'\n', &mut buffer)? == 0 {
+            return Ok(());
+        }
+        let line = String::from_utf8_lossy(&buffer);
+        if let Some(rendered) = viewer.render(line.trim_end_matches(['\n', '\r'])) {
+            // A closed pipe (e.g. into head) just ends the output
+            if writeln!(stdout, "{}", rendered).is_err() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+// Where the last `count` lines of `data` start, ignoring a final newline
+fn tail_start(data: &[u8], count: usize) -> usize {
+    let body = data.strip_suffix(b"\n").unwrap_or(data);
+    body.iter().enumerate().rev().filter(|(_, byte)| **byte == b'\n').nth(count.saturating_sub(1)).map(|(index, _)| index + 1).unwrap_or(0)
+}
+
+fn follow(path: &str, viewer: &mut Viewer) -> Result<(), Box<dyn Error>> {
+    let mut file = File::open(path).map_err(|e| format!("Failed to open '{}': {}", path, e))?;
+    // Only the end of the file is needed for the first lines
+    let length = file.metadata()?.len();
+    let window = length.min(64 * 1024);
+    file.seek(SeekFrom::Start(length - window))?;
+    let mut data = Vec::new();
+    file.read_to_end(&mut data)?;
+    let mut position = length - window + tail_start(&data, TAIL_LINES) as u64;
+    let mut pending = Vec::new();
+    loop {
+        let current = fs::metadata(path).map(|metadata| metadata.len()).unwrap_or(position);
+        if current < position {
+            eprintln!("{}", viewer.paint(&format!("--- {} was truncated; reading from the start ---", path), |t| t.dimmed()));
+            file = File::open(path)?;
+            position = 0;
+            pending.clear();
+        }
+        if current > position {
+            file.seek(SeekFrom::Start(position))?;
+            let read = (&mut file).take(current - position).read_to_end(&mut pending)?;
+            position += read as u64;
+            // Keep a half-written last line until the rest of it arrives
+            let complete = pending.iter().rposition(|byte| *byte == b'\n').map(|index| index + 1).unwrap_or(0);
+            let chunk: Vec<u8> = pending.drain(..complete).collect();
+            show(chunk.as_slice(), viewer)?;
+            io::stdout().flush()?;
+        }
+        thread::sleep(POLL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn viewer(filter: &str, grep: Option<&str>) -> Viewer {
+        let filter = if filter.is_empty() { None } else { Some(LevelFilter::parse(filter).unwrap()) };
+        Viewer::new(filter, grep.map(|pattern| Regex::new(pattern).unwrap()), false)
+    }
+
+    #[test]
+    fn test_filter_parse() {
+        assert_eq!(LevelFilter::parse("level>=warn").unwrap(), LevelFilter { comparison: Comparison::GreaterOrEqual, level: Level::Warn });
+        assert_eq!(LevelFilter::parse("level != DEBUG").unwrap(), LevelFilter { comparison: Comparison::NotEqual, level: Level::Debug });
+        assert_eq!(LevelFilter::parse("=error").unwrap().level, Level::Error);
+        assert!(LevelFilter::parse("level>=loud").is_err());
+        assert!(LevelFilter::parse("warn").is_err());
+        assert!(LevelFilter::parse("level<info").unwrap().allows(Level::Debug));
+        assert!(!LevelFilter::parse("level>info").unwrap().allows(Level::Info));
+    }
+
+    #[test]
+    fn test_text_lines() {
+        let mut all = viewer("", None);
+        assert_eq!(all.text_level("2024-05-01 12:00:00 [warn] disk at 91%").map(|found| found.0), Some(Level::Warn));
+        assert_eq!(all.text_level("time=now level=error msg=boom").map(|found| found.0), Some(Level::Error));
+        assert_eq!(all.text_level("the error was ignored"), None);
+        assert_eq!(all.render("plain line").unwrap(), "plain line");
+
+        let mut warnings = viewer("level>=warn", None);
+        assert_eq!(warnings.render("INFO started"), None);
+        assert_eq!(warnings.render("ERROR failed to connect").unwrap(), "ERROR failed to connect");
+        // The stack trace stays with its error
+        assert!(warnings.render("    at connect (db.js:10)").is_some());
+        assert_eq!(warnings.render("INFO retrying"), None);
+        assert_eq!(warnings.render("    at retry (db.js:20)"), None);
+
+        let mut grep = viewer("", Some("user=\\d+"));
+        assert!(grep.render("INFO login user=42").is_some());
+        assert!(grep.render("INFO login user=bob").is_none());
+    }
+
+    #[test]
+    fn test_json_lines() {
+        let mut all = viewer("", None);
+        assert_eq!(
+            all.render(r#"{"time":"2024-05-01T12:00:00Z","level":"info","msg":"listening","port":8080}"#).unwrap(),
+            "2024-05-01T12:00:00Z INFO  listening\n  {\n    \"port\": 8080\n  }"
+        );
+        assert_eq!(all.render(r#"{"level":50,"msg":"crash"}"#).unwrap(), "ERROR crash");
+        assert_eq!(all.render(r#"worker: {"a":1}"#).unwrap(), "worker:\n  {\n    \"a\": 1\n  }");
+        let mut errors = viewer("level=error", None);
+        assert!(errors.render(r#"{"level":50,"msg":"crash"}"#).is_some());
+        assert!(errors.render(r#"{"level":"debug","msg":"tick"}"#).is_none());
+    }
+
+    #[test]
+    fn test_tail_start() {
+        let data = b"one\ntwo\nthree\nfour\n";
+        assert_eq!(&data[tail_start(data, 2)..], b"three\nfour\n");
+        assert_eq!(tail_start(data, 10), 0);
+        assert_eq!(tail_start(b"a\nb", 1), 2);
+    }
+}
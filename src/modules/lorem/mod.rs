@@ -0,0 +1,228 @@
+use crate::tool_module::ToolModule;
+use arboard::Clipboard;
+use clap::{Arg, ArgMatches, Command};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::error::Error;
+
+pub struct LoremModule;
+
+const CLASSIC_OPENING: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit";
+const CLASSIC_OPENING_WORDS: [&str; 8] = ["lorem", "ipsum", "dolor", "sit", "amet", "consectetur", "adipiscing", "elit"];
+
+const CLASSIC_WORDS: &[&str] = &[
+    "lorem", "ipsum", "dolor", "sit", "amet", "consectetur", "adipiscing", "elit", "sed", "do",
+    "eiusmod", "tempor", "incididunt", "ut", "labore", "et", "dolore", "magna", "aliqua", "enim",
+    "ad", "minim", "veniam", "quis", "nostrud", "exercitation", "ullamco", "laboris", "nisi",
+    "aliquip", "ex", "ea", "commodo", "consequat", "duis", "aute", "irure", "in", "reprehenderit",
+    "voluptate", "velit", "esse", "cillum", "fugiat", "nulla", "pariatur", "excepteur", "sint",
+    "occaecat", "cupidatat", "non", "proident", "sunt", "culpa", "qui", "officia", "deserunt",
+    "mollit", "anim", "id", "est", "laborum", "vitae", "sapien", "pellentesque", "habitant",
+    "morbi", "tristique", "senectus", "netus", "malesuada", "fames", "ac", "turpis", "egestas",
+];
+
+const HIPSTER_WORDS: &[&str] = &[
+    "artisan", "kombucha", "sriracha", "vinyl", "flannel", "fixie", "typewriter", "kale", "chips",
+    "mustache", "beard", "oil", "pour-over", "cold-pressed", "single-origin", "coffee", "tote",
+    "bag", "normcore", "aesthetic", "vegan", "gluten-free", "small", "batch", "craft", "beer",
+    "locavore", "farm-to-table", "succulents", "cornhole", "yr", "heirloom", "organic", "tofu",
+    "jianbing", "meditation", "tattooed", "chillwave", "leggings", "bespoke", "sustainable",
+    "thundercats", "polaroid", "ethical", "retro", "selvage", "denim", "microdosing", "lumbersexual",
+    "raclette", "biodiesel", "brunch", "wolf", "moon", "forage", "umami", "shoreditch", "banjo",
+];
+
+const TECH_WORDS: &[&str] = &[
+    "cloud-native", "microservices", "kubernetes", "serverless", "pipeline", "deploy", "latency",
+    "throughput", "scalable", "api", "endpoint", "cache", "cluster", "container", "observability",
+    "telemetry", "rollback", "canary", "release", "refactor", "monorepo", "schema", "migration",
+    "queue", "stream", "event-driven", "idempotent", "sharding", "replica", "failover", "load",
+    "balancer", "edge", "runtime", "compiler", "async", "await", "middleware", "payload", "token",
+    "webhook", "backlog", "sprint", "standup", "roadmap", "stakeholder", "synergy", "leverage",
+    "bandwidth", "paradigm", "blockchain", "machine", "learning", "dashboard", "metrics", "uptime",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Unit {
+    Paragraphs,
+    Sentences,
+    Words,
+}
+
+impl ToolModule for LoremModule {
+    fn name(&self) -> &'static str {
+        "lorem"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("lorem")
+                .long("lorem")
+                .value_name("COUNT")
+                .help("Generate placeholder text, e.g. 3p, 5s or 50w (default: 1p)")
+                .long_help("Generate placeholder text and copy it to the clipboard. COUNT is a number followed by p (paragraphs), s (sentences) or w (words), e.g. 3p, 5s or 50w; a bare number means paragraphs. Classic text starts with \"Lorem ipsum dolor sit amet\". Use --lorem-style for hipster or tech flavoured filler.")
+                .num_args(0..=1)
+                .default_missing_value("1p")
+        )
+        .arg(
+            Arg::new("lorem-style")
+                .long("lorem-style")
+                .value_name("STYLE")
+                .help("Vocabulary for --lorem")
+                .value_parser(["classic", "hipster", "tech"])
+                .default_value("classic")
+                .requires("lorem")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(count) = matches.get_one::<String>("lorem") {
+            let (count, unit) = parse_count(count)?;
+            let style = matches.get_one::<String>("lorem-style").map(String::as_str).unwrap_or("classic");
+            let text = generate(&mut rand::thread_rng(), count, unit, style);
+            copy_to_clipboard_and_print(&text);
+        }
+        Ok(())
+    }
+}
+
+// "3p", "5 sentences", "50w" or a bare number of paragraphs
+fn parse_count(text: &str) -> Result<(usize, Unit), String> {
+    let text = text.trim().to_lowercase();
+    let split = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+    let (number, suffix) = text.split_at(split);
+    let count: usize = number.parse().map_err(|_| format!("Invalid count '{}'. Use e.g. 3p, 5s or 50w", text))?;
+    if count == 0 {
+        return Err("The count must be at least 1".to_string());
+    }
+    let unit = match suffix.trim() {
+        "" | "p" | "paragraph" | "paragraphs" => Unit::Paragraphs,
+        "s" | "sentence" | "sentences" => Unit::Sentences,
+        "w" | "word" | "words" => Unit::Words,
+        other => return Err(format!("Unknown unit '{}'. Use p (paragraphs), s (sentences) or w (words)", other)),
+    };
+    Ok((count, unit))
+}
+
+fn vocabulary(style: &str) -> &'static [&'static str] {
+    match style {
+        "hipster" => HIPSTER_WORDS,
+        "tech" => TECH_WORDS,
+        _ => CLASSIC_WORDS,
+    }
+}
+
+fn generate<R: Rng>(rng: &mut R, count: usize, unit: Unit, style: &str) -> String {
+    let words = vocabulary(style);
+    let classic = style == "classic";
+    match unit {
+        Unit::Words => {
+            let mut chosen: Vec<&str> = if classic { CLASSIC_OPENING_WORDS.to_vec() } else { Vec::new() };
+            chosen.truncate(count);
+            while chosen.len() < count {
+                chosen.push(words.choose(rng).copied().unwrap_or("lorem"));
+            }
+            chosen.join(" ")
+        }
+        Unit::Sentences => (0..count)
+            .map(|i| if i == 0 && classic { format!("{}.", CLASSIC_OPENING) } else { sentence(rng, words) })
+            .collect::<Vec<_>>()
+            .join(" "),
+        Unit::Paragraphs => (0..count)
+            .map(|i| {
+                let sentences = rng.gen_range(4..=7);
+                (0..sentences)
+                    .map(|j| if i == 0 && j == 0 && classic { format!("{}.", CLASSIC_OPENING) } else { sentence(rng, words) })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+    }
+}
+
+// 6 to 14 words, capitalized, with an occasional comma
+fn sentence<R: Rng>(rng: &mut R, words: &[&str]) -> String {
+    let length = rng.gen_range(6..=14);
+    let mut sentence = String::new();
+    for i in 0..length {
+        let word = words.choose(rng).copied().unwrap_or("lorem");
+        if i == 0 {
+            let mut chars = word.chars();
+            sentence.extend(chars.next().into_iter().flat_map(char::to_uppercase).chain(chars));
+            continue;
+        }
+        if i > 2 && i < length - 2 && rng.gen_bool(0.1) {
+            sentence.push(',');
+        }
+        sentence.push(' ');
+        sentence.push_str(word);
+    }
+    sentence.push('.');
+    sentence
+}
+
+fn copy_to_clipboard_and_print(text: &str) {
+    match Clipboard::new() {
+        Ok(mut clipboard) => {
+            if let Err(e) = clipboard.set_text(text) {
+                eprintln!("Warning: Failed to copy to clipboard: {}", e);
+                println!("{}", text);
+            } else {
+                println!("{}\n(copied to clipboard)", text);
+            }
+        }
+        Err(e) => {
+            eprintln!("Warning: Failed to access clipboard: {}", e);
+            println!("{}", text);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_parse_count() {
+        assert_eq!(parse_count("3p").unwrap(), (3, Unit::Paragraphs));
+        assert_eq!(parse_count("50W").unwrap(), (50, Unit::Words));
+        assert_eq!(parse_count("5 sentences").unwrap(), (5, Unit::Sentences));
+        assert_eq!(parse_count("2").unwrap(), (2, Unit::Paragraphs));
+        assert!(parse_count("0w").is_err());
+        assert!(parse_count("3x").is_err());
+        assert!(parse_count("p").is_err());
+    }
+
+    #[test]
+    fn test_words() {
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_eq!(generate(&mut rng, 5, Unit::Words, "classic"), "lorem ipsum dolor sit amet");
+        let text = generate(&mut rng, 50, Unit::Words, "classic");
+        assert_eq!(text.split(' ').count(), 50);
+        assert!(text.starts_with("lorem ipsum dolor sit amet consectetur adipiscing elit "));
+    }
+
+    #[test]
+    fn test_sentences_and_paragraphs() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let sentences = generate(&mut rng, 4, Unit::Sentences, "classic");
+        assert!(sentences.starts_with("Lorem ipsum dolor sit amet, consectetur adipiscing elit. "));
+        assert_eq!(sentences.matches('.').count(), 4);
+
+        let paragraphs = generate(&mut rng, 3, Unit::Paragraphs, "classic");
+        assert_eq!(paragraphs.split("\n\n").count(), 3);
+        assert!(paragraphs.split("\n\n").all(|paragraph| paragraph.ends_with('.')));
+    }
+
+    #[test]
+    fn test_styles() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let tech = generate(&mut rng, 20, Unit::Words, "tech");
+        assert!(tech.split(' ').all(|word| TECH_WORDS.contains(&word)));
+        let hipster = generate(&mut rng, 2, Unit::Sentences, "hipster");
+        assert!(!hipster.starts_with("Lorem"));
+        assert!(hipster.chars().next().unwrap().is_uppercase());
+    }
+}
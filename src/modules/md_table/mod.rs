@@ -0,0 +1,304 @@
+use crate::csv_json::parse_delimiter;
+use crate::tool_module::{read_input, ToolModule};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use csv::ReaderBuilder;
+use std::error::Error;
+use unicode_width::UnicodeWidthStr;
+
+pub struct MdTableModule;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Align {
+    // No colons in the separator row
+    Default,
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Debug, PartialEq)]
+struct Table {
+    header: Vec<String>,
+    align: Vec<Align>,
+    rows: Vec<Vec<String>>,
+}
+
+impl ToolModule for MdTableModule {
+    fn name(&self) -> &'static str {
+        "md-table"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("md-table")
+                .long("md-table")
+                .value_name("FILE")
+                .num_args(0..=1)
+                .help("Reformat a Markdown table (or CSV/TSV) into a column-aligned Markdown table (or read from stdin)")
+                .long_help("Tidy a hand-edited Markdown table: cells are trimmed and padded so the pipes line up, missing cells and the separator row are filled in, and alignment colons are kept. CSV or TSV input (anything whose first line has no |) becomes a Markdown table with the first row as header; pipes in cells are escaped. Use --drop-column, --add-column and --align to reshape it. If no file (or -) is given, reads from stdin.")
+        )
+        .arg(
+            Arg::new("drop-column")
+                .long("drop-column")
+                .value_name("COLUMN")
+                .action(ArgAction::Append)
+                .help("Remove a column from --md-table by header or 1-based number (repeatable)")
+                .requires("md-table")
+        )
+        .arg(
+            Arg::new("add-column")
+                .long("add-column")
+                .value_name("HEADER")
+                .action(ArgAction::Append)
+                .help("Append an empty column to --md-table (repeatable)")
+                .requires("md-table")
+        )
+        .arg(
+            Arg::new("align")
+                .long("align")
+                .value_name("LIST")
+                .help("Column alignment for --md-table: comma-separated l, c, r or - (none), one per column; a single value applies to all")
+                .long_help("Set column alignment for --md-table as a comma-separated list with one entry per column: l (left), c (center), r (right), - (no colons), or an empty entry to keep what the table has. A single value applies to every column, e.g. --align r; --align ,,r only right-aligns the third.")
+                .requires("md-table")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if matches.contains_id("md-table") {
+            let input = read_input(matches.get_one::<String>("md-table"))?;
+            let delimiter = matches.get_one::<String>("delimiter").map(|delimiter| parse_delimiter(delimiter)).transpose()?;
+            let mut table = parse_table(&input, delimiter)?;
+            for column in matches.get_many::<String>("drop-column").into_iter().flatten() {
+                drop_column(&mut table, column)?;
+            }
+            for header in matches.get_many::<String>("add-column").into_iter().flatten() {
+                add_column(&mut table, header);
+            }
+            if let Some(spec) = matches.get_one::<String>("align") {
+                set_alignment(&mut table, spec)?;
+            }
+            print!("{}", format_table(&table));
+        }
+        Ok(())
+    }
+}
+
+// Markdown when the first line has a pipe, otherwise CSV (or TSV when it has tabs)
+fn parse_table(input: &str, delimiter: Option<u8>) -> Result<Table, String> {
+    let first = input.lines().find(|line| !line.trim().is_empty()).ok_or("No table found in the input")?;
+    let mut table = if first.contains('|') && delimiter.is_none() {
+        parse_markdown(input)
+    } else {
+        let delimiter = delimiter.unwrap_or(if first.contains('\t') { b'\t' } else { b',' });
+        parse_delimited(input, delimiter)?
+    };
+    // Every row as wide as the widest one
+    let columns = table.rows.iter().map(Vec::len).chain([table.header.len()]).max().unwrap_or(0);
+    table.header.resize(columns, String::new());
+    table.align.resize(columns, Align::Default);
+    for row in &mut table.rows {
+        row.resize(columns, String::new());
+    }
+    Ok(table)
+}
+
+fn parse_markdown(input: &str) -> Table {
+    let mut lines = input.lines().map(str::trim).filter(|line| !line.is_empty()).map(split_row).peekable();
+    let header = lines.next().unwrap_or_default();
+    let align = match lines.peek().and_then(|cells| parse_separator(cells)) {
+        Some(align) => {
+            lines.next();
+            align
+        }
+        None => Vec::new(),
+    };
+    Table { header, align, rows: lines.collect() }
+}
+
+// Cells between unescaped pipes; the outer pipes are optional
+fn split_row(line: &str) -> Vec<String> {
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = match line.strip_suffix('|') {
+        Some(rest) if !rest.ends_with('\\') => rest,
+        _ => line,
+    };
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut escaped = false;
+    for c in line.chars() {
+        if c == '|' && !escaped {
+            cells.push(cell.trim().to_string());
+            cell.clear();
+        } else {
+            cell.push(c);
+        }
+        escaped = c == '\\' && !escaped;
+    }
+    cells.push(cell.trim().to_string());
+    cells
+}
+
+// The alignment row, like | :--- | :---: | ---: |
+fn parse_separator(cells: &[String]) -> Option<Vec<Align>> {
+    cells
+        .iter()
+        .map(|cell| {
+            let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+            if dashes.is_empty() || !dashes.chars().all(|c| c == '-') {
+                return None;
+            }
+            Some(match (cell.starts_with(':'), cell.ends_with(':')) {
+                (true, true) => Align::Center,
+                (true, false) => Align::Left,
+                (false, true) => Align::Right,
+                (false, false) => Align::Default,
+            })
+        })
+        .collect()
+}
+
+fn parse_delimited(input: &str, delimiter: u8) -> Result<Table, String> {
+    let mut reader = ReaderBuilder::new().delimiter(delimiter).has_headers(false).flexible(true).from_reader(input.as_bytes());
+    let escape = |field: &str| field.trim().replace('|', "\\|").replace('\n', "<br>");
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| format!("Invalid CSV: {}", e))?;
+        rows.push(record.iter().map(escape).collect::<Vec<String>>());
+    }
+    if rows.is_empty() {
+        return Err("No table found in the input".to_string());
+    }
+    let header = rows.remove(0);
+    Ok(Table { header, align: Vec::new(), rows })
+}
+
+// By header (ignoring case) or 1-based column number
+fn find_column(table: &Table, column: &str) -> Result<usize, String> {
+    if let Some(index) = table.header.iter().position(|header| header.eq_ignore_ascii_case(column.trim())) {
+        return Ok(index);
+    }
+    match column.trim().parse::<usize>() {
+        Ok(number) if (1..=table.header.len()).contains(&number) => Ok(number - 1),
+        _ => Err(format!("No column '{}'; the columns are: {}", column, table.header.join(", "))),
+    }
+}
+
+fn drop_column(table: &mut Table, column: &str) -> Result<(), String> {
+    let index = find_column(table, column)?;
+    table.header.remove(index);
+    table.align.remove(index);
+    for row in &mut table.rows {
+        row.remove(index);
+    }
+    Ok(())
+}
+
+fn add_column(table: &mut Table, header: &str) {
+    table.header.push(header.trim().replace('|', "\\|"));
+    table.align.push(Align::Default);
+    for row in &mut table.rows {
+        row.push(String::new());
+    }
+}
+
+fn set_alignment(table: &mut Table, spec: &str) -> Result<(), String> {
+    let values: Vec<&str> = spec.split(',').map(str::trim).collect();
+    if values.len() > table.align.len() {
+        return Err(format!("--align has {} entries but the table has {} columns", values.len(), table.align.len()));
+    }
+    for (index, current) in table.align.iter_mut().enumerate() {
+        let value = if values.len() == 1 { values[0] } else { values.get(index).copied().unwrap_or("") };
+        *current = match value.to_lowercase().as_str() {
+            "" => *current,
+            "l" | "left" => Align::Left,
+            "c" | "center" => Align::Center,
+            "r" | "right" => Align::Right,
+            "-" | "none" => Align::Default,
+            _ => return Err(format!("Invalid alignment '{}'. Use l, c, r or -", value)),
+        };
+    }
+    Ok(())
+}
+
+fn pad(text: &str, width: usize, align: Align) -> String {
+    let fill = width.saturating_sub(text.width());
+    match align {
+        Align::Right => format!("{}{}", " ".repeat(fill), text),
+        Align::Center => format!("{}{}{}", " ".repeat(fill / 2), text, " ".repeat(fill - fill / 2)),
+        Align::Default | Align::Left => format!("{}{}", text, " ".repeat(fill)),
+    }
+}
+
+fn format_table(table: &Table) -> String {
+    // Three dashes is the shortest separator every renderer accepts
+    let widths: Vec<usize> = (0..table.header.len())
+        .map(|i| table.rows.iter().map(|row| row[i].width()).chain([table.header[i].width(), 3]).max().unwrap_or(3))
+        .collect();
+    let line = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+    let aligned = |row: &[String]| row.iter().zip(&widths).zip(&table.align).map(|((cell, width), align)| pad(cell, *width, *align)).collect();
+    let separator = widths
+        .iter()
+        .zip(&table.align)
+        .map(|(width, align)| match align {
+            Align::Default => "-".repeat(*width),
+            Align::Left => format!(":{}", "-".repeat(width - 1)),
+            Align::Right => format!("{}:", "-".repeat(width - 1)),
+            Align::Center => format!(":{}:", "-".repeat(width - 2)),
+        })
+        .collect();
+    let mut output = line(aligned(&table.header));
+    output.push_str(&line(separator));
+    for row in &table.rows {
+        output.push_str(&line(aligned(row)));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reformat_sloppy_markdown() {
+        let input = "Name|Role | Since\n:--|:-:|--:\n| Ada | Engineer|1843|\n|Grace|Admiral\n";
+        let table = parse_table(input, None).unwrap();
+        assert_eq!(table.align, [Align::Left, Align::Center, Align::Right]);
+        assert_eq!(
+            format_table(&table),
+            "| Name  |   Role   | Since |\n\
+             | :---- | :------: | ----: |\n\
+             | Ada   | Engineer |  1843 |\n\
+             | Grace | Admiral  |       |\n"
+        );
+    }
+
+    #[test]
+    fn test_escaped_pipes_and_wide_characters() {
+        assert_eq!(split_row(r"| a \| b | `x` |"), [r"a \| b", "`x`"]);
+        let table = parse_table("| City | Note |\n| 東京 | ✓ |\n", None).unwrap();
+        assert_eq!(format_table(&table), "| City | Note |\n| ---- | ---- |\n| 東京 | ✓    |\n");
+    }
+
+    #[test]
+    fn test_csv_and_tsv_input() {
+        let table = parse_table("id,\"name, full\",cmd\n1,\"Lovelace, Ada\",a|b\n", None).unwrap();
+        assert_eq!(table.header, ["id", "name, full", "cmd"]);
+        assert_eq!(table.rows, [["1", "Lovelace, Ada", r"a\|b"]]);
+        let table = parse_table("a\tb\n1\t2\n", None).unwrap();
+        assert_eq!(table.header, ["a", "b"]);
+        assert!(parse_table("\n\n", None).is_err());
+    }
+
+    #[test]
+    fn test_reshape_columns() {
+        let mut table = parse_table("| a | b | c |\n|---|---|---|\n| 1 | 2 | 3 |\n", None).unwrap();
+        drop_column(&mut table, "B").unwrap();
+        drop_column(&mut table, "2").unwrap();
+        add_column(&mut table, "Status");
+        set_alignment(&mut table, ",c").unwrap();
+        assert_eq!(format_table(&table), "| a   | Status |\n| --- | :----: |\n| 1   |        |\n");
+        assert!(drop_column(&mut table, "missing").is_err());
+        assert!(set_alignment(&mut table, "x").is_err());
+        assert!(set_alignment(&mut table, "l,l,l").is_err());
+    }
+}
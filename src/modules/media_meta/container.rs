@@ -0,0 +1,313 @@
+use chrono::DateTime;
+use std::io::{self, Read, Seek, SeekFrom};
+
+// Basic facts about audio and video files, read straight from the container
+// headers: MP4/QuickTime boxes, WAV chunks and the FLAC stream info block.
+
+// Seconds between 1904-01-01 (the MP4 epoch) and 1970-01-01
+const MP4_EPOCH_OFFSET: u64 = 2_082_844_800;
+// Larger moov boxes are not read; real ones are at most a few MB
+const MAX_MOOV_BYTES: u64 = 64 * 1024 * 1024;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct MediaInfo {
+    pub duration: Option<f64>,
+    pub created: Option<String>,
+    pub tracks: Vec<Track>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Track {
+    pub kind: &'static str,
+    pub codec: String,
+    pub details: String,
+}
+
+pub fn read_mp4<R: Read + Seek>(reader: &mut R) -> io::Result<MediaInfo> {
+    let end = reader.seek(SeekFrom::End(0))?;
+    let mut position = reader.seek(SeekFrom::Start(0))?;
+    while position + 8 <= end {
+        let mut header = [0u8; 8];
+        reader.read_exact(&mut header)?;
+        let mut size = u32::from_be_bytes(header[..4].try_into().unwrap()) as u64;
+        let mut header_len = 8;
+        if size == 1 {
+            let mut large = [0u8; 8];
+            reader.read_exact(&mut large)?;
+            size = u64::from_be_bytes(large);
+            header_len = 16;
+        } else if size == 0 {
+            size = end - position;
+        }
+        if size < header_len {
+            break;
+        }
+        if &header[4..] == b"moov" {
+            let length = size - header_len;
+            if length > MAX_MOOV_BYTES {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "moov box is too large"));
+            }
+            let mut moov = vec![0u8; length as usize];
+            reader.read_exact(&mut moov)?;
+            return Ok(parse_moov(&moov));
+        }
+        position = reader.seek(SeekFrom::Start(position + size))?;
+    }
+    Err(io::Error::new(io::ErrorKind::InvalidData, "no moov box found"))
+}
+
+// Child boxes of a container box's payload, as (type, payload)
+fn boxes(data: &[u8]) -> Vec<(&[u8], &[u8])> {
+    let mut children = Vec::new();
+    let mut offset = 0;
+    while offset + 8 <= data.len() {
+        let size = u32::from_be_bytes(data[offset..offset + 4].try_into().unwrap()) as usize;
+        let (start, end) = match size {
+            0 => (offset + 8, data.len()),
+            1 if offset + 16 <= data.len() => (offset + 16, offset + u64::from_be_bytes(data[offset + 8..offset + 16].try_into().unwrap()) as usize),
+            _ => (offset + 8, offset + size),
+        };
+        if end < start || end > data.len() {
+            break;
+        }
+        children.push((&data[offset + 4..offset + 8], &data[start..end]));
+        offset = end;
+    }
+    children
+}
+
+fn child<'a>(data: &'a [u8], kind: &[u8]) -> Option<&'a [u8]> {
+    boxes(data).into_iter().find(|(child_kind, _)| *child_kind == kind).map(|(_, payload)| payload)
+}
+
+fn be_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(data.get(offset..offset + 2)?.try_into().ok()?))
+}
+
+fn be_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(offset..offset + 4)?.try_into().ok()?))
+}
+
+fn be_u64(data: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_be_bytes(data.get(offset..offset + 8)?.try_into().ok()?))
+}
+
+fn parse_moov(moov: &[u8]) -> MediaInfo {
+    let mut info = MediaInfo::default();
+    if let Some(mvhd) = child(moov, b"mvhd") {
+        let (created, timescale, duration) = match mvhd.first() {
+            Some(1) => (be_u64(mvhd, 4), be_u32(mvhd, 20), be_u64(mvhd, 24)),
+            _ => (be_u32(mvhd, 4).map(u64::from), be_u32(mvhd, 12), be_u32(mvhd, 16).map(u64::from)),
+        };
+        if let (Some(timescale), Some(duration)) = (timescale, duration) {
+            if timescale > 0 {
+                info.duration = Some(duration as f64 / timescale as f64);
+            }
+        }
+        info.created = created
+            .filter(|&seconds| seconds > MP4_EPOCH_OFFSET)
+            .and_then(|seconds| DateTime::from_timestamp((seconds - MP4_EPOCH_OFFSET) as i64, 0))
+            .map(|date| date.format("%Y-%m-%d %H:%M:%S UTC").to_string());
+    }
+    for (kind, trak) in boxes(moov) {
+        if kind == b"trak" {
+            info.tracks.extend(parse_trak(trak));
+        }
+    }
+    info
+}
+
+fn parse_trak(trak: &[u8]) -> Option<Track> {
+    let mdia = child(trak, b"mdia")?;
+    let handler = child(mdia, b"hdlr")?.get(8..12)?;
+    let stsd = child(child(child(mdia, b"minf")?, b"stbl")?, b"stsd")?;
+    let codec = String::from_utf8_lossy(stsd.get(12..16)?).trim().to_string();
+    match handler {
+        b"vide" => {
+            let tkhd = child(trak, b"tkhd")?;
+            let offset = if tkhd.first() == Some(&1) { 88 } else { 76 };
+            let (width, height) = (be_u32(tkhd, offset)? >> 16, be_u32(tkhd, offset + 4)? >> 16);
+            Some(Track { kind: "Video", codec, details: format!("{}×{}", width, height) })
+        }
+        // The sample entry starts at offset 8: channels at +24, sample rate (16.16) at +32
+        b"soun" => Some(Track { kind: "Audio", codec, details: audio_details(be_u16(stsd, 32)?, be_u16(stsd, 40)? as u32, None) }),
+        b"subt" | b"sbtl" | b"text" => Some(Track { kind: "Subtitles", codec, details: String::new() }),
+        _ => None,
+    }
+}
+
+pub fn read_wav<R: Read + Seek>(reader: &mut R) -> io::Result<MediaInfo> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+    let mut header = [0u8; 12];
+    reader.read_exact(&mut header)?;
+    if &header[..4] != b"RIFF" || &header[8..] != b"WAVE" {
+        return Err(invalid("not a WAVE file"));
+    }
+    let mut format: Option<[u8; 16]> = None;
+    let mut data_size = None;
+    let mut chunk = [0u8; 8];
+    while data_size.is_none() && reader.read_exact(&mut chunk).is_ok() {
+        let size = u32::from_le_bytes(chunk[4..].try_into().unwrap()) as i64;
+        let mut skip = size + size % 2;
+        match &chunk[..4] {
+            b"fmt " if size >= 16 => {
+                let mut fmt = [0u8; 16];
+                reader.read_exact(&mut fmt)?;
+                format = Some(fmt);
+                skip -= 16;
+            }
+            b"data" => data_size = Some(size as u64),
+            _ => {}
+        }
+        reader.seek(SeekFrom::Current(skip))?;
+    }
+    let fmt = format.ok_or_else(|| invalid("no fmt chunk found"))?;
+    let le_u16 = |offset: usize| u16::from_le_bytes([fmt[offset], fmt[offset + 1]]);
+    let le_u32 = |offset: usize| u32::from_le_bytes(fmt[offset..offset + 4].try_into().unwrap());
+    let codec = match le_u16(0) {
+        1 => "PCM".to_string(),
+        3 => "IEEE float".to_string(),
+        6 => "A-law".to_string(),
+        7 => "μ-law".to_string(),
+        0xFFFE => "PCM (extensible)".to_string(),
+        other => format!("format 0x{:04X}", other),
+    };
+    let byte_rate = le_u32(8);
+    Ok(MediaInfo {
+        duration: data_size.filter(|_| byte_rate > 0).map(|size| size as f64 / byte_rate as f64),
+        created: None,
+        tracks: vec![Track { kind: "Audio", codec, details: audio_details(le_u16(2), le_u32(4), Some(le_u16(14))) }],
+    })
+}
+
+pub fn read_flac<R: Read>(reader: &mut R) -> io::Result<MediaInfo> {
+    let mut header = [0u8; 42];
+    reader.read_exact(&mut header)?;
+    // "fLaC", then the STREAMINFO block, which must come first
+    if &header[..4] != b"fLaC" || header[4] & 0x7F != 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "not a FLAC stream"));
+    }
+    let info = &header[8..];
+    let sample_rate = (info[10] as u32) << 12 | (info[11] as u32) << 4 | (info[12] as u32) >> 4;
+    let channels = ((info[12] >> 1) & 0x07) as u16 + 1;
+    let bits = (((info[12] & 0x01) << 4) | (info[13] >> 4)) as u16 + 1;
+    let samples = ((info[13] & 0x0F) as u64) << 32 | u32::from_be_bytes(info[14..18].try_into().unwrap()) as u64;
+    Ok(MediaInfo {
+        duration: (sample_rate > 0 && samples > 0).then(|| samples as f64 / sample_rate as f64),
+        created: None,
+        tracks: vec![Track { kind: "Audio", codec: "FLAC".to_string(), details: audio_details(channels, sample_rate, Some(bits)) }],
+    })
+}
+
+fn audio_details(channels: u16, sample_rate: u32, bits: Option<u16>) -> String {
+    let layout = match channels {
+        1 => "mono".to_string(),
+        2 => "stereo".to_string(),
+        other => format!("{} channels", other),
+    };
+    match bits {
+        Some(bits) => format!("{}, {} Hz, {}-bit", layout, sample_rate, bits),
+        None => format!("{}, {} Hz", layout, sample_rate),
+    }
+}
+
+// 3725.4 -> "1:02:05"
+pub fn format_duration(seconds: f64) -> String {
+    let total = seconds.round() as u64;
+    let (hours, minutes, seconds) = (total / 3600, total / 60 % 60, total % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn mp4_box(kind: &[u8], payload: &[u8]) -> Vec<u8> {
+        let mut data = ((payload.len() + 8) as u32).to_be_bytes().to_vec();
+        data.extend_from_slice(kind);
+        data.extend_from_slice(payload);
+        data
+    }
+
+    fn track(handler: &[u8], stsd: &[u8], tkhd: &[u8]) -> Vec<u8> {
+        let mut hdlr = vec![0u8; 8];
+        hdlr.extend_from_slice(handler);
+        hdlr.extend_from_slice(&[0u8; 12]);
+        let stbl = mp4_box(b"stbl", &mp4_box(b"stsd", stsd));
+        let mdia = [mp4_box(b"hdlr", &hdlr), mp4_box(b"minf", &stbl)].concat();
+        mp4_box(b"trak", &[mp4_box(b"tkhd", tkhd), mp4_box(b"mdia", &mdia)].concat())
+    }
+
+    #[test]
+    fn test_read_mp4() {
+        // mvhd v0: created 2024-01-02 03:04:05 UTC, timescale 1000, duration 65.5 s
+        let mut mvhd = vec![0u8; 4];
+        mvhd.extend_from_slice(&((1_704_164_645 + MP4_EPOCH_OFFSET) as u32).to_be_bytes());
+        mvhd.extend_from_slice(&[0u8; 4]);
+        mvhd.extend_from_slice(&1000u32.to_be_bytes());
+        mvhd.extend_from_slice(&65_500u32.to_be_bytes());
+        let mut tkhd = vec![0u8; 76];
+        tkhd.extend_from_slice(&(1920u32 << 16).to_be_bytes());
+        tkhd.extend_from_slice(&(1080u32 << 16).to_be_bytes());
+        let mut video_stsd = vec![0u8; 8];
+        video_stsd.extend_from_slice(&[0, 0, 0, 86]);
+        video_stsd.extend_from_slice(b"avc1");
+        let mut audio_stsd = vec![0u8; 8];
+        audio_stsd.extend_from_slice(&[0, 0, 0, 36]);
+        audio_stsd.extend_from_slice(b"mp4a");
+        audio_stsd.extend_from_slice(&[0u8; 16]);
+        audio_stsd.extend_from_slice(&2u16.to_be_bytes());
+        audio_stsd.extend_from_slice(&[0, 16, 0, 0, 0, 0]);
+        audio_stsd.extend_from_slice(&48_000u16.to_be_bytes());
+        audio_stsd.extend_from_slice(&[0, 0]);
+        let moov = [mp4_box(b"mvhd", &mvhd), track(b"vide", &video_stsd, &tkhd), track(b"soun", &audio_stsd, &[0u8; 84])].concat();
+        let file = [mp4_box(b"ftyp", b"isom\0\0\0\0"), mp4_box(b"mdat", &[0u8; 32]), mp4_box(b"moov", &moov)].concat();
+
+        let info = read_mp4(&mut Cursor::new(file)).unwrap();
+        assert_eq!(info.duration, Some(65.5));
+        assert_eq!(info.created.as_deref(), Some("2024-01-02 03:04:05 UTC"));
+        assert_eq!(info.tracks, [
+            Track { kind: "Video", codec: "avc1".to_string(), details: "1920×1080".to_string() },
+            Track { kind: "Audio", codec: "mp4a".to_string(), details: "stereo, 48000 Hz".to_string() },
+        ]);
+        assert!(read_mp4(&mut Cursor::new(mp4_box(b"ftyp", b"isom"))).is_err());
+    }
+
+    #[test]
+    fn test_read_wav_and_flac() {
+        let mut wav = b"RIFF\0\0\0\0WAVE".to_vec();
+        wav.extend_from_slice(b"LIST\x03\0\0\0abc\0");
+        wav.extend_from_slice(b"fmt \x10\0\0\0");
+        wav.extend_from_slice(&[1, 0, 2, 0]);
+        wav.extend_from_slice(&44_100u32.to_le_bytes());
+        wav.extend_from_slice(&176_400u32.to_le_bytes());
+        wav.extend_from_slice(&[4, 0, 16, 0]);
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&352_800u32.to_le_bytes());
+        let info = read_wav(&mut Cursor::new(wav)).unwrap();
+        assert_eq!(info.duration, Some(2.0));
+        assert_eq!(info.tracks[0].details, "stereo, 44100 Hz, 16-bit");
+
+        // STREAMINFO for 44.1 kHz, 2 channels, 24-bit, 441000 samples
+        let mut flac = b"fLaC\0\0\0\x22".to_vec();
+        flac.extend_from_slice(&[0u8; 10]);
+        flac.extend_from_slice(&[0x0A, 0xC4, 0x43, 0x70]);
+        flac.extend_from_slice(&441_000u32.to_be_bytes());
+        flac.extend_from_slice(&[0u8; 16]);
+        let info = read_flac(&mut Cursor::new(flac)).unwrap();
+        assert_eq!(info.duration, Some(10.0));
+        assert_eq!(info.tracks[0].details, "stereo, 44100 Hz, 24-bit");
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(65.5), "1:06");
+        assert_eq!(format_duration(3725.4), "1:02:05");
+        assert_eq!(format_duration(0.2), "0:00");
+    }
+}
@@ -0,0 +1,433 @@
+use crate::file_size::format_bytes;
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use exif::{Exif, In, Reader, Tag, Value};
+use img_parts::jpeg::{markers, Jpeg};
+use img_parts::png::Png;
+use img_parts::webp::WebP;
+use img_parts::{Bytes, ImageEXIF};
+use infer::MatcherType;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{BufReader, Seek, SeekFrom};
+use std::path::Path;
+
+mod container;
+
+use container::{format_duration, MediaInfo};
+
+pub struct MediaMetaModule;
+
+// IPTC-IIM datasets in record 2 worth showing, in display order
+const IPTC_DATASETS: [(u8, &str); 10] = [
+    (5, "Title"),
+    (105, "Headline"),
+    (120, "Caption"),
+    (25, "Keywords"),
+    (80, "By-line"),
+    (110, "Credit"),
+    (116, "Copyright"),
+    (90, "City"),
+    (95, "State"),
+    (101, "Country"),
+];
+
+impl ToolModule for MediaMetaModule {
+    fn name(&self) -> &'static str {
+        "media-meta"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("metadata")
+                .long("metadata")
+                .value_name("FILE")
+                .help("Show EXIF/IPTC metadata of a photo (camera, GPS, timestamps) or basic audio/video info")
+                .long_help("Show the metadata a file carries. Photos (JPEG, PNG, WebP, TIFF, HEIF): camera and lens, exposure, timestamps, author and copyright from EXIF and IPTC, and GPS coordinates with a map link. Audio and video: duration, creation time and tracks for MP4/MOV/M4A, WAV and FLAC. Use --strip to write a copy without the metadata.")
+        )
+        .arg(
+            Arg::new("strip")
+                .long("strip")
+                .value_name("OUT")
+                .num_args(0..=1)
+                .default_missing_value("")
+                .help("Write a copy of the --metadata photo without EXIF, XMP, IPTC and comments (default: NAME.stripped.EXT)")
+                .long_help("Write a copy of the --metadata photo (JPEG, PNG or WebP) with EXIF (including GPS), XMP, IPTC, comments and text chunks removed, ready to share. The image data and colour profile are kept as they are, without re-encoding. The copy goes to OUT, or next to the original as NAME.stripped.EXT; existing files are never overwritten.")
+                .requires("metadata")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(path) = matches.get_one::<String>("metadata") {
+            if let Some(out) = matches.get_one::<String>("strip") {
+                return strip_file(path, out);
+            }
+            let mut file = File::open(path).map_err(|e| format!("Failed to open '{}': {}", path, e))?;
+            let kind = infer::get_from_path(path)?;
+            let size = file.metadata()?.len();
+            let description = kind.map(|kind| kind.mime_type()).unwrap_or("unknown type");
+            println!("{:<14}{} ({}, {})", "File:".cyan(), path, description, format_bytes(size));
+            match kind.map(|kind| (kind.matcher_type(), kind.extension())) {
+                Some((MatcherType::Image, _)) => {
+                    if let Ok((width, height)) = image::image_dimensions(path) {
+                        println!("{:<14}{}×{}", "Dimensions:".cyan(), width, height);
+                    }
+                    let mut reader = BufReader::new(&file);
+                    match Reader::new().read_from_container(&mut reader) {
+                        Ok(exif) => print_exif(&exif),
+                        Err(_) => println!("No EXIF data"),
+                    }
+                    if kind.is_some_and(|kind| kind.extension() == "jpg") {
+                        let jpeg = Jpeg::from_bytes(fs::read(path)?.into()).map_err(|e| format!("Failed to read '{}': {}", path, e))?;
+                        let iptc: Vec<(&str, String)> = jpeg.segments_by_marker(markers::APP13).flat_map(|segment| parse_iptc(segment.contents())).collect();
+                        if !iptc.is_empty() {
+                            println!("\n{}", "IPTC".bold());
+                            for (label, value) in iptc {
+                                println!("{:<14}{}", format!("{}:", label).cyan(), value);
+                            }
+                        }
+                    }
+                }
+                Some((MatcherType::Video | MatcherType::Audio, extension)) => {
+                    file.seek(SeekFrom::Start(0))?;
+                    let info = match extension {
+                        "mp4" | "m4v" | "mov" | "m4a" => Some(container::read_mp4(&mut file)?),
+                        "wav" => Some(container::read_wav(&mut file)?),
+                        "flac" => Some(container::read_flac(&mut file)?),
+                        _ => None,
+                    };
+                    match info {
+                        Some(info) => print_media(&info),
+                        None => println!("No further details for .{} files; MP4/MOV/M4A, WAV and FLAC are supported", extension),
+                    }
+                }
+                _ => return Err(format!("{} is not an image, audio or video file", path).into()),
+            }
+        }
+        Ok(())
+    }
+}
+
+fn print_media(info: &MediaInfo) {
+    if let Some(duration) = info.duration {
+        println!("{:<14}{}", "Duration:".cyan(), format_duration(duration));
+    }
+    if let Some(created) = &info.created {
+        println!("{:<14}{}", "Created:".cyan(), created);
+    }
+    for track in &info.tracks {
+        let details = if track.details.is_empty() { String::new() } else { format!(", {}", track.details) };
+        println!("{:<14}{}{}", format!("{}:", track.kind).cyan(), track.codec, details);
+    }
+}
+
+fn print_exif(exif: &Exif) {
+    let rows = exif_summary(exif);
+    println!("\n{}", "EXIF".bold());
+    for (label, value) in &rows {
+        println!("{:<14}{}", format!("{}:", label).cyan(), value);
+    }
+    if let Some((latitude, longitude)) = gps_position(exif) {
+        println!("{:<14}{}", "Map:".cyan(), map_link(latitude, longitude));
+        println!("⚠️  This photo records where it was taken; --strip writes a copy without it");
+    }
+    println!("{:<14}{} in total", "Fields:".cyan(), exif.fields().len());
+}
+
+// ASCII fields without the quotes display_value() adds
+fn text(exif: &Exif, tag: Tag) -> Option<String> {
+    match &exif.get_field(tag, In::PRIMARY)?.value {
+        Value::Ascii(values) => {
+            let text = String::from_utf8_lossy(values.first()?).trim().to_string();
+            (!text.is_empty()).then_some(text)
+        }
+        _ => None,
+    }
+}
+
+fn display(exif: &Exif, tag: Tag) -> Option<String> {
+    Some(exif.get_field(tag, In::PRIMARY)?.display_value().with_unit(exif).to_string())
+}
+
+fn exif_summary(exif: &Exif) -> Vec<(&'static str, String)> {
+    let mut rows = Vec::new();
+    // Model usually repeats the make ("Canon" / "Canon EOS R6")
+    let camera = match (text(exif, Tag::Make), text(exif, Tag::Model)) {
+        (Some(make), Some(model)) if model.to_lowercase().starts_with(&make.to_lowercase()) => Some(model),
+        (Some(make), Some(model)) => Some(format!("{} {}", make, model)),
+        (make, model) => make.or(model),
+    };
+    rows.extend(camera.map(|camera| ("Camera", camera)));
+    rows.extend(text(exif, Tag::LensModel).map(|lens| ("Lens", lens)));
+    let exposure: Vec<String> = [
+        display(exif, Tag::ExposureTime),
+        display(exif, Tag::FNumber),
+        display(exif, Tag::PhotographicSensitivity).map(|iso| format!("ISO {}", iso)),
+        display(exif, Tag::FocalLength),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    if !exposure.is_empty() {
+        rows.push(("Exposure", exposure.join(", ")));
+    }
+    if let Some(taken) = text(exif, Tag::DateTimeOriginal) {
+        let offset = text(exif, Tag::OffsetTimeOriginal).map(|offset| format!(" {}", offset)).unwrap_or_default();
+        rows.push(("Taken", format!("{}{}", taken, offset)));
+    }
+    rows.extend(text(exif, Tag::DateTime).map(|modified| ("Modified", modified)));
+    rows.extend(text(exif, Tag::Software).map(|software| ("Software", software)));
+    rows.extend(text(exif, Tag::Artist).map(|artist| ("Artist", artist)));
+    rows.extend(text(exif, Tag::Copyright).map(|copyright| ("Copyright", copyright)));
+    rows.extend(text(exif, Tag::ImageDescription).map(|description| ("Description", description)));
+    if let Some((latitude, longitude)) = gps_position(exif) {
+        rows.push(("GPS", format!("{:.6}, {:.6}", latitude, longitude)));
+        rows.extend(display(exif, Tag::GPSAltitude).map(|altitude| ("Altitude", altitude)));
+    }
+    rows
+}
+
+// Degrees, minutes and seconds with an N/S or E/W reference -> signed decimal degrees
+fn gps_coordinate(exif: &Exif, tag: Tag, reference: Tag) -> Option<f64> {
+    let Value::Rational(parts) = &exif.get_field(tag, In::PRIMARY)?.value else {
+        return None;
+    };
+    if parts.len() < 3 || parts.iter().any(|part| part.denom == 0) {
+        return None;
+    }
+    let degrees = parts[0].to_f64() + parts[1].to_f64() / 60.0 + parts[2].to_f64() / 3600.0;
+    let negative = text(exif, reference).is_some_and(|reference| reference == "S" || reference == "W");
+    Some(if negative { -degrees } else { degrees })
+}
+
+fn gps_position(exif: &Exif) -> Option<(f64, f64)> {
+    Some((gps_coordinate(exif, Tag::GPSLatitude, Tag::GPSLatitudeRef)?, gps_coordinate(exif, Tag::GPSLongitude, Tag::GPSLongitudeRef)?))
+}
+
+fn map_link(latitude: f64, longitude: f64) -> String {
+    format!("https://www.openstreetmap.org/?mlat={:.6}&mlon={:.6}#map=16/{:.6}/{:.6}", latitude, longitude, latitude, longitude)
+}
+
+// IPTC-IIM records inside a JPEG APP13 "Photoshop 3.0" segment (image resource 0x0404)
+fn parse_iptc(app13: &[u8]) -> Vec<(&'static str, String)> {
+    let Some(mut resources) = app13.strip_prefix(b"Photoshop 3.0\0") else {
+        return Vec::new();
+    };
+    let mut iim: &[u8] = &[];
+    // Each resource: "8BIM", id, padded Pascal name, size, data padded to an even length
+    while resources.len() >= 12 && resources.starts_with(b"8BIM") {
+        let id = u16::from_be_bytes([resources[4], resources[5]]);
+        let name_len = resources[6] as usize + 1;
+        let offset = 6 + name_len + name_len % 2;
+        let Some(size) = resources.get(offset..offset + 4).map(|size| u32::from_be_bytes(size.try_into().unwrap()) as usize) else {
+            break;
+        };
+        let Some(data) = resources.get(offset + 4..offset + 4 + size) else {
+            break;
+        };
+        if id == 0x0404 {
+            iim = data;
+            break;
+        }
+        resources = resources.get(offset + 4 + size + size % 2..).unwrap_or(&[]);
+    }
+
+    let mut values: Vec<(u8, Vec<String>)> = Vec::new();
+    while iim.len() >= 5 && iim[0] == 0x1C {
+        let (record, dataset) = (iim[1], iim[2]);
+        let size = u16::from_be_bytes([iim[3], iim[4]]) as usize;
+        // Extended datasets (high bit set) are not used for text fields
+        let Some(data) = iim.get(5..5 + size).filter(|_| size & 0x8000 == 0) else {
+            break;
+        };
+        if record == 2 {
+            let value = String::from_utf8_lossy(data).trim().to_string();
+            match values.iter_mut().find(|(known, _)| *known == dataset) {
+                Some((_, list)) => list.push(value),
+                None => values.push((dataset, vec![value])),
+            }
+        }
+        iim = &iim[5 + size..];
+    }
+    IPTC_DATASETS
+        .iter()
+        .filter_map(|(dataset, label)| values.iter().find(|(known, _)| known == dataset).map(|(_, list)| (*label, list.join(", "))))
+        .collect()
+}
+
+// Returns the stripped image and what was removed from it
+fn strip_metadata(data: Vec<u8>) -> Result<(Bytes, Vec<&'static str>), String> {
+    let mut removed: Vec<&'static str> = Vec::new();
+    let mut note = |what: &'static str| {
+        if !removed.contains(&what) {
+            removed.push(what);
+        }
+    };
+    let kind = infer::get(&data).map(|kind| kind.extension());
+    let bytes = Bytes::from(data);
+    let stripped = match kind {
+        Some("jpg") => {
+            let mut jpeg = Jpeg::from_bytes(bytes).map_err(|e| e.to_string())?;
+            // APP0 (JFIF), APP2 (ICC profile) and APP14 (Adobe colour transform) are needed to show the image correctly
+            jpeg.segments_mut().retain(|segment| {
+                let what = match segment.marker() {
+                    markers::APP1 if segment.contents().starts_with(b"Exif") => "EXIF",
+                    markers::APP1 => "XMP",
+                    markers::APP13 => "IPTC",
+                    markers::COM => "comments",
+                    markers::APP3..=markers::APP12 | markers::APP15 => "vendor data",
+                    _ => return true,
+                };
+                note(what);
+                false
+            });
+            jpeg.encoder().bytes()
+        }
+        Some("png") => {
+            let mut png = Png::from_bytes(bytes).map_err(|e| e.to_string())?;
+            png.chunks_mut().retain(|chunk| {
+                let what = match &chunk.kind() {
+                    b"eXIf" => "EXIF",
+                    // XMP is stored in an iTXt chunk
+                    b"tEXt" | b"zTXt" | b"iTXt" => "text chunks",
+                    b"tIME" => "timestamp",
+                    _ => return true,
+                };
+                note(what);
+                false
+            });
+            png.encoder().bytes()
+        }
+        Some("webp") => {
+            let mut webp = WebP::from_bytes(bytes).map_err(|e| e.to_string())?;
+            if webp.has_chunk(*b"EXIF") {
+                note("EXIF");
+            }
+            if webp.has_chunk(*b"XMP ") {
+                note("XMP");
+            }
+            webp.remove_chunks_by_id(*b"XMP ");
+            // Removes the EXIF chunk with or without an "Exif" header and rewrites the VP8X flags
+            webp.set_exif(None);
+            webp.encoder().bytes()
+        }
+        _ => return Err("Stripping metadata supports JPEG, PNG and WebP images".to_string()),
+    };
+    Ok((stripped, removed))
+}
+
+fn strip_file(path: &str, out: &str) -> Result<(), Box<dyn Error>> {
+    let data = fs::read(path).map_err(|e| format!("Failed to read '{}': {}", path, e))?;
+    let (stripped, removed) = strip_metadata(data).map_err(|e| format!("{}: {}", path, e))?;
+    if removed.is_empty() {
+        println!("✅ {} has no metadata to strip", path);
+        return Ok(());
+    }
+    let out = if out.is_empty() {
+        let source = Path::new(path);
+        let stem = source.file_stem().and_then(|stem| stem.to_str()).unwrap_or("image");
+        let name = match source.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) => format!("{}.stripped.{}", stem, extension),
+            None => format!("{}.stripped", stem),
+        };
+        source.with_file_name(name).to_string_lossy().into_owned()
+    } else {
+        out.to_string()
+    };
+    if Path::new(&out).exists() {
+        return Err(format!("{} already exists; not overwriting it", out).into());
+    }
+    fs::write(&out, &stripped).map_err(|e| format!("Failed to write {}: {}", out, e))?;
+    println!("✅ Removed {} and wrote {}", removed.join(", "), out);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exif::experimental::Writer;
+    use exif::{Field, Rational};
+    use std::io::Cursor;
+
+    fn ascii(tag: Tag, value: &str) -> Field {
+        Field { tag, ifd_num: In::PRIMARY, value: Value::Ascii(vec![value.as_bytes().to_vec()]) }
+    }
+
+    fn rationals(tag: Tag, values: &[(u32, u32)]) -> Field {
+        Field { tag, ifd_num: In::PRIMARY, value: Value::Rational(values.iter().map(|&(num, denom)| Rational { num, denom }).collect()) }
+    }
+
+    // A JPEG with EXIF (camera and GPS), a comment and a JFIF header; no image data is needed
+    fn sample_jpeg() -> Vec<u8> {
+        let fields = [
+            ascii(Tag::Make, "Apple"),
+            ascii(Tag::Model, "iPhone 13"),
+            ascii(Tag::DateTimeOriginal, "2024:05:01 14:30:00"),
+            rationals(Tag::GPSLatitude, &[(35, 1), (39, 1), (2916, 100)]),
+            ascii(Tag::GPSLatitudeRef, "N"),
+            rationals(Tag::GPSLongitude, &[(139, 1), (44, 1), (2862, 100)]),
+            ascii(Tag::GPSLongitudeRef, "W"),
+        ];
+        let mut writer = Writer::new();
+        for field in &fields {
+            writer.push_field(field);
+        }
+        let mut tiff = Cursor::new(Vec::new());
+        writer.write(&mut tiff, false).unwrap();
+        let segment = |marker: u8, contents: &[u8]| [&[0xFF, marker][..], &((contents.len() + 2) as u16).to_be_bytes(), contents].concat();
+        [
+            vec![0xFF, markers::SOI],
+            segment(markers::APP0, b"JFIF\0\x01\x01\0\0\x01\0\x01\0\0"),
+            segment(markers::APP1, &[b"Exif\0\0".as_slice(), tiff.get_ref()].concat()),
+            segment(markers::COM, b"shot on my phone"),
+            segment(markers::SOS, &[1, 1, 0, 0, 0x3F, 0]),
+            vec![0x00, 0xFF, markers::EOI],
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn test_exif_summary_and_gps() {
+        let exif = Reader::new().read_from_container(&mut Cursor::new(sample_jpeg())).unwrap();
+        let rows = exif_summary(&exif);
+        assert_eq!(rows[0], ("Camera", "Apple iPhone 13".to_string()));
+        assert_eq!(rows[1], ("Taken", "2024:05:01 14:30:00".to_string()));
+        assert_eq!(rows[2], ("GPS", "35.658100, -139.741283".to_string()));
+        let (latitude, longitude) = gps_position(&exif).unwrap();
+        assert_eq!(map_link(latitude, longitude), "https://www.openstreetmap.org/?mlat=35.658100&mlon=-139.741283#map=16/35.658100/-139.741283");
+    }
+
+    #[test]
+    fn test_strip_jpeg() {
+        let (stripped, removed) = strip_metadata(sample_jpeg()).unwrap();
+        assert_eq!(removed, ["EXIF", "comments"]);
+        assert!(Reader::new().read_from_container(&mut Cursor::new(stripped.to_vec())).is_err());
+        let jpeg = Jpeg::from_bytes(stripped.clone()).unwrap();
+        assert!(jpeg.segment_by_marker(markers::APP0).is_some());
+        assert!(jpeg.segment_by_marker(markers::COM).is_none());
+        let (_, removed_again) = strip_metadata(stripped.to_vec()).unwrap();
+        assert!(removed_again.is_empty());
+        assert!(strip_metadata(b"GIF89a\x01\0\x01\0".to_vec()).is_err());
+    }
+
+    #[test]
+    fn test_parse_iptc() {
+        let mut iim = Vec::new();
+        for (dataset, value) in [(25u8, "tokyo"), (5, "Shibuya crossing"), (25, "night"), (116, "© Jane Doe")] {
+            iim.extend_from_slice(&[0x1C, 2, dataset]);
+            iim.extend_from_slice(&(value.len() as u16).to_be_bytes());
+            iim.extend_from_slice(value.as_bytes());
+        }
+        let mut app13 = b"Photoshop 3.0\08BIM\x03\xED\0\0\0\0\0\x02ab".to_vec();
+        app13.extend_from_slice(b"8BIM\x04\x04\0\0");
+        app13.extend_from_slice(&(iim.len() as u32).to_be_bytes());
+        app13.extend_from_slice(&iim);
+        assert_eq!(parse_iptc(&app13), [
+            ("Title", "Shibuya crossing".to_string()),
+            ("Keywords", "tokyo, night".to_string()),
+            ("Copyright", "© Jane Doe".to_string()),
+        ]);
+        assert!(parse_iptc(b"not iptc").is_empty());
+    }
+}
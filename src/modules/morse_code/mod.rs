@@ -0,0 +1,218 @@
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use std::error::Error;
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+pub struct MorseCodeModule;
+
+// International Morse code (ITU-R M.1677)
+const MORSE: &[(char, &str)] = &[
+    ('A', ".-"), ('B', "-..."), ('C', "-.-."), ('D', "-.."), ('E', "."), ('F', "..-."), ('G', "--."),
+    ('H', "...."), ('I', ".."), ('J', ".---"), ('K', "-.-"), ('L', ".-.."), ('M', "--"), ('N', "-."),
+    ('O', "---"), ('P', ".--."), ('Q', "--.-"), ('R', ".-."), ('S', "..."), ('T', "-"), ('U', "..-"),
+    ('V', "...-"), ('W', ".--"), ('X', "-..-"), ('Y', "-.--"), ('Z', "--.."),
+    ('0', "-----"), ('1', ".----"), ('2', "..---"), ('3', "...--"), ('4', "....-"),
+    ('5', "....."), ('6', "-...."), ('7', "--..."), ('8', "---.."), ('9', "----."),
+    ('.', ".-.-.-"), (',', "--..--"), ('?', "..--.."), ('\'', ".----."), ('!', "-.-.--"), ('/', "-..-."),
+    ('(', "-.--."), (')', "-.--.-"), ('&', ".-..."), (':', "---..."), (';', "-.-.-."), ('=', "-...-"),
+    ('+', ".-.-."), ('-', "-....-"), ('_', "..--.-"), ('"', ".-..-."), ('$', "...-..-"), ('@', ".--.-."),
+];
+const DEFAULT_LETTER_SEPARATOR: &str = " ";
+const DEFAULT_WORD_SEPARATOR: &str = " / ";
+// Length of a dot when playing; dashes and gaps are multiples of it (about 12 WPM)
+const UNIT: Duration = Duration::from_millis(100);
+
+impl ToolModule for MorseCodeModule {
+    fn name(&self) -> &'static str {
+        "morse-code"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("morse-encode")
+                .long("morse-encode")
+                .value_name("TEXT")
+                .help("Encode text as Morse code")
+                .long_help("Encode text as international Morse code: letters, digits and common punctuation. Letters are separated by spaces and words by \" / \"; change them with --letter-sep and --word-sep.")
+        )
+        .arg(
+            Arg::new("morse-decode")
+                .long("morse-decode")
+                .value_name("CODE")
+                .help("Decode Morse code, e.g. \"... --- ...\"")
+                .long_help("Decode Morse code into text. Dots may be written as . or ·, dashes as -, _ or −. Letters are split on --letter-sep (default: whitespace) and words on --word-sep (default: /).")
+        )
+        .arg(
+            Arg::new("letter-sep")
+                .long("letter-sep")
+                .value_name("SEP")
+                .help("Separator between Morse letters (default: space)")
+        )
+        .arg(
+            Arg::new("word-sep")
+                .long("word-sep")
+                .value_name("SEP")
+                .help("Separator between Morse words (default: \" / \")")
+        )
+        .arg(
+            Arg::new("play")
+                .long("play")
+                .help("Beep the Morse code through the terminal bell after printing it")
+                .long_help("Beep the encoded or decoded Morse code through the terminal bell, printing each symbol as it sounds. A bell cannot be held, so dashes are told apart from dots by the pause after them.")
+                .action(ArgAction::SetTrue)
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let letter_separator = matches.get_one::<String>("letter-sep").map(String::as_str).unwrap_or(DEFAULT_LETTER_SEPARATOR);
+        let word_separator = matches.get_one::<String>("word-sep").map(String::as_str).unwrap_or(DEFAULT_WORD_SEPARATOR);
+        let code = if let Some(text) = matches.get_one::<String>("morse-encode") {
+            let code = encode(text, letter_separator, word_separator)?;
+            println!("{}", code);
+            code
+        } else if let Some(code) = matches.get_one::<String>("morse-decode") {
+            println!("{}", decode(code, letter_separator, word_separator)?);
+            code.clone()
+        } else {
+            return Ok(());
+        };
+        if matches.get_flag("play") {
+            play(&split_code(&code, letter_separator, word_separator), letter_separator, word_separator)?;
+        }
+        Ok(())
+    }
+}
+
+fn encode(text: &str, letter_separator: &str, word_separator: &str) -> Result<String, String> {
+    let words = text
+        .split_whitespace()
+        .map(|word| {
+            word.chars()
+                .map(|c| {
+                    let c = c.to_ascii_uppercase();
+                    MORSE.iter().find(|(known, _)| *known == c).map(|(_, code)| *code).ok_or_else(|| format!("{:?} has no Morse code", c))
+                })
+                .collect::<Result<Vec<&str>, String>>()
+                .map(|letters| letters.join(letter_separator))
+        })
+        .collect::<Result<Vec<String>, String>>()?;
+    if words.is_empty() {
+        return Err("Nothing to encode".to_string());
+    }
+    Ok(words.join(word_separator))
+}
+
+// Dots and dashes as typed or pasted from elsewhere
+fn normalize(code: &str) -> String {
+    code.chars()
+        .map(|c| match c {
+            '·' | '•' | '∙' => '.',
+            '_' | '−' | '–' | '—' => '-',
+            c => c,
+        })
+        .collect()
+}
+
+// Letter codes grouped into words. Separators are matched without their
+// surrounding spaces, so "... / ---" and ".../---" both work.
+fn split_code(code: &str, letter_separator: &str, word_separator: &str) -> Vec<Vec<String>> {
+    let split = |text: &str, separator: &str| -> Vec<String> {
+        match separator.trim() {
+            "" => text.split_whitespace().map(str::to_string).collect(),
+            separator => text.split(separator).map(|part| part.trim().to_string()).filter(|part| !part.is_empty()).collect(),
+        }
+    };
+    split(&normalize(code), word_separator).iter().map(|word| split(word, letter_separator)).collect()
+}
+
+fn decode(code: &str, letter_separator: &str, word_separator: &str) -> Result<String, String> {
+    let mut words = Vec::new();
+    for word in split_code(code, letter_separator, word_separator) {
+        let mut decoded = String::new();
+        for letter in word {
+            let c = MORSE.iter().find(|(_, known)| *known == letter).map(|(c, _)| *c).ok_or_else(|| format!("Unknown Morse code '{}'", letter))?;
+            decoded.push(c);
+        }
+        words.push(decoded);
+    }
+    if words.is_empty() {
+        return Err("Nothing to decode".to_string());
+    }
+    Ok(words.join(" "))
+}
+
+// What to print and how many units to wait after it. A bell cannot be held,
+// so a dot waits 2 units (itself plus the gap) and a dash 4; the gaps after
+// letters and words add up to the standard 3 and 7 units.
+fn schedule<'a>(words: &[Vec<String>], letter_separator: &'a str, word_separator: &'a str) -> Vec<(&'a str, u32)> {
+    let mut steps = Vec::new();
+    for (word_index, word) in words.iter().enumerate() {
+        if word_index > 0 {
+            steps.push((word_separator, 6));
+        }
+        for (letter_index, letter) in word.iter().enumerate() {
+            if letter_index > 0 {
+                steps.push((letter_separator, 2));
+            }
+            for element in letter.chars() {
+                steps.push(if element == '-' { ("-", 4) } else { (".", 2) });
+            }
+        }
+    }
+    steps
+}
+
+fn play(words: &[Vec<String>], letter_separator: &str, word_separator: &str) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    for (symbol, units) in schedule(words, letter_separator, word_separator) {
+        if symbol == "." || symbol == "-" {
+            write!(stdout, "\x07")?;
+        }
+        write!(stdout, "{}", symbol)?;
+        stdout.flush()?;
+        thread::sleep(UNIT * units);
+    }
+    writeln!(stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode("SOS", " ", " / ").unwrap(), "... --- ...");
+        assert_eq!(encode("hello  world!", " ", " / ").unwrap(), ".... . .-.. .-.. --- / .-- --- .-. .-.. -.. -.-.--");
+        assert_eq!(encode("ab c", "|", "   ").unwrap(), ".-|-...   -.-.");
+        assert!(encode("naïve", " ", " / ").unwrap_err().contains("'ï'"));
+        assert!(encode("  ", " ", " / ").is_err());
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode("... --- ...", " ", " / ").unwrap(), "SOS");
+        assert_eq!(decode(".... .. / - .... . .-. .", " ", " / ").unwrap(), "HI THERE");
+        assert_eq!(decode("....|..//-", "|", "//").unwrap(), "HI T");
+        // Pasted dots and dashes
+        assert_eq!(decode("··· ——— ···", " ", " / ").unwrap(), "SOS");
+        assert_eq!(decode("..--.-", " ", " / ").unwrap(), "_");
+        assert!(decode("........", " ", " / ").unwrap_err().contains("........"));
+        assert!(decode(" / ", " ", " / ").is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let text = "THE QUICK BROWN FOX JUMPS OVER THE LAZY DOG 0123456789 .,?'!/()&:;=+-_\"$@";
+        let code = encode(text, " ", " / ").unwrap();
+        assert_eq!(decode(&code, " ", " / ").unwrap(), text);
+    }
+
+    #[test]
+    fn test_schedule_timing() {
+        let words = split_code(". - / .", " ", " / ");
+        assert_eq!(schedule(&words, " ", " / "), vec![(".", 2), (" ", 2), ("-", 4), (" / ", 6), (".", 2)]);
+        assert_eq!(schedule(&split_code("..|-", "|", "/"), "|", "/")[1..], [(".", 2), ("|", 2), ("-", 4)]);
+    }
+}
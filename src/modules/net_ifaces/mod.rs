@@ -0,0 +1,302 @@
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use colored::*;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::{self, IsTerminal};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use sysinfo::Networks;
+
+pub struct NetIfacesModule;
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+struct Interface {
+    name: String,
+    // None where the platform doesn't report link state
+    up: Option<bool>,
+    mtu: u64,
+    mac: Option<String>,
+    addresses: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+struct Route {
+    gateway: IpAddr,
+    interface: String,
+}
+
+impl ToolModule for NetIfacesModule {
+    fn name(&self) -> &'static str {
+        "net-ifaces"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("ifaces")
+                .long("ifaces")
+                .action(ArgAction::SetTrue)
+                .help("List network interfaces with their IPv4/IPv6 addresses, MAC, MTU and up/down state")
+        )
+        .arg(
+            Arg::new("default-route")
+                .long("default-route")
+                .action(ArgAction::SetTrue)
+                .help("Show the default IPv4 and IPv6 gateways and the interfaces and addresses they go out through")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let show_ifaces = matches.get_flag("ifaces");
+        let show_route = matches.get_flag("default-route");
+        if !show_ifaces && !show_route {
+            return Ok(());
+        }
+        // --format is declared by the text-diff module
+        let json = match matches.get_one::<String>("format").map(String::as_str) {
+            None | Some("text") => false,
+            Some("json") => true,
+            Some(other) => return Err(format!("--format {} does not apply to --ifaces or --default-route; use text or json", other).into()),
+        };
+        let interfaces = interfaces();
+        let color = io::stdout().is_terminal();
+        if show_ifaces {
+            if json {
+                println!("{}", serde_json::to_string_pretty(&interfaces)?);
+            } else {
+                print!("{}", render_interfaces(&interfaces, color));
+            }
+        }
+        if show_route {
+            let routes = default_routes()?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&routes)?);
+            } else {
+                if show_ifaces {
+                    println!();
+                }
+                print!("{}", render_routes(&routes, &interfaces, color));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn interfaces() -> Vec<Interface> {
+    let networks = Networks::new_with_refreshed_list();
+    let states = link_states();
+    let mut interfaces: Vec<Interface> = networks
+        .list()
+        .iter()
+        .map(|(name, data)| {
+            let mut addresses: Vec<(IpAddr, u8)> = data.ip_networks().iter().map(|network| (network.addr, network.prefix)).collect();
+            // IPv4 first, then IPv6
+            addresses.sort_by_key(|(address, prefix)| (address.is_ipv6(), *address, *prefix));
+            Interface {
+                name: name.clone(),
+                up: states.get(name).copied(),
+                mtu: data.mtu(),
+                mac: Some(data.mac_address()).filter(|mac| !mac.is_unspecified()).map(|mac| mac.to_string()),
+                addresses: addresses.iter().map(|(address, prefix)| format!("{}/{}", address, prefix)).collect(),
+            }
+        })
+        .collect();
+    interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+    interfaces
+}
+
+// Whether each interface is up and has a link, from getifaddrs flags
+#[cfg(unix)]
+fn link_states() -> HashMap<String, bool> {
+    let mut states = HashMap::new();
+    let mut addresses: *mut libc::ifaddrs = std::ptr::null_mut();
+    if unsafe { libc::getifaddrs(&mut addresses) } != 0 {
+        return states;
+    }
+    let mut current = addresses;
+    while !current.is_null() {
+        let entry = unsafe { &*current };
+        let name = unsafe { std::ffi::CStr::from_ptr(entry.ifa_name) }.to_string_lossy().to_string();
+        let flags = entry.ifa_flags as libc::c_int;
+        let up = flags & libc::IFF_UP != 0 && flags & libc::IFF_RUNNING != 0;
+        states.insert(name, up);
+        current = entry.ifa_next;
+    }
+    unsafe { libc::freeifaddrs(addresses) };
+    states
+}
+
+#[cfg(not(unix))]
+fn link_states() -> HashMap<String, bool> {
+    HashMap::new()
+}
+
+fn render_interfaces(interfaces: &[Interface], color: bool) -> String {
+    let width = interfaces.iter().map(|interface| interface.name.chars().count()).max().unwrap_or(0);
+    let mut output = String::new();
+    for interface in interfaces {
+        let state = match interface.up {
+            Some(true) if color => "up".green().bold().to_string(),
+            Some(true) => "up".to_string(),
+            Some(false) if color => "down".red().to_string(),
+            Some(false) => "down".to_string(),
+            None => "?".to_string(),
+        };
+        let name = format!("{:<width$}", interface.name, width = width);
+        let mut line = format!("{}  {:<4}  mtu {}", if color { name.bold().to_string() } else { name }, state, interface.mtu);
+        if let Some(mac) = &interface.mac {
+            line.push_str(&format!("  mac {}", mac));
+        }
+        output.push_str(&line);
+        output.push('\n');
+        for address in &interface.addresses {
+            let family = if address.contains(':') { "inet6" } else { "inet" };
+            output.push_str(&format!("{:width$}  {:<5} {}\n", "", family, if color { address.cyan().to_string() } else { address.clone() }, width = width));
+        }
+    }
+    output
+}
+
+fn render_routes(routes: &[Route], interfaces: &[Interface], color: bool) -> String {
+    if routes.is_empty() {
+        return "No default route; this machine has no way off the local network\n".to_string();
+    }
+    let mut output = String::new();
+    for route in routes {
+        let family = if route.gateway.is_ipv4() { "IPv4" } else { "IPv6" };
+        let gateway = if color { route.gateway.to_string().green().bold().to_string() } else { route.gateway.to_string() };
+        output.push_str(&format!("{} default via {} dev {}\n", family, gateway, route.interface));
+        // The addresses the traffic leaves from, same family only
+        let sources: Vec<&String> = interfaces
+            .iter()
+            .filter(|interface| interface.name == route.interface)
+            .flat_map(|interface| &interface.addresses)
+            .filter(|address| address.contains(':') == route.gateway.is_ipv6())
+            .collect();
+        if !sources.is_empty() {
+            output.push_str(&format!("     from {}\n", sources.iter().map(|address| address.as_str()).collect::<Vec<_>>().join(", ")));
+        }
+    }
+    output
+}
+
+#[cfg(target_os = "linux")]
+fn default_routes() -> Result<Vec<Route>, Box<dyn Error>> {
+    let ipv4 = std::fs::read_to_string("/proc/net/route").map_err(|e| format!("Failed to read /proc/net/route: {}", e))?;
+    // IPv6 may be disabled
+    let ipv6 = std::fs::read_to_string("/proc/net/ipv6_route").unwrap_or_default();
+    Ok(parse_proc_routes(&ipv4, &ipv6))
+}
+
+// /proc/net/route has little-endian IPv4 hex; /proc/net/ipv6_route plain hex
+#[cfg(any(target_os = "linux", test))]
+fn parse_proc_routes(ipv4: &str, ipv6: &str) -> Vec<Route> {
+    let mut routes = Vec::new();
+    for line in ipv4.lines().skip(1) {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if let [interface, "00000000", gateway, _, _, _, _, "00000000", ..] = fields.as_slice() {
+            if let Ok(gateway) = u32::from_str_radix(gateway, 16) {
+                if gateway != 0 {
+                    routes.push(Route { gateway: IpAddr::V4(Ipv4Addr::from(gateway.swap_bytes())), interface: interface.to_string() });
+                }
+            }
+        }
+    }
+    for line in ipv6.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if let [destination, "00", _, _, gateway, _, _, _, _, interface] = fields.as_slice() {
+            let gateway = u128::from_str_radix(gateway, 16).unwrap_or(0);
+            if destination.bytes().all(|b| b == b'0') && gateway != 0 && *interface != "lo" {
+                routes.push(Route { gateway: IpAddr::V6(Ipv6Addr::from(gateway)), interface: interface.to_string() });
+            }
+        }
+    }
+    routes.dedup();
+    routes
+}
+
+#[cfg(target_os = "macos")]
+fn default_routes() -> Result<Vec<Route>, Box<dyn Error>> {
+    let mut routes = Vec::new();
+    for family in ["-inet", "-inet6"] {
+        let output = std::process::Command::new("route").args(["-n", "get", family, "default"]).output().map_err(|e| format!("Failed to run route: {}", e))?;
+        routes.extend(parse_route_get(&String::from_utf8_lossy(&output.stdout)));
+    }
+    Ok(routes)
+}
+
+// "gateway: 192.168.1.1" and "interface: en0" lines of route -n get default
+#[cfg(any(target_os = "macos", test))]
+fn parse_route_get(output: &str) -> Option<Route> {
+    let field = |name: &str| output.lines().find_map(|line| line.trim().strip_prefix(name)?.strip_prefix(':').map(|value| value.trim().to_string()));
+    // Link-local IPv6 gateways carry a %scope suffix
+    let gateway = field("gateway")?.split('%').next()?.parse().ok()?;
+    Some(Route { gateway, interface: field("interface")? })
+}
+
+#[cfg(windows)]
+fn default_routes() -> Result<Vec<Route>, Box<dyn Error>> {
+    let output = std::process::Command::new("route").args(["print", "-4", "0.0.0.0"]).output().map_err(|e| format!("Failed to run route: {}", e))?;
+    let interfaces = interfaces();
+    Ok(parse_route_print(&String::from_utf8_lossy(&output.stdout))
+        .into_iter()
+        .map(|(gateway, source)| {
+            // Windows names the interface by its address; map it back to a name
+            let interface = interfaces.iter().find(|interface| interface.addresses.iter().any(|address| address.split('/').next() == Some(source.as_str()))).map_or(source, |interface| interface.name.clone());
+            Route { gateway, interface }
+        })
+        .collect())
+}
+
+// "0.0.0.0  0.0.0.0  192.168.1.1  192.168.1.20  25" rows: gateway and interface address
+#[cfg(any(windows, test))]
+fn parse_route_print(output: &str) -> Vec<(IpAddr, String)> {
+    output
+        .lines()
+        .filter_map(|line| match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+            ["0.0.0.0", "0.0.0.0", gateway, source, _] => Some((gateway.parse().ok()?, source.to_string())),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_routes() {
+        let ipv4 = "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT\neth0\t00000000\t010200C0\t0003\t0\t0\t0\t00000000\t0\t0\t0\neth0\t000200C0\t00000000\t0001\t0\t0\t0\t00FFFFFF\t0\t0\t0\n";
+        let ipv6 = "fe800000000000000000000000000000 40 00000000000000000000000000000000 00 00000000000000000000000000000000 00000100 00000002 00000000 00000001     eth0\n00000000000000000000000000000000 00 00000000000000000000000000000000 00 fd000000000000000000000000000001 00000400 00000001 00000000 00000003     eth0\n00000000000000000000000000000000 00 00000000000000000000000000000000 00 00000000000000000000000000000000 ffffffff 00000001 00000000 00200200       lo\n";
+        let routes = parse_proc_routes(ipv4, ipv6);
+        assert_eq!(routes, [Route { gateway: "192.0.2.1".parse().unwrap(), interface: "eth0".to_string() }, Route { gateway: "fd00::1".parse().unwrap(), interface: "eth0".to_string() }]);
+
+        let mac = "   route to: default\ndestination: default\n       mask: default\n    gateway: 192.168.1.1\n  interface: en0\n      flags: <UP,GATEWAY,DONE,STATIC,PRCLONING>\n";
+        assert_eq!(parse_route_get(mac), Some(Route { gateway: "192.168.1.1".parse().unwrap(), interface: "en0".to_string() }));
+        assert_eq!(parse_route_get("route: writing to routing socket: not in table\n"), None);
+
+        let windows = "IPv4 Route Table\n===\nActive Routes:\nNetwork Destination        Netmask          Gateway       Interface  Metric\n          0.0.0.0          0.0.0.0      192.168.1.1    192.168.1.20     25\n===\n";
+        assert_eq!(parse_route_print(windows), [("192.168.1.1".parse().unwrap(), "192.168.1.20".to_string())]);
+    }
+
+    #[test]
+    fn test_render() {
+        let interfaces = [
+            Interface { name: "eth0".to_string(), up: Some(true), mtu: 1500, mac: Some("02:42:ac:11:00:02".to_string()), addresses: vec!["192.0.2.5/24".to_string(), "fd00::2/64".to_string()] },
+            Interface { name: "lo".to_string(), up: Some(true), mtu: 65536, mac: None, addresses: vec!["127.0.0.1/8".to_string()] },
+        ];
+        assert_eq!(
+            render_interfaces(&interfaces, false),
+            "eth0  up    mtu 1500  mac 02:42:ac:11:00:02\n      inet  192.0.2.5/24\n      inet6 fd00::2/64\nlo    up    mtu 65536\n      inet  127.0.0.1/8\n"
+        );
+        let routes = [Route { gateway: "192.0.2.1".parse().unwrap(), interface: "eth0".to_string() }];
+        assert_eq!(render_routes(&routes, &interfaces, false), "IPv4 default via 192.0.2.1 dev eth0\n     from 192.0.2.5/24\n");
+        assert!(render_routes(&[], &interfaces, false).starts_with("No default route"));
+    }
+
+    #[test]
+    fn test_interfaces() {
+        // Every machine running the tests has a loopback interface
+        assert!(interfaces().iter().any(|interface| interface.addresses.iter().any(|address| address.starts_with("127.") || address.starts_with("::1"))));
+    }
+}
@@ -0,0 +1,213 @@
+use crate::tool_module::{format_number, ToolModule};
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use regex::Regex;
+use serde_json::json;
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, IsTerminal};
+
+pub struct NumStatsModule;
+
+const PERCENTILES: [f64; 3] = [50.0, 90.0, 99.0];
+
+#[derive(Debug, Clone, PartialEq)]
+struct Summary {
+    count: usize,
+    sum: f64,
+    min: f64,
+    max: f64,
+    mean: f64,
+    median: f64,
+    stddev: f64,
+    percentiles: Vec<(f64, f64)>,
+}
+
+impl ToolModule for NumStatsModule {
+    fn name(&self) -> &'static str {
+        "num-stats"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("num-stats")
+                .long("num-stats")
+                .value_name("FILE")
+                .num_args(0..=1)
+                .default_missing_value("")
+                .help("Summarize the numbers in FILE or stdin, one per line: count, sum, min, max, mean, median, stddev and percentiles")
+        )
+        .arg(
+            Arg::new("extract")
+                .long("extract")
+                .value_name("REGEX")
+                .help("Take each line's value from REGEX's first capture group (or whole match) for --num-stats or --histogram, e.g. 'took (\\d+)ms'")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let Some(source) = matches.get_one::<String>("num-stats") else {
+            return Ok(());
+        };
+        // --format is declared by the text-diff module
+        let json = match matches.get_one::<String>("format").map(String::as_str) {
+            None | Some("text") => false,
+            Some("json") => true,
+            Some(other) => return Err(format!("--format {} does not apply to --num-stats; use text or json", other).into()),
+        };
+        let extract = matches.get_one::<String>("extract").map(|pattern| Regex::new(pattern).map_err(|e| format!("Invalid --extract regex: {}", e))).transpose()?;
+        let reader: Box<dyn BufRead> = if source.is_empty() || source == "-" {
+            Box::new(BufReader::new(io::stdin()))
+        } else {
+            Box::new(BufReader::new(File::open(source).map_err(|e| format!("Failed to open '{}': {}", source, e))?))
+        };
+        let (mut numbers, skipped) = read_numbers(reader, extract.as_ref())?;
+        if skipped > 0 {
+            eprintln!("⚠️ Skipped {} line{} without a number", skipped, if skipped == 1 { "" } else { "s" });
+        }
+        let summary = summarize(&mut numbers).ok_or("No numbers found in the input")?;
+        if json {
+            println!("{}", serde_json::to_string_pretty(&summary_json(&summary))?);
+        } else {
+            print!("{}", render(&summary, io::stdout().is_terminal()));
+        }
+        Ok(())
+    }
+}
+
+// One number per line, or the first --extract match; blank lines are ignored and others counted as skipped
+fn read_numbers(reader: impl BufRead, extract: Option<&Regex>) -> Result<(Vec<f64>, usize), Box<dyn Error>> {
+    let mut numbers = Vec::new();
+    let mut skipped = 0;
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match parse_line(&line, extract) {
+            Some(number) => numbers.push(number),
+            None => skipped += 1,
+        }
+    }
+    Ok((numbers, skipped))
+}
+
+fn parse_line(line: &str, extract: Option<&Regex>) -> Option<f64> {
+    extract_value(line, extract)?.parse::<f64>().ok().filter(|number| number.is_finite())
+}
+
+// The whole trimmed line, or the first capture group (else the whole match) of an --extract regex
+pub fn extract_value<'a>(line: &'a str, extract: Option<&Regex>) -> Option<&'a str> {
+    let text = match extract {
+        Some(regex) => {
+            let captures = regex.captures(line)?;
+            captures.get(1).or_else(|| captures.get(0))?.as_str()
+        }
+        None => line,
+    };
+    Some(text.trim())
+}
+
+fn summarize(numbers: &mut [f64]) -> Option<Summary> {
+    if numbers.is_empty() {
+        return None;
+    }
+    numbers.sort_by(f64::total_cmp);
+    let count = numbers.len();
+    let sum: f64 = numbers.iter().sum();
+    let mean = sum / count as f64;
+    // Sample standard deviation; a single value has none to speak of
+    let variance = if count > 1 { numbers.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / (count - 1) as f64 } else { 0.0 };
+    Some(Summary {
+        count,
+        sum,
+        min: numbers[0],
+        max: numbers[count - 1],
+        mean,
+        median: percentile(numbers, 50.0),
+        stddev: variance.sqrt(),
+        percentiles: PERCENTILES.iter().map(|p| (*p, percentile(numbers, *p))).collect(),
+    })
+}
+
+// Linear interpolation between the closest ranks of sorted values
+pub fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+fn rows(summary: &Summary) -> Vec<(String, f64)> {
+    let mut rows = vec![
+        ("count".to_string(), summary.count as f64),
+        ("sum".to_string(), summary.sum),
+        ("min".to_string(), summary.min),
+        ("max".to_string(), summary.max),
+        ("mean".to_string(), summary.mean),
+        ("median".to_string(), summary.median),
+        ("stddev".to_string(), summary.stddev),
+    ];
+    rows.extend(summary.percentiles.iter().map(|(p, value)| (format!("p{}", p), *value)));
+    rows
+}
+
+fn render(summary: &Summary, color: bool) -> String {
+    let rows: Vec<(String, String)> = rows(summary).into_iter().map(|(name, value)| (name, format_number(value, 4))).collect();
+    let width = rows.iter().map(|(_, value)| value.len()).max().unwrap_or(0);
+    rows.iter()
+        .map(|(name, value)| {
+            let name = format!("{:<7}", name);
+            format!("{} {:>width$}\n", if color { name.cyan().to_string() } else { name }, value, width = width)
+        })
+        .collect()
+}
+
+fn summary_json(summary: &Summary) -> serde_json::Value {
+    let mut value = json!({});
+    for (name, number) in rows(summary) {
+        value[name] = if name == "count" { json!(summary.count) } else { json!(number) };
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_numbers() {
+        let input = "12\n 7.5 \n\nn/a\n-3\n";
+        assert_eq!(read_numbers(input.as_bytes(), None).unwrap(), (vec![12.0, 7.5, -3.0], 1));
+        let log = "GET / took 120ms\nGET /a took 80ms\nstarting up\n";
+        let regex = Regex::new(r"took (\d+)ms").unwrap();
+        assert_eq!(read_numbers(log.as_bytes(), Some(&regex)).unwrap(), (vec![120.0, 80.0], 1));
+        let whole = Regex::new(r"\d+\.\d+").unwrap();
+        assert_eq!(parse_line("latency=0.25s", Some(&whole)), Some(0.25));
+    }
+
+    #[test]
+    fn test_summarize() {
+        let mut numbers: Vec<f64> = (1..=100).rev().map(f64::from).collect();
+        let summary = summarize(&mut numbers).unwrap();
+        assert_eq!((summary.count, summary.sum, summary.min, summary.max), (100, 5050.0, 1.0, 100.0));
+        assert_eq!(summary.mean, 50.5);
+        assert_eq!(summary.median, 50.5);
+        let percentiles: Vec<String> = summary.percentiles.iter().map(|(p, value)| format!("p{}={}", p, format_number(*value, 4))).collect();
+        assert_eq!(percentiles, ["p50=50.5", "p90=90.1", "p99=99.01"]);
+        assert_eq!(format_number(summary.stddev, 4), "29.0115");
+        assert_eq!(summarize(&mut [4.0]).unwrap().stddev, 0.0);
+        assert!(summarize(&mut []).is_none());
+    }
+
+    #[test]
+    fn test_render() {
+        let summary = summarize(&mut [1.0, 2.0, 4.0]).unwrap();
+        let text = render(&summary, false);
+        assert!(text.starts_with("count        3\nsum          7\n"));
+        assert!(text.contains("mean    2.3333\n"));
+        assert!(text.ends_with("p99       3.96\n"));
+        let json = summary_json(&summary);
+        assert_eq!(json["count"], 3);
+        assert!((json["p90"].as_f64().unwrap() - 3.6).abs() < 1e-9);
+    }
+}
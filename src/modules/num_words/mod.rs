@@ -0,0 +1,607 @@
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgMatches, Command};
+use std::error::Error;
+
+pub struct NumWordsModule;
+
+const LANGS: [&str; 5] = ["en", "de", "es", "fr", "tr"];
+const MAX: u64 = 999_999_999_999_999;
+
+const EN_ONES: [&str; 20] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen",
+    "seventeen", "eighteen", "nineteen",
+];
+const EN_TENS: [&str; 10] = ["", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"];
+const EN_SCALES: [&str; 5] = ["", "thousand", "million", "billion", "trillion"];
+
+const DE_ONES: [&str; 20] = [
+    "null", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun", "zehn", "elf", "zwölf", "dreizehn", "vierzehn", "fünfzehn", "sechzehn",
+    "siebzehn", "achtzehn", "neunzehn",
+];
+const DE_TENS: [&str; 10] = ["", "", "zwanzig", "dreißig", "vierzig", "fünfzig", "sechzig", "siebzig", "achtzig", "neunzig"];
+// Long scale: Milliarde is 10^9, Billion 10^12
+const DE_SCALES: [(&str, &str); 3] = [("Million", "Millionen"), ("Milliarde", "Milliarden"), ("Billion", "Billionen")];
+
+const ES_ONES: [&str; 30] = [
+    "cero", "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve", "diez", "once", "doce", "trece", "catorce", "quince", "dieciséis",
+    "diecisiete", "dieciocho", "diecinueve", "veinte", "veintiuno", "veintidós", "veintitrés", "veinticuatro", "veinticinco", "veintiséis", "veintisiete",
+    "veintiocho", "veintinueve",
+];
+const ES_TENS: [&str; 10] = ["", "", "", "treinta", "cuarenta", "cincuenta", "sesenta", "setenta", "ochenta", "noventa"];
+const ES_HUNDREDS: [&str; 10] = [
+    "", "ciento", "doscientos", "trescientos", "cuatrocientos", "quinientos", "seiscientos", "setecientos", "ochocientos", "novecientos",
+];
+
+const FR_ONES: [&str; 17] = ["zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf", "dix", "onze", "douze", "treize", "quatorze", "quinze", "seize"];
+const FR_TENS: [&str; 7] = ["", "dix", "vingt", "trente", "quarante", "cinquante", "soixante"];
+const FR_SCALES: [&str; 3] = ["million", "milliard", "billion"];
+
+const TR_ONES: [&str; 10] = ["sıfır", "bir", "iki", "üç", "dört", "beş", "altı", "yedi", "sekiz", "dokuz"];
+const TR_TENS: [&str; 10] = ["", "on", "yirmi", "otuz", "kırk", "elli", "altmış", "yetmiş", "seksen", "doksan"];
+const TR_SCALES: [&str; 5] = ["", "bin", "milyon", "milyar", "trilyon"];
+
+// Per language in LANGS order: unit, units, subunit, subunits, whether the unit is feminine
+type Names = (&'static str, &'static str, &'static str, &'static str, bool);
+
+struct Currency {
+    code: &'static str,
+    decimals: u32,
+    names: [Names; 5],
+}
+
+const CURRENCIES: &[Currency] = &[
+    Currency {
+        code: "USD",
+        decimals: 2,
+        names: [
+            ("dollar", "dollars", "cent", "cents", false),
+            ("Dollar", "Dollar", "Cent", "Cent", false),
+            ("dólar", "dólares", "centavo", "centavos", false),
+            ("dollar", "dollars", "cent", "cents", false),
+            ("dolar", "dolar", "sent", "sent", false),
+        ],
+    },
+    Currency {
+        code: "EUR",
+        decimals: 2,
+        names: [
+            ("euro", "euros", "cent", "cents", false),
+            ("Euro", "Euro", "Cent", "Cent", false),
+            ("euro", "euros", "céntimo", "céntimos", false),
+            ("euro", "euros", "centime", "centimes", false),
+            ("avro", "avro", "sent", "sent", false),
+        ],
+    },
+    Currency {
+        code: "GBP",
+        decimals: 2,
+        names: [
+            ("pound", "pounds", "penny", "pence", false),
+            ("Pfund", "Pfund", "Penny", "Pence", false),
+            ("libra", "libras", "penique", "peniques", true),
+            ("livre", "livres", "penny", "pence", true),
+            ("sterlin", "sterlin", "peni", "peni", false),
+        ],
+    },
+    Currency {
+        code: "CHF",
+        decimals: 2,
+        names: [
+            ("franc", "francs", "centime", "centimes", false),
+            ("Franken", "Franken", "Rappen", "Rappen", false),
+            ("franco", "francos", "céntimo", "céntimos", false),
+            ("franc", "francs", "centime", "centimes", false),
+            ("frank", "frank", "santim", "santim", false),
+        ],
+    },
+    Currency {
+        code: "JPY",
+        decimals: 0,
+        names: [("yen", "yen", "", "", false), ("Yen", "Yen", "", "", false), ("yen", "yenes", "", "", false), ("yen", "yens", "", "", false), ("yen", "yen", "", "", false)],
+    },
+    Currency {
+        code: "TRY",
+        decimals: 2,
+        names: [
+            ("lira", "lira", "kuruş", "kuruş", false),
+            ("Lira", "Lira", "Kuruş", "Kuruş", true),
+            ("lira", "liras", "kurus", "kurus", true),
+            ("livre turque", "livres turques", "kuruş", "kuruş", true),
+            ("lira", "lira", "kuruş", "kuruş", false),
+        ],
+    },
+];
+
+const ROMAN: [(u32, &str); 13] = [(1000, "M"), (900, "CM"), (500, "D"), (400, "CD"), (100, "C"), (90, "XC"), (50, "L"), (40, "XL"), (10, "X"), (9, "IX"), (5, "V"), (4, "IV"), (1, "I")];
+
+impl ToolModule for NumWordsModule {
+    fn name(&self) -> &'static str {
+        "num-words"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("num-to-words")
+                .long("num-to-words")
+                .value_name("NUMBER")
+                .allow_hyphen_values(true)
+                .help("Spell out a number in words, e.g. 1234 -> one thousand two hundred thirty-four")
+                .long_help("Spell out a whole or decimal number (up to 999 999 999 999 999; commas and underscores between digits are ignored) in English, German, Spanish, French or Turkish with --lang. Digits after the decimal point are read one by one, as in \"twelve point zero five\". With --currency the amount is written the way it goes on a check or invoice: \"one thousand two hundred thirty-four dollars and fifty-six cents\".")
+        )
+        .arg(
+            Arg::new("lang")
+                .long("lang")
+                .value_name("LANG")
+                .help("Language for --num-to-words")
+                .value_parser(LANGS)
+                .default_value("en")
+        )
+        .arg(
+            Arg::new("currency")
+                .long("currency")
+                .value_name("CODE")
+                .help("Write --num-to-words as an amount of money: USD, EUR, GBP, CHF, JPY or TRY")
+                .requires("num-to-words")
+        )
+        .arg(
+            Arg::new("roman")
+                .long("roman")
+                .value_name("NUMBER|NUMERAL")
+                .help("Convert a number to Roman numerals or back, e.g. 2024 <-> MMXXIV")
+                .long_help("Convert a number from 1 to 3999 to Roman numerals, or a Roman numeral (any case) back to a number. Numerals that are not written the standard way, such as IIII or IC, are rejected with the correct spelling.")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(number) = matches.get_one::<String>("num-to-words") {
+            let lang = matches.get_one::<String>("lang").map(String::as_str).unwrap_or("en");
+            let words = match matches.get_one::<String>("currency") {
+                Some(code) => {
+                    let code = code.to_uppercase();
+                    let currency = CURRENCIES.iter().find(|currency| currency.code == code).ok_or_else(|| {
+                        format!("Unknown currency '{}'. Use one of: {}", code, CURRENCIES.iter().map(|currency| currency.code).collect::<Vec<_>>().join(", "))
+                    })?;
+                    amount_to_words(number, lang, currency)?
+                }
+                None => number_to_words(number, lang)?,
+            };
+            println!("{}", words);
+        }
+
+        if let Some(value) = matches.get_one::<String>("roman") {
+            let value = value.trim();
+            match value.parse::<u32>() {
+                Ok(number) => println!("{}", to_roman(number)?),
+                Err(_) => println!("{}", from_roman(value)?),
+            }
+        }
+        Ok(())
+    }
+}
+
+// (negative, whole part, digits after the point)
+fn parse_number(number: &str) -> Result<(bool, u64, String), String> {
+    let invalid = || format!("'{}' is not a number", number);
+    let cleaned: String = number.trim().chars().filter(|c| !matches!(c, ',' | '_')).collect();
+    let (negative, cleaned) = cleaned.strip_prefix('-').map_or((false, cleaned.as_str()), |rest| (true, rest));
+    let (whole, fraction) = cleaned.split_once('.').unwrap_or((cleaned, ""));
+    if whole.is_empty() && fraction.is_empty() || !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let whole: u64 = if whole.is_empty() { 0 } else { whole.parse().map_err(|_| invalid())? };
+    if whole > MAX {
+        return Err(format!("'{}' is too large; numbers up to 999 999 999 999 999 are supported", number));
+    }
+    Ok((negative, whole, fraction.to_string()))
+}
+
+fn minus(lang: &str) -> &'static str {
+    match lang {
+        "fr" => "moins",
+        "es" => "menos",
+        "tr" => "eksi",
+        _ => "minus",
+    }
+}
+
+fn number_to_words(number: &str, lang: &str) -> Result<String, String> {
+    let (negative, whole, fraction) = parse_number(number)?;
+    let mut words = integer_words(whole, lang);
+    if !fraction.is_empty() {
+        let point = match lang {
+            "de" => "Komma",
+            "es" => "coma",
+            "fr" => "virgule",
+            "tr" => "virgül",
+            _ => "point",
+        };
+        let digits: Vec<String> = fraction.chars().map(|digit| integer_words(digit as u64 - '0' as u64, lang)).collect();
+        words = format!("{} {} {}", words, point, digits.join(" "));
+    }
+    let is_zero = whole == 0 && fraction.chars().all(|c| c == '0');
+    Ok(if negative && !is_zero { format!("{} {}", minus(lang), words) } else { words })
+}
+
+fn amount_to_words(number: &str, lang: &str, currency: &Currency) -> Result<String, String> {
+    let (negative, whole, fraction) = parse_number(number)?;
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > currency.decimals as usize {
+        return Err(format!("{} has {} decimal place{}, '{}' has more", currency.code, currency.decimals, if currency.decimals == 1 { "" } else { "s" }, number));
+    }
+    let cents: u64 = format!("{:0<width$}", fraction, width = currency.decimals as usize).parse().unwrap_or(0);
+    let (unit, units, subunit, subunits, feminine) = currency.names[LANGS.iter().position(|l| *l == lang).unwrap_or(0)];
+    let mut words = counted(whole, lang, unit, units, feminine);
+    if cents > 0 {
+        let joiner = match lang {
+            "de" => " und ",
+            "es" => " con ",
+            "fr" => " et ",
+            "tr" => " ",
+            _ => " and ",
+        };
+        words = format!("{}{}{}", words, joiner, counted(cents, lang, subunit, subunits, false));
+    }
+    Ok(if negative && (whole > 0 || cents > 0) { format!("{} {}", minus(lang), words) } else { words })
+}
+
+// "one dollar", "zwei Euro", "un millón de dólares", "une livre"
+fn counted(count: u64, lang: &str, singular: &str, plural: &str, feminine: bool) -> String {
+    let words = before_noun(&integer_words(count, lang), lang, feminine);
+    // French uses the singular for zero as well
+    let noun = if count == 1 || (lang == "fr" && count == 0) { singular } else { plural };
+    // Whole millions take "de" in Spanish and French: "dos millones de euros"
+    if count >= 1_000_000 && count.is_multiple_of(1_000_000) && (lang == "es" || lang == "fr") {
+        if lang == "fr" && noun.starts_with(['a', 'e', 'é', 'i', 'o', 'u']) {
+            return format!("{} d'{}", words, noun);
+        }
+        return format!("{} de {}", words, noun);
+    }
+    format!("{} {}", words, noun)
+}
+
+// Adjusts the words for "one" in front of a noun: "eins" becomes "ein"/"eine",
+// "uno" becomes "un"/"una", and Spanish hundreds agree with a feminine noun
+fn before_noun(words: &str, lang: &str, feminine: bool) -> String {
+    match lang {
+        "de" => match words.strip_suffix("eins") {
+            Some(stem) => format!("{}{}", stem, if feminine { "eine" } else { "ein" }),
+            None => words.to_string(),
+        },
+        "fr" if feminine && (words == "un" || words.ends_with(" un") || words.ends_with("-un")) => format!("{}e", words),
+        "es" => {
+            let mut parts: Vec<String> = words.split(' ').map(str::to_string).collect();
+            let after_millions = parts.iter().rposition(|part| part.starts_with("millón") || part.starts_with("millones") || part.starts_with("billón") || part.starts_with("billones")).map_or(0, |index| index + 1);
+            let last = parts.len() - 1;
+            for (index, part) in parts.iter_mut().enumerate().skip(after_millions) {
+                if feminine {
+                    if let Some(stem) = part.strip_suffix("ientos") {
+                        *part = format!("{}ientas", stem);
+                    }
+                    *part = match part.as_str() {
+                        "un" | "uno" => "una".to_string(),
+                        "veintiún" | "veintiuno" => "veintiuna".to_string(),
+                        _ => part.clone(),
+                    };
+                } else if index == last {
+                    *part = match part.as_str() {
+                        "uno" => "un".to_string(),
+                        "veintiuno" => "veintiún".to_string(),
+                        _ => part.clone(),
+                    };
+                }
+            }
+            parts.join(" ")
+        }
+        _ => words.to_string(),
+    }
+}
+
+fn integer_words(number: u64, lang: &str) -> String {
+    match lang {
+        "de" => german(number),
+        "es" => spanish(number),
+        "fr" => french(number),
+        "tr" => turkish(number),
+        _ => english(number),
+    }
+}
+
+// Groups of three digits, least significant first
+fn groups(number: u64) -> Vec<u64> {
+    let mut groups = Vec::new();
+    let mut rest = number;
+    while rest > 0 {
+        groups.push(rest % 1000);
+        rest /= 1000;
+    }
+    groups
+}
+
+fn english(number: u64) -> String {
+    if number == 0 {
+        return EN_ONES[0].to_string();
+    }
+    let below_thousand = |n: u64| {
+        let mut words = Vec::new();
+        if n >= 100 {
+            words.push(format!("{} hundred", EN_ONES[(n / 100) as usize]));
+        }
+        match n % 100 {
+            0 => {}
+            rest @ 1..=19 => words.push(EN_ONES[rest as usize].to_string()),
+            rest if rest % 10 == 0 => words.push(EN_TENS[(rest / 10) as usize].to_string()),
+            rest => words.push(format!("{}-{}", EN_TENS[(rest / 10) as usize], EN_ONES[(rest % 10) as usize])),
+        }
+        words.join(" ")
+    };
+    let mut words: Vec<String> = Vec::new();
+    for (scale, group) in groups(number).into_iter().enumerate().rev().filter(|(_, group)| *group > 0) {
+        words.push(if scale == 0 { below_thousand(group) } else { format!("{} {}", below_thousand(group), EN_SCALES[scale]) });
+    }
+    words.join(" ")
+}
+
+// Written as one word below a million: 1234 is "eintausendzweihundertvierunddreißig"
+fn german(number: u64) -> String {
+    if number == 0 {
+        return DE_ONES[0].to_string();
+    }
+    let below_hundred = |n: u64| match n {
+        0 => String::new(),
+        1..=19 => DE_ONES[n as usize].to_string(),
+        _ if n.is_multiple_of(10) => DE_TENS[(n / 10) as usize].to_string(),
+        _ => format!("{}und{}", if n % 10 == 1 { "ein" } else { DE_ONES[(n % 10) as usize] }, DE_TENS[(n / 10) as usize]),
+    };
+    let below_thousand = |n: u64| {
+        let hundreds = match n / 100 {
+            0 => String::new(),
+            1 => "einhundert".to_string(),
+            h => format!("{}hundert", DE_ONES[h as usize]),
+        };
+        hundreds + &below_hundred(n % 100)
+    };
+    // "ein" in front of tausend and hundert, "eins" only at the very end
+    let stem = |n: u64| {
+        let words = below_thousand(n);
+        words.strip_suffix("eins").map_or(words.clone(), |stem| format!("{}ein", stem))
+    };
+    let groups = groups(number);
+    let mut words = Vec::new();
+    for scale in (2..groups.len()).rev().filter(|scale| groups[*scale] > 0) {
+        let (singular, plural) = DE_SCALES[scale - 2];
+        if groups[scale] == 1 {
+            words.push(format!("eine {}", singular));
+        } else {
+            let count = below_thousand(groups[scale]);
+            words.push(format!("{} {}", count.strip_suffix("eins").map_or(count.clone(), |stem| format!("{}eine", stem)), plural));
+        }
+    }
+    let thousands = groups.get(1).copied().unwrap_or(0);
+    let mut below_million = if thousands > 0 { format!("{}tausend", stem(thousands)) } else { String::new() };
+    below_million.push_str(&below_thousand(groups[0]));
+    if !below_million.is_empty() {
+        words.push(below_million);
+    }
+    words.join(" ")
+}
+
+fn spanish(number: u64) -> String {
+    let below_thousand = |n: u64| {
+        if n == 100 {
+            return "cien".to_string();
+        }
+        let mut words = Vec::new();
+        if n >= 100 {
+            words.push(ES_HUNDREDS[(n / 100) as usize].to_string());
+        }
+        match n % 100 {
+            0 if n > 0 => {}
+            rest @ 0..=29 => words.push(ES_ONES[rest as usize].to_string()),
+            rest if rest % 10 == 0 => words.push(ES_TENS[(rest / 10) as usize].to_string()),
+            rest => words.push(format!("{} y {}", ES_TENS[(rest / 10) as usize], ES_ONES[(rest % 10) as usize])),
+        }
+        words.join(" ")
+    };
+    // "uno" shortens to "un" in front of mil, millón and billón
+    let apocope = |words: String| match words.as_str() {
+        "uno" => "un".to_string(),
+        _ if words.ends_with("veintiuno") => format!("{}veintiún", &words[..words.len() - "veintiuno".len()]),
+        _ if words.ends_with(" uno") => format!("{} un", &words[..words.len() - " uno".len()]),
+        _ => words,
+    };
+    let below_million = |n: u64| {
+        let (thousands, rest) = (n / 1000, n % 1000);
+        let mut words = match thousands {
+            0 => return below_thousand(rest),
+            1 => "mil".to_string(),
+            _ => format!("{} mil", apocope(below_thousand(thousands))),
+        };
+        if rest > 0 {
+            words = format!("{} {}", words, below_thousand(rest));
+        }
+        words
+    };
+    let mut words = Vec::new();
+    for (value, singular, plural) in [(number / 1_000_000_000_000, "billón", "billones"), (number / 1_000_000 % 1_000_000, "millón", "millones")] {
+        match value {
+            0 => {}
+            1 => words.push(format!("un {}", singular)),
+            _ => words.push(format!("{} {}", apocope(below_million(value)), plural)),
+        }
+    }
+    if !number.is_multiple_of(1_000_000) || number == 0 {
+        words.push(below_million(number % 1_000_000));
+    }
+    words.join(" ")
+}
+
+fn french(number: u64) -> String {
+    let below_hundred = |n: u64| -> String {
+        let (tens, ones) = (n / 10, n % 10);
+        match n {
+            0..=16 => FR_ONES[n as usize].to_string(),
+            17..=19 => format!("dix-{}", FR_ONES[ones as usize]),
+            _ if tens <= 6 && ones == 0 => FR_TENS[tens as usize].to_string(),
+            _ if tens <= 6 && ones == 1 => format!("{} et un", FR_TENS[tens as usize]),
+            _ if tens <= 6 => format!("{}-{}", FR_TENS[tens as usize], FR_ONES[ones as usize]),
+            71 => "soixante et onze".to_string(),
+            72..=76 => format!("soixante-{}", FR_ONES[(n - 60) as usize]),
+            70 | 77..=79 => format!("soixante-dix{}", if ones == 0 { String::new() } else { format!("-{}", FR_ONES[ones as usize]) }),
+            80 => "quatre-vingts".to_string(),
+            81..=89 => format!("quatre-vingt-{}", FR_ONES[ones as usize]),
+            90..=96 => format!("quatre-vingt-{}", FR_ONES[(n - 80) as usize]),
+            _ => format!("quatre-vingt-dix-{}", FR_ONES[ones as usize]),
+        }
+    };
+    let below_thousand = |n: u64| {
+        let (hundreds, rest) = (n / 100, n % 100);
+        let mut words = match hundreds {
+            0 => return below_hundred(rest),
+            1 => "cent".to_string(),
+            // "deux cents", but "deux cent trois"
+            _ if rest == 0 => format!("{} cents", FR_ONES[hundreds as usize]),
+            _ => format!("{} cent", FR_ONES[hundreds as usize]),
+        };
+        if rest > 0 {
+            words = format!("{} {}", words, below_hundred(rest));
+        }
+        words
+    };
+    if number == 0 {
+        return FR_ONES[0].to_string();
+    }
+    let groups = groups(number);
+    let mut words = Vec::new();
+    for (scale, group) in groups.into_iter().enumerate().rev().filter(|(_, group)| *group > 0) {
+        words.push(match scale {
+            0 => below_thousand(group),
+            // mille never takes an s, and neither do cents and vingts in front of it
+            1 if group == 1 => "mille".to_string(),
+            1 => {
+                let count = below_thousand(group);
+                format!("{} mille", count.strip_suffix('s').filter(|stem| stem.ends_with("cent") || stem.ends_with("vingt")).unwrap_or(&count))
+            }
+            _ if group == 1 => format!("un {}", FR_SCALES[scale - 2]),
+            _ => format!("{} {}s", below_thousand(group), FR_SCALES[scale - 2]),
+        });
+    }
+    words.join(" ")
+}
+
+// "bin" and "yüz" stand alone for one thousand and one hundred
+fn turkish(number: u64) -> String {
+    if number == 0 {
+        return TR_ONES[0].to_string();
+    }
+    let below_thousand = |n: u64| {
+        let mut words = Vec::new();
+        match n / 100 {
+            0 => {}
+            1 => words.push("yüz".to_string()),
+            h => words.push(format!("{} yüz", TR_ONES[h as usize])),
+        }
+        if !(n / 10).is_multiple_of(10) {
+            words.push(TR_TENS[(n / 10 % 10) as usize].to_string());
+        }
+        if !n.is_multiple_of(10) {
+            words.push(TR_ONES[(n % 10) as usize].to_string());
+        }
+        words.join(" ")
+    };
+    let mut words = Vec::new();
+    for (scale, group) in groups(number).into_iter().enumerate().rev().filter(|(_, group)| *group > 0) {
+        words.push(match scale {
+            0 => below_thousand(group),
+            1 if group == 1 => "bin".to_string(),
+            _ => format!("{} {}", below_thousand(group), TR_SCALES[scale]),
+        });
+    }
+    words.join(" ")
+}
+
+fn to_roman(number: u32) -> Result<String, String> {
+    if !(1..=3999).contains(&number) {
+        return Err(format!("{} can't be written in Roman numerals; use 1 to 3999", number));
+    }
+    let mut rest = number;
+    let mut numeral = String::new();
+    for (value, symbol) in ROMAN {
+        while rest >= value {
+            numeral.push_str(symbol);
+            rest -= value;
+        }
+    }
+    Ok(numeral)
+}
+
+// Only the standard spelling is accepted; anything else is reported with it
+fn from_roman(numeral: &str) -> Result<u32, String> {
+    let upper = numeral.to_uppercase();
+    let values = upper
+        .chars()
+        .map(|c| ROMAN.iter().find(|(_, symbol)| symbol.len() == 1 && symbol.starts_with(c)).map(|(value, _)| *value))
+        .collect::<Option<Vec<u32>>>()
+        .filter(|values| !values.is_empty())
+        .ok_or_else(|| format!("'{}' is neither a number nor a Roman numeral (I, V, X, L, C, D, M)", numeral))?;
+    // A symbol smaller than the one after it is subtracted, as in IX
+    let total: i64 = values.iter().enumerate().map(|(index, value)| if values.get(index + 1).is_some_and(|next| next > value) { -(*value as i64) } else { *value as i64 }).sum();
+    match to_roman(u32::try_from(total).unwrap_or(0)) {
+        Ok(standard) if standard == upper => Ok(total as u32),
+        Ok(standard) => Err(format!("'{}' is not a standard Roman numeral; {} is written {}", numeral, total, standard)),
+        Err(_) => Err(format!("'{}' is out of range; Roman numerals go from I to MMMCMXCIX (3999)", numeral)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn currency(code: &str) -> &'static Currency {
+        CURRENCIES.iter().find(|currency| currency.code == code).unwrap()
+    }
+
+    #[test]
+    fn test_english() {
+        assert_eq!(number_to_words("1234", "en").unwrap(), "one thousand two hundred thirty-four");
+        assert_eq!(number_to_words("0", "en").unwrap(), "zero");
+        assert_eq!(number_to_words("-1,000,015", "en").unwrap(), "minus one million fifteen");
+        assert_eq!(number_to_words("12.05", "en").unwrap(), "twelve point zero five");
+        assert_eq!(number_to_words("999999999999999", "en").unwrap().split(' ').next(), Some("nine"));
+        assert!(number_to_words("1000000000000000", "en").unwrap_err().contains("too large"));
+        assert!(number_to_words("12a", "en").is_err());
+        assert_eq!(amount_to_words("1234.56", "en", currency("USD")).unwrap(), "one thousand two hundred thirty-four dollars and fifty-six cents");
+        assert_eq!(amount_to_words("1.5", "en", currency("GBP")).unwrap(), "one pound and fifty pence");
+        assert!(amount_to_words("3.5", "en", currency("JPY")).unwrap_err().contains("0 decimal places"));
+    }
+
+    #[test]
+    fn test_other_languages() {
+        assert_eq!(number_to_words("1234", "de").unwrap(), "eintausendzweihundertvierunddreißig");
+        assert_eq!(number_to_words("2000101", "de").unwrap(), "zwei Millionen einhunderteins");
+        assert_eq!(amount_to_words("21.01", "de", currency("EUR")).unwrap(), "einundzwanzig Euro und ein Cent");
+        assert_eq!(number_to_words("1234", "es").unwrap(), "mil doscientos treinta y cuatro");
+        assert_eq!(number_to_words("21100", "es").unwrap(), "veintiún mil cien");
+        assert_eq!(amount_to_words("2000000", "es", currency("USD")).unwrap(), "dos millones de dólares");
+        assert_eq!(amount_to_words("201", "es", currency("GBP")).unwrap(), "doscientas una libras");
+        assert_eq!(number_to_words("80", "fr").unwrap(), "quatre-vingts");
+        assert_eq!(number_to_words("71", "fr").unwrap(), "soixante et onze");
+        assert_eq!(number_to_words("200080", "fr").unwrap(), "deux cent mille quatre-vingts");
+        assert_eq!(amount_to_words("21", "fr", currency("GBP")).unwrap(), "vingt et une livres");
+        assert_eq!(amount_to_words("1000000", "fr", currency("EUR")).unwrap(), "un million d'euros");
+        assert_eq!(number_to_words("1234", "tr").unwrap(), "bin iki yüz otuz dört");
+        assert_eq!(amount_to_words("100.5", "tr", currency("TRY")).unwrap(), "yüz lira elli kuruş");
+    }
+
+    #[test]
+    fn test_roman() {
+        assert_eq!(to_roman(2024).unwrap(), "MMXXIV");
+        assert_eq!(to_roman(3999).unwrap(), "MMMCMXCIX");
+        assert!(to_roman(0).is_err());
+        assert_eq!(from_roman("mmxxiv").unwrap(), 2024);
+        assert_eq!(from_roman("XLIX").unwrap(), 49);
+        assert_eq!(from_roman("IIII").unwrap_err(), "'IIII' is not a standard Roman numeral; 4 is written IV");
+        assert_eq!(from_roman("IC").unwrap_err(), "'IC' is not a standard Roman numeral; 99 is written XCIX");
+        assert!(from_roman("MMMM").unwrap_err().contains("out of range"));
+        assert!(from_roman("ABC").is_err());
+    }
+}
@@ -0,0 +1,483 @@
+use crate::tool_module::{read_input, ExitCodeError, ToolModule};
+use crate::yaml_format::describe_error;
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use serde_yaml::Value;
+use std::collections::HashSet;
+use std::error::Error;
+use unicode_width::UnicodeWidthStr;
+
+pub struct OpenApiModule;
+
+const METHODS: [&str; 8] = ["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+
+#[derive(Debug, PartialEq)]
+struct Endpoint {
+    method: String,
+    path: String,
+    operation_id: Option<String>,
+    summary: Option<String>,
+    tags: Vec<String>,
+    auth: String,
+    deprecated: bool,
+}
+
+#[derive(Debug, Default)]
+struct Report {
+    title: String,
+    version: String,
+    // "OpenAPI 3.1.0" or "Swagger 2.0"
+    spec: String,
+    endpoints: Vec<Endpoint>,
+    // Declared tags first, then the rest as they are used
+    tags: Vec<String>,
+    errors: Vec<String>,
+    warnings: Vec<String>,
+}
+
+impl ToolModule for OpenApiModule {
+    fn name(&self) -> &'static str {
+        "openapi"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("openapi")
+                .long("openapi")
+                .value_name("FILE")
+                .num_args(0..=1)
+                .help("Validate an OpenAPI/Swagger spec (YAML or JSON) and list its endpoints by tag (or read from stdin)")
+                .long_help("Check an OpenAPI 3.x or Swagger 2.0 document before publishing it, then list every endpoint grouped by tag with its method, operationId, auth requirement and summary. Errors cover missing info fields, unresolved $refs, undeclared or unused path parameters, duplicate operationIds, unknown security schemes and operations or responses missing what the spec requires; warnings cover missing operationIds and operations without a documented success response. Exits with 1 when there are errors. If no file (or -) is given, reads from stdin.")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if matches.contains_id("openapi") {
+            let input = read_input(matches.get_one::<String>("openapi"))?;
+            let document: Value = serde_yaml::from_str(&input).map_err(|e| describe_error(&input, &e))?;
+            let report = analyze(&document)?;
+            print_report(&report);
+            if !report.errors.is_empty() {
+                return Err(Box::new(ExitCodeError::silent(1)));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Mapping keys as text; response codes are often written unquoted
+fn key_text(key: &Value) -> String {
+    match key {
+        Value::String(text) => text.clone(),
+        Value::Number(number) => number.to_string(),
+        Value::Bool(flag) => flag.to_string(),
+        other => serde_yaml::to_string(other).unwrap_or_default().trim().to_string(),
+    }
+}
+
+fn text<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(Value::as_str)
+}
+
+// A local reference like #/components/schemas/User, with ~1 for / and ~0 for ~
+fn lookup<'a>(document: &'a Value, reference: &str) -> Option<&'a Value> {
+    let pointer = reference.strip_prefix("#/")?;
+    pointer.split('/').try_fold(document, |value, segment| {
+        let segment = segment.replace("~1", "/").replace("~0", "~");
+        match value {
+            Value::Mapping(mapping) => mapping.iter().find(|(key, _)| key_text(key) == segment).map(|(_, value)| value),
+            Value::Sequence(items) => segment.parse::<usize>().ok().and_then(|index| items.get(index)),
+            _ => None,
+        }
+    })
+}
+
+fn resolve<'a>(document: &'a Value, value: &'a Value) -> &'a Value {
+    match text(value, "$ref").and_then(|reference| lookup(document, reference)) {
+        Some(target) => target,
+        None => value,
+    }
+}
+
+// Every local $ref that points nowhere, with where it was found
+fn unresolved_refs(document: &Value, value: &Value, location: &str, found: &mut Vec<String>) {
+    match value {
+        Value::Mapping(mapping) => {
+            for (key, child) in mapping {
+                let key = key_text(key);
+                match child.as_str() {
+                    Some(reference) if key == "$ref" && reference.starts_with('#') && lookup(document, reference).is_none() => {
+                        found.push(format!("Unresolved $ref '{}' at {}", reference, location));
+                    }
+                    _ => unresolved_refs(document, child, &format!("{}/{}", location, key.replace('~', "~0").replace('/', "~1")), found),
+                }
+            }
+        }
+        Value::Sequence(items) => {
+            for (index, item) in items.iter().enumerate() {
+                unresolved_refs(document, item, &format!("{}/{}", location, index), found);
+            }
+        }
+        _ => {}
+    }
+}
+
+// {id} and {slug} in /users/{id}/posts/{slug}
+fn template_params(path: &str) -> Vec<&str> {
+    path.split('{').skip(1).filter_map(|rest| rest.split_once('}').map(|(name, _)| name)).collect()
+}
+
+// Alternatives joined by |, schemes that must all be present by +; an empty list makes the endpoint public
+fn describe_security(security: Option<&Value>) -> String {
+    let Some(requirements) = security.and_then(Value::as_sequence) else {
+        return "public".to_string();
+    };
+    let alternatives: Vec<String> = requirements
+        .iter()
+        .map(|requirement| {
+            let schemes: Vec<String> = requirement
+                .as_mapping()
+                .into_iter()
+                .flatten()
+                .map(|(name, scopes)| {
+                    let scopes: Vec<&str> = scopes.as_sequence().into_iter().flatten().filter_map(Value::as_str).collect();
+                    match scopes.is_empty() {
+                        true => key_text(name),
+                        false => format!("{} ({})", key_text(name), scopes.join(", ")),
+                    }
+                })
+                .collect();
+            if schemes.is_empty() {
+                "none".to_string()
+            } else {
+                schemes.join(" + ")
+            }
+        })
+        .collect();
+    if alternatives.is_empty() {
+        "public".to_string()
+    } else {
+        alternatives.join(" | ")
+    }
+}
+
+fn check_info(document: &Value, report: &mut Report) {
+    let info = document.get("info");
+    if info.is_none() {
+        report.errors.push("Missing info object".to_string());
+    }
+    for field in ["title", "version"] {
+        match info.and_then(|info| info.get(field)) {
+            Some(value) => {
+                let value = key_text(value);
+                if field == "title" {
+                    report.title = value;
+                } else {
+                    report.version = value;
+                }
+            }
+            None if info.is_some() => report.errors.push(format!("Missing info.{}", field)),
+            None => {}
+        }
+    }
+}
+
+fn analyze(document: &Value) -> Result<Report, String> {
+    if !document.is_mapping() {
+        return Err("Not an OpenAPI document: expected a mapping at the top level".to_string());
+    }
+    let mut report = Report::default();
+    let swagger = match (document.get("openapi").map(key_text), document.get("swagger").map(key_text)) {
+        (Some(version), _) => {
+            if !version.starts_with("3.0") && !version.starts_with("3.1") {
+                report.errors.push(format!("Unsupported openapi version '{}', expected 3.0.x or 3.1.x", version));
+            }
+            report.spec = format!("OpenAPI {}", version);
+            false
+        }
+        (None, Some(version)) => {
+            if version != "2.0" {
+                report.errors.push(format!("Unsupported swagger version '{}', expected 2.0", version));
+            }
+            report.spec = format!("Swagger {}", version);
+            true
+        }
+        (None, None) => return Err("Not an OpenAPI document: no openapi or swagger version field".to_string()),
+    };
+    check_info(document, &mut report);
+
+    let schemes = if swagger { document.get("securityDefinitions") } else { document.get("components").and_then(|components| components.get("securitySchemes")) };
+    let scheme_names: HashSet<String> = schemes.and_then(Value::as_mapping).into_iter().flatten().map(|(name, _)| key_text(name)).collect();
+    let check_security = |security: Option<&Value>, location: &str, errors: &mut Vec<String>| {
+        for requirement in security.and_then(Value::as_sequence).into_iter().flatten() {
+            for (name, _) in requirement.as_mapping().into_iter().flatten() {
+                if !scheme_names.contains(&key_text(name)) {
+                    errors.push(format!("{}: unknown security scheme '{}'", location, key_text(name)));
+                }
+            }
+        }
+    };
+    check_security(document.get("security"), "security", &mut report.errors);
+
+    report.tags = document
+        .get("tags")
+        .and_then(Value::as_sequence)
+        .into_iter()
+        .flatten()
+        .filter_map(|tag| text(tag, "name").map(str::to_string))
+        .collect();
+
+    // 3.1 allows a document with only webhooks or components
+    let paths = document.get("paths").and_then(Value::as_mapping);
+    let paths_optional = !swagger && report.spec.starts_with("OpenAPI 3.1") && (document.get("webhooks").is_some() || document.get("components").is_some());
+    if paths.is_none() && !paths_optional {
+        report.errors.push("Missing paths object".to_string());
+    }
+    let mut operation_ids = HashSet::new();
+    for (path, item) in paths.into_iter().flatten() {
+        let path = key_text(path);
+        if !path.starts_with('/') {
+            report.errors.push(format!("Path '{}' must start with /", path));
+        }
+        let item = resolve(document, item);
+        let shared_params: Vec<&Value> = item.get("parameters").and_then(Value::as_sequence).into_iter().flatten().map(|param| resolve(document, param)).collect();
+        for method in METHODS {
+            let Some(operation) = item.get(method) else { continue };
+            let label = format!("{} {}", method.to_uppercase(), path);
+
+            // Operation parameters override path-level ones with the same name and location
+            let mut params: Vec<&Value> = operation.get("parameters").and_then(Value::as_sequence).into_iter().flatten().map(|param| resolve(document, param)).collect();
+            for shared in &shared_params {
+                if !params.iter().any(|param| text(param, "name") == text(shared, "name") && text(param, "in") == text(shared, "in")) {
+                    params.push(shared);
+                }
+            }
+            let path_params: Vec<&Value> = params.iter().copied().filter(|param| text(param, "in") == Some("path")).collect();
+            let template = template_params(&path);
+            for name in &template {
+                if !path_params.iter().any(|param| text(param, "name") == Some(name)) {
+                    report.errors.push(format!("{}: path parameter '{}' is not declared", label, name));
+                }
+            }
+            for param in &path_params {
+                let name = text(param, "name").unwrap_or_default();
+                if !template.contains(&name) {
+                    report.errors.push(format!("{}: path parameter '{}' is not in the path", label, name));
+                } else if param.get("required").and_then(Value::as_bool) != Some(true) {
+                    report.errors.push(format!("{}: path parameter '{}' must have required: true", label, name));
+                }
+            }
+
+            let operation_id = text(operation, "operationId").map(str::to_string);
+            match &operation_id {
+                Some(id) if !operation_ids.insert(id.clone()) => report.errors.push(format!("{}: duplicate operationId '{}'", label, id)),
+                Some(_) => {}
+                None => report.warnings.push(format!("{}: no operationId", label)),
+            }
+
+            match operation.get("responses").and_then(Value::as_mapping) {
+                Some(responses) if !responses.is_empty() => {
+                    let codes: Vec<String> = responses.keys().map(key_text).collect();
+                    if !codes.iter().any(|code| code.starts_with('2') || code == "default") {
+                        report.warnings.push(format!("{}: no 2xx or default response documented", label));
+                    }
+                    for (code, response) in responses {
+                        // Unresolved $refs are reported on their own
+                        let response = resolve(document, response);
+                        if response.get("description").is_none() && response.get("$ref").is_none() {
+                            report.errors.push(format!("{}: response {} has no description", label, key_text(code)));
+                        }
+                    }
+                }
+                _ => report.errors.push(format!("{}: no responses documented", label)),
+            }
+
+            let security = operation.get("security");
+            check_security(security, &label, &mut report.errors);
+            let tags: Vec<String> = operation.get("tags").and_then(Value::as_sequence).into_iter().flatten().filter_map(Value::as_str).map(str::to_string).collect();
+            for tag in &tags {
+                if !report.tags.contains(tag) {
+                    report.tags.push(tag.clone());
+                }
+            }
+            report.endpoints.push(Endpoint {
+                method: method.to_uppercase(),
+                path: path.clone(),
+                operation_id,
+                summary: text(operation, "summary").map(str::to_string),
+                tags,
+                auth: describe_security(security.or(document.get("security"))),
+                deprecated: operation.get("deprecated").and_then(Value::as_bool) == Some(true),
+            });
+        }
+    }
+
+    let mut refs = Vec::new();
+    unresolved_refs(document, document, "#", &mut refs);
+    report.errors.extend(refs);
+    Ok(report)
+}
+
+fn print_report(report: &Report) {
+    let title = if report.title.is_empty() { "Untitled API" } else { &report.title };
+    println!("{} {} {}", title.bold(), report.version, format!("({}, {} endpoints)", report.spec, report.endpoints.len()).dimmed());
+    let path_width = report.endpoints.iter().map(|endpoint| endpoint.path.len()).max().unwrap_or(0);
+    let id_width = report.endpoints.iter().map(|endpoint| endpoint.operation_id.as_deref().unwrap_or("-").len()).max().unwrap_or(0);
+    let auth_text = |endpoint: &Endpoint| if endpoint.auth == "public" { endpoint.auth.clone() } else { format!("🔒 {}", endpoint.auth) };
+    let auth_width = report.endpoints.iter().map(|endpoint| auth_text(endpoint).width()).max().unwrap_or(0);
+    let untagged = "(untagged)".to_string();
+    let groups = report.tags.iter().chain(report.endpoints.iter().any(|endpoint| endpoint.tags.is_empty()).then_some(&untagged));
+    for tag in groups {
+        let endpoints: Vec<&Endpoint> = report
+            .endpoints
+            .iter()
+            .filter(|endpoint| if tag == &untagged { endpoint.tags.is_empty() } else { endpoint.tags.contains(tag) })
+            .collect();
+        if endpoints.is_empty() {
+            continue;
+        }
+        println!("\n{} ({})", tag.cyan().bold(), endpoints.len());
+        for endpoint in endpoints {
+            let auth = auth_text(endpoint);
+            let padded = format!("{}{}", auth, " ".repeat(auth_width - auth.width()));
+            let auth = if endpoint.auth == "public" { padded.dimmed().to_string() } else { padded };
+            let summary = endpoint.summary.as_deref().unwrap_or_default();
+            let deprecated = if endpoint.deprecated { " (deprecated)".yellow().to_string() } else { String::new() };
+            let line = format!(
+                "  {} {:<path_width$}  {:<id_width$}  {}  {}{}",
+                format!("{:<7}", endpoint.method).green(),
+                endpoint.path,
+                endpoint.operation_id.as_deref().unwrap_or("-"),
+                auth,
+                summary.dimmed(),
+                deprecated,
+                path_width = path_width,
+                id_width = id_width
+            );
+            println!("{}", line.trim_end());
+        }
+    }
+    println!();
+    if !report.warnings.is_empty() {
+        println!("{}", format!("⚠️  {} warning(s)", report.warnings.len()).yellow());
+        for warning in &report.warnings {
+            println!("   - {}", warning);
+        }
+    }
+    if report.errors.is_empty() {
+        println!("{}", format!("✅ Valid {} document", report.spec).green());
+    } else {
+        println!("{}", format!("❌ {} error(s)", report.errors.len()).red());
+        for error in &report.errors {
+            println!("   - {}", error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PETSTORE: &str = "openapi: 3.0.3
+info:
+  title: Pet Store
+  version: 1.2.0
+tags:
+  - name: pets
+security:
+  - bearerAuth: []
+paths:
+  /pets:
+    get:
+      tags: [pets]
+      operationId: listPets
+      summary: List pets
+      security: []
+      responses:
+        200:
+          description: OK
+    post:
+      tags: [pets, admin]
+      responses:
+        '201':
+          $ref: '#/components/responses/Created'
+  /pets/{petId}:
+    parameters:
+      - $ref: '#/components/parameters/PetId'
+    get:
+      operationId: getPet
+      responses:
+        '404':
+          description: Missing
+components:
+  securitySchemes:
+    bearerAuth:
+      type: http
+      scheme: bearer
+  parameters:
+    PetId:
+      name: petId
+      in: path
+      required: true
+      schema: {type: string}
+  responses:
+    Created:
+      description: Created
+";
+
+    fn parse(yaml: &str) -> Value {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_endpoints_and_auth() {
+        let report = analyze(&parse(PETSTORE)).unwrap();
+        assert_eq!((report.title.as_str(), report.version.as_str(), report.spec.as_str()), ("Pet Store", "1.2.0", "OpenAPI 3.0.3"));
+        assert_eq!(report.tags, ["pets", "admin"]);
+        let summary: Vec<(&str, &str, &str)> = report.endpoints.iter().map(|e| (e.method.as_str(), e.path.as_str(), e.auth.as_str())).collect();
+        assert_eq!(summary, [("GET", "/pets", "public"), ("POST", "/pets", "bearerAuth"), ("GET", "/pets/{petId}", "bearerAuth")]);
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert_eq!(report.warnings, ["POST /pets: no operationId", "GET /pets/{petId}: no 2xx or default response documented"]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let broken = PETSTORE
+            .replace("operationId: getPet", "operationId: listPets\n      security:\n        - apiKey: []")
+            .replace("      - $ref: '#/components/parameters/PetId'\n", "      - {name: id, in: path}\n")
+            .replace("'#/components/responses/Created'", "'#/components/responses/Made'")
+            .replace("          description: OK\n", "          content: {}\n");
+        let report = analyze(&parse(&broken)).unwrap();
+        assert_eq!(
+            report.errors,
+            [
+                "GET /pets: response 200 has no description",
+                "GET /pets/{petId}: path parameter 'petId' is not declared",
+                "GET /pets/{petId}: path parameter 'id' is not in the path",
+                "GET /pets/{petId}: duplicate operationId 'listPets'",
+                "GET /pets/{petId}: unknown security scheme 'apiKey'",
+                "Unresolved $ref '#/components/responses/Made' at #/paths/~1pets/post/responses/201",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_document_level_checks() {
+        let report = analyze(&parse("swagger: '2.0'\ninfo: {title: Old}\n")).unwrap();
+        assert_eq!(report.spec, "Swagger 2.0");
+        assert_eq!(report.errors, ["Missing info.version", "Missing paths object"]);
+        let report = analyze(&parse("openapi: 3.1.0\ninfo: {title: Hooks, version: '1'}\nwebhooks: {}\n")).unwrap();
+        assert!(report.errors.is_empty());
+        assert!(analyze(&parse("name: not a spec\n")).is_err());
+        assert!(analyze(&parse("- a\n")).is_err());
+    }
+
+    #[test]
+    fn test_helpers() {
+        assert_eq!(template_params("/a/{x}/b/{y}.json"), ["x", "y"]);
+        let security = parse("- {oauth: [read, write], key: []}\n- {}\n");
+        assert_eq!(describe_security(Some(&security)), "oauth (read, write) + key | none");
+        let document = parse("paths:\n  /a/b:\n    get: {x: 1}\n");
+        assert_eq!(lookup(&document, "#/paths/~1a~1b/get/x"), Some(&Value::from(1)));
+    }
+}
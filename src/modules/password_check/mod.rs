@@ -0,0 +1,267 @@
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use colored::*;
+use reqwest::blocking::Client;
+use ring::digest;
+use std::error::Error;
+use std::time::Duration;
+
+mod scoring;
+use scoring::{analyze, feedback, Match, Pattern};
+
+pub struct PasswordCheckModule;
+
+// Pattern matching slows down on long input, and longer passwords only get stronger
+const MAX_ANALYZED_CHARS: usize = 100;
+const HIBP_RANGE_URL: &str = "https://api.pwnedpasswords.com/range/";
+
+// Guesses per second for the usual attack scenarios
+const ATTACK_SCENARIOS: [(&str, f64); 4] = [
+    ("Online, throttled (100/hour)", 100.0 / 3600.0),
+    ("Online, unthrottled (10/s)", 10.0),
+    ("Offline, slow hash (10k/s)", 1e4),
+    ("Offline, fast hash (10B/s)", 1e10),
+];
+
+impl ToolModule for PasswordCheckModule {
+    fn name(&self) -> &'static str {
+        "password-check"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("password-check")
+                .long("password-check")
+                .value_name("PASSWORD")
+                .num_args(0..=1)
+                .default_missing_value("-")
+                .help("Estimate how hard a password is to crack, with the patterns found and suggestions (prompts if no value or -)")
+                .long_help("Estimate how many guesses a password takes to crack, zxcvbn-style: it looks for common passwords, dictionary words and names (also reversed or with l33t substitutions like @ for a), keyboard patterns, repeats, sequences, years and dates, and scores the cheapest combination from 0 (too guessable) to 4 (very unguessable). Shows crack times for online and offline attacks, the patterns found and how to improve it. The password is only echoed masked; leave out the value to type it at a hidden prompt and keep it out of your shell history.")
+        )
+        .arg(
+            Arg::new("hibp")
+                .long("hibp")
+                .help("With --password-check, also count how often it appears in known breaches (Have I Been Pwned)")
+                .long_help("With --password-check, also look the password up in Have I Been Pwned's breach corpus. Uses the k-anonymity range API: only the first 5 characters of its SHA-1 hash are sent, and responses are padded so their size gives nothing away.")
+                .action(ArgAction::SetTrue)
+                .requires("password-check")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(value) = matches.get_one::<String>("password-check") {
+            // Checked before prompting, so an empty value never reaches the terminal code
+            if value.is_empty() {
+                return Err("No password given. Pass it as the value, or leave the value out to type it at a hidden prompt".into());
+            }
+            let password = if value == "-" {
+                rpassword::prompt_password("Password: ").map_err(|e| format!("Could not read the password from the terminal ({}); pass it as the value instead", e))?
+            } else {
+                value.clone()
+            };
+            if password.is_empty() {
+                return Err("No password given".into());
+            }
+            report(&password);
+            if matches.get_flag("hibp") {
+                match breach_count(&password)? {
+                    0 => println!("✅ Not found in any known data breach"),
+                    count => println!("❌ Seen {} times in data breaches; don't use it anywhere", count),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn report(password: &str) {
+    let length = password.chars().count();
+    let analyzed: String = password.chars().take(MAX_ANALYZED_CHARS).collect();
+    let analysis = analyze(&analyzed);
+    println!("{:<14}{} ({} characters)", "Password:".cyan(), mask(password), length);
+    let (label, color) = match analysis.score {
+        0 => ("too guessable", Color::Red),
+        1 => ("very guessable", Color::Red),
+        2 => ("somewhat guessable", Color::Yellow),
+        3 => ("safely unguessable", Color::Green),
+        _ => ("very unguessable", Color::Green),
+    };
+    let bar = format!("{}{}", "█".repeat(analysis.score as usize), "░".repeat(4 - analysis.score as usize));
+    println!("{:<14}{} {}/4, {}", "Strength:".cyan(), bar.color(color), analysis.score, label.color(color));
+    println!("{:<14}{}", "Guesses:".cyan(), format_guesses(analysis.guesses));
+    if length > MAX_ANALYZED_CHARS {
+        println!("   Only the first {} characters were analyzed; the rest only makes it stronger", MAX_ANALYZED_CHARS);
+    }
+    println!("{}", "Crack time:".cyan());
+    for (scenario, rate) in ATTACK_SCENARIOS {
+        println!("  {:<30}{}", scenario, format_crack_time(analysis.guesses / rate));
+    }
+    println!("{}", "Patterns:".cyan());
+    for found in &analysis.sequence {
+        let positions = if found.start == found.end {
+            format!("{}", found.start + 1)
+        } else {
+            format!("{}-{}", found.start + 1, found.end + 1)
+        };
+        println!("  {:<8}{} (10^{:.1} guesses)", positions, describe(found), found.guesses.log10());
+    }
+    let feedback = feedback(&analysis);
+    if let Some(warning) = feedback.warning {
+        println!("⚠️  {}", warning);
+    }
+    if !feedback.suggestions.is_empty() {
+        println!("{}", "Suggestions:".cyan());
+        for suggestion in feedback.suggestions {
+            println!("  - {}", suggestion);
+        }
+    }
+}
+
+// The first and last characters of longer passwords, so you can tell which one was checked
+fn mask(password: &str) -> String {
+    let chars: Vec<char> = password.chars().collect();
+    if chars.len() < 8 {
+        return "*".repeat(chars.len());
+    }
+    format!("{}{}{}", chars[0], "*".repeat(chars.len() - 2), chars[chars.len() - 1])
+}
+
+// What a match is, without repeating the characters it covers
+fn describe(found: &Match) -> String {
+    match &found.pattern {
+        Pattern::Dictionary { word, dictionary, rank, reversed, l33t } => {
+            let mut notes = vec![format!("{} #{}", dictionary, rank)];
+            if *reversed {
+                notes.push("reversed".to_string());
+            }
+            if !l33t.is_empty() {
+                let subs: Vec<String> = l33t.iter().map(|(symbol, letter)| format!("{}→{}", symbol, letter)).collect();
+                notes.push(format!("l33t {}", subs.join(" ")));
+            }
+            if found.token.chars().any(char::is_uppercase) {
+                notes.push("capitalized".to_string());
+            }
+            format!("dictionary word \"{}\" ({})", word, notes.join(", "))
+        }
+        Pattern::Spatial { turns, shifted } => {
+            let shifted = if *shifted > 0 { format!(", {} shifted", shifted) } else { String::new() };
+            format!("keyboard pattern ({} turn{}{})", turns, if *turns == 1 { "" } else { "s" }, shifted)
+        }
+        Pattern::Repeat { base, count } => format!("repeat ({}×, {}-character unit)", count, base.chars().count()),
+        Pattern::Sequence { ascending } => format!("{} sequence", if *ascending { "ascending" } else { "descending" }),
+        Pattern::Year { year } => format!("year {}", year),
+        Pattern::Date { year, separator } => format!("date in {}{}", year, if *separator { ", with separators" } else { "" }),
+        Pattern::BruteForce => "brute force".to_string(),
+    }
+}
+
+// Exact below a billion, otherwise just the order of magnitude
+fn format_guesses(guesses: f64) -> String {
+    if guesses < 1e9 {
+        format!("{} (10^{:.1})", group_digits(guesses.round() as u64), guesses.log10())
+    } else {
+        format!("10^{:.1}", guesses.log10())
+    }
+}
+
+fn group_digits(count: u64) -> String {
+    let digits = count.to_string();
+    let mut grouped = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+// "less than a second", "3 hours", "centuries"
+fn format_crack_time(seconds: f64) -> String {
+    const UNITS: [(&str, f64); 6] = [
+        ("year", 31.0 * 86_400.0 * 12.0),
+        ("month", 31.0 * 86_400.0),
+        ("day", 86_400.0),
+        ("hour", 3_600.0),
+        ("minute", 60.0),
+        ("second", 1.0),
+    ];
+    if seconds < 1.0 {
+        return "less than a second".to_string();
+    }
+    if seconds >= 100.0 * UNITS[0].1 {
+        return "centuries".to_string();
+    }
+    let (unit, size) = UNITS.iter().find(|(_, size)| seconds >= *size).copied().unwrap_or(UNITS[5]);
+    let count = (seconds / size).round() as u64;
+    format!("{} {}{}", count, unit, if count == 1 { "" } else { "s" })
+}
+
+fn sha1_hex(password: &str) -> String {
+    digest::digest(&digest::SHA1_FOR_LEGACY_USE_ONLY, password.as_bytes())
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect()
+}
+
+// How often the hash suffix appears in a range response of "SUFFIX:COUNT" lines
+fn count_in_range(body: &str, suffix: &str) -> u64 {
+    body.lines()
+        .filter_map(|line| line.trim().split_once(':'))
+        .find(|(candidate, _)| candidate.eq_ignore_ascii_case(suffix))
+        .and_then(|(_, count)| count.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+// Only the first 5 hex characters of the SHA-1 hash leave the machine
+fn breach_count(password: &str) -> Result<u64, Box<dyn Error>> {
+    let hash = sha1_hex(password);
+    let (prefix, suffix) = hash.split_at(5);
+    let client = Client::builder()
+        .user_agent(concat!("micro-swiss/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(15))
+        .build()?;
+    let response = client
+        .get(format!("{}{}", HIBP_RANGE_URL, prefix))
+        // Decoy entries with a count of 0 hide how many real ones share the prefix
+        .header("Add-Padding", "true")
+        .send()
+        .map_err(|e| format!("Breach lookup failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Breach lookup failed: HTTP {}", response.status()).into());
+    }
+    let body = response.text()?;
+    Ok(count_in_range(&body, suffix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask() {
+        assert_eq!(mask("Tr0ub4dor&3"), "T*********3");
+        assert_eq!(mask("secret"), "******");
+        assert_eq!(mask("pässwörd"), "p******d");
+    }
+
+    #[test]
+    fn test_format_crack_time() {
+        assert_eq!(format_crack_time(0.2), "less than a second");
+        assert_eq!(format_crack_time(1.0), "1 second");
+        assert_eq!(format_crack_time(7_200.0), "2 hours");
+        assert_eq!(format_crack_time(86_400.0 * 45.0), "1 month");
+        assert_eq!(format_crack_time(1e12), "centuries");
+    }
+
+    #[test]
+    fn test_breach_range() {
+        // SHA-1 of "password"
+        let hash = sha1_hex("password");
+        assert_eq!(hash, "5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8");
+        let body = "003D68EB55068C33ACE09247EE4C639306B:3\r\n1E4C9B93F3F0682250B6CF8331B7EE68FD8:9545824\r\n1E4C9B93F3F0682250B6CF8331B7EE68FD9:0\r\n";
+        assert_eq!(count_in_range(body, &hash[5..]), 9545824);
+        assert_eq!(count_in_range(body, "0000000000000000000000000000000000A"), 0);
+    }
+}
@@ -0,0 +1,711 @@
+use chrono::Datelike;
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+// Guess estimation in the style of Dropbox's zxcvbn: every way the password
+// matches a known pattern is found, then the cheapest way to cover it with
+// patterns and brute force decides how many guesses an attacker needs.
+
+// Each extra pattern in a sequence costs at least this many guesses
+const MIN_GUESSES_BEFORE_GROWING_SEQUENCE: f64 = 10_000.0;
+const MIN_SUBMATCH_GUESSES_SINGLE_CHAR: f64 = 10.0;
+const MIN_SUBMATCH_GUESSES_MULTI_CHAR: f64 = 50.0;
+const BRUTEFORCE_CARDINALITY: f64 = 10.0;
+// Years closer than this to the current one are all about as likely
+const MIN_YEAR_SPACE: i32 = 20;
+// Substitution choices tried per password, so symbol-heavy passwords stay fast
+const MAX_L33T_VARIANTS: usize = 64;
+
+// Ranked by how common they are, most common first
+const PASSWORDS: &str = "123456 password 123456789 12345678 12345 qwerty 1234567 111111 1234567890 123123 abc123 1234 \
+    password1 iloveyou 1q2w3e4r 000000 qwerty123 zaq12wsx dragon sunshine princess letmein 654321 monkey 27653 1qaz2wsx \
+    123321 qwertyuiop superman asdfghjkl trustno1 football baseball welcome shadow master hello freedom whatever qazwsx \
+    michael login starwars admin passw0rd charlie donald aa123456 121212 flower hottie loveme zaq1zaq1 batman access \
+    solo ninja mustang jessica jordan hunter buster soccer harley ranger daniel thomas robert andrew joshua pepper killer \
+    george summer ashley nicole chelsea biteme matthew yankees computer amanda tigger cheese secret internet samsung \
+    maggie cookie orange pokemon purple banana silver ginger junior hockey diamond corvette butterfly chocolate test \
+    guest root changeme default 666666 7777777 888888 987654321 159753 112233 1111 123qwe qwe123 asdf asdfgh zxcvbnm \
+    azerty 11111111 00000000 123654 lovely princess1 iloveyou1 monkey1 dragon1 sunshine1 master1 welcome1 admin123 \
+    root123 test123 pass pass123 password123 letmein1 qwerty1 abcd1234 abcdef 696969 baseball1 football1 superman1 \
+    michelle jennifer hunter2 starwars1 whatever1 secret1 matrix merlin";
+
+const ENGLISH: &str = "the of and to in is you that it he was for on are as with his they at be this have from or one \
+    had by word but not what all were we when your can said there use an each which she do how their if will up other \
+    about out many then them these so some her would make like him into time has look two more write go see number no \
+    way could people my than first water been call who oil its now find long down day did get come made may part love \
+    house world life home money family friend happy horse battery staple correct dog cat summer winter spring autumn \
+    baby angel star sun moon blue red green black white music magic power super hello welcome apple orange banana tiger \
+    dragon monkey secret company office work school game player football soccer hockey golf king queen prince princess \
+    lucky cookie sugar honey sweet heart kiss smile forever always never together girl boy man woman mother father \
+    brother sister daddy mommy friends peace freedom jesus god heaven hell devil death dark light fire ice snow rain \
+    storm thunder ocean sea river mountain forest flower rose tree garden night morning evening today tomorrow \
+    yesterday computer internet phone google window door car truck bike train plane ship rock metal gold silver \
+    diamond crystal cool hot fast speed rocket space planet earth mars hunter killer master shadow ninja pirate wizard \
+    knight warrior soldier police doctor teacher student letter password admin login user guest test change chocolate \
+    coffee pizza cheese bread butter pepper salt lemon cherry strawberry";
+
+const NAMES: &str = "michael james john robert david william richard joseph thomas charles christopher daniel matthew \
+    anthony mark donald steven paul andrew joshua mary patricia jennifer linda elizabeth barbara susan jessica sarah \
+    karen nancy lisa betty margaret sandra ashley kimberly emily donna michelle carol amanda melissa deborah stephanie \
+    rebecca laura sharon cynthia kathleen amy angela anna emma olivia sophia smith johnson williams brown jones garcia \
+    miller davis rodriguez martinez wilson anderson taylor moore jackson martin lee thompson";
+
+// Symbols commonly typed in place of letters
+const L33T_TABLE: &[(char, &str)] = &[
+    ('4', "a"), ('@', "a"), ('8', "b"), ('(', "c"), ('{', "c"), ('[', "c"), ('<', "c"), ('3', "e"), ('6', "g"),
+    ('9', "g"), ('1', "il"), ('!', "i"), ('|', "il"), ('7', "lt"), ('0', "o"), ('$', "s"), ('5', "s"), ('+', "t"),
+    ('%', "x"), ('2', "z"),
+];
+
+// US QWERTY rows with each row's horizontal offset in key widths
+const KEYBOARD_ROWS: [(&str, &str, f64); 4] = [
+    ("`1234567890-=", "~!@#$%^&*()_+", 0.0),
+    ("qwertyuiop[]\\", "QWERTYUIOP{}|", 1.5),
+    ("asdfghjkl;'", "ASDFGHJKL:\"", 1.75),
+    ("zxcvbnm,./", "ZXCVBNM<>?", 2.25),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    Dictionary { word: String, dictionary: &'static str, rank: usize, reversed: bool, l33t: Vec<(char, char)> },
+    Spatial { turns: usize, shifted: usize },
+    Repeat { base: String, count: usize },
+    Sequence { ascending: bool },
+    Year { year: i32 },
+    Date { year: i32, separator: bool },
+    BruteForce,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match {
+    // Character positions, both inclusive
+    pub start: usize,
+    pub end: usize,
+    pub token: String,
+    pub pattern: Pattern,
+    pub guesses: f64,
+}
+
+#[derive(Debug)]
+pub struct Analysis {
+    pub guesses: f64,
+    // 0 (too guessable) to 4 (very unguessable)
+    pub score: u8,
+    // The patterns that make up the cheapest guess, in password order
+    pub sequence: Vec<Match>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Feedback {
+    pub warning: Option<String>,
+    pub suggestions: Vec<String>,
+}
+
+struct Step {
+    found: Match,
+    // Product of the guesses of the matches so far
+    product: f64,
+    total: f64,
+}
+
+fn dictionaries() -> &'static [(&'static str, HashMap<&'static str, usize>)] {
+    static DICTIONARIES: OnceLock<Vec<(&'static str, HashMap<&'static str, usize>)>> = OnceLock::new();
+    DICTIONARIES.get_or_init(|| {
+        [("passwords", PASSWORDS), ("english", ENGLISH), ("names", NAMES)]
+            .into_iter()
+            .map(|(name, words)| {
+                let mut ranks = HashMap::new();
+                for word in words.split_whitespace() {
+                    let rank = ranks.len() + 1;
+                    ranks.entry(word).or_insert(rank);
+                }
+                (name, ranks)
+            })
+            .collect()
+    })
+}
+
+fn reference_year() -> i32 {
+    chrono::Local::now().year()
+}
+
+pub fn analyze(password: &str) -> Analysis {
+    let chars: Vec<char> = password.chars().collect();
+    let (guesses, sequence) = most_guessable(&chars, find_matches(&chars));
+    Analysis { guesses, score: score(guesses), sequence }
+}
+
+// zxcvbn's thresholds, with a little slack so exact powers of ten round down
+fn score(guesses: f64) -> u8 {
+    const DELTA: f64 = 5.0;
+    match guesses {
+        g if g < 1e3 + DELTA => 0,
+        g if g < 1e6 + DELTA => 1,
+        g if g < 1e8 + DELTA => 2,
+        g if g < 1e10 + DELTA => 3,
+        _ => 4,
+    }
+}
+
+fn find_matches(chars: &[char]) -> Vec<Match> {
+    let mut matches = dictionary_matches(chars);
+    matches.extend(spatial_matches(chars));
+    matches.extend(repeat_matches(chars));
+    matches.extend(sequence_matches(chars));
+    matches.extend(date_matches(chars));
+    matches
+}
+
+fn make_match(chars: &[char], start: usize, end: usize, pattern: Pattern) -> Match {
+    let token: String = chars[start..=end].iter().collect();
+    let guesses = estimate_guesses(&pattern, &token, chars.len());
+    Match { start, end, token, pattern, guesses }
+}
+
+fn lowercase(chars: &[char]) -> Vec<char> {
+    chars.iter().map(|c| c.to_lowercase().next().unwrap_or(*c)).collect()
+}
+
+// (start, end, dictionary, word, rank) for every dictionary word in `lower`
+fn find_words(lower: &[char]) -> Vec<(usize, usize, &'static str, &'static str, usize)> {
+    let mut found = Vec::new();
+    for start in 0..lower.len() {
+        let mut candidate = String::new();
+        for (offset, &c) in lower[start..].iter().enumerate() {
+            let end = start + offset;
+            candidate.push(c);
+            for (name, ranks) in dictionaries() {
+                if let Some((word, rank)) = ranks.get_key_value(candidate.as_str()) {
+                    found.push((start, end, *name, *word, *rank));
+                }
+            }
+        }
+    }
+    found
+}
+
+fn dictionary_matches(chars: &[char]) -> Vec<Match> {
+    let lower = lowercase(chars);
+    let length = chars.len();
+    let mut matches = Vec::new();
+    let dictionary = |word: &str, name, rank, reversed, l33t| Pattern::Dictionary { word: word.to_string(), dictionary: name, rank, reversed, l33t };
+    for (start, end, name, word, rank) in find_words(&lower) {
+        matches.push(make_match(chars, start, end, dictionary(word, name, rank, false, Vec::new())));
+    }
+    let reversed: Vec<char> = lower.iter().rev().copied().collect();
+    for (start, end, name, word, rank) in find_words(&reversed) {
+        // Palindromes were already found forwards
+        if word.chars().rev().collect::<String>() != word {
+            matches.push(make_match(chars, length - 1 - end, length - 1 - start, dictionary(word, name, rank, true, Vec::new())));
+        }
+    }
+    let mut seen = Vec::new();
+    for substituted in l33t_variants(&lower) {
+        for (start, end, name, word, rank) in find_words(&substituted) {
+            let mut subs: Vec<(char, char)> = (start..=end).filter(|&i| lower[i] != substituted[i]).map(|i| (lower[i], substituted[i])).collect();
+            subs.sort_unstable();
+            subs.dedup();
+            // A lone symbol standing for a letter is not worth reporting
+            if subs.is_empty() || start == end || seen.contains(&(start, end, word)) {
+                continue;
+            }
+            seen.push((start, end, word));
+            matches.push(make_match(chars, start, end, dictionary(word, name, rank, false, subs)));
+        }
+    }
+    matches
+}
+
+// `lower` with its l33t symbols swapped for letters, one variant per choice of letters
+fn l33t_variants(lower: &[char]) -> Vec<Vec<char>> {
+    let mut variants = vec![lower.to_vec()];
+    let mut symbols: Vec<char> = lower.iter().copied().filter(|c| L33T_TABLE.iter().any(|(symbol, _)| symbol == c)).collect();
+    symbols.sort_unstable();
+    symbols.dedup();
+    for symbol in symbols {
+        let letters = L33T_TABLE.iter().find(|(candidate, _)| *candidate == symbol).map_or("", |(_, letters)| letters);
+        variants = variants
+            .iter()
+            .flat_map(|variant| {
+                letters.chars().map(move |letter| variant.iter().map(|&c| if c == symbol { letter } else { c }).collect::<Vec<char>>())
+            })
+            .take(MAX_L33T_VARIANTS)
+            .collect();
+    }
+    if variants.len() == 1 && variants[0] == lower {
+        return Vec::new();
+    }
+    variants
+}
+
+// (row, horizontal position, shifted) of a character on the keyboard
+fn key_position(c: char) -> Option<(usize, f64, bool)> {
+    KEYBOARD_ROWS.iter().enumerate().find_map(|(row, (plain, shifted, offset))| {
+        plain
+            .chars()
+            .position(|key| key == c)
+            .map(|column| (row, offset + column as f64, false))
+            .or_else(|| shifted.chars().position(|key| key == c).map(|column| (row, offset + column as f64, true)))
+    })
+}
+
+// The direction from one key to a neighbouring one, or None if they are not adjacent
+fn key_direction(from: char, to: char) -> Option<(i32, i32)> {
+    let ((from_row, from_x, _), (to_row, to_x, _)) = (key_position(from)?, key_position(to)?);
+    let rows = to_row as i32 - from_row as i32;
+    let dx = to_x - from_x;
+    let adjacent = match rows {
+        0 => (dx.abs() - 1.0).abs() < 1e-9,
+        -1 | 1 => dx.abs() <= 0.75,
+        _ => false,
+    };
+    adjacent.then(|| (rows, (dx * 4.0).round() as i32))
+}
+
+// Keys on the keyboard and the average number of neighbours each has
+fn keyboard_stats() -> (f64, f64) {
+    let keys: Vec<char> = KEYBOARD_ROWS.iter().flat_map(|(plain, _, _)| plain.chars()).collect();
+    let neighbours: usize = keys.iter().map(|&from| keys.iter().filter(|&&to| key_direction(from, to).is_some()).count()).sum();
+    // Shifted and unshifted characters can both start a pattern
+    ((keys.len() * 2) as f64, neighbours as f64 / keys.len() as f64)
+}
+
+fn spatial_matches(chars: &[char]) -> Vec<Match> {
+    let mut matches = Vec::new();
+    let mut start = 0;
+    while start + 1 < chars.len() {
+        let mut end = start;
+        let mut turns = 0;
+        let mut direction = None;
+        while end + 1 < chars.len() {
+            match key_direction(chars[end], chars[end + 1]) {
+                Some(next) => {
+                    if direction != Some(next) {
+                        turns += 1;
+                        direction = Some(next);
+                    }
+                    end += 1;
+                }
+                None => break,
+            }
+        }
+        if end - start >= 2 {
+            let shifted = chars[start..=end].iter().filter(|&&c| key_position(c).is_some_and(|(_, _, shifted)| shifted)).count();
+            matches.push(make_match(chars, start, end, Pattern::Spatial { turns, shifted }));
+        }
+        start = if end > start { end } else { start + 1 };
+    }
+    matches
+}
+
+// Runs of a repeated unit, the longest at each position, e.g. "aaaa" or "abcabc"
+fn repeat_matches(chars: &[char]) -> Vec<Match> {
+    let mut matches = Vec::new();
+    let mut start = 0;
+    while start < chars.len() {
+        let mut best: Option<(usize, usize)> = None;
+        for unit in 1..=(chars.len() - start) / 2 {
+            let mut count = 1;
+            while start + (count + 1) * unit <= chars.len()
+                && chars[start + count * unit..start + (count + 1) * unit] == chars[start..start + unit]
+            {
+                count += 1;
+            }
+            if count >= 2 && best.is_none_or(|(best_unit, best_count)| unit * count > best_unit * best_count) {
+                best = Some((unit, count));
+            }
+        }
+        match best {
+            Some((unit, count)) => {
+                let end = start + unit * count - 1;
+                let base: String = chars[start..start + unit].iter().collect();
+                matches.push(make_match(chars, start, end, Pattern::Repeat { base, count }));
+                start = end + 1;
+            }
+            None => start += 1,
+        }
+    }
+    matches
+}
+
+// Evenly spaced runs of letters or digits, like "abc", "9753" or "XYZ"
+fn sequence_matches(chars: &[char]) -> Vec<Match> {
+    let class = |c: char| match c {
+        'a'..='z' => Some(0),
+        'A'..='Z' => Some(1),
+        '0'..='9' => Some(2),
+        _ => None,
+    };
+    let step = |i: usize| chars[i + 1] as i64 - chars[i] as i64;
+    let mut matches = Vec::new();
+    let mut start = 0;
+    while start + 2 < chars.len() {
+        let delta = step(start);
+        let same_class = |i: usize| class(chars[i]).is_some() && class(chars[i]) == class(chars[start]);
+        if (1..=5).contains(&delta.abs()) && same_class(start + 1) {
+            let mut end = start + 1;
+            while end + 1 < chars.len() && step(end) == delta && same_class(end + 1) {
+                end += 1;
+            }
+            if end - start >= 2 {
+                matches.push(make_match(chars, start, end, Pattern::Sequence { ascending: delta > 0 }));
+                start = end;
+                continue;
+            }
+        }
+        start += 1;
+    }
+    matches
+}
+
+// Two-digit years as people write them: 87 is 1987, 12 is 2012
+fn expand_year(digits: &str) -> Option<i32> {
+    let year: i32 = digits.parse().ok()?;
+    match digits.len() {
+        1 | 2 if year > 50 => Some(1900 + year),
+        1 | 2 => Some(2000 + year),
+        4 if (1000..=2050).contains(&year) => Some(year),
+        _ => None,
+    }
+}
+
+// The year of the most likely reading of three numbers as day, month and year in any common order
+fn read_date(parts: [&str; 3], reference: i32) -> Option<i32> {
+    let valid = |day: &str, month: &str| {
+        let (day, month) = (day.parse::<u32>().ok()?, month.parse::<u32>().ok()?);
+        ((1..=31).contains(&day) && (1..=12).contains(&month) && parts.iter().all(|part| part.len() <= 4)).then_some(())
+    };
+    let [first, second, third] = parts;
+    let mut years = Vec::new();
+    for (year, a, b) in [(third, first, second), (first, second, third)] {
+        if let Some(year) = expand_year(year) {
+            if valid(a, b).is_some() || valid(b, a).is_some() {
+                years.push(year);
+            }
+        }
+    }
+    years.into_iter().min_by_key(|year| (year - reference).abs())
+}
+
+fn date_matches(chars: &[char]) -> Vec<Match> {
+    static SEPARATED: OnceLock<Regex> = OnceLock::new();
+    let separated = SEPARATED.get_or_init(|| Regex::new(r"^(\d{1,4})([\s/\\_.-])(\d{1,2})([\s/\\_.-])(\d{1,4})$").unwrap());
+    // Where each split cuts a digit string into day, month and year
+    const SPLITS: [&[(usize, usize)]; 5] = [
+        &[(1, 2), (2, 3)],
+        &[(1, 3), (2, 3)],
+        &[(1, 2), (2, 4), (4, 5)],
+        &[(1, 3), (2, 3), (4, 5), (4, 6)],
+        &[(2, 4), (4, 6)],
+    ];
+    let reference = reference_year();
+    let mut matches = Vec::new();
+    for start in 0..chars.len() {
+        for end in start..chars.len().min(start + 10) {
+            let token: String = chars[start..=end].iter().collect();
+            let length = end - start + 1;
+            if token.bytes().all(|byte| byte.is_ascii_digit()) {
+                if length == 4 {
+                    if let Ok(year @ 1900..=2099) = token.parse::<i32>() {
+                        matches.push(make_match(chars, start, end, Pattern::Year { year }));
+                    }
+                }
+                if (4..=8).contains(&length) {
+                    let year = SPLITS[length - 4]
+                        .iter()
+                        .filter_map(|&(a, b)| read_date([&token[..a], &token[a..b], &token[b..]], reference))
+                        .min_by_key(|year| (year - reference).abs());
+                    if let Some(year) = year {
+                        matches.push(make_match(chars, start, end, Pattern::Date { year, separator: false }));
+                    }
+                }
+            } else if (6..=10).contains(&length) {
+                if let Some(captures) = separated.captures(&token) {
+                    if captures[2] == captures[4] {
+                        if let Some(year) = read_date([&captures[1], &captures[3], &captures[5]], reference) {
+                            matches.push(make_match(chars, start, end, Pattern::Date { year, separator: true }));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    matches
+}
+
+fn binomial(n: usize, k: usize) -> f64 {
+    if k > n {
+        return 0.0;
+    }
+    (1..=k).fold(1.0, |result, i| result * (n - k + i) as f64 / i as f64)
+}
+
+fn factorial(n: usize) -> f64 {
+    (2..=n).fold(1.0, |result, i| result * i as f64)
+}
+
+// Ways to mix `a` of one kind with `b` of another, as in zxcvbn's case and l33t variations
+fn variations(a: usize, b: usize) -> f64 {
+    if a == 0 || b == 0 {
+        return 2.0;
+    }
+    (1..=a.min(b)).map(|i| binomial(a + b, i)).sum()
+}
+
+fn uppercase_variations(token: &str) -> f64 {
+    let upper = token.chars().filter(|c| c.is_uppercase()).count();
+    let lower = token.chars().filter(|c| c.is_lowercase()).count();
+    if upper == 0 {
+        return 1.0;
+    }
+    let first_only = token.chars().next().is_some_and(char::is_uppercase) && upper == 1;
+    let last_only = token.chars().last().is_some_and(char::is_uppercase) && upper == 1;
+    if first_only || last_only || lower == 0 {
+        return 2.0;
+    }
+    variations(upper, lower)
+}
+
+fn l33t_variations(token: &str, subs: &[(char, char)]) -> f64 {
+    let lower = token.to_lowercase();
+    subs.iter()
+        .map(|&(symbol, letter)| variations(lower.chars().filter(|&c| c == symbol).count(), lower.chars().filter(|&c| c == letter).count()))
+        .product()
+}
+
+fn estimate_guesses(pattern: &Pattern, token: &str, password_length: usize) -> f64 {
+    let length = token.chars().count();
+    let guesses = match pattern {
+        Pattern::BruteForce => {
+            let least = if length == 1 { MIN_SUBMATCH_GUESSES_SINGLE_CHAR } else { MIN_SUBMATCH_GUESSES_MULTI_CHAR } + 1.0;
+            BRUTEFORCE_CARDINALITY.powi(length as i32).max(least)
+        }
+        Pattern::Dictionary { rank, reversed, l33t, .. } => {
+            let reversed = if *reversed { 2.0 } else { 1.0 };
+            *rank as f64 * uppercase_variations(token) * l33t_variations(token, l33t) * reversed
+        }
+        Pattern::Spatial { turns, shifted } => {
+            let (starts, degree) = keyboard_stats();
+            let mut guesses = 0.0;
+            for i in 2..=length {
+                for j in 1..=(*turns).min(i - 1) {
+                    guesses += binomial(i - 1, j - 1) * starts * degree.powi(j as i32);
+                }
+            }
+            if *shifted > 0 {
+                guesses *= variations(*shifted, length - shifted);
+            }
+            guesses
+        }
+        Pattern::Repeat { base, count } => {
+            let base: Vec<char> = base.chars().collect();
+            most_guessable(&base, find_matches(&base)).0 * *count as f64
+        }
+        Pattern::Sequence { ascending } => {
+            let first = token.chars().next().unwrap_or_default();
+            let base = match first {
+                'a' | 'A' | 'z' | 'Z' | '0' | '1' | '9' => 4.0,
+                c if c.is_ascii_digit() => 10.0,
+                _ => 26.0,
+            };
+            let direction = if *ascending { 1.0 } else { 2.0 };
+            base * direction * length as f64
+        }
+        Pattern::Year { year } => (year - reference_year()).abs().max(MIN_YEAR_SPACE) as f64,
+        Pattern::Date { year, separator } => {
+            let separator = if *separator { 4.0 } else { 1.0 };
+            (year - reference_year()).abs().max(MIN_YEAR_SPACE) as f64 * 365.0 * separator
+        }
+    };
+    // Parts of a longer password are never credited with fewer than a handful of guesses
+    let least = match length {
+        _ if length >= password_length => 1.0,
+        1 => MIN_SUBMATCH_GUESSES_SINGLE_CHAR,
+        _ => MIN_SUBMATCH_GUESSES_MULTI_CHAR,
+    };
+    guesses.max(least)
+}
+
+fn extend(best: &mut [HashMap<usize, Step>], found: Match, count: usize) {
+    let end = found.end;
+    let mut product = found.guesses;
+    if count > 1 {
+        product *= best[found.start - 1][&(count - 1)].product;
+    }
+    // An attacker tries sequences of fewer patterns first, in any order
+    let total = factorial(count) * product + MIN_GUESSES_BEFORE_GROWING_SEQUENCE.powi(count as i32 - 1);
+    if best[end].iter().any(|(&other, step)| other <= count && step.total <= total) {
+        return;
+    }
+    best[end].insert(count, Step { found, product, total });
+}
+
+// The fewest guesses over every way of covering the password with matches and brute force
+fn most_guessable(chars: &[char], matches: Vec<Match>) -> (f64, Vec<Match>) {
+    let length = chars.len();
+    if length == 0 {
+        return (1.0, Vec::new());
+    }
+    let mut by_end: Vec<Vec<Match>> = vec![Vec::new(); length];
+    for found in matches {
+        by_end[found.end].push(found);
+    }
+    // best[k][n] is the cheapest sequence of n matches covering characters 0..=k
+    let mut best: Vec<HashMap<usize, Step>> = (0..length).map(|_| HashMap::new()).collect();
+    for (end, ending) in by_end.into_iter().enumerate() {
+        for found in ending {
+            if found.start == 0 {
+                extend(&mut best, found, 1);
+            } else {
+                let counts: Vec<usize> = best[found.start - 1].keys().copied().collect();
+                for count in counts {
+                    extend(&mut best, found.clone(), count + 1);
+                }
+            }
+        }
+        extend(&mut best, make_match(chars, 0, end, Pattern::BruteForce), 1);
+        for start in 1..=end {
+            // Two brute-force runs in a row are just one longer run
+            let counts: Vec<usize> =
+                best[start - 1].iter().filter(|(_, step)| step.found.pattern != Pattern::BruteForce).map(|(&count, _)| count).collect();
+            for count in counts {
+                extend(&mut best, make_match(chars, start, end, Pattern::BruteForce), count + 1);
+            }
+        }
+    }
+    let (mut count, last) = best[length - 1].iter().min_by(|a, b| a.1.total.total_cmp(&b.1.total)).map(|(&count, step)| (count, step)).unwrap();
+    let guesses = last.total;
+    let mut sequence = vec![last.found.clone()];
+    while sequence[0].start > 0 {
+        count -= 1;
+        sequence.insert(0, best[sequence[0].start - 1][&count].found.clone());
+    }
+    (guesses, sequence)
+}
+
+pub fn feedback(analysis: &Analysis) -> Feedback {
+    let Some(longest) = analysis.sequence.iter().max_by_key(|found| found.token.chars().count()) else {
+        return Feedback {
+            warning: None,
+            suggestions: vec!["Use a few words and avoid common phrases".to_string(), "No need for symbols, digits or uppercase letters".to_string()],
+        };
+    };
+    if analysis.score > 2 {
+        return Feedback { warning: None, suggestions: Vec::new() };
+    }
+    let sole = analysis.sequence.len() == 1;
+    let mut suggestions = vec!["Add another word or two; uncommon words are better".to_string()];
+    let warning = match &longest.pattern {
+        Pattern::Dictionary { dictionary, rank, reversed, l33t, .. } => {
+            let warning = match *dictionary {
+                "passwords" if sole && !reversed && l33t.is_empty() => Some(match *rank {
+                    0..=10 => "This is a top-10 common password",
+                    11..=100 => "This is a top-100 common password",
+                    _ => "This is a very common password",
+                }),
+                "passwords" if longest.guesses.log10() <= 4.0 => Some("This is similar to a commonly used password"),
+                "english" if sole => Some("A word by itself is easy to guess"),
+                "names" if sole => Some("Names and surnames by themselves are easy to guess"),
+                "names" => Some("Common names and surnames are easy to guess"),
+                _ => None,
+            };
+            let token = &longest.token;
+            let mut rest = token.chars().skip(1);
+            if token.chars().next().is_some_and(char::is_uppercase) && rest.all(|c| !c.is_uppercase()) {
+                suggestions.push("Capitalization doesn't help very much".to_string());
+            } else if token.chars().any(char::is_uppercase) && !token.chars().any(char::is_lowercase) {
+                suggestions.push("All-uppercase is almost as easy to guess as all-lowercase".to_string());
+            }
+            if *reversed && token.chars().count() >= 4 {
+                suggestions.push("Reversed words aren't much harder to guess".to_string());
+            }
+            if !l33t.is_empty() {
+                suggestions.push("Predictable substitutions like '@' instead of 'a' don't help very much".to_string());
+            }
+            warning.map(str::to_string)
+        }
+        Pattern::Spatial { turns, .. } => {
+            suggestions.push("Use a longer keyboard pattern with more turns".to_string());
+            Some(if *turns == 1 { "Straight rows of keys are easy to guess" } else { "Short keyboard patterns are easy to guess" }.to_string())
+        }
+        Pattern::Repeat { base, .. } => {
+            suggestions.push("Avoid repeated words and characters".to_string());
+            Some(if base.chars().count() == 1 {
+                "Repeats like \"aaa\" are easy to guess"
+            } else {
+                "Repeats like \"abcabcabc\" are only slightly harder to guess than \"abc\""
+            }
+            .to_string())
+        }
+        Pattern::Sequence { .. } => {
+            suggestions.push("Avoid sequences".to_string());
+            Some("Sequences like abc or 6543 are easy to guess".to_string())
+        }
+        Pattern::Year { .. } => {
+            suggestions.push("Avoid recent years and years that are associated with you".to_string());
+            Some("Recent years are easy to guess".to_string())
+        }
+        Pattern::Date { .. } => {
+            suggestions.push("Avoid dates and years that are associated with you".to_string());
+            Some("Dates are often easy to guess".to_string())
+        }
+        Pattern::BruteForce => None,
+    };
+    Feedback { warning, suggestions }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns(password: &str) -> Vec<Pattern> {
+        analyze(password).sequence.into_iter().map(|found| found.pattern).collect()
+    }
+
+    #[test]
+    fn test_common_passwords() {
+        let analysis = analyze("password");
+        assert_eq!(analysis.score, 0);
+        assert_eq!(analysis.guesses, 3.0);
+        assert_eq!(feedback(&analysis).warning.as_deref(), Some("This is a top-10 common password"));
+        // Substitutions and capitals barely help
+        let analysis = analyze("P@ssw0rd");
+        assert_eq!(analysis.score, 0);
+        assert!(matches!(&analysis.sequence[0].pattern, Pattern::Dictionary { word, l33t, .. } if word == "password" && l33t == &[('0', 'o'), ('@', 'a')]));
+        assert!(matches!(&patterns("drowssap")[0], Pattern::Dictionary { reversed: true, .. }));
+    }
+
+    #[test]
+    fn test_patterns() {
+        assert_eq!(patterns("kjhgfd"), [Pattern::Spatial { turns: 1, shifted: 0 }]);
+        assert_eq!(spatial_matches(&"qwedsa".chars().collect::<Vec<char>>())[0].pattern, Pattern::Spatial { turns: 3, shifted: 0 });
+        assert_eq!(patterns("zzzzzzzz"), [Pattern::Repeat { base: "z".to_string(), count: 8 }]);
+        assert_eq!(patterns("lmnopq"), [Pattern::Sequence { ascending: true }]);
+        assert_eq!(patterns("1987"), [Pattern::Year { year: 1987 }]);
+        assert_eq!(patterns("13/05/1990"), [Pattern::Date { year: 1990, separator: true }]);
+        assert_eq!(patterns("19900513"), [Pattern::Date { year: 1990, separator: false }]);
+    }
+
+    #[test]
+    fn test_scores() {
+        assert!(analyze("kjhgfd").score <= 1);
+        assert!(analyze("lmnopq").score <= 1);
+        assert_eq!(analyze("correct horse battery staple").score, 4);
+        assert_eq!(analyze("Xk9#mQ2$vL7@pR4!").score, 4);
+        // Longer is stronger, even with the same pattern
+        assert!(analyze("zzzzzzzzzzzz").guesses > analyze("zzzz").guesses);
+        assert_eq!(analyze("").guesses, 1.0);
+    }
+
+    #[test]
+    fn test_guess_variations() {
+        assert_eq!(uppercase_variations("password"), 1.0);
+        assert_eq!(uppercase_variations("Password"), 2.0);
+        assert_eq!(uppercase_variations("PASSWORD"), 2.0);
+        // Two capitals among six letters: 6 + 15 ways
+        assert_eq!(uppercase_variations("PaSswo"), 21.0);
+        assert_eq!(l33t_variations("p4ss", &[('4', 'a')]), 2.0);
+        assert_eq!(l33t_variations("4a", &[('4', 'a')]), 2.0);
+    }
+}
@@ -80,7 +80,7 @@ pub fn generate_secure_password(length: usize) -> String {
 
 pub fn generate_base64_password(length: usize) -> String {
     // Generate random bytes and encode as base64, then trim to desired length
-    let byte_count = (length * 3 + 3) / 4; // Calculate bytes needed for base64 encoding
+    let byte_count = (length * 3).div_ceil(4); // Calculate bytes needed for base64 encoding
     let mut rng = rand::thread_rng();
 
     let random_bytes: Vec<u8> = (0..byte_count).map(|_| rng.gen()).collect();
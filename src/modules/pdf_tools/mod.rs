@@ -0,0 +1,287 @@
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use lopdf::{decode_text_string, Document, Object};
+use std::error::Error;
+use std::io::{self, IsTerminal};
+
+pub struct PdfToolsModule;
+
+// Document information keys, in display order
+const INFO_KEYS: [(&str, &str); 8] = [
+    ("Title", "Title"),
+    ("Author", "Author"),
+    ("Subject", "Subject"),
+    ("Keywords", "Keywords"),
+    ("Creator", "Creator"),
+    ("Producer", "Producer"),
+    ("CreationDate", "Created"),
+    ("ModDate", "Modified"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Encryption {
+    None,
+    // Encrypted with an empty user password, so it opens without one
+    OpenWithoutPassword,
+    PasswordRequired,
+}
+
+impl ToolModule for PdfToolsModule {
+    fn name(&self) -> &'static str {
+        "pdf-tools"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("pdf-text")
+                .long("pdf-text")
+                .value_name("FILE")
+                .help("Extract the plain text of a PDF, optionally only --pages")
+        )
+        .arg(
+            Arg::new("pages")
+                .long("pages")
+                .value_name("RANGES")
+                .help("Pages for --pdf-text, e.g. 1-5, 3,7-9 or 10- (default: all)")
+                .requires("pdf-text")
+        )
+        .arg(
+            Arg::new("pdf-info")
+                .long("pdf-info")
+                .value_name("FILE")
+                .help("Show a PDF's page count, version, title, author and other metadata, and whether it is encrypted")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(path) = matches.get_one::<String>("pdf-text") {
+            let (document, encryption) = open(path)?;
+            if encryption == Encryption::PasswordRequired {
+                return Err(format!("'{}' is encrypted and needs a password to read its text", path).into());
+            }
+            let page_count = document.get_pages().len() as u32;
+            let pages = match matches.get_one::<String>("pages") {
+                Some(spec) => parse_pages(spec, page_count)?,
+                None => (1..=page_count).collect(),
+            };
+            print!("{}", extract_text(&document, &pages)?);
+        }
+
+        if let Some(path) = matches.get_one::<String>("pdf-info") {
+            let (document, encryption) = open(path)?;
+            print!("{}", render_info(&document, encryption, io::stdout().is_terminal()));
+        }
+        Ok(())
+    }
+}
+
+fn open(path: &str) -> Result<(Document, Encryption), Box<dyn Error>> {
+    let mut document = Document::load(path).map_err(|e| format!("Failed to open '{}' as a PDF: {}", path, e))?;
+    let encryption = if !document.is_encrypted() {
+        Encryption::None
+    } else if document.decrypt("").is_ok() {
+        Encryption::OpenWithoutPassword
+    } else {
+        Encryption::PasswordRequired
+    };
+    Ok((document, encryption))
+}
+
+// "1-5", "3,7-9" or "10-" into page numbers, checked against the page count
+fn parse_pages(spec: &str, page_count: u32) -> Result<Vec<u32>, String> {
+    let mut pages = Vec::new();
+    for part in spec.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+        let parse = |number: &str| number.trim().parse::<u32>().map_err(|_| format!("Invalid page range '{}'", part));
+        let (first, last) = match part.split_once('-') {
+            Some((first, "")) => (parse(first)?, page_count),
+            Some((first, last)) => (parse(first)?, parse(last)?),
+            None => (parse(part)?, parse(part)?),
+        };
+        if first == 0 || first > last {
+            return Err(format!("Invalid page range '{}'", part));
+        }
+        if last > page_count {
+            return Err(format!("Page {} is out of range; the document has {} pages", last, page_count));
+        }
+        pages.extend(first..=last);
+    }
+    if pages.is_empty() {
+        return Err("No pages given".to_string());
+    }
+    Ok(pages)
+}
+
+// Pages are extracted one at a time so a page lopdf cannot read is reported
+// and skipped instead of losing the whole document
+fn extract_text(document: &Document, pages: &[u32]) -> Result<String, Box<dyn Error>> {
+    let mut output = String::new();
+    let mut failed = 0;
+    for &page in pages {
+        match document.extract_text(&[page]) {
+            Ok(text) => {
+                output.push_str(text.trim_end());
+                output.push('\n');
+            }
+            Err(e) => {
+                eprintln!("⚠️ Could not extract text from page {}: {}", page, e);
+                failed += 1;
+            }
+        }
+    }
+    if failed == pages.len() {
+        return Err("No text could be extracted; the PDF may contain only scanned images".into());
+    }
+    Ok(output)
+}
+
+fn info_value(document: &Document, key: &str) -> Option<String> {
+    let info = document.trailer.get(b"Info").ok()?;
+    let (_, info) = document.dereference(info).ok()?;
+    let value = info.as_dict().ok()?.get(key.as_bytes()).ok()?;
+    let (_, value) = document.dereference(value).ok()?;
+    let text = match value {
+        Object::String(..) => decode_text_string(value).ok()?,
+        Object::Name(name) => String::from_utf8_lossy(name).to_string(),
+        _ => return None,
+    };
+    let text = text.trim();
+    if text.is_empty() {
+        None
+    } else if key.ends_with("Date") {
+        Some(format_pdf_date(text))
+    } else {
+        Some(text.to_string())
+    }
+}
+
+// D:YYYYMMDDHHmmSSOHH'mm' to "YYYY-MM-DD HH:mm:SS +HH:mm"; anything that does
+// not look like a PDF date is shown as is
+fn format_pdf_date(date: &str) -> String {
+    let digits = date.strip_prefix("D:").unwrap_or(date);
+    let numeric: String = digits.chars().take_while(char::is_ascii_digit).collect();
+    if numeric.len() < 8 {
+        return date.to_string();
+    }
+    let field = |start: usize, default: &'static str| numeric.get(start..start + 2).unwrap_or(default).to_string();
+    let mut formatted = format!("{}-{}-{}", &numeric[0..4], &numeric[4..6], &numeric[6..8]);
+    if numeric.len() >= 12 {
+        formatted.push_str(&format!(" {}:{}:{}", field(8, "00"), field(10, "00"), field(12, "00")));
+    }
+    let zone = &digits[numeric.len()..];
+    match zone.chars().next() {
+        Some('Z') => formatted.push_str(" UTC"),
+        Some(sign @ ('+' | '-')) => {
+            let offset: String = zone[1..].chars().filter(char::is_ascii_digit).collect();
+            if offset.len() >= 2 {
+                formatted.push_str(&format!(" {}{}:{}", sign, &offset[0..2], offset.get(2..4).unwrap_or("00")));
+            }
+        }
+        _ => {}
+    }
+    formatted
+}
+
+fn render_info(document: &Document, encryption: Encryption, color: bool) -> String {
+    let label = |text: &str| {
+        let padded = format!("{:<10}", text);
+        if color { padded.cyan().bold().to_string() } else { padded }
+    };
+    let mut output = format!("{} {}\n", label("Pages"), document.get_pages().len());
+    output.push_str(&format!("{} {}\n", label("Version"), document.version));
+    if encryption != Encryption::PasswordRequired {
+        for (key, name) in INFO_KEYS {
+            if let Some(value) = info_value(document, key) {
+                output.push_str(&format!("{} {}\n", label(name), value));
+            }
+        }
+    }
+    let encrypted = match encryption {
+        Encryption::None => "no",
+        Encryption::OpenWithoutPassword => "yes (opens without a password)",
+        Encryption::PasswordRequired => "yes (password required)",
+    };
+    output.push_str(&format!("{} {}\n", label("Encrypted"), encrypted));
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::content::{Content, Operation};
+    use lopdf::{dictionary, text_string, Stream};
+
+    // A minimal PDF with one line of Helvetica text per page
+    fn document(pages: &[&str]) -> Document {
+        let mut document = Document::with_version("1.5");
+        let pages_id = document.new_object_id();
+        let font_id = document.add_object(dictionary! { "Type" => "Font", "Subtype" => "Type1", "BaseFont" => "Helvetica" });
+        let resources_id = document.add_object(dictionary! { "Font" => dictionary! { "F1" => font_id } });
+        let kids: Vec<Object> = pages
+            .iter()
+            .map(|text| {
+                let content = Content {
+                    operations: vec![
+                        Operation::new("BT", vec![]),
+                        Operation::new("Tf", vec!["F1".into(), 12.into()]),
+                        Operation::new("Td", vec![72.into(), 720.into()]),
+                        Operation::new("Tj", vec![Object::string_literal(*text)]),
+                        Operation::new("ET", vec![]),
+                    ],
+                };
+                let content_id = document.add_object(Stream::new(dictionary! {}, content.encode().unwrap()));
+                document.add_object(dictionary! { "Type" => "Page", "Parent" => pages_id, "Contents" => content_id }).into()
+            })
+            .collect();
+        let count = kids.len() as i64;
+        document.objects.insert(pages_id, Object::Dictionary(dictionary! {
+            "Type" => "Pages",
+            "Kids" => kids,
+            "Count" => count,
+            "Resources" => resources_id,
+            "MediaBox" => vec![0.into(), 0.into(), 612.into(), 792.into()],
+        }));
+        let catalog_id = document.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+        let info_id = document.add_object(dictionary! {
+            "Title" => text_string("Quarterly Report"),
+            "Author" => Object::string_literal("Jane Doe"),
+            "CreationDate" => Object::string_literal("D:20240115103000+01'00'"),
+        });
+        document.trailer.set("Root", catalog_id);
+        document.trailer.set("Info", info_id);
+        let mut buffer = Vec::new();
+        document.save_to(&mut buffer).unwrap();
+        Document::load_mem(&buffer).unwrap()
+    }
+
+    #[test]
+    fn test_parse_pages() {
+        assert_eq!(parse_pages("1-3", 10).unwrap(), vec![1, 2, 3]);
+        assert_eq!(parse_pages("2, 5,8-", 9).unwrap(), vec![2, 5, 8, 9]);
+        assert!(parse_pages("0-2", 10).is_err());
+        assert!(parse_pages("4-2", 10).is_err());
+        assert!(parse_pages("1-11", 10).is_err());
+        assert!(parse_pages("a", 10).is_err());
+    }
+
+    #[test]
+    fn test_format_pdf_date() {
+        assert_eq!(format_pdf_date("D:20240115103000+01'00'"), "2024-01-15 10:30:00 +01:00");
+        assert_eq!(format_pdf_date("D:20231231235959Z"), "2023-12-31 23:59:59 UTC");
+        assert_eq!(format_pdf_date("D:20240115"), "2024-01-15");
+        assert_eq!(format_pdf_date("yesterday"), "yesterday");
+    }
+
+    #[test]
+    fn test_extract_text_and_info() {
+        let document = document(&["Hello contract", "Second page", "Third page"]);
+        let text = extract_text(&document, &parse_pages("2-3", 3).unwrap()).unwrap();
+        assert!(!text.contains("Hello contract"));
+        assert!(text.contains("Second page") && text.contains("Third page"));
+
+        let info = render_info(&document, Encryption::None, false);
+        assert!(info.starts_with("Pages      3\nVersion    1.5\nTitle      Quarterly Report\nAuthor     Jane Doe\n"));
+        assert!(info.contains("Created    2024-01-15 10:30:00 +01:00\n"));
+        assert!(info.ends_with("Encrypted  no\n"));
+    }
+}
@@ -0,0 +1,320 @@
+use crate::tool_module::{ExitCodeError, ToolModule};
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use socket2::{Domain, Protocol, SockAddr, Socket, Type};
+use std::error::Error;
+use std::io::{ErrorKind, Read};
+use std::net::{IpAddr, SocketAddr, TcpStream, ToSocketAddrs};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub struct PingModule;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2);
+const INTERVAL: Duration = Duration::from_secs(1);
+// Port tried by the TCP fallback when none is given
+const DEFAULT_TCP_PORT: u16 = 443;
+const PAYLOAD: &[u8] = b"micro-swiss ping";
+
+impl ToolModule for PingModule {
+    fn name(&self) -> &'static str {
+        "ping"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("ping")
+                .long("ping")
+                .value_name("HOST")
+                .help("Ping a host and report min/avg/max latency, jitter and packet loss")
+                .long_help("Send ICMP echo requests to HOST once a second and report each round trip, then min/avg/max latency, jitter and packet loss. When ICMP is not permitted (no raw socket access and unprivileged ICMP disabled), falls back to timing TCP connections to port 443, or to the port given as HOST:PORT. The per-probe timeout is 2 seconds; change it with --timeout.")
+        )
+        .arg(
+            Arg::new("count")
+                .long("count")
+                .value_name("N")
+                .help("Number of probes sent by --ping (default: 5), or colors extracted by --img-palette (default: 6)")
+                .value_parser(clap::value_parser!(u32).range(1..))
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(target) = matches.get_one::<String>("ping") {
+            let count = matches.get_one::<u32>("count").copied().unwrap_or(5);
+            // --timeout is declared by the run-file module
+            let timeout = match matches.get_one::<f64>("timeout") {
+                Some(seconds) if *seconds > 0.0 => Duration::from_secs_f64(*seconds),
+                Some(_) => return Err("--timeout must be greater than 0".into()),
+                None => DEFAULT_TIMEOUT,
+            };
+            let (host, port) = split_target(target);
+            let ip = resolve(&host)?;
+
+            // An explicit port means the caller wants TCP timing
+            let (mut prober, note) = match port {
+                Some(port) => (Prober::tcp(SocketAddr::new(ip, port)), ""),
+                None => match Prober::icmp(ip, timeout) {
+                    Some(prober) => (prober, ""),
+                    None => (Prober::tcp(SocketAddr::new(ip, DEFAULT_TCP_PORT)), " (ICMP not permitted)"),
+                },
+            };
+            println!("PING {} ({}) {}", host.bold(), ip, format!("{}{}", prober.describe(), note).dimmed());
+
+            let mut samples = Vec::new();
+            for sequence in 0..count {
+                if sequence > 0 {
+                    thread::sleep(INTERVAL);
+                }
+                match prober.probe(sequence as u16, timeout) {
+                    Ok(rtt) => {
+                        println!("  seq={} time={:.2} ms", sequence, ms(rtt));
+                        samples.push(Some(rtt));
+                    }
+                    Err(reason) => {
+                        println!("  seq={} {}", sequence, reason.red());
+                        samples.push(None);
+                    }
+                }
+            }
+
+            let stats = Stats::from_samples(&samples);
+            println!(
+                "{} sent, {} received, {:.0}% loss",
+                stats.sent,
+                stats.received,
+                stats.loss_percent()
+            );
+            if stats.received > 0 {
+                println!(
+                    "min/avg/max/jitter = {:.2}/{:.2}/{:.2}/{:.2} ms",
+                    ms(stats.min),
+                    ms(stats.avg),
+                    ms(stats.max),
+                    ms(stats.jitter)
+                );
+            } else {
+                return Err(Box::new(ExitCodeError::new(1, format!("{} did not answer", host))));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+// "example.com" -> (example.com, None); "example.com:22" and "[::1]:22" -> port 22
+fn split_target(target: &str) -> (String, Option<u16>) {
+    if let Some(rest) = target.strip_prefix('[') {
+        if let Some((host, port)) = rest.split_once("]:") {
+            return (host.to_string(), port.parse().ok());
+        }
+        return (rest.trim_end_matches(']').to_string(), None);
+    }
+    match target.rsplit_once(':') {
+        Some((host, port)) if !host.contains(':') => (host.to_string(), port.parse().ok()),
+        _ => (target.to_string(), None),
+    }
+}
+
+fn resolve(host: &str) -> Result<IpAddr, String> {
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        return Ok(ip);
+    }
+    let addresses: Vec<SocketAddr> = (host, 0).to_socket_addrs().map_err(|e| format!("Failed to resolve {}: {}", host, e))?.collect();
+    addresses
+        .iter()
+        .find(|address| address.is_ipv4())
+        .or(addresses.first())
+        .map(SocketAddr::ip)
+        .ok_or_else(|| format!("{} has no addresses", host))
+}
+
+enum Prober {
+    Icmp { socket: Socket, ipv6: bool, raw: bool, identifier: u16 },
+    Tcp(SocketAddr),
+}
+
+impl Prober {
+    // Unprivileged ICMP sockets first (Linux ping_group_range, macOS), then raw sockets
+    fn icmp(ip: IpAddr, timeout: Duration) -> Option<Prober> {
+        let (domain, protocol) = match ip {
+            IpAddr::V4(_) => (Domain::IPV4, Protocol::ICMPV4),
+            IpAddr::V6(_) => (Domain::IPV6, Protocol::ICMPV6),
+        };
+        let (socket, raw) = match Socket::new(domain, Type::DGRAM, Some(protocol)) {
+            Ok(socket) => (socket, false),
+            Err(_) => (Socket::new(domain, Type::RAW, Some(protocol)).ok()?, true),
+        };
+        socket.set_read_timeout(Some(timeout)).ok()?;
+        socket.connect(&SockAddr::from(SocketAddr::new(ip, 0))).ok()?;
+        Some(Prober::Icmp { socket, ipv6: ip.is_ipv6(), raw, identifier: std::process::id() as u16 })
+    }
+
+    fn tcp(address: SocketAddr) -> Prober {
+        Prober::Tcp(address)
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Prober::Icmp { .. } => "ICMP echo".to_string(),
+            Prober::Tcp(address) => format!("TCP connect to port {}", address.port()),
+        }
+    }
+
+    fn probe(&mut self, sequence: u16, timeout: Duration) -> Result<Duration, String> {
+        match self {
+            Prober::Icmp { socket, ipv6, raw, identifier } => {
+                let request = echo_request(*ipv6, *identifier, sequence);
+                let started = Instant::now();
+                socket.send(&request).map_err(|e| format!("send failed: {}", e))?;
+                let mut buffer = [0u8; 1500];
+                loop {
+                    let length = match socket.read(&mut buffer) {
+                        Ok(length) => length,
+                        Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => return Err("timed out".to_string()),
+                        Err(e) => return Err(format!("receive failed: {}", e)),
+                    };
+                    // Unprivileged sockets get their identifier rewritten by the kernel, so only raw sockets check it
+                    if is_echo_reply(&buffer[..length], *ipv6, (*raw).then_some(*identifier), sequence) {
+                        return Ok(started.elapsed());
+                    }
+                    if started.elapsed() >= timeout {
+                        return Err("timed out".to_string());
+                    }
+                }
+            }
+            Prober::Tcp(address) => {
+                let started = Instant::now();
+                match TcpStream::connect_timeout(address, timeout) {
+                    Ok(_) => Ok(started.elapsed()),
+                    // A refusal still crossed the network and back, which is what is being timed
+                    Err(e) if e.kind() == ErrorKind::ConnectionRefused => Ok(started.elapsed()),
+                    Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => Err("timed out".to_string()),
+                    Err(e) => Err(e.to_string()),
+                }
+            }
+        }
+    }
+}
+
+// Internet checksum (RFC 1071)
+fn checksum(data: &[u8]) -> u16 {
+    let mut sum: u32 = data.chunks(2).map(|pair| u32::from(u16::from_be_bytes([pair[0], *pair.get(1).unwrap_or(&0)]))).sum();
+    while sum > 0xffff {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+// Type, code, checksum, identifier, sequence, payload. The kernel fills in the ICMPv6 checksum.
+fn echo_request(ipv6: bool, identifier: u16, sequence: u16) -> Vec<u8> {
+    let mut packet = vec![if ipv6 { 128 } else { 8 }, 0, 0, 0];
+    packet.extend_from_slice(&identifier.to_be_bytes());
+    packet.extend_from_slice(&sequence.to_be_bytes());
+    packet.extend_from_slice(PAYLOAD);
+    if !ipv6 {
+        let sum = checksum(&packet);
+        packet[2..4].copy_from_slice(&sum.to_be_bytes());
+    }
+    packet
+}
+
+fn is_echo_reply(packet: &[u8], ipv6: bool, identifier: Option<u16>, sequence: u16) -> bool {
+    // Raw IPv4 sockets (and macOS datagram ones) deliver the IP header too
+    let packet = if !ipv6 && packet.first().is_some_and(|byte| byte >> 4 == 4) {
+        let header_length = usize::from(packet[0] & 0x0f) * 4;
+        packet.get(header_length..).unwrap_or(&[])
+    } else {
+        packet
+    };
+    if packet.len() < 8 || packet[0] != if ipv6 { 129 } else { 0 } {
+        return false;
+    }
+    let matches_identifier = identifier.is_none_or(|identifier| u16::from_be_bytes([packet[4], packet[5]]) == identifier);
+    matches_identifier && u16::from_be_bytes([packet[6], packet[7]]) == sequence
+}
+
+#[derive(Debug)]
+struct Stats {
+    sent: usize,
+    received: usize,
+    min: Duration,
+    avg: Duration,
+    max: Duration,
+    // Mean difference between consecutive round trips
+    jitter: Duration,
+}
+
+impl Stats {
+    fn from_samples(samples: &[Option<Duration>]) -> Stats {
+        let received: Vec<Duration> = samples.iter().flatten().copied().collect();
+        let avg = if received.is_empty() { Duration::ZERO } else { received.iter().sum::<Duration>() / received.len() as u32 };
+        let differences: Vec<Duration> = received.windows(2).map(|pair| pair[0].abs_diff(pair[1])).collect();
+        let jitter = if differences.is_empty() { Duration::ZERO } else { differences.iter().sum::<Duration>() / differences.len() as u32 };
+        Stats {
+            sent: samples.len(),
+            received: received.len(),
+            min: received.iter().min().copied().unwrap_or_default(),
+            avg,
+            max: received.iter().max().copied().unwrap_or_default(),
+            jitter,
+        }
+    }
+
+    fn loss_percent(&self) -> f64 {
+        if self.sent == 0 { 0.0 } else { (self.sent - self.received) as f64 * 100.0 / self.sent as f64 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_target() {
+        assert_eq!(split_target("example.com"), ("example.com".to_string(), None));
+        assert_eq!(split_target("example.com:22"), ("example.com".to_string(), Some(22)));
+        assert_eq!(split_target("[::1]:8080"), ("::1".to_string(), Some(8080)));
+        assert_eq!(split_target("2001:db8::1"), ("2001:db8::1".to_string(), None));
+    }
+
+    #[test]
+    fn test_echo_request_checksum() {
+        let packet = echo_request(false, 0x1234, 7);
+        assert_eq!(&packet[..2], [8, 0]);
+        assert_eq!(&packet[4..8], [0x12, 0x34, 0, 7]);
+        // A packet with a correct checksum sums to zero
+        assert_eq!(checksum(&packet), 0);
+        assert_eq!(echo_request(true, 1, 1)[0], 128);
+        assert_eq!(checksum(&[0x00, 0x01, 0xf2, 0x03, 0xf4, 0xf5, 0xf6, 0xf7]), 0x220d);
+    }
+
+    #[test]
+    fn test_is_echo_reply() {
+        let mut reply = echo_request(false, 42, 3);
+        reply[0] = 0;
+        assert!(is_echo_reply(&reply, false, Some(42), 3));
+        assert!(is_echo_reply(&reply, false, None, 3));
+        assert!(!is_echo_reply(&reply, false, Some(41), 3));
+        assert!(!is_echo_reply(&reply, false, None, 4));
+        // The same reply behind a 20-byte IPv4 header
+        let mut with_header = vec![0x45; 1];
+        with_header.extend_from_slice(&[0; 19]);
+        with_header.extend_from_slice(&reply);
+        assert!(is_echo_reply(&with_header, false, Some(42), 3));
+        assert!(!is_echo_reply(&echo_request(false, 42, 3), false, None, 3));
+    }
+
+    #[test]
+    fn test_stats() {
+        let ms = Duration::from_millis;
+        let stats = Stats::from_samples(&[Some(ms(10)), None, Some(ms(20)), Some(ms(15)), None]);
+        assert_eq!((stats.sent, stats.received), (5, 3));
+        assert_eq!(stats.loss_percent(), 40.0);
+        assert_eq!((stats.min, stats.avg, stats.max), (ms(10), ms(15), ms(20)));
+        assert_eq!(stats.jitter, Duration::from_micros(7500));
+        assert_eq!(Stats::from_samples(&[None]).loss_percent(), 100.0);
+    }
+}
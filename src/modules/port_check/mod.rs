@@ -0,0 +1,311 @@
+use crate::tool_module::{ExitCodeError, ToolModule};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use colored::*;
+use serde_json::json;
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::io::ErrorKind;
+use std::net::{IpAddr, SocketAddr, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub struct PortCheckModule;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2);
+const DEFAULT_CONCURRENCY: usize = 200;
+
+impl ToolModule for PortCheckModule {
+    fn name(&self) -> &'static str {
+        "port-check"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("port-check")
+                .long("port-check")
+                .value_name("HOST:PORT")
+                .help("Check whether a TCP port is reachable and how long the connection takes")
+                .long_help("Open a TCP connection to HOST:PORT (use [::1]:443 for IPv6 addresses) and report whether the port is open, refused or filtered, with the connect time. Exits with code 1 when the port is not reachable. The connect timeout is 2 seconds; change it with --timeout.")
+        )
+        .arg(
+            Arg::new("port-scan")
+                .long("port-scan")
+                .value_names(["HOST", "PORTS"])
+                .num_args(1..=2)
+                .help("Scan TCP ports on a host (default 1-1024) and list the open ones")
+                .long_help("Try a TCP connection to every port in PORTS on HOST and list the open ones with their usual service. PORTS is a list of ports and ranges such as 1-1024 (the default) or 22,80,443,8000-8100. Runs 200 connections at a time (--concurrency) with a 2 second connect timeout (--timeout). Only scan hosts you are allowed to.")
+        )
+        .arg(
+            Arg::new("port-json")
+                .long("port-json")
+                .help("Print --port-check and --port-scan results as JSON")
+                .action(ArgAction::SetTrue)
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        // --timeout is declared by the run-file module, --concurrency by the http-bench module
+        let timeout = match matches.get_one::<f64>("timeout") {
+            Some(seconds) if *seconds > 0.0 => Duration::from_secs_f64(*seconds),
+            Some(_) => return Err("--timeout must be greater than 0".into()),
+            None => DEFAULT_TIMEOUT,
+        };
+        let as_json = matches.get_flag("port-json");
+        if let Some(target) = matches.get_one::<String>("port-check") {
+            let (host, port) = split_host_port(target)?;
+            let address = resolve(&host, port)?;
+            let probe = probe(address, timeout);
+            if as_json {
+                println!("{}", serde_json::to_string_pretty(&json!({
+                    "host": host,
+                    "ip": address.ip().to_string(),
+                    "port": port,
+                    "state": probe.state.label(),
+                    "latency_ms": probe.latency.map(|latency| latency.as_secs_f64() * 1000.0),
+                }))?);
+            } else {
+                let via = if host == address.ip().to_string() { String::new() } else { format!(" via {}", address.ip()) };
+                match probe.latency {
+                    Some(latency) => println!("✅ {}:{} is open ({:.1} ms{})", host, port, latency.as_secs_f64() * 1000.0, via),
+                    None => println!("❌ {}:{} is {}{}", host, port, probe.state.label(), via),
+                }
+            }
+            if probe.state != State::Open {
+                return Err(Box::new(ExitCodeError::silent(1)));
+            }
+        } else if let Some(values) = matches.get_many::<String>("port-scan") {
+            let values: Vec<&String> = values.collect();
+            let host = values[0].trim_start_matches('[').trim_end_matches(']').to_string();
+            let ports = parse_ports(values.get(1).map(|ports| ports.as_str()).unwrap_or("1-1024"))?;
+            let ip = resolve(&host, 0)?.ip();
+            let concurrency = matches.get_one::<usize>("concurrency").copied().unwrap_or(DEFAULT_CONCURRENCY).max(1);
+            if !as_json {
+                println!("Scanning {} ports on {} ({})...", ports.len(), host.bold(), ip);
+            }
+            let started = Instant::now();
+            let open = scan(ip, &ports, concurrency, timeout);
+            let elapsed = started.elapsed();
+            if as_json {
+                println!("{}", serde_json::to_string_pretty(&json!({
+                    "host": host,
+                    "ip": ip.to_string(),
+                    "scanned": ports.len(),
+                    "open": open.iter().map(|(port, latency)| json!({
+                        "port": port,
+                        "service": service_name(*port),
+                        "latency_ms": latency.as_secs_f64() * 1000.0,
+                    })).collect::<Vec<_>>(),
+                }))?);
+            } else {
+                for (port, latency) in &open {
+                    println!(
+                        "  {:<7}{:<14}{}",
+                        port.to_string().green().bold(),
+                        service_name(*port).unwrap_or(""),
+                        format!("{:.1} ms", latency.as_secs_f64() * 1000.0).dimmed()
+                    );
+                }
+                println!("{} open of {} scanned in {:.1} s", open.len(), ports.len(), elapsed.as_secs_f64());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Open,
+    // Something answered and refused the connection
+    Closed,
+    // Nothing answered before the timeout, usually a firewall dropping packets
+    Filtered,
+    Unreachable,
+}
+
+impl State {
+    fn label(self) -> &'static str {
+        match self {
+            State::Open => "open",
+            State::Closed => "closed (connection refused)",
+            State::Filtered => "filtered (timed out)",
+            State::Unreachable => "unreachable",
+        }
+    }
+}
+
+struct Probe {
+    state: State,
+    latency: Option<Duration>,
+}
+
+fn probe(address: SocketAddr, timeout: Duration) -> Probe {
+    let started = Instant::now();
+    match TcpStream::connect_timeout(&address, timeout) {
+        Ok(_) => Probe { state: State::Open, latency: Some(started.elapsed()) },
+        Err(e) => {
+            let state = match e.kind() {
+                ErrorKind::ConnectionRefused => State::Closed,
+                ErrorKind::TimedOut | ErrorKind::WouldBlock => State::Filtered,
+                _ => State::Unreachable,
+            };
+            Probe { state, latency: None }
+        }
+    }
+}
+
+// "example.com:443", "10.0.0.1:22", "[::1]:8080"
+fn split_host_port(target: &str) -> Result<(String, u16), String> {
+    let error = || format!("Expected HOST:PORT such as example.com:443 or [::1]:8080, got '{}'", target);
+    let (host, port) = match target.strip_prefix('[') {
+        Some(rest) => {
+            let (host, port) = rest.split_once("]:").ok_or_else(error)?;
+            (host, port)
+        }
+        None => target.rsplit_once(':').filter(|(host, _)| !host.contains(':')).ok_or_else(error)?,
+    };
+    let port = port.parse::<u16>().ok().filter(|port| *port > 0).ok_or_else(error)?;
+    if host.is_empty() {
+        return Err(error());
+    }
+    Ok((host.to_string(), port))
+}
+
+// The first address the host resolves to; IPv4 first, as most services listen there
+fn resolve(host: &str, port: u16) -> Result<SocketAddr, String> {
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        return Ok(SocketAddr::new(ip, port));
+    }
+    let addresses: Vec<SocketAddr> = (host, port).to_socket_addrs().map_err(|e| format!("Failed to resolve {}: {}", host, e))?.collect();
+    addresses
+        .iter()
+        .find(|address| address.is_ipv4())
+        .or(addresses.first())
+        .copied()
+        .ok_or_else(|| format!("{} has no addresses", host))
+}
+
+// "22,80,8000-8002" -> [22, 80, 8000, 8001, 8002], sorted and without duplicates
+fn parse_ports(spec: &str) -> Result<Vec<u16>, String> {
+    let mut ports = BTreeSet::new();
+    for part in spec.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+        let parse = |value: &str| value.trim().parse::<u16>().ok().filter(|port| *port > 0).ok_or_else(|| format!("Invalid port '{}' (use 1-65535)", value.trim()));
+        match part.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse(start)?, parse(end)?);
+                if start > end {
+                    return Err(format!("Invalid port range '{}'", part));
+                }
+                ports.extend(start..=end);
+            }
+            None => {
+                ports.insert(parse(part)?);
+            }
+        }
+    }
+    if ports.is_empty() {
+        return Err("No ports to scan".to_string());
+    }
+    Ok(ports.into_iter().collect())
+}
+
+// Open ports with their connect time, in port order
+fn scan(ip: IpAddr, ports: &[u16], concurrency: usize, timeout: Duration) -> Vec<(u16, Duration)> {
+    let ports: Arc<Vec<u16>> = Arc::new(ports.to_vec());
+    let next = Arc::new(AtomicUsize::new(0));
+    let workers: Vec<_> = (0..concurrency.min(ports.len()))
+        .map(|_| {
+            let (ports, next) = (Arc::clone(&ports), Arc::clone(&next));
+            thread::spawn(move || {
+                let mut open = Vec::new();
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(&port) = ports.get(index) else { break };
+                    if let Some(latency) = probe(SocketAddr::new(ip, port), timeout).latency {
+                        open.push((port, latency));
+                    }
+                }
+                open
+            })
+        })
+        .collect();
+    let mut open: Vec<(u16, Duration)> = workers.into_iter().flat_map(|worker| worker.join().unwrap_or_default()).collect();
+    open.sort_by_key(|(port, _)| *port);
+    open
+}
+
+fn service_name(port: u16) -> Option<&'static str> {
+    Some(match port {
+        21 => "ftp",
+        22 => "ssh",
+        23 => "telnet",
+        25 => "smtp",
+        53 => "dns",
+        80 => "http",
+        110 => "pop3",
+        143 => "imap",
+        443 => "https",
+        465 => "smtps",
+        587 => "submission",
+        993 => "imaps",
+        995 => "pop3s",
+        1433 => "mssql",
+        1521 => "oracle",
+        2375 | 2376 => "docker",
+        3000 => "dev-server",
+        3306 => "mysql",
+        3389 => "rdp",
+        5432 => "postgresql",
+        5672 => "amqp",
+        5900 => "vnc",
+        6379 => "redis",
+        8080 | 8000 | 8888 => "http-alt",
+        8443 => "https-alt",
+        9092 => "kafka",
+        9200 => "elasticsearch",
+        11211 => "memcached",
+        27017 => "mongodb",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_split_host_port() {
+        assert_eq!(split_host_port("example.com:443").unwrap(), ("example.com".to_string(), 443));
+        assert_eq!(split_host_port("[::1]:8080").unwrap(), ("::1".to_string(), 8080));
+        assert!(split_host_port("example.com").is_err());
+        assert!(split_host_port("::1:80").is_err());
+        assert!(split_host_port("example.com:0").is_err());
+        assert!(split_host_port(":80").is_err());
+    }
+
+    #[test]
+    fn test_parse_ports() {
+        assert_eq!(parse_ports("443, 22,8000-8002,22").unwrap(), [22, 443, 8000, 8001, 8002]);
+        assert_eq!(parse_ports("1-1024").unwrap().len(), 1024);
+        assert!(parse_ports("10-5").is_err());
+        assert!(parse_ports("0").is_err());
+        assert!(parse_ports("70000").is_err());
+        assert!(parse_ports(",").is_err());
+    }
+
+    #[test]
+    fn test_probe_and_scan_local_ports() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let open_port = listener.local_addr().unwrap().port();
+        // Bind and drop to find a port that is very likely closed
+        let closed_port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let localhost: IpAddr = "127.0.0.1".parse().unwrap();
+
+        assert_eq!(probe(SocketAddr::new(localhost, open_port), DEFAULT_TIMEOUT).state, State::Open);
+        assert_eq!(probe(SocketAddr::new(localhost, closed_port), DEFAULT_TIMEOUT).state, State::Closed);
+        let open = scan(localhost, &[closed_port, open_port], 2, DEFAULT_TIMEOUT);
+        assert_eq!(open.iter().map(|(port, _)| *port).collect::<Vec<_>>(), [open_port]);
+    }
+}
@@ -0,0 +1,321 @@
+use crate::tool_module::{ExitCodeError, ToolModule};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use colored::*;
+use std::error::Error;
+use std::net::IpAddr;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind, Users};
+
+pub struct PortOwnerModule;
+
+const COMMAND_WIDTH: usize = 60;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Listener {
+    protocol: &'static str,
+    address: IpAddr,
+    port: u16,
+    // None when the socket belongs to a process we are not allowed to inspect
+    pid: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Owner {
+    name: String,
+    command: String,
+    user: String,
+}
+
+impl ToolModule for PortOwnerModule {
+    fn name(&self) -> &'static str {
+        "port-owner"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("whoports")
+                .long("whoports")
+                .value_name("PORT")
+                .value_parser(clap::value_parser!(u16).range(1..))
+                .help("Show which process (PID, name, command line, user) is listening on PORT")
+                .long_help("Show the process listening on PORT over TCP or UDP, with its PID, name, command line and user. Sockets owned by other users may need sudo to be attributed on Linux and macOS. Exits with code 1 when nothing listens on the port.")
+        )
+        .arg(
+            Arg::new("ports")
+                .long("ports")
+                .action(ArgAction::SetTrue)
+                .help("List all listening TCP and UDP sockets with their processes")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let whoports = matches.get_one::<u16>("whoports").copied();
+        if whoports.is_none() && !matches.get_flag("ports") {
+            return Ok(());
+        }
+        let mut listeners = listeners()?;
+        listeners.sort_by(|a, b| (a.port, a.protocol, a.address).cmp(&(b.port, b.protocol, b.address)));
+        listeners.dedup();
+        if let Some(port) = whoports {
+            let held: Vec<&Listener> = listeners.iter().filter(|listener| listener.port == port).collect();
+            if held.is_empty() {
+                return Err(Box::new(ExitCodeError::new(1, format!("Nothing is listening on port {}", port))));
+            }
+            let owners = owners(&held);
+            for (listener, owner) in held.iter().zip(&owners) {
+                println!("{} {} on {}", format!("Port {}", port).bold(), listener.protocol, display_address(&listener.address));
+                match (listener.pid, owner) {
+                    (Some(pid), Some(owner)) => {
+                        println!("  {:<8} {}", "PID", pid.to_string().green().bold());
+                        println!("  {:<8} {}", "Process", owner.name);
+                        println!("  {:<8} {}", "Command", owner.command);
+                        println!("  {:<8} {}", "User", owner.user);
+                    }
+                    (Some(pid), None) => println!("  {:<8} {} (exited since)", "PID", pid),
+                    (None, _) => println!("  {}", "Owned by a process of another user; run with sudo to see which".yellow()),
+                }
+            }
+        }
+        if matches.get_flag("ports") {
+            let all: Vec<&Listener> = listeners.iter().collect();
+            let owners = owners(&all);
+            print!("{}", render_table(&all, &owners));
+            if listeners.iter().any(|listener| listener.pid.is_none()) {
+                eprintln!("⚠️ Some sockets belong to other users' processes; run with sudo to see them");
+            }
+        }
+        Ok(())
+    }
+}
+
+// Looks up name, command line and user of each listener's process
+fn owners(listeners: &[&Listener]) -> Vec<Option<Owner>> {
+    let pids: Vec<Pid> = listeners.iter().filter_map(|listener| listener.pid).map(Pid::from_u32).collect();
+    let mut system = System::new();
+    system.refresh_processes_specifics(ProcessesToUpdate::Some(&pids), true, ProcessRefreshKind::nothing().with_cmd(UpdateKind::Always).with_user(UpdateKind::Always));
+    let users = Users::new_with_refreshed_list();
+    listeners
+        .iter()
+        .map(|listener| {
+            let process = system.process(Pid::from_u32(listener.pid?))?;
+            // Arguments may span lines (python -c scripts); keep the command on one
+            let command = process.cmd().iter().flat_map(|part| part.to_string_lossy().split_whitespace().map(str::to_string).collect::<Vec<_>>()).collect::<Vec<_>>().join(" ");
+            let user = process.user_id().and_then(|uid| users.get_user_by_id(uid)).map(|user| user.name().to_string()).or_else(|| process.user_id().map(|uid| uid.to_string()));
+            Some(Owner { name: process.name().to_string_lossy().to_string(), command, user: user.unwrap_or_else(|| "?".to_string()) })
+        })
+        .collect()
+}
+
+fn display_address(address: &IpAddr) -> String {
+    match address {
+        IpAddr::V4(v4) if v4.is_unspecified() => "* (all IPv4)".to_string(),
+        IpAddr::V6(v6) if v6.is_unspecified() => "* (all IPv6)".to_string(),
+        address => address.to_string(),
+    }
+}
+
+fn render_table(listeners: &[&Listener], owners: &[Option<Owner>]) -> String {
+    if listeners.is_empty() {
+        return "No listening sockets found\n".to_string();
+    }
+    let rows: Vec<[String; 7]> = listeners
+        .iter()
+        .zip(owners)
+        .map(|(listener, owner)| {
+            let address = match listener.address {
+                address if address.is_unspecified() => "*".to_string(),
+                IpAddr::V6(v6) => format!("[{}]", v6),
+                address => address.to_string(),
+            };
+            let pid = listener.pid.map(|pid| pid.to_string()).unwrap_or_else(|| "-".to_string());
+            let (name, user, command) = match owner {
+                Some(owner) => (owner.name.clone(), owner.user.clone(), truncate(&owner.command, COMMAND_WIDTH)),
+                None => ("-".to_string(), "-".to_string(), String::new()),
+            };
+            [listener.port.to_string(), listener.protocol.to_string(), address, pid, name, user, command]
+        })
+        .collect();
+    let header = ["PORT", "PROTO", "ADDRESS", "PID", "PROCESS", "USER", "COMMAND"];
+    let widths: Vec<usize> = (0..header.len()).map(|column| rows.iter().map(|row| row[column].chars().count()).chain([header[column].len()]).max().unwrap_or(0)).collect();
+    let line = |cells: [&str; 7]| -> String {
+        let text: Vec<String> = cells.iter().zip(&widths).map(|(cell, width)| format!("{:<width$}", cell, width = width)).collect();
+        text.join("  ").trim_end().to_string()
+    };
+    let mut output = format!("{}\n", line(header).bold());
+    for row in &rows {
+        output.push_str(&line(row.each_ref().map(String::as_str)));
+        output.push('\n');
+    }
+    output
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    format!("{}…", text.chars().take(width - 1).collect::<String>())
+}
+
+#[cfg(target_os = "linux")]
+fn listeners() -> Result<Vec<Listener>, Box<dyn Error>> {
+    use std::collections::HashMap;
+    use std::fs;
+
+    let mut sockets = Vec::new();
+    for (file, protocol) in [("tcp", "tcp"), ("tcp6", "tcp"), ("udp", "udp"), ("udp6", "udp")] {
+        // IPv6 may be disabled, leaving no tcp6/udp6 table
+        if let Ok(table) = fs::read_to_string(format!("/proc/net/{}", file)) {
+            sockets.extend(parse_proc_net(&table, protocol));
+        }
+    }
+    // Map socket inodes to the processes holding them open
+    let mut inodes: HashMap<u64, u32> = HashMap::new();
+    for entry in fs::read_dir("/proc")?.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) else { continue };
+        let Ok(fds) = fs::read_dir(entry.path().join("fd")) else { continue };
+        for fd in fds.flatten() {
+            if let Some(inode) = fs::read_link(fd.path()).ok().and_then(|target| target.to_str()?.strip_prefix("socket:[")?.strip_suffix(']')?.parse().ok()) {
+                inodes.entry(inode).or_insert(pid);
+            }
+        }
+    }
+    Ok(sockets.into_iter().map(|(mut listener, inode)| {
+        listener.pid = inodes.get(&inode).copied();
+        listener
+    }).collect())
+}
+
+// Listening TCP and bound UDP sockets from a /proc/net table, with their inodes
+#[cfg(any(target_os = "linux", test))]
+fn parse_proc_net(table: &str, protocol: &'static str) -> Vec<(Listener, u64)> {
+    // TCP_LISTEN, and TCP_CLOSE which is how unconnected UDP sockets show up
+    let wanted = if protocol == "tcp" { "0A" } else { "07" };
+    table
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 10 || fields[3] != wanted {
+                return None;
+            }
+            let (address, port) = fields[1].split_once(':')?;
+            let port = u16::from_str_radix(port, 16).ok().filter(|port| *port != 0)?;
+            Some((Listener { protocol, address: parse_proc_address(address)?, port, pid: None }, fields[9].parse().ok()?))
+        })
+        .collect()
+}
+
+// /proc/net addresses are hex in host byte order, one 32-bit word at a time
+#[cfg(any(target_os = "linux", test))]
+fn parse_proc_address(hex: &str) -> Option<IpAddr> {
+    let words: Vec<u32> = (0..hex.len() / 8).map(|index| u32::from_str_radix(&hex[index * 8..index * 8 + 8], 16).map(u32::swap_bytes)).collect::<Result<_, _>>().ok()?;
+    match words.as_slice() {
+        [word] => Some(IpAddr::from(word.to_be_bytes())),
+        [a, b, c, d] => {
+            let mut bytes = [0u8; 16];
+            for (index, word) in [a, b, c, d].iter().enumerate() {
+                bytes[index * 4..index * 4 + 4].copy_from_slice(&word.to_be_bytes());
+            }
+            Some(IpAddr::from(bytes))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn listeners() -> Result<Vec<Listener>, Box<dyn Error>> {
+    let output = std::process::Command::new("lsof").args(["-nP", "-iTCP", "-sTCP:LISTEN", "-iUDP", "-FpPn"]).output().map_err(|e| format!("Failed to run lsof: {}", e))?;
+    Ok(parse_lsof(&String::from_utf8_lossy(&output.stdout)))
+}
+
+// lsof -F output: a p<pid> line per process, then P<protocol> and n<name> per socket
+#[cfg(any(target_os = "macos", test))]
+fn parse_lsof(output: &str) -> Vec<Listener> {
+    let mut listeners = Vec::new();
+    let (mut pid, mut protocol) = (None, "tcp");
+    for line in output.lines() {
+        let (field, value) = line.split_at(line.len().min(1));
+        match field {
+            "p" => pid = value.parse().ok(),
+            "P" => protocol = if value.eq_ignore_ascii_case("udp") { "udp" } else { "tcp" },
+            // Connected sockets show as local->remote
+            "n" if !value.contains("->") => {
+                if let Some((address, port)) = split_address(value) {
+                    listeners.push(Listener { protocol, address, port, pid });
+                }
+            }
+            _ => {}
+        }
+    }
+    listeners
+}
+
+#[cfg(windows)]
+fn listeners() -> Result<Vec<Listener>, Box<dyn Error>> {
+    let output = std::process::Command::new("netstat").arg("-ano").output().map_err(|e| format!("Failed to run netstat: {}", e))?;
+    Ok(parse_netstat(&String::from_utf8_lossy(&output.stdout)))
+}
+
+// netstat -ano rows; listening TCP sockets have a zero remote port, whatever the localized state says
+#[cfg(any(windows, test))]
+fn parse_netstat(output: &str) -> Vec<Listener> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (protocol, pid) = match fields.as_slice() {
+                [proto, _, remote, _, pid] if proto.eq_ignore_ascii_case("tcp") && remote.ends_with(":0") => ("tcp", pid),
+                [proto, _, _, pid] if proto.eq_ignore_ascii_case("udp") => ("udp", pid),
+                _ => return None,
+            };
+            let (address, port) = split_address(fields[1])?;
+            Some(Listener { protocol, address, port, pid: pid.parse().ok() })
+        })
+        .collect()
+}
+
+// "*:3000", "127.0.0.1:8080" or "[::1]:5432"
+#[cfg(any(not(target_os = "linux"), test))]
+fn split_address(text: &str) -> Option<(IpAddr, u16)> {
+    let (host, port) = text.rsplit_once(':')?;
+    let port = port.parse().ok().filter(|port| *port != 0)?;
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let address = if host == "*" { IpAddr::from([0, 0, 0, 0]) } else { host.split('%').next()?.parse().ok()? };
+    Some((address, port))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn test_parse_proc_net() {
+        let tcp = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode\n   0: 0100007F:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 4242 1 0000000000000000 100 0 0 10 0\n   1: 0100007F:1F91 0100007F:D431 01 00000000:00000000 00:00000000 00000000  1000        0 4243 1 0000000000000000 20 4 30 10 -1\n";
+        let listeners = parse_proc_net(tcp, "tcp");
+        assert_eq!(listeners, [(Listener { protocol: "tcp", address: IpAddr::V4(Ipv4Addr::LOCALHOST), port: 8080, pid: None }, 4242)]);
+        assert_eq!(parse_proc_address("00000000000000000000000001000000"), Some(IpAddr::V6(Ipv6Addr::LOCALHOST)));
+        assert_eq!(parse_proc_address("00000000"), Some(IpAddr::V4(Ipv4Addr::UNSPECIFIED)));
+    }
+
+    #[test]
+    fn test_parse_lsof_and_netstat() {
+        let lsof = "p501\nf12\nPTCP\nn*:3000\nf13\nPTCP\nn[::1]:5432\np77\nf4\nPUDP\nn127.0.0.1:5353\nf5\nPUDP\nn10.0.0.2:5000->10.0.0.3:6000\n";
+        let ports: Vec<(u16, &str, Option<u32>)> = parse_lsof(lsof).iter().map(|l| (l.port, l.protocol, l.pid)).collect();
+        assert_eq!(ports, [(3000, "tcp", Some(501)), (5432, "tcp", Some(501)), (5353, "udp", Some(77))]);
+
+        let netstat = "\nActive Connections\n\n  Proto  Local Address          Foreign Address        State           PID\n  TCP    0.0.0.0:135            0.0.0.0:0              LISTENING       1044\n  TCP    10.0.0.5:50000         20.1.1.1:443           ESTABLISHED     880\n  TCP    [::]:445               [::]:0                 LISTENING       4\n  UDP    0.0.0.0:5353           *:*                                    2212\n";
+        let ports: Vec<(u16, &str, Option<u32>)> = parse_netstat(netstat).iter().map(|l| (l.port, l.protocol, l.pid)).collect();
+        assert_eq!(ports, [(135, "tcp", Some(1044)), (445, "tcp", Some(4)), (5353, "udp", Some(2212))]);
+    }
+
+    #[test]
+    fn test_find_own_listener() {
+        let socket = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = socket.local_addr().unwrap().port();
+        let found: Vec<Listener> = listeners().unwrap().into_iter().filter(|listener| listener.port == port && listener.protocol == "tcp").collect();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].pid, Some(std::process::id()));
+        let owner = owners(&[&found[0]]).remove(0).unwrap();
+        assert!(owner.name.starts_with("micro_swiss") || owner.name.starts_with("micro-swiss"));
+    }
+}
@@ -0,0 +1,336 @@
+use crate::binary_json::payload_bytes;
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgMatches, Command};
+use std::error::Error;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+mod schema;
+mod wire;
+use schema::{parse_proto, FieldDef, Message, Resolved, Schema};
+use wire::{parse_message, read_varint, Field, WireValue};
+
+pub struct ProtoDecodeModule;
+
+const INDENT: &str = "  ";
+
+impl ToolModule for ProtoDecodeModule {
+    fn name(&self) -> &'static str {
+        "proto-decode"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("proto-decode")
+                .long("proto-decode")
+                .value_name("DATA")
+                .help("Decode a protobuf message given as base64, hex or a file (- for stdin), with or without a .proto")
+                .long_help("Decode protobuf wire format like protoc --decode_raw: every field by number, with varints, fixed32/64 (also shown as float/double) and length-delimited values guessed as text, nested messages or bytes. DATA is base64 or hex text, a file holding raw bytes or such text, or - for stdin. A gRPC frame header (compression flag and length) is stripped. Pass --proto to get field names, enum names and exact types.")
+        )
+        .arg(
+            Arg::new("proto")
+                .long("proto")
+                .value_name("FILE")
+                .help("A .proto file that names the fields for --proto-decode")
+                .requires("proto-decode")
+        )
+        .arg(
+            Arg::new("proto-message")
+                .long("proto-message")
+                .value_name("NAME")
+                .help("Message type in the --proto file to decode as (default: the first one)")
+                .requires("proto")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(data) = matches.get_one::<String>("proto-decode") {
+            let bytes = strip_grpc_frame(payload_bytes(read_data(data)?))?;
+            let fields = parse_message(&bytes).map_err(|e| format!("Not a protobuf message: {}", e))?;
+            let schema = match matches.get_one::<String>("proto") {
+                Some(path) => Some(parse_proto(&fs::read_to_string(path).map_err(|e| format!("Failed to read '{}': {}", path, e))?)?),
+                None => None,
+            };
+            let message = match (&schema, matches.get_one::<String>("proto-message")) {
+                (Some(schema), Some(name)) => Some(schema.find_message(name)?),
+                (Some(schema), None) => Some(schema.messages.first().ok_or("The .proto file defines no messages")?),
+                (None, _) => None,
+            };
+            let mut output = String::new();
+            match (&schema, message) {
+                (Some(schema), Some(message)) => render_typed(&fields, message, schema, 0, &mut output),
+                _ => render_raw(&fields, 0, &mut output),
+            }
+            print!("{}", output);
+        }
+        Ok(())
+    }
+}
+
+// - for stdin, an existing file, or the text itself
+fn read_data(data: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    if data == "-" {
+        let mut buffer = Vec::new();
+        io::stdin().read_to_end(&mut buffer)?;
+        return Ok(buffer);
+    }
+    if Path::new(data).is_file() {
+        return fs::read(data).map_err(|e| format!("Failed to read '{}': {}", data, e).into());
+    }
+    Ok(data.as_bytes().to_vec())
+}
+
+// gRPC prefixes each message with a compressed flag and a 4-byte length. A message
+// cannot start with 0x00 or 0x01 (field number 0), so the check is unambiguous.
+fn strip_grpc_frame(bytes: Vec<u8>) -> Result<Vec<u8>, String> {
+    if bytes.len() < 5 || bytes[0] > 1 {
+        return Ok(bytes);
+    }
+    let length = u32::from_be_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]) as usize;
+    if length != bytes.len() - 5 {
+        return Ok(bytes);
+    }
+    if bytes[0] == 1 {
+        return Err("This gRPC message is compressed; decompress it first".to_string());
+    }
+    Ok(bytes[5..].to_vec())
+}
+
+// Text format escaping; bytes outside printable ASCII become \x escapes unless they are valid UTF-8 text
+fn quote(bytes: &[u8]) -> String {
+    let mut quoted = String::from("\"");
+    match std::str::from_utf8(bytes) {
+        Ok(text) => {
+            for c in text.chars() {
+                match c {
+                    '"' => quoted.push_str("\\\""),
+                    '\\' => quoted.push_str("\\\\"),
+                    '\n' => quoted.push_str("\\n"),
+                    '\r' => quoted.push_str("\\r"),
+                    '\t' => quoted.push_str("\\t"),
+                    c if c.is_control() => quoted.push_str(&format!("\\x{:02x}", c as u32)),
+                    c => quoted.push(c),
+                }
+            }
+        }
+        Err(_) => {
+            for byte in bytes {
+                match byte {
+                    b'"' => quoted.push_str("\\\""),
+                    b'\\' => quoted.push_str("\\\\"),
+                    0x20..=0x7e => quoted.push(*byte as char),
+                    _ => quoted.push_str(&format!("\\x{:02x}", byte)),
+                }
+            }
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn is_text(bytes: &[u8]) -> bool {
+    std::str::from_utf8(bytes).is_ok_and(|text| text.chars().all(|c| !c.is_control() || matches!(c, '\n' | '\r' | '\t')))
+}
+
+fn line(output: &mut String, depth: usize, text: &str) {
+    output.push_str(&INDENT.repeat(depth));
+    output.push_str(text);
+    output.push('\n');
+}
+
+fn nested(output: &mut String, depth: usize, label: &str, render: impl FnOnce(&mut String)) {
+    line(output, depth, &format!("{} {{", label));
+    render(output);
+    line(output, depth, "}");
+}
+
+// Without a schema: numbers, and a guess for each length-delimited value
+fn render_raw(fields: &[Field], depth: usize, output: &mut String) {
+    for field in fields {
+        let number = field.number;
+        match &field.value {
+            WireValue::Varint(value) if *value > i64::MAX as u64 => line(output, depth, &format!("{}: {}  # int64 {}", number, value, *value as i64)),
+            WireValue::Varint(value) => line(output, depth, &format!("{}: {}", number, value)),
+            WireValue::Fixed64(value) => line(output, depth, &format!("{}: 0x{:016x}  # double {}", number, value, f64::from_bits(*value))),
+            WireValue::Fixed32(value) => line(output, depth, &format!("{}: 0x{:08x}  # float {}", number, value, f32::from_bits(*value))),
+            WireValue::Group(fields) => nested(output, depth, &number.to_string(), |output| render_raw(fields, depth + 1, output)),
+            // Text first: nested messages almost always contain control bytes in their keys
+            WireValue::Bytes(bytes) if bytes.is_empty() || is_text(bytes) => line(output, depth, &format!("{}: {}", number, quote(bytes))),
+            WireValue::Bytes(bytes) => match parse_message(bytes) {
+                Ok(fields) => nested(output, depth, &number.to_string(), |output| render_raw(&fields, depth + 1, output)),
+                Err(_) => line(output, depth, &format!("{}: {}", number, quote(bytes))),
+            },
+        }
+    }
+}
+
+fn zigzag(value: u64) -> i64 {
+    (value >> 1) as i64 ^ -((value & 1) as i64)
+}
+
+// A scalar value as text, or None when the wire type does not fit the declared type
+fn scalar(kind: &str, value: &WireValue) -> Option<String> {
+    Some(match (kind, value) {
+        ("int32", WireValue::Varint(value)) => (*value as i32).to_string(),
+        ("int64", WireValue::Varint(value)) => (*value as i64).to_string(),
+        ("uint32", WireValue::Varint(value)) => (*value as u32).to_string(),
+        ("uint64", WireValue::Varint(value)) => value.to_string(),
+        ("sint32", WireValue::Varint(value)) => (zigzag(*value) as i32).to_string(),
+        ("sint64", WireValue::Varint(value)) => zigzag(*value).to_string(),
+        ("bool", WireValue::Varint(value)) => (*value != 0).to_string(),
+        ("fixed64", WireValue::Fixed64(value)) => value.to_string(),
+        ("sfixed64", WireValue::Fixed64(value)) => (*value as i64).to_string(),
+        ("double", WireValue::Fixed64(value)) => f64::from_bits(*value).to_string(),
+        ("fixed32", WireValue::Fixed32(value)) => value.to_string(),
+        ("sfixed32", WireValue::Fixed32(value)) => (*value as i32).to_string(),
+        ("float", WireValue::Fixed32(value)) => f32::from_bits(*value).to_string(),
+        ("string" | "bytes", WireValue::Bytes(bytes)) => quote(bytes),
+        _ => return None,
+    })
+}
+
+// Packed repeated numbers: one length-delimited run of varints or fixed-width values
+fn unpack<'a>(kind: &str, bytes: &'a [u8]) -> Option<Vec<WireValue<'a>>> {
+    let mut values = Vec::new();
+    match kind {
+        "int32" | "int64" | "uint32" | "uint64" | "sint32" | "sint64" | "bool" => {
+            let mut position = 0;
+            while position < bytes.len() {
+                values.push(WireValue::Varint(read_varint(bytes, &mut position).ok()?));
+            }
+        }
+        "fixed64" | "sfixed64" | "double" if bytes.len().is_multiple_of(8) => {
+            values.extend(bytes.chunks(8).map(|chunk| WireValue::Fixed64(u64::from_le_bytes(chunk.try_into().unwrap_or_default()))));
+        }
+        "fixed32" | "sfixed32" | "float" if bytes.len().is_multiple_of(4) => {
+            values.extend(bytes.chunks(4).map(|chunk| WireValue::Fixed32(u32::from_le_bytes(chunk.try_into().unwrap_or_default()))));
+        }
+        _ => return None,
+    }
+    Some(values)
+}
+
+fn render_typed(fields: &[Field], message: &Message, schema: &Schema, depth: usize, output: &mut String) {
+    for field in fields {
+        match message.field(field.number) {
+            Some(definition) => render_field(definition, &field.value, message, schema, depth, output),
+            // Unknown to the schema, e.g. added in a newer version
+            None => render_raw(std::slice::from_ref(field), depth, output),
+        }
+    }
+}
+
+fn render_field(definition: &FieldDef, value: &WireValue, message: &Message, schema: &Schema, depth: usize, output: &mut String) {
+    let name = &definition.name;
+    if let Some(text) = scalar(&definition.kind, value) {
+        return line(output, depth, &format!("{}: {}", name, text));
+    }
+    // A map entry is a message with the key as field 1 and the value as field 2
+    let entry = definition.map.as_ref().map(|(key, value)| Message {
+        name: message.name.clone(),
+        fields: vec![
+            FieldDef { name: "key".to_string(), number: 1, kind: key.clone(), repeated: false, map: None },
+            FieldDef { name: "value".to_string(), number: 2, kind: value.clone(), repeated: false, map: None },
+        ],
+    });
+    let resolved = match &entry {
+        Some(entry) => Some(Resolved::Message(entry)),
+        None => schema.resolve(&definition.kind, &message.name),
+    };
+    match (resolved, value) {
+        (Some(Resolved::Message(target)), WireValue::Bytes(bytes)) => match parse_message(bytes) {
+            Ok(fields) => nested(output, depth, name, |output| render_typed(&fields, target, schema, depth + 1, output)),
+            Err(e) => line(output, depth, &format!("{}: {}  # not a valid {}: {}", name, quote(bytes), target.name, e)),
+        },
+        (Some(Resolved::Message(target)), WireValue::Group(fields)) => {
+            nested(output, depth, name, |output| render_typed(fields, target, schema, depth + 1, output))
+        }
+        (Some(Resolved::Enum(values)), WireValue::Varint(number)) => {
+            let number = *number as i32 as i64;
+            match values.iter().find(|(_, value)| *value == number) {
+                Some((label, _)) => line(output, depth, &format!("{}: {}", name, label)),
+                None => line(output, depth, &format!("{}: {}  # not in the enum", name, number)),
+            }
+        }
+        (Some(Resolved::Enum(_)), WireValue::Bytes(bytes)) if definition.repeated => {
+            for value in unpack("int32", bytes).unwrap_or_default() {
+                render_field(&FieldDef { repeated: false, ..definition.clone() }, &value, message, schema, depth, output);
+            }
+        }
+        (None, WireValue::Bytes(bytes)) if definition.repeated => match unpack(&definition.kind, bytes) {
+            Some(values) => {
+                for value in values {
+                    render_field(definition, &value, message, schema, depth, output);
+                }
+            }
+            None => render_mismatch(definition, value, depth, output),
+        },
+        _ => render_mismatch(definition, value, depth, output),
+    }
+}
+
+// The wire type disagrees with the .proto, or the type is unknown: show the raw value
+fn render_mismatch(definition: &FieldDef, value: &WireValue, depth: usize, output: &mut String) {
+    let mut raw = String::new();
+    render_raw(&[Field { number: definition.number, value: value.clone() }], 0, &mut raw);
+    let raw = raw.trim_end();
+    let note = format!("  # {} {} in the .proto", definition.kind, definition.name);
+    match raw.split_once('\n') {
+        Some((first, rest)) => {
+            line(output, depth, &format!("{}{}", first, note));
+            for raw_line in rest.lines() {
+                line(output, depth, raw_line);
+            }
+        }
+        None => line(output, depth, &format!("{}{}", raw, note)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw(bytes: &[u8]) -> String {
+        let mut output = String::new();
+        render_raw(&parse_message(bytes).unwrap(), 0, &mut output);
+        output
+    }
+
+    fn typed(bytes: &[u8], proto: &str, message: &str) -> String {
+        let schema = parse_proto(proto).unwrap();
+        let mut output = String::new();
+        render_typed(&parse_message(bytes).unwrap(), schema.find_message(message).unwrap(), &schema, 0, &mut output);
+        output
+    }
+
+    #[test]
+    fn test_render_raw() {
+        assert_eq!(raw(b"\x08\x96\x01\x12\x07testing\x1a\x03\x08\x96\x01"), "1: 150\n2: \"testing\"\n3 {\n  1: 150\n}\n");
+        assert_eq!(raw(b"\x08\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01"), "1: 18446744073709551615  # int64 -1\n");
+        assert_eq!(raw(b"\x0d\x00\x00\x80\x3f\x12\x02\xff\x00"), "1: 0x3f800000  # float 1\n2: \"\\xff\\x00\"\n");
+    }
+
+    #[test]
+    fn test_render_typed() {
+        let proto = "syntax = \"proto3\";\nmessage Order {\n  sint32 delta = 1;\n  repeated Item items = 2;\n  Status status = 3;\n  map<string, int32> counts = 4;\n  repeated uint32 codes = 5;\n  message Item { string sku = 1; }\n}\nenum Status { NEW = 0; SHIPPED = 1; }";
+        // delta -2, an item, status SHIPPED, counts {"a": 5}, packed codes [1, 300], then unknown field 9
+        let bytes = b"\x08\x03\x12\x03\x0a\x01x\x18\x01\x22\x05\x0a\x01a\x10\x05\x2a\x03\x01\xac\x02\x48\x07";
+        assert_eq!(
+            typed(bytes, proto, "Order"),
+            "delta: -2\nitems {\n  sku: \"x\"\n}\nstatus: SHIPPED\ncounts {\n  key: \"a\"\n  value: 5\n}\ncodes: 1\ncodes: 300\n9: 7\n"
+        );
+        // Wire type 0 where the .proto says string
+        assert_eq!(typed(b"\x0a\x01x\x10\x01", "message I { string sku = 1; string name = 2; }", "I"), "sku: \"x\"\n2: 1  # string name in the .proto\n");
+    }
+
+    #[test]
+    fn test_input_helpers() {
+        assert_eq!(strip_grpc_frame(b"\x00\x00\x00\x00\x02\x08\x01".to_vec()).unwrap(), b"\x08\x01");
+        assert!(strip_grpc_frame(b"\x01\x00\x00\x00\x02\x08\x01".to_vec()).is_err());
+        assert_eq!(strip_grpc_frame(b"\x08\x01".to_vec()).unwrap(), b"\x08\x01");
+        assert_eq!(zigzag(3), -2);
+        assert_eq!(zigzag(4), 2);
+        assert!(!is_text(b"\x08\x01"));
+    }
+}
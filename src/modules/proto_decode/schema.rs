@@ -0,0 +1,372 @@
+// Just enough of the .proto language to name fields: messages, enums, oneofs and maps.
+// Services, options and extensions are skipped.
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDef {
+    pub name: String,
+    pub number: u32,
+    // As written: a scalar like sint64 or a message or enum name
+    pub kind: String,
+    pub repeated: bool,
+    // Key and value types of a map<K, V> field
+    pub map: Option<(String, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Message {
+    // Fully qualified, like shop.Order.Item
+    pub name: String,
+    pub fields: Vec<FieldDef>,
+}
+
+impl Message {
+    pub fn field(&self, number: u32) -> Option<&FieldDef> {
+        self.fields.iter().find(|field| field.number == number)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Schema {
+    pub messages: Vec<Message>,
+    // Fully qualified enum name to its values
+    pub enums: HashMap<String, Vec<(String, i64)>>,
+}
+
+pub enum Resolved<'a> {
+    Message(&'a Message),
+    Enum(&'a [(String, i64)]),
+}
+
+impl Schema {
+    pub fn message(&self, name: &str) -> Option<&Message> {
+        self.messages.iter().find(|message| message.name == name)
+    }
+
+    // A message by full name or by its last components, like Order or Order.Item
+    pub fn find_message(&self, name: &str) -> Result<&Message, String> {
+        let name = name.trim_start_matches('.');
+        if let Some(message) = self.message(name) {
+            return Ok(message);
+        }
+        let suffix = format!(".{}", name);
+        let matches: Vec<&Message> = self.messages.iter().filter(|message| message.name.ends_with(&suffix)).collect();
+        match matches.as_slice() {
+            [message] => Ok(message),
+            [] => Err(format!("No message '{}' in the .proto file", name)),
+            _ => Err(format!("'{}' is ambiguous: {}", name, matches.iter().map(|message| message.name.as_str()).collect::<Vec<_>>().join(", "))),
+        }
+    }
+
+    // Type names resolve like C++ scopes: innermost enclosing scope first, a leading dot means fully qualified
+    pub fn resolve(&self, kind: &str, scope: &str) -> Option<Resolved<'_>> {
+        let lookup = |name: &str| {
+            self.message(name).map(Resolved::Message).or_else(|| self.enums.get(name).map(|values| Resolved::Enum(values)))
+        };
+        if let Some(full) = kind.strip_prefix('.') {
+            return lookup(full);
+        }
+        let mut scope = scope.to_string();
+        loop {
+            let candidate = if scope.is_empty() { kind.to_string() } else { format!("{}.{}", scope, kind) };
+            if let Some(resolved) = lookup(&candidate) {
+                return Some(resolved);
+            }
+            if scope.is_empty() {
+                return None;
+            }
+            scope = scope.rsplit_once('.').map(|(parent, _)| parent.to_string()).unwrap_or_default();
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<String>, String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if chars[i..].starts_with(&['/', '/']) {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if chars[i..].starts_with(&['/', '*']) {
+            let end = (i + 2..chars.len().saturating_sub(1)).find(|&j| chars[j] == '*' && chars[j + 1] == '/').ok_or("Unterminated /* comment")?;
+            i = end + 2;
+        } else if c == '"' || c == '\'' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                i += if chars[i] == '\\' { 2 } else { 1 };
+            }
+            if i >= chars.len() {
+                return Err("Unterminated string".to_string());
+            }
+            i += 1;
+            tokens.push(chars[start..i].iter().collect());
+        } else if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' || c == '+' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '.' | '-' | '+')) {
+                i += 1;
+            }
+            tokens.push(chars[start..i].iter().collect());
+        } else {
+            tokens.push(c.to_string());
+            i += 1;
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<String>,
+    position: usize,
+    schema: Schema,
+}
+
+impl Parser {
+    fn next(&mut self) -> Result<String, String> {
+        let token = self.tokens.get(self.position).cloned().ok_or("Unexpected end of the .proto file")?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.position).map(String::as_str)
+    }
+
+    fn expect(&mut self, wanted: &str) -> Result<(), String> {
+        let token = self.next()?;
+        if token != wanted {
+            return Err(format!("Expected '{}' but found '{}' in the .proto file", wanted, token));
+        }
+        Ok(())
+    }
+
+    // Through the ; that ends a statement, or a whole { } block, whichever comes first
+    fn skip_statement(&mut self) -> Result<(), String> {
+        let mut depth = 0;
+        loop {
+            match self.next()?.as_str() {
+                "{" => depth += 1,
+                "}" => {
+                    depth -= 1;
+                    if depth == 0 {
+                        if self.peek() == Some(";") {
+                            self.position += 1;
+                        }
+                        return Ok(());
+                    }
+                }
+                ";" if depth == 0 => return Ok(()),
+                _ => {}
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<i64, String> {
+        let token = self.next()?;
+        let parsed = match token.strip_prefix("0x").or_else(|| token.strip_prefix("0X")) {
+            Some(hex) => i64::from_str_radix(hex, 16),
+            None => token.parse::<i64>(),
+        };
+        parsed.map_err(|_| format!("Expected a number but found '{}' in the .proto file", token))
+    }
+
+    // Statements of a file (scope = package) or a message body up to its closing brace
+    fn parse_body(&mut self, scope: &str, message: Option<&mut Message>) -> Result<(), String> {
+        let mut fields = Vec::new();
+        let mut scope = scope.to_string();
+        let mut closed = false;
+        while let Some(token) = self.peek().map(str::to_string) {
+            match token.as_str() {
+                "}" if message.is_some() => {
+                    self.position += 1;
+                    closed = true;
+                    break;
+                }
+                "package" if message.is_none() => {
+                    self.position += 1;
+                    scope = self.next()?;
+                    self.expect(";")?;
+                }
+                "message" => {
+                    self.position += 1;
+                    let name = self.next()?;
+                    self.expect("{")?;
+                    let full = if scope.is_empty() { name } else { format!("{}.{}", scope, name) };
+                    // Reserve the slot so messages keep their order of appearance
+                    let index = self.schema.messages.len();
+                    self.schema.messages.push(Message { name: full.clone(), fields: Vec::new() });
+                    let mut nested = Message { name: full.clone(), fields: Vec::new() };
+                    self.parse_body(&full, Some(&mut nested))?;
+                    self.schema.messages[index] = nested;
+                }
+                "enum" => {
+                    self.position += 1;
+                    let name = self.next()?;
+                    let full = if scope.is_empty() { name } else { format!("{}.{}", scope, name) };
+                    let values = self.parse_enum()?;
+                    self.schema.enums.insert(full, values);
+                }
+                "oneof" => {
+                    // Its fields belong to the enclosing message
+                    self.position += 2;
+                    self.expect("{")?;
+                    while self.peek() != Some("}") {
+                        if self.peek() == Some("option") {
+                            self.skip_statement()?;
+                        } else {
+                            fields.push(self.parse_field()?);
+                        }
+                    }
+                    self.position += 1;
+                }
+                "syntax" | "edition" | "import" | "option" | "reserved" | "extensions" | "service" | "extend" | ";" => self.skip_statement()?,
+                _ if message.is_some() => fields.push(self.parse_field()?),
+                _ => return Err(format!("Unexpected '{}' at the top level of the .proto file", token)),
+            }
+        }
+        if let Some(message) = message {
+            if !closed {
+                return Err(format!("Message {} is missing its closing brace", message.name));
+            }
+            message.fields = fields;
+        }
+        Ok(())
+    }
+
+    fn parse_enum(&mut self) -> Result<Vec<(String, i64)>, String> {
+        self.expect("{")?;
+        let mut values = Vec::new();
+        loop {
+            let token = self.next()?;
+            match token.as_str() {
+                "}" => return Ok(values),
+                "option" | "reserved" => {
+                    self.position -= 1;
+                    self.skip_statement()?;
+                }
+                ";" => {}
+                _ => {
+                    self.expect("=")?;
+                    values.push((token, self.number()?));
+                    self.skip_statement()?;
+                }
+            }
+        }
+    }
+
+    // [label] type name = number [options];  or  map<K, V> name = number;
+    fn parse_field(&mut self) -> Result<FieldDef, String> {
+        let mut token = self.next()?;
+        let mut repeated = false;
+        if matches!(token.as_str(), "repeated" | "optional" | "required") {
+            repeated = token == "repeated";
+            token = self.next()?;
+        }
+        let mut map = None;
+        if token == "map" && self.peek() == Some("<") {
+            self.position += 1;
+            let key = self.next()?;
+            self.expect(",")?;
+            let value = self.next()?;
+            self.expect(">")?;
+            map = Some((key, value));
+            repeated = true;
+        }
+        if token == "group" {
+            return Err("proto2 groups in the .proto file are not supported".to_string());
+        }
+        let name = self.next()?;
+        self.expect("=")?;
+        let number = self.number()?;
+        let number = u32::try_from(number).map_err(|_| format!("Invalid field number {} for '{}'", number, name))?;
+        // Options like [packed = true] up to the ;
+        self.skip_statement()?;
+        Ok(FieldDef { name, number, kind: token, repeated, map })
+    }
+}
+
+pub fn parse_proto(source: &str) -> Result<Schema, String> {
+    let mut parser = Parser { tokens: tokenize(source)?, position: 0, schema: Schema::default() };
+    parser.parse_body("", None)?;
+    Ok(parser.schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROTO: &str = r#"
+syntax = "proto3";
+package shop;
+import "google/protobuf/timestamp.proto";
+
+/* An order */
+message Order {
+  option deprecated = false;
+  int64 id = 1;
+  repeated Item items = 2 [deprecated = true];
+  Status status = 3;
+  map<string, int32> counts = 4;
+  oneof payment {
+    string card = 5;
+    string voucher = 6;
+  }
+  reserved 7, 8;
+
+  message Item {
+    string sku = 1; // stock keeping unit
+    sint32 quantity = 2;
+  }
+}
+
+enum Status {
+  STATUS_UNKNOWN = 0;
+  SHIPPED = 0x2 [(custom) = "x"];
+}
+
+service Orders {
+  rpc Get (Order) returns (Order) { option (google.api.http) = { get: "/v1" }; }
+}
+"#;
+
+    #[test]
+    fn test_parse_proto() {
+        let schema = parse_proto(PROTO).unwrap();
+        let names: Vec<&str> = schema.messages.iter().map(|message| message.name.as_str()).collect();
+        assert_eq!(names, ["shop.Order", "shop.Order.Item"]);
+        let order = schema.message("shop.Order").unwrap();
+        let fields: Vec<(&str, u32, &str, bool)> = order.fields.iter().map(|f| (f.name.as_str(), f.number, f.kind.as_str(), f.repeated)).collect();
+        assert_eq!(
+            fields,
+            [
+                ("id", 1, "int64", false),
+                ("items", 2, "Item", true),
+                ("status", 3, "Status", false),
+                ("counts", 4, "map", true),
+                ("card", 5, "string", false),
+                ("voucher", 6, "string", false)
+            ]
+        );
+        assert_eq!(order.field(4).unwrap().map, Some(("string".to_string(), "int32".to_string())));
+        assert_eq!(schema.enums["shop.Status"], [("STATUS_UNKNOWN".to_string(), 0), ("SHIPPED".to_string(), 2)]);
+    }
+
+    #[test]
+    fn test_resolve_and_find() {
+        let schema = parse_proto(PROTO).unwrap();
+        assert!(matches!(schema.resolve("Item", "shop.Order"), Some(Resolved::Message(message)) if message.name == "shop.Order.Item"));
+        assert!(matches!(schema.resolve("Status", "shop.Order"), Some(Resolved::Enum(_))));
+        assert!(matches!(schema.resolve(".shop.Order", "other"), Some(Resolved::Message(_))));
+        assert!(schema.resolve("Item", "shop").is_none());
+        assert_eq!(schema.find_message("Order.Item").unwrap().name, "shop.Order.Item");
+        assert!(schema.find_message("Missing").is_err());
+        assert!(parse_proto("message A { int32 a = ; }").is_err());
+        assert!(parse_proto("message A { /* open").is_err());
+        assert_eq!(parse_proto("message A { int32 a = 1;").unwrap_err(), "Message A is missing its closing brace");
+    }
+}
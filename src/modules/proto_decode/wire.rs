@@ -0,0 +1,104 @@
+// The protobuf wire format: a key varint (field number << 3 | wire type) before every value
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WireValue<'a> {
+    Varint(u64),
+    Fixed64(u64),
+    Fixed32(u32),
+    Bytes(&'a [u8]),
+    // Deprecated proto2 groups, delimited by start and end markers instead of a length
+    Group(Vec<Field<'a>>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field<'a> {
+    pub number: u32,
+    pub value: WireValue<'a>,
+}
+
+const MAX_FIELD_NUMBER: u64 = (1 << 29) - 1;
+
+pub fn read_varint(bytes: &[u8], position: &mut usize) -> Result<u64, String> {
+    let start = *position;
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *bytes.get(*position).ok_or_else(|| format!("Truncated varint at byte {}", start))?;
+        *position += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(format!("Varint longer than 10 bytes at byte {}", start))
+}
+
+fn take<'a>(bytes: &'a [u8], position: &mut usize, count: usize) -> Result<&'a [u8], String> {
+    let end = position.checked_add(count).filter(|end| *end <= bytes.len());
+    let end = end.ok_or_else(|| format!("Field at byte {} needs {} bytes but only {} are left", position, count, bytes.len() - *position))?;
+    let slice = &bytes[*position..end];
+    *position = end;
+    Ok(slice)
+}
+
+pub fn parse_message(bytes: &[u8]) -> Result<Vec<Field<'_>>, String> {
+    let mut position = 0;
+    parse_fields(bytes, &mut position, None)
+}
+
+fn parse_fields<'a>(bytes: &'a [u8], position: &mut usize, group: Option<u32>) -> Result<Vec<Field<'a>>, String> {
+    let mut fields = Vec::new();
+    while *position < bytes.len() {
+        let start = *position;
+        let key = read_varint(bytes, position)?;
+        let number = key >> 3;
+        if number == 0 || number > MAX_FIELD_NUMBER {
+            return Err(format!("Invalid field number {} at byte {}", number, start));
+        }
+        let number = number as u32;
+        let value = match key & 7 {
+            0 => WireValue::Varint(read_varint(bytes, position)?),
+            1 => WireValue::Fixed64(u64::from_le_bytes(take(bytes, position, 8)?.try_into().unwrap_or_default())),
+            2 => {
+                let length = read_varint(bytes, position)?;
+                let length = usize::try_from(length).map_err(|_| format!("Length {} at byte {} is too large", length, start))?;
+                WireValue::Bytes(take(bytes, position, length)?)
+            }
+            3 => WireValue::Group(parse_fields(bytes, position, Some(number))?),
+            4 if group == Some(number) => return Ok(fields),
+            5 => WireValue::Fixed32(u32::from_le_bytes(take(bytes, position, 4)?.try_into().unwrap_or_default())),
+            wire_type => return Err(format!("Unexpected wire type {} for field {} at byte {}", wire_type, number, start)),
+        };
+        fields.push(Field { number, value });
+    }
+    match group {
+        Some(number) => Err(format!("Group {} is never closed", number)),
+        None => Ok(fields),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_message() {
+        // The examples from the protobuf encoding guide: 150, "testing" and a nested message
+        let fields = parse_message(b"\x08\x96\x01\x12\x07testing\x1a\x03\x08\x96\x01").unwrap();
+        assert_eq!(fields[0], Field { number: 1, value: WireValue::Varint(150) });
+        assert_eq!(fields[1], Field { number: 2, value: WireValue::Bytes(b"testing") });
+        assert_eq!(fields[2], Field { number: 3, value: WireValue::Bytes(b"\x08\x96\x01") });
+        let fields = parse_message(b"\x0d\x00\x00\x80\x3f\x11\x00\x00\x00\x00\x00\x00\xf0\x3f\x1b\x08\x01\x1c").unwrap();
+        assert_eq!(fields[0].value, WireValue::Fixed32(0x3f80_0000));
+        assert_eq!(fields[1].value, WireValue::Fixed64(0x3ff0_0000_0000_0000));
+        assert_eq!(fields[2].value, WireValue::Group(vec![Field { number: 1, value: WireValue::Varint(1) }]));
+    }
+
+    #[test]
+    fn test_invalid_messages() {
+        assert_eq!(parse_message(b"\x00\x01").unwrap_err(), "Invalid field number 0 at byte 0");
+        assert!(parse_message(b"\x08\x96").unwrap_err().starts_with("Truncated varint"));
+        assert!(parse_message(b"\x12\x05ab").unwrap_err().contains("needs 5 bytes"));
+        assert!(parse_message(b"\x0f").unwrap_err().starts_with("Unexpected wire type 7"));
+        assert_eq!(parse_message(b"\x1b\x08\x01").unwrap_err(), "Group 3 is never closed");
+    }
+}
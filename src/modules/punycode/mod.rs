@@ -0,0 +1,104 @@
+use crate::tool_module::ToolModule;
+use crate::unicode_text::mixed_script_words;
+use clap::{Arg, ArgMatches, Command};
+use std::error::Error;
+
+pub struct PunycodeModule;
+
+impl ToolModule for PunycodeModule {
+    fn name(&self) -> &'static str {
+        "punycode"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("punycode-encode")
+                .long("punycode-encode")
+                .value_name("DOMAIN")
+                .help("Convert an internationalized domain to its ASCII form (münchen.de → xn--mnchen-3ya.de)")
+                .long_help("Convert an internationalized domain name to the ASCII form DNS uses. Each label is handled on its own: ASCII labels are only lowercased, the rest are normalized (IDNA/UTS #46) and Punycode-encoded with an xn-- prefix. For an email address only the part after @ is converted.")
+        )
+        .arg(
+            Arg::new("punycode-decode")
+                .long("punycode-decode")
+                .value_name("DOMAIN")
+                .help("Convert an xn-- domain back to Unicode (xn--mnchen-3ya.de → münchen.de)")
+                .long_help("Convert the xn-- labels of a domain name back to Unicode; other labels are kept as they are. For an email address only the part after @ is converted. Warns when a decoded label mixes scripts, a common trick for lookalike phishing domains.")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(domain) = matches.get_one::<String>("punycode-encode") {
+            println!("{}", domain_to_ascii(domain.trim())?);
+        }
+        if let Some(domain) = matches.get_one::<String>("punycode-decode") {
+            let decoded = domain_to_unicode(domain.trim())?;
+            println!("{}", decoded);
+            let suspicious = mixed_script_words(&decoded.replace('.', " "));
+            if !suspicious.is_empty() {
+                eprintln!("⚠️  Mixes scripts, possibly a lookalike domain: {}", suspicious.join(", "));
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn domain_to_ascii(input: &str) -> Result<String, String> {
+    convert_domain(input, |domain| idna::domain_to_ascii(domain).map_err(|e| e.to_string()))
+}
+
+pub fn domain_to_unicode(input: &str) -> Result<String, String> {
+    convert_domain(input, |domain| match idna::domain_to_unicode(domain) {
+        (decoded, Ok(())) => Ok(decoded),
+        (_, Err(e)) => Err(e.to_string()),
+    })
+}
+
+// Converts the domain of "domain" or "user@domain"; on failure the error names the label at fault
+fn convert_domain(input: &str, convert: impl Fn(&str) -> Result<String, String>) -> Result<String, String> {
+    let (local, domain) = match input.rsplit_once('@') {
+        Some((local, domain)) => (Some(local), domain),
+        None => (None, input),
+    };
+    if domain.is_empty() {
+        return Err("Domain cannot be empty".to_string());
+    }
+    let converted = convert(domain).map_err(|e| {
+        match domain.split('.').find(|label| convert(label).is_err()) {
+            Some(label) => format!("Invalid label '{}' in {}", label, domain),
+            None => format!("Invalid domain {}: {}", domain, e),
+        }
+    })?;
+    Ok(match local {
+        Some(local) => format!("{}@{}", local, converted),
+        None => converted,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_domain_to_ascii() {
+        assert_eq!(domain_to_ascii("münchen.de").unwrap(), "xn--mnchen-3ya.de");
+        assert_eq!(domain_to_ascii("Bücher.Example.COM").unwrap(), "xn--bcher-kva.example.com");
+        assert_eq!(domain_to_ascii("例え.テスト").unwrap(), "xn--r8jz45g.xn--zckzah");
+        assert_eq!(domain_to_ascii("example.com").unwrap(), "example.com");
+    }
+
+    #[test]
+    fn test_domain_to_unicode() {
+        assert_eq!(domain_to_unicode("xn--mnchen-3ya.de").unwrap(), "münchen.de");
+        assert_eq!(domain_to_unicode("www.xn--r8jz45g.xn--zckzah").unwrap(), "www.例え.テスト");
+        let error = domain_to_unicode("xn--mnchen-3ya.xn--99.de").unwrap_err();
+        assert_eq!(error, "Invalid label 'xn--99' in xn--mnchen-3ya.xn--99.de");
+    }
+
+    #[test]
+    fn test_email_local_part_kept() {
+        assert_eq!(domain_to_ascii("Jörg@bücher.de").unwrap(), "Jörg@xn--bcher-kva.de");
+        assert_eq!(domain_to_unicode("info@xn--bcher-kva.de").unwrap(), "info@bücher.de");
+        assert!(domain_to_ascii("user@").is_err());
+    }
+}
@@ -0,0 +1,799 @@
+use image::GrayImage;
+use std::error::Error;
+
+// QR code reader for clean, roughly flat images such as screenshots.
+// Locates the three finder patterns, maps the module grid with an affine
+// transform, then unmasks, error-corrects and decodes the data segments.
+
+const ALPHANUMERIC: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+// Indexed by error correction level (L, M, Q, H) and version
+const ECC_CODEWORDS_PER_BLOCK: [[usize; 41]; 4] = [
+    [0, 7, 10, 15, 20, 26, 18, 20, 24, 30, 18, 20, 24, 26, 30, 22, 24, 28, 30, 28, 28, 28, 28, 30, 30, 26, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30],
+    [0, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26, 30, 22, 22, 24, 24, 28, 28, 26, 26, 26, 26, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28],
+    [0, 13, 22, 18, 26, 18, 24, 18, 22, 20, 24, 28, 26, 24, 20, 30, 24, 28, 28, 26, 30, 28, 30, 30, 30, 30, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30],
+    [0, 17, 28, 22, 16, 22, 28, 26, 26, 24, 28, 24, 28, 22, 24, 24, 30, 28, 28, 26, 28, 30, 24, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30],
+];
+
+const NUM_ERROR_CORRECTION_BLOCKS: [[usize; 41]; 4] = [
+    [0, 1, 1, 1, 1, 1, 2, 2, 2, 2, 4, 4, 4, 4, 4, 6, 6, 6, 6, 7, 8, 8, 9, 9, 10, 12, 12, 12, 13, 14, 15, 16, 17, 18, 19, 19, 20, 21, 22, 24, 25],
+    [0, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5, 5, 8, 9, 9, 10, 10, 11, 13, 14, 16, 17, 17, 18, 20, 21, 23, 25, 26, 28, 29, 31, 33, 35, 37, 38, 40, 43, 45, 47, 49],
+    [0, 1, 1, 2, 2, 4, 4, 6, 6, 8, 8, 8, 10, 12, 16, 12, 17, 16, 18, 21, 20, 23, 23, 25, 27, 29, 34, 34, 35, 38, 40, 43, 45, 48, 51, 53, 56, 59, 62, 65, 68],
+    [0, 1, 1, 2, 4, 4, 4, 5, 6, 8, 8, 11, 11, 16, 16, 18, 16, 19, 21, 25, 25, 25, 34, 30, 32, 35, 37, 40, 42, 45, 48, 51, 54, 57, 60, 63, 66, 70, 74, 77, 81],
+];
+
+pub fn decode_qr_file(path: &str) -> Result<String, Box<dyn Error>> {
+    let image = image::open(path).map_err(|e| format!("Failed to open image '{}': {}", path, e))?;
+    decode_qr_image(&image.to_luma8())
+}
+
+pub fn decode_qr_image(image: &GrayImage) -> Result<String, Box<dyn Error>> {
+    let bitmap = Bitmap::from_gray(image);
+    let candidates = find_finder_patterns(&bitmap);
+    let (top_left, top_right, bottom_left) = select_finder_triple(&candidates)
+        .ok_or("No QR code found in image")?;
+
+    let module = (top_left.module + top_right.module + bottom_left.module) / 3.0;
+    let span = (distance(&top_left, &top_right) + distance(&top_left, &bottom_left)) / 2.0;
+    let estimated = ((span / module + 7.0 - 17.0) / 4.0).round() as i32;
+
+    let mut last_error: Box<dyn Error> = "Failed to decode QR code".into();
+    // The version estimate can be off by one or two for small or blurry codes
+    for offset in [0, -1, 1, -2, 2] {
+        let version = estimated + offset;
+        if !(1..=40).contains(&version) {
+            continue;
+        }
+        let grid = sample_grid(&bitmap, &top_left, &top_right, &bottom_left, version as usize);
+        match decode_grid(&grid, version as usize) {
+            Ok(text) => return Ok(text),
+            Err(e) => last_error = e,
+        }
+    }
+    Err(last_error)
+}
+
+struct Bitmap {
+    width: usize,
+    height: usize,
+    dark: Vec<bool>,
+}
+
+impl Bitmap {
+    fn from_gray(image: &GrayImage) -> Self {
+        let (min, max) = image.pixels().fold((u8::MAX, u8::MIN), |(lo, hi), p| (lo.min(p[0]), hi.max(p[0])));
+        let threshold = (min as u16 + max as u16) / 2;
+        Self {
+            width: image.width() as usize,
+            height: image.height() as usize,
+            dark: image.pixels().map(|p| (p[0] as u16) <= threshold).collect(),
+        }
+    }
+
+    fn get(&self, x: i64, y: i64) -> bool {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return false;
+        }
+        self.dark[y as usize * self.width + x as usize]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FinderPattern {
+    x: f64,
+    y: f64,
+    module: f64,
+    count: usize,
+}
+
+fn distance(a: &FinderPattern, b: &FinderPattern) -> f64 {
+    ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt()
+}
+
+fn is_finder_ratio(runs: &[usize; 5]) -> bool {
+    let total: usize = runs.iter().sum();
+    if total < 7 || runs.contains(&0) {
+        return false;
+    }
+    let unit = total as f64 / 7.0;
+    let variance = unit / 2.0;
+    (unit - runs[0] as f64).abs() < variance
+        && (unit - runs[1] as f64).abs() < variance
+        && (3.0 * unit - runs[2] as f64).abs() < 3.0 * variance
+        && (unit - runs[3] as f64).abs() < variance
+        && (unit - runs[4] as f64).abs() < variance
+}
+
+// Measures the 1:1:3:1:1 pattern through (x, y) along (dx, dy), returning the
+// centre offset along the line and the total pattern length
+fn cross_check(bitmap: &Bitmap, x: i64, y: i64, dx: i64, dy: i64) -> Option<(f64, usize)> {
+    if !bitmap.get(x, y) {
+        return None;
+    }
+    let run = |start: i64, step: i64, dark: bool| -> usize {
+        let mut count = 0;
+        let mut i = start;
+        while count < 10_000 && bitmap.get(x + dx * i, y + dy * i) == dark && in_bounds(bitmap, x + dx * i, y + dy * i) {
+            count += 1;
+            i += step;
+        }
+        count
+    };
+
+    let center_back = run(0, -1, true);
+    let inner_back = run(-(center_back as i64), -1, false);
+    let outer_back = run(-((center_back + inner_back) as i64), -1, true);
+    let center_forward = run(1, 1, true);
+    let inner_forward = run(1 + center_forward as i64, 1, false);
+    let outer_forward = run(1 + (center_forward + inner_forward) as i64, 1, true);
+
+    let runs = [outer_back, inner_back, center_back + center_forward, inner_forward, outer_forward];
+    if !is_finder_ratio(&runs) {
+        return None;
+    }
+    let center_start = 1 - center_back as i64;
+    Some((center_start as f64 + runs[2] as f64 / 2.0 - 0.5, runs.iter().sum()))
+}
+
+fn in_bounds(bitmap: &Bitmap, x: i64, y: i64) -> bool {
+    x >= 0 && y >= 0 && x < bitmap.width as i64 && y < bitmap.height as i64
+}
+
+fn find_finder_patterns(bitmap: &Bitmap) -> Vec<FinderPattern> {
+    let mut patterns: Vec<FinderPattern> = Vec::new();
+
+    for y in 0..bitmap.height {
+        let row = &bitmap.dark[y * bitmap.width..(y + 1) * bitmap.width];
+
+        // Run-length encode the row as (start, length, dark)
+        let mut runs: Vec<(usize, usize, bool)> = Vec::new();
+        for (x, &dark) in row.iter().enumerate() {
+            match runs.last_mut() {
+                Some(last) if last.2 == dark => last.1 += 1,
+                _ => runs.push((x, 1, dark)),
+            }
+        }
+
+        for window in runs.windows(5) {
+            if !window[0].2 {
+                continue;
+            }
+            let lengths = [window[0].1, window[1].1, window[2].1, window[3].1, window[4].1];
+            if !is_finder_ratio(&lengths) {
+                continue;
+            }
+            let horizontal_total: usize = lengths.iter().sum();
+            let center_x = (window[2].0 + window[2].1 / 2) as i64;
+
+            let Some((offset_y, vertical_total)) = cross_check(bitmap, center_x, y as i64, 0, 1) else {
+                continue;
+            };
+            if 5 * vertical_total.abs_diff(horizontal_total) >= 2 * horizontal_total {
+                continue;
+            }
+            let center_y = y as f64 + offset_y;
+            let Some((offset_x, refined_total)) = cross_check(bitmap, center_x, center_y.round() as i64, 1, 0) else {
+                continue;
+            };
+            let center_x = center_x as f64 + offset_x;
+            let module = (refined_total + vertical_total) as f64 / 14.0;
+
+            match patterns.iter_mut().find(|p| {
+                (p.x - center_x).abs() <= module * 2.0 && (p.y - center_y).abs() <= module * 2.0
+            }) {
+                Some(existing) => {
+                    let n = existing.count as f64;
+                    existing.x = (existing.x * n + center_x) / (n + 1.0);
+                    existing.y = (existing.y * n + center_y) / (n + 1.0);
+                    existing.module = (existing.module * n + module) / (n + 1.0);
+                    existing.count += 1;
+                }
+                None => patterns.push(FinderPattern { x: center_x, y: center_y, module, count: 1 }),
+            }
+        }
+    }
+
+    patterns
+}
+
+// Picks the three candidates that best form the right-angled corner layout
+// and returns them as (top-left, top-right, bottom-left)
+fn select_finder_triple(candidates: &[FinderPattern]) -> Option<(FinderPattern, FinderPattern, FinderPattern)> {
+    let mut sorted: Vec<&FinderPattern> = candidates.iter().collect();
+    sorted.sort_by_key(|p| std::cmp::Reverse(p.count));
+    sorted.truncate(10);
+
+    let mut best: Option<(f64, [&FinderPattern; 3])> = None;
+    for i in 0..sorted.len() {
+        for j in i + 1..sorted.len() {
+            for k in j + 1..sorted.len() {
+                let triple = [sorted[i], sorted[j], sorted[k]];
+                let modules: Vec<f64> = triple.iter().map(|p| p.module).collect();
+                let max_module = modules.iter().cloned().fold(f64::MIN, f64::max);
+                let min_module = modules.iter().cloned().fold(f64::MAX, f64::min);
+                if max_module > min_module * 1.5 {
+                    continue;
+                }
+
+                // The corner is opposite the longest side
+                let sides = [
+                    (distance(triple[1], triple[2]), 0),
+                    (distance(triple[0], triple[2]), 1),
+                    (distance(triple[0], triple[1]), 2),
+                ];
+                let (hypotenuse, corner) = sides.iter().cloned().fold((f64::MIN, 0), |a, b| if b.0 > a.0 { b } else { a });
+                let legs: Vec<f64> = sides.iter().filter(|s| s.1 != corner).map(|s| s.0).collect();
+                if legs[0] < min_module * 10.0 || legs[1] < min_module * 10.0 {
+                    continue;
+                }
+                let score = (legs[0] - legs[1]).abs() / legs[0].max(legs[1])
+                    + (hypotenuse - (legs[0].powi(2) + legs[1].powi(2)).sqrt()).abs() / hypotenuse;
+                if score > 0.25 {
+                    continue;
+                }
+
+                let others: Vec<&FinderPattern> = (0..3).filter(|&n| n != corner).map(|n| triple[n]).collect();
+                let ordered = [triple[corner], others[0], others[1]];
+                if best.as_ref().is_none_or(|(s, _)| score < *s) {
+                    best = Some((score, ordered));
+                }
+            }
+        }
+    }
+
+    let (_, [top_left, a, b]) = best?;
+    // Orient so that top-right follows top-left clockwise in image coordinates
+    let cross = (a.x - top_left.x) * (b.y - top_left.y) - (a.y - top_left.y) * (b.x - top_left.x);
+    if cross > 0.0 {
+        Some((top_left.clone(), a.clone(), b.clone()))
+    } else {
+        Some((top_left.clone(), b.clone(), a.clone()))
+    }
+}
+
+fn sample_grid(
+    bitmap: &Bitmap,
+    top_left: &FinderPattern,
+    top_right: &FinderPattern,
+    bottom_left: &FinderPattern,
+    version: usize,
+) -> Vec<Vec<bool>> {
+    let size = 17 + 4 * version;
+    let span = (size - 7) as f64;
+    let (ux, uy) = ((top_right.x - top_left.x) / span, (top_right.y - top_left.y) / span);
+    let (vx, vy) = ((bottom_left.x - top_left.x) / span, (bottom_left.y - top_left.y) / span);
+
+    (0..size)
+        .map(|row| {
+            (0..size)
+                .map(|col| {
+                    // Finder centres sit at module coordinate 3.5
+                    let mx = col as f64 - 3.0;
+                    let my = row as f64 - 3.0;
+                    let px = top_left.x + mx * ux + my * vx;
+                    let py = top_left.y + mx * uy + my * vy;
+                    bitmap.get(px.round() as i64, py.round() as i64)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn alignment_positions(version: usize) -> Vec<usize> {
+    if version == 1 {
+        return Vec::new();
+    }
+    let size = 17 + 4 * version;
+    let count = version / 7 + 2;
+    let step = (version * 8 + count * 3 + 5) / (count * 4 - 4) * 2;
+    let mut positions: Vec<usize> = (0..count - 1).map(|i| size - 7 - i * step).collect();
+    positions.push(6);
+    positions.reverse();
+    positions
+}
+
+fn function_modules(version: usize) -> Vec<Vec<bool>> {
+    let size = 17 + 4 * version;
+    let mut function = vec![vec![false; size]; size];
+    let mut mark = |x: usize, y: usize, width: usize, height: usize| {
+        for row in function.iter_mut().skip(y).take(height) {
+            for cell in row.iter_mut().skip(x).take(width) {
+                *cell = true;
+            }
+        }
+    };
+
+    // Finder patterns with separators and format information
+    mark(0, 0, 9, 9);
+    mark(size - 8, 0, 8, 9);
+    mark(0, size - 8, 9, 8);
+    // Timing patterns
+    mark(6, 0, 1, size);
+    mark(0, 6, size, 1);
+
+    let positions = alignment_positions(version);
+    let last = positions.len().saturating_sub(1);
+    for (i, &y) in positions.iter().enumerate() {
+        for (j, &x) in positions.iter().enumerate() {
+            // Skip the three corners occupied by finder patterns
+            if matches!((i, j), (0, 0)) || (i == 0 && j == last) || (i == last && j == 0) {
+                continue;
+            }
+            mark(x - 2, y - 2, 5, 5);
+        }
+    }
+
+    if version >= 7 {
+        mark(size - 11, 0, 3, 6);
+        mark(0, size - 11, 6, 3);
+    }
+    function
+}
+
+fn num_raw_data_modules(version: usize) -> usize {
+    let mut result = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let count = version / 7 + 2;
+        result -= (25 * count - 10) * count - 55;
+        if version >= 7 {
+            result -= 36;
+        }
+    }
+    result
+}
+
+// Returns (error correction level index L/M/Q/H, mask pattern)
+fn read_format(grid: &[Vec<bool>]) -> Result<(usize, usize), Box<dyn Error>> {
+    let size = grid.len();
+    let bit = |x: usize, y: usize| grid[y][x] as u32;
+
+    let mut first = 0u32;
+    let mut coordinates: Vec<(usize, usize)> = (0..6).map(|i| (i, 8)).collect();
+    coordinates.extend([(7, 8), (8, 8), (8, 7)]);
+    coordinates.extend((0..6).rev().map(|j| (8, j)));
+    for (x, y) in coordinates {
+        first = (first << 1) | bit(x, y);
+    }
+
+    let mut second = 0u32;
+    let mut coordinates: Vec<(usize, usize)> = (size - 7..size).rev().map(|j| (8, j)).collect();
+    coordinates.extend((size - 8..size).map(|i| (i, 8)));
+    for (x, y) in coordinates {
+        second = (second << 1) | bit(x, y);
+    }
+
+    let mut best: Option<(u32, u32)> = None;
+    for data in 0..32u32 {
+        let mut remainder = data << 10;
+        for i in (10..15).rev() {
+            if remainder & (1 << i) != 0 {
+                remainder ^= 0x537 << (i - 10);
+            }
+        }
+        let codeword = ((data << 10) | remainder) ^ 0x5412;
+        let distance = (codeword ^ first).count_ones().min((codeword ^ second).count_ones());
+        if best.is_none_or(|(d, _)| distance < d) {
+            best = Some((distance, data));
+        }
+    }
+
+    match best {
+        Some((distance, data)) if distance <= 3 => {
+            let level = match data >> 3 {
+                1 => 0,
+                0 => 1,
+                3 => 2,
+                _ => 3,
+            };
+            Ok((level, (data & 7) as usize))
+        }
+        _ => Err("Could not read QR format information".into()),
+    }
+}
+
+fn is_masked(mask: usize, x: usize, y: usize) -> bool {
+    match mask {
+        0 => (x + y).is_multiple_of(2),
+        1 => y.is_multiple_of(2),
+        2 => x.is_multiple_of(3),
+        3 => (x + y).is_multiple_of(3),
+        4 => (x / 3 + y / 2).is_multiple_of(2),
+        5 => x * y % 2 + x * y % 3 == 0,
+        6 => (x * y % 2 + x * y % 3).is_multiple_of(2),
+        _ => ((x + y) % 2 + x * y % 3).is_multiple_of(2),
+    }
+}
+
+fn decode_grid(grid: &[Vec<bool>], version: usize) -> Result<String, Box<dyn Error>> {
+    let size = grid.len();
+    let (level, mask) = read_format(grid)?;
+    let function = function_modules(version);
+
+    // Read codewords in the zig-zag column-pair order
+    let raw_count = num_raw_data_modules(version) / 8;
+    let mut raw = vec![0u8; raw_count];
+    let mut bit_index = 0;
+    let mut right = size as i64 - 1;
+    while right >= 1 {
+        if right == 6 {
+            right = 5;
+        }
+        let upward = ((right + 1) & 2) == 0;
+        for vertical in 0..size {
+            for j in 0..2 {
+                let x = (right - j) as usize;
+                let y = if upward { size - 1 - vertical } else { vertical };
+                if function[y][x] || bit_index >= raw_count * 8 {
+                    continue;
+                }
+                if grid[y][x] ^ is_masked(mask, x, y) {
+                    raw[bit_index / 8] |= 1 << (7 - bit_index % 8);
+                }
+                bit_index += 1;
+            }
+        }
+        right -= 2;
+    }
+
+    // De-interleave the blocks and correct errors
+    let block_count = NUM_ERROR_CORRECTION_BLOCKS[level][version];
+    let ecc_length = ECC_CODEWORDS_PER_BLOCK[level][version];
+    let short_blocks = block_count - raw_count % block_count;
+    let short_length = raw_count / block_count;
+
+    let mut blocks: Vec<Vec<u8>> = vec![Vec::new(); block_count];
+    let mut codewords = raw.iter();
+    for i in 0..=short_length {
+        for (j, block) in blocks.iter_mut().enumerate() {
+            if i == short_length - ecc_length && j < short_blocks {
+                continue;
+            }
+            if let Some(&value) = codewords.next() {
+                block.push(value);
+            }
+        }
+    }
+
+    let mut data = Vec::new();
+    for mut block in blocks {
+        correct_errors(&mut block, ecc_length)?;
+        let data_length = block.len() - ecc_length;
+        data.extend_from_slice(&block[..data_length]);
+    }
+
+    decode_segments(&data, version)
+}
+
+struct Galois {
+    exp: [u8; 512],
+    log: [u8; 256],
+}
+
+impl Galois {
+    fn new() -> Self {
+        let mut exp = [0u8; 512];
+        let mut log = [0u8; 256];
+        let mut value: u16 = 1;
+        for (i, slot) in exp.iter_mut().enumerate().take(255) {
+            *slot = value as u8;
+            log[value as usize] = i as u8;
+            value <<= 1;
+            if value & 0x100 != 0 {
+                value ^= 0x11d;
+            }
+        }
+        for i in 255..512 {
+            exp[i] = exp[i - 255];
+        }
+        Self { exp, log }
+    }
+
+    fn mul(&self, a: u8, b: u8) -> u8 {
+        if a == 0 || b == 0 {
+            return 0;
+        }
+        self.exp[self.log[a as usize] as usize + self.log[b as usize] as usize]
+    }
+
+    fn div(&self, a: u8, b: u8) -> u8 {
+        if a == 0 {
+            return 0;
+        }
+        self.exp[(self.log[a as usize] as usize + 255 - self.log[b as usize] as usize) % 255]
+    }
+
+    fn pow(&self, exponent: i64) -> u8 {
+        self.exp[exponent.rem_euclid(255) as usize]
+    }
+
+    // Evaluates a polynomial stored lowest power first
+    fn eval(&self, poly: &[u8], x: u8) -> u8 {
+        poly.iter().rev().fold(0, |acc, &c| self.mul(acc, x) ^ c)
+    }
+}
+
+// Reed-Solomon decoding with Berlekamp-Massey and Forney
+fn correct_errors(block: &mut [u8], ecc_length: usize) -> Result<(), Box<dyn Error>> {
+    let gf = Galois::new();
+    let n = block.len();
+
+    let syndromes: Vec<u8> = (0..ecc_length)
+        .map(|i| block.iter().fold(0, |acc, &c| gf.mul(acc, gf.pow(i as i64)) ^ c))
+        .collect();
+    if syndromes.iter().all(|&s| s == 0) {
+        return Ok(());
+    }
+
+    let mut locator = vec![1u8];
+    let mut previous = vec![1u8];
+    let mut errors = 0;
+    let mut shift = 1;
+    let mut previous_discrepancy = 1u8;
+    for step in 0..ecc_length {
+        let mut discrepancy = syndromes[step];
+        for i in 1..=errors.min(locator.len() - 1) {
+            discrepancy ^= gf.mul(locator[i], syndromes[step - i]);
+        }
+        if discrepancy == 0 {
+            shift += 1;
+            continue;
+        }
+        let factor = gf.div(discrepancy, previous_discrepancy);
+        let mut updated = locator.clone();
+        if updated.len() < previous.len() + shift {
+            updated.resize(previous.len() + shift, 0);
+        }
+        for (i, &c) in previous.iter().enumerate() {
+            updated[i + shift] ^= gf.mul(factor, c);
+        }
+        if 2 * errors <= step {
+            errors = step + 1 - errors;
+            previous = locator;
+            previous_discrepancy = discrepancy;
+            shift = 1;
+        } else {
+            shift += 1;
+        }
+        locator = updated;
+    }
+    if 2 * errors > ecc_length {
+        return Err("Too many errors to correct in QR code".into());
+    }
+
+    // Error evaluator: syndromes * locator mod x^ecc_length
+    let mut evaluator = vec![0u8; ecc_length];
+    for (i, &s) in syndromes.iter().enumerate() {
+        for (j, &l) in locator.iter().enumerate() {
+            if i + j < ecc_length {
+                evaluator[i + j] ^= gf.mul(s, l);
+            }
+        }
+    }
+    let derivative: Vec<u8> = locator.iter().enumerate().skip(1)
+        .map(|(i, &c)| if i % 2 == 1 { c } else { 0 })
+        .collect();
+
+    let mut found = 0;
+    for (position, value) in block.iter_mut().enumerate() {
+        let power = (n - 1 - position) as i64;
+        let x_inverse = gf.pow(-power);
+        if gf.eval(&locator, x_inverse) != 0 {
+            continue;
+        }
+        let denominator = gf.eval(&derivative, x_inverse);
+        if denominator == 0 {
+            return Err("Failed to correct QR code errors".into());
+        }
+        let magnitude = gf.mul(gf.pow(power), gf.div(gf.eval(&evaluator, x_inverse), denominator));
+        *value ^= magnitude;
+        found += 1;
+    }
+    if found != errors {
+        return Err("Failed to correct QR code errors".into());
+    }
+    Ok(())
+}
+
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl BitReader<'_> {
+    fn remaining(&self) -> usize {
+        self.data.len() * 8 - self.position
+    }
+
+    fn read(&mut self, count: usize) -> Result<u32, Box<dyn Error>> {
+        if count > self.remaining() {
+            return Err("Unexpected end of QR data".into());
+        }
+        let mut value = 0u32;
+        for _ in 0..count {
+            let bit = (self.data[self.position / 8] >> (7 - self.position % 8)) & 1;
+            value = (value << 1) | bit as u32;
+            self.position += 1;
+        }
+        Ok(value)
+    }
+}
+
+fn decode_segments(data: &[u8], version: usize) -> Result<String, Box<dyn Error>> {
+    let mut reader = BitReader { data, position: 0 };
+    let mut bytes = Vec::new();
+    let size_class = match version {
+        1..=9 => 0,
+        10..=26 => 1,
+        _ => 2,
+    };
+
+    while reader.remaining() >= 4 {
+        match reader.read(4)? {
+            0b0000 => break,
+            0b0001 => {
+                let count = reader.read([10, 12, 14][size_class])? as usize;
+                let mut remaining = count;
+                while remaining >= 3 {
+                    bytes.extend(format!("{:03}", reader.read(10)?).bytes());
+                    remaining -= 3;
+                }
+                if remaining == 2 {
+                    bytes.extend(format!("{:02}", reader.read(7)?).bytes());
+                } else if remaining == 1 {
+                    bytes.extend(format!("{}", reader.read(4)?).bytes());
+                }
+            }
+            0b0010 => {
+                let count = reader.read([9, 11, 13][size_class])? as usize;
+                for _ in 0..count / 2 {
+                    let value = reader.read(11)? as usize;
+                    let (first, second) = (value / 45, value % 45);
+                    if first >= 45 {
+                        return Err("Invalid alphanumeric data in QR code".into());
+                    }
+                    bytes.push(ALPHANUMERIC[first]);
+                    bytes.push(ALPHANUMERIC[second]);
+                }
+                if count % 2 == 1 {
+                    let value = reader.read(6)? as usize;
+                    bytes.push(*ALPHANUMERIC.get(value).ok_or("Invalid alphanumeric data in QR code")?);
+                }
+            }
+            0b0100 => {
+                let count = reader.read([8, 16, 16][size_class])?;
+                for _ in 0..count {
+                    bytes.push(reader.read(8)? as u8);
+                }
+            }
+            0b0111 => {
+                // ECI designator; payloads are treated as UTF-8 regardless
+                let first = reader.read(8)?;
+                if first & 0x80 == 0x80 {
+                    reader.read(if first & 0xc0 == 0xc0 { 16 } else { 8 })?;
+                }
+            }
+            0b0011 => {
+                reader.read(16)?;
+            }
+            0b0101 => {}
+            0b1001 => {
+                reader.read(8)?;
+            }
+            0b1000 => return Err("Kanji mode QR codes are not supported".into()),
+            mode => return Err(format!("Unsupported QR data mode: {:04b}", mode).into()),
+        }
+    }
+
+    Ok(match String::from_utf8(bytes) {
+        Ok(text) => text,
+        // Fall back to ISO-8859-1, the QR default character set
+        Err(e) => e.into_bytes().iter().map(|&b| b as char).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{imageops, Luma};
+    use qrcode::{Color, EcLevel, QrCode, Version};
+
+    fn render(code: &QrCode, scale: u32) -> GrayImage {
+        let width = code.width() as u32;
+        let colors = code.to_colors();
+        let quiet = 4;
+        let size = (width + quiet * 2) * scale;
+        GrayImage::from_fn(size, size, |x, y| {
+            let (mx, my) = (x / scale, y / scale);
+            if mx < quiet || my < quiet || mx >= width + quiet || my >= width + quiet {
+                return Luma([255]);
+            }
+            match colors[((my - quiet) * width + (mx - quiet)) as usize] {
+                Color::Dark => Luma([0]),
+                Color::Light => Luma([255]),
+            }
+        })
+    }
+
+    #[test]
+    fn test_decode_simple_text() {
+        let code = QrCode::new("Hello, World!").unwrap();
+        assert_eq!(decode_qr_image(&render(&code, 4)).unwrap(), "Hello, World!");
+    }
+
+    #[test]
+    fn test_decode_all_error_correction_levels() {
+        for level in [EcLevel::L, EcLevel::M, EcLevel::Q, EcLevel::H] {
+            let code = QrCode::with_error_correction_level("https://example.com/path?q=1", level).unwrap();
+            assert_eq!(decode_qr_image(&render(&code, 3)).unwrap(), "https://example.com/path?q=1");
+        }
+    }
+
+    #[test]
+    fn test_decode_numeric_and_alphanumeric() {
+        let code = QrCode::new("0123456789012").unwrap();
+        assert_eq!(decode_qr_image(&render(&code, 4)).unwrap(), "0123456789012");
+        let code = QrCode::new("HELLO WORLD 123").unwrap();
+        assert_eq!(decode_qr_image(&render(&code, 4)).unwrap(), "HELLO WORLD 123");
+    }
+
+    #[test]
+    fn test_decode_large_versions() {
+        let text = "otpauth://totp/Example:alice@example.com?secret=JBSWY3DPEHPK3PXP&issuer=Example ".repeat(6);
+        let code = QrCode::with_error_correction_level(text.as_bytes(), EcLevel::M).unwrap();
+        assert!(matches!(code.version(), Version::Normal(v) if v >= 10));
+        assert_eq!(decode_qr_image(&render(&code, 3)).unwrap(), text);
+
+        let code = QrCode::with_version("version seven", Version::Normal(7), EcLevel::L).unwrap();
+        assert_eq!(decode_qr_image(&render(&code, 3)).unwrap(), "version seven");
+    }
+
+    #[test]
+    fn test_decode_unicode() {
+        let code = QrCode::new("Hello 🌍 café").unwrap();
+        assert_eq!(decode_qr_image(&render(&code, 4)).unwrap(), "Hello 🌍 café");
+    }
+
+    #[test]
+    fn test_decode_rotated() {
+        let code = QrCode::new("rotated payload").unwrap();
+        let image = imageops::rotate90(&render(&code, 4));
+        assert_eq!(decode_qr_image(&image).unwrap(), "rotated payload");
+    }
+
+    #[test]
+    fn test_decode_with_damaged_modules() {
+        let code = QrCode::with_error_correction_level("error correction test", EcLevel::H).unwrap();
+        let mut image = render(&code, 4);
+        // Paint over a block of data modules near the bottom-right corner
+        let size = image.width();
+        for y in size - 40..size - 24 {
+            for x in size - 40..size - 24 {
+                image.put_pixel(x, y, Luma([0]));
+            }
+        }
+        assert_eq!(decode_qr_image(&image).unwrap(), "error correction test");
+    }
+
+    #[test]
+    fn test_decode_no_qr_code() {
+        let blank = GrayImage::from_pixel(100, 100, Luma([255]));
+        assert!(decode_qr_image(&blank).is_err());
+    }
+
+    #[test]
+    fn test_data_module_counts() {
+        for version in 1..=40 {
+            let function = function_modules(version);
+            let data_modules = function.iter().flatten().filter(|&&f| !f).count();
+            assert_eq!(data_modules, num_raw_data_modules(version), "version {}", version);
+        }
+    }
+
+    #[test]
+    fn test_alignment_positions() {
+        assert!(alignment_positions(1).is_empty());
+        assert_eq!(alignment_positions(2), vec![6, 18]);
+        assert_eq!(alignment_positions(7), vec![6, 22, 38]);
+        assert_eq!(alignment_positions(32), vec![6, 34, 60, 86, 112, 138]);
+    }
+}
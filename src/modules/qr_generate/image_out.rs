@@ -0,0 +1,90 @@
+use image::{GrayImage, ImageFormat, Luma};
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+
+// Writes a grid of dark (true) and light modules to a PNG or SVG file, chosen
+// by extension. Shared by the QR and barcode generators; the caller includes
+// the quiet zone. Modules are `module_width` × `module_height` pixels, so a
+// one-row barcode can be stretched into tall bars.
+pub fn save_modules(path: &str, rows: &[Vec<bool>], module_width: u32, module_height: u32) -> Result<(), String> {
+    let extension = Path::new(path).extension().and_then(|extension| extension.to_str()).unwrap_or_default().to_lowercase();
+    let bytes = match extension.as_str() {
+        "png" => modules_png(rows, module_width, module_height)?,
+        "svg" => modules_svg(rows, module_width, module_height).into_bytes(),
+        _ => return Err(format!("Cannot write '{}': use a .png or .svg file name", path)),
+    };
+    if Path::new(path).exists() {
+        return Err(format!("{} already exists; not overwriting it", path));
+    }
+    fs::write(path, bytes).map_err(|e| format!("Failed to write {}: {}", path, e))
+}
+
+fn modules_png(rows: &[Vec<bool>], module_width: u32, module_height: u32) -> Result<Vec<u8>, String> {
+    let columns = rows.first().map_or(0, Vec::len) as u32;
+    let image = GrayImage::from_fn(columns * module_width, rows.len() as u32 * module_height, |x, y| {
+        let dark = rows[(y / module_height) as usize][(x / module_width) as usize];
+        Luma([if dark { 0 } else { 255 }])
+    });
+    let mut bytes = Cursor::new(Vec::new());
+    image.write_to(&mut bytes, ImageFormat::Png).map_err(|e| format!("Failed to encode PNG: {}", e))?;
+    Ok(bytes.into_inner())
+}
+
+// One path of horizontal runs in module units, scaled by the width and height attributes
+fn modules_svg(rows: &[Vec<bool>], module_width: u32, module_height: u32) -> String {
+    let columns = rows.first().map_or(0, Vec::len);
+    let mut path = String::new();
+    for (y, row) in rows.iter().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            let run = row[x..].iter().take_while(|&&dark| dark == row[x]).count();
+            if row[x] {
+                path.push_str(&format!("M{},{}h{}v1h-{}z", x, y, run, run));
+            }
+            x += run;
+        }
+    }
+    format!(
+        concat!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}" preserveAspectRatio="none" shape-rendering="crispEdges">"#,
+            r##"<rect width="100%" height="100%" fill="#fff"/><path fill="#000" d="{}"/></svg>"##,
+            "\n"
+        ),
+        columns as u32 * module_width,
+        rows.len() as u32 * module_height,
+        columns,
+        rows.len(),
+        path
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_modules_png_scales_modules() {
+        let rows = vec![vec![true, false, true]];
+        let png = modules_png(&rows, 2, 5).unwrap();
+        let image = image::load_from_memory(&png).unwrap().to_luma8();
+        assert_eq!(image.dimensions(), (6, 5));
+        assert_eq!(image.get_pixel(1, 4).0, [0]);
+        assert_eq!(image.get_pixel(2, 0).0, [255]);
+        assert_eq!(image.get_pixel(5, 2).0, [0]);
+    }
+
+    #[test]
+    fn test_modules_svg_merges_runs() {
+        let rows = vec![vec![true, true, false, true], vec![false, false, false, false]];
+        let svg = modules_svg(&rows, 3, 3);
+        assert!(svg.contains(r#"width="12" height="6" viewBox="0 0 4 2""#));
+        assert!(svg.contains(r#"d="M0,0h2v1h-2zM3,0h1v1h-1z""#));
+    }
+
+    #[test]
+    fn test_save_modules_checks_extension() {
+        let rows = vec![vec![true]];
+        assert!(save_modules("code.gif", &rows, 1, 1).unwrap_err().contains(".png or .svg"));
+    }
+}
@@ -1,10 +1,22 @@
 use crate::tool_module::ToolModule;
-use clap::{Arg, ArgMatches, Command};
-use qrcode::{QrCode, render::unicode};
+use crate::url_encode::{url_decode, url_encode};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use qrcode::{Color, EcLevel, QrCode, Version};
+use serde::Serialize;
 use std::error::Error;
+use url::Url;
+
+mod decode;
+mod image_out;
+
+pub use decode::{decode_qr_file, decode_qr_image};
+pub use image_out::save_modules;
 
 pub struct QrGenerateModule;
 
+// Module size in pixels for --qr-out PNGs
+const QR_MODULE_PIXELS: u32 = 8;
+
 impl ToolModule for QrGenerateModule {
     fn name(&self) -> &'static str {
         "qr-generate"
@@ -18,24 +30,578 @@ impl ToolModule for QrGenerateModule {
                 .help("Generate QR code for text or URL")
                 .long_help("Generate a QR code as ASCII art for the given text or URL. Perfect for terminal display.")
         )
+        .arg(
+            Arg::new("qr-decode")
+                .long("qr-decode")
+                .value_name("IMAGE")
+                .help("Decode a QR code from an image file (PNG, JPEG, GIF, BMP, WebP)")
+                .long_help("Locate and decode a QR code in an image file, such as a screenshot of a 2FA enrollment code, and print its payload. Use --otpauth to break otpauth:// URIs down into their components.")
+        )
+        .arg(
+            Arg::new("otpauth")
+                .long("otpauth")
+                .help("Parse a decoded otpauth:// URI into its components (JSON)")
+                .action(ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("ec")
+                .long("ec")
+                .value_name("LEVEL")
+                .help("QR error correction level: L, M (default), Q or H")
+                .long_help("QR error correction level: L (~7% recovery), M (~15%, default), Q (~25%) or H (~30%). Higher levels survive damaged prints but produce larger codes.")
+                .value_parser(["L", "M", "Q", "H", "l", "m", "q", "h"])
+        )
+        .arg(
+            Arg::new("qr-version")
+                .long("qr-version")
+                .value_name("VERSION")
+                .help("Minimum QR version (1-40); larger data still grows the code")
+                .value_parser(clap::value_parser!(i16).range(1..=40))
+        )
+        .arg(
+            Arg::new("quiet-zone")
+                .long("quiet-zone")
+                .value_name("MODULES")
+                .help("Quiet zone width around the QR code in modules (default: 4)")
+                .value_parser(clap::value_parser!(u32).range(0..=16))
+        )
+        .arg(
+            Arg::new("invert")
+                .long("invert")
+                .help("Draw dark QR modules as blocks, or dark --img-ascii pixels as dense characters, for light-background terminals")
+                .long_help("Draw dark QR modules as filled blocks instead of light ones, and dark pixels of --img-ascii art as dense characters instead of light ones. The default suits dark terminals; use --invert on light-background terminals or when a phone camera struggles to scan the code.")
+                .action(ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("qr-style")
+                .long("qr-style")
+                .value_name("STYLE")
+                .help("QR rendering style: half (default), quarter or full")
+                .long_help("QR rendering style:\n- half: two module rows per line with half blocks (default)\n- quarter: 2x2 modules per character, the most compact\n- full: two characters per module, largest but easiest to scan")
+                .value_parser(["half", "quarter", "full"])
+        )
+        .arg(
+            Arg::new("qr-out")
+                .long("qr-out")
+                .value_name("FILE")
+                .help("Save the QR code as a PNG or SVG image instead of printing it")
+                .long_help("Save the generated QR code to FILE instead of printing it; the format follows the extension (.png or .svg). Dark modules are black on white with the --quiet-zone border, whatever --invert says. Existing files are never overwritten.")
+        )
+        .arg(
+            Arg::new("qr-wifi")
+                .long("qr-wifi")
+                .value_names(["SSID", "PASSWORD", "AUTH"])
+                .num_args(2..=3)
+                .help("Generate a WiFi QR code (AUTH: WPA (default), WEP or nopass)")
+                .long_help("Generate a QR code that joins a WiFi network when scanned. AUTH is WPA (default, also covers WPA2/WPA3), WEP or nopass for open networks.")
+        )
+        .arg(
+            Arg::new("qr-vcard")
+                .long("qr-vcard")
+                .value_names(["NAME", "PHONE", "EMAIL", "ORG"])
+                .num_args(1..=4)
+                .help("Generate a vCard contact QR code")
+        )
+        .arg(
+            Arg::new("qr-geo")
+                .long("qr-geo")
+                .value_names(["LATITUDE", "LONGITUDE"])
+                .num_args(2)
+                .allow_negative_numbers(true)
+                .help("Generate a geo location QR code")
+        )
+        .arg(
+            Arg::new("qr-mailto")
+                .long("qr-mailto")
+                .value_names(["EMAIL", "SUBJECT", "BODY"])
+                .num_args(1..=3)
+                .help("Generate an email QR code with optional subject and body")
+        )
+        .arg(
+            Arg::new("qr-sms")
+                .long("qr-sms")
+                .value_names(["NUMBER", "MESSAGE"])
+                .num_args(1..=2)
+                .help("Generate an SMS QR code with optional message")
+        )
+        .arg(
+            Arg::new("qr-tel")
+                .long("qr-tel")
+                .value_name("NUMBER")
+                .help("Generate a phone call QR code")
+        )
+        .arg(
+            Arg::new("qr-epc")
+                .long("qr-epc")
+                .value_names(["NAME", "IBAN", "AMOUNT", "REFERENCE", "BIC"])
+                .num_args(3..=5)
+                .help("Generate an EPC/SEPA credit transfer QR code (amount in EUR)")
+                .long_help("Generate an EPC (GiroCode) QR code for SEPA credit transfers, scannable by most European banking apps. AMOUNT is in EUR; REFERENCE is an optional remittance text and BIC is optional within the EEA.")
+        )
     }
 
     fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
-        if let Some(text) = matches.get_one::<String>("qr-generate") {
-            let qr_ascii = generate_qr_ascii(text)?;
-            println!("{}", qr_ascii);
+        if let Some(text) = qr_payload(matches)? {
+            let options = QrOptions {
+                ec_level: parse_ec_level(matches.get_one::<String>("ec").map(|s| s.as_str()).unwrap_or("M"))?,
+                min_version: matches.get_one::<i16>("qr-version").copied(),
+                quiet_zone: matches.get_one::<u32>("quiet-zone").copied().unwrap_or(4),
+                invert: matches.get_flag("invert"),
+                style: QrStyle::parse(matches.get_one::<String>("qr-style").map(|s| s.as_str()).unwrap_or("half"))?,
+            };
+            if let Some(out) = matches.get_one::<String>("qr-out") {
+                save_modules(out, &qr_modules(&text, &options)?, QR_MODULE_PIXELS, QR_MODULE_PIXELS)?;
+                println!("✅ Saved QR code to {}", out);
+            } else {
+                let qr_ascii = generate_qr_ascii(&text, &options)?;
+                println!("{}", qr_ascii);
+            }
+        } else if let Some(path) = matches.get_one::<String>("qr-decode") {
+            let payload = decode_qr_file(path)?;
+            if matches.get_flag("otpauth") {
+                let parsed = parse_otpauth(&payload)?;
+                println!("{}", serde_json::to_string_pretty(&parsed)?);
+            } else {
+                println!("{}", payload);
+            }
         }
         Ok(())
     }
 }
 
-fn generate_qr_ascii(text: &str) -> Result<String, Box<dyn Error>> {
-    let code = QrCode::new(text)?;
-    let image = code.render::<unicode::Dense1x2>()
-        .dark_color(unicode::Dense1x2::Light)
-        .light_color(unicode::Dense1x2::Dark)
-        .build();
-    Ok(image)
+fn qr_payload(matches: &ArgMatches) -> Result<Option<String>, Box<dyn Error>> {
+    let values = |id: &str| -> Option<Vec<String>> {
+        matches.get_many::<String>(id).map(|v| v.cloned().collect())
+    };
+
+    if let Some(text) = matches.get_one::<String>("qr-generate") {
+        return Ok(Some(text.clone()));
+    }
+    if let Some(v) = values("qr-wifi") {
+        return Ok(Some(wifi_payload(&v[0], &v[1], v.get(2).map(|s| s.as_str()).unwrap_or("WPA"))?));
+    }
+    if let Some(v) = values("qr-vcard") {
+        let field = |i: usize| v.get(i).map(|s| s.as_str());
+        return Ok(Some(vcard_payload(&v[0], field(1), field(2), field(3))));
+    }
+    if let Some(v) = values("qr-geo") {
+        return Ok(Some(geo_payload(&v[0], &v[1])?));
+    }
+    if let Some(v) = values("qr-mailto") {
+        let field = |i: usize| v.get(i).map(|s| s.as_str());
+        return Ok(Some(mailto_payload(&v[0], field(1), field(2))));
+    }
+    if let Some(v) = values("qr-sms") {
+        return Ok(Some(sms_payload(&v[0], v.get(1).map(|s| s.as_str()))));
+    }
+    if let Some(number) = matches.get_one::<String>("qr-tel") {
+        return Ok(Some(format!("tel:{}", strip_phone(number))));
+    }
+    if let Some(v) = values("qr-epc") {
+        let field = |i: usize| v.get(i).map(|s| s.as_str());
+        return Ok(Some(epc_payload(&v[0], &v[1], &v[2], field(3), field(4))?));
+    }
+    Ok(None)
+}
+
+fn escape_wifi(value: &str) -> String {
+    let mut escaped = String::new();
+    for c in value.chars() {
+        if matches!(c, '\\' | ';' | ',' | ':' | '"') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+pub fn wifi_payload(ssid: &str, password: &str, auth: &str) -> Result<String, Box<dyn Error>> {
+    let auth = match auth.to_uppercase().as_str() {
+        "WPA" | "WPA2" | "WPA3" => "WPA",
+        "WEP" => "WEP",
+        "NOPASS" | "NONE" | "OPEN" => "nopass",
+        _ => return Err("Invalid WiFi authentication type. Use WPA, WEP or nopass".into()),
+    };
+    if ssid.is_empty() {
+        return Err("WiFi SSID cannot be empty".into());
+    }
+    if auth == "nopass" {
+        return Ok(format!("WIFI:T:nopass;S:{};;", escape_wifi(ssid)));
+    }
+    Ok(format!("WIFI:T:{};S:{};P:{};;", auth, escape_wifi(ssid), escape_wifi(password)))
+}
+
+fn escape_vcard(value: &str) -> String {
+    value.replace('\\', "\\\\").replace(',', "\\,").replace(';', "\\;")
+}
+
+pub fn vcard_payload(name: &str, phone: Option<&str>, email: Option<&str>, org: Option<&str>) -> String {
+    let name = name.trim();
+    let (first, last) = match name.rsplit_once(' ') {
+        Some((first, last)) => (first, last),
+        None => (name, ""),
+    };
+
+    let mut lines = vec![
+        "BEGIN:VCARD".to_string(),
+        "VERSION:3.0".to_string(),
+        format!("N:{};{};;;", escape_vcard(last), escape_vcard(first)),
+        format!("FN:{}", escape_vcard(name)),
+    ];
+    if let Some(phone) = phone.filter(|p| !p.is_empty()) {
+        lines.push(format!("TEL:{}", strip_phone(phone)));
+    }
+    if let Some(email) = email.filter(|e| !e.is_empty()) {
+        lines.push(format!("EMAIL:{}", escape_vcard(email)));
+    }
+    if let Some(org) = org.filter(|o| !o.is_empty()) {
+        lines.push(format!("ORG:{}", escape_vcard(org)));
+    }
+    lines.push("END:VCARD".to_string());
+    lines.join("\r\n")
+}
+
+pub fn geo_payload(latitude: &str, longitude: &str) -> Result<String, Box<dyn Error>> {
+    let lat: f64 = latitude.trim().parse().map_err(|_| "Invalid latitude")?;
+    let lon: f64 = longitude.trim().parse().map_err(|_| "Invalid longitude")?;
+    if !(-90.0..=90.0).contains(&lat) {
+        return Err("Latitude must be between -90 and 90".into());
+    }
+    if !(-180.0..=180.0).contains(&lon) {
+        return Err("Longitude must be between -180 and 180".into());
+    }
+    Ok(format!("geo:{},{}", lat, lon))
+}
+
+// Mail clients do not decode '+' as a space, so spaces become %20
+fn encode_mailto_component(value: &str) -> String {
+    url_encode(value).replace('+', "%20")
+}
+
+pub fn mailto_payload(email: &str, subject: Option<&str>, body: Option<&str>) -> String {
+    let mut params = Vec::new();
+    if let Some(subject) = subject.filter(|s| !s.is_empty()) {
+        params.push(format!("subject={}", encode_mailto_component(subject)));
+    }
+    if let Some(body) = body.filter(|b| !b.is_empty()) {
+        params.push(format!("body={}", encode_mailto_component(body)));
+    }
+    if params.is_empty() {
+        format!("mailto:{}", email.trim())
+    } else {
+        format!("mailto:{}?{}", email.trim(), params.join("&"))
+    }
+}
+
+fn strip_phone(number: &str) -> String {
+    number.chars().filter(|c| c.is_ascii_digit() || *c == '+').collect()
+}
+
+pub fn sms_payload(number: &str, message: Option<&str>) -> String {
+    match message {
+        Some(message) => format!("SMSTO:{}:{}", strip_phone(number), message),
+        None => format!("SMSTO:{}", strip_phone(number)),
+    }
+}
+
+fn is_valid_iban(iban: &str) -> bool {
+    if !(15..=34).contains(&iban.len()) || !iban.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return false;
+    }
+    // Move the country code and check digits to the end, then check mod 97
+    let rearranged = format!("{}{}", &iban[4..], &iban[..4]);
+    let mut remainder = 0u32;
+    for c in rearranged.chars() {
+        let value = c.to_digit(36).unwrap();
+        remainder = if value >= 10 {
+            (remainder * 100 + value) % 97
+        } else {
+            (remainder * 10 + value) % 97
+        };
+    }
+    remainder == 1
+}
+
+pub fn epc_payload(name: &str, iban: &str, amount: &str, reference: Option<&str>, bic: Option<&str>) -> Result<String, Box<dyn Error>> {
+    let iban: String = iban.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_uppercase();
+    if !is_valid_iban(&iban) {
+        return Err("Invalid IBAN".into());
+    }
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > 70 {
+        return Err("Beneficiary name must be between 1 and 70 characters".into());
+    }
+    let amount: f64 = amount.trim().parse().map_err(|_| "Invalid amount")?;
+    if !(0.01..=999_999_999.99).contains(&amount) {
+        return Err("Amount must be between 0.01 and 999999999.99 EUR".into());
+    }
+    let reference = reference.unwrap_or("");
+    if reference.chars().count() > 140 {
+        return Err("Remittance reference must be 140 characters or less".into());
+    }
+    let bic = bic.unwrap_or("").trim().to_uppercase();
+
+    let lines = [
+        "BCD".to_string(),
+        "002".to_string(),
+        "1".to_string(),
+        "SCT".to_string(),
+        bic,
+        name.to_string(),
+        iban,
+        format!("EUR{:.2}", amount),
+        String::new(),
+        String::new(),
+        reference.to_string(),
+    ];
+    Ok(lines.join("\n").trim_end().to_string())
+}
+
+pub struct QrOptions {
+    pub ec_level: EcLevel,
+    pub min_version: Option<i16>,
+    pub quiet_zone: u32,
+    pub invert: bool,
+    pub style: QrStyle,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QrStyle {
+    Half,
+    Quarter,
+    Full,
+}
+
+impl QrStyle {
+    fn parse(style: &str) -> Result<Self, Box<dyn Error>> {
+        match style.to_lowercase().as_str() {
+            "half" => Ok(QrStyle::Half),
+            "quarter" => Ok(QrStyle::Quarter),
+            "full" => Ok(QrStyle::Full),
+            _ => Err("Invalid QR style. Use half, quarter or full".into()),
+        }
+    }
+}
+
+impl Default for QrOptions {
+    fn default() -> Self {
+        Self {
+            ec_level: EcLevel::M,
+            min_version: None,
+            quiet_zone: 4,
+            invert: false,
+            style: QrStyle::Half,
+        }
+    }
+}
+
+fn parse_ec_level(level: &str) -> Result<EcLevel, Box<dyn Error>> {
+    match level.to_uppercase().as_str() {
+        "L" => Ok(EcLevel::L),
+        "M" => Ok(EcLevel::M),
+        "Q" => Ok(EcLevel::Q),
+        "H" => Ok(EcLevel::H),
+        _ => Err("Invalid error correction level. Use L, M, Q or H".into()),
+    }
+}
+
+fn build_qr_code(text: &str, options: &QrOptions) -> Result<QrCode, Box<dyn Error>> {
+    match options.min_version {
+        Some(min_version) => (min_version..=40)
+            .find_map(|v| QrCode::with_version(text, Version::Normal(v), options.ec_level).ok())
+            .ok_or_else(|| "Data is too long for a QR code at this error correction level".into()),
+        None => Ok(QrCode::with_error_correction_level(text, options.ec_level)?),
+    }
+}
+
+pub fn generate_qr_ascii(text: &str, options: &QrOptions) -> Result<String, Box<dyn Error>> {
+    let code = build_qr_code(text, options)?;
+    let grid = ModuleGrid::new(&code, options.quiet_zone, options.invert);
+    Ok(match options.style {
+        QrStyle::Half => render_half(&grid),
+        QrStyle::Quarter => render_quarter(&grid),
+        QrStyle::Full => render_full(&grid),
+    })
+}
+
+// Dark modules including the quiet zone, row by row, for image output
+fn qr_modules(text: &str, options: &QrOptions) -> Result<Vec<Vec<bool>>, Box<dyn Error>> {
+    let code = build_qr_code(text, options)?;
+    let grid = ModuleGrid::new(&code, options.quiet_zone, true);
+    let size = grid.size();
+    Ok((0..size).map(|y| (0..size).map(|x| grid.filled(x, y)).collect()).collect())
+}
+
+// Module colours including the quiet zone. `filled` reports whether a module
+// is drawn as a block: light modules by default, dark modules when inverted.
+struct ModuleGrid {
+    colors: Vec<Color>,
+    width: usize,
+    quiet: usize,
+    invert: bool,
+}
+
+impl ModuleGrid {
+    fn new(code: &QrCode, quiet_zone: u32, invert: bool) -> Self {
+        Self {
+            colors: code.to_colors(),
+            width: code.width(),
+            quiet: quiet_zone as usize,
+            invert,
+        }
+    }
+
+    fn size(&self) -> usize {
+        self.width + self.quiet * 2
+    }
+
+    fn filled(&self, x: usize, y: usize) -> bool {
+        let (quiet, width) = (self.quiet, self.width);
+        let light = x < quiet
+            || y < quiet
+            || x >= width + quiet
+            || y >= width + quiet
+            || self.colors[(y - quiet) * width + (x - quiet)] == Color::Light;
+        light != self.invert
+    }
+}
+
+// Renders two module rows per line with half-block characters. Without
+// --invert, dark modules are drawn as blanks and light modules as blocks,
+// which suits dark terminals.
+fn render_half(grid: &ModuleGrid) -> String {
+    let size = grid.size();
+    (0..size)
+        .step_by(2)
+        .map(|y| {
+            (0..size)
+                .map(|x| {
+                    let top = grid.filled(x, y);
+                    let bottom = y + 1 < size && grid.filled(x, y + 1);
+                    match (top, bottom) {
+                        (true, true) => '█',
+                        (true, false) => '▀',
+                        (false, true) => '▄',
+                        (false, false) => ' ',
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// Quadrant characters indexed by filled corners: top-left = 1, top-right = 2,
+// bottom-left = 4, bottom-right = 8
+const QUADRANTS: [char; 16] = [
+    ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█',
+];
+
+// Renders a 2x2 block of modules per character, halving the width again
+fn render_quarter(grid: &ModuleGrid) -> String {
+    let size = grid.size();
+    let filled = |x: usize, y: usize| x < size && y < size && grid.filled(x, y);
+    (0..size)
+        .step_by(2)
+        .map(|y| {
+            (0..size)
+                .step_by(2)
+                .map(|x| {
+                    let index = filled(x, y) as usize
+                        | (filled(x + 1, y) as usize) << 1
+                        | (filled(x, y + 1) as usize) << 2
+                        | (filled(x + 1, y + 1) as usize) << 3;
+                    QUADRANTS[index]
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// Renders one line per module row with two characters per module, which
+// keeps modules roughly square and is the easiest for cameras to scan
+fn render_full(grid: &ModuleGrid) -> String {
+    let size = grid.size();
+    (0..size)
+        .map(|y| {
+            (0..size)
+                .map(|x| if grid.filled(x, y) { "██" } else { "  " })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct OtpAuth {
+    #[serde(rename = "type")]
+    pub otp_type: String,
+    pub label: String,
+    pub issuer: Option<String>,
+    pub account: String,
+    pub secret: String,
+    pub algorithm: String,
+    pub digits: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub counter: Option<u64>,
+}
+
+pub fn parse_otpauth(uri: &str) -> Result<OtpAuth, Box<dyn Error>> {
+    let url = Url::parse(uri.trim()).map_err(|e| format!("Invalid otpauth URI: {}", e))?;
+    if url.scheme() != "otpauth" {
+        return Err("Not an otpauth:// URI".into());
+    }
+
+    let otp_type = url.host_str().unwrap_or_default().to_lowercase();
+    if otp_type != "totp" && otp_type != "hotp" {
+        return Err(format!("Unsupported OTP type '{}'. Expected totp or hotp", otp_type).into());
+    }
+
+    let label = url_decode(url.path().trim_start_matches('/'))?;
+    let (label_issuer, account) = match label.split_once(':') {
+        Some((issuer, account)) => (Some(issuer.trim().to_string()), account.trim().to_string()),
+        None => (None, label.clone()),
+    };
+
+    let mut secret = None;
+    let mut issuer = None;
+    let mut algorithm = "SHA1".to_string();
+    let mut digits = 6;
+    let mut period = None;
+    let mut counter = None;
+    for (key, value) in url.query_pairs() {
+        match key.to_lowercase().as_str() {
+            "secret" => secret = Some(value.to_uppercase()),
+            "issuer" => issuer = Some(value.to_string()),
+            "algorithm" => algorithm = value.to_uppercase(),
+            "digits" => digits = value.parse().map_err(|_| "Invalid digits parameter")?,
+            "period" => period = Some(value.parse().map_err(|_| "Invalid period parameter")?),
+            "counter" => counter = Some(value.parse().map_err(|_| "Invalid counter parameter")?),
+            _ => {}
+        }
+    }
+
+    if otp_type == "totp" && period.is_none() {
+        period = Some(30);
+    }
+    if otp_type == "hotp" && counter.is_none() {
+        return Err("HOTP URIs require a counter parameter".into());
+    }
+
+    Ok(OtpAuth {
+        otp_type,
+        label,
+        issuer: issuer.or(label_issuer),
+        account,
+        secret: secret.ok_or("otpauth URI is missing the secret parameter")?,
+        algorithm,
+        digits,
+        period,
+        counter,
+    })
 }
 
 #[cfg(test)]
@@ -44,7 +610,7 @@ mod tests {
 
     #[test]
     fn test_generate_qr_ascii_simple() {
-        let result = generate_qr_ascii("Hello");
+        let result = generate_qr_ascii("Hello", &QrOptions::default());
         assert!(result.is_ok());
         let qr_code = result.unwrap();
         assert!(!qr_code.is_empty());
@@ -54,7 +620,7 @@ mod tests {
 
     #[test]
     fn test_generate_qr_ascii_url() {
-        let result = generate_qr_ascii("https://github.com");
+        let result = generate_qr_ascii("https://github.com", &QrOptions::default());
         assert!(result.is_ok());
         let qr_code = result.unwrap();
         assert!(!qr_code.is_empty());
@@ -63,7 +629,7 @@ mod tests {
 
     #[test]
     fn test_generate_qr_ascii_empty() {
-        let result = generate_qr_ascii("");
+        let result = generate_qr_ascii("", &QrOptions::default());
         assert!(result.is_ok());
         let qr_code = result.unwrap();
         assert!(!qr_code.is_empty());
@@ -72,7 +638,7 @@ mod tests {
     #[test]
     fn test_generate_qr_ascii_long_text() {
         let long_text = "This is a very long text that will test the QR code generation with more data content to ensure it works properly with larger inputs";
-        let result = generate_qr_ascii(long_text);
+        let result = generate_qr_ascii(long_text, &QrOptions::default());
         assert!(result.is_ok());
         let qr_code = result.unwrap();
         assert!(!qr_code.is_empty());
@@ -83,7 +649,7 @@ mod tests {
     #[test]
     fn test_generate_qr_ascii_special_chars() {
         let special_text = "Hello 🌍! Special chars: @#$%^&*()";
-        let result = generate_qr_ascii(special_text);
+        let result = generate_qr_ascii(special_text, &QrOptions::default());
         assert!(result.is_ok());
         let qr_code = result.unwrap();
         assert!(!qr_code.is_empty());
@@ -93,7 +659,7 @@ mod tests {
     #[test]
     fn test_generate_qr_ascii_json() {
         let json_text = r#"{"name":"John","age":30,"city":"New York"}"#;
-        let result = generate_qr_ascii(json_text);
+        let result = generate_qr_ascii(json_text, &QrOptions::default());
         assert!(result.is_ok());
         let qr_code = result.unwrap();
         assert!(!qr_code.is_empty());
@@ -102,7 +668,7 @@ mod tests {
 
     #[test]
     fn test_qr_code_structure() {
-        let result = generate_qr_ascii("test");
+        let result = generate_qr_ascii("test", &QrOptions::default());
         assert!(result.is_ok());
         let qr_code = result.unwrap();
         
@@ -117,4 +683,203 @@ mod tests {
             }
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_render_matches_library_renderer() {
+        use qrcode::render::unicode;
+        for text in ["Hello", "https://github.com", ""] {
+            let code = QrCode::new(text).unwrap();
+            let expected = code.render::<unicode::Dense1x2>()
+                .dark_color(unicode::Dense1x2::Light)
+                .light_color(unicode::Dense1x2::Dark)
+                .build();
+            assert_eq!(render_half(&ModuleGrid::new(&code, 4, false)), expected);
+        }
+    }
+
+    #[test]
+    fn test_error_correction_levels() {
+        let low = generate_qr_ascii("error correction", &QrOptions { ec_level: EcLevel::L, ..Default::default() }).unwrap();
+        let high = generate_qr_ascii("error correction", &QrOptions { ec_level: EcLevel::H, ..Default::default() }).unwrap();
+        assert!(high.len() >= low.len());
+        assert!(parse_ec_level("q").is_ok());
+        assert!(parse_ec_level("X").is_err());
+    }
+
+    #[test]
+    fn test_minimum_version() {
+        let code = build_qr_code("hi", &QrOptions { min_version: Some(5), ..Default::default() }).unwrap();
+        assert_eq!(code.version(), Version::Normal(5));
+
+        let long_text = "x".repeat(200);
+        let code = build_qr_code(&long_text, &QrOptions { min_version: Some(1), ..Default::default() }).unwrap();
+        assert!(matches!(code.version(), Version::Normal(v) if v > 1));
+
+        let too_long = "x".repeat(5000);
+        assert!(build_qr_code(&too_long, &QrOptions { min_version: Some(1), ..Default::default() }).is_err());
+    }
+
+    #[test]
+    fn test_quiet_zone_width() {
+        let code = QrCode::new("quiet").unwrap();
+        let width = code.width();
+        let none = render_half(&ModuleGrid::new(&code, 0, false));
+        assert_eq!(none.lines().next().unwrap().chars().count(), width);
+        let wide = render_half(&ModuleGrid::new(&code, 8, false));
+        assert_eq!(wide.lines().next().unwrap().chars().count(), width + 16);
+        assert!(wide.lines().next().unwrap().chars().all(|c| c == '█'));
+    }
+
+    #[test]
+    fn test_invert_matches_library_renderer() {
+        use qrcode::render::unicode;
+        let code = QrCode::new("invert").unwrap();
+        let expected = code.render::<unicode::Dense1x2>().build();
+        // The library renders the odd padding row as light, so compare whole rows only
+        let rendered = render_half(&ModuleGrid::new(&code, 4, true));
+        let expected_lines: Vec<&str> = expected.lines().collect();
+        let rendered_lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(rendered_lines.len(), expected_lines.len());
+        assert_eq!(rendered_lines[..rendered_lines.len() - 1], expected_lines[..expected_lines.len() - 1]);
+    }
+
+    #[test]
+    fn test_quarter_and_full_styles() {
+        let code = QrCode::new("styles").unwrap();
+        let size = code.width() + 8;
+        let grid = ModuleGrid::new(&code, 4, false);
+
+        let quarter = render_quarter(&grid);
+        assert_eq!(quarter.lines().count(), size.div_ceil(2));
+        assert_eq!(quarter.lines().next().unwrap().chars().count(), size.div_ceil(2));
+
+        let full = render_full(&grid);
+        assert_eq!(full.lines().count(), size);
+        assert_eq!(full.lines().next().unwrap().chars().count(), size * 2);
+
+        // Top-left corner of the finder pattern sits on the quiet zone boundary
+        let inverted = render_full(&ModuleGrid::new(&code, 4, true));
+        assert!(inverted.lines().next().unwrap().trim().is_empty());
+        assert!(inverted.lines().nth(4).unwrap().contains("██"));
+
+        assert_eq!(QrStyle::parse("Quarter").unwrap(), QrStyle::Quarter);
+        assert!(QrStyle::parse("tiny").is_err());
+    }
+
+    #[test]
+    fn test_qr_out_png_decodes() {
+        let path = std::env::temp_dir().join(format!("micro-swiss-qr-out-{}.png", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let path = path.to_str().unwrap();
+        let modules = qr_modules("https://example.com/?q=1", &QrOptions::default()).unwrap();
+        assert_eq!(modules.len(), 25 + 8);
+        save_modules(path, &modules, QR_MODULE_PIXELS, QR_MODULE_PIXELS).unwrap();
+        assert_eq!(decode_qr_file(path).unwrap(), "https://example.com/?q=1");
+        assert!(save_modules(path, &modules, 1, 1).unwrap_err().contains("not overwriting"));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_wifi_payload() {
+        assert_eq!(wifi_payload("HomeNet", "secret", "WPA").unwrap(), "WIFI:T:WPA;S:HomeNet;P:secret;;");
+        assert_eq!(wifi_payload("Cafe", "", "nopass").unwrap(), "WIFI:T:nopass;S:Cafe;;");
+        assert_eq!(wifi_payload("a;b", "p:\"q\"", "wep").unwrap(), "WIFI:T:WEP;S:a\\;b;P:p\\:\\\"q\\\";;");
+        assert!(wifi_payload("Net", "pw", "WPA9").is_err());
+        assert!(wifi_payload("", "pw", "WPA").is_err());
+    }
+
+    #[test]
+    fn test_vcard_payload() {
+        let vcard = vcard_payload("Jane Doe", Some("+1 (555) 123-4567"), Some("jane@example.com"), Some("ACME, Inc"));
+        assert!(vcard.starts_with("BEGIN:VCARD\r\nVERSION:3.0\r\n"));
+        assert!(vcard.contains("N:Doe;Jane;;;"));
+        assert!(vcard.contains("FN:Jane Doe"));
+        assert!(vcard.contains("TEL:+15551234567"));
+        assert!(vcard.contains("EMAIL:jane@example.com"));
+        assert!(vcard.contains("ORG:ACME\\, Inc"));
+        assert!(vcard.ends_with("END:VCARD"));
+
+        let minimal = vcard_payload("Prince", None, None, None);
+        assert!(minimal.contains("N:;Prince;;;"));
+        assert!(!minimal.contains("TEL:"));
+    }
+
+    #[test]
+    fn test_geo_payload() {
+        assert_eq!(geo_payload("40.7128", "-74.006").unwrap(), "geo:40.7128,-74.006");
+        assert!(geo_payload("91", "0").is_err());
+        assert!(geo_payload("0", "181").is_err());
+        assert!(geo_payload("north", "0").is_err());
+    }
+
+    #[test]
+    fn test_mailto_payload() {
+        assert_eq!(mailto_payload("a@b.com", None, None), "mailto:a@b.com");
+        assert_eq!(
+            mailto_payload("a@b.com", Some("Hello there"), Some("1+1=2")),
+            "mailto:a@b.com?subject=Hello%20there&body=1%2B1%3D2"
+        );
+    }
+
+    #[test]
+    fn test_sms_and_tel_payloads() {
+        assert_eq!(sms_payload("+1 555 0100", Some("On my way")), "SMSTO:+15550100:On my way");
+        assert_eq!(sms_payload("5550100", None), "SMSTO:5550100");
+        assert_eq!(strip_phone("+44 (20) 7946-0958"), "+442079460958");
+    }
+
+    #[test]
+    fn test_epc_payload() {
+        let payload = epc_payload("Red Cross", "BE72 0000 0000 1616", "12.5", Some("Donation"), Some("bpotbeb1")).unwrap();
+        assert_eq!(payload, "BCD\n002\n1\nSCT\nBPOTBEB1\nRed Cross\nBE72000000001616\nEUR12.50\n\n\nDonation");
+
+        let payload = epc_payload("Red Cross", "BE72000000001616", "1", None, None).unwrap();
+        assert!(payload.ends_with("EUR1.00"));
+
+        assert!(epc_payload("Red Cross", "BE72000000001617", "1", None, None).is_err());
+        assert!(epc_payload("Red Cross", "BE72000000001616", "0", None, None).is_err());
+        assert!(epc_payload("", "BE72000000001616", "1", None, None).is_err());
+    }
+
+    #[test]
+    fn test_iban_validation() {
+        assert!(is_valid_iban("GB82WEST12345698765432"));
+        assert!(is_valid_iban("DE89370400440532013000"));
+        assert!(!is_valid_iban("GB82WEST12345698765431"));
+        assert!(!is_valid_iban("GB82"));
+    }
+
+    #[test]
+    fn test_parse_otpauth_totp() {
+        let parsed = parse_otpauth("otpauth://totp/Example:alice%40example.com?secret=jbswy3dpehpk3pxp&issuer=Example").unwrap();
+        assert_eq!(parsed.otp_type, "totp");
+        assert_eq!(parsed.label, "Example:alice@example.com");
+        assert_eq!(parsed.issuer.as_deref(), Some("Example"));
+        assert_eq!(parsed.account, "alice@example.com");
+        assert_eq!(parsed.secret, "JBSWY3DPEHPK3PXP");
+        assert_eq!(parsed.algorithm, "SHA1");
+        assert_eq!(parsed.digits, 6);
+        assert_eq!(parsed.period, Some(30));
+        assert_eq!(parsed.counter, None);
+    }
+
+    #[test]
+    fn test_parse_otpauth_hotp() {
+        let parsed = parse_otpauth("otpauth://hotp/bob?secret=ABC&counter=7&digits=8&algorithm=sha256").unwrap();
+        assert_eq!(parsed.otp_type, "hotp");
+        assert_eq!(parsed.issuer, None);
+        assert_eq!(parsed.account, "bob");
+        assert_eq!(parsed.counter, Some(7));
+        assert_eq!(parsed.digits, 8);
+        assert_eq!(parsed.algorithm, "SHA256");
+        assert_eq!(parsed.period, None);
+    }
+
+    #[test]
+    fn test_parse_otpauth_invalid() {
+        assert!(parse_otpauth("https://example.com").is_err());
+        assert!(parse_otpauth("otpauth://totp/label").is_err());
+        assert!(parse_otpauth("otpauth://hotp/label?secret=ABC").is_err());
+        assert!(parse_otpauth("otpauth://xotp/label?secret=ABC").is_err());
+    }
+}
@@ -0,0 +1,120 @@
+use crate::tool_module::ToolModule;
+use base64::{engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD}, Engine as _};
+use clap::{Arg, ArgMatches, Command};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+pub struct RandomBytesModule;
+
+// Printed values beyond this are a mistake; --bytes-out has no limit
+const MAX_PRINTED: usize = 1024 * 1024;
+const CHUNK: usize = 64 * 1024;
+
+impl ToolModule for RandomBytesModule {
+    fn name(&self) -> &'static str {
+        "random-bytes"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("random-bytes")
+                .long("random-bytes")
+                .value_name("COUNT")
+                .num_args(0..=1)
+                .default_missing_value("32")
+                .value_parser(clap::value_parser!(usize))
+                .help("Print COUNT random bytes from the OS CSPRNG as hex, base64 or base64url (default: 32)")
+                .long_help("Generate COUNT bytes (default 32, i.e. 256 bits) from the operating system's cryptographically secure random number generator, for API secrets, session keys and salts. Printed as hex unless --format base64 or --format base64url (unpadded, safe in URLs and file names) is given. With --bytes-out the raw bytes go to a file instead.")
+        )
+        .arg(
+            Arg::new("bytes-out")
+                .long("bytes-out")
+                .value_name("FILE")
+                .help("Write the raw --random-bytes to FILE instead of printing them")
+                .requires("random-bytes")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(&count) = matches.get_one::<usize>("random-bytes") {
+            if count == 0 {
+                return Err("Ask for at least one byte, e.g. --random-bytes 32".into());
+            }
+            // --format is declared by the text-diff module
+            let format = matches.get_one::<String>("format").map(String::as_str).unwrap_or("hex");
+            if let Some(path) = matches.get_one::<String>("bytes-out") {
+                write_random(path, count)?;
+                println!("✅ Wrote {} random bytes to {}", count, path);
+                return Ok(());
+            }
+            if count > MAX_PRINTED {
+                return Err(format!("{} bytes is too many to print; use --bytes-out FILE for more than {}", count, MAX_PRINTED).into());
+            }
+            let mut bytes = vec![0u8; count];
+            OsRng.try_fill_bytes(&mut bytes)?;
+            println!("{}", encode(&bytes, format)?);
+        }
+        Ok(())
+    }
+}
+
+fn encode(bytes: &[u8], format: &str) -> Result<String, String> {
+    match format {
+        "hex" => Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect()),
+        "base64" => Ok(STANDARD.encode(bytes)),
+        "base64url" => Ok(URL_SAFE_NO_PAD.encode(bytes)),
+        other => Err(format!("--format {} does not apply to --random-bytes; use hex, base64 or base64url", other)),
+    }
+}
+
+// Streams in chunks so large key files never sit in memory whole
+fn write_random(path: &str, count: usize) -> Result<(), Box<dyn Error>> {
+    if Path::new(path).exists() {
+        return Err(format!("{} already exists; not overwriting it", path).into());
+    }
+    let file = File::create(path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+    let mut writer = BufWriter::new(file);
+    let mut chunk = vec![0u8; CHUNK.min(count)];
+    let mut remaining = count;
+    while remaining > 0 {
+        let size = remaining.min(chunk.len());
+        OsRng.try_fill_bytes(&mut chunk[..size])?;
+        writer.write_all(&chunk[..size]).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+        remaining -= size;
+    }
+    writer.flush().map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_encode() {
+        let bytes = [0xfb, 0xff, 0x00, 0x10];
+        assert_eq!(encode(&bytes, "hex").unwrap(), "fbff0010");
+        assert_eq!(encode(&bytes, "base64").unwrap(), "+/8AEA==");
+        assert_eq!(encode(&bytes, "base64url").unwrap(), "-_8AEA");
+        assert!(encode(&bytes, "json").is_err());
+    }
+
+    #[test]
+    fn test_write_random() {
+        let path = std::env::temp_dir().join(format!("ms-random-bytes-{}.bin", std::process::id()));
+        let path = path.to_str().unwrap();
+        let _ = fs::remove_file(path);
+        write_random(path, CHUNK + 7).unwrap();
+        let bytes = fs::read(path).unwrap();
+        assert_eq!(bytes.len(), CHUNK + 7);
+        // Two chunks of the same stream must differ
+        assert_ne!(bytes[..7], bytes[CHUNK..]);
+        assert!(write_random(path, 1).unwrap_err().to_string().contains("not overwriting"));
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -1,9 +1,24 @@
-use crate::tool_module::ToolModule;
+use crate::config::load_config;
+use crate::tool_module::{ExitCodeError, ToolModule};
+use chrono::Local;
 use clap::{Arg, ArgMatches, Command};
 use colored::*;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::HashMap;
 use std::error::Error;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
 use std::process;
-use std::time::Instant;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Exit codes follow the shell conventions used by coreutils timeout and sh
+pub const TIMEOUT_EXIT_CODE: i32 = 124;
+pub const CANNOT_EXECUTE_EXIT_CODE: i32 = 126;
+pub const INTERPRETER_NOT_FOUND_EXIT_CODE: i32 = 127;
 
 pub struct RunFileModule;
 
@@ -19,7 +34,74 @@ impl ToolModule for RunFileModule {
                 .long("run")
                 .value_name("FILE")
                 .help("Run file based on extension")
-                .long_help("Execute a file using the appropriate interpreter based on its extension. Supports: .py (uv run), .js (node), .ts (deno), .go (go run), .mojo/🔥 (mojo). Shows execution time and handles exit codes properly.")
+                .long_help("Execute a file using the appropriate interpreter based on its extension. Supports: .py (uv run), .js (node), .ts (deno), .go (go run), .mojo/🔥 (mojo), .rs (rust-script or cargo -Zscript), .rb (ruby), .php (php), .sh (sh), .lua (lua), .zig (zig run), .java (java single-file source), .c/.cpp (compiled with cc/c++ to a temporary binary). Files without a known extension run via their shebang line. Shows execution time and handles exit codes properly.\n\nInterpreters can be overridden per extension in the config file (~/.config/micro-swiss/config.toml or $MICRO_SWISS_CONFIG):\n\n[run.interpreters]\npy = \"python3\"\nts = \"bun run\"")
+        )
+        .arg(
+            Arg::new("watch")
+                .long("watch")
+                .value_name("DIR")
+                .num_args(0..=1)
+                .default_missing_value("")
+                .help("Re-run the file on every change (optionally also watching DIR)")
+                .long_help("Watch the file passed to --run and re-run it whenever it changes, clearing the screen and showing the run time. Pass a directory to also re-run on any change beneath it, e.g. --run main.py --watch src. To re-run a shell command instead, use --watch-cmd.")
+                .requires("run")
+        )
+        .arg(
+            Arg::new("timeout")
+                .long("timeout")
+                .value_name("SECS")
+                .help("Kill the script after SECS seconds (exit code 124); also the connect timeout for --port-check and --port-scan, and how long --send serves (default 600)")
+                .value_parser(clap::value_parser!(f64))
+        )
+        .arg(
+            Arg::new("max-memory")
+                .long("max-memory")
+                .value_name("MB")
+                .help("Limit the script's address space to MB megabytes (Unix only)")
+                .value_parser(clap::value_parser!(u64).range(1..))
+        )
+        .arg(
+            Arg::new("max-cpu")
+                .long("max-cpu")
+                .value_name("SECS")
+                .help("Limit the script's CPU time to SECS seconds (Unix only)")
+                .value_parser(clap::value_parser!(u64).range(1..))
+        )
+        .arg(
+            Arg::new("env-file")
+                .long("env-file")
+                .value_name("FILE")
+                .help("Load environment variables for the script from a .env file (repeatable)")
+                .action(clap::ArgAction::Append)
+        )
+        .arg(
+            Arg::new("env")
+                .long("env")
+                .value_name("KEY=VALUE")
+                .help("Set an environment variable for the script (repeatable, overrides --env-file)")
+                .action(clap::ArgAction::Append)
+        )
+        .arg(
+            Arg::new("capture")
+                .long("capture")
+                .value_name("FILE")
+                .help("Tee the script's stdout/stderr to FILE with timestamps")
+        )
+        .arg(
+            Arg::new("stats")
+                .long("stats")
+                .value_name("FILE")
+                .num_args(0..=1)
+                .default_missing_value("")
+                .help("Print a JSON run summary (exit code, wall time, the program's peak RSS) to stderr or FILE")
+                .long_help("Print a JSON run summary of the --run file to stderr, or write it to FILE: the exit code, whether the timeout fired, the wall time and peak_rss_kb, the peak resident memory of the program's own process. On Linux the kernel also counts the memory micro-swiss itself used when it launched the program (roughly 30 MB), so treat peak_rss_kb as an upper bound for small programs. For statistics over a file of numbers, use --num-stats.")
+                .requires("run")
+        )
+        .arg(
+            Arg::new("run-doctor")
+                .long("run-doctor")
+                .help("Check which interpreters used by --run are installed")
+                .action(clap::ArgAction::SetTrue)
         )
         .arg(
             Arg::new("args")
@@ -33,7 +115,45 @@ impl ToolModule for RunFileModule {
         if let Some(file) = matches.get_one::<String>("run") {
             let args: Vec<String> = matches.get_many::<String>("args").unwrap_or_default().cloned().collect();
             let arg_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-            FileRunner::run(file, &arg_refs);
+            let config = load_config()?;
+            let timeout = match matches.get_one::<f64>("timeout") {
+                Some(secs) if *secs > 0.0 && secs.is_finite() => Some(Duration::from_secs_f64(*secs)),
+                Some(_) => return Err("Timeout must be a positive number of seconds".into()),
+                None => None,
+            };
+            let mut env = Vec::new();
+            for path in matches.get_many::<String>("env-file").unwrap_or_default() {
+                let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read env file {}: {}", path, e))?;
+                env.extend(parse_env_file(&content).map_err(|e| format!("{}: {}", path, e))?);
+            }
+            for assignment in matches.get_many::<String>("env").unwrap_or_default() {
+                env.push(parse_env_assignment(assignment)?);
+            }
+            let options = RunOptions {
+                interpreters: config.run.interpreters,
+                env,
+                timeout,
+                max_memory_mb: matches.get_one::<u64>("max-memory").copied(),
+                max_cpu_secs: matches.get_one::<u64>("max-cpu").copied(),
+                capture: matches.get_one::<String>("capture").map(PathBuf::from),
+                stats: matches.get_one::<String>("stats").map(|path| Some(PathBuf::from(path)).filter(|p| !p.as_os_str().is_empty())),
+            };
+            if let Some(path) = &options.capture {
+                // Start each invocation with a fresh log; watch-mode runs append to it
+                File::create(path).map_err(|e| format!("Failed to create capture file {}: {}", path.display(), e))?;
+            }
+            if let Some(watch_dir) = matches.get_one::<String>("watch") {
+                let watch_dir = Some(watch_dir.as_str()).filter(|d| !d.is_empty());
+                FileRunner::watch(file, &arg_refs, &options, watch_dir)?;
+            } else {
+                FileRunner::run(file, &arg_refs, &options)?;
+            }
+        } else if matches.get_flag("run-doctor") {
+            let config = load_config()?;
+            for (extension, program, found) in FileRunner::doctor(&config.run.interpreters) {
+                let state = if found { "ok".green().bold() } else { "missing".red().bold() };
+                println!(".{:<6} {:<12} {}", extension, program, state);
+            }
         }
         Ok(())
     }
@@ -41,66 +161,603 @@ impl ToolModule for RunFileModule {
 
 pub struct FileRunner;
 
+#[derive(Default)]
+pub struct RunOptions {
+    pub interpreters: HashMap<String, String>,
+    // Applied in order, so later entries win
+    pub env: Vec<(String, String)>,
+    pub timeout: Option<Duration>,
+    pub max_memory_mb: Option<u64>,
+    pub max_cpu_secs: Option<u64>,
+    pub capture: Option<PathBuf>,
+    // Some(None) prints the summary to stderr, Some(Some(path)) writes it to a file
+    pub stats: Option<Option<PathBuf>>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RunReport {
+    pub file: String,
+    pub exit_code: i32,
+    pub timed_out: bool,
+    pub wall_time_ms: u128,
+    // Peak resident set size of the program's own process, where the OS reports it.
+    // An upper bound on Linux, which counts our own memory at the time of exec too
+    pub peak_rss_kb: Option<u64>,
+}
+
+impl RunReport {
+    fn write_stats(&self, destination: &Option<PathBuf>) -> Result<(), Box<dyn Error>> {
+        let json = serde_json::to_string_pretty(self)?;
+        match destination {
+            Some(path) => std::fs::write(path, json + "\n").map_err(|e| format!("Failed to write stats to {}: {}", path.display(), e))?,
+            None => eprintln!("{}", json),
+        }
+        Ok(())
+    }
+}
+
+// How a file gets executed: handed to an interpreter, or compiled to a
+// temporary binary first (C/C++)
+#[derive(Debug, PartialEq)]
+enum Runner<'a> {
+    Interpret { command: &'a str, args: Vec<&'a str> },
+    Compile { compiler: &'static str, flags: Vec<&'static str> },
+}
+
+impl Runner<'_> {
+    fn program(&self) -> &str {
+        match self {
+            Runner::Interpret { command, .. } => command,
+            Runner::Compile { compiler, .. } => compiler,
+        }
+    }
+}
+
 impl FileRunner {
-    pub fn run(file: &str, args: &[&str]) {
+    // Runs the file and turns a non-zero child exit into an ExitCodeError so
+    // the CLI exits with the same code
+    pub fn run(file: &str, args: &[&str], options: &RunOptions) -> Result<(), Box<dyn Error>> {
+        let report = Self::run_once(file, args, options)?;
+        if let Some(destination) = &options.stats {
+            report.write_stats(destination)?;
+        }
+        match report.exit_code {
+            0 => Ok(()),
+            code => Err(Box::new(ExitCodeError::silent(code))),
+        }
+    }
+
+    // Runs the file once and reports how it went
+    pub fn run_once(file: &str, args: &[&str], options: &RunOptions) -> Result<RunReport, Box<dyn Error>> {
+        // Checked up front, otherwise the interpreter reports it and it reads like a missing interpreter
+        if !Path::new(file).is_file() {
+            return Err(format!("'{}' does not exist", file).into());
+        }
         let start = Instant::now();
         
-        let extension = file.split('.').last().unwrap_or("");
+        let extension = file.split('.').next_back().unwrap_or("");
+        let shebang = read_shebang(file);
         
-        let (command, interpreter_args) = match extension {
-            "go" => {
-                println!("{}", "Golang triggered".blue().bold());
-                ("go", vec!["run"])
-            }
-            "py" => {
-                println!("{}", "uv for python triggered".green().bold());
-                ("uv", vec!["run"])
-            }
-            "js" => {
-                println!("{}", "Node interpreter triggered".yellow().bold());
-                ("node", vec![])
+        let runner = match Self::configured_interpreter(extension, &options.interpreters) {
+            Some((command, interpreter_args)) => {
+                println!("{}", format!("{} triggered (from config)", command).cyan().bold());
+                Runner::Interpret { command, args: interpreter_args }
             }
-            "ts" => {
-                println!("{}", "TypeScript triggered. Running with Deno.".blue().bold());
-                ("deno", vec!["run", "--allow-all"])
-            }
-            "mojo" | "🔥" => {
-                println!("{}", "Mojo triggered 🔥".red().bold());
-                ("mojo", vec![])
+            None => match Self::builtin_runner(extension) {
+                Some((runner, banner)) => {
+                    println!("{}", banner);
+                    runner
+                }
+                None => match shebang.as_deref().and_then(parse_shebang) {
+                    Some((command, interpreter_args)) => {
+                        println!("{}", format!("Shebang triggered. Running with {}", command).cyan().bold());
+                        Runner::Interpret { command, args: interpreter_args }
+                    }
+                    None => return Err(format!("Unknown file type: {}", extension).into()),
+                },
+            },
+        };
+
+        let (exit_code, timed_out, peak_rss_kb) = match runner {
+            Runner::Interpret { command, args: interpreter_args } => {
+                let mut cmd_args = interpreter_args;
+                cmd_args.push(file);
+                cmd_args.extend(args);
+
+                let mut child = process::Command::new(command);
+                child.args(&cmd_args);
+                Self::run_child(child, options)?
             }
-            _ => {
-                eprintln!("Unknown file type: {}", extension);
-                process::exit(1);
+            Runner::Compile { compiler, flags } => {
+                let binary = Self::temp_binary_path(file);
+                let compiled = process::Command::new(compiler)
+                    .args(&flags)
+                    .arg(file)
+                    .arg("-o")
+                    .arg(&binary)
+                    .status()
+                    .map_err(|e| spawn_error(compiler, e))?;
+                if !compiled.success() {
+                    return Ok(RunReport {
+                        file: file.to_string(),
+                        exit_code: compiled.code().unwrap_or(1),
+                        timed_out: false,
+                        wall_time_ms: start.elapsed().as_millis(),
+                        peak_rss_kb: None,
+                    });
+                }
+
+                let mut child = process::Command::new(&binary);
+                child.args(args);
+                let outcome = Self::run_child(child, options);
+                let _ = std::fs::remove_file(&binary);
+                outcome?
             }
         };
         
-        let mut cmd_args = interpreter_args;
-        cmd_args.push(file);
-        cmd_args.extend(args);
-        
-        let status = process::Command::new(command)
-            .args(&cmd_args)
-            .status()
-            .expect("Failed to execute command");
-        
         let duration = start.elapsed();
         println!("{}", format!("Task duration: {}ms", duration.as_millis()).color("orange"));
         
-        if !status.success() {
-            process::exit(status.code().unwrap_or(1));
+        Ok(RunReport {
+            file: file.to_string(),
+            exit_code,
+            timed_out,
+            wall_time_ms: duration.as_millis(),
+            peak_rss_kb,
+        })
+    }
+
+    // Spawns the child with any resource limits applied, tees its output when
+    // capturing, and waits for it, killing it once the timeout elapses.
+    // Returns the exit code, whether the timeout fired and the child's peak RSS.
+    fn run_child(mut command: process::Command, options: &RunOptions) -> Result<(i32, bool, Option<u64>), Box<dyn Error>> {
+        command.envs(options.env.iter().map(|(key, value)| (key, value)));
+        apply_resource_limits(&mut command, options)?;
+        if options.capture.is_some() {
+            command.stdout(process::Stdio::piped()).stderr(process::Stdio::piped());
+        }
+        let program = command.get_program().to_string_lossy().to_string();
+        let mut child = command.spawn().map_err(|e| spawn_error(&program, e))?;
+
+        let mut tees = Vec::new();
+        if let Some(path) = &options.capture {
+            let log = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|e| format!("Failed to open capture file {}: {}", path.display(), e))?;
+            let log = Arc::new(Mutex::new(log));
+            if let Some(stdout) = child.stdout.take() {
+                tees.push(tee_output(stdout, std::io::stdout(), Arc::clone(&log), "stdout"));
+            }
+            if let Some(stderr) = child.stderr.take() {
+                tees.push(tee_output(stderr, std::io::stderr(), Arc::clone(&log), "stderr"));
+            }
+        }
+
+        let outcome = Self::wait_child(&mut child, options.timeout);
+        for tee in tees {
+            let _ = tee.join();
+        }
+        outcome
+    }
+
+    // Reaps the child with wait4 so its own rusage is known; getrusage(RUSAGE_CHILDREN)
+    // would report the largest of every child so far, our own forks included
+    #[cfg(unix)]
+    fn wait_child(child: &mut process::Child, timeout: Option<Duration>) -> Result<(i32, bool, Option<u64>), Box<dyn Error>> {
+        use std::os::unix::process::ExitStatusExt;
+
+        let pid = child.id() as libc::pid_t;
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let mut timed_out = false;
+        loop {
+            let flags = if deadline.is_some() && !timed_out { libc::WNOHANG } else { 0 };
+            let mut status = 0;
+            // SAFETY: wait4 only writes into the status and the zeroed struct we own
+            let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+            let reaped = unsafe { libc::wait4(pid, &mut status, flags, &mut usage) };
+            if reaped == -1 {
+                let error = std::io::Error::last_os_error();
+                if error.kind() == std::io::ErrorKind::Interrupted {
+                    continue;
+                }
+                return Err(error.into());
+            }
+            if reaped == pid {
+                let code = if timed_out { TIMEOUT_EXIT_CODE } else { process::ExitStatus::from_raw(status).code().unwrap_or(1) };
+                return Ok((code, timed_out, Some(max_rss_kb(&usage))));
+            }
+            // Still running, which WNOHANG only reports while the timeout is pending
+            let (Some(timeout), Some(deadline)) = (timeout, deadline) else { continue };
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                eprintln!("{}", format!("Timed out after {:.1}s", timeout.as_secs_f64()).red().bold());
+                timed_out = true;
+                continue;
+            }
+            thread::sleep(Duration::from_millis(20).min(deadline - Instant::now()));
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn wait_child(child: &mut process::Child, timeout: Option<Duration>) -> Result<(i32, bool, Option<u64>), Box<dyn Error>> {
+        let Some(timeout) = timeout else {
+            return Ok((child.wait()?.code().unwrap_or(1), false, None));
+        };
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(status) = child.try_wait()? {
+                return Ok((status.code().unwrap_or(1), false, None));
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                eprintln!("{}", format!("Timed out after {:.1}s", timeout.as_secs_f64()).red().bold());
+                return Ok((TIMEOUT_EXIT_CODE, true, None));
+            }
+            thread::sleep(Duration::from_millis(20).min(deadline - Instant::now()));
         }
     }
 
+    // Re-runs the file whenever it (or anything under watch_dir) changes
+    pub fn watch(file: &str, args: &[&str], options: &RunOptions, watch_dir: Option<&str>) -> Result<(), Box<dyn Error>> {
+        let file_path = std::fs::canonicalize(file).map_err(|e| format!("Cannot watch {}: {}", file, e))?;
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(tx)?;
+
+        // Watch the parent directory since editors often save by replacing the file
+        let parent = file_path.parent().unwrap_or(Path::new("."));
+        watcher.watch(parent, RecursiveMode::NonRecursive)?;
+        let watch_root = match watch_dir {
+            Some(dir) => {
+                let dir = std::fs::canonicalize(dir).map_err(|e| format!("Cannot watch {}: {}", dir, e))?;
+                watcher.watch(&dir, RecursiveMode::Recursive)?;
+                Some(dir)
+            }
+            None => None,
+        };
+
+        loop {
+            print!("\x1B[2J\x1B[H");
+            println!("{}", format!("[{}] Running {}", Local::now().format("%H:%M:%S"), file).dimmed());
+            match Self::run_once(file, args, options) {
+                Ok(report) => {
+                    if report.exit_code != 0 {
+                        println!("{}", format!("Exited with code {}", report.exit_code).red());
+                    }
+                    if let Some(destination) = &options.stats {
+                        report.write_stats(destination)?;
+                    }
+                }
+                Err(e) => eprintln!("{}", e),
+            }
+            println!("{}", "Watching for changes... (Ctrl-C to stop)".dimmed());
+
+            // Wait for a relevant change, then let the burst of editor events settle
+            loop {
+                let event = rx.recv()?;
+                if is_relevant_change(&event, &file_path, watch_root.as_deref()) {
+                    break;
+                }
+            }
+            while rx.recv_timeout(Duration::from_millis(200)).is_ok() {}
+        }
+    }
+
+    fn builtin_runner(extension: &str) -> Option<(Runner<'static>, ColoredString)> {
+        let interpret = |command, args| Runner::Interpret { command, args };
+        let runner = match extension {
+            "go" => (interpret("go", vec!["run"]), "Golang triggered".blue().bold()),
+            "py" => (interpret("uv", vec!["run"]), "uv for python triggered".green().bold()),
+            "js" => (interpret("node", vec![]), "Node interpreter triggered".yellow().bold()),
+            "ts" => (interpret("deno", vec!["run", "--allow-all"]), "TypeScript triggered. Running with Deno.".blue().bold()),
+            "mojo" | "🔥" => (interpret("mojo", vec![]), "Mojo triggered 🔥".red().bold()),
+            "rs" => {
+                // rust-script handles dependencies in comments; fall back to nightly cargo scripts
+                if find_in_path("rust-script").is_some() {
+                    (interpret("rust-script", vec![]), "Rust triggered. Running with rust-script.".red().bold())
+                } else {
+                    (interpret("cargo", vec!["-Zscript"]), "Rust triggered. Running with cargo -Zscript.".red().bold())
+                }
+            }
+            "rb" => (interpret("ruby", vec![]), "Ruby triggered".red().bold()),
+            "php" => (interpret("php", vec![]), "PHP triggered".magenta().bold()),
+            "sh" => (interpret("sh", vec![]), "Shell script triggered".green().bold()),
+            "lua" => (interpret("lua", vec![]), "Lua triggered".blue().bold()),
+            "zig" => (interpret("zig", vec!["run"]), "Zig triggered".yellow().bold()),
+            "java" => (interpret("java", vec![]), "Java triggered. Running single-file source.".red().bold()),
+            "c" => (Runner::Compile { compiler: "cc", flags: vec!["-O2"] }, "C triggered. Compiling with cc.".blue().bold()),
+            "cpp" | "cc" | "cxx" => (Runner::Compile { compiler: "c++", flags: vec!["-O2", "-std=c++17"] }, "C++ triggered. Compiling with c++.".blue().bold()),
+            _ => return None,
+        };
+        Some(runner)
+    }
+
+    fn temp_binary_path(file: &str) -> std::path::PathBuf {
+        let stem = std::path::Path::new(file)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("program");
+        std::env::temp_dir().join(format!("micro-swiss-{}-{}", stem, process::id()))
+    }
+
+    // Splits a configured command line such as "bun run" into program and arguments
+    fn configured_interpreter<'a>(extension: &str, interpreters: &'a HashMap<String, String>) -> Option<(&'a str, Vec<&'a str>)> {
+        let mut parts = interpreters.get(extension)?.split_whitespace();
+        let command = parts.next()?;
+        Some((command, parts.collect()))
+    }
+
+    // Reports which interpreter each extension resolves to and whether it is installed
+    pub fn doctor(interpreters: &HashMap<String, String>) -> Vec<(String, String, bool)> {
+        let mut extensions: Vec<String> = Self::get_supported_extensions().iter().map(|e| e.to_string()).collect();
+        for extension in interpreters.keys() {
+            if !extensions.contains(extension) {
+                extensions.push(extension.clone());
+            }
+        }
+
+        extensions
+            .into_iter()
+            .filter_map(|extension| {
+                let program = match Self::configured_interpreter(&extension, interpreters) {
+                    Some((command, _)) => command.to_string(),
+                    None => Self::builtin_runner(&extension)?.0.program().to_string(),
+                };
+                let found = find_in_path(&program).is_some();
+                Some((extension, program, found))
+            })
+            .collect()
+    }
+
     pub fn get_supported_extensions() -> Vec<&'static str> {
-        vec!["go", "py", "js", "ts", "mojo", "🔥"]
+        vec!["go", "py", "js", "ts", "mojo", "🔥", "rs", "rb", "php", "sh", "lua", "zig", "java", "c", "cpp", "cc", "cxx"]
     }
 
     pub fn is_supported_file(file: &str) -> bool {
-        let extension = file.split('.').last().unwrap_or("");
+        let extension = file.split('.').next_back().unwrap_or("");
         Self::get_supported_extensions().contains(&extension)
     }
 }
 
+// Copies a child stream to our own stdout/stderr line by line while appending
+// each line to the capture log with a timestamp
+fn tee_output<R, W>(reader: R, mut echo: W, log: Arc<Mutex<File>>, stream: &'static str) -> thread::JoinHandle<()>
+where
+    R: Read + Send + 'static,
+    W: Write + Send + 'static,
+{
+    thread::spawn(move || {
+        let mut reader = BufReader::new(reader);
+        let mut line = Vec::new();
+        while let Ok(read) = reader.read_until(b'\n', &mut line) {
+            if read == 0 {
+                break;
+            }
+            let _ = echo.write_all(&line);
+            let _ = echo.flush();
+            let text = String::from_utf8_lossy(&line);
+            if let Ok(mut log) = log.lock() {
+                let _ = writeln!(log, "[{}] [{}] {}", Local::now().format("%Y-%m-%d %H:%M:%S%.3f"), stream, text.trim_end_matches(['\n', '\r']));
+            }
+            line.clear();
+        }
+    })
+}
+
+// ru_maxrss is in KiB on Linux and bytes on macOS
+#[cfg(unix)]
+fn max_rss_kb(usage: &libc::rusage) -> u64 {
+    let max_rss = usage.ru_maxrss as u64;
+    if cfg!(target_os = "macos") {
+        max_rss / 1024
+    } else {
+        max_rss
+    }
+}
+
+#[cfg(unix)]
+fn apply_resource_limits(command: &mut process::Command, options: &RunOptions) -> Result<(), Box<dyn Error>> {
+    use std::os::unix::process::CommandExt;
+
+    let memory = options.max_memory_mb.map(|mb| mb.saturating_mul(1024 * 1024));
+    let cpu = options.max_cpu_secs;
+    if memory.is_none() && cpu.is_none() {
+        return Ok(());
+    }
+
+    let set_limit = |resource, value: u64| {
+        let limit = libc::rlimit {
+            rlim_cur: value as libc::rlim_t,
+            rlim_max: value as libc::rlim_t,
+        };
+        // SAFETY: setrlimit only reads the struct we pass in
+        if unsafe { libc::setrlimit(resource, &limit) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+    };
+
+    // SAFETY: the closure only calls setrlimit, which is async-signal-safe
+    unsafe {
+        command.pre_exec(move || {
+            if let Some(bytes) = memory {
+                set_limit(libc::RLIMIT_AS, bytes)?;
+            }
+            if let Some(secs) = cpu {
+                set_limit(libc::RLIMIT_CPU, secs)?;
+            }
+            Ok(())
+        });
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn apply_resource_limits(_command: &mut process::Command, options: &RunOptions) -> Result<(), Box<dyn Error>> {
+    if options.max_memory_mb.is_some() || options.max_cpu_secs.is_some() {
+        return Err("--max-memory and --max-cpu are only supported on Unix".into());
+    }
+    Ok(())
+}
+
+pub fn install_hint(program: &str) -> Option<&'static str> {
+    let hint = match Path::new(program).file_name()?.to_str()? {
+        "uv" => "curl -LsSf https://astral.sh/uv/install.sh | sh",
+        "node" => "install Node.js from https://nodejs.org or via your package manager (e.g. brew install node)",
+        "deno" => "curl -fsSL https://deno.land/install.sh | sh",
+        "bun" => "curl -fsSL https://bun.sh/install | bash",
+        "go" => "install Go from https://go.dev/dl or via your package manager (e.g. brew install go)",
+        "mojo" => "see https://docs.modular.com/mojo/manual/get-started",
+        "rust-script" => "cargo install rust-script",
+        "cargo" | "rustc" => "curl --proto '=https' --tlsv1.2 -sSf https://sh.rustup.rs | sh",
+        "python" | "python3" => "install Python from https://python.org or run: uv python install",
+        "ruby" => "install Ruby via your package manager (e.g. brew install ruby)",
+        "php" => "install PHP via your package manager (e.g. brew install php)",
+        "lua" => "install Lua via your package manager (e.g. brew install lua)",
+        "zig" => "install Zig from https://ziglang.org/download",
+        "java" => "install a JDK (11+) such as https://adoptium.net",
+        "cc" | "gcc" | "clang" | "c++" | "g++" | "clang++" => "install a C/C++ toolchain (build-essential, Xcode Command Line Tools, or clang)",
+        _ => return None,
+    };
+    Some(hint)
+}
+
+// Turns a spawn failure into a friendly error with a shell-style exit code
+fn spawn_error(program: &str, error: std::io::Error) -> Box<dyn Error> {
+    if error.kind() == std::io::ErrorKind::NotFound {
+        let message = match install_hint(program) {
+            Some(hint) => format!("Interpreter '{}' not found. To install: {}", program, hint),
+            None => format!("Interpreter '{}' not found on PATH", program),
+        };
+        return Box::new(ExitCodeError::new(INTERPRETER_NOT_FOUND_EXIT_CODE, message));
+    }
+    Box::new(ExitCodeError::new(CANNOT_EXECUTE_EXIT_CODE, format!("Failed to run '{}': {}", program, error)))
+}
+
+fn is_valid_env_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+pub fn parse_env_assignment(assignment: &str) -> Result<(String, String), Box<dyn Error>> {
+    let (key, value) = assignment
+        .split_once('=')
+        .ok_or_else(|| format!("Invalid --env '{}': expected KEY=VALUE", assignment))?;
+    if !is_valid_env_key(key) {
+        return Err(format!("Invalid environment variable name '{}'", key).into());
+    }
+    Ok((key.to_string(), value.to_string()))
+}
+
+// Parses .env content: KEY=VALUE lines with optional `export`, comments,
+// single quotes (literal) and double quotes (with \n, \t, \" and \\ escapes)
+pub fn parse_env_file(content: &str) -> Result<Vec<(String, String)>, String> {
+    let mut vars = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        if let Some(var) = parse_env_line(line).map_err(|e| format!("line {}: {}", index + 1, e))? {
+            vars.push(var);
+        }
+    }
+    Ok(vars)
+}
+
+// One .env line; None for blank lines and comments
+pub fn parse_env_line(line: &str) -> Result<Option<(String, String)>, String> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return Ok(None);
+    }
+    let line = line.strip_prefix("export ").map(str::trim_start).unwrap_or(line);
+    let (key, value) = line.split_once('=').ok_or("expected KEY=VALUE")?;
+    let key = key.trim();
+    if !is_valid_env_key(key) {
+        return Err(format!("invalid variable name '{}'", key));
+    }
+    Ok(Some((key.to_string(), parse_env_value(value.trim())?)))
+}
+
+fn parse_env_value(value: &str) -> Result<String, String> {
+    if let Some(rest) = value.strip_prefix('\'') {
+        let end = rest.find('\'').ok_or("unterminated single quote")?;
+        return Ok(rest[..end].to_string());
+    }
+    if let Some(rest) = value.strip_prefix('"') {
+        let mut result = String::new();
+        let mut chars = rest.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => return Ok(result),
+                '\\' => match chars.next() {
+                    Some('n') => result.push('\n'),
+                    Some('t') => result.push('\t'),
+                    Some('r') => result.push('\r'),
+                    Some(other) => result.push(other),
+                    None => break,
+                },
+                _ => result.push(c),
+            }
+        }
+        return Err("unterminated double quote".to_string());
+    }
+    // Unquoted values end at an inline comment
+    let value = match value.find(" #") {
+        Some(index) => &value[..index],
+        None => value,
+    };
+    Ok(value.trim_end().to_string())
+}
+
+// Reads the first line of a file if it is a shebang, e.g. "#!/usr/bin/env python3"
+fn read_shebang(file: &str) -> Option<String> {
+    let handle = std::fs::File::open(file).ok()?;
+    let mut first_line = String::new();
+    BufReader::new(handle).take(512).read_line(&mut first_line).ok()?;
+    first_line.starts_with("#!").then(|| first_line.trim_end().to_string())
+}
+
+// Splits a shebang into program and arguments, unwrapping /usr/bin/env so the
+// interpreter is looked up on PATH ("#!/usr/bin/env -S deno run" works too)
+fn parse_shebang(line: &str) -> Option<(&str, Vec<&str>)> {
+    let mut parts = line.strip_prefix("#!")?.split_whitespace();
+    let mut command = parts.next()?;
+    if Path::new(command).file_name().is_some_and(|name| name == "env") {
+        command = parts.next()?;
+        if command == "-S" {
+            command = parts.next()?;
+        }
+    }
+    Some((command, parts.collect()))
+}
+
+fn is_relevant_change(event: &notify::Result<Event>, file: &Path, watch_root: Option<&Path>) -> bool {
+    let Ok(event) = event else {
+        return false;
+    };
+    if matches!(event.kind, EventKind::Access(_)) {
+        return false;
+    }
+    event.paths.iter().any(|path| path == file || watch_root.is_some_and(|root| path.starts_with(root)))
+}
+
+pub fn find_in_path(program: &str) -> Option<std::path::PathBuf> {
+    let candidate = std::path::Path::new(program);
+    if candidate.components().count() > 1 {
+        return candidate.is_file().then(|| candidate.to_path_buf());
+    }
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path).find_map(|dir| {
+        let full = dir.join(program);
+        if full.is_file() {
+            return Some(full);
+        }
+        let exe = dir.join(format!("{}.exe", program));
+        exe.is_file().then_some(exe)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -187,6 +844,239 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_configured_interpreter() {
+        let mut interpreters = HashMap::new();
+        interpreters.insert("py".to_string(), "python3".to_string());
+        interpreters.insert("ts".to_string(), "bun run --hot".to_string());
+        interpreters.insert("js".to_string(), "   ".to_string());
+
+        assert_eq!(FileRunner::configured_interpreter("py", &interpreters), Some(("python3", vec![])));
+        assert_eq!(FileRunner::configured_interpreter("ts", &interpreters), Some(("bun", vec!["run", "--hot"])));
+        assert_eq!(FileRunner::configured_interpreter("js", &interpreters), None);
+        assert_eq!(FileRunner::configured_interpreter("go", &interpreters), None);
+    }
+
+    #[test]
+    fn test_builtin_runners_for_new_languages() {
+        for extension in ["rs", "rb", "php", "sh", "lua", "zig", "java", "c", "cpp"] {
+            assert!(FileRunner::builtin_runner(extension).is_some(), "no runner for .{}", extension);
+            assert!(FileRunner::is_supported_file(&format!("main.{}", extension)));
+        }
+        assert_eq!(FileRunner::builtin_runner("zig").unwrap().0, Runner::Interpret { command: "zig", args: vec!["run"] });
+        assert!(matches!(FileRunner::builtin_runner("c").unwrap().0, Runner::Compile { compiler: "cc", .. }));
+        assert!(FileRunner::builtin_runner("txt").is_none());
+    }
+
+    #[test]
+    fn test_doctor_includes_configured_extensions() {
+        let mut interpreters = HashMap::new();
+        interpreters.insert("exs".to_string(), "elixir".to_string());
+        interpreters.insert("py".to_string(), "python3".to_string());
+        let report = FileRunner::doctor(&interpreters);
+        assert!(report.iter().any(|(ext, program, _)| ext == "exs" && program == "elixir"));
+        assert!(report.iter().any(|(ext, program, _)| ext == "py" && program == "python3"));
+        assert!(report.iter().any(|(ext, program, _)| ext == "go" && program == "go"));
+    }
+
+    #[test]
+    fn test_run_once_unknown_extension() {
+        assert!(FileRunner::run_once("notes.txt", &[], &RunOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_run_once_missing_file() {
+        let error = FileRunner::run_once("definitely-missing.py", &[], &RunOptions::default()).unwrap_err();
+        assert_eq!(error.to_string(), "'definitely-missing.py' does not exist");
+    }
+
+    fn write_script(name: &str, content: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("micro-swiss-{}-{}.sh", name, process::id()));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_run_once_exit_code() {
+        let path = write_script("exit", "exit 7\n");
+        let report = FileRunner::run_once(path.to_str().unwrap(), &[], &RunOptions::default()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(report.exit_code, 7);
+        assert!(!report.timed_out);
+    }
+
+    #[test]
+    fn test_run_once_timeout() {
+        let path = write_script("timeout", "sleep 5\n");
+        let options = RunOptions { timeout: Some(Duration::from_millis(200)), ..Default::default() };
+        let start = Instant::now();
+        let report = FileRunner::run_once(path.to_str().unwrap(), &[], &options).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(report.exit_code, TIMEOUT_EXIT_CODE);
+        assert!(report.timed_out);
+        assert!(start.elapsed() < Duration::from_secs(3));
+    }
+
+    #[test]
+    fn test_missing_interpreter_error() {
+        let mut interpreters = HashMap::new();
+        interpreters.insert("py".to_string(), "definitely-not-installed-python".to_string());
+        let path = write_script("missing", "");
+        let py_path = path.with_extension("py");
+        std::fs::rename(&path, &py_path).unwrap();
+
+        let options = RunOptions { interpreters, ..Default::default() };
+        let error = FileRunner::run_once(py_path.to_str().unwrap(), &[], &options).unwrap_err();
+        std::fs::remove_file(&py_path).unwrap();
+
+        let exit = error.downcast_ref::<ExitCodeError>().unwrap();
+        assert_eq!(exit.code, INTERPRETER_NOT_FOUND_EXIT_CODE);
+        assert!(exit.message.as_deref().unwrap().contains("definitely-not-installed-python"));
+    }
+
+    #[test]
+    fn test_run_propagates_exit_code() {
+        let path = write_script("propagate", "exit 3\n");
+        let error = FileRunner::run(path.to_str().unwrap(), &[], &RunOptions::default()).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        let exit = error.downcast_ref::<ExitCodeError>().unwrap();
+        assert_eq!(exit.code, 3);
+        assert!(exit.message.is_none());
+    }
+
+    #[test]
+    fn test_install_hint() {
+        assert!(install_hint("uv").unwrap().contains("astral.sh"));
+        assert!(install_hint("/usr/local/bin/deno").unwrap().contains("deno"));
+        assert!(install_hint("unknown-tool").is_none());
+    }
+
+    #[test]
+    fn test_parse_env_file() {
+        let content = "# comment\n\nexport API_URL=https://example.com\nNAME='literal $HOME'\nGREETING=\"hello\\nworld\"\nPORT=8080 # inline comment\nEMPTY=\n";
+        let vars = parse_env_file(content).unwrap();
+        assert_eq!(vars, vec![
+            ("API_URL".to_string(), "https://example.com".to_string()),
+            ("NAME".to_string(), "literal $HOME".to_string()),
+            ("GREETING".to_string(), "hello\nworld".to_string()),
+            ("PORT".to_string(), "8080".to_string()),
+            ("EMPTY".to_string(), String::new()),
+        ]);
+    }
+
+    #[test]
+    fn test_parse_env_file_errors() {
+        assert_eq!(parse_env_file("OK=1\nBROKEN\n").unwrap_err(), "line 2: expected KEY=VALUE");
+        assert!(parse_env_file("1BAD=x").is_err());
+        assert!(parse_env_file("A=\"open").is_err());
+        assert!(parse_env_file("A='open").is_err());
+    }
+
+    #[test]
+    fn test_parse_env_assignment() {
+        assert_eq!(parse_env_assignment("KEY=a=b").unwrap(), ("KEY".to_string(), "a=b".to_string()));
+        assert!(parse_env_assignment("NOVALUE").is_err());
+        assert!(parse_env_assignment("BAD-KEY=1").is_err());
+    }
+
+    #[test]
+    fn test_run_once_sets_env() {
+        let path = write_script("env", "test \"$MS_TEST_VALUE\" = expected\n");
+        let options = RunOptions {
+            env: vec![("MS_TEST_VALUE".to_string(), "wrong".to_string()), ("MS_TEST_VALUE".to_string(), "expected".to_string())],
+            ..Default::default()
+        };
+        let report = FileRunner::run_once(path.to_str().unwrap(), &[], &options).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(report.exit_code, 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_once_cpu_limit() {
+        // The shell busy-loops until the CPU limit sends SIGXCPU/SIGKILL
+        let path = write_script("cpu", "while :; do :; done\n");
+        let options = RunOptions { max_cpu_secs: Some(1), timeout: Some(Duration::from_secs(10)), ..Default::default() };
+        let report = FileRunner::run_once(path.to_str().unwrap(), &[], &options).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_ne!(report.exit_code, 0);
+        assert!(!report.timed_out);
+    }
+
+    #[test]
+    fn test_run_once_capture() {
+        let path = write_script("capture", "echo out line\necho err line >&2\n");
+        let log = path.with_extension("log");
+        let options = RunOptions { capture: Some(log.clone()), ..Default::default() };
+        let report = FileRunner::run_once(path.to_str().unwrap(), &[], &options).unwrap();
+        let captured = std::fs::read_to_string(&log).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(&log).unwrap();
+
+        assert_eq!(report.exit_code, 0);
+        assert!(captured.lines().any(|l| l.starts_with('[') && l.ends_with("[stdout] out line")));
+        assert!(captured.lines().any(|l| l.ends_with("[stderr] err line")));
+    }
+
+    #[test]
+    fn test_run_stats_file() {
+        let path = write_script("stats", "exit 0\n");
+        let stats = path.with_extension("json");
+        let options = RunOptions { stats: Some(Some(stats.clone())), ..Default::default() };
+        FileRunner::run(path.to_str().unwrap(), &[], &options).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&stats).unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(&stats).unwrap();
+
+        assert_eq!(json["exit_code"], 0);
+        assert_eq!(json["timed_out"], false);
+        assert!(json["wall_time_ms"].is_u64());
+        if cfg!(unix) {
+            assert!(json["peak_rss_kb"].as_u64().unwrap() > 0);
+        }
+    }
+
+    #[test]
+    fn test_is_relevant_change() {
+        use notify::event::{AccessKind, ModifyKind};
+        let file = Path::new("/project/main.py");
+        let modify = |path: &str| Ok(Event::new(EventKind::Modify(ModifyKind::Any)).add_path(path.into()));
+
+        assert!(is_relevant_change(&modify("/project/main.py"), file, None));
+        assert!(!is_relevant_change(&modify("/project/other.py"), file, None));
+        assert!(is_relevant_change(&modify("/project/src/lib.py"), file, Some(Path::new("/project/src"))));
+        let access = Ok(Event::new(EventKind::Access(AccessKind::Any)).add_path("/project/main.py".into()));
+        assert!(!is_relevant_change(&access, file, None));
+    }
+
+    #[test]
+    fn test_parse_shebang() {
+        assert_eq!(parse_shebang("#!/usr/bin/env python3"), Some(("python3", vec![])));
+        assert_eq!(parse_shebang("#!/bin/bash -e"), Some(("/bin/bash", vec!["-e"])));
+        assert_eq!(parse_shebang("#!/usr/bin/env -S deno run --allow-net"), Some(("deno", vec!["run", "--allow-net"])));
+        assert_eq!(parse_shebang("#! /usr/bin/python3"), Some(("/usr/bin/python3", vec![])));
+        assert_eq!(parse_shebang("#!"), None);
+        assert_eq!(parse_shebang("#!/usr/bin/env"), None);
+        assert_eq!(parse_shebang("print('hi')"), None);
+    }
+
+    #[test]
+    fn test_read_shebang() {
+        let path = std::env::temp_dir().join(format!("micro-swiss-shebang-{}", process::id()));
+        std::fs::write(&path, "#!/bin/sh\necho hi\n").unwrap();
+        assert_eq!(read_shebang(path.to_str().unwrap()).as_deref(), Some("#!/bin/sh"));
+        std::fs::write(&path, "echo hi\n").unwrap();
+        assert_eq!(read_shebang(path.to_str().unwrap()), None);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read_shebang(path.to_str().unwrap()), None);
+    }
+
+    #[test]
+    fn test_find_in_path() {
+        assert!(find_in_path("sh").is_some());
+        assert!(find_in_path("definitely-not-a-real-program-xyz").is_none());
+    }
+
     #[test]
     fn test_is_supported_file_no_extension() {
         assert!(!FileRunner::is_supported_file("makefile"));
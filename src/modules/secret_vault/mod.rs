@@ -0,0 +1,288 @@
+use crate::config::data_dir;
+use crate::file_crypt::read_passphrase;
+use crate::tool_module::{ExitCodeError, ToolModule};
+use age::secrecy::ExposeSecret;
+use arboard::Clipboard;
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use chrono::Local;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use colored::*;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io::{self, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+
+pub struct SecretVaultModule;
+
+const VAULT_FILE: &str = "vault.json";
+const FORMAT_VERSION: u32 = 1;
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+
+// The whole entry map is encrypted as one blob, so key names stay private too
+#[derive(Serialize, Deserialize, Debug)]
+struct VaultFile {
+    version: u32,
+    kdf: KdfParams,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+// Argon2id cost, stored so it can be raised later without breaking old vaults
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+struct KdfParams {
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self { memory_kib: 64 * 1024, iterations: 3, parallelism: 1 }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct Entry {
+    value: String,
+    updated: String,
+}
+
+type Entries = BTreeMap<String, Entry>;
+
+impl ToolModule for SecretVaultModule {
+    fn name(&self) -> &'static str {
+        "secret-vault"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("vault")
+                .long("vault")
+                .value_names(["ACTION", "KEY"])
+                .num_args(1..=2)
+                .help("Encrypted secret store: set KEY, get KEY, list, remove KEY")
+                .long_help("A local store for API keys, TOTP seeds and other secrets, encrypted with a passphrase (Argon2id key derivation, AES-256-GCM) and kept in vault.json in the data directory ($MICRO_SWISS_DATA).\n\n  set KEY     store a value, prompted without echo or read from stdin\n  get KEY     print the value, or copy it to the clipboard with --copy\n  list        show the stored keys\n  remove KEY  delete an entry\n\nThe passphrase is prompted, or read from MICRO_SWISS_PASSPHRASE in scripts. Other modules read entries as vault:KEY, e.g. --totp vault:github or password = \"vault:prod-db\" for a [db.connections] entry.")
+        )
+        .arg(
+            Arg::new("copy")
+                .long("copy")
+                .action(ArgAction::SetTrue)
+                .help("With --vault get or --snippet get, copy the value to the clipboard instead of printing it")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(values) = matches.get_many::<String>("vault") {
+            let values: Vec<&str> = values.map(String::as_str).collect();
+            let path = vault_path()?;
+            match values.as_slice() {
+                ["set", key] => {
+                    let exists = path.exists();
+                    let passphrase = read_passphrase(if exists { "Vault passphrase: " } else { "New vault passphrase: " }, !exists)?;
+                    let passphrase = passphrase.expose_secret();
+                    let mut entries = if exists { load(&path, passphrase)? } else { Entries::new() };
+                    let value = read_value(key)?;
+                    let replaced = entries.insert(key.to_string(), Entry { value, updated: Local::now().format("%Y-%m-%d %H:%M").to_string() }).is_some();
+                    save(&path, passphrase, &entries, KdfParams::default())?;
+                    println!("✅ {} {}", if replaced { "Updated" } else { "Stored" }, key.bold());
+                }
+                ["get", key] => {
+                    let value = get_entry(&load_existing(&path)?, key)?;
+                    if matches.get_flag("copy") {
+                        Clipboard::new()
+                            .and_then(|mut clipboard| clipboard.set_text(&value))
+                            .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+                        println!("✅ Copied {} to clipboard", key.bold());
+                    } else {
+                        println!("{}", value);
+                    }
+                }
+                ["list"] => {
+                    let entries = load_existing(&path)?;
+                    if entries.is_empty() {
+                        println!("The vault is empty");
+                    }
+                    let width = entries.keys().map(|key| key.chars().count()).max().unwrap_or(0);
+                    for (key, entry) in &entries {
+                        println!("{:<width$}  {}", key, format!("updated {}", entry.updated).dimmed(), width = width);
+                    }
+                }
+                ["remove", key] => {
+                    let passphrase = read_passphrase("Vault passphrase: ", false)?;
+                    let passphrase = passphrase.expose_secret();
+                    let mut entries = load(&path, passphrase)?;
+                    if entries.remove(*key).is_none() {
+                        return Err(Box::new(ExitCodeError::new(1, format!("No entry named '{}' in the vault", key))));
+                    }
+                    save(&path, passphrase, &entries, KdfParams::default())?;
+                    println!("✅ Removed {}", key.bold());
+                }
+                _ => return Err("Usage: --vault set KEY | get KEY | list | remove KEY".into()),
+            }
+        }
+        Ok(())
+    }
+}
+
+// The value of a vault entry, for modules that accept vault:KEY
+pub fn vault_secret(key: &str) -> Result<String, Box<dyn Error>> {
+    get_entry(&load_existing(&vault_path()?)?, key)
+}
+
+fn vault_path() -> Result<PathBuf, Box<dyn Error>> {
+    data_dir().map(|dir| dir.join(VAULT_FILE)).ok_or_else(|| "Could not determine the data directory; set MICRO_SWISS_DATA".into())
+}
+
+fn get_entry(entries: &Entries, key: &str) -> Result<String, Box<dyn Error>> {
+    entries
+        .get(key)
+        .map(|entry| entry.value.clone())
+        .ok_or_else(|| Box::new(ExitCodeError::new(1, format!("No entry named '{}' in the vault", key))) as Box<dyn Error>)
+}
+
+fn load_existing(path: &Path) -> Result<Entries, Box<dyn Error>> {
+    if !path.exists() {
+        return Err("The vault is empty; add a secret with --vault set KEY".into());
+    }
+    load(path, read_passphrase("Vault passphrase: ", false)?.expose_secret())
+}
+
+// Prompted without echo on a terminal, otherwise all of stdin minus the final newline
+fn read_value(key: &str) -> Result<String, Box<dyn Error>> {
+    let value = if io::stdin().is_terminal() {
+        rpassword::prompt_password(format!("Value for {}: ", key))?
+    } else {
+        let mut value = String::new();
+        io::stdin().read_to_string(&mut value)?;
+        value.strip_suffix('\n').map(|value| value.strip_suffix('\r').unwrap_or(value)).unwrap_or(&value).to_string()
+    };
+    if value.is_empty() {
+        return Err("The value must not be empty".into());
+    }
+    Ok(value)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], kdf: KdfParams) -> Result<LessSafeKey, Box<dyn Error>> {
+    let params = Params::new(kdf.memory_kib, kdf.iterations, kdf.parallelism, Some(KEY_LEN)).map_err(|e| format!("Invalid vault parameters: {}", e))?;
+    let mut key = [0u8; KEY_LEN];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("Key derivation failed: {}", e))?;
+    let key = UnboundKey::new(&AES_256_GCM, &key).map_err(|_| "Key derivation failed")?;
+    Ok(LessSafeKey::new(key))
+}
+
+fn encrypt(passphrase: &str, entries: &Entries, kdf: KdfParams) -> Result<VaultFile, Box<dyn Error>> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut salt);
+    OsRng.fill_bytes(&mut nonce);
+    let mut data = serde_json::to_vec(entries)?;
+    derive_key(passphrase, &salt, kdf)?
+        .seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut data)
+        .map_err(|_| "Encryption failed")?;
+    Ok(VaultFile {
+        version: FORMAT_VERSION,
+        kdf,
+        salt: STANDARD.encode(salt),
+        nonce: STANDARD.encode(nonce),
+        ciphertext: STANDARD.encode(data),
+    })
+}
+
+fn decrypt(passphrase: &str, vault: &VaultFile) -> Result<Entries, Box<dyn Error>> {
+    if vault.version != FORMAT_VERSION {
+        return Err(format!("Unsupported vault format version {}", vault.version).into());
+    }
+    let corrupted = |_| "The vault file is corrupted";
+    let salt = STANDARD.decode(&vault.salt).map_err(corrupted)?;
+    let nonce = Nonce::try_assume_unique_for_key(&STANDARD.decode(&vault.nonce).map_err(corrupted)?).map_err(|_| "The vault file is corrupted")?;
+    let mut data = STANDARD.decode(&vault.ciphertext).map_err(corrupted)?;
+    let plaintext = derive_key(passphrase, &salt, vault.kdf)?
+        .open_in_place(nonce, Aad::empty(), &mut data)
+        .map_err(|_| "Wrong passphrase, or the vault file was modified")?;
+    Ok(serde_json::from_slice(plaintext)?)
+}
+
+fn load(path: &Path, passphrase: &str) -> Result<Entries, Box<dyn Error>> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let vault: VaultFile = serde_json::from_str(&content).map_err(|e| format!("Invalid vault file {}: {}", path.display(), e))?;
+    decrypt(passphrase, &vault)
+}
+
+// Written to a temporary file (owner-only on unix) and renamed over the old
+// vault, so an interrupted write never loses it
+fn save(path: &Path, passphrase: &str, entries: &Entries, kdf: KdfParams) -> Result<(), Box<dyn Error>> {
+    let content = serde_json::to_string_pretty(&encrypt(passphrase, entries, kdf)?)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    let temp = path.with_extension("json.tmp");
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&temp).map_err(|e| format!("Failed to write {}: {}", temp.display(), e))?;
+    file.write_all(content.as_bytes())?;
+    file.sync_all()?;
+    fs::rename(&temp, path).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A low Argon2 cost keeps the tests fast
+    const TEST_KDF: KdfParams = KdfParams { memory_kib: 64, iterations: 1, parallelism: 1 };
+
+    fn entries() -> Entries {
+        let mut entries = Entries::new();
+        entries.insert("github".to_string(), Entry { value: "JBSWY3DPEHPK3PXP".to_string(), updated: "2024-05-01 09:00".to_string() });
+        entries
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let vault = encrypt("correct horse", &entries(), TEST_KDF).unwrap();
+        assert!(!vault.ciphertext.contains("github"));
+        assert_eq!(decrypt("correct horse", &vault).unwrap(), entries());
+        assert_eq!(decrypt("wrong", &vault).unwrap_err().to_string(), "Wrong passphrase, or the vault file was modified");
+    }
+
+    #[test]
+    fn test_tampering_is_detected() {
+        let mut vault = encrypt("correct horse", &entries(), TEST_KDF).unwrap();
+        let mut data = STANDARD.decode(&vault.ciphertext).unwrap();
+        data[0] ^= 1;
+        vault.ciphertext = STANDARD.encode(data);
+        assert!(decrypt("correct horse", &vault).is_err());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("ms-secret-vault-{}", std::process::id()));
+        let path = dir.join(VAULT_FILE);
+        save(&path, "pass", &entries(), TEST_KDF).unwrap();
+        let loaded = load(&path, "pass").unwrap();
+        assert_eq!(get_entry(&loaded, "github").unwrap(), "JBSWY3DPEHPK3PXP");
+        assert!(get_entry(&loaded, "gitlab").is_err());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
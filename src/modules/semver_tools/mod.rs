@@ -0,0 +1,241 @@
+use crate::tool_module::{ExitCodeError, ToolModule};
+use clap::{Arg, ArgMatches, Command};
+use semver::{BuildMetadata, Prerelease, Version, VersionReq};
+use std::cmp::Ordering;
+use std::error::Error;
+
+pub struct SemverToolsModule;
+
+const LEVELS: [&str; 8] = ["major", "minor", "patch", "premajor", "preminor", "prepatch", "prerelease", "release"];
+
+impl ToolModule for SemverToolsModule {
+    fn name(&self) -> &'static str {
+        "semver-tools"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("semver-bump")
+                .long("semver-bump")
+                .value_names(["VERSION", "LEVEL"])
+                .num_args(2)
+                .help("Print VERSION bumped by LEVEL: major, minor, patch, premajor, preminor, prepatch, prerelease or release")
+                .long_help("Print VERSION bumped by LEVEL, following npm's rules:\n- major, minor, patch: the next release; a pre-release becomes its own release when it already is that bump (1.5.0-rc.1 minor → 1.5.0)\n- premajor, preminor, prepatch: the next release as the first pre-release, e.g. 1.4.2 preminor → 1.5.0-rc.0\n- prerelease: the next pre-release (1.5.0-rc.1 → 1.5.0-rc.2), or prepatch for a release\n- release: drop the pre-release (1.5.0-rc.2 → 1.5.0)\nBuild metadata is dropped and a leading v is kept.")
+        )
+        .arg(
+            Arg::new("preid")
+                .long("preid")
+                .value_name("ID")
+                .help("Pre-release identifier for --semver-bump pre* levels, e.g. alpha or beta (default: rc)")
+                .requires("semver-bump")
+        )
+        .arg(
+            Arg::new("semver-compare")
+                .long("semver-compare")
+                .value_names(["A", "B"])
+                .num_args(2)
+                .help("Compare two versions by semver precedence, e.g. 1.10.0 > 1.9.0 and 1.0.0-rc.1 < 1.0.0")
+        )
+        .arg(
+            Arg::new("semver-satisfies")
+                .long("semver-satisfies")
+                .value_names(["RANGE", "VERSION"])
+                .num_args(2)
+                .help("Check whether VERSION matches a range like \"^1.2\", \"~1.4.0\" or \">=1.2, <2\"; exits 1 if not")
+                .long_help("Check whether VERSION matches RANGE and exit 1 if it does not. Ranges use Cargo syntax: ^1.2, ~1.4.0, 1.x, >=1.2 <2 (or >=1.2, <2), and alternatives joined with ||. A bare version means ^VERSION. Pre-releases only match a comparator that names the same MAJOR.MINOR.PATCH with a pre-release, so 2.0.0-rc.1 does not satisfy >=1.0.")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(values) = matches.get_many::<String>("semver-bump") {
+            let values: Vec<&String> = values.collect();
+            let (prefix, version) = parse_version(values[0])?;
+            let preid = matches.get_one::<String>("preid").map(String::as_str);
+            println!("{}{}", prefix, bump(&version, values[1], preid)?);
+        } else if let Some(values) = matches.get_many::<String>("semver-compare") {
+            let values: Vec<&String> = values.collect();
+            let (_, a) = parse_version(values[0])?;
+            let (_, b) = parse_version(values[1])?;
+            let symbol = match a.cmp_precedence(&b) {
+                Ordering::Less => "<",
+                Ordering::Equal => "=",
+                Ordering::Greater => ">",
+            };
+            println!("{} {} {}", values[0], symbol, values[1]);
+        } else if let Some(values) = matches.get_many::<String>("semver-satisfies") {
+            let values: Vec<&String> = values.collect();
+            let (_, version) = parse_version(values[1])?;
+            if satisfies(values[0], &version)? {
+                println!("✅ {} satisfies {}", values[1], values[0]);
+            } else {
+                println!("❌ {} does not satisfy {}", values[1], values[0]);
+                return Err(Box::new(ExitCodeError::silent(1)));
+            }
+        }
+        Ok(())
+    }
+}
+
+// "v1.4.2" -> ("v", 1.4.2), so tags keep their prefix when bumped
+fn parse_version(input: &str) -> Result<(&'static str, Version), String> {
+    let trimmed = input.trim();
+    let (prefix, bare) = match trimmed.strip_prefix(['v', 'V']) {
+        Some(bare) => ("v", bare),
+        None => ("", trimmed),
+    };
+    let version = Version::parse(bare).map_err(|e| format!("'{}' is not a semantic version (MAJOR.MINOR.PATCH[-PRE][+BUILD]): {}", input, e))?;
+    Ok((prefix, version))
+}
+
+fn bump(version: &Version, level: &str, preid: Option<&str>) -> Result<Version, String> {
+    let mut next = version.clone();
+    next.build = BuildMetadata::EMPTY;
+    let is_pre = !version.pre.is_empty();
+    match level {
+        "major" => {
+            if !(is_pre && version.minor == 0 && version.patch == 0) {
+                next.major += 1;
+            }
+            next.minor = 0;
+            next.patch = 0;
+            next.pre = Prerelease::EMPTY;
+        }
+        "minor" => {
+            if !(is_pre && version.patch == 0) {
+                next.minor += 1;
+            }
+            next.patch = 0;
+            next.pre = Prerelease::EMPTY;
+        }
+        "patch" => {
+            if !is_pre {
+                next.patch += 1;
+            }
+            next.pre = Prerelease::EMPTY;
+        }
+        "premajor" => {
+            next = Version::new(version.major + 1, 0, 0);
+            next.pre = first_prerelease(preid)?;
+        }
+        "preminor" => {
+            next = Version::new(version.major, version.minor + 1, 0);
+            next.pre = first_prerelease(preid)?;
+        }
+        "prepatch" => {
+            next = Version::new(version.major, version.minor, version.patch + 1);
+            next.pre = first_prerelease(preid)?;
+        }
+        "prerelease" if !is_pre => return bump(version, "prepatch", preid),
+        "prerelease" => next.pre = next_prerelease(&version.pre, preid)?,
+        "release" => next.pre = Prerelease::EMPTY,
+        other => return Err(format!("Unknown bump level '{}'. Use one of: {}", other, LEVELS.join(", "))),
+    }
+    Ok(next)
+}
+
+fn first_prerelease(preid: Option<&str>) -> Result<Prerelease, String> {
+    let preid = preid.unwrap_or("rc");
+    Prerelease::new(&format!("{}.0", preid)).map_err(|e| format!("Invalid pre-release identifier '{}': {}", preid, e))
+}
+
+// rc.1 -> rc.2 and beta -> beta.0; switching identifier (rc.3 with --preid beta) starts over at beta.0
+fn next_prerelease(pre: &Prerelease, preid: Option<&str>) -> Result<Prerelease, String> {
+    let mut parts: Vec<String> = pre.as_str().split('.').map(str::to_string).collect();
+    if preid.is_some_and(|preid| parts[0] != preid) {
+        return first_prerelease(preid);
+    }
+    match parts.last().and_then(|last| last.parse::<u64>().ok()) {
+        Some(number) => *parts.last_mut().unwrap() = (number + 1).to_string(),
+        None => parts.push("0".to_string()),
+    }
+    Prerelease::new(&parts.join(".")).map_err(|e| e.to_string())
+}
+
+fn satisfies(range: &str, version: &Version) -> Result<bool, String> {
+    for alternative in range.split("||") {
+        let normalized = normalize_range(alternative);
+        let req = VersionReq::parse(&normalized).map_err(|e| format!("Invalid range '{}': {}", alternative.trim(), e))?;
+        if req.matches(version) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+// npm-style ">= 1.2 <2" -> ">=1.2, <2"; comma-separated ranges pass through unchanged
+fn normalize_range(range: &str) -> String {
+    if range.contains(',') {
+        return range.trim().to_string();
+    }
+    let mut comparators: Vec<String> = Vec::new();
+    let mut pending_op = String::new();
+    for token in range.split_whitespace() {
+        if token.chars().all(|c| "<>=~^".contains(c)) {
+            pending_op.push_str(token);
+        } else {
+            comparators.push(format!("{}{}", pending_op, token));
+            pending_op.clear();
+        }
+    }
+    comparators.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bumped(version: &str, level: &str) -> String {
+        let (prefix, version) = parse_version(version).unwrap();
+        format!("{}{}", prefix, bump(&version, level, None).unwrap())
+    }
+
+    #[test]
+    fn test_bump() {
+        assert_eq!(bumped("1.4.2", "major"), "2.0.0");
+        assert_eq!(bumped("1.4.2", "minor"), "1.5.0");
+        assert_eq!(bumped("v1.4.2+build.7", "patch"), "v1.4.3");
+        // A pre-release of the bump target is released rather than skipped
+        assert_eq!(bumped("2.0.0-rc.1", "major"), "2.0.0");
+        assert_eq!(bumped("1.5.0-rc.1", "minor"), "1.5.0");
+        assert_eq!(bumped("1.5.1-rc.1", "minor"), "1.6.0");
+        assert_eq!(bumped("1.4.3-rc.1", "patch"), "1.4.3");
+        assert_eq!(bumped("1.5.0-rc.2", "release"), "1.5.0");
+        assert!(bump(&Version::new(1, 0, 0), "huge", None).is_err());
+    }
+
+    #[test]
+    fn test_prerelease_bumps() {
+        assert_eq!(bumped("1.4.2", "preminor"), "1.5.0-rc.0");
+        assert_eq!(bumped("1.4.2", "prerelease"), "1.4.3-rc.0");
+        assert_eq!(bumped("1.5.0-rc.1", "prerelease"), "1.5.0-rc.2");
+        assert_eq!(bumped("1.5.0-beta", "prerelease"), "1.5.0-beta.0");
+        let (_, rc) = parse_version("1.5.0-rc.3").unwrap();
+        assert_eq!(bump(&rc, "prerelease", Some("beta")).unwrap().to_string(), "1.5.0-beta.0");
+        assert_eq!(bump(&rc, "prerelease", Some("rc")).unwrap().to_string(), "1.5.0-rc.4");
+        assert_eq!(bump(&rc, "premajor", Some("alpha")).unwrap().to_string(), "2.0.0-alpha.0");
+        assert!(bump(&rc, "prepatch", Some("not valid")).is_err());
+    }
+
+    #[test]
+    fn test_precedence_ignores_build_metadata() {
+        let version = |input: &str| parse_version(input).unwrap().1;
+        assert_eq!(version("1.10.0").cmp_precedence(&version("1.9.0")), Ordering::Greater);
+        assert_eq!(version("1.0.0-rc.1").cmp_precedence(&version("1.0.0")), Ordering::Less);
+        assert_eq!(version("1.0.0-alpha.10").cmp_precedence(&version("1.0.0-alpha.9")), Ordering::Greater);
+        assert_eq!(version("1.0.0+linux").cmp_precedence(&version("v1.0.0+mac")), Ordering::Equal);
+        assert!(parse_version("1.2").is_err());
+    }
+
+    #[test]
+    fn test_satisfies() {
+        let version = |input: &str| parse_version(input).unwrap().1;
+        assert!(satisfies("^1.2", &version("1.4.0")).unwrap());
+        assert!(!satisfies("~1.2.0", &version("1.4.0")).unwrap());
+        assert!(satisfies(">= 1.2 <2", &version("1.9.9")).unwrap());
+        assert!(satisfies(">=1.2, <2", &version("1.9.9")).unwrap());
+        assert!(satisfies("^1 || ^3", &version("3.1.0")).unwrap());
+        assert!(!satisfies(">=1.0", &version("2.0.0-rc.1")).unwrap());
+        assert!(satisfies(">=2.0.0-rc.0", &version("2.0.0-rc.1")).unwrap());
+        assert!(satisfies("not a range", &version("1.0.0")).is_err());
+    }
+}
@@ -0,0 +1,206 @@
+use crate::config::{load_config, ShareConfig};
+use crate::qr_generate::{generate_qr_ascii, QrOptions};
+use crate::tool_module::ToolModule;
+use arboard::Clipboard;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use reqwest::blocking::Client;
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use std::error::Error;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::time::Duration;
+
+pub struct SharePasteModule;
+
+const GIST_API: &str = "https://api.github.com/gists";
+const DEFAULT_PASTE_URL: &str = "https://paste.rs";
+const STDIN_FILE_NAME: &str = "paste.txt";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Service {
+    Gist,
+    Paste,
+}
+
+impl ToolModule for SharePasteModule {
+    fn name(&self) -> &'static str {
+        "share-paste"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("share")
+                .long("share")
+                .value_name("FILE")
+                .help("Upload a file or stdin to a GitHub Gist or pastebin and print and copy the URL")
+                .long_help("Upload a file, or stdin when no file is given, and print the resulting URL, which is also copied to the clipboard. Uses a GitHub Gist by default, with the token from the config file or $GITHUB_TOKEN; set service = \"paste\" to use a pastebin instead:\n\n[share]\nservice = \"gist\"                # or \"paste\"\ngithub_token = \"env:GITHUB_TOKEN\"  # or the token itself\npaste_url = \"https://paste.rs\"   # takes the raw body as a POST and answers with the URL")
+                .num_args(0..=1)
+                .default_missing_value("")
+        )
+        .arg(
+            Arg::new("private")
+                .long("private")
+                .action(ArgAction::SetTrue)
+                .help("With --share, create a secret gist that is only reachable through its URL")
+                .requires("share")
+        )
+        .arg(
+            Arg::new("qr")
+                .long("qr")
+                .action(ArgAction::SetTrue)
+                .help("With --share, also render the URL as a QR code")
+                .requires("share")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(source) = matches.get_one::<String>("share") {
+            let (file_name, content) = read_source(source)?;
+            if content.trim().is_empty() {
+                return Err("Nothing to share: the input is empty".into());
+            }
+            let share = load_config()?.share;
+            let private = matches.get_flag("private");
+            let url = match parse_service(share.service.as_deref())? {
+                Service::Gist => {
+                    let token = resolve_token(&share, |var| std::env::var(var).ok())?;
+                    upload_gist(&token, &file_name, &content, private)?
+                }
+                Service::Paste => {
+                    if private {
+                        eprintln!("⚠️ --private only applies to gists; the paste is reachable by anyone with the URL");
+                    }
+                    upload_paste(share.paste_url.as_deref().unwrap_or(DEFAULT_PASTE_URL), &content)?
+                }
+            };
+            match Clipboard::new().and_then(|mut clipboard| clipboard.set_text(&url)) {
+                Ok(()) => println!("✅ {} (copied to clipboard)", url),
+                Err(_) => println!("✅ {}", url),
+            }
+            if matches.get_flag("qr") {
+                println!();
+                println!("{}", generate_qr_ascii(&url, &QrOptions::default())?);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn read_source(source: &str) -> Result<(String, String), Box<dyn Error>> {
+    if source.is_empty() || source == "-" {
+        let mut content = String::new();
+        io::stdin().read_to_string(&mut content)?;
+        return Ok((STDIN_FILE_NAME.to_string(), content));
+    }
+    let content = fs::read_to_string(source).map_err(|e| format!("Failed to read '{}': {}", source, e))?;
+    let file_name = Path::new(source)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| STDIN_FILE_NAME.to_string());
+    Ok((file_name, content))
+}
+
+fn parse_service(service: Option<&str>) -> Result<Service, Box<dyn Error>> {
+    match service.map(str::to_lowercase).as_deref() {
+        None | Some("gist") => Ok(Service::Gist),
+        Some("paste") | Some("pastebin") => Ok(Service::Paste),
+        Some(other) => Err(format!("Unknown share service '{}' in the config file; use \"gist\" or \"paste\"", other).into()),
+    }
+}
+
+// The configured token, "env:VAR", or $GITHUB_TOKEN as the fallback
+fn resolve_token(share: &ShareConfig, env: impl Fn(&str) -> Option<String>) -> Result<String, Box<dyn Error>> {
+    match share.github_token.as_deref() {
+        Some(source) => match source.strip_prefix("env:") {
+            Some(var) => env(var).ok_or_else(|| format!("Environment variable {} (GitHub token for --share) is not set", var).into()),
+            None => Ok(source.to_string()),
+        },
+        None => env("GITHUB_TOKEN").ok_or_else(|| "A GitHub token is needed to create gists. Set github_token under [share] in the config file or export GITHUB_TOKEN, or set service = \"paste\"".into()),
+    }
+}
+
+fn gist_payload(file_name: &str, content: &str, private: bool) -> Value {
+    json!({
+        "description": format!("{} (shared with micro-swiss)", file_name),
+        "public": !private,
+        "files": { file_name: { "content": content } },
+    })
+}
+
+fn client() -> Result<Client, reqwest::Error> {
+    Client::builder()
+        .user_agent(concat!("micro-swiss/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(30))
+        .build()
+}
+
+fn upload_gist(token: &str, file_name: &str, content: &str, private: bool) -> Result<String, Box<dyn Error>> {
+    let response = client()?
+        .post(GIST_API)
+        .header(ACCEPT, "application/vnd.github+json")
+        .bearer_auth(token)
+        .header(CONTENT_TYPE, "application/json")
+        .body(gist_payload(file_name, content, private).to_string())
+        .send()
+        .map_err(|e| format!("Failed to create gist: {}", e))?;
+    match response.status() {
+        StatusCode::UNAUTHORIZED => return Err("GitHub rejected the token (HTTP 401); check that it is valid and has the gist scope".into()),
+        status if !status.is_success() => return Err(format!("Failed to create gist: HTTP {}", status).into()),
+        _ => {}
+    }
+    let response: Value = serde_json::from_str(&response.text()?)?;
+    response["html_url"].as_str().map(str::to_string).ok_or_else(|| "GitHub did not return a gist URL".into())
+}
+
+fn upload_paste(paste_url: &str, content: &str) -> Result<String, Box<dyn Error>> {
+    let response = client()?
+        .post(paste_url)
+        .header(CONTENT_TYPE, "text/plain; charset=utf-8")
+        .body(content.to_string())
+        .send()
+        .map_err(|e| format!("Failed to upload to {}: {}", paste_url, e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to upload to {}: HTTP {}", paste_url, response.status()).into());
+    }
+    let body = response.text()?;
+    let url = body.trim();
+    if url.starts_with("http://") || url.starts_with("https://") {
+        Ok(url.to_string())
+    } else {
+        Err(format!("Unexpected response from {}: {}", paste_url, url.chars().take(200).collect::<String>()).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_service() {
+        assert_eq!(parse_service(None).unwrap(), Service::Gist);
+        assert_eq!(parse_service(Some("Paste")).unwrap(), Service::Paste);
+        assert!(parse_service(Some("dropbox")).is_err());
+    }
+
+    #[test]
+    fn test_resolve_token() {
+        let env = |var: &str| (var == "GH_TOKEN" || var == "GITHUB_TOKEN").then(|| format!("from-{}", var));
+        let share = |token: Option<&str>| ShareConfig { github_token: token.map(str::to_string), ..Default::default() };
+        assert_eq!(resolve_token(&share(Some("ghp_literal")), env).unwrap(), "ghp_literal");
+        assert_eq!(resolve_token(&share(Some("env:GH_TOKEN")), env).unwrap(), "from-GH_TOKEN");
+        assert_eq!(resolve_token(&share(None), env).unwrap(), "from-GITHUB_TOKEN");
+        assert!(resolve_token(&share(Some("env:MISSING")), env).is_err());
+        assert!(resolve_token(&share(None), |_: &str| None).is_err());
+    }
+
+    #[test]
+    fn test_gist_payload() {
+        let payload = gist_payload("notes.md", "# hi\n", true);
+        assert_eq!(payload["public"], false);
+        assert_eq!(payload["files"]["notes.md"]["content"], "# hi\n");
+        assert_eq!(payload["description"], "notes.md (shared with micro-swiss)");
+    }
+}
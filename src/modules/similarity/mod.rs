@@ -0,0 +1,156 @@
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use std::error::Error;
+use std::io::{self, Read};
+
+pub struct SimilarityModule;
+
+impl ToolModule for SimilarityModule {
+    fn name(&self) -> &'static str {
+        "similarity"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("similarity")
+                .long("similarity")
+                .value_names(["A", "B"])
+                .num_args(2)
+                .help("Compare two strings: Levenshtein distance, Jaro-Winkler and a similarity percentage")
+                .conflicts_with("fuzzy-find")
+        )
+        .arg(
+            Arg::new("fuzzy-find")
+                .long("fuzzy-find")
+                .value_name("NEEDLE")
+                .help("Rank the lines read from stdin by how closely they match NEEDLE")
+                .long_help("Read lines from stdin and print the closest matches to NEEDLE, best first, with their score. Lines containing NEEDLE rank highest; the rest are ranked by Jaro-Winkler similarity, so typos and transposed letters still match. Use --top to change how many matches are shown.")
+        )
+        .arg(
+            Arg::new("top")
+                .long("top")
+                .value_name("N")
+                .help("Number of matches shown by --fuzzy-find (default: 10)")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("10")
+                .requires("fuzzy-find")
+        )
+        .arg(
+            Arg::new("ignore-case")
+                .long("ignore-case")
+                .help("Compare case-insensitively in --similarity, --fuzzy-find, --lines and --logs --grep")
+                .action(ArgAction::SetTrue)
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let ignore_case = matches.get_flag("ignore-case");
+        if let Some(values) = matches.get_many::<String>("similarity") {
+            let values: Vec<String> = values.map(|value| normalize(value, ignore_case)).collect();
+            let report = compare(&values[0], &values[1]);
+            println!("Levenshtein distance: {}", report.distance);
+            println!("Damerau-Levenshtein:  {}", report.damerau_distance);
+            println!("Jaro-Winkler:         {:.4}", report.jaro_winkler);
+            println!("Similarity:           {:.1}%", report.similarity * 100.0);
+        } else if let Some(needle) = matches.get_one::<String>("fuzzy-find") {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            let top = matches.get_one::<usize>("top").copied().unwrap_or(10);
+            let ranked = fuzzy_find(needle, input.lines(), ignore_case);
+            if ranked.is_empty() {
+                return Err("No input lines to search".into());
+            }
+            for (score, line) in ranked.into_iter().take(top) {
+                println!("{:>6.1}%  {}", score * 100.0, line);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn normalize(text: &str, ignore_case: bool) -> String {
+    if ignore_case { text.to_lowercase() } else { text.to_string() }
+}
+
+struct Comparison {
+    distance: usize,
+    damerau_distance: usize,
+    jaro_winkler: f64,
+    // 1 - distance / length of the longer string
+    similarity: f64,
+}
+
+fn compare(a: &str, b: &str) -> Comparison {
+    Comparison {
+        distance: strsim::levenshtein(a, b),
+        damerau_distance: strsim::damerau_levenshtein(a, b),
+        jaro_winkler: strsim::jaro_winkler(a, b),
+        similarity: strsim::normalized_levenshtein(a, b),
+    }
+}
+
+// Substring hits score 0.9 and up, scaled by how much of the line they cover;
+// everything else scores its Jaro-Winkler similarity capped just below that
+pub fn match_score(needle: &str, line: &str) -> f64 {
+    if needle.is_empty() || line.is_empty() {
+        return 0.0;
+    }
+    if line.contains(needle) {
+        return 0.9 + 0.1 * needle.chars().count() as f64 / line.chars().count() as f64;
+    }
+    let whole = strsim::jaro_winkler(needle, line);
+    // Compare against each word too, so "jonh" finds "John Smith"
+    let best_word = line.split_whitespace().map(|word| strsim::jaro_winkler(needle, word)).fold(0.0, f64::max);
+    whole.max(best_word * 0.95).min(0.89)
+}
+
+fn fuzzy_find<'a>(needle: &str, lines: impl Iterator<Item = &'a str>, ignore_case: bool) -> Vec<(f64, &'a str)> {
+    let needle = normalize(needle.trim(), ignore_case);
+    let mut ranked: Vec<(f64, &str)> = lines
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| (match_score(&needle, &normalize(line.trim(), ignore_case)), line))
+        .collect();
+    // Stable, so equal scores keep their input order
+    ranked.sort_by(|a, b| b.0.total_cmp(&a.0));
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare() {
+        let report = compare("kitten", "sitting");
+        assert_eq!(report.distance, 3);
+        assert!((report.similarity - 4.0 / 7.0).abs() < 1e-9);
+        assert!((compare("martha", "marhta").jaro_winkler - 0.9611).abs() < 1e-4);
+        assert_eq!(compare("ca", "ac").damerau_distance, 1);
+        assert_eq!(compare("same", "same").similarity, 1.0);
+    }
+
+    #[test]
+    fn test_ignore_case() {
+        assert_eq!(compare(&normalize("Hello", true), &normalize("hELLO", true)).distance, 0);
+        assert_eq!(compare(&normalize("Hello", false), &normalize("hELLO", false)).distance, 5);
+    }
+
+    #[test]
+    fn test_fuzzy_find_ranks_typos() {
+        let names = "Jonathan Smith\nJohn Smith\nJane Doe\nJohanna Schmidt\n\nBob";
+        let ranked = fuzzy_find("jonh smith", names.lines(), true);
+        assert_eq!(ranked.len(), 5);
+        assert_eq!(ranked[0].1, "John Smith");
+        assert_eq!(ranked.last().unwrap().1, "Bob");
+    }
+
+    #[test]
+    fn test_fuzzy_find_prefers_substrings() {
+        let lines = ["configuration.yaml", "config.yaml", "conifg.yml", "readme.md"];
+        let ranked = fuzzy_find("config", lines.into_iter(), false);
+        assert_eq!(ranked[0].1, "config.yaml");
+        assert_eq!(ranked[1].1, "configuration.yaml");
+        assert_eq!(ranked[2].1, "conifg.yml");
+        assert!(fuzzy_find("Config", lines.into_iter(), false)[0].0 < 0.9);
+    }
+}
@@ -0,0 +1,246 @@
+use crate::clip_tools::preview;
+use crate::config::data_dir;
+use crate::similarity::match_score;
+use crate::tool_module::{ExitCodeError, ToolModule};
+use arboard::Clipboard;
+use chrono::Local;
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io::{self, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+
+pub struct SnippetsModule;
+
+const SNIPPETS_FILE: &str = "snippets.json";
+// Names at least this similar are offered when a lookup misses
+const SUGGESTION_THRESHOLD: f64 = 0.8;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct Snippet {
+    text: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    updated: String,
+}
+
+type Snippets = BTreeMap<String, Snippet>;
+
+impl ToolModule for SnippetsModule {
+    fn name(&self) -> &'static str {
+        "snippets"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("snippet")
+                .long("snippet")
+                .value_names(["ACTION", "NAME"])
+                .num_args(1..=2)
+                .help("Saved commands and code blocks: save NAME, get NAME, search [TERM], remove NAME")
+                .long_help("Keep frequently reused commands and code blocks in snippets.json in the data directory ($MICRO_SWISS_DATA).\n\n  save NAME      store stdin, or the clipboard when nothing is piped; add --tags to label it\n  get NAME       print the snippet, or copy it to the clipboard with --copy\n  search [TERM]  list snippets whose name, tags or text contain TERM; #tag matches a tag only\n  remove NAME    delete a snippet")
+        )
+        .arg(
+            Arg::new("tags")
+                .long("tags")
+                .value_name("TAGS")
+                .help("Comma-separated tags for --snippet save, e.g. git,cleanup")
+                .requires("snippet")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(values) = matches.get_many::<String>("snippet") {
+            let values: Vec<&str> = values.map(String::as_str).collect();
+            let path = snippets_path()?;
+            let mut snippets = load(&path)?;
+            match values.as_slice() {
+                ["save", name] => {
+                    let text = read_text()?;
+                    let tags = matches.get_one::<String>("tags").map(|tags| parse_tags(tags)).unwrap_or_default();
+                    let snippet = Snippet { text, tags, updated: Local::now().format("%Y-%m-%d %H:%M").to_string() };
+                    let replaced = snippets.insert(name.to_string(), snippet).is_some();
+                    save(&path, &snippets)?;
+                    println!("✅ {} snippet {}", if replaced { "Updated" } else { "Saved" }, name.bold());
+                }
+                ["get", name] => {
+                    let snippet = find(&snippets, name)?;
+                    // --copy is declared by the secret-vault module
+                    if matches.get_flag("copy") {
+                        Clipboard::new()
+                            .and_then(|mut clipboard| clipboard.set_text(&snippet.text))
+                            .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+                        println!("✅ Copied snippet {} to clipboard", name.bold());
+                    } else {
+                        print!("{}", snippet.text);
+                        if !snippet.text.ends_with('\n') {
+                            println!();
+                        }
+                    }
+                }
+                ["search"] | ["list"] => print_snippets(&snippets, snippets.keys().map(String::as_str).collect()),
+                ["search", term] => {
+                    let found = search(&snippets, term);
+                    if found.is_empty() {
+                        return Err(Box::new(ExitCodeError::new(1, format!("No snippets match '{}'", term))));
+                    }
+                    print_snippets(&snippets, found);
+                }
+                ["remove", name] => {
+                    if snippets.remove(*name).is_none() {
+                        return Err(not_found(&snippets, name));
+                    }
+                    save(&path, &snippets)?;
+                    println!("✅ Removed snippet {}", name.bold());
+                }
+                _ => return Err("Usage: --snippet save NAME | get NAME | search [TERM] | remove NAME".into()),
+            }
+        }
+        Ok(())
+    }
+}
+
+fn snippets_path() -> Result<PathBuf, String> {
+    data_dir().map(|dir| dir.join(SNIPPETS_FILE)).ok_or_else(|| "Could not determine a data directory; set MICRO_SWISS_DATA".to_string())
+}
+
+// Piped stdin, or the clipboard when run from a terminal
+fn read_text() -> Result<String, Box<dyn Error>> {
+    let text = if io::stdin().is_terminal() {
+        Clipboard::new().and_then(|mut clipboard| clipboard.get_text()).map_err(|e| format!("Failed to read the clipboard: {}", e))?
+    } else {
+        let mut text = String::new();
+        io::stdin().read_to_string(&mut text)?;
+        text
+    };
+    if text.trim().is_empty() {
+        return Err("Nothing to save: pipe the snippet in or copy it to the clipboard first".into());
+    }
+    Ok(text)
+}
+
+// Lowercased, deduplicated and without a leading #
+fn parse_tags(tags: &str) -> Vec<String> {
+    let mut parsed: Vec<String> = Vec::new();
+    for tag in tags.split(',').map(|tag| tag.trim().trim_start_matches('#').to_lowercase()).filter(|tag| !tag.is_empty()) {
+        if !parsed.contains(&tag) {
+            parsed.push(tag);
+        }
+    }
+    parsed
+}
+
+fn find<'a>(snippets: &'a Snippets, name: &str) -> Result<&'a Snippet, Box<dyn Error>> {
+    snippets.get(name).ok_or_else(|| not_found(snippets, name))
+}
+
+fn not_found(snippets: &Snippets, name: &str) -> Box<dyn Error> {
+    let similar: Vec<&str> = snippets
+        .keys()
+        .filter(|candidate| match_score(&name.to_lowercase(), &candidate.to_lowercase()) >= SUGGESTION_THRESHOLD)
+        .map(String::as_str)
+        .collect();
+    let hint = if similar.is_empty() { String::new() } else { format!(" (did you mean {}?)", similar.join(", ")) };
+    Box::new(ExitCodeError::new(1, format!("No snippet named '{}'{}", name, hint)))
+}
+
+// Names of the snippets matching TERM; "#tag" only looks at tags
+fn search<'a>(snippets: &'a Snippets, term: &str) -> Vec<&'a str> {
+    let term = term.to_lowercase();
+    snippets
+        .iter()
+        .filter(|(name, snippet)| match term.strip_prefix('#') {
+            Some(tag) => snippet.tags.iter().any(|candidate| candidate == tag),
+            None => {
+                name.to_lowercase().contains(&term)
+                    || snippet.tags.iter().any(|tag| tag.contains(&term))
+                    || snippet.text.to_lowercase().contains(&term)
+            }
+        })
+        .map(|(name, _)| name.as_str())
+        .collect()
+}
+
+fn print_snippets(snippets: &Snippets, names: Vec<&str>) {
+    if names.is_empty() {
+        println!("No snippets yet; save one with --snippet save NAME");
+        return;
+    }
+    let tags = |snippet: &Snippet| snippet.tags.iter().map(|tag| format!("#{}", tag)).collect::<Vec<_>>().join(" ");
+    let width = names.iter().map(|name| name.chars().count()).max().unwrap_or(0);
+    let tags_width = names.iter().map(|name| tags(&snippets[*name]).chars().count()).max().unwrap_or(0);
+    for name in names {
+        let snippet = &snippets[name];
+        println!("{:<width$}  {:<tags_width$}  {}", name.green().bold(), tags(snippet).cyan(), preview(&snippet.text), width = width, tags_width = tags_width);
+    }
+}
+
+fn load(path: &Path) -> Result<Snippets, String> {
+    match fs::read_to_string(path) {
+        Ok(text) => serde_json::from_str(&text).map_err(|e| format!("Corrupt snippets file {}: {}", path.display(), e)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Snippets::new()),
+        Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+// Written to a temporary file and renamed into place, owner-only on unix
+fn save(path: &Path, snippets: &Snippets) -> Result<(), String> {
+    let fail = |e: io::Error| format!("Failed to write {}: {}", path.display(), e);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(fail)?;
+    }
+    let temporary = path.with_extension("json.tmp");
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&temporary).map_err(fail)?;
+    file.write_all(serde_json::to_string_pretty(snippets).map_err(|e| e.to_string())?.as_bytes()).map_err(fail)?;
+    drop(file);
+    fs::rename(&temporary, path).map_err(fail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snippets() -> Snippets {
+        let snippet = |text: &str, tags: &str| Snippet { text: text.to_string(), tags: parse_tags(tags), updated: "2024-05-01 09:00".to_string() };
+        Snippets::from([
+            ("prune-branches".to_string(), snippet("git branch --merged | grep -v main | xargs git branch -d\n", "git,cleanup")),
+            ("docker-clean".to_string(), snippet("docker system prune -af\n", "docker,cleanup")),
+            ("rust-main".to_string(), snippet("fn main() {\n    println!(\"hi\");\n}\n", "rust")),
+        ])
+    }
+
+    #[test]
+    fn test_parse_tags() {
+        assert_eq!(parse_tags("Git, #cleanup,,git"), vec!["git", "cleanup"]);
+        assert!(parse_tags(" , ").is_empty());
+    }
+
+    #[test]
+    fn test_search() {
+        let snippets = snippets();
+        assert_eq!(search(&snippets, "#cleanup"), vec!["docker-clean", "prune-branches"]);
+        assert_eq!(search(&snippets, "PRINTLN"), vec!["rust-main"]);
+        assert_eq!(search(&snippets, "git"), vec!["prune-branches"]);
+        assert!(search(&snippets, "#clean").is_empty());
+        assert_eq!(not_found(&snippets, "docker-cleen").to_string(), "No snippet named 'docker-cleen' (did you mean docker-clean?)");
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir().join(format!("ms-snippets-{}", std::process::id())).join(SNIPPETS_FILE);
+        assert!(load(&path).unwrap().is_empty());
+        save(&path, &snippets()).unwrap();
+        assert_eq!(load(&path).unwrap(), snippets());
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
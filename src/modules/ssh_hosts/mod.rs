@@ -0,0 +1,318 @@
+use crate::similarity::match_score;
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use std::error::Error;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+
+pub struct SshHostsModule;
+
+// Includes nested deeper than this are assumed to be a loop, as ssh does
+const MAX_INCLUDE_DEPTH: usize = 16;
+// Fuzzy matches scoring below this are dropped from --ssh-hosts PATTERN
+const MIN_SCORE: f64 = 0.75;
+// Options ssh collects from every matching block instead of taking the first
+const MULTI_VALUED: [&str; 6] = ["identityfile", "certificatefile", "localforward", "remoteforward", "dynamicforward", "sendenv"];
+
+#[derive(Debug, Clone, PartialEq)]
+struct Directive {
+    keyword: String,
+    value: String,
+    // Where it came from, for --ssh-resolve
+    source: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Item {
+    // Host patterns, or None for a Match block we cannot evaluate
+    Block(Option<Vec<String>>),
+    Option(Directive),
+}
+
+impl ToolModule for SshHostsModule {
+    fn name(&self) -> &'static str {
+        "ssh-hosts"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("ssh-hosts")
+                .long("ssh-hosts")
+                .value_name("PATTERN")
+                .num_args(0..=1)
+                .default_missing_value("")
+                .help("List hosts from ~/.ssh/config (following Include) with HostName, User, Port and IdentityFile, fuzzy-filtered by PATTERN")
+        )
+        .arg(
+            Arg::new("ssh-resolve")
+                .long("ssh-resolve")
+                .value_name("HOST")
+                .help("Print the options ~/.ssh/config gives HOST after applying every matching Host block, and where each came from")
+                .long_help("Print the options ssh would use for HOST from ~/.ssh/config: the first value of each option across every matching Host block (wildcards and negations included), with IdentityFile, forwards and SendEnv collected from all of them. Each option shows the file and line it came from. Match blocks are skipped since they depend on the connection.")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let pattern = matches.get_one::<String>("ssh-hosts");
+        let resolve = matches.get_one::<String>("ssh-resolve");
+        if pattern.is_none() && resolve.is_none() {
+            return Ok(());
+        }
+        let ssh_dir = dirs::home_dir().ok_or("Could not determine your home directory")?.join(".ssh");
+        let config = ssh_dir.join("config");
+        if !config.exists() {
+            return Err(format!("{} does not exist", config.display()).into());
+        }
+        let items = load(&config, &ssh_dir)?;
+        let color = io::stdout().is_terminal();
+        if let Some(pattern) = pattern {
+            let mut rows: Vec<(f64, [String; 5])> = aliases(&items)
+                .into_iter()
+                .map(|alias| {
+                    let options = resolve_host(&items, &alias);
+                    let get = |keyword: &str| options.iter().find(|option| option.keyword == keyword).map(|option| option.value.clone()).unwrap_or_default();
+                    let row = [alias.clone(), get("hostname"), get("user"), get("port"), get("identityfile")];
+                    let score = if pattern.is_empty() { 1.0 } else { row[..3].iter().map(|field| match_score(&pattern.to_lowercase(), &field.to_lowercase())).fold(0.0, f64::max) };
+                    (score, row)
+                })
+                .filter(|(score, _)| *score >= MIN_SCORE)
+                .collect();
+            // Stable, so equally good matches keep their config order
+            rows.sort_by(|a, b| b.0.total_cmp(&a.0));
+            let rows: Vec<[String; 5]> = rows.into_iter().map(|(_, row)| row).collect();
+            print!("{}", render_table(&rows, color));
+        }
+        if let Some(host) = resolve {
+            let options = resolve_host(&items, host);
+            let width = options.iter().map(|option| option.keyword.len()).max().unwrap_or(0);
+            for option in options {
+                let keyword = format!("{:<width$}", option.keyword, width = width);
+                let keyword = if color { keyword.cyan().to_string() } else { keyword };
+                let source = if color { option.source.dimmed().to_string() } else { option.source.clone() };
+                println!("{} {}  {}", keyword, option.value, source);
+            }
+        }
+        Ok(())
+    }
+}
+
+// The config with every Include spliced in where it appears
+fn load(path: &Path, ssh_dir: &Path) -> Result<Vec<Item>, String> {
+    let mut items = Vec::new();
+    load_into(path, ssh_dir, 0, &mut items)?;
+    Ok(items)
+}
+
+fn load_into(path: &Path, ssh_dir: &Path, depth: usize, items: &mut Vec<Item>) -> Result<(), String> {
+    if depth > MAX_INCLUDE_DEPTH {
+        return Err(format!("Include nested too deeply at {}; is there a loop?", path.display()));
+    }
+    let text = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    for (number, line) in text.lines().enumerate() {
+        let Some((keyword, value)) = split_directive(line) else { continue };
+        let source = format!("{}:{}", path.display(), number + 1);
+        match keyword.as_str() {
+            "host" => items.push(Item::Block(Some(words(&value)))),
+            "match" if value.trim().eq_ignore_ascii_case("all") => items.push(Item::Block(Some(vec!["*".to_string()]))),
+            "match" => items.push(Item::Block(None)),
+            "include" => {
+                for pattern in words(&value) {
+                    for file in expand_include(&pattern, ssh_dir) {
+                        load_into(&file, ssh_dir, depth + 1, items)?;
+                    }
+                }
+            }
+            _ => items.push(Item::Option(Directive { keyword, value, source })),
+        }
+    }
+    Ok(())
+}
+
+// "Keyword value", "Keyword=value" or "Keyword = value"; keywords are case-insensitive
+fn split_directive(line: &str) -> Option<(String, String)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let end = line.find(|c: char| c.is_whitespace() || c == '=').unwrap_or(line.len());
+    let (keyword, rest) = line.split_at(end);
+    let value = rest.trim_start();
+    let value = value.strip_prefix('=').unwrap_or(value).trim();
+    Some((keyword.to_lowercase(), value.to_string()))
+}
+
+// Whitespace-separated words, with double quotes keeping spaces together
+fn words(value: &str) -> Vec<String> {
+    shlex::split(value).unwrap_or_else(|| value.split_whitespace().map(str::to_string).collect())
+}
+
+// Relative includes are under ~/.ssh; wildcards match file names in one directory
+fn expand_include(pattern: &str, ssh_dir: &Path) -> Vec<PathBuf> {
+    let expanded = match pattern.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().map(|home| home.join(rest)).unwrap_or_else(|| PathBuf::from(pattern)),
+        None => PathBuf::from(pattern),
+    };
+    let path = if expanded.is_absolute() { expanded } else { ssh_dir.join(expanded) };
+    let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    if !name.contains(['*', '?']) {
+        // A missing include is not an error for ssh either
+        return if path.is_file() { vec![path] } else { Vec::new() };
+    }
+    let Some(dir) = path.parent() else { return Vec::new() };
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| entries.flatten().map(|entry| entry.path()).filter(|file| file.is_file() && file.file_name().is_some_and(|file| wildcard(&name, &file.to_string_lossy()))).collect())
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+// ssh_config patterns: * for any run of characters, ? for exactly one
+fn wildcard(pattern: &str, text: &str) -> bool {
+    let (pattern, text): (Vec<char>, Vec<char>) = (pattern.chars().collect(), text.chars().collect());
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p].eq_ignore_ascii_case(&text[t])) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+// A Host line applies when a pattern matches and no !negated one does
+fn host_matches(patterns: &[String], host: &str) -> bool {
+    let mut matched = false;
+    for pattern in patterns {
+        match pattern.strip_prefix('!') {
+            Some(negated) if wildcard(negated, host) => return false,
+            Some(_) => {}
+            None => matched |= wildcard(pattern, host),
+        }
+    }
+    matched
+}
+
+// Concrete host aliases, in config order
+fn aliases(items: &[Item]) -> Vec<String> {
+    let mut aliases: Vec<String> = Vec::new();
+    for item in items {
+        if let Item::Block(Some(patterns)) = item {
+            for pattern in patterns {
+                if !pattern.contains(['*', '?', '!']) && !aliases.contains(pattern) {
+                    aliases.push(pattern.clone());
+                }
+            }
+        }
+    }
+    aliases
+}
+
+// The options ssh would use for `host`: first value wins, except for the multi-valued ones
+fn resolve_host(items: &[Item], host: &str) -> Vec<Directive> {
+    let mut options: Vec<Directive> = Vec::new();
+    // Lines before the first Host apply to every host
+    let mut active = true;
+    for item in items {
+        match item {
+            Item::Block(Some(patterns)) => active = host_matches(patterns, host),
+            Item::Block(None) => active = false,
+            Item::Option(directive) if active => {
+                let repeated = options.iter().any(|option| option.keyword == directive.keyword);
+                if !repeated || MULTI_VALUED.contains(&directive.keyword.as_str()) {
+                    options.push(directive.clone());
+                }
+            }
+            Item::Option(_) => {}
+        }
+    }
+    for option in &mut options {
+        if option.keyword == "hostname" {
+            option.value = option.value.replace("%h", host);
+        }
+    }
+    options
+}
+
+fn render_table(rows: &[[String; 5]], color: bool) -> String {
+    if rows.is_empty() {
+        return "No matching hosts in ~/.ssh/config\n".to_string();
+    }
+    let header = ["HOST", "HOSTNAME", "USER", "PORT", "IDENTITY FILE"];
+    let widths: Vec<usize> = (0..header.len()).map(|column| rows.iter().map(|row| row[column].chars().count()).chain([header[column].len()]).max().unwrap_or(0)).collect();
+    let line = |cells: [&str; 5]| -> String {
+        let text: Vec<String> = cells.iter().zip(&widths).map(|(cell, width)| format!("{:<width$}", cell, width = width)).collect();
+        text.join("  ").trim_end().to_string()
+    };
+    let mut output = format!("{}\n", if color { line(header).bold().to_string() } else { line(header) });
+    for row in rows {
+        output.push_str(&line(row.each_ref().map(String::as_str)));
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ms-ssh-{}-{}", name, std::process::id()));
+        fs::create_dir_all(dir.join("config.d")).unwrap();
+        fs::write(
+            dir.join("config"),
+            "Include config.d/*.conf\n\nHost web prod-web\n    HostName 10.0.0.10\n    User deploy\n\nHost *.internal !bastion.internal\n    ProxyJump bastion.internal\n\nMatch exec \"true\"\n    User nobody\n\nHost *\n    User me\n    IdentityFile ~/.ssh/id_ed25519\n    ServerAliveInterval=30\n",
+        )
+        .unwrap();
+        fs::write(dir.join("config.d").join("work.conf"), "Host db.internal\n  Port 2222\n  IdentityFile ~/.ssh/work\n").unwrap();
+        fs::write(dir.join("config.d").join("notes.txt"), "Host ignored\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_patterns() {
+        assert!(wildcard("*.internal", "db.internal"));
+        assert!(wildcard("web?", "web1"));
+        assert!(!wildcard("web?", "web"));
+        assert!(wildcard("*", ""));
+        assert!(host_matches(&["*.internal".to_string(), "!bastion.internal".to_string()], "db.internal"));
+        assert!(!host_matches(&["*.internal".to_string(), "!bastion.internal".to_string()], "bastion.internal"));
+        assert_eq!(split_directive("  ServerAliveInterval = 30"), Some(("serveraliveinterval".to_string(), "30".to_string())));
+        assert_eq!(split_directive("# comment"), None);
+    }
+
+    #[test]
+    fn test_load_and_resolve() {
+        let dir = setup("resolve");
+        let items = load(&dir.join("config"), &dir).unwrap();
+        assert_eq!(aliases(&items), ["db.internal", "web", "prod-web"]);
+
+        let db: Vec<(String, String)> = resolve_host(&items, "db.internal").into_iter().map(|option| (option.keyword, option.value)).collect();
+        let pairs: Vec<(&str, &str)> = db.iter().map(|(keyword, value)| (keyword.as_str(), value.as_str())).collect();
+        assert_eq!(pairs, [("port", "2222"), ("identityfile", "~/.ssh/work"), ("proxyjump", "bastion.internal"), ("user", "me"), ("identityfile", "~/.ssh/id_ed25519"), ("serveraliveinterval", "30")]);
+
+        let web = resolve_host(&items, "web");
+        assert_eq!(web.iter().find(|option| option.keyword == "user").unwrap().value, "deploy");
+        assert!(web[0].source.ends_with("config:4"));
+        assert!(resolve_host(&items, "bastion.internal").iter().all(|option| option.keyword != "proxyjump"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_render_table() {
+        let rows = [["web".to_string(), "10.0.0.10".to_string(), "deploy".to_string(), String::new(), "~/.ssh/id".to_string()]];
+        assert_eq!(render_table(&rows, false), "HOST  HOSTNAME   USER    PORT  IDENTITY FILE\nweb   10.0.0.10  deploy        ~/.ssh/id\n");
+        assert!(render_table(&[], false).starts_with("No matching hosts"));
+    }
+}
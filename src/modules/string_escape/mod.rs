@@ -0,0 +1,296 @@
+use crate::tool_module::{read_text, ToolModule};
+use clap::{Arg, ArgMatches, Command};
+use std::error::Error;
+
+pub struct StringEscapeModule;
+
+const FORMATS: [&str; 6] = ["json", "shell", "c", "rust", "sql", "regex"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum EscapeFormat {
+    Json,
+    Shell,
+    C,
+    Rust,
+    Sql,
+    Regex,
+}
+
+impl EscapeFormat {
+    fn parse(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "json" => Ok(EscapeFormat::Json),
+            "shell" | "sh" | "bash" => Ok(EscapeFormat::Shell),
+            "c" => Ok(EscapeFormat::C),
+            "rust" | "rs" => Ok(EscapeFormat::Rust),
+            "sql" => Ok(EscapeFormat::Sql),
+            "regex" | "re" => Ok(EscapeFormat::Regex),
+            _ => Err(format!("Unknown format '{}'. Use one of: {}", name, FORMATS.join(", "))),
+        }
+    }
+}
+
+impl ToolModule for StringEscapeModule {
+    fn name(&self) -> &'static str {
+        "string-escape"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("escape")
+                .long("escape")
+                .value_names(["TEXT", "FORMAT"])
+                .num_args(2)
+                .help("Escape text as a json, shell, c, rust, sql or regex literal")
+                .long_help("Escape text so it can be pasted into code or a command line. Formats: json (double-quoted JSON string), shell (POSIX single-quoted word), c and rust (double-quoted string literals), sql (single-quoted literal) and regex (pattern matching the text literally). Use - as TEXT to read stdin; one trailing newline is dropped.")
+                .conflicts_with("unescape")
+        )
+        .arg(
+            Arg::new("unescape")
+                .long("unescape")
+                .value_names(["TEXT", "FORMAT"])
+                .num_args(2)
+                .help("Turn a json, shell, c, rust, sql or regex literal back into plain text")
+                .long_help("Decode an escaped literal in one of the --escape formats back to plain text. Surrounding quotes are optional. Use - as TEXT to read stdin; one trailing newline is dropped.")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(values) = matches.get_many::<String>("escape") {
+            let values: Vec<&String> = values.collect();
+            let format = EscapeFormat::parse(values[1])?;
+            println!("{}", escape(&read_text(values[0])?, format));
+        } else if let Some(values) = matches.get_many::<String>("unescape") {
+            let values: Vec<&String> = values.collect();
+            let format = EscapeFormat::parse(values[1])?;
+            println!("{}", unescape(&read_text(values[0])?, format)?);
+        }
+        Ok(())
+    }
+}
+
+fn escape(text: &str, format: EscapeFormat) -> String {
+    match format {
+        EscapeFormat::Json => serde_json::Value::String(text.to_string()).to_string(),
+        // Inside single quotes nothing is special, so only ' itself needs care
+        EscapeFormat::Shell => format!("'{}'", text.replace('\'', "'\\''")),
+        EscapeFormat::C | EscapeFormat::Rust => {
+            let mut escaped = String::from("\"");
+            for c in text.chars() {
+                match c {
+                    '\\' => escaped.push_str("\\\\"),
+                    '"' => escaped.push_str("\\\""),
+                    '\n' => escaped.push_str("\\n"),
+                    '\r' => escaped.push_str("\\r"),
+                    '\t' => escaped.push_str("\\t"),
+                    '\0' => escaped.push_str("\\0"),
+                    // Octal always takes exactly three digits in C, so a following
+                    // digit can't be swallowed the way it would be after \x
+                    c if c.is_control() && format == EscapeFormat::C && (c as u32) < 0x100 => {
+                        escaped.push_str(&format!("\\{:03o}", c as u32))
+                    }
+                    c if c.is_control() => escaped.push_str(&format!("\\u{{{:x}}}", c as u32)),
+                    c => escaped.push(c),
+                }
+            }
+            escaped.push('"');
+            escaped
+        }
+        EscapeFormat::Sql => format!("'{}'", text.replace('\'', "''")),
+        EscapeFormat::Regex => regex::escape(text),
+    }
+}
+
+fn unescape(text: &str, format: EscapeFormat) -> Result<String, String> {
+    match format {
+        EscapeFormat::Json => {
+            let quoted = if is_quoted(text, '"') { text.to_string() } else { format!("\"{}\"", text) };
+            serde_json::from_str::<String>(&quoted).map_err(|e| format!("Invalid JSON string: {}", e))
+        }
+        EscapeFormat::Shell => unescape_shell(text),
+        EscapeFormat::C | EscapeFormat::Rust => {
+            let inner = if is_quoted(text, '"') { &text[1..text.len() - 1] } else { text };
+            unescape_c_like(inner)
+        }
+        EscapeFormat::Sql => {
+            let inner = if is_quoted(text, '\'') { &text[1..text.len() - 1] } else { text };
+            Ok(inner.replace("''", "'"))
+        }
+        EscapeFormat::Regex => {
+            let mut plain = String::with_capacity(text.len());
+            let mut chars = text.chars();
+            while let Some(c) = chars.next() {
+                match (c, chars.clone().next()) {
+                    ('\\', Some(next)) if !next.is_alphanumeric() => {
+                        plain.push(next);
+                        chars.next();
+                    }
+                    ('\\', Some(next)) => return Err(format!("\\{} is a regex class, not an escaped character", next)),
+                    _ => plain.push(c),
+                }
+            }
+            Ok(plain)
+        }
+    }
+}
+
+fn is_quoted(text: &str, quote: char) -> bool {
+    text.len() >= 2 && text.starts_with(quote) && text.ends_with(quote)
+}
+
+// Handles the union of C and Rust escapes: \n \r \t \0 \a \b \f \v \e, \xNN,
+// octal \ooo, \uXXXX, \UXXXXXXXX and \u{X..}
+fn unescape_c_like(text: &str) -> Result<String, String> {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            plain.push(c);
+            continue;
+        }
+        let Some(escape) = chars.next() else {
+            return Err("Trailing backslash".to_string());
+        };
+        let code_point = |digits: String, radix: u32| {
+            u32::from_str_radix(&digits, radix).ok().and_then(char::from_u32)
+                .ok_or_else(|| format!("Invalid escape \\{}{}", escape, digits))
+        };
+        let decoded = match escape {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'a' => '\u{7}',
+            'b' => '\u{8}',
+            'f' => '\u{c}',
+            'v' => '\u{b}',
+            'e' => '\u{1b}',
+            '\\' | '"' | '\'' | '?' => escape,
+            '\n' => {
+                // Rust line continuation: skip the newline and leading whitespace
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                continue;
+            }
+            '0'..='7' => {
+                let mut digits = escape.to_string();
+                while digits.len() < 3 {
+                    match chars.next_if(|c| ('0'..='7').contains(c)) {
+                        Some(digit) => digits.push(digit),
+                        None => break,
+                    }
+                }
+                code_point(digits, 8)?
+            }
+            'x' => {
+                let mut digits = String::new();
+                while let Some(digit) = chars.next_if(char::is_ascii_hexdigit) {
+                    digits.push(digit);
+                }
+                code_point(digits, 16)?
+            }
+            'u' if chars.next_if_eq(&'{').is_some() => {
+                let mut digits = String::new();
+                while let Some(digit) = chars.next_if(|c| *c != '}') {
+                    digits.push(digit);
+                }
+                if chars.next() != Some('}') {
+                    return Err("Unclosed \\u{...} escape".to_string());
+                }
+                code_point(digits.replace('_', ""), 16)?
+            }
+            'u' | 'U' => {
+                let length = if escape == 'u' { 4 } else { 8 };
+                let digits: String = (0..length).filter_map(|_| chars.next_if(char::is_ascii_hexdigit)).collect();
+                if digits.len() != length {
+                    return Err(format!("\\{} needs {} hex digits", escape, length));
+                }
+                code_point(digits, 16)?
+            }
+            other => return Err(format!("Unknown escape \\{}", other)),
+        };
+        plain.push(decoded);
+    }
+    Ok(plain)
+}
+
+// POSIX shell words: 'single quotes' are literal, "double quotes" allow
+// \$ \` \" \\ and line continuations, and a bare backslash escapes one character
+fn unescape_shell(text: &str) -> Result<String, String> {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => loop {
+                match chars.next() {
+                    Some('\'') => break,
+                    Some(c) => plain.push(c),
+                    None => return Err("Unclosed single quote".to_string()),
+                }
+            },
+            '"' => loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => match chars.next() {
+                        Some(c @ ('$' | '`' | '"' | '\\')) => plain.push(c),
+                        Some('\n') => {}
+                        Some(c) => {
+                            plain.push('\\');
+                            plain.push(c);
+                        }
+                        None => return Err("Unclosed double quote".to_string()),
+                    },
+                    Some(c) => plain.push(c),
+                    None => return Err("Unclosed double quote".to_string()),
+                }
+            },
+            '\\' => match chars.next() {
+                Some('\n') | None => {}
+                Some(c) => plain.push(c),
+            },
+            c => plain.push(c),
+        }
+    }
+    Ok(plain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "It's a \"test\"\n\tpath: C:\\tmp $HOME";
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape(SAMPLE, EscapeFormat::Json), "\"It's a \\\"test\\\"\\n\\tpath: C:\\\\tmp $HOME\"");
+        assert_eq!(escape(SAMPLE, EscapeFormat::Shell), "'It'\\''s a \"test\"\n\tpath: C:\\tmp $HOME'");
+        assert_eq!(escape(SAMPLE, EscapeFormat::Rust), "\"It's a \\\"test\\\"\\n\\tpath: C:\\\\tmp $HOME\"");
+        assert_eq!(escape(SAMPLE, EscapeFormat::Sql), "'It''s a \"test\"\n\tpath: C:\\tmp $HOME'");
+        assert_eq!(escape("a.b*(c)", EscapeFormat::Regex), "a\\.b\\*\\(c\\)");
+        assert_eq!(escape("\u{1}9", EscapeFormat::C), "\"\\0019\"");
+        assert_eq!(escape("\u{1}9", EscapeFormat::Rust), "\"\\u{1}9\"");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for format in [EscapeFormat::Json, EscapeFormat::Shell, EscapeFormat::C, EscapeFormat::Rust, EscapeFormat::Sql, EscapeFormat::Regex] {
+            let text = format!("{} \u{7} ünïcode ^[a-z]+$", SAMPLE);
+            assert_eq!(unescape(&escape(&text, format), format).unwrap(), text, "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn test_unescape_literals() {
+        assert!(unescape("hello\\x41", EscapeFormat::Json).is_err());
+        assert_eq!(unescape("caf\\u00e9", EscapeFormat::Json).unwrap(), "café");
+        assert_eq!(unescape("\"\\x41\\101\\u{1F600}\\u00e9\"", EscapeFormat::C).unwrap(), "AA😀é");
+        assert_eq!(unescape("echo \"$\\\"x\\\"\" it\\'s 'a b'", EscapeFormat::Shell).unwrap(), "echo $\"x\" it's a b");
+        assert!(unescape("'open", EscapeFormat::Shell).is_err());
+        assert!(unescape("\\q", EscapeFormat::Rust).is_err());
+        assert!(unescape("\\d+", EscapeFormat::Regex).is_err());
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(EscapeFormat::parse("Bash").unwrap(), EscapeFormat::Shell);
+        assert!(EscapeFormat::parse("yaml").is_err());
+    }
+}
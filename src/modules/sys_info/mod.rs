@@ -0,0 +1,233 @@
+use crate::file_size::format_bytes;
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use colored::*;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::error::Error;
+use std::io::{self, IsTerminal};
+use std::thread;
+use sysinfo::{CpuRefreshKind, Disks, MemoryRefreshKind, RefreshKind, System};
+
+pub struct SysInfoModule;
+
+const BAR_WIDTH: usize = 20;
+const LABEL_WIDTH: usize = 8;
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+struct Summary {
+    host: Option<String>,
+    os: String,
+    kernel: Option<String>,
+    arch: String,
+    uptime_seconds: u64,
+    cpu: Cpu,
+    memory: Usage,
+    swap: Usage,
+    disks: Vec<Disk>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+struct Cpu {
+    model: String,
+    cores: Option<usize>,
+    threads: usize,
+    usage_percent: f32,
+    // 1, 5 and 15 minutes; Windows has no load average
+    load_average: Option<[f64; 3]>,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+struct Usage {
+    total_bytes: u64,
+    used_bytes: u64,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+struct Disk {
+    mount: String,
+    filesystem: String,
+    total_bytes: u64,
+    used_bytes: u64,
+}
+
+impl Usage {
+    fn percent(&self) -> f64 {
+        if self.total_bytes == 0 { 0.0 } else { self.used_bytes as f64 / self.total_bytes as f64 * 100.0 }
+    }
+}
+
+impl ToolModule for SysInfoModule {
+    fn name(&self) -> &'static str {
+        "sys-info"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("sysinfo")
+                .long("sysinfo")
+                .action(ArgAction::SetTrue)
+                .help("Summarize OS, kernel, CPU and load, memory, disks and uptime; --format json for scripts")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if matches.get_flag("sysinfo") {
+            // --format is declared by the text-diff module
+            let json = match matches.get_one::<String>("format").map(String::as_str) {
+                None | Some("text") => false,
+                Some("json") => true,
+                Some(other) => return Err(format!("--format {} does not apply to --sysinfo; use text or json", other).into()),
+            };
+            let summary = collect();
+            if json {
+                println!("{}", serde_json::to_string_pretty(&summary)?);
+            } else {
+                print!("{}", render(&summary, io::stdout().is_terminal()));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn collect() -> Summary {
+    let mut system = System::new_with_specifics(RefreshKind::nothing().with_cpu(CpuRefreshKind::everything()).with_memory(MemoryRefreshKind::everything()));
+    // CPU usage is measured between two refreshes
+    thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+    system.refresh_cpu_usage();
+    let load = System::load_average();
+    let mut seen = HashSet::new();
+    let disks = Disks::new_with_refreshed_list()
+        .list()
+        .iter()
+        .filter(|disk| disk.total_space() > 0 && seen.insert(disk.mount_point().to_path_buf()))
+        .map(|disk| Disk {
+            mount: disk.mount_point().display().to_string(),
+            filesystem: disk.file_system().to_string_lossy().to_string(),
+            total_bytes: disk.total_space(),
+            used_bytes: disk.total_space().saturating_sub(disk.available_space()),
+        })
+        .collect();
+    Summary {
+        host: System::host_name(),
+        os: System::long_os_version().or_else(System::name).unwrap_or_else(|| std::env::consts::OS.to_string()),
+        kernel: System::kernel_version(),
+        arch: System::cpu_arch(),
+        uptime_seconds: System::uptime(),
+        cpu: Cpu {
+            model: system.cpus().first().map(|cpu| cpu.brand().trim().to_string()).filter(|brand| !brand.is_empty()).unwrap_or_else(|| "unknown".to_string()),
+            cores: System::physical_core_count(),
+            threads: system.cpus().len(),
+            usage_percent: system.global_cpu_usage(),
+            load_average: (!cfg!(windows)).then_some([load.one, load.five, load.fifteen]),
+        },
+        memory: Usage { total_bytes: system.total_memory(), used_bytes: system.used_memory() },
+        swap: Usage { total_bytes: system.total_swap(), used_bytes: system.used_swap() },
+        disks,
+    }
+}
+
+// "3d 4h 12m", leaving out leading zero units
+fn format_uptime(seconds: u64) -> String {
+    let (days, hours, minutes) = (seconds / 86_400, seconds / 3600 % 24, seconds / 60 % 60);
+    match (days, hours) {
+        (0, 0) => format!("{}m", minutes),
+        (0, _) => format!("{}h {}m", hours, minutes),
+        _ => format!("{}d {}h {}m", days, hours, minutes),
+    }
+}
+
+// A fill bar coloured green, then yellow from 70% and red from 90%
+fn usage_bar(usage: &Usage, color: bool) -> String {
+    let percent = usage.percent();
+    let filled = ((percent / 100.0 * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH);
+    let bar = format!("{}{}", "█".repeat(filled), "░".repeat(BAR_WIDTH - filled));
+    let bar = if !color {
+        bar
+    } else if percent >= 90.0 {
+        bar.red().to_string()
+    } else if percent >= 70.0 {
+        bar.yellow().to_string()
+    } else {
+        bar.green().to_string()
+    };
+    format!("{} {} / {} ({:.0}%)", bar, format_bytes(usage.used_bytes), format_bytes(usage.total_bytes), percent)
+}
+
+fn render(summary: &Summary, color: bool) -> String {
+    let paint = |text: &str| if color { text.cyan().bold().to_string() } else { text.to_string() };
+    let label = |text: &str| paint(&format!("{:<width$}", text, width = LABEL_WIDTH));
+    let mut output = String::new();
+    if let Some(host) = &summary.host {
+        output.push_str(&format!("{} {}\n", label("Host"), host));
+    }
+    let kernel = summary.kernel.as_ref().map(|kernel| format!(", kernel {}", kernel)).unwrap_or_default();
+    output.push_str(&format!("{} {}, {}{}\n", label("OS"), summary.os, summary.arch, kernel));
+    output.push_str(&format!("{} {}\n", label("Uptime"), format_uptime(summary.uptime_seconds)));
+    let cores = summary.cpu.cores.map(|cores| format!("{} cores, ", cores)).unwrap_or_default();
+    output.push_str(&format!("{} {} ({}{} threads)\n", label("CPU"), summary.cpu.model, cores, summary.cpu.threads));
+    let load = summary.cpu.load_average.map(|[one, five, fifteen]| format!(", load {:.2} {:.2} {:.2}", one, five, fifteen)).unwrap_or_default();
+    output.push_str(&format!("{} {:.1}% busy{}\n", label("Load"), summary.cpu.usage_percent, load));
+    output.push_str(&format!("{} {}\n", label("Memory"), usage_bar(&summary.memory, color)));
+    if summary.swap.total_bytes > 0 {
+        output.push_str(&format!("{} {}\n", label("Swap"), usage_bar(&summary.swap, color)));
+    }
+    if !summary.disks.is_empty() {
+        output.push_str(&format!("{}\n", paint("Disks")));
+        let mount_width = summary.disks.iter().map(|disk| disk.mount.chars().count()).max().unwrap_or(0);
+        let fs_width = summary.disks.iter().map(|disk| disk.filesystem.chars().count()).max().unwrap_or(0);
+        for disk in &summary.disks {
+            let usage = Usage { total_bytes: disk.total_bytes, used_bytes: disk.used_bytes };
+            output.push_str(&format!("  {:<mount_width$}  {:<fs_width$}  {}\n", disk.mount, disk.filesystem, usage_bar(&usage, color), mount_width = mount_width, fs_width = fs_width));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    fn summary() -> Summary {
+        Summary {
+            host: Some("devbox".to_string()),
+            os: "Linux (Ubuntu 24.04)".to_string(),
+            kernel: Some("6.8.0".to_string()),
+            arch: "x86_64".to_string(),
+            uptime_seconds: 3 * 86_400 + 4 * 3600 + 12 * 60 + 5,
+            cpu: Cpu { model: "Example CPU".to_string(), cores: Some(4), threads: 8, usage_percent: 12.34, load_average: Some([0.5, 0.75, 1.0]) },
+            memory: Usage { total_bytes: 16 * GB, used_bytes: 4 * GB },
+            swap: Usage { total_bytes: 0, used_bytes: 0 },
+            disks: vec![Disk { mount: "/".to_string(), filesystem: "ext4".to_string(), total_bytes: 100 * GB, used_bytes: 95 * GB }],
+        }
+    }
+
+    #[test]
+    fn test_format_uptime() {
+        assert_eq!(format_uptime(59), "0m");
+        assert_eq!(format_uptime(3 * 3600 + 60), "3h 1m");
+        assert_eq!(format_uptime(86_400 * 2 + 90), "2d 0h 1m");
+    }
+
+    #[test]
+    fn test_render() {
+        let text = render(&summary(), false);
+        assert!(text.starts_with("Host     devbox\nOS       Linux (Ubuntu 24.04), x86_64, kernel 6.8.0\nUptime   3d 4h 12m\n"));
+        assert!(text.contains("CPU      Example CPU (4 cores, 8 threads)\nLoad     12.3% busy, load 0.50 0.75 1.00\n"));
+        assert!(text.contains(&format!("Memory   █████{} 4.0 GB / 16.0 GB (25%)\n", "░".repeat(15))));
+        assert!(!text.contains("Swap"));
+        assert!(text.ends_with("Disks\n  /  ext4  ███████████████████░ 95.0 GB / 100.0 GB (95%)\n"));
+    }
+
+    #[test]
+    fn test_collect() {
+        let summary = collect();
+        assert!(summary.cpu.threads > 0);
+        assert!(summary.memory.total_bytes > 0);
+        let json = serde_json::to_value(&summary).unwrap();
+        assert!(json["memory"]["used_bytes"].is_u64());
+        assert!(json["disks"].is_array());
+    }
+}
@@ -0,0 +1,692 @@
+use crate::case_convert::convert_case;
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use serde_json::{Map, Value};
+use std::error::Error;
+use std::fs;
+use std::io::{self, Read};
+
+pub struct TemplateRenderModule;
+
+const FILTERS: [&str; 17] = [
+    "upper", "lower", "title", "camel", "pascal", "snake", "kebab", "constant", "default", "length", "join", "trim", "json", "replace", "first",
+    "last", "quote",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Text(String),
+    Output(String, usize),
+    // A block tag as a Jinja statement; Handlebars tags are rewritten into these
+    Block(String, usize),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Literal(Value),
+    Path(Vec<String>),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(Box<Expr>, &'static str, Box<Expr>),
+    Filter(Box<Expr>, String, Vec<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Text(String),
+    Output(Expr, usize),
+    If { branches: Vec<(Expr, Vec<Node>)>, otherwise: Vec<Node> },
+    For { key: Option<String>, value: String, iterable: Expr, body: Vec<Node>, handlebars: bool, line: usize },
+}
+
+impl ToolModule for TemplateRenderModule {
+    fn name(&self) -> &'static str {
+        "template-render"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("template")
+                .long("template")
+                .value_names(["ACTION", "FILE"])
+                .num_args(2)
+                .help("Render a Jinja/Handlebars-style template: render FILE with --data and --set (- for stdin)")
+                .long_help("Render FILE (- for stdin) with the values from --data and --set, for generating config files and boilerplate.\n\n  {{ name }}                         a value; nested with user.email or items.0\n  {{ name | snake }}                 filters: upper, lower, title, camel, pascal, snake, kebab, constant,\n                                     default(\"x\"), length, join(\", \"), trim, json, quote, replace(\"a\", \"b\"), first, last\n  {% if a and not b %}…{% elif c == \"x\" %}…{% else %}…{% endif %}\n  {% for item in items %}…{% endfor %}   with loop.index, loop.first, loop.last; {% for key, value in map %}\n  {{#if x}}…{{else}}…{{/if}}  {{#unless x}}…{{/unless}}  {{#each items}}{{this}} {{@index}}{{/each}}\n  {# comment #}  {{! comment }}\n\nUsing a value that is not defined is an error unless it goes through default. Block tags on a line of their own leave no blank line behind; {{- and -}} trim whitespace next to a tag.")
+        )
+        .arg(
+            Arg::new("data")
+                .long("data")
+                .value_name("FILE")
+                .help("JSON or YAML file with the values for --template")
+                .requires("template")
+        )
+        .arg(
+            Arg::new("set")
+                .long("set")
+                .value_name("KEY=VALUE")
+                .help("Set a --template value (repeatable); dotted keys nest, JSON values such as 8080 or true keep their type")
+                .action(ArgAction::Append)
+                .requires("template")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(values) = matches.get_many::<String>("template") {
+            let values: Vec<&String> = values.collect();
+            if values[0] != "render" {
+                return Err(format!("Unknown template action '{}'. Use: --template render FILE", values[0]).into());
+            }
+            let template = if values[1] == "-" {
+                let mut buffer = String::new();
+                io::stdin().read_to_string(&mut buffer)?;
+                buffer
+            } else {
+                fs::read_to_string(values[1]).map_err(|e| format!("Failed to read '{}': {}", values[1], e))?
+            };
+            let mut data = match matches.get_one::<String>("data") {
+                Some(path) => load_data(path)?,
+                None => Value::Object(Map::new()),
+            };
+            for assignment in matches.get_many::<String>("set").unwrap_or_default() {
+                set_value(&mut data, assignment)?;
+            }
+            print!("{}", render(&template, &data).map_err(|e| format!("{}: {}", values[1], e))?);
+        }
+        Ok(())
+    }
+}
+
+// YAML for .yaml/.yml files, JSON otherwise
+fn load_data(path: &str) -> Result<Value, Box<dyn Error>> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read '{}': {}", path, e))?;
+    let data: Value = if path.ends_with(".yaml") || path.ends_with(".yml") {
+        serde_yaml::from_str(&content).map_err(|e| format!("Invalid YAML in '{}': {}", path, e))?
+    } else {
+        serde_json::from_str(&content).map_err(|e| format!("Invalid JSON in '{}': {}", path, e))?
+    };
+    if !data.is_object() {
+        return Err(format!("'{}' must hold an object at the top level", path).into());
+    }
+    Ok(data)
+}
+
+// "a.b=value", creating objects along the way
+fn set_value(data: &mut Value, assignment: &str) -> Result<(), String> {
+    let (key, value) = assignment.split_once('=').ok_or_else(|| format!("Invalid --set '{}'. Use KEY=VALUE", assignment))?;
+    let value = serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_string()));
+    let mut target = data;
+    let parts: Vec<&str> = key.trim().split('.').collect();
+    for (index, part) in parts.iter().enumerate() {
+        if !target.is_object() {
+            *target = Value::Object(Map::new());
+        }
+        let object = target.as_object_mut().ok_or("unreachable")?;
+        if index + 1 == parts.len() {
+            object.insert(part.to_string(), value);
+            return Ok(());
+        }
+        target = object.entry(part.to_string()).or_insert_with(|| Value::Object(Map::new()));
+    }
+    Ok(())
+}
+
+fn render(template: &str, data: &Value) -> Result<String, String> {
+    let tokens = tokenize(template)?;
+    let mut position = 0;
+    let (nodes, stray) = parse_nodes(&tokens, &mut position, &[])?;
+    if let Some((tag, line)) = stray {
+        return Err(format!("line {}: unexpected {{% {} %}}", line, tag));
+    }
+    let mut renderer = Renderer { root: data, scopes: Vec::new() };
+    let mut output = String::new();
+    renderer.render(&nodes, &mut output)?;
+    Ok(output)
+}
+
+// Rewrites a Handlebars tag body (after {{) into a Jinja statement, or None for output tags
+fn handlebars_block(inner: &str) -> Option<String> {
+    let (word, rest) = inner.split_once(char::is_whitespace).map_or((inner, ""), |(word, rest)| (word, rest.trim()));
+    Some(match word {
+        "#if" => format!("if {}", rest),
+        "#unless" => format!("if not ({})", rest),
+        "#each" => format!("each {}", rest),
+        "/if" | "/unless" => "endif".to_string(),
+        "/each" => "endfor".to_string(),
+        "else" if rest.starts_with("if ") => format!("elif {}", &rest[3..]),
+        "else" => "else".to_string(),
+        _ => return None,
+    })
+}
+
+fn tokenize(template: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut offset = 0;
+    let mut line = 1;
+    // Text after a tag waiting to be pushed, after any trimming the tag asked for
+    let mut pending = String::new();
+    loop {
+        let rest = &template[offset..];
+        let Some(start) = ["{{", "{%", "{#"].iter().filter_map(|open| rest.find(open)).min() else {
+            pending.push_str(rest);
+            break;
+        };
+        pending.push_str(&rest[..start]);
+        let tag_line = line + rest[..start].matches('\n').count();
+        let (open, close) = match &rest[start..start + 2] {
+            "{#" => ("{#", "#}"),
+            "{%" => ("{%", "%}"),
+            _ if rest[start..].starts_with("{{{") => ("{{{", "}}}"),
+            _ => ("{{", "}}"),
+        };
+        let inner_start = start + open.len();
+        let inner_end = inner_start + rest[inner_start..].find(close).ok_or_else(|| format!("line {}: {} is never closed with {}", tag_line, open, close))?;
+        let raw = &rest[inner_start..inner_end];
+        let trim_before = raw.starts_with('-');
+        let trim_after = raw.len() > 1 && raw.ends_with('-');
+        let inner = raw.trim_start_matches('-').trim_end_matches('-').trim();
+
+        let block = match open {
+            "{#" => Some(None),
+            "{%" => Some(Some(inner.to_string())),
+            "{{" if inner.starts_with('!') => Some(None),
+            "{{" => handlebars_block(inner).map(Some),
+            _ => None,
+        };
+        let mut after = inner_end + close.len();
+        if trim_before {
+            pending.truncate(pending.trim_end().len());
+        }
+        if block.is_some() {
+            // A block tag alone on its line takes the whole line with it
+            let line_start = template[..offset + start].rfind('\n').map_or(0, |index| index + 1);
+            let before_blank = template[line_start..offset + start].trim().is_empty();
+            let line_end = rest[after..].find('\n').map_or(rest.len(), |index| after + index + 1);
+            if before_blank && rest[after..line_end].trim().is_empty() {
+                pending.truncate(pending.trim_end_matches([' ', '\t']).len());
+                after = line_end;
+            }
+        }
+        if trim_after {
+            after = rest.len() - rest[after..].trim_start().len();
+        }
+        line += rest[..after].matches('\n').count();
+        if !pending.is_empty() {
+            tokens.push(Token::Text(std::mem::take(&mut pending)));
+        }
+        match block {
+            Some(Some(statement)) => tokens.push(Token::Block(statement, tag_line)),
+            Some(None) => {}
+            None => tokens.push(Token::Output(inner.to_string(), tag_line)),
+        }
+        offset += after;
+    }
+    if !pending.is_empty() {
+        tokens.push(Token::Text(pending));
+    }
+    Ok(tokens)
+}
+
+type Parsed = (Vec<Node>, Option<(String, usize)>);
+
+// Nodes up to one of the `until` keywords, which is returned with its line
+fn parse_nodes(tokens: &[Token], position: &mut usize, until: &[&str]) -> Result<Parsed, String> {
+    let mut nodes = Vec::new();
+    while let Some(token) = tokens.get(*position) {
+        *position += 1;
+        match token {
+            Token::Text(text) => nodes.push(Node::Text(text.clone())),
+            Token::Output(expr, line) => nodes.push(Node::Output(parse_expr(expr).map_err(|e| format!("line {}: {}", line, e))?, *line)),
+            Token::Block(statement, line) => {
+                let (keyword, rest) = statement.split_once(char::is_whitespace).map_or((statement.as_str(), ""), |(keyword, rest)| (keyword, rest.trim()));
+                if until.contains(&keyword) {
+                    return Ok((nodes, Some((statement.clone(), *line))));
+                }
+                let located = |e: String| format!("line {}: {}", line, e);
+                match keyword {
+                    "if" => {
+                        let mut branches = Vec::new();
+                        let mut condition = parse_expr(rest).map_err(located)?;
+                        let mut otherwise = Vec::new();
+                        loop {
+                            let (body, end) = parse_nodes(tokens, position, &["elif", "else", "endif"])?;
+                            branches.push((condition, body));
+                            match end {
+                                Some((end, end_line)) if end.starts_with("elif") => {
+                                    condition = parse_expr(end[4..].trim()).map_err(|e| format!("line {}: {}", end_line, e))?;
+                                }
+                                Some((end, _)) if end == "else" => {
+                                    let (body, end) = parse_nodes(tokens, position, &["endif"])?;
+                                    end.ok_or_else(|| located("if is never closed with endif".to_string()))?;
+                                    otherwise = body;
+                                    break;
+                                }
+                                Some(_) => break,
+                                None => return Err(located("if is never closed with endif".to_string())),
+                            }
+                        }
+                        nodes.push(Node::If { branches, otherwise });
+                    }
+                    "for" | "each" => {
+                        let (key, value, iterable) = if keyword == "each" {
+                            (None, "this".to_string(), rest)
+                        } else {
+                            let (names, iterable) = rest.split_once(" in ").ok_or_else(|| located(format!("expected 'for NAME in VALUE', found 'for {}'", rest)))?;
+                            match names.split_once(',') {
+                                Some((key, value)) => (Some(key.trim().to_string()), value.trim().to_string(), iterable),
+                                None => (None, names.trim().to_string(), iterable),
+                            }
+                        };
+                        let iterable = parse_expr(iterable).map_err(located)?;
+                        let (body, end) = parse_nodes(tokens, position, &["endfor"])?;
+                        // Named in the syntax the template used, since {{#each}} never mentions endfor
+                        let unclosed = if keyword == "each" { "{{#each}} is never closed with {{/each}}" } else { "for is never closed with endfor" };
+                        end.ok_or_else(|| located(unclosed.to_string()))?;
+                        nodes.push(Node::For { key, value, iterable, body, handlebars: keyword == "each", line: *line });
+                    }
+                    _ => return Err(located(format!("unexpected {{% {} %}}", statement))),
+                }
+            }
+        }
+    }
+    Ok((nodes, None))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ExprToken {
+    Word(String),
+    Literal(Value),
+    Symbol(&'static str),
+}
+
+fn lex_expr(text: &str) -> Result<Vec<ExprToken>, String> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = text.chars().collect();
+    let mut index = 0;
+    while index < chars.len() {
+        let c = chars[index];
+        if c.is_whitespace() {
+            index += 1;
+        } else if c == '"' || c == '\'' {
+            let mut literal = String::new();
+            index += 1;
+            while index < chars.len() && chars[index] != c {
+                if chars[index] == '\\' && index + 1 < chars.len() {
+                    index += 1;
+                }
+                literal.push(chars[index]);
+                index += 1;
+            }
+            if index == chars.len() {
+                return Err(format!("unclosed string in '{}'", text));
+            }
+            index += 1;
+            tokens.push(ExprToken::Literal(Value::String(literal)));
+        } else if c.is_ascii_digit() || (c == '-' && chars.get(index + 1).is_some_and(char::is_ascii_digit)) {
+            let start = index;
+            index += 1;
+            while index < chars.len() && (chars[index].is_ascii_digit() || chars[index] == '.') {
+                index += 1;
+            }
+            let number: String = chars[start..index].iter().collect();
+            tokens.push(ExprToken::Literal(serde_json::from_str(&number).map_err(|_| format!("invalid number '{}'", number))?));
+        } else if c.is_alphanumeric() || c == '_' || c == '@' {
+            let start = index;
+            while index < chars.len() && (chars[index].is_alphanumeric() || matches!(chars[index], '_' | '@' | '.')) {
+                index += 1;
+            }
+            tokens.push(ExprToken::Word(chars[start..index].iter().collect()));
+        } else {
+            let two: String = chars[index..(index + 2).min(chars.len())].iter().collect();
+            let symbol = ["==", "!=", "<=", ">=", "<", ">", "|", "(", ")", ","]
+                .into_iter()
+                .find(|symbol| two.starts_with(symbol))
+                .ok_or_else(|| format!("unexpected '{}' in '{}'", c, text))?;
+            index += symbol.len();
+            tokens.push(ExprToken::Symbol(symbol));
+        }
+    }
+    Ok(tokens)
+}
+
+struct ExprParser {
+    tokens: Vec<ExprToken>,
+    position: usize,
+}
+
+impl ExprParser {
+    fn peek(&self) -> Option<&ExprToken> {
+        self.tokens.get(self.position)
+    }
+
+    fn next_is_word(&mut self, word: &str) -> bool {
+        let found = matches!(self.peek(), Some(ExprToken::Word(next)) if next == word);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn next_is_symbol(&mut self, symbol: &str) -> bool {
+        let found = matches!(self.peek(), Some(ExprToken::Symbol(next)) if *next == symbol);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut left = self.and()?;
+        while self.next_is_word("or") {
+            left = Expr::Or(Box::new(left), Box::new(self.and()?));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut left = self.not()?;
+        while self.next_is_word("and") {
+            left = Expr::And(Box::new(left), Box::new(self.not()?));
+        }
+        Ok(left)
+    }
+
+    fn not(&mut self) -> Result<Expr, String> {
+        if self.next_is_word("not") {
+            return Ok(Expr::Not(Box::new(self.not()?)));
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Expr, String> {
+        let left = self.filtered()?;
+        let operator = match self.peek() {
+            Some(ExprToken::Symbol(symbol)) if ["==", "!=", "<=", ">=", "<", ">"].contains(symbol) => *symbol,
+            Some(ExprToken::Word(word)) if word == "in" => "in",
+            _ => return Ok(left),
+        };
+        self.position += 1;
+        Ok(Expr::Compare(Box::new(left), operator, Box::new(self.filtered()?)))
+    }
+
+    fn filtered(&mut self) -> Result<Expr, String> {
+        let mut expr = self.primary()?;
+        while self.next_is_symbol("|") {
+            let Some(ExprToken::Word(name)) = self.tokens.get(self.position).cloned() else {
+                return Err("expected a filter name after |".to_string());
+            };
+            self.position += 1;
+            if !FILTERS.contains(&name.as_str()) {
+                return Err(format!("unknown filter '{}'. Use one of: {}", name, FILTERS.join(", ")));
+            }
+            let mut arguments = Vec::new();
+            if self.next_is_symbol("(") && !self.next_is_symbol(")") {
+                loop {
+                    arguments.push(self.or()?);
+                    if self.next_is_symbol(")") {
+                        break;
+                    }
+                    if !self.next_is_symbol(",") {
+                        return Err(format!("expected , or ) in the arguments of '{}'", name));
+                    }
+                }
+            }
+            expr = Expr::Filter(Box::new(expr), name, arguments);
+        }
+        Ok(expr)
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        let token = self.tokens.get(self.position).cloned().ok_or("expected a value")?;
+        self.position += 1;
+        match token {
+            ExprToken::Literal(value) => Ok(Expr::Literal(value)),
+            ExprToken::Symbol("(") => {
+                let expr = self.or()?;
+                if !self.next_is_symbol(")") {
+                    return Err("expected )".to_string());
+                }
+                Ok(expr)
+            }
+            ExprToken::Word(word) => Ok(match word.as_str() {
+                "true" => Expr::Literal(Value::Bool(true)),
+                "false" => Expr::Literal(Value::Bool(false)),
+                "null" | "none" | "None" => Expr::Literal(Value::Null),
+                _ => Expr::Path(word.split('.').map(str::to_string).collect()),
+            }),
+            ExprToken::Symbol(symbol) => Err(format!("unexpected '{}'", symbol)),
+        }
+    }
+}
+
+fn parse_expr(text: &str) -> Result<Expr, String> {
+    let mut parser = ExprParser { tokens: lex_expr(text)?, position: 0 };
+    if parser.tokens.is_empty() {
+        return Err("empty expression".to_string());
+    }
+    let expr = parser.or()?;
+    if parser.position < parser.tokens.len() {
+        return Err(format!("unexpected text after the expression in '{}'", text));
+    }
+    Ok(expr)
+}
+
+fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(flag) => *flag,
+        Value::Number(number) => number.as_f64() != Some(0.0),
+        Value::String(text) => !text.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(map) => !map.is_empty(),
+    }
+}
+
+// How a value is written into the output; objects and arrays as JSON
+fn display(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+struct Renderer<'a> {
+    root: &'a Value,
+    // Loop variables, innermost last
+    scopes: Vec<Map<String, Value>>,
+}
+
+impl Renderer<'_> {
+    fn lookup(&self, path: &[String]) -> Option<Value> {
+        let first = &path[0];
+        let mut value = self.scopes.iter().rev().find_map(|scope| scope.get(first)).or_else(|| self.root.get(first))?;
+        for part in &path[1..] {
+            value = match value {
+                Value::Array(items) => items.get(part.parse::<usize>().ok()?)?,
+                other => other.get(part)?,
+            };
+        }
+        Some(value.clone())
+    }
+
+    // Undefined values are null unless `strict`, where they are an error
+    fn eval(&self, expr: &Expr, strict: bool) -> Result<Value, String> {
+        Ok(match expr {
+            Expr::Literal(value) => value.clone(),
+            Expr::Path(path) => match self.lookup(path) {
+                Some(value) => value,
+                None if strict => return Err(format!("'{}' is not defined (use | default(...) for optional values)", path.join("."))),
+                None => Value::Null,
+            },
+            Expr::Not(inner) => Value::Bool(!truthy(&self.eval(inner, false)?)),
+            Expr::And(left, right) => Value::Bool(truthy(&self.eval(left, false)?) && truthy(&self.eval(right, false)?)),
+            Expr::Or(left, right) => Value::Bool(truthy(&self.eval(left, false)?) || truthy(&self.eval(right, false)?)),
+            Expr::Compare(left, operator, right) => Value::Bool(compare(&self.eval(left, false)?, operator, &self.eval(right, false)?)),
+            Expr::Filter(inner, name, arguments) => {
+                let value = self.eval(inner, strict && name != "default")?;
+                let arguments = arguments.iter().map(|argument| self.eval(argument, strict)).collect::<Result<Vec<_>, _>>()?;
+                apply_filter(value, name, &arguments)?
+            }
+        })
+    }
+
+    fn render(&mut self, nodes: &[Node], output: &mut String) -> Result<(), String> {
+        for node in nodes {
+            match node {
+                Node::Text(text) => output.push_str(text),
+                Node::Output(expr, line) => output.push_str(&display(&self.eval(expr, true).map_err(|e| format!("line {}: {}", line, e))?)),
+                Node::If { branches, otherwise } => {
+                    let mut taken = otherwise;
+                    for (condition, body) in branches {
+                        if truthy(&self.eval(condition, false)?) {
+                            taken = body;
+                            break;
+                        }
+                    }
+                    self.render(taken, output)?;
+                }
+                Node::For { key, value, iterable, body, handlebars, line } => {
+                    let items: Vec<(Value, Value)> = match self.eval(iterable, false)? {
+                        Value::Array(items) => items.into_iter().enumerate().map(|(index, item)| (Value::from(index), item)).collect(),
+                        // A single loop variable over an object gets the keys, as in Jinja; #each gets the values
+                        Value::Object(map) => map.into_iter().map(|(name, item)| (Value::String(name.clone()), if key.is_some() || *handlebars { item } else { Value::String(name) })).collect(),
+                        Value::Null => Vec::new(),
+                        other => return Err(format!("line {}: can't loop over {}", line, other)),
+                    };
+                    let length = items.len();
+                    for (index, (item_key, item)) in items.into_iter().enumerate() {
+                        let mut scope = Map::new();
+                        if *handlebars {
+                            // Handlebars looks names up on the current item first
+                            if let Value::Object(fields) = &item {
+                                scope.extend(fields.clone());
+                            }
+                            scope.insert("@index".to_string(), Value::from(index));
+                            scope.insert("@key".to_string(), item_key);
+                            scope.insert("@first".to_string(), Value::Bool(index == 0));
+                            scope.insert("@last".to_string(), Value::Bool(index + 1 == length));
+                        } else {
+                            if let Some(key) = key {
+                                scope.insert(key.clone(), item_key);
+                            }
+                            let info = serde_json::json!({ "index": index + 1, "index0": index, "first": index == 0, "last": index + 1 == length, "length": length });
+                            scope.insert("loop".to_string(), info);
+                        }
+                        scope.insert(value.clone(), item);
+                        self.scopes.push(scope);
+                        let result = self.render(body, output);
+                        self.scopes.pop();
+                        result?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn compare(left: &Value, operator: &str, right: &Value) -> bool {
+    let ordering = match (left, right) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64().partial_cmp(&b.as_f64()),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    };
+    match operator {
+        "==" => ordering.map_or(left == right, |ordering| ordering.is_eq()),
+        "!=" => ordering.map_or(left != right, |ordering| ordering.is_ne()),
+        "<" => ordering.is_some_and(|ordering| ordering.is_lt()),
+        ">" => ordering.is_some_and(|ordering| ordering.is_gt()),
+        "<=" => ordering.is_some_and(|ordering| ordering.is_le()),
+        ">=" => ordering.is_some_and(|ordering| ordering.is_ge()),
+        _ => match right {
+            Value::Array(items) => items.contains(left),
+            Value::Object(map) => map.contains_key(&display(left)),
+            Value::String(text) => text.contains(&display(left)),
+            _ => false,
+        },
+    }
+}
+
+fn apply_filter(value: Value, name: &str, arguments: &[Value]) -> Result<Value, String> {
+    let argument = |index: usize| arguments.get(index).map(display).unwrap_or_default();
+    Ok(match name {
+        "default" if value.is_null() || value == Value::String(String::new()) => arguments.first().cloned().unwrap_or(Value::String(String::new())),
+        "default" => value,
+        "length" => Value::from(match &value {
+            Value::Array(items) => items.len(),
+            Value::Object(map) => map.len(),
+            other => display(other).chars().count(),
+        }),
+        "join" => match &value {
+            Value::Array(items) => Value::String(items.iter().map(display).collect::<Vec<_>>().join(&argument(0))),
+            other => other.clone(),
+        },
+        "first" | "last" => match &value {
+            Value::Array(items) => (if name == "first" { items.first() } else { items.last() }).cloned().unwrap_or(Value::Null),
+            other => {
+                let text = display(other);
+                let c = if name == "first" { text.chars().next() } else { text.chars().last() };
+                c.map(|c| Value::String(c.to_string())).unwrap_or(Value::Null)
+            }
+        },
+        "json" => Value::String(value.to_string()),
+        // Double-quoted with JSON escaping, for YAML and shell-safe-ish output
+        "quote" => Value::String(Value::String(display(&value)).to_string()),
+        "trim" => Value::String(display(&value).trim().to_string()),
+        "replace" => Value::String(display(&value).replace(&argument(0), &argument(1))),
+        case => Value::String(convert_case(&display(&value), case).ok_or_else(|| format!("unknown filter '{}'", case))?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn data() -> Value {
+        json!({
+            "app": { "name": "Billing Service", "port": 8080, "debug": false },
+            "env": "prod",
+            "hosts": ["a.example.com", "b.example.com"],
+            "users": [{ "name": "Ada", "admin": true }, { "name": "Linus", "admin": false }],
+            "labels": { "team": "payments", "tier": "1" }
+        })
+    }
+
+    #[test]
+    fn test_jinja_syntax() {
+        let template = "name: {{ app.name | kebab }}\nconst: {{ app.name|constant }}\nport: {{ app.port }}\n{% if env == \"prod\" and not app.debug %}\nreplicas: 3\n{% elif env == 'staging' %}\nreplicas: 2\n{% else %}\nreplicas: 1\n{% endif %}\nhosts:\n{% for host in hosts %}\n  - {{ loop.index }}: {{ host }}{% if not loop.last %},{% endif %}\n{% endfor %}\nlabels:\n{% for key, value in labels %}\n  {{ key }}: {{ value | quote }}\n{% endfor %}\n{# a comment #}\nregion: {{ region | default(\"eu-west-1\") }} {{- \"!\" }}\n";
+        assert_eq!(
+            render(template, &data()).unwrap(),
+            "name: billing-service\nconst: BILLING_SERVICE\nport: 8080\nreplicas: 3\nhosts:\n  - 1: a.example.com,\n  - 2: b.example.com\nlabels:\n  team: \"payments\"\n  tier: \"1\"\nregion: eu-west-1!\n"
+        );
+    }
+
+    #[test]
+    fn test_handlebars_syntax() {
+        let template = "{{#each users}}\n{{@index}} {{name}}{{#if admin}} (admin){{else}} (user){{/if}}\n{{/each}}\n{{#unless app.debug}}quiet{{/unless}} {{! hidden }}{{hosts | join(\", \")}} {{hosts | length}}\n";
+        assert_eq!(render(template, &data()).unwrap(), "0 Ada (admin)\n1 Linus (user)\nquiet a.example.com, b.example.com 2\n");
+    }
+
+    #[test]
+    fn test_errors_and_set() {
+        assert_eq!(render("a\n{{ missing }}", &data()).unwrap_err(), "line 2: 'missing' is not defined (use | default(...) for optional values)");
+        assert_eq!(render("{% if env %}x", &data()).unwrap_err(), "line 1: if is never closed with endif");
+        assert_eq!(render("{% endfor %}", &data()).unwrap_err(), "line 1: unexpected {% endfor %}");
+        assert_eq!(render("a\n{{#each hosts}}{{this}}", &data()).unwrap_err(), "line 2: {{#each}} is never closed with {{/each}}");
+        assert_eq!(render("{% for host in hosts %}x", &data()).unwrap_err(), "line 1: for is never closed with endfor");
+        assert!(render("{{ env | shout }}", &data()).unwrap_err().contains("unknown filter 'shout'"));
+        assert!(render("{{ env ", &data()).unwrap_err().contains("never closed"));
+        assert_eq!(render("{% if missing %}yes{% else %}no{% endif %}", &data()).unwrap(), "no");
+
+        let mut values = data();
+        set_value(&mut values, "app.port=9090").unwrap();
+        set_value(&mut values, "feature.flags.beta=true").unwrap();
+        set_value(&mut values, "greeting=hello world").unwrap();
+        assert_eq!(values["app"]["port"], json!(9090));
+        assert_eq!(values["feature"]["flags"]["beta"], json!(true));
+        assert_eq!(values["greeting"], json!("hello world"));
+        assert!(set_value(&mut values, "novalue").is_err());
+    }
+}
@@ -0,0 +1,259 @@
+use crate::tool_module::{ExitCodeError, ToolModule};
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use serde_json::{json, Value};
+use similar::{ChangeTag, TextDiff};
+use std::error::Error;
+use std::fs;
+use std::io::{self, IsTerminal, Read};
+use std::path::Path;
+
+pub struct TextDiffModule;
+
+const CONTEXT_LINES: usize = 3;
+
+type Style = fn(&str) -> ColoredString;
+
+impl ToolModule for TextDiffModule {
+    fn name(&self) -> &'static str {
+        "text-diff"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("diff")
+                .long("diff")
+                .value_names(["OLD", "NEW"])
+                .num_args(2)
+                .help("Show a colored unified diff of two files or strings")
+                .long_help("Compare two files (or two strings, when the arguments are not existing files) and print a unified diff with three lines of context. Changed words within a line are highlighted. Use - for one side to read it from stdin, and --format json for machine-readable output. Like diff(1), exits with 0 when the inputs are the same, 1 when they differ and 2 when one cannot be read.")
+        )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .help("Output format: text or json for --diff, --num-stats, --sysinfo, --ifaces and --default-route; hex, base64 or base64url for --random-bytes")
+                .value_parser(["text", "json", "hex", "base64", "base64url"])
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(values) = matches.get_many::<String>("diff") {
+            let format = matches.get_one::<String>("format").map(String::as_str);
+            if let Some(other @ ("hex" | "base64" | "base64url")) = format {
+                return Err(format!("--format {} does not apply to --diff; use text or json", other).into());
+            }
+            let values: Vec<&String> = values.collect();
+            let (old_label, old) = read_side(values[0], "a").map_err(|e| ExitCodeError::new(2, e.to_string()))?;
+            let (new_label, new) = read_side(values[1], "b").map_err(|e| ExitCodeError::new(2, e.to_string()))?;
+            let hunks = diff_hunks(&old, &new);
+            match format {
+                Some("json") => println!("{}", serde_json::to_string_pretty(&diff_json(&old_label, &new_label, &hunks))?),
+                _ if hunks.is_empty() => println!("✅ No differences"),
+                _ => print!("{}", render_text(&old_label, &new_label, &hunks, io::stdout().is_terminal())),
+            }
+            if !hunks.is_empty() {
+                return Err(Box::new(ExitCodeError::silent(1)));
+            }
+        }
+        Ok(())
+    }
+}
+
+// An existing file, stdin for "-", otherwise the argument itself (labelled a or b)
+fn read_side(value: &str, label: &str) -> Result<(String, String), Box<dyn Error>> {
+    if value == "-" {
+        let mut buffer = String::new();
+        io::stdin().read_to_string(&mut buffer)?;
+        return Ok(("stdin".to_string(), buffer));
+    }
+    if Path::new(value).is_file() {
+        let text = fs::read_to_string(value).map_err(|e| format!("Failed to read '{}': {}", value, e))?;
+        return Ok((value.to_string(), text));
+    }
+    // Two strings are compared as text, so \n written on the command line splits lines
+    Ok((label.to_string(), format!("{}\n", value.replace("\\n", "\n"))))
+}
+
+struct Hunk {
+    old_start: usize,
+    old_lines: usize,
+    new_start: usize,
+    new_lines: usize,
+    lines: Vec<DiffLine>,
+}
+
+struct DiffLine {
+    tag: ChangeTag,
+    old_line: Option<usize>,
+    new_line: Option<usize>,
+    // Pieces of the line; true marks the words that changed
+    segments: Vec<(bool, String)>,
+}
+
+impl DiffLine {
+    fn text(&self) -> String {
+        self.segments.iter().map(|(_, text)| text.as_str()).collect()
+    }
+}
+
+fn diff_hunks(old: &str, new: &str) -> Vec<Hunk> {
+    let diff = TextDiff::from_lines(old, new);
+    diff.grouped_ops(CONTEXT_LINES)
+        .iter()
+        .map(|group| {
+            let (first, last) = (group[0], group[group.len() - 1]);
+            let mut lines = Vec::new();
+            for op in group {
+                for change in diff.iter_inline_changes(op) {
+                    lines.push(DiffLine {
+                        tag: change.tag(),
+                        old_line: change.old_index().map(|index| index + 1),
+                        new_line: change.new_index().map(|index| index + 1),
+                        segments: change.iter_strings_lossy()
+                            .map(|(emphasized, text)| (emphasized, text.trim_end_matches(['\n', '\r']).to_string()))
+                            .filter(|(_, text)| !text.is_empty())
+                            .collect(),
+                    });
+                }
+            }
+            Hunk {
+                old_start: first.old_range().start + 1,
+                old_lines: last.old_range().end - first.old_range().start,
+                new_start: first.new_range().start + 1,
+                new_lines: last.new_range().end - first.new_range().start,
+                lines,
+            }
+        })
+        .collect()
+}
+
+// "@@ -3,4 +3,5 @@"; empty ranges point at the line before, as in diff -u
+fn hunk_header(hunk: &Hunk) -> String {
+    let range = |start: usize, lines: usize| match lines {
+        1 => start.to_string(),
+        0 => format!("{},0", start - 1),
+        _ => format!("{},{}", start, lines),
+    };
+    format!("@@ -{} +{} @@", range(hunk.old_start, hunk.old_lines), range(hunk.new_start, hunk.new_lines))
+}
+
+fn render_text(old_label: &str, new_label: &str, hunks: &[Hunk], color: bool) -> String {
+    let paint = |text: &str, style: Style| if color { style(text).to_string() } else { text.to_string() };
+    let mut output = String::new();
+    output.push_str(&format!("{}\n", paint(&format!("--- {}", old_label), |t| t.bold())));
+    output.push_str(&format!("{}\n", paint(&format!("+++ {}", new_label), |t| t.bold())));
+    let (mut insertions, mut deletions) = (0, 0);
+    for hunk in hunks {
+        output.push_str(&format!("{}\n", paint(&hunk_header(hunk), |t| t.cyan())));
+        for line in &hunk.lines {
+            let (sign, style, emphasis): (&str, Style, Style) = match line.tag {
+                ChangeTag::Delete => {
+                    deletions += 1;
+                    ("-", |t| t.red(), |t| t.white().on_red().bold())
+                }
+                ChangeTag::Insert => {
+                    insertions += 1;
+                    ("+", |t| t.green(), |t| t.black().on_green().bold())
+                }
+                ChangeTag::Equal => (" ", |t| t.normal(), |t| t.normal()),
+            };
+            output.push_str(&paint(sign, style));
+            for (emphasized, text) in &line.segments {
+                output.push_str(&paint(text, if *emphasized { emphasis } else { style }));
+            }
+            output.push('\n');
+        }
+    }
+    output.push_str(&format!(
+        "{} insertion{}(+), {} deletion{}(-)\n",
+        insertions,
+        if insertions == 1 { "" } else { "s" },
+        deletions,
+        if deletions == 1 { "" } else { "s" }
+    ));
+    output
+}
+
+fn diff_json(old_label: &str, new_label: &str, hunks: &[Hunk]) -> Value {
+    let count = |tag: ChangeTag| hunks.iter().flat_map(|hunk| &hunk.lines).filter(|line| line.tag == tag).count();
+    json!({
+        "old": old_label,
+        "new": new_label,
+        "identical": hunks.is_empty(),
+        "insertions": count(ChangeTag::Insert),
+        "deletions": count(ChangeTag::Delete),
+        "hunks": hunks.iter().map(|hunk| json!({
+            "header": hunk_header(hunk),
+            "old_start": hunk.old_start,
+            "old_lines": hunk.old_lines,
+            "new_start": hunk.new_start,
+            "new_lines": hunk.new_lines,
+            "lines": hunk.lines.iter().map(|line| {
+                let mut entry = json!({
+                    "type": match line.tag {
+                        ChangeTag::Delete => "delete",
+                        ChangeTag::Insert => "insert",
+                        ChangeTag::Equal => "equal",
+                    },
+                    "old_line": line.old_line,
+                    "new_line": line.new_line,
+                    "text": line.text(),
+                });
+                let changed: Vec<&str> = line.segments.iter().filter(|(emphasized, _)| *emphasized).map(|(_, text)| text.as_str()).collect();
+                if !changed.is_empty() {
+                    entry["changed"] = json!(changed);
+                }
+                entry
+            }).collect::<Vec<_>>(),
+        })).collect::<Vec<_>>(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = "host = localhost\nport = 5432\nuser = admin\ndebug = false\n";
+    const NEW: &str = "host = localhost\nport = 6432\nuser = admin\ndebug = false\ncache = true\n";
+
+    #[test]
+    fn test_render_text() {
+        let hunks = diff_hunks(OLD, NEW);
+        assert_eq!(
+            render_text("a.conf", "b.conf", &hunks, false),
+            "--- a.conf\n+++ b.conf\n@@ -1,4 +1,5 @@\n host = localhost\n-port = 5432\n+port = 6432\n user = admin\n debug = false\n+cache = true\n2 insertions(+), 1 deletion(-)\n"
+        );
+    }
+
+    #[test]
+    fn test_word_highlighting() {
+        let hunks = diff_hunks(OLD, NEW);
+        let deleted = hunks[0].lines.iter().find(|line| line.tag == ChangeTag::Delete).unwrap();
+        assert_eq!(deleted.segments, vec![(false, "port = ".to_string()), (true, "5432".to_string())]);
+    }
+
+    #[test]
+    fn test_hunks_and_headers() {
+        let old: String = (1..=20).map(|n| format!("line {}\n", n)).collect();
+        let new = old.replace("line 2\n", "").replace("line 18\n", "line eighteen\n");
+        let hunks = diff_hunks(&old, &new);
+        assert_eq!(hunks.len(), 2);
+        assert_eq!(hunk_header(&hunks[0]), "@@ -1,5 +1,4 @@");
+        assert_eq!(hunk_header(&hunks[1]), "@@ -15,6 +14,6 @@");
+        assert_eq!(hunk_header(&diff_hunks("", "a\n")[0]), "@@ -0,0 +1 @@");
+        assert!(diff_hunks(OLD, OLD).is_empty());
+    }
+
+    #[test]
+    fn test_json_output() {
+        let output = diff_json("a", "b", &diff_hunks(OLD, NEW));
+        assert_eq!(output["identical"], false);
+        assert_eq!(output["insertions"], 2);
+        assert_eq!(output["deletions"], 1);
+        let lines = &output["hunks"][0]["lines"];
+        assert_eq!(lines[1], json!({ "type": "delete", "old_line": 2, "new_line": null, "text": "port = 5432", "changed": ["5432"] }));
+        assert_eq!(lines[5]["new_line"], 5);
+        assert_eq!(diff_json("a", "a", &[])["identical"], true);
+    }
+}
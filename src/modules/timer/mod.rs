@@ -0,0 +1,284 @@
+use crate::cmd_watch::notify_desktop;
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use colored::*;
+use std::error::Error;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub struct TimerModule;
+
+const TICK: Duration = Duration::from_millis(200);
+const BAR_WIDTH: usize = 30;
+const FOCUS: Duration = Duration::from_secs(25 * 60);
+const SHORT_BREAK: Duration = Duration::from_secs(5 * 60);
+const LONG_BREAK: Duration = Duration::from_secs(15 * 60);
+// A long break comes after every this many focus sessions
+const LONG_BREAK_EVERY: u32 = 4;
+
+#[derive(Debug, PartialEq)]
+struct Phase {
+    label: String,
+    duration: Duration,
+}
+
+impl ToolModule for TimerModule {
+    fn name(&self) -> &'static str {
+        "timer"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("timer")
+                .long("timer")
+                .value_name("DURATION")
+                .help("Count down DURATION (e.g. 25m, 1h30m, 90s or 4:30) and ring when done")
+                .long_help("Show a live countdown for DURATION, given as seconds, a combination of h/m/s parts such as 1h30m or 2m30s, or MM:SS / HH:MM:SS, then ring the terminal bell. Add --label to name it and --notify for a desktop notification.")
+        )
+        .arg(
+            Arg::new("stopwatch")
+                .long("stopwatch")
+                .action(ArgAction::SetTrue)
+                .help("Run a stopwatch; press Enter for a lap, q then Enter to stop")
+        )
+        .arg(
+            Arg::new("pomodoro")
+                .long("pomodoro")
+                .value_name("CYCLES")
+                .num_args(0..=1)
+                .default_missing_value("4")
+                .value_parser(clap::value_parser!(u32).range(1..=24))
+                .help("Run CYCLES pomodoros (default: 4): 25 minutes of focus, then a 5 minute break, 15 after every fourth")
+        )
+        .arg(
+            Arg::new("label")
+                .long("label")
+                .value_name("TEXT")
+                .help("Name shown with the --timer countdown and in its notification")
+                .requires("timer")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        // --notify is declared by the cmd-watch module
+        let notify = matches.get_flag("notify");
+        if let Some(text) = matches.get_one::<String>("timer") {
+            let duration = parse_duration(text)?;
+            let label = matches.get_one::<String>("label").map(String::as_str).unwrap_or("Timer");
+            countdown(label, duration)?;
+            done(&format!("{} done ({})", label, clock(duration)), notify);
+        }
+        if matches.get_flag("stopwatch") {
+            stopwatch()?;
+        }
+        if let Some(&cycles) = matches.get_one::<u32>("pomodoro") {
+            let phases = pomodoro(cycles);
+            for (index, phase) in phases.iter().enumerate() {
+                countdown(&phase.label, phase.duration)?;
+                let next = phases.get(index + 1).map(|next| format!("; next: {} ({})", next.label, clock(next.duration))).unwrap_or_default();
+                done(&format!("{} over{}", phase.label, next), notify);
+            }
+            println!("🍅 {} pomodoro{} complete", cycles, if cycles == 1 { "" } else { "s" });
+        }
+        Ok(())
+    }
+}
+
+// Seconds, 1h30m-style parts, or MM:SS and HH:MM:SS
+fn parse_duration(text: &str) -> Result<Duration, String> {
+    let invalid = || format!("Invalid duration '{}'; use e.g. 25m, 1h30m, 90s or 4:30", text);
+    let text = text.trim().to_lowercase();
+    let seconds = if text.contains(':') {
+        let parts: Vec<&str> = text.split(':').collect();
+        if parts.len() > 3 {
+            return Err(invalid());
+        }
+        let mut seconds = 0u64;
+        for part in parts {
+            let value: u64 = part.parse().map_err(|_| invalid())?;
+            seconds = seconds * 60 + value;
+        }
+        seconds
+    } else if text.bytes().all(|b| b.is_ascii_digit()) && !text.is_empty() {
+        text.parse().map_err(|_| invalid())?
+    } else {
+        let mut seconds = 0u64;
+        let mut number = String::new();
+        for c in text.chars() {
+            if c.is_ascii_digit() {
+                number.push(c);
+                continue;
+            }
+            let multiplier = match c {
+                'h' => 3600,
+                'm' => 60,
+                's' => 1,
+                _ => return Err(invalid()),
+            };
+            let value: u64 = number.parse().map_err(|_| invalid())?;
+            seconds += value * multiplier;
+            number.clear();
+        }
+        if !number.is_empty() {
+            return Err(invalid());
+        }
+        seconds
+    };
+    if seconds == 0 {
+        return Err("The duration must be at least one second".to_string());
+    }
+    Ok(Duration::from_secs(seconds))
+}
+
+// MM:SS, or H:MM:SS from an hour up
+fn clock(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    if seconds >= 3600 {
+        format!("{}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+    } else {
+        format!("{:02}:{:02}", seconds / 60, seconds % 60)
+    }
+}
+
+// MM:SS.cc for stopwatch readings
+fn precise_clock(duration: Duration) -> String {
+    let centis = duration.as_millis() / 10;
+    format!("{}.{:02}", clock(Duration::from_secs((centis / 100) as u64)), centis % 100)
+}
+
+fn bar(done: f64) -> String {
+    let filled = ((done.clamp(0.0, 1.0) * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH);
+    format!("{}{}", "█".repeat(filled), "░".repeat(BAR_WIDTH - filled))
+}
+
+fn pomodoro(cycles: u32) -> Vec<Phase> {
+    let mut phases = Vec::new();
+    for cycle in 1..=cycles {
+        phases.push(Phase { label: format!("Focus {}/{}", cycle, cycles), duration: FOCUS });
+        if cycle < cycles {
+            let (label, duration) = if cycle % LONG_BREAK_EVERY == 0 { ("Long break", LONG_BREAK) } else { ("Break", SHORT_BREAK) };
+            phases.push(Phase { label: label.to_string(), duration });
+        }
+    }
+    phases
+}
+
+// Redraws one line in place until the time is up
+fn countdown(label: &str, duration: Duration) -> Result<(), Box<dyn Error>> {
+    let started = Instant::now();
+    let live = io::stdout().is_terminal();
+    if !live {
+        println!("⏳ {} for {}", label, clock(duration));
+    }
+    loop {
+        let elapsed = started.elapsed();
+        if elapsed >= duration {
+            break;
+        }
+        if live {
+            // Round up so the display reaches 00:00 exactly when the time is up
+            let remaining = Duration::from_secs((duration - elapsed).as_secs_f64().ceil() as u64);
+            print!("\r\x1B[2K⏳ {}  {}  {}", label.bold(), clock(remaining).cyan().bold(), bar(elapsed.as_secs_f64() / duration.as_secs_f64()).dimmed());
+            io::stdout().flush()?;
+        }
+        thread::sleep(TICK.min(duration - elapsed));
+    }
+    if live {
+        print!("\r\x1B[2K");
+    }
+    Ok(())
+}
+
+fn done(message: &str, notify: bool) {
+    println!("🔔 {}\x07", message.green().bold());
+    if notify {
+        notify_desktop("micro-swiss timer", message);
+    }
+}
+
+fn stopwatch() -> Result<(), Box<dyn Error>> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    let live = io::stdout().is_terminal();
+    println!("⏱️  Stopwatch running: Enter for a lap, q then Enter to stop");
+    let started = Instant::now();
+    let mut laps: Vec<Duration> = Vec::new();
+    loop {
+        match rx.recv_timeout(TICK) {
+            Ok(line) if line.trim().eq_ignore_ascii_case("q") => break,
+            Ok(_) => {
+                let total = started.elapsed();
+                let lap = total - laps.iter().sum::<Duration>();
+                laps.push(lap);
+                // The Enter keypress already moved down a line; overwrite the old reading
+                let up = if live { "\x1B[1A\r\x1B[2K" } else { "" };
+                println!("{}Lap {:>2}  {}  (total {})", up, laps.len(), precise_clock(lap).bold(), precise_clock(total));
+            }
+            // stdin closed: keep timing until interrupted
+            Err(mpsc::RecvTimeoutError::Disconnected) if !live => break,
+            Err(_) => {}
+        }
+        if live {
+            print!("\r\x1B[2K   {}", precise_clock(started.elapsed()).cyan().bold());
+            io::stdout().flush()?;
+        }
+    }
+    let total = started.elapsed();
+    if live {
+        print!("\x1B[1A\r\x1B[2K");
+    }
+    println!("⏹️  Stopped at {}", precise_clock(total).bold());
+    if laps.len() > 1 {
+        let fastest = laps.iter().min().copied().unwrap_or_default();
+        let slowest = laps.iter().max().copied().unwrap_or_default();
+        println!("   {} laps, fastest {}, slowest {}", laps.len(), precise_clock(fastest), precise_clock(slowest));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("25m").unwrap(), Duration::from_secs(1500));
+        assert_eq!(parse_duration("1h30m").unwrap(), Duration::from_secs(5400));
+        assert_eq!(parse_duration("2m30s").unwrap(), Duration::from_secs(150));
+        assert_eq!(parse_duration("90").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_duration("4:30").unwrap(), Duration::from_secs(270));
+        assert_eq!(parse_duration("1:00:05").unwrap(), Duration::from_secs(3605));
+        assert!(parse_duration("0s").is_err());
+        assert!(parse_duration("10x").is_err());
+        assert!(parse_duration("m").is_err());
+        assert!(parse_duration("5m3").is_err());
+    }
+
+    #[test]
+    fn test_clock_and_bar() {
+        assert_eq!(clock(Duration::from_secs(1500)), "25:00");
+        assert_eq!(clock(Duration::from_secs(3725)), "1:02:05");
+        assert_eq!(precise_clock(Duration::from_millis(83_456)), "01:23.45");
+        assert_eq!(bar(0.0), "░".repeat(BAR_WIDTH));
+        assert_eq!(bar(0.5).chars().filter(|c| *c == '█').count(), BAR_WIDTH / 2);
+        assert_eq!(bar(2.0), "█".repeat(BAR_WIDTH));
+    }
+
+    #[test]
+    fn test_pomodoro() {
+        let phases = pomodoro(5);
+        let labels: Vec<&str> = phases.iter().map(|phase| phase.label.as_str()).collect();
+        assert_eq!(labels, ["Focus 1/5", "Break", "Focus 2/5", "Break", "Focus 3/5", "Break", "Focus 4/5", "Long break", "Focus 5/5"]);
+        assert_eq!(phases[7].duration, LONG_BREAK);
+        assert_eq!(pomodoro(1).len(), 1);
+    }
+}
@@ -0,0 +1,197 @@
+use crate::tool_module::ToolModule;
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, SecondsFormat, SubsecRound, Utc};
+use clap::{Arg, ArgMatches, Command};
+use std::error::Error;
+
+pub struct TimestampModule;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum EpochUnit {
+    Seconds,
+    Milliseconds,
+    Microseconds,
+    Nanoseconds,
+}
+
+impl EpochUnit {
+    // Picks the unit that puts the value within a few thousand years of 1970:
+    // seconds up to 10^11 (year 5138), then each unit 1000x larger
+    fn detect(value: i128) -> Self {
+        match value.unsigned_abs() {
+            v if v < 100_000_000_000 => EpochUnit::Seconds,
+            v if v < 100_000_000_000_000 => EpochUnit::Milliseconds,
+            v if v < 100_000_000_000_000_000 => EpochUnit::Microseconds,
+            _ => EpochUnit::Nanoseconds,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            EpochUnit::Seconds => "seconds",
+            EpochUnit::Milliseconds => "milliseconds",
+            EpochUnit::Microseconds => "microseconds",
+            EpochUnit::Nanoseconds => "nanoseconds",
+        }
+    }
+
+    fn nanos_per_unit(self) -> i128 {
+        match self {
+            EpochUnit::Seconds => 1_000_000_000,
+            EpochUnit::Milliseconds => 1_000_000,
+            EpochUnit::Microseconds => 1_000,
+            EpochUnit::Nanoseconds => 1,
+        }
+    }
+}
+
+impl ToolModule for TimestampModule {
+    fn name(&self) -> &'static str {
+        "timestamp"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("timestamp")
+                .long("timestamp")
+                .value_name("VALUE")
+                .help("Convert a unix timestamp to dates, or a date to a unix timestamp (default: now)")
+                .long_help("Convert between unix timestamps and dates. A number is read as seconds, milliseconds, microseconds or nanoseconds depending on its size (fractional seconds such as 1714557600.5 work too) and shown in UTC, local time and relative to now. A date such as 2024-05-01T10:00:00Z, '2024-05-01 10:00' or 2024-05-01 is converted to epoch seconds and milliseconds; dates without an offset are taken as UTC. Without a value, shows the current time.")
+                .num_args(0..=1)
+                .default_missing_value("now")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(value) = matches.get_one::<String>("timestamp") {
+            let (datetime, unit) = parse_input(value)?;
+            let now = Utc::now();
+
+            if let Some(unit) = unit {
+                println!("Detected:     {}", unit.name());
+            }
+            println!("Seconds:      {}", datetime.timestamp());
+            println!("Milliseconds: {}", datetime.timestamp_millis());
+            println!("UTC:          {}", datetime.to_rfc3339_opts(SecondsFormat::AutoSi, true));
+            println!("Local:        {}", datetime.with_timezone(&Local).to_rfc3339_opts(SecondsFormat::AutoSi, false));
+            println!("Relative:     {}", relative_time(datetime.timestamp() - now.timestamp()));
+        }
+        Ok(())
+    }
+}
+
+// Returns the instant and, for numeric input, the unit it was read in
+fn parse_input(input: &str) -> Result<(DateTime<Utc>, Option<EpochUnit>), Box<dyn Error>> {
+    let input = input.trim();
+    if input.eq_ignore_ascii_case("now") {
+        return Ok((Utc::now().trunc_subsecs(3), None));
+    }
+    if let Some((nanos, unit)) = parse_epoch(input) {
+        let datetime = i64::try_from(nanos)
+            .map(DateTime::from_timestamp_nanos)
+            .map_err(|_| format!("Timestamp {} is out of range", input))?;
+        return Ok((datetime, Some(unit)));
+    }
+    parse_date(input).map(|datetime| (datetime, None)).ok_or_else(|| {
+        format!("Invalid timestamp '{}'. Use a unix timestamp, an ISO 8601 date such as 2024-05-01T10:00:00Z, or now", input).into()
+    })
+}
+
+// A whole number in any unit, or decimal seconds; returns nanoseconds since the epoch
+fn parse_epoch(input: &str) -> Option<(i128, EpochUnit)> {
+    let (whole, fraction) = input.split_once('.').unwrap_or((input, ""));
+    let digits = whole.strip_prefix('-').unwrap_or(whole);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let value: i128 = whole.parse().ok()?;
+    if input.contains('.') {
+        // Fractional values are always seconds; keep up to nanosecond precision
+        let fraction = format!("{:0<9}", &fraction[..fraction.len().min(9)]);
+        let nanos: i128 = fraction.parse().ok()?;
+        let sign = if whole.starts_with('-') { -1 } else { 1 };
+        return Some((value * 1_000_000_000 + sign * nanos, EpochUnit::Seconds));
+    }
+    let unit = EpochUnit::detect(value);
+    Some((value * unit.nanos_per_unit(), unit))
+}
+
+// RFC 3339 keeps its offset; dates and times without one are UTC
+fn parse_date(input: &str) -> Option<DateTime<Utc>> {
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(input) {
+        return Some(datetime.with_timezone(&Utc));
+    }
+    for format in ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M"] {
+        if let Ok(datetime) = NaiveDateTime::parse_from_str(input, format) {
+            return Some(datetime.and_utc());
+        }
+    }
+    NaiveDate::parse_from_str(input, "%Y-%m-%d").ok().map(|date| date.and_hms_opt(0, 0, 0).unwrap().and_utc())
+}
+
+// "3 hours ago" or "in 2 days", using the largest whole unit
+pub fn relative_time(offset_seconds: i64) -> String {
+    let seconds = offset_seconds.abs();
+    if seconds == 0 {
+        return "just now".to_string();
+    }
+    let (amount, unit) = match seconds {
+        s if s >= 31_536_000 => (s / 31_536_000, "year"),
+        s if s >= 2_592_000 => (s / 2_592_000, "month"),
+        s if s >= 86_400 => (s / 86_400, "day"),
+        s if s >= 3_600 => (s / 3_600, "hour"),
+        s if s >= 60 => (s / 60, "minute"),
+        s => (s, "second"),
+    };
+    let duration = format!("{} {}{}", amount, unit, if amount == 1 { "" } else { "s" });
+    if offset_seconds < 0 { format!("{} ago", duration) } else { format!("in {}", duration) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_unit() {
+        assert_eq!(EpochUnit::detect(1_714_557_600), EpochUnit::Seconds);
+        assert_eq!(EpochUnit::detect(1_714_557_600_000), EpochUnit::Milliseconds);
+        assert_eq!(EpochUnit::detect(1_714_557_600_000_000), EpochUnit::Microseconds);
+        assert_eq!(EpochUnit::detect(1_714_557_600_000_000_000), EpochUnit::Nanoseconds);
+        assert_eq!(EpochUnit::detect(-86_400), EpochUnit::Seconds);
+    }
+
+    #[test]
+    fn test_parse_epoch() {
+        let expected = "2024-05-01T10:00:00Z";
+        for input in ["1714557600", "1714557600000", "1714557600000000", "1714557600000000000"] {
+            let (datetime, _) = parse_input(input).unwrap();
+            assert_eq!(datetime.to_rfc3339_opts(SecondsFormat::AutoSi, true), expected);
+        }
+        let (datetime, unit) = parse_input("1714557600.25").unwrap();
+        assert_eq!(unit, Some(EpochUnit::Seconds));
+        assert_eq!(datetime.timestamp_millis(), 1_714_557_600_250);
+        assert_eq!(parse_input("-1.5").unwrap().0.timestamp_millis(), -1_500);
+        assert!(parse_epoch("12a").is_none());
+        assert!(parse_epoch("-").is_none());
+    }
+
+    #[test]
+    fn test_parse_date() {
+        let expected = 1_714_557_600;
+        assert_eq!(parse_date("2024-05-01T10:00:00Z").unwrap().timestamp(), expected);
+        assert_eq!(parse_date("2024-05-01T13:00:00+03:00").unwrap().timestamp(), expected);
+        assert_eq!(parse_date("2024-05-01 10:00").unwrap().timestamp(), expected);
+        assert_eq!(parse_date("2024-05-01T10:00:00.500").unwrap().timestamp_millis(), expected * 1000 + 500);
+        assert_eq!(parse_date("2024-05-01").unwrap().timestamp(), expected - 36_000);
+        assert!(parse_date("01/05/2024").is_none());
+        assert!(parse_input("yesterday").is_err());
+    }
+
+    #[test]
+    fn test_relative_time() {
+        assert_eq!(relative_time(0), "just now");
+        assert_eq!(relative_time(-10_800), "3 hours ago");
+        assert_eq!(relative_time(172_800), "in 2 days");
+        assert_eq!(relative_time(-60), "1 minute ago");
+        assert_eq!(relative_time(-3 * 31_536_000), "3 years ago");
+    }
+}
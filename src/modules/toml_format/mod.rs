@@ -0,0 +1,268 @@
+use crate::tool_module::{read_input, ToolModule};
+use clap::{Arg, ArgMatches, Command};
+use std::error::Error;
+use toml_edit::{Decor, DocumentMut, Item, Table};
+
+pub struct TomlFormatModule;
+
+impl ToolModule for TomlFormatModule {
+    fn name(&self) -> &'static str {
+        "toml-format"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("toml-format")
+                .long("toml-format")
+                .value_name("FILE")
+                .help("Reformat TOML spacing and indentation, keeping comments (or read from stdin)")
+                .long_help("Normalize a TOML file: one space around =, no indentation, single blank lines and a blank line before each [table]. Comments and the order of tables are kept; --sort-keys sorts the keys within each table. If no file (or -) is given, reads from stdin.")
+                .num_args(0..=1)
+                .conflicts_with_all(["toml-validate", "toml-get"])
+        )
+        .arg(
+            Arg::new("toml-validate")
+                .long("toml-validate")
+                .value_name("FILE")
+                .help("Check TOML syntax and show the line and column of any error (or read from stdin)")
+                .num_args(0..=1)
+                .conflicts_with("toml-get")
+        )
+        .arg(
+            Arg::new("toml-get")
+                .long("toml-get")
+                .value_names(["KEY", "FILE"])
+                .help("Print the value at a dotted key such as package.version (FILE or stdin)")
+                .long_help("Print the value at a dotted key path, e.g. package.version, tool.poetry.dependencies or workspace.members[0]. Quote keys that contain dots: 'dependencies.\"serde.json\"'. Strings print without quotes, tables print as TOML. Reads FILE, or stdin when no file (or -) is given.")
+                .num_args(1..=2)
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if matches.contains_id("toml-format") {
+            let input = read_input(matches.get_one::<String>("toml-format"))?;
+            let mut document = parse_document(&input)?;
+            // --sort-keys is declared by the YAML formatter
+            print!("{}", format_document(&mut document, matches.get_flag("sort-keys")));
+        } else if matches.contains_id("toml-validate") {
+            let input = read_input(matches.get_one::<String>("toml-validate"))?;
+            parse_document(&input)?;
+            println!("✅ Valid TOML");
+        } else if let Some(values) = matches.get_many::<String>("toml-get") {
+            let values: Vec<&String> = values.collect();
+            let input = read_input(values.get(1).copied())?;
+            let table: toml::Table = input.parse().map_err(|e: toml::de::Error| format!("Invalid TOML: {}", e))?;
+            println!("{}", get_value(&toml::Value::Table(table), values[0])?);
+        }
+        Ok(())
+    }
+}
+
+// toml_edit errors already point at the line and column with a snippet
+fn parse_document(input: &str) -> Result<DocumentMut, String> {
+    input.parse::<DocumentMut>().map_err(|e| format!("Invalid TOML: {}", e.to_string().trim_end()))
+}
+
+fn format_document(document: &mut DocumentMut, sort_keys: bool) -> String {
+    format_table(document.as_table_mut(), sort_keys);
+    // The trailing text may end in a comment without a final newline
+    let trailing = document.trailing().as_str().map(|text| tidy_prefix(&format!("{}\n", text), false)).unwrap_or_default();
+    document.set_trailing(trailing);
+    let output = document.to_string();
+    format!("{}\n", output.trim_start_matches('\n').trim_end())
+}
+
+fn format_table(table: &mut Table, sort_keys: bool) {
+    if sort_keys {
+        table.sort_values();
+    }
+    if !table.is_implicit() {
+        tidy_decor(table.decor_mut(), true);
+    }
+    for (mut key, item) in table.iter_mut() {
+        match item {
+            Item::Value(value) => {
+                tidy_decor(key.leaf_decor_mut(), false);
+                key.leaf_decor_mut().set_suffix(" ");
+                let decor = value.decor_mut();
+                let comment = decor.suffix().and_then(|suffix| suffix.as_str()).map(str::trim).unwrap_or("");
+                let suffix = if comment.is_empty() { String::new() } else { format!(" {}", comment) };
+                decor.set_prefix(" ");
+                decor.set_suffix(suffix);
+            }
+            Item::Table(table) => format_table(table, sort_keys),
+            Item::ArrayOfTables(tables) => tables.iter_mut().for_each(|table| format_table(table, sort_keys)),
+            Item::None => {}
+        }
+    }
+}
+
+// Keeps the comment lines in a prefix but drops indentation and extra blank lines
+fn tidy_decor(decor: &mut Decor, header: bool) {
+    let prefix = decor.prefix().and_then(|prefix| prefix.as_str()).unwrap_or("").to_string();
+    decor.set_prefix(tidy_prefix(&prefix, header));
+}
+
+// The whitespace and comments before a key or [header]. Headers always get a
+// blank line above them; the leading one is trimmed from the document later.
+fn tidy_prefix(text: &str, header: bool) -> String {
+    // Whatever follows the last newline is indentation on the key's own line
+    let mut lines: Vec<&str> = text.split('\n').map(str::trim).collect();
+    lines.pop();
+    lines.dedup_by(|a, b| a.is_empty() && b.is_empty());
+    if header && lines.first().is_none_or(|line| !line.is_empty()) {
+        lines.insert(0, "");
+    }
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+#[derive(Debug, PartialEq)]
+enum KeySegment {
+    Key(String),
+    Index(usize),
+}
+
+// package.version, tool."my.tool".name, workspace.members[0] or workspace.members.0
+fn parse_key_path(path: &str) -> Result<Vec<KeySegment>, String> {
+    let mut segments = Vec::new();
+    let mut chars = path.trim().chars().peekable();
+    while chars.peek().is_some() {
+        let mut key = String::new();
+        let quoted = matches!(chars.peek(), Some('"') | Some('\''));
+        if quoted {
+            let quote = chars.next().unwrap_or('"');
+            loop {
+                match chars.next() {
+                    Some(c) if c == quote => break,
+                    Some(c) => key.push(c),
+                    None => return Err(format!("Unclosed quote in key '{}'", path)),
+                }
+            }
+        } else {
+            while let Some(c) = chars.next_if(|c| *c != '.' && *c != '[') {
+                key.push(c);
+            }
+            if key.is_empty() && chars.peek() != Some(&'[') {
+                return Err(format!("Empty key segment in '{}'", path));
+            }
+        }
+        if !key.is_empty() || quoted {
+            segments.push(KeySegment::Key(key));
+        }
+        while chars.next_if_eq(&'[').is_some() {
+            let mut index = String::new();
+            while let Some(c) = chars.next_if(|c| *c != ']') {
+                index.push(c);
+            }
+            if chars.next() != Some(']') {
+                return Err(format!("Unclosed [ in key '{}'", path));
+            }
+            let index = index.trim().parse().map_err(|_| format!("Invalid array index [{}] in '{}'", index, path))?;
+            segments.push(KeySegment::Index(index));
+        }
+        match chars.next() {
+            None | Some('.') => {}
+            Some(c) => return Err(format!("Unexpected '{}' in key '{}'", c, path)),
+        }
+    }
+    if segments.is_empty() {
+        return Err("The key must not be empty".to_string());
+    }
+    Ok(segments)
+}
+
+fn get_value(root: &toml::Value, path: &str) -> Result<String, String> {
+    let mut current = root;
+    let mut walked = String::new();
+    for segment in parse_key_path(path)? {
+        current = match (&segment, current) {
+            (KeySegment::Key(key), toml::Value::Table(table)) => table.get(key).ok_or_else(|| {
+                let mut available: Vec<&str> = table.keys().map(String::as_str).collect();
+                available.sort();
+                format!("Key '{}' not found{}. Available keys: {}", key, location(&walked), available.join(", "))
+            })?,
+            // A numeric segment on an array works like [N]
+            (KeySegment::Key(key), toml::Value::Array(items)) if key.parse::<usize>().is_ok() => {
+                let index: usize = key.parse().unwrap_or_default();
+                items.get(index).ok_or_else(|| format!("Index {} is out of range{} ({} items)", index, location(&walked), items.len()))?
+            }
+            (KeySegment::Index(index), toml::Value::Array(items)) => {
+                items.get(*index).ok_or_else(|| format!("Index {} is out of range{} ({} items)", index, location(&walked), items.len()))?
+            }
+            (_, other) => return Err(format!("'{}' is a {}, not a table or array", walked, other.type_str())),
+        };
+        match segment {
+            KeySegment::Key(key) if walked.is_empty() => walked = key,
+            KeySegment::Key(key) => walked = format!("{}.{}", walked, key),
+            KeySegment::Index(index) => walked = format!("{}[{}]", walked, index),
+        }
+    }
+    Ok(match current {
+        toml::Value::String(text) => text.clone(),
+        toml::Value::Table(table) => toml::to_string(table).map_err(|e| e.to_string())?.trim_end().to_string(),
+        other => other.to_string(),
+    })
+}
+
+fn location(walked: &str) -> String {
+    if walked.is_empty() { String::new() } else { format!(" in '{}'", walked) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = "# Package info\n[package]\n  name   =  \"demo\"   # the crate name\n\n\n  version=\"0.1.0\"\n[dependencies]\nserde = { version = \"1\", features = [\"derive\"] }\nclap=\"4\"\n[workspace]\nmembers = [\"core\", \"cli\"]\n";
+
+    #[test]
+    fn test_format_keeps_comments() {
+        let mut document = parse_document(MANIFEST).unwrap();
+        assert_eq!(
+            format_document(&mut document, false),
+            "# Package info\n[package]\nname = \"demo\" # the crate name\n\nversion = \"0.1.0\"\n\n[dependencies]\nserde = { version = \"1\", features = [\"derive\"] }\nclap = \"4\"\n\n[workspace]\nmembers = [\"core\", \"cli\"]\n"
+        );
+    }
+
+    #[test]
+    fn test_format_sort_keys() {
+        let mut document = parse_document("[dependencies]\nzip = \"2\"\nanyhow = \"1\"\n").unwrap();
+        assert_eq!(format_document(&mut document, true), "[dependencies]\nanyhow = \"1\"\nzip = \"2\"\n");
+    }
+
+    #[test]
+    fn test_validate_reports_position() {
+        let error = parse_document("[package]\nname = \"demo\nversion = 1\n").unwrap_err();
+        assert!(error.contains("line 2"), "{}", error);
+        assert!(parse_document("a = 1\na = 2\n").unwrap_err().contains("duplicate key"));
+    }
+
+    #[test]
+    fn test_parse_key_path() {
+        assert_eq!(
+            parse_key_path("workspace.members[0]").unwrap(),
+            vec![KeySegment::Key("workspace".into()), KeySegment::Key("members".into()), KeySegment::Index(0)]
+        );
+        assert_eq!(
+            parse_key_path("dependencies.\"serde.json\".version").unwrap(),
+            vec![KeySegment::Key("dependencies".into()), KeySegment::Key("serde.json".into()), KeySegment::Key("version".into())]
+        );
+        assert!(parse_key_path("a..b").is_err());
+        assert!(parse_key_path("a[x]").is_err());
+        assert!(parse_key_path("a.\"b").is_err());
+    }
+
+    #[test]
+    fn test_get_value() {
+        let root = toml::Value::Table(MANIFEST.parse().unwrap());
+        assert_eq!(get_value(&root, "package.name").unwrap(), "demo");
+        assert_eq!(get_value(&root, "workspace.members[1]").unwrap(), "cli");
+        assert_eq!(get_value(&root, "workspace.members.0").unwrap(), "core");
+        assert_eq!(get_value(&root, "dependencies.serde.features").unwrap(), "[\"derive\"]");
+        assert_eq!(get_value(&root, "package").unwrap(), "name = \"demo\"\nversion = \"0.1.0\"");
+        assert_eq!(
+            get_value(&root, "package.edition").unwrap_err(),
+            "Key 'edition' not found in 'package'. Available keys: name, version"
+        );
+        assert!(get_value(&root, "package.name.first").is_err());
+    }
+}
@@ -0,0 +1,201 @@
+use crate::qr_generate::parse_otpauth;
+use crate::secret_vault::vault_secret;
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use ring::hmac;
+use std::error::Error;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub struct TotpModule;
+
+impl ToolModule for TotpModule {
+    fn name(&self) -> &'static str {
+        "totp"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("totp")
+                .long("totp")
+                .value_name("SECRET")
+                .help("Show the current and next TOTP code for a base32 secret, otpauth:// URI or vault:KEY")
+                .long_help("Generate the current one-time code for a 2FA secret, with the seconds it stays valid and the code that follows. SECRET is the base32 key shown during enrollment (spaces and case are ignored; 6 digits, 30 seconds, SHA1) or a full otpauth:// URI, whose digits, period and algorithm are used. vault:KEY reads either from the --vault store. Use --qr-decode to get the URI out of a QR code screenshot.")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(secret) = matches.get_one::<String>("totp") {
+            let params = match secret.strip_prefix("vault:") {
+                Some(key) => OtpParams::parse(&vault_secret(key)?)?,
+                None => OtpParams::parse(secret)?,
+            };
+            let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+            if let Some(label) = &params.label {
+                println!("{}", label.bold());
+            }
+            match params.counter {
+                // HOTP codes advance per use rather than per period
+                Some(counter) => {
+                    println!("{:<12}{}", "Code:".cyan(), format_code(&params.code(counter)).green().bold());
+                    println!("{:<12}{}", "Counter:".cyan(), counter);
+                    println!("{:<12}{}", "Next:".cyan(), format_code(&params.code(counter + 1)));
+                }
+                None => {
+                    let step = now / params.period;
+                    let remaining = params.period - now % params.period;
+                    println!("{:<12}{}", "Code:".cyan(), format_code(&params.code(step)).green().bold());
+                    let expires = format!("{}s", remaining);
+                    println!("{:<12}{}", "Expires in:".cyan(), if remaining <= 5 { expires.yellow() } else { expires.normal() });
+                    println!("{:<12}{}", "Next:".cyan(), format_code(&params.code(step + 1)));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct OtpParams {
+    key: Vec<u8>,
+    algorithm: hmac::Algorithm,
+    digits: u32,
+    period: u64,
+    // Set for HOTP URIs only
+    counter: Option<u64>,
+    label: Option<String>,
+}
+
+impl OtpParams {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        let input = input.trim();
+        if !input.to_lowercase().starts_with("otpauth://") {
+            return Ok(OtpParams {
+                key: base32_decode(input)?,
+                algorithm: hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY,
+                digits: 6,
+                period: 30,
+                counter: None,
+                label: None,
+            });
+        }
+        let uri = parse_otpauth(input)?;
+        let algorithm = match uri.algorithm.as_str() {
+            "SHA1" => hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY,
+            "SHA256" => hmac::HMAC_SHA256,
+            "SHA512" => hmac::HMAC_SHA512,
+            other => return Err(format!("Unsupported algorithm '{}'. Expected SHA1, SHA256 or SHA512", other).into()),
+        };
+        if !(6..=8).contains(&uri.digits) {
+            return Err(format!("Unsupported digits '{}'. Expected 6 to 8", uri.digits).into());
+        }
+        let period = uri.period.unwrap_or(30);
+        if period == 0 {
+            return Err("The period must be at least 1 second".into());
+        }
+        let label = match &uri.issuer {
+            Some(issuer) if !uri.account.is_empty() && *issuer != uri.account => format!("{} ({})", issuer, uri.account),
+            Some(issuer) => issuer.clone(),
+            None => uri.account.clone(),
+        };
+        Ok(OtpParams {
+            key: base32_decode(&uri.secret)?,
+            algorithm,
+            digits: uri.digits,
+            period,
+            counter: uri.counter,
+            label: Some(label).filter(|label| !label.is_empty()),
+        })
+    }
+
+    fn code(&self, counter: u64) -> String {
+        hotp(&self.key, self.algorithm, counter, self.digits)
+    }
+}
+
+// RFC 4648 base32 as used by authenticator apps: case-insensitive, spaces and padding ignored
+fn base32_decode(input: &str) -> Result<Vec<u8>, String> {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+    let mut bytes = Vec::new();
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for c in input.chars().filter(|c| !c.is_whitespace() && *c != '-' && *c != '=') {
+        let value = ALPHABET
+            .iter()
+            .position(|letter| *letter as char == c.to_ascii_uppercase())
+            .ok_or_else(|| format!("Invalid base32 character '{}' in secret", c))?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    if bytes.is_empty() {
+        return Err("The secret is empty".to_string());
+    }
+    Ok(bytes)
+}
+
+// RFC 4226 HOTP; TOTP (RFC 6238) is HOTP over the number of elapsed periods
+fn hotp(key: &[u8], algorithm: hmac::Algorithm, counter: u64, digits: u32) -> String {
+    let tag = hmac::sign(&hmac::Key::new(algorithm, key), &counter.to_be_bytes());
+    let hash = tag.as_ref();
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([hash[offset] & 0x7f, hash[offset + 1], hash[offset + 2], hash[offset + 3]]);
+    format!("{:0width$}", binary % 10u32.pow(digits), width = digits as usize)
+}
+
+// "123456" -> "123 456", the grouping authenticator apps use
+fn format_code(code: &str) -> String {
+    let middle = code.len().div_ceil(2);
+    format!("{} {}", &code[..middle], &code[middle..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base32_decode() {
+        assert_eq!(base32_decode("GEZDGNBVGY3TQOJQ").unwrap(), b"1234567890");
+        assert_eq!(base32_decode("gezd gnbv gy3t qojq").unwrap(), b"1234567890");
+        assert_eq!(base32_decode("MZXW6===").unwrap(), b"foo");
+        assert!(base32_decode("GEZD1").is_err());
+        assert!(base32_decode("  ").is_err());
+    }
+
+    #[test]
+    fn test_rfc6238_vectors() {
+        let sha1 = base32_decode("GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ").unwrap();
+        assert_eq!(hotp(&sha1, hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, 59 / 30, 8), "94287082");
+        assert_eq!(hotp(&sha1, hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, 1111111109 / 30, 8), "07081804");
+        assert_eq!(hotp(&sha1, hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, 1234567890 / 30, 8), "89005924");
+        let sha256 = base32_decode("GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZA====").unwrap();
+        assert_eq!(hotp(&sha256, hmac::HMAC_SHA256, 59 / 30, 8), "46119246");
+        let sha512 = base32_decode("GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNA=").unwrap();
+        assert_eq!(hotp(&sha512, hmac::HMAC_SHA512, 59 / 30, 8), "90693936");
+        // RFC 4226 appendix D
+        assert_eq!(hotp(b"12345678901234567890", hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, 0, 6), "755224");
+    }
+
+    #[test]
+    fn test_parse_otpauth_params() {
+        let params = OtpParams::parse("otpauth://totp/ACME:alice@example.com?secret=GEZDGNBVGY3TQOJQ&issuer=ACME&digits=8&period=60&algorithm=SHA256").unwrap();
+        assert_eq!(params.digits, 8);
+        assert_eq!(params.period, 60);
+        assert_eq!(params.label.as_deref(), Some("ACME (alice@example.com)"));
+        assert_eq!(params.key, b"1234567890");
+        assert_eq!(OtpParams::parse("GEZDGNBVGY3TQOJQ").unwrap().period, 30);
+        assert_eq!(OtpParams::parse("otpauth://hotp/x?secret=GEZDGNBVGY3TQOJQ&counter=7").unwrap().counter, Some(7));
+        assert!(OtpParams::parse("otpauth://totp/x?secret=GEZDGNBVGY3TQOJQ&algorithm=MD5").is_err());
+        assert!(OtpParams::parse("otpauth://totp/x?secret=GEZDGNBVGY3TQOJQ&digits=4").is_err());
+    }
+
+    #[test]
+    fn test_format_code() {
+        assert_eq!(format_code("123456"), "123 456");
+        assert_eq!(format_code("1234567"), "1234 567");
+        assert_eq!(format_code("12345678"), "1234 5678");
+    }
+}
@@ -0,0 +1,304 @@
+use crate::tool_module::{read_text, ExitCodeError, ToolModule};
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use std::error::Error;
+use unicode_normalization::UnicodeNormalization;
+use unicode_security::{skeleton, MixedScript};
+
+pub struct UnicodeTextModule;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Form {
+    Nfc,
+    Nfd,
+    Nfkc,
+    Nfkd,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    // Renders as nothing
+    Invisible,
+    // Changes the order text is displayed in
+    Bidi,
+    // Looks like a plain space
+    Space,
+}
+
+#[derive(Debug, PartialEq)]
+enum Issue {
+    Special(Kind, &'static str),
+    // Unicode tag characters mirror ASCII invisibly and can smuggle hidden text
+    Tag(char),
+    Control,
+    Lookalike(String),
+}
+
+const SPECIAL_CHARACTERS: &[(char, Kind, &str)] = &[
+    ('\u{00A0}', Kind::Space, "NO-BREAK SPACE"),
+    ('\u{00AD}', Kind::Invisible, "SOFT HYPHEN"),
+    ('\u{034F}', Kind::Invisible, "COMBINING GRAPHEME JOINER"),
+    ('\u{061C}', Kind::Bidi, "ARABIC LETTER MARK"),
+    ('\u{115F}', Kind::Invisible, "HANGUL CHOSEONG FILLER"),
+    ('\u{1160}', Kind::Invisible, "HANGUL JUNGSEONG FILLER"),
+    ('\u{1680}', Kind::Space, "OGHAM SPACE MARK"),
+    ('\u{17B4}', Kind::Invisible, "KHMER VOWEL INHERENT AQ"),
+    ('\u{17B5}', Kind::Invisible, "KHMER VOWEL INHERENT AA"),
+    ('\u{180E}', Kind::Invisible, "MONGOLIAN VOWEL SEPARATOR"),
+    ('\u{2000}', Kind::Space, "EN QUAD"),
+    ('\u{2001}', Kind::Space, "EM QUAD"),
+    ('\u{2002}', Kind::Space, "EN SPACE"),
+    ('\u{2003}', Kind::Space, "EM SPACE"),
+    ('\u{2004}', Kind::Space, "THREE-PER-EM SPACE"),
+    ('\u{2005}', Kind::Space, "FOUR-PER-EM SPACE"),
+    ('\u{2006}', Kind::Space, "SIX-PER-EM SPACE"),
+    ('\u{2007}', Kind::Space, "FIGURE SPACE"),
+    ('\u{2008}', Kind::Space, "PUNCTUATION SPACE"),
+    ('\u{2009}', Kind::Space, "THIN SPACE"),
+    ('\u{200A}', Kind::Space, "HAIR SPACE"),
+    ('\u{200B}', Kind::Invisible, "ZERO WIDTH SPACE"),
+    ('\u{200C}', Kind::Invisible, "ZERO WIDTH NON-JOINER"),
+    ('\u{200D}', Kind::Invisible, "ZERO WIDTH JOINER"),
+    ('\u{200E}', Kind::Bidi, "LEFT-TO-RIGHT MARK"),
+    ('\u{200F}', Kind::Bidi, "RIGHT-TO-LEFT MARK"),
+    ('\u{2028}', Kind::Space, "LINE SEPARATOR"),
+    ('\u{2029}', Kind::Space, "PARAGRAPH SEPARATOR"),
+    ('\u{202A}', Kind::Bidi, "LEFT-TO-RIGHT EMBEDDING"),
+    ('\u{202B}', Kind::Bidi, "RIGHT-TO-LEFT EMBEDDING"),
+    ('\u{202C}', Kind::Bidi, "POP DIRECTIONAL FORMATTING"),
+    ('\u{202D}', Kind::Bidi, "LEFT-TO-RIGHT OVERRIDE"),
+    ('\u{202E}', Kind::Bidi, "RIGHT-TO-LEFT OVERRIDE"),
+    ('\u{202F}', Kind::Space, "NARROW NO-BREAK SPACE"),
+    ('\u{205F}', Kind::Space, "MEDIUM MATHEMATICAL SPACE"),
+    ('\u{2060}', Kind::Invisible, "WORD JOINER"),
+    ('\u{2061}', Kind::Invisible, "FUNCTION APPLICATION"),
+    ('\u{2062}', Kind::Invisible, "INVISIBLE TIMES"),
+    ('\u{2063}', Kind::Invisible, "INVISIBLE SEPARATOR"),
+    ('\u{2064}', Kind::Invisible, "INVISIBLE PLUS"),
+    ('\u{2066}', Kind::Bidi, "LEFT-TO-RIGHT ISOLATE"),
+    ('\u{2067}', Kind::Bidi, "RIGHT-TO-LEFT ISOLATE"),
+    ('\u{2068}', Kind::Bidi, "FIRST STRONG ISOLATE"),
+    ('\u{2069}', Kind::Bidi, "POP DIRECTIONAL ISOLATE"),
+    ('\u{3000}', Kind::Space, "IDEOGRAPHIC SPACE"),
+    ('\u{3164}', Kind::Invisible, "HANGUL FILLER"),
+    ('\u{FEFF}', Kind::Invisible, "ZERO WIDTH NO-BREAK SPACE (BOM)"),
+    ('\u{FFA0}', Kind::Invisible, "HALFWIDTH HANGUL FILLER"),
+];
+
+impl ToolModule for UnicodeTextModule {
+    fn name(&self) -> &'static str {
+        "unicode-text"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("unicode-normalize")
+                .long("unicode-normalize")
+                .value_names(["TEXT", "FORM"])
+                .num_args(1..=2)
+                .help("Normalize text to NFC (default), NFD, NFKC or NFKD (- for stdin)")
+                .long_help("Print TEXT in a Unicode normalization form: NFC (composed, the default), NFD (decomposed), NFKC or NFKD (also folding compatibility characters such as ﬁ, ² and fullwidth letters). Use - to read stdin. How many code points changed is reported on stderr, so the output can be piped.")
+        )
+        .arg(
+            Arg::new("confusables")
+                .long("confusables")
+                .value_name("TEXT")
+                .help("Flag homoglyphs, invisible characters and bidi overrides, and print an ASCII-safe version (- for stdin)")
+                .long_help("Check TEXT for characters that hide or disguise what it says: lookalikes of ASCII letters from other scripts (Cyrillic а for a), zero-width and other invisible characters, bidi controls that reorder how text is displayed (Trojan Source), unusual spaces, control characters and Unicode tag characters carrying hidden text. Words mixing scripts are listed, and a cleaned ASCII-safe version is printed. Exits with 1 when anything is found. Use - to read stdin.")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(values) = matches.get_many::<String>("unicode-normalize") {
+            let values: Vec<&String> = values.collect();
+            let text = read_text(values[0])?;
+            let form = values.get(1).map_or(Ok(Form::Nfc), |form| parse_form(form))?;
+            let normalized = normalize(&text, form);
+            // On stderr, so only the text is piped on
+            if normalized == text {
+                eprintln!("Already normalized; nothing changed");
+            } else {
+                eprintln!("Normalized: {} → {} code points", text.chars().count(), normalized.chars().count());
+            }
+            println!("{}", normalized);
+        } else if let Some(value) = matches.get_one::<String>("confusables") {
+            let text = read_text(value)?;
+            let issues = find_issues(&text);
+            let mixed = mixed_script_words(&text);
+            if issues.is_empty() && mixed.is_empty() {
+                println!("✅ No lookalike, invisible or bidi control characters found");
+                return Ok(());
+            }
+            println!("⚠️  {} suspicious character{}", issues.len(), if issues.len() == 1 { "" } else { "s" });
+            for (line, column, c, issue) in &issues {
+                println!("  {:<8}{:<10}{}", format!("{}:{}", line, column), format!("U+{:04X}", *c as u32).yellow(), describe(issue));
+            }
+            let hidden: String = issues.iter().filter_map(|(.., issue)| if let Issue::Tag(c) = issue { Some(*c) } else { None }).collect();
+            if !hidden.is_empty() {
+                println!("{:<14}{:?}", "Hidden text:".cyan(), hidden);
+            }
+            if !mixed.is_empty() {
+                println!("{:<14}{}", "Mixed script:".cyan(), mixed.join(", "));
+            }
+            println!("{:<14}{}", "Cleaned:".cyan(), clean(&text));
+            return Err(Box::new(ExitCodeError::silent(1)));
+        }
+        Ok(())
+    }
+}
+
+fn parse_form(form: &str) -> Result<Form, String> {
+    match form.to_uppercase().as_str() {
+        "NFC" => Ok(Form::Nfc),
+        "NFD" => Ok(Form::Nfd),
+        "NFKC" => Ok(Form::Nfkc),
+        "NFKD" => Ok(Form::Nfkd),
+        _ => Err(format!("Unknown normalization form '{}'. Use NFC, NFD, NFKC or NFKD", form)),
+    }
+}
+
+fn normalize(text: &str, form: Form) -> String {
+    match form {
+        Form::Nfc => text.nfc().collect(),
+        Form::Nfd => text.nfd().collect(),
+        Form::Nfkc => text.nfkc().collect(),
+        Form::Nfkd => text.nfkd().collect(),
+    }
+}
+
+// The ASCII character a non-ASCII one is confusable with, by their UTS #39 skeletons;
+// a letter of the same case is preferred, as Cyrillic І should become I rather than l
+fn ascii_lookalike(c: char) -> Option<String> {
+    if c.is_ascii() {
+        return None;
+    }
+    let target: String = skeleton(c.encode_utf8(&mut [0; 4])).collect();
+    // Typographic quotes and dashes are everywhere and rarely an attack; slashes and dots can fake URLs
+    if !target.is_ascii() || target.is_empty() || !(c.is_alphanumeric() || matches!(target.as_str(), "/" | "." | ":" | "@" | "\\")) {
+        return None;
+    }
+    let candidates: Vec<char> = (' '..='~').filter(|a| skeleton(a.encode_utf8(&mut [0; 4])).eq(target.chars())).collect();
+    let same_case = candidates.iter().find(|a| a.is_alphabetic() && a.is_uppercase() == c.is_uppercase());
+    Some(same_case.or(candidates.first()).map_or(target, char::to_string))
+}
+
+// The name of an invisible, bidi control or unusual space character
+pub fn special_character_name(c: char) -> Option<&'static str> {
+    SPECIAL_CHARACTERS.iter().find(|(special, ..)| *special == c).map(|(_, _, name)| *name)
+}
+
+fn classify(c: char) -> Option<Issue> {
+    if let Some((_, kind, name)) = SPECIAL_CHARACTERS.iter().find(|(special, ..)| *special == c) {
+        return Some(Issue::Special(*kind, name));
+    }
+    if ('\u{E0000}'..='\u{E007F}').contains(&c) {
+        return Some(Issue::Tag(char::from_u32(c as u32 - 0xE0000).filter(|c| c.is_ascii_graphic() || *c == ' ').unwrap_or('�')));
+    }
+    if c.is_control() && !matches!(c, '\t' | '\n' | '\r') {
+        return Some(Issue::Control);
+    }
+    ascii_lookalike(c).map(Issue::Lookalike)
+}
+
+// (line, column, character, issue), all 1-based
+fn find_issues(text: &str) -> Vec<(usize, usize, char, Issue)> {
+    let mut issues = Vec::new();
+    for (number, line) in text.split('\n').enumerate() {
+        for (index, c) in line.chars().enumerate() {
+            if let Some(issue) = classify(c) {
+                issues.push((number + 1, index + 1, c, issue));
+            }
+        }
+    }
+    issues
+}
+
+fn describe(issue: &Issue) -> String {
+    match issue {
+        Issue::Special(Kind::Invisible, name) => format!("invisible: {}", name),
+        Issue::Special(Kind::Bidi, name) => format!("bidi control: {} (changes the display order)", name),
+        Issue::Special(Kind::Space, name) => format!("unusual space: {}", name),
+        Issue::Tag(c) => format!("tag character hiding {:?}", c),
+        Issue::Control => "control character".to_string(),
+        Issue::Lookalike(ascii) => format!("looks like {:?}", ascii),
+    }
+}
+
+// Words whose letters come from more than one script, like "pаypal" with a Cyrillic а
+pub fn mixed_script_words(text: &str) -> Vec<String> {
+    let mut words: Vec<String> = text
+        .split(|c: char| c.is_whitespace() || (c.is_ascii_punctuation() && c != '-'))
+        .filter(|word| !word.is_empty() && !word.is_single_script())
+        .map(str::to_string)
+        .collect();
+    words.dedup();
+    words
+}
+
+// Invisible and bidi characters dropped, spaces made plain, lookalikes replaced,
+// then anything still outside ASCII transliterated
+fn clean(text: &str) -> String {
+    let mapped: String = text
+        .chars()
+        .filter_map(|c| match classify(c) {
+            Some(Issue::Special(Kind::Space, _)) => Some(" ".to_string()),
+            Some(Issue::Special(..) | Issue::Tag(_) | Issue::Control) => None,
+            Some(Issue::Lookalike(ascii)) => Some(ascii),
+            None => Some(c.to_string()),
+        })
+        .collect();
+    deunicode::deunicode(&mapped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        let composed = "caf\u{e9}";
+        let decomposed = "cafe\u{301}";
+        assert_eq!(normalize(decomposed, Form::Nfc), composed);
+        assert_eq!(normalize(composed, Form::Nfd), decomposed);
+        assert_eq!(normalize("ﬁ²", Form::Nfc), "ﬁ²");
+        assert_eq!(normalize("ﬁ²", Form::Nfkc), "fi2");
+        assert_eq!(normalize("Ｈｅｌｌｏ", Form::Nfkd), "Hello");
+        assert_eq!(parse_form("nfkc").unwrap(), Form::Nfkc);
+        assert!(parse_form("NFX").is_err());
+    }
+
+    #[test]
+    fn test_lookalikes() {
+        assert_eq!(ascii_lookalike('а'), Some("a".to_string()));
+        assert_eq!(ascii_lookalike('О'), Some("O".to_string()));
+        assert_eq!(ascii_lookalike('І'), Some("I".to_string()));
+        assert_eq!(ascii_lookalike('ｅ'), Some("e".to_string()));
+        assert_eq!(ascii_lookalike('a'), None);
+        // Accented letters are not disguised as anything
+        assert_eq!(ascii_lookalike('é'), None);
+        assert_eq!(ascii_lookalike('日'), None);
+        assert_eq!(ascii_lookalike('’'), None);
+        assert_eq!(ascii_lookalike('\u{2215}'), Some("/".to_string()));
+    }
+
+    #[test]
+    fn test_find_issues() {
+        let text = "p\u{430}ypal\u{200B}.com\nx = 1\u{202E}";
+        let issues = find_issues(text);
+        assert_eq!(issues.len(), 3);
+        assert_eq!(issues[0], (1, 2, '\u{430}', Issue::Lookalike("a".to_string())));
+        assert_eq!(issues[1], (1, 7, '\u{200B}', Issue::Special(Kind::Invisible, "ZERO WIDTH SPACE")));
+        assert_eq!(issues[2], (2, 6, '\u{202E}', Issue::Special(Kind::Bidi, "RIGHT-TO-LEFT OVERRIDE")));
+        assert_eq!(find_issues("\u{E0068}\u{E0069}")[1].3, Issue::Tag('i'));
+        assert!(find_issues("Grüße, 日本語 and plain text\t!").is_empty());
+    }
+
+    #[test]
+    fn test_clean() {
+        assert_eq!(clean("p\u{430}ypal\u{200B}.com"), "paypal.com");
+        assert_eq!(clean("a\u{00A0}b\u{E0041}c"), "a bc");
+        assert_eq!(clean("Crème brûlée"), "Creme brulee");
+        assert_eq!(mixed_script_words("login at p\u{430}ypal.com now"), ["p\u{430}ypal"]);
+        assert!(mixed_script_words("Grüße aus München").is_empty());
+    }
+}
@@ -0,0 +1,344 @@
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgMatches, Command};
+use regex::Regex;
+use std::error::Error;
+
+pub struct UnitConvertModule;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Category {
+    Length,
+    Mass,
+    Temperature,
+    Area,
+    Volume,
+    Speed,
+    Data,
+}
+
+const CATEGORIES: [Category; 7] = [
+    Category::Length,
+    Category::Mass,
+    Category::Temperature,
+    Category::Area,
+    Category::Volume,
+    Category::Speed,
+    Category::Data,
+];
+
+impl Category {
+    fn name(self) -> &'static str {
+        match self {
+            Category::Length => "length",
+            Category::Mass => "mass",
+            Category::Temperature => "temperature",
+            Category::Area => "area",
+            Category::Volume => "volume",
+            Category::Speed => "speed",
+            Category::Data => "data",
+        }
+    }
+}
+
+// A value in this unit equals `value * factor + offset` in the category's base
+// unit (m, kg, K, m², L, m/s, byte). Only temperatures have an offset.
+struct Unit {
+    symbol: &'static str,
+    name: &'static str,
+    category: Category,
+    factor: f64,
+    offset: f64,
+    aliases: &'static [&'static str],
+}
+
+const fn unit(symbol: &'static str, name: &'static str, category: Category, factor: f64, aliases: &'static [&'static str]) -> Unit {
+    Unit { symbol, name, category, factor, offset: 0.0, aliases }
+}
+
+const INCH: f64 = 0.0254;
+const FOOT: f64 = 0.3048;
+const YARD: f64 = 0.9144;
+const MILE: f64 = 1609.344;
+const US_GALLON: f64 = 3.785411784;
+
+const UNITS: &[Unit] = &[
+    unit("nm", "nanometer", Category::Length, 1e-9, &["nanometre"]),
+    unit("um", "micrometer", Category::Length, 1e-6, &["µm", "micron", "micrometre"]),
+    unit("mm", "millimeter", Category::Length, 1e-3, &["millimetre"]),
+    unit("cm", "centimeter", Category::Length, 1e-2, &["centimetre"]),
+    unit("m", "meter", Category::Length, 1.0, &["metre"]),
+    unit("km", "kilometer", Category::Length, 1e3, &["kilometre"]),
+    unit("in", "inch", Category::Length, INCH, &["inches", "\""]),
+    unit("ft", "foot", Category::Length, FOOT, &["feet", "'"]),
+    unit("yd", "yard", Category::Length, YARD, &[]),
+    unit("mi", "mile", Category::Length, MILE, &[]),
+    unit("nmi", "nautical mile", Category::Length, 1852.0, &["nauticalmile"]),
+    unit("mg", "milligram", Category::Mass, 1e-6, &["milligramme"]),
+    unit("g", "gram", Category::Mass, 1e-3, &["gramme"]),
+    unit("kg", "kilogram", Category::Mass, 1.0, &["kilo", "kilogramme"]),
+    unit("t", "tonne", Category::Mass, 1e3, &["metric ton"]),
+    unit("oz", "ounce", Category::Mass, 0.028349523125, &[]),
+    unit("lb", "pound", Category::Mass, 0.45359237, &["lbs"]),
+    unit("st", "stone", Category::Mass, 6.35029318, &[]),
+    Unit { symbol: "C", name: "celsius", category: Category::Temperature, factor: 1.0, offset: 273.15, aliases: &["°C", "degC"] },
+    Unit { symbol: "F", name: "fahrenheit", category: Category::Temperature, factor: 5.0 / 9.0, offset: 459.67 * 5.0 / 9.0, aliases: &["°F", "degF"] },
+    Unit { symbol: "K", name: "kelvin", category: Category::Temperature, factor: 1.0, offset: 0.0, aliases: &[] },
+    Unit { symbol: "R", name: "rankine", category: Category::Temperature, factor: 5.0 / 9.0, offset: 0.0, aliases: &["°R", "degR"] },
+    unit("mm2", "square millimeter", Category::Area, 1e-6, &["mm²"]),
+    unit("cm2", "square centimeter", Category::Area, 1e-4, &["cm²"]),
+    unit("m2", "square meter", Category::Area, 1.0, &["m²", "sqm"]),
+    unit("ha", "hectare", Category::Area, 1e4, &[]),
+    unit("km2", "square kilometer", Category::Area, 1e6, &["km²"]),
+    unit("in2", "square inch", Category::Area, INCH * INCH, &["in²", "sqin"]),
+    unit("ft2", "square foot", Category::Area, FOOT * FOOT, &["ft²", "sqft", "square feet"]),
+    unit("yd2", "square yard", Category::Area, YARD * YARD, &["yd²", "sqyd"]),
+    unit("acre", "acre", Category::Area, 4046.8564224, &["ac"]),
+    unit("mi2", "square mile", Category::Area, MILE * MILE, &["mi²", "sqmi"]),
+    unit("ml", "milliliter", Category::Volume, 1e-3, &["cm3", "cm³", "cc", "millilitre"]),
+    unit("cl", "centiliter", Category::Volume, 1e-2, &["centilitre"]),
+    unit("dl", "deciliter", Category::Volume, 1e-1, &["decilitre"]),
+    unit("l", "liter", Category::Volume, 1.0, &["litre"]),
+    unit("m3", "cubic meter", Category::Volume, 1e3, &["m³"]),
+    unit("tsp", "teaspoon", Category::Volume, US_GALLON / 768.0, &[]),
+    unit("tbsp", "tablespoon", Category::Volume, US_GALLON / 256.0, &[]),
+    unit("floz", "fluid ounce", Category::Volume, US_GALLON / 128.0, &["fl oz", "fl-oz"]),
+    unit("cup", "cup", Category::Volume, US_GALLON / 16.0, &[]),
+    unit("pt", "pint", Category::Volume, US_GALLON / 8.0, &[]),
+    unit("qt", "quart", Category::Volume, US_GALLON / 4.0, &[]),
+    unit("gal", "gallon", Category::Volume, US_GALLON, &["usgal"]),
+    unit("ukgal", "imperial gallon", Category::Volume, 4.54609, &["impgal"]),
+    unit("ft3", "cubic foot", Category::Volume, FOOT * FOOT * FOOT * 1e3, &["ft³", "cuft", "cubic feet"]),
+    unit("m/s", "meter per second", Category::Speed, 1.0, &["mps"]),
+    unit("km/h", "kilometer per hour", Category::Speed, 1e3 / 3600.0, &["kmh", "kph"]),
+    unit("mph", "mile per hour", Category::Speed, MILE / 3600.0, &["mi/h", "miles per hour"]),
+    unit("ft/s", "foot per second", Category::Speed, FOOT, &["fps"]),
+    unit("kn", "knot", Category::Speed, 1852.0 / 3600.0, &["kt", "kts"]),
+    // Bytes come before bits so a lowercase "kb" or "mb" means bytes
+    unit("B", "byte", Category::Data, 1.0, &[]),
+    unit("KB", "kilobyte", Category::Data, 1e3, &["kB"]),
+    unit("MB", "megabyte", Category::Data, 1e6, &[]),
+    unit("GB", "gigabyte", Category::Data, 1e9, &[]),
+    unit("TB", "terabyte", Category::Data, 1e12, &[]),
+    unit("PB", "petabyte", Category::Data, 1e15, &[]),
+    unit("KiB", "kibibyte", Category::Data, 1024.0, &[]),
+    unit("MiB", "mebibyte", Category::Data, 1048576.0, &[]),
+    unit("GiB", "gibibyte", Category::Data, 1073741824.0, &[]),
+    unit("TiB", "tebibyte", Category::Data, 1099511627776.0, &[]),
+    unit("PiB", "pebibyte", Category::Data, 1125899906842624.0, &[]),
+    unit("b", "bit", Category::Data, 0.125, &[]),
+    unit("Kb", "kilobit", Category::Data, 125.0, &["kbit"]),
+    unit("Mb", "megabit", Category::Data, 125e3, &["mbit"]),
+    unit("Gb", "gigabit", Category::Data, 125e6, &["gbit"]),
+    unit("Tb", "terabit", Category::Data, 125e9, &["tbit"]),
+];
+
+impl Unit {
+    fn unit_to_base(&self, value: f64) -> f64 {
+        value * self.factor + self.offset
+    }
+
+    fn base_to_unit(&self, value: f64) -> f64 {
+        (value - self.offset) / self.factor
+    }
+
+    fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        [self.symbol, self.name].into_iter().chain(self.aliases.iter().copied())
+    }
+}
+
+impl ToolModule for UnitConvertModule {
+    fn name(&self) -> &'static str {
+        "unit-convert"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("convert")
+                .long("convert")
+                .value_names(["VALUE", "UNIT"])
+                .num_args(1..=4)
+                .allow_hyphen_values(true)
+                .help("Convert between units, e.g. --convert 5km mi (see --list-units)")
+                .long_help("Convert a value between units of length, mass, temperature, area, volume, speed or data size. The unit can be attached to the value or given separately: --convert 5km mi, --convert 5 km mi or --convert \"72 F\" C. Without a target unit, prints the value in every unit of its kind. Run --list-units to see the supported units.")
+                .conflicts_with("list-units")
+        )
+        .arg(
+            Arg::new("list-units")
+                .long("list-units")
+                .value_name("CATEGORY")
+                .num_args(0..=1)
+                .value_parser(CATEGORIES.map(Category::name))
+                .help("List the units --convert understands, optionally for one category")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(values) = matches.get_many::<String>("convert") {
+            let values: Vec<&str> = values.map(String::as_str).collect();
+            let (value, from, to) = parse_request(&values)?;
+            match to {
+                Some(to) => {
+                    let result = convert(value, from, to)?;
+                    println!("{} {} = {} {}", format_significant(value), from.symbol, format_significant(result), to.symbol);
+                }
+                None => {
+                    println!("{} {} =", format_significant(value), from.symbol);
+                    for unit in UNITS.iter().filter(|unit| unit.category == from.category && unit.symbol != from.symbol) {
+                        println!("  {:>16} {:<6} ({})", format_significant(convert(value, from, unit)?), unit.symbol, unit.name);
+                    }
+                }
+            }
+        } else if matches.contains_id("list-units") {
+            let only = matches.get_one::<String>("list-units");
+            for category in CATEGORIES.iter().filter(|category| only.is_none_or(|name| name == category.name())) {
+                println!("{}:", capitalize(category.name()));
+                for unit in UNITS.iter().filter(|unit| unit.category == *category) {
+                    println!("  {:<6} {}", unit.symbol, unit.name);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// "5km mi", "5 km mi", "5km to mi" or just "5km"
+fn parse_request(values: &[&str]) -> Result<(f64, &'static Unit, Option<&'static Unit>), String> {
+    let (value, from, rest) = match values[0].trim().parse::<f64>() {
+        Ok(number) => {
+            let unit = values.get(1).ok_or("Missing the unit to convert from, e.g. --convert 5 km mi")?;
+            (number, find_unit(unit)?, &values[2..])
+        }
+        Err(_) => {
+            let (number, unit) = split_quantity(values[0])?;
+            (number, find_unit(&unit)?, &values[1..])
+        }
+    };
+    let target = match rest {
+        [] => None,
+        [to, target] if matches!(to.to_lowercase().as_str(), "to" | "in") => Some(*target),
+        [target] => Some(*target),
+        _ => return Err(format!("Too many values: {}", values.join(" "))),
+    };
+    Ok((value, from, target.map(find_unit).transpose()?))
+}
+
+// "5km", "-40°F", "1.5e3 m" into the number and the unit text
+fn split_quantity(text: &str) -> Result<(f64, String), String> {
+    let pattern = Regex::new(r"^\s*([-+]?(?:\d+\.?\d*|\.\d+)(?:[eE][-+]?\d+)?)\s*(.+?)\s*$").map_err(|e| e.to_string())?;
+    let captures = pattern.captures(text).ok_or_else(|| format!("Could not read '{}' as a number and unit, e.g. 5km", text))?;
+    let number = captures[1].parse().map_err(|_| format!("Invalid number '{}'", &captures[1]))?;
+    Ok((number, captures[2].to_string()))
+}
+
+// Exact matches win so "b" is a bit and "B" a byte; otherwise case and plural
+// endings are ignored
+fn find_unit(text: &str) -> Result<&'static Unit, String> {
+    let text = text.trim();
+    if let Some(unit) = UNITS.iter().find(|unit| unit.names().any(|name| name == text)) {
+        return Ok(unit);
+    }
+    let lower = text.to_lowercase();
+    let singular = lower.strip_suffix('s').filter(|stem| stem.len() > 1);
+    UNITS.iter()
+        .find(|unit| unit.names().any(|name| {
+            let name = name.to_lowercase();
+            name == lower || singular.is_some_and(|stem| stem == name)
+        }))
+        .ok_or_else(|| format!("Unknown unit '{}'. Run --list-units to see the supported units", text))
+}
+
+fn convert(value: f64, from: &Unit, to: &Unit) -> Result<f64, String> {
+    if from.category != to.category {
+        return Err(format!(
+            "Cannot convert {} ({}) to {} ({})",
+            from.symbol,
+            from.category.name(),
+            to.symbol,
+            to.category.name()
+        ));
+    }
+    Ok(to.base_to_unit(from.unit_to_base(value)))
+}
+
+// Ten significant digits hide floating point noise such as 2.5400000000000005
+fn format_significant(value: f64) -> String {
+    if value == 0.0 || !value.is_finite() {
+        return value.abs().to_string();
+    }
+    let magnitude = value.abs().log10().floor() as i32;
+    if !(-6..15).contains(&magnitude) {
+        let formatted = format!("{:.9e}", value);
+        let (mantissa, exponent) = formatted.split_once('e').unwrap_or((&formatted, "0"));
+        return format!("{}e{}", trim_zeros(mantissa), exponent);
+    }
+    trim_zeros(&format!("{:.*}", (9 - magnitude).max(0) as usize, value))
+}
+
+fn trim_zeros(number: &str) -> String {
+    if number.contains('.') {
+        number.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        number.to_string()
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert_text(values: &[&str]) -> Result<String, String> {
+        let (value, from, to) = parse_request(values)?;
+        convert(value, from, to.unwrap()).map(format_significant)
+    }
+
+    #[test]
+    fn test_convert_length_and_mass() {
+        assert_eq!(convert_text(&["5km", "mi"]).unwrap(), "3.106855961");
+        assert_eq!(convert_text(&["1", "in", "cm"]).unwrap(), "2.54");
+        assert_eq!(convert_text(&["10 lbs", "to", "kg"]).unwrap(), "4.5359237");
+        assert_eq!(convert_text(&["6ft", "m"]).unwrap(), "1.8288");
+    }
+
+    #[test]
+    fn test_convert_temperature() {
+        assert_eq!(convert_text(&["-40C", "F"]).unwrap(), "-40");
+        assert_eq!(convert_text(&["98.6", "fahrenheit", "celsius"]).unwrap(), "37");
+        assert_eq!(convert_text(&["0K", "C"]).unwrap(), "-273.15");
+    }
+
+    #[test]
+    fn test_convert_area_volume_speed() {
+        assert_eq!(convert_text(&["1ha", "m2"]).unwrap(), "10000");
+        assert_eq!(convert_text(&["1 gal", "l"]).unwrap(), "3.785411784");
+        assert_eq!(convert_text(&["100km/h", "mph"]).unwrap(), "62.13711922");
+    }
+
+    #[test]
+    fn test_convert_data_sizes() {
+        assert_eq!(convert_text(&["1GiB", "MB"]).unwrap(), "1073.741824");
+        assert_eq!(convert_text(&["100Mb", "MB"]).unwrap(), "12.5");
+        assert_eq!(convert_text(&["8b", "B"]).unwrap(), "1");
+        assert_eq!(convert_text(&["2mb", "kb"]).unwrap(), "2000");
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(convert_text(&["5km", "kg"]).unwrap_err(), "Cannot convert km (length) to kg (mass)");
+        assert!(convert_text(&["5 furlongs", "m"]).unwrap_err().starts_with("Unknown unit 'furlongs'"));
+        assert!(convert_text(&["km", "mi"]).is_err());
+        assert!(convert_text(&["5", "km", "in", "mi", "ft"]).is_err());
+    }
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_significant(0.1 + 0.2), "0.3");
+        assert_eq!(format_significant(1e18), "1e18");
+        assert_eq!(format_significant(2.5e-9), "2.5e-9");
+        assert_eq!(format_significant(-0.0), "0");
+    }
+}
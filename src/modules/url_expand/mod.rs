@@ -0,0 +1,200 @@
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use regex::Regex;
+use reqwest::blocking::Client;
+use reqwest::header::{CONTENT_TYPE, LOCATION};
+use reqwest::redirect::Policy;
+use std::error::Error;
+use std::io::{self, IsTerminal, Read};
+use std::sync::OnceLock;
+use std::time::Duration;
+use url::Url;
+
+pub struct UrlExpandModule;
+
+const MAX_HOPS: usize = 20;
+// Only the start of an HTML page is searched for a meta refresh
+const MAX_HTML_BYTES: u64 = 64 * 1024;
+
+// Click IDs and campaign tags added by ad networks, mailers and social sites.
+// Entries ending in _ are prefixes.
+const TRACKING_PARAMS: [&str; 24] = [
+    "utm_", "fbclid", "gclid", "gclsrc", "dclid", "gbraid", "wbraid", "msclkid", "yclid", "twclid", "ttclid", "igshid",
+    "li_fat_id", "mc_cid", "mc_eid", "_hsenc", "_hsmi", "mkt_tok", "vero_id", "oly_enc_id", "oly_anon_id", "s_cid", "_gl", "__s",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+struct Hop {
+    url: String,
+    status: u16,
+    // Where this hop sends the browser next, from a Location header or a meta refresh
+    next: Option<String>,
+}
+
+impl ToolModule for UrlExpandModule {
+    fn name(&self) -> &'static str {
+        "url-expand"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("expand-url")
+                .long("expand-url")
+                .value_name("URL")
+                .help("Follow a short or suspicious link's redirects and show the chain, final URL and tracking parameters")
+                .long_help("Follow URL hop by hop without running any JavaScript: HTTP redirects (Location headers) and HTML meta refresh tags are followed, up to 20 hops. Every hop is printed with its status code, followed by the final destination, any tracking parameters found in it (utm_*, fbclid, gclid and the like) and the URL with those removed. Use --parse-url to take the result apart further.")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(url) = matches.get_one::<String>("expand-url") {
+            let start = if url.contains("://") { url.clone() } else { format!("https://{}", url) };
+            let hops = follow(&start)?;
+            print!("{}", render(&hops, io::stdout().is_terminal()));
+        }
+        Ok(())
+    }
+}
+
+fn follow(start: &str) -> Result<Vec<Hop>, Box<dyn Error>> {
+    let client = Client::builder()
+        .redirect(Policy::none())
+        .user_agent(concat!("micro-swiss/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(15))
+        .build()?;
+    let mut hops: Vec<Hop> = Vec::new();
+    let mut url = Url::parse(start).map_err(|e| format!("Invalid URL '{}': {}", start, e))?;
+    loop {
+        if hops.iter().any(|hop| hop.url == url.as_str()) {
+            return Err(format!("Redirect loop: {} was already visited", url).into());
+        }
+        if hops.len() == MAX_HOPS {
+            return Err(format!("Gave up after {} redirects", MAX_HOPS).into());
+        }
+        let response = client.get(url.as_str()).send().map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
+        let status = response.status().as_u16();
+        let header = |name| response.headers().get(name).and_then(|value| value.to_str().ok()).map(str::to_string);
+        let next = if (300..400).contains(&status) {
+            header(LOCATION)
+        } else if status == 200 && header(CONTENT_TYPE).is_some_and(|content_type| content_type.contains("html")) {
+            let mut html = String::new();
+            response.take(MAX_HTML_BYTES).read_to_string(&mut html).ok();
+            meta_refresh(&html)
+        } else {
+            None
+        };
+        let next_url = match &next {
+            Some(location) => Some(url.join(location).map_err(|e| format!("Invalid redirect target '{}': {}", location, e))?),
+            None => None,
+        };
+        hops.push(Hop { url: url.to_string(), status, next });
+        match next_url {
+            Some(next_url) => url = next_url,
+            None => return Ok(hops),
+        }
+    }
+}
+
+// The target of <meta http-equiv="refresh" content="0; url=...">, if the page has one
+fn meta_refresh(html: &str) -> Option<String> {
+    static META: OnceLock<Regex> = OnceLock::new();
+    static CONTENT: OnceLock<Regex> = OnceLock::new();
+    let meta = META.get_or_init(|| Regex::new(r#"(?is)<meta\b[^>]*http-equiv\s*=\s*["']?refresh["']?[^>]*>"#).unwrap());
+    let content = CONTENT.get_or_init(|| Regex::new(r#"(?is)content\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap());
+    let tag = meta.find(html)?.as_str();
+    let captures = content.captures(tag)?;
+    let value = captures.get(1).or_else(|| captures.get(2))?.as_str();
+    let (_, target) = value.split_once(';')?;
+    let target = target.trim();
+    let target = target.get(..4).filter(|prefix| prefix.eq_ignore_ascii_case("url=")).map(|_| &target[4..]).unwrap_or(target);
+    let target = target.trim().trim_matches(|c| c == '\'' || c == '"');
+    (!target.is_empty()).then(|| target.to_string())
+}
+
+fn is_tracking_param(name: &str) -> bool {
+    let name = name.to_lowercase();
+    TRACKING_PARAMS.iter().any(|param| if param.ends_with('_') { name.starts_with(param) && name.len() > param.len() } else { name == *param })
+}
+
+// The tracking parameters of a URL, and the URL without them
+fn strip_tracking(url: &Url) -> (Vec<(String, String)>, Url) {
+    let (tracking, kept): (Vec<_>, Vec<_>) = url.query_pairs().map(|(key, value)| (key.to_string(), value.to_string())).partition(|(key, _)| is_tracking_param(key));
+    let mut clean = url.clone();
+    if kept.is_empty() {
+        clean.set_query(None);
+    } else {
+        clean.query_pairs_mut().clear().extend_pairs(&kept);
+    }
+    (tracking, clean)
+}
+
+fn render(hops: &[Hop], color: bool) -> String {
+    let paint_status = |status: u16| {
+        let text = status.to_string();
+        match status {
+            _ if !color => text,
+            200..=299 => text.green().bold().to_string(),
+            300..=399 => text.yellow().bold().to_string(),
+            _ => text.red().bold().to_string(),
+        }
+    };
+    let mut output = String::new();
+    for (index, hop) in hops.iter().enumerate() {
+        let via = match &hop.next {
+            Some(_) if !(300..400).contains(&hop.status) => "  (meta refresh)",
+            _ => "",
+        };
+        output.push_str(&format!("{:>2}. {}  {}{}\n", index + 1, paint_status(hop.status), hop.url, via));
+    }
+    let Some(last) = hops.last() else {
+        return output;
+    };
+    let label = |text: &str| if color { text.cyan().bold().to_string() } else { text.to_string() };
+    output.push_str(&format!("\n{} {}\n", label("Final:"), last.url));
+    if let Ok(url) = Url::parse(&last.url) {
+        let (tracking, clean) = strip_tracking(&url);
+        if !tracking.is_empty() {
+            let params: Vec<String> = tracking.iter().map(|(key, value)| format!("{}={}", key, value)).collect();
+            output.push_str(&format!("{} {}\n", label("Tracking:"), params.join(", ")));
+            output.push_str(&format!("{} {}\n", label("Clean:"), clean));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meta_refresh() {
+        assert_eq!(meta_refresh(r#"<html><head><META HTTP-EQUIV="Refresh" CONTENT="0; URL=https://example.com/landing"></head>"#).as_deref(), Some("https://example.com/landing"));
+        assert_eq!(meta_refresh(r#"<meta content='5;url="/next"' http-equiv='refresh'>"#).as_deref(), Some("/next"));
+        assert_eq!(meta_refresh(r#"<meta http-equiv="refresh" content="30">"#), None);
+        assert_eq!(meta_refresh("<p>no refresh here</p>"), None);
+    }
+
+    #[test]
+    fn test_strip_tracking() {
+        let url = Url::parse("https://shop.example.com/item?id=42&utm_source=news&UTM_Campaign=spring&fbclid=abc").unwrap();
+        let (tracking, clean) = strip_tracking(&url);
+        assert_eq!(tracking.iter().map(|(key, _)| key.as_str()).collect::<Vec<_>>(), vec!["utm_source", "UTM_Campaign", "fbclid"]);
+        assert_eq!(clean.as_str(), "https://shop.example.com/item?id=42");
+        let (_, clean) = strip_tracking(&Url::parse("https://example.com/?gclid=x").unwrap());
+        assert_eq!(clean.as_str(), "https://example.com/");
+        assert!(!is_tracking_param("utm"));
+    }
+
+    #[test]
+    fn test_render() {
+        let hops = vec![
+            Hop { url: "https://bit.ly/abc".to_string(), status: 301, next: Some("https://example.com/a?utm_medium=social".to_string()) },
+            Hop { url: "https://example.com/a?utm_medium=social".to_string(), status: 200, next: None },
+        ];
+        assert_eq!(
+            render(&hops, false),
+            " 1. 301  https://bit.ly/abc\n 2. 200  https://example.com/a?utm_medium=social\n\nFinal: https://example.com/a?utm_medium=social\nTracking: utm_medium=social\nClean: https://example.com/a\n"
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use crate::tool_module::ToolModule;
-use clap::{Arg, ArgMatches, Command};
+use crate::punycode::{domain_to_ascii, domain_to_unicode};
+use clap::{Arg, ArgAction, ArgMatches, Command};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -20,12 +21,22 @@ impl ToolModule for UrlParseModule {
                 .help("Parse URL and output structured JSON with components")
                 .long_help("Parse a URL into its components (protocol, domain, path, query parameters) and output as prettified JSON. Query parameters are parsed into key-value pairs for easy access.")
         )
+        .arg(
+            Arg::new("idn")
+                .long("idn")
+                .help("With --parse-url, also show the domain in ASCII (xn--) and Unicode form")
+                .action(ArgAction::SetTrue)
+                .requires("parse-url")
+        )
     }
 
     fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
         if let Some(url) = matches.get_one::<String>("parse-url") {
             match parse_url(url) {
-                Ok(parsed) => {
+                Ok(mut parsed) => {
+                    if matches.get_flag("idn") && !parsed.domain.is_empty() {
+                        add_idn_forms(&mut parsed)?;
+                    }
                     let json = serde_json::to_string_pretty(&parsed)?;
                     println!("{}", json);
                 }
@@ -40,6 +51,10 @@ impl ToolModule for UrlParseModule {
 pub struct ParsedUrl {
     pub protocol: String,
     pub domain: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain_ascii: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain_unicode: Option<String>,
     pub path: String,
     pub query: HashMap<String, Value>,
 }
@@ -51,13 +66,14 @@ pub fn parse_url(url: &str) -> Result<ParsedUrl, String> {
     }
 
     // Special case: query-only input (starts with ?)
-    if url.starts_with('?') {
-        let query_string = &url[1..]; // Remove the leading '?'
+    if let Some(query_string) = url.strip_prefix('?') {
         let query = parse_query_parameters(query_string);
         
         return Ok(ParsedUrl {
             protocol: "".to_string(),
             domain: "".to_string(),
+            domain_ascii: None,
+            domain_unicode: None,
             path: "".to_string(),
             query,
         });
@@ -103,11 +119,28 @@ pub fn parse_url(url: &str) -> Result<ParsedUrl, String> {
     Ok(ParsedUrl {
         protocol,
         domain,
+        domain_ascii: None,
+        domain_unicode: None,
         path,
         query,
     })
 }
 
+// Both forms of the host, keeping any userinfo and port as they are
+fn add_idn_forms(parsed: &mut ParsedUrl) -> Result<(), String> {
+    let (userinfo, host) = match parsed.domain.rsplit_once('@') {
+        Some((userinfo, host)) => (format!("{}@", userinfo), host),
+        None => (String::new(), parsed.domain.as_str()),
+    };
+    let (host, port) = match host.rsplit_once(':') {
+        Some((host, port)) if port.bytes().all(|byte| byte.is_ascii_digit()) => (host, format!(":{}", port)),
+        _ => (host, String::new()),
+    };
+    parsed.domain_ascii = Some(format!("{}{}{}", userinfo, domain_to_ascii(host)?, port));
+    parsed.domain_unicode = Some(format!("{}{}{}", userinfo, domain_to_unicode(host)?, port));
+    Ok(())
+}
+
 fn is_likely_domain(input: &str) -> bool {
     // Consider it a domain if:
     // 1. Contains a dot (like api.example.com, localhost.local)
@@ -153,6 +186,8 @@ fn parse_path_only(input: &str) -> Result<ParsedUrl, String> {
     Ok(ParsedUrl {
         protocol: "".to_string(),
         domain: "".to_string(),
+        domain_ascii: None,
+        domain_unicode: None,
         path: normalized_path,
         query,
     })
@@ -496,4 +531,16 @@ mod tests {
         assert_eq!(parse_url("/api/users").unwrap().path, "/api/users");
         assert_eq!(parse_url("/vendor/category").unwrap().path, "/vendor/category");
     }
+
+    #[test]
+    fn test_idn_forms() {
+        let mut parsed = parse_url("https://user@münchen.de:8080/karte").unwrap();
+        add_idn_forms(&mut parsed).unwrap();
+        assert_eq!(parsed.domain_ascii.as_deref(), Some("user@xn--mnchen-3ya.de:8080"));
+        assert_eq!(parsed.domain_unicode.as_deref(), Some("user@münchen.de:8080"));
+
+        // Only serialized when requested
+        let json = serde_json::to_value(parse_url("https://example.com").unwrap()).unwrap();
+        assert!(json.get("domain_ascii").is_none());
+    }
 }
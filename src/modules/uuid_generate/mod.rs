@@ -1,11 +1,22 @@
 use crate::tool_module::ToolModule;
 use arboard::Clipboard;
-use clap::{Arg, ArgMatches, Command};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use rand::Rng;
+use sha3::{Digest, Sha3_512};
 use std::error::Error;
+use std::time::{SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
 pub struct UuidGenerateModule;
 
+const CROCKFORD_BASE32: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const BASE62: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+const BASE36: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+const BASE58: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE64URL: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+const NANOID_ALPHABET: &str = "useandom-26T198340PX75pxJACKVERYMINDBUSHWOLF_GQZbfghjklqvwyzrict";
+const KSUID_EPOCH: u64 = 1_400_000_000;
+
 impl ToolModule for UuidGenerateModule {
     fn name(&self) -> &'static str {
         "uuid-generate"
@@ -17,42 +28,466 @@ impl ToolModule for UuidGenerateModule {
                 .long("uuid-generate")
                 .value_name("VERSION")
                 .help("Generate UUID (v4 by default)")
-                .long_help("Generate UUID. Versions: v4 (random), v7 (timestamp). Result is automatically copied to clipboard.")
+                .long_help("Generate UUID. Versions: v4 (random), v7 (timestamp). Other ID schemes (ulid, nanoid, ksuid, cuid2) are accepted as well. Result is automatically copied to clipboard.")
                 .num_args(0..=1)
                 .default_missing_value("v4")
         )
+        .arg(
+            Arg::new("id-generate")
+                .long("id-generate")
+                .value_name("TYPE")
+                .help("Generate an ID (uuid, v7, ulid, nanoid, ksuid, cuid2)")
+                .long_help("Generate an identifier of the given type: uuid/v4 (random UUID), v7 (timestamp UUID), ulid (26-char sortable, use --monotonic for strictly increasing batches), nanoid (21-char URL-safe, see --alphabet and --id-length), ksuid (27-char sortable), cuid2 (24-char collision-resistant, see --id-length). Result is automatically copied to clipboard.")
+                .num_args(1)
+        )
+        .arg(
+            Arg::new("id-count")
+                .long("id-count")
+                .value_name("N")
+                .help("Number of IDs to generate (default: 1)")
+                .num_args(1)
+        )
+        .arg(
+            Arg::new("monotonic")
+                .long("monotonic")
+                .help("Generate monotonically increasing ULIDs within the same millisecond")
+                .action(ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("alphabet")
+                .long("alphabet")
+                .value_name("CHARS")
+                .help("Custom alphabet for NanoID generation, or for --hashid-encode and --hashid-decode")
+                .num_args(1)
+        )
+        .arg(
+            Arg::new("id-length")
+                .long("id-length")
+                .value_name("LENGTH")
+                .help("Length for NanoID (default: 21) or CUID2 (default: 24) generation")
+                .num_args(1)
+        )
+        .arg(
+            Arg::new("upper")
+                .long("upper")
+                .help("Output generated IDs, or --hex-encode digits, in uppercase")
+                .action(ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("no-dashes")
+                .long("no-dashes")
+                .help("Output UUIDs without dashes (32 hex characters)")
+                .action(ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("braces")
+                .long("braces")
+                .help("Wrap UUIDs in curly braces, e.g. {xxxxxxxx-...}")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("urn")
+        )
+        .arg(
+            Arg::new("urn")
+                .long("urn")
+                .help("Output UUIDs as URNs, e.g. urn:uuid:xxxxxxxx-...")
+                .action(ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("short")
+                .long("short")
+                .value_name("ENCODING")
+                .help("Output UUIDs in a short 22-char form (base64url by default, or base58)")
+                .long_help("Output UUIDs in a short form: base64url (22 chars, default) or base58 (22 chars, no look-alike characters). Use --uuid-expand to convert a short UUID back to its canonical form.")
+                .num_args(0..=1)
+                .default_missing_value("base64url")
+                .conflicts_with_all(["upper", "no-dashes", "braces", "urn"])
+        )
+        .arg(
+            Arg::new("uuid-expand")
+                .long("uuid-expand")
+                .value_names(["SHORT_UUID", "ENCODING"])
+                .num_args(1..=2)
+                .help("Convert a short UUID (base64url/base58) back to canonical form")
+                .long_help("Convert a short UUID produced by --short back to its canonical 36-character form. Encoding is base64url by default; pass base58 as the second argument for base58 input.")
+        )
     }
 
     fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
-        if matches.contains_id("uuid-generate") {
-            let version = matches.get_one::<String>("uuid-generate")
-                .map(|s| s.as_str()).unwrap_or("v4");
-            
-            let uuid = match version {
-                "v4" => Uuid::new_v4().to_string(),
-                "v7" => Uuid::now_v7().to_string(),
-                _ => return Err("Unsupported UUID version. Use v4 or v7".into()),
-            };
-            
-            match Clipboard::new() {
-                Ok(mut clipboard) => {
-                    if let Err(e) = clipboard.set_text(&uuid) {
-                        eprintln!("Warning: Failed to copy to clipboard: {}", e);
-                        println!("{}", uuid);
-                    } else {
-                        println!("{} (copied to clipboard)", uuid);
-                    }
-                }
-                Err(e) => {
-                    eprintln!("Warning: Failed to access clipboard: {}", e);
-                    println!("{}", uuid);
+        if let Some(values) = matches.get_many::<String>("uuid-expand") {
+            let values: Vec<&String> = values.collect();
+            let encoding = ShortEncoding::parse(values.get(1).map(|s| s.as_str()).unwrap_or("base64url"))?;
+            println!("{}", expand_short_uuid(values[0], encoding)?);
+            return Ok(());
+        }
+
+        let kind = if let Some(kind) = matches.get_one::<String>("id-generate") {
+            kind.as_str()
+        } else if matches.contains_id("uuid-generate") {
+            matches.get_one::<String>("uuid-generate")
+                .map(|s| s.as_str()).unwrap_or("v4")
+        } else {
+            return Ok(());
+        };
+
+        let count: usize = match matches.get_one::<String>("id-count") {
+            Some(value) => value.parse().map_err(|_| "ID count must be a positive number")?,
+            None => 1,
+        };
+        if count == 0 || count > 10000 {
+            return Err("ID count must be between 1 and 10000".into());
+        }
+
+        let length = match matches.get_one::<String>("id-length") {
+            Some(value) => Some(value.parse::<usize>().map_err(|_| "ID length must be a positive number")?),
+            None => None,
+        };
+
+        let short = match matches.get_one::<String>("short") {
+            Some(value) => Some(ShortEncoding::parse(value)?),
+            None => None,
+        };
+
+        let options = IdOptions {
+            monotonic: matches.get_flag("monotonic"),
+            alphabet: matches.get_one::<String>("alphabet").cloned(),
+            length,
+            format: UuidFormat {
+                upper: matches.get_flag("upper"),
+                no_dashes: matches.get_flag("no-dashes"),
+                braces: matches.get_flag("braces"),
+                urn: matches.get_flag("urn"),
+                short,
+            },
+        };
+
+        let ids = generate_ids(kind, count, &options)?;
+        let output = ids.join("\n");
+
+        match Clipboard::new() {
+            Ok(mut clipboard) => {
+                if let Err(e) = clipboard.set_text(&output) {
+                    eprintln!("Warning: Failed to copy to clipboard: {}", e);
+                    println!("{}", output);
+                } else {
+                    println!("{} (copied to clipboard)", output);
                 }
             }
+            Err(e) => {
+                eprintln!("Warning: Failed to access clipboard: {}", e);
+                println!("{}", output);
+            }
         }
         Ok(())
     }
 }
 
+#[derive(Debug, Default)]
+pub struct IdOptions {
+    pub monotonic: bool,
+    pub alphabet: Option<String>,
+    pub length: Option<usize>,
+    pub format: UuidFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShortEncoding {
+    Base64Url,
+    Base58,
+}
+
+impl ShortEncoding {
+    pub fn parse(value: &str) -> Result<Self, Box<dyn Error>> {
+        match value.to_lowercase().as_str() {
+            "base64url" | "base64" | "b64" => Ok(ShortEncoding::Base64Url),
+            "base58" | "b58" => Ok(ShortEncoding::Base58),
+            _ => Err("Unsupported short encoding. Use base64url or base58".into()),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct UuidFormat {
+    pub upper: bool,
+    pub no_dashes: bool,
+    pub braces: bool,
+    pub urn: bool,
+    pub short: Option<ShortEncoding>,
+}
+
+impl UuidFormat {
+    fn is_uuid_specific(&self) -> bool {
+        self.no_dashes || self.braces || self.urn || self.short.is_some()
+    }
+}
+
+pub fn format_uuid(uuid: &Uuid, format: &UuidFormat) -> String {
+    match format.short {
+        Some(ShortEncoding::Base64Url) => return encode_base64url(uuid.as_bytes()),
+        Some(ShortEncoding::Base58) => return format!("{:1>22}", encode_base(uuid.as_bytes(), BASE58)),
+        None => {}
+    }
+
+    let mut result = if format.no_dashes {
+        uuid.simple().to_string()
+    } else {
+        uuid.hyphenated().to_string()
+    };
+    if format.upper {
+        result = result.to_uppercase();
+    }
+    if format.braces {
+        result = format!("{{{}}}", result);
+    } else if format.urn {
+        result = format!("urn:uuid:{}", result);
+    }
+    result
+}
+
+pub fn expand_short_uuid(short: &str, encoding: ShortEncoding) -> Result<String, Box<dyn Error>> {
+    let short = short.trim();
+    let bytes = match encoding {
+        ShortEncoding::Base64Url => decode_base64url(short)?,
+        ShortEncoding::Base58 => decode_base(short, BASE58, 16)?,
+    };
+    let bytes: [u8; 16] = bytes.try_into().map_err(|_| "Short UUID must decode to exactly 16 bytes")?;
+    Ok(Uuid::from_bytes(bytes).to_string())
+}
+
+pub fn generate_ids(kind: &str, count: usize, options: &IdOptions) -> Result<Vec<String>, Box<dyn Error>> {
+    let kind = kind.to_lowercase();
+    let is_uuid = matches!(kind.as_str(), "uuid" | "v4" | "v7");
+    if !is_uuid && options.format.is_uuid_specific() {
+        return Err("--no-dashes, --braces, --urn and --short only apply to UUIDs".into());
+    }
+
+    let ids = generate_raw_ids(&kind, count, options)?;
+    if options.format.upper && !is_uuid {
+        return Ok(ids.into_iter().map(|id| id.to_uppercase()).collect());
+    }
+    Ok(ids)
+}
+
+fn generate_raw_ids(kind: &str, count: usize, options: &IdOptions) -> Result<Vec<String>, Box<dyn Error>> {
+    match kind {
+        "uuid" | "v4" => Ok((0..count).map(|_| format_uuid(&Uuid::new_v4(), &options.format)).collect()),
+        "v7" => Ok((0..count).map(|_| format_uuid(&Uuid::now_v7(), &options.format)).collect()),
+        "ulid" => {
+            let mut generator = UlidGenerator::new(options.monotonic);
+            (0..count).map(|_| generator.generate(now_millis())).collect()
+        }
+        "nanoid" => {
+            let alphabet = options.alphabet.as_deref().unwrap_or(NANOID_ALPHABET);
+            let length = options.length.unwrap_or(21);
+            (0..count).map(|_| generate_nanoid(alphabet, length)).collect()
+        }
+        "ksuid" => Ok((0..count).map(|_| generate_ksuid(now_secs())).collect()),
+        "cuid2" => {
+            let length = options.length.unwrap_or(24);
+            let mut generator = Cuid2Generator::new();
+            (0..count).map(|_| generator.generate(length)).collect()
+        }
+        _ => Err("Unsupported ID type. Use v4, v7, ulid, nanoid, ksuid, or cuid2".into()),
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+pub struct UlidGenerator {
+    monotonic: bool,
+    last: Option<(u64, u128)>,
+}
+
+impl UlidGenerator {
+    pub fn new(monotonic: bool) -> Self {
+        Self { monotonic, last: None }
+    }
+
+    pub fn generate(&mut self, timestamp_ms: u64) -> Result<String, Box<dyn Error>> {
+        if timestamp_ms >= 1 << 48 {
+            return Err("Timestamp is too large for a ULID".into());
+        }
+
+        let random = match self.last {
+            // Within the same millisecond, increment the previous random component
+            Some((last_ts, last_random)) if self.monotonic && last_ts == timestamp_ms => {
+                let next = last_random + 1;
+                if next >= 1 << 80 {
+                    return Err("ULID random component overflowed within the same millisecond".into());
+                }
+                next
+            }
+            _ => rand::thread_rng().gen::<u128>() & ((1 << 80) - 1),
+        };
+        self.last = Some((timestamp_ms, random));
+
+        Ok(encode_ulid(timestamp_ms, random))
+    }
+}
+
+fn encode_ulid(timestamp_ms: u64, random: u128) -> String {
+    let value = ((timestamp_ms as u128) << 80) | random;
+    (0..26)
+        .rev()
+        .map(|i| CROCKFORD_BASE32[((value >> (i * 5)) & 0x1f) as usize] as char)
+        .collect()
+}
+
+pub fn generate_nanoid(alphabet: &str, length: usize) -> Result<String, Box<dyn Error>> {
+    let chars: Vec<char> = alphabet.chars().collect();
+    if chars.len() < 2 || chars.len() > 255 {
+        return Err("NanoID alphabet must contain between 2 and 255 characters".into());
+    }
+    if length == 0 || length > 1000 {
+        return Err("NanoID length must be between 1 and 1000".into());
+    }
+
+    let mut rng = rand::thread_rng();
+    Ok((0..length).map(|_| chars[rng.gen_range(0..chars.len())]).collect())
+}
+
+pub fn generate_ksuid(unix_secs: u64) -> String {
+    let mut bytes = [0u8; 20];
+    let timestamp = unix_secs.saturating_sub(KSUID_EPOCH) as u32;
+    bytes[..4].copy_from_slice(&timestamp.to_be_bytes());
+    rand::thread_rng().fill(&mut bytes[4..]);
+    encode_ksuid(&bytes)
+}
+
+fn encode_ksuid(bytes: &[u8; 20]) -> String {
+    let encoded = encode_base(bytes, BASE62);
+    format!("{:0>27}", encoded)
+}
+
+// Converts a big-endian byte string to the given alphabet by repeated division
+fn encode_base(bytes: &[u8], alphabet: &[u8]) -> String {
+    let base = alphabet.len() as u32;
+    let mut number: Vec<u8> = bytes.iter().skip_while(|&&b| b == 0).copied().collect();
+    let mut digits = Vec::new();
+
+    while !number.is_empty() {
+        let mut quotient = Vec::with_capacity(number.len());
+        let mut remainder = 0u32;
+        for &byte in &number {
+            let accumulator = (remainder << 8) | byte as u32;
+            let digit = accumulator / base;
+            remainder = accumulator % base;
+            if !quotient.is_empty() || digit != 0 {
+                quotient.push(digit as u8);
+            }
+        }
+        digits.push(alphabet[remainder as usize]);
+        number = quotient;
+    }
+
+    if digits.is_empty() {
+        digits.push(alphabet[0]);
+    }
+    digits.iter().rev().map(|&b| b as char).collect()
+}
+
+fn encode_base64url(bytes: &[u8]) -> String {
+    let mut result = String::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 6 {
+            bits -= 6;
+            result.push(BASE64URL[((buffer >> bits) & 0x3f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        result.push(BASE64URL[((buffer << (6 - bits)) & 0x3f) as usize] as char);
+    }
+    result
+}
+
+fn decode_base64url(input: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut result = Vec::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in input.trim_end_matches('=').chars() {
+        let value = BASE64URL.iter().position(|&b| b as char == c)
+            .ok_or_else(|| format!("Invalid character '{}' in base64url string", c))?;
+        buffer = (buffer << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            result.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(result)
+}
+
+// Inverse of encode_base, producing exactly `size` big-endian bytes
+fn decode_base(input: &str, alphabet: &[u8], size: usize) -> Result<Vec<u8>, Box<dyn Error>> {
+    let base = alphabet.len() as u32;
+    let mut bytes = vec![0u8; size];
+    for c in input.chars() {
+        let mut carry = alphabet.iter().position(|&b| b as char == c)
+            .ok_or_else(|| format!("Invalid character '{}' in encoded string", c))? as u32;
+        for byte in bytes.iter_mut().rev() {
+            let value = *byte as u32 * base + carry;
+            *byte = (value & 0xff) as u8;
+            carry = value >> 8;
+        }
+        if carry != 0 {
+            return Err("Encoded value is too large".into());
+        }
+    }
+    Ok(bytes)
+}
+
+struct Cuid2Generator {
+    counter: u64,
+    fingerprint: String,
+}
+
+impl Cuid2Generator {
+    fn new() -> Self {
+        let mut rng = rand::thread_rng();
+        let host = std::env::var("HOSTNAME").unwrap_or_default();
+        let source = format!("{}{}{}", std::process::id(), host, random_base36(32));
+        Self {
+            counter: rng.gen_range(0..476_782_367),
+            fingerprint: cuid2_hash(&source),
+        }
+    }
+
+    fn generate(&mut self, length: usize) -> Result<String, Box<dyn Error>> {
+        if !(2..=32).contains(&length) {
+            return Err("CUID2 length must be between 2 and 32".into());
+        }
+
+        let first_letter = BASE36[rand::thread_rng().gen_range(10..36)] as char;
+        let time = encode_base(&(now_millis() as u128).to_be_bytes(), BASE36);
+        let count = encode_base(&(self.counter as u128).to_be_bytes(), BASE36);
+        self.counter += 1;
+
+        let input = format!("{}{}{}{}", time, random_base36(length), count, self.fingerprint);
+        let hash = cuid2_hash(&input);
+        Ok(format!("{}{}", first_letter, &hash[1..length]))
+    }
+}
+
+fn cuid2_hash(input: &str) -> String {
+    let digest = Sha3_512::digest(input.as_bytes());
+    // Drop the first character, which is biased by the hash's leading bits
+    encode_base(&digest, BASE36)[1..].to_string()
+}
+
+fn random_base36(length: usize) -> String {
+    let mut rng = rand::thread_rng();
+    (0..length).map(|_| BASE36[rng.gen_range(0..36)] as char).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -77,4 +512,152 @@ mod tests {
         let uuid2 = Uuid::new_v4().to_string();
         assert_ne!(uuid1, uuid2);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_ulid_format() {
+        let mut generator = UlidGenerator::new(false);
+        let ulid = generator.generate(now_millis()).unwrap();
+        assert_eq!(ulid.len(), 26);
+        assert!(ulid.bytes().all(|b| CROCKFORD_BASE32.contains(&b)));
+    }
+
+    #[test]
+    fn test_ulid_timestamp_encoding() {
+        assert_eq!(encode_ulid(0, 0), "00000000000000000000000000");
+        assert_eq!(&encode_ulid(1_469_918_176_385, 0)[..10], "01ARYZ6S41");
+    }
+
+    #[test]
+    fn test_ulid_monotonic() {
+        let mut generator = UlidGenerator::new(true);
+        let first = generator.generate(1_700_000_000_000).unwrap();
+        let second = generator.generate(1_700_000_000_000).unwrap();
+        assert_eq!(first[..10], second[..10]);
+        assert!(second > first);
+    }
+
+    #[test]
+    fn test_ulid_rejects_large_timestamp() {
+        let mut generator = UlidGenerator::new(false);
+        assert!(generator.generate(1 << 48).is_err());
+    }
+
+    #[test]
+    fn test_nanoid_default() {
+        let id = generate_nanoid(NANOID_ALPHABET, 21).unwrap();
+        assert_eq!(id.len(), 21);
+        assert!(id.chars().all(|c| NANOID_ALPHABET.contains(c)));
+    }
+
+    #[test]
+    fn test_nanoid_custom_alphabet() {
+        let id = generate_nanoid("abc", 50).unwrap();
+        assert_eq!(id.len(), 50);
+        assert!(id.chars().all(|c| "abc".contains(c)));
+    }
+
+    #[test]
+    fn test_nanoid_invalid_options() {
+        assert!(generate_nanoid("a", 10).is_err());
+        assert!(generate_nanoid(NANOID_ALPHABET, 0).is_err());
+    }
+
+    #[test]
+    fn test_ksuid_format() {
+        let ksuid = generate_ksuid(now_secs());
+        assert_eq!(ksuid.len(), 27);
+        assert!(ksuid.bytes().all(|b| BASE62.contains(&b)));
+    }
+
+    #[test]
+    fn test_ksuid_encoding() {
+        assert_eq!(encode_ksuid(&[0u8; 20]), "000000000000000000000000000");
+        assert_eq!(encode_ksuid(&[0xff; 20]), "aWgEPTl1tmebfsQzFP4bxwgy80V");
+    }
+
+    #[test]
+    fn test_encode_base() {
+        assert_eq!(encode_base(&[0], BASE36), "0");
+        assert_eq!(encode_base(&[1, 0], BASE36), "74");
+        assert_eq!(encode_base(&[0, 0, 255], BASE62), "47");
+    }
+
+    #[test]
+    fn test_cuid2_format() {
+        let mut generator = Cuid2Generator::new();
+        let id = generator.generate(24).unwrap();
+        assert_eq!(id.len(), 24);
+        assert!(id.chars().next().unwrap().is_ascii_lowercase());
+        assert!(id.bytes().all(|b| BASE36.contains(&b)));
+        assert_ne!(id, generator.generate(24).unwrap());
+    }
+
+    #[test]
+    fn test_cuid2_length_bounds() {
+        let mut generator = Cuid2Generator::new();
+        assert_eq!(generator.generate(10).unwrap().len(), 10);
+        assert!(generator.generate(1).is_err());
+        assert!(generator.generate(33).is_err());
+    }
+
+    #[test]
+    fn test_format_uuid_variants() {
+        let uuid = Uuid::parse_str("0190a5b8-7c4e-7d3a-9f21-3b6c2d8e4f10").unwrap();
+        let format = |f: UuidFormat| format_uuid(&uuid, &f);
+        assert_eq!(format(UuidFormat::default()), "0190a5b8-7c4e-7d3a-9f21-3b6c2d8e4f10");
+        assert_eq!(format(UuidFormat { upper: true, ..Default::default() }), "0190A5B8-7C4E-7D3A-9F21-3B6C2D8E4F10");
+        assert_eq!(format(UuidFormat { no_dashes: true, ..Default::default() }), "0190a5b87c4e7d3a9f213b6c2d8e4f10");
+        assert_eq!(format(UuidFormat { braces: true, ..Default::default() }), "{0190a5b8-7c4e-7d3a-9f21-3b6c2d8e4f10}");
+        assert_eq!(format(UuidFormat { urn: true, ..Default::default() }), "urn:uuid:0190a5b8-7c4e-7d3a-9f21-3b6c2d8e4f10");
+    }
+
+    #[test]
+    fn test_short_uuid_base64url() {
+        let uuid = Uuid::nil();
+        let short = format_uuid(&uuid, &UuidFormat { short: Some(ShortEncoding::Base64Url), ..Default::default() });
+        assert_eq!(short, "AAAAAAAAAAAAAAAAAAAAAA");
+
+        let max = Uuid::max();
+        let short = format_uuid(&max, &UuidFormat { short: Some(ShortEncoding::Base64Url), ..Default::default() });
+        assert_eq!(short, "_____________________w");
+    }
+
+    #[test]
+    fn test_short_uuid_round_trip() {
+        for encoding in [ShortEncoding::Base64Url, ShortEncoding::Base58] {
+            for _ in 0..20 {
+                let uuid = Uuid::new_v4();
+                let short = format_uuid(&uuid, &UuidFormat { short: Some(encoding), ..Default::default() });
+                assert_eq!(short.len(), 22);
+                assert_eq!(expand_short_uuid(&short, encoding).unwrap(), uuid.to_string());
+            }
+        }
+    }
+
+    #[test]
+    fn test_expand_short_uuid_invalid() {
+        assert!(expand_short_uuid("not valid!", ShortEncoding::Base64Url).is_err());
+        assert!(expand_short_uuid("AAAA", ShortEncoding::Base64Url).is_err());
+        assert!(expand_short_uuid("0OIl", ShortEncoding::Base58).is_err());
+        assert!(expand_short_uuid("zzzzzzzzzzzzzzzzzzzzzzzz", ShortEncoding::Base58).is_err());
+    }
+
+    #[test]
+    fn test_format_options_for_non_uuid() {
+        let options = IdOptions { format: UuidFormat { upper: true, ..Default::default() }, ..Default::default() };
+        let nanoid = generate_ids("nanoid", 1, &IdOptions { alphabet: Some("abc".to_string()), ..options }).unwrap();
+        assert!(nanoid[0].chars().all(|c| "ABC".contains(c)));
+
+        let options = IdOptions { format: UuidFormat { braces: true, ..Default::default() }, ..Default::default() };
+        assert!(generate_ids("ulid", 1, &options).is_err());
+    }
+
+    #[test]
+    fn test_generate_ids_kinds() {
+        let options = IdOptions::default();
+        assert_eq!(generate_ids("v4", 3, &options).unwrap().len(), 3);
+        assert_eq!(generate_ids("ULID", 1, &options).unwrap()[0].len(), 26);
+        assert_eq!(generate_ids("ksuid", 1, &options).unwrap()[0].len(), 27);
+        assert!(generate_ids("v1", 1, &options).is_err());
+    }
+}
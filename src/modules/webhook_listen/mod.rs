@@ -0,0 +1,280 @@
+use crate::json_format::format_json_pretty;
+use crate::tool_module::ToolModule;
+use chrono::Local;
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use std::error::Error;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::Duration;
+use url::form_urlencoded;
+
+pub struct WebhookListenModule;
+
+// Refuse bodies larger than this instead of buffering them
+const MAX_BODY_BYTES: usize = 10 * 1024 * 1024;
+
+impl ToolModule for WebhookListenModule {
+    fn name(&self) -> &'static str {
+        "webhook-listen"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("webhook-listen")
+                .long("webhook-listen")
+                .value_name("PORT")
+                .num_args(0..=1)
+                .default_missing_value("8080")
+                .value_parser(clap::value_parser!(u16))
+                .help("Listen on localhost:PORT (default 8080) and print every request received")
+                .long_help("Start a local request bin on 127.0.0.1:PORT (default 8080). Every incoming request is printed with its method, path, query, headers and body, with JSON bodies pretty-printed, and answered with --webhook-status and --webhook-body. Point a tunnel such as ngrok at the port to inspect webhooks from external services. Press Ctrl+C to stop.")
+        )
+        .arg(
+            Arg::new("webhook-status")
+                .long("webhook-status")
+                .value_name("CODE")
+                .help("Status code returned by --webhook-listen (default: 200)")
+                .value_parser(clap::value_parser!(u16).range(100..=599))
+                .default_value("200")
+                .requires("webhook-listen")
+        )
+        .arg(
+            Arg::new("webhook-body")
+                .long("webhook-body")
+                .value_name("BODY")
+                .help("Response body returned by --webhook-listen (default: {\"ok\":true})")
+                .default_value("{\"ok\":true}")
+                .requires("webhook-listen")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(port) = matches.get_one::<u16>("webhook-listen") {
+            let status = matches.get_one::<u16>("webhook-status").copied().unwrap_or(200);
+            let body = matches.get_one::<String>("webhook-body").map(String::as_str).unwrap_or("");
+            let listener = TcpListener::bind(("127.0.0.1", *port)).map_err(|e| format!("Failed to listen on port {}: {}", port, e))?;
+            println!("✅ Listening on {} (Ctrl+C to stop)", format!("http://127.0.0.1:{}", port).bold());
+            println!("   Responding with {} and {}", status, if body.is_empty() { "an empty body" } else { body });
+            let response = build_response(status, body);
+            let mut count = 0;
+            for stream in listener.incoming() {
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(e) => {
+                        eprintln!("⚠️  Connection failed: {}", e);
+                        continue;
+                    }
+                };
+                count += 1;
+                if let Err(e) = handle_connection(stream, count, &response) {
+                    eprintln!("⚠️  Request #{}: {}", count, e);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct Request {
+    pub method: String,
+    pub target: String,
+    pub version: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, value)| value.as_str())
+    }
+}
+
+fn handle_connection(mut stream: TcpStream, number: usize, response: &[u8]) -> Result<(), String> {
+    stream.set_read_timeout(Some(Duration::from_secs(10))).map_err(|e| e.to_string())?;
+    let peer = stream.peer_addr().map(|addr| addr.to_string()).unwrap_or_else(|_| "unknown".to_string());
+    let request = read_request(&mut BufReader::new(&stream))?;
+    print!("\n{}", render_request(&request, number, &peer));
+    stream.write_all(response).map_err(|e| format!("Failed to send response: {}", e))
+}
+
+pub fn read_request<R: BufRead>(reader: &mut R) -> Result<Request, String> {
+    let request_line = read_line(reader)?;
+    let mut parts = request_line.split_whitespace();
+    let (method, target, version) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(method), Some(target), Some(version)) => (method.to_string(), target.to_string(), version.to_string()),
+        _ => return Err(format!("Malformed request line '{}'", request_line)),
+    };
+
+    let mut headers = Vec::new();
+    loop {
+        let line = read_line(reader)?;
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line.split_once(':').ok_or_else(|| format!("Malformed header '{}'", line))?;
+        headers.push((name.trim().to_string(), value.trim().to_string()));
+    }
+
+    let mut request = Request { method, target, version, headers, body: Vec::new() };
+    if request.header("Transfer-Encoding").is_some_and(|value| value.eq_ignore_ascii_case("chunked")) {
+        request.body = read_chunked(reader)?;
+    } else if let Some(length) = request.header("Content-Length") {
+        let length: usize = length.parse().map_err(|_| format!("Invalid Content-Length '{}'", length))?;
+        if length > MAX_BODY_BYTES {
+            return Err(format!("Body of {} bytes is larger than the {} byte limit", length, MAX_BODY_BYTES));
+        }
+        request.body = vec![0; length];
+        reader.read_exact(&mut request.body).map_err(|e| format!("Failed to read body: {}", e))?;
+    }
+    Ok(request)
+}
+
+// One CRLF-terminated line, without the line ending
+fn read_line<R: BufRead>(reader: &mut R) -> Result<String, String> {
+    let mut line = String::new();
+    match reader.read_line(&mut line) {
+        Ok(0) => Err("Connection closed before the request was complete".to_string()),
+        Ok(_) => Ok(line.trim_end_matches(['\r', '\n']).to_string()),
+        Err(e) => Err(format!("Failed to read request: {}", e)),
+    }
+}
+
+// Hex chunk size, chunk data, CRLF, ... until a zero-size chunk and optional trailers
+fn read_chunked<R: BufRead>(reader: &mut R) -> Result<Vec<u8>, String> {
+    let mut body = Vec::new();
+    loop {
+        let size_line = read_line(reader)?;
+        let size_hex = size_line.split(';').next().unwrap_or("").trim();
+        let size = usize::from_str_radix(size_hex, 16).map_err(|_| format!("Invalid chunk size '{}'", size_line))?;
+        if size == 0 {
+            while !read_line(reader)?.is_empty() {}
+            return Ok(body);
+        }
+        if body.len() + size > MAX_BODY_BYTES {
+            return Err(format!("Body is larger than the {} byte limit", MAX_BODY_BYTES));
+        }
+        let start = body.len();
+        body.resize(start + size, 0);
+        reader.read_exact(&mut body[start..]).map_err(|e| format!("Failed to read body: {}", e))?;
+        read_line(reader)?;
+    }
+}
+
+fn render_request(request: &Request, number: usize, peer: &str) -> String {
+    let mut output = format!(
+        "{} {} {} {}\n",
+        format!("#{}", number).dimmed(),
+        request.method.bold().green(),
+        request.target.bold(),
+        format!("{} from {} at {}", request.version, peer, Local::now().format("%H:%M:%S")).dimmed()
+    );
+    if let Some((_, query)) = request.target.split_once('?') {
+        output.push_str(&format!("{}\n", "Query:".bold()));
+        for (key, value) in form_urlencoded::parse(query.as_bytes()) {
+            output.push_str(&format!("  {} = {}\n", key.cyan(), value));
+        }
+    }
+    output.push_str(&format!("{}\n", "Headers:".bold()));
+    for (name, value) in &request.headers {
+        output.push_str(&format!("  {}: {}\n", name.cyan(), value));
+    }
+    if !request.body.is_empty() {
+        output.push_str(&format!("{}\n{}\n", format!("Body ({} bytes):", request.body.len()).bold(), format_body(&request.body, request.header("Content-Type"))));
+    }
+    output
+}
+
+fn format_body(body: &[u8], content_type: Option<&str>) -> String {
+    let Ok(text) = std::str::from_utf8(body) else {
+        return format!("<{} bytes of binary data>", body.len());
+    };
+    let content_type = content_type.unwrap_or("").to_lowercase();
+    if content_type.contains("json") || matches!(text.trim_start().chars().next(), Some('{') | Some('[')) {
+        if let Ok(pretty) = format_json_pretty(text) {
+            return pretty;
+        }
+    }
+    if content_type.starts_with("application/x-www-form-urlencoded") {
+        return form_urlencoded::parse(body)
+            .map(|(key, value)| format!("{} = {}", key, value))
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+    text.to_string()
+}
+
+fn build_response(status: u16, body: &str) -> Vec<u8> {
+    let content_type = if serde_json::from_str::<serde_json::Value>(body).is_ok() { "application/json" } else { "text/plain; charset=utf-8" };
+    format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason_phrase(status),
+        content_type,
+        body.len(),
+        body
+    )
+    .into_bytes()
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        202 => "Accepted",
+        204 => "No Content",
+        301 => "Moved Permanently",
+        302 => "Found",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        409 => "Conflict",
+        422 => "Unprocessable Entity",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_request_with_content_length() {
+        let raw = "POST /hooks/github?ref=main HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: 13\r\n\r\n{\"action\":1}\n";
+        let request = read_request(&mut raw.as_bytes()).unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.target, "/hooks/github?ref=main");
+        assert_eq!(request.header("content-type"), Some("application/json"));
+        assert_eq!(request.body, b"{\"action\":1}\n");
+    }
+
+    #[test]
+    fn test_read_chunked_request() {
+        let raw = "POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n6;ext=1\r\n world\r\n0\r\n\r\n";
+        assert_eq!(read_request(&mut raw.as_bytes()).unwrap().body, b"hello world");
+        assert!(read_request(&mut "GET /\r\n\r\n".as_bytes()).is_err());
+        assert!(read_request(&mut "GET / HTTP/1.1\r\nHost: x\r\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_format_body() {
+        assert_eq!(format_body(br#"{"a":[1]}"#, Some("application/json")), "{\n  \"a\": [\n    1\n  ]\n}");
+        assert_eq!(format_body(b"name=Ada+L&tag=%23rust", Some("application/x-www-form-urlencoded")), "name = Ada L\ntag = #rust");
+        assert_eq!(format_body(b"plain text", None), "plain text");
+        assert_eq!(format_body(&[0xff, 0xfe], None), "<2 bytes of binary data>");
+    }
+
+    #[test]
+    fn test_build_response() {
+        let response = String::from_utf8(build_response(201, "{\"ok\":true}")).unwrap();
+        assert!(response.starts_with("HTTP/1.1 201 Created\r\nContent-Type: application/json\r\nContent-Length: 11\r\n"));
+        assert!(response.ends_with("\r\n\r\n{\"ok\":true}"));
+        let text = String::from_utf8(build_response(418, "teapot")).unwrap();
+        assert!(text.contains("Content-Type: text/plain; charset=utf-8"));
+    }
+}
@@ -0,0 +1,263 @@
+use crate::tool_module::ToolModule;
+use crate::unicode_text::special_character_name;
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::io::{self, IsTerminal, Read};
+
+pub struct WhitespaceToolsModule;
+
+// Used when a file has no space indentation to measure
+const DEFAULT_WIDTH: usize = 4;
+// Tab stops when drawing tabs in --show-whitespace
+const TAB_STOP: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Indent {
+    Spaces(usize),
+    Tabs,
+}
+
+impl Indent {
+    fn describe(&self) -> String {
+        match self {
+            Indent::Spaces(width) => format!("{} spaces", width),
+            Indent::Tabs => "tabs".to_string(),
+        }
+    }
+}
+
+impl ToolModule for WhitespaceToolsModule {
+    fn name(&self) -> &'static str {
+        "whitespace-tools"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("indent-convert")
+                .long("indent-convert")
+                .value_name("FILE")
+                .help("Re-indent a file with --to spaces:N or --to tabs (- for stdin)")
+                .long_help("Rewrite the leading whitespace of every line as tabs or N spaces per level, chosen with --to spaces:2, spaces:4 or tabs. The current indent width is detected from the file (the most common step between indented lines, 4 when there is none); spaces left over after whole levels are kept for alignment, and blank lines are left alone. Prints the result unless --in-place is given. Use - to read stdin.")
+                .requires("to")
+        )
+        .arg(
+            Arg::new("show-whitespace")
+                .long("show-whitespace")
+                .value_name("FILE")
+                .num_args(0..=1)
+                .default_missing_value("-")
+                .help("Show tabs, trailing spaces, non-breaking spaces and zero-width characters visibly (stdin without FILE)")
+                .long_help("Print FILE with invisible characters drawn: tabs as →, trailing spaces as ·, non-breaking spaces as ⍽, other unusual spaces as ␣, carriage returns as ␍, and zero-width, bidi and control characters as <U+200B>. The line and column of each trailing whitespace run, unusual character and indentation mixing tabs with spaces are listed on stderr, for hunting down what breaks YAML files and diffs. Reads stdin without FILE.")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(file) = matches.get_one::<String>("indent-convert") {
+            // --to is declared by the env-convert module, --in-place by the line-endings module
+            let target = parse_target(matches.get_one::<String>("to").map(String::as_str).unwrap_or_default())?;
+            let text = read_text(file)?;
+            let width = detect_width(&text).unwrap_or(match target {
+                Indent::Spaces(width) => width,
+                Indent::Tabs => DEFAULT_WIDTH,
+            });
+            let (converted, changed) = reindent(&text, width, target);
+            if !matches.get_flag("in-place") {
+                print!("{}", converted);
+            } else if file == "-" {
+                return Err("--in-place needs a file, not stdin".into());
+            } else if changed > 0 {
+                fs::write(file, &converted).map_err(|e| format!("Failed to write '{}': {}", file, e))?;
+                println!("✅ Re-indented {} line{} in {} ({} → {})", changed, if changed == 1 { "" } else { "s" }, file, Indent::Spaces(width).describe(), target.describe());
+            } else {
+                println!("✅ {} is already indented with {}", file, target.describe());
+            }
+        }
+
+        if let Some(file) = matches.get_one::<String>("show-whitespace") {
+            let text = read_text(file)?;
+            print!("{}", render(&text, io::stdout().is_terminal()));
+            // On stderr, so the drawn text can be piped on its own
+            let findings = findings(&text);
+            if findings.is_empty() {
+                eprintln!("✅ No trailing whitespace, unusual spaces or invisible characters");
+            } else {
+                eprintln!("⚠️ {} finding{}", findings.len(), if findings.len() == 1 { "" } else { "s" });
+                for (line, column, description) in findings {
+                    eprintln!("  {:<8}{}", format!("{}:{}", line, column), description);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn read_text(file: &str) -> Result<String, Box<dyn Error>> {
+    if file == "-" {
+        let mut buffer = String::new();
+        io::stdin().read_to_string(&mut buffer)?;
+        return Ok(buffer);
+    }
+    Ok(fs::read_to_string(file).map_err(|e| format!("Failed to read '{}': {}", file, e))?)
+}
+
+// "spaces:2", "spaces" (4) or "tabs"
+fn parse_target(target: &str) -> Result<Indent, String> {
+    let invalid = || format!("Unknown indentation '{}'. Use spaces:N (1-16) or tabs", target);
+    match target.to_lowercase().as_str() {
+        "tabs" | "tab" => Ok(Indent::Tabs),
+        "spaces" => Ok(Indent::Spaces(DEFAULT_WIDTH)),
+        other => {
+            let width: usize = other.strip_prefix("spaces:").ok_or_else(invalid)?.parse().map_err(|_| invalid())?;
+            if !(1..=16).contains(&width) {
+                return Err(invalid());
+            }
+            Ok(Indent::Spaces(width))
+        }
+    }
+}
+
+// The most common increase in indentation between space-indented lines (the
+// smaller one on a tie), or None when no line is indented with spaces
+fn detect_width(text: &str) -> Option<usize> {
+    let mut steps: HashMap<usize, usize> = HashMap::new();
+    let mut previous = 0;
+    for line in text.lines().filter(|line| !line.trim().is_empty()) {
+        let leading = &line[..line.len() - line.trim_start_matches([' ', '\t']).len()];
+        if leading.contains('\t') {
+            continue;
+        }
+        if leading.len() > previous {
+            *steps.entry(leading.len() - previous).or_insert(0) += 1;
+        }
+        previous = leading.len();
+    }
+    steps.into_iter().max_by(|(step_a, count_a), (step_b, count_b)| count_a.cmp(count_b).then(step_b.cmp(step_a))).map(|(step, _)| step)
+}
+
+// The text with its leading whitespace rewritten and the number of lines that changed.
+// Tabs count as one level, `width` spaces as one level; the rest stays as spaces.
+fn reindent(text: &str, width: usize, target: Indent) -> (String, usize) {
+    let mut changed = 0;
+    let lines: Vec<String> = text
+        .split('\n')
+        .map(|line| {
+            let rest = line.trim_start_matches([' ', '\t']);
+            if rest.trim_end_matches('\r').is_empty() {
+                return line.to_string();
+            }
+            let leading = &line[..line.len() - rest.len()];
+            let columns = leading.chars().fold(0, |columns, c| if c == '\t' { columns + width - columns % width } else { columns + 1 });
+            let indent = match target {
+                Indent::Tabs => "\t".repeat(columns / width),
+                Indent::Spaces(size) => " ".repeat(columns / width * size),
+            } + &" ".repeat(columns % width);
+            if indent != leading {
+                changed += 1;
+            }
+            indent + rest
+        })
+        .collect();
+    (lines.join("\n"), changed)
+}
+
+fn render(text: &str, color: bool) -> String {
+    let paint = |marker: &str, style: fn(ColoredString) -> ColoredString| if color { style(marker.normal()).to_string() } else { marker.to_string() };
+    let mut output = String::new();
+    let body = text.strip_suffix('\n').unwrap_or(text);
+    for line in body.split('\n') {
+        let (line, carriage_return) = line.strip_suffix('\r').map_or((line, false), |line| (line, true));
+        let trailing = line.trim_end_matches([' ', '\t']).len();
+        let mut column = 0;
+        for (index, c) in line.char_indices() {
+            let drawn = match c {
+                '\t' => {
+                    let marker = format!("→{}", " ".repeat(TAB_STOP - 1 - column % TAB_STOP));
+                    column += TAB_STOP - column % TAB_STOP;
+                    output.push_str(&paint(&marker, if index >= trailing { |text| text.red() } else { |text| text.dimmed() }));
+                    continue;
+                }
+                ' ' if index >= trailing => paint("·", |text| text.red()),
+                '\u{00A0}' | '\u{202F}' => paint("⍽", |text| text.magenta()),
+                c if c.is_whitespace() && c != ' ' => paint("␣", |text| text.magenta()),
+                c if c.is_control() || special_character_name(c).is_some() => paint(&format!("<U+{:04X}>", c as u32), |text| text.red().bold()),
+                c => c.to_string(),
+            };
+            output.push_str(&drawn);
+            column += 1;
+        }
+        if carriage_return {
+            output.push_str(&paint("␍", |text| text.dimmed()));
+        }
+        output.push('\n');
+    }
+    output
+}
+
+// (line, column, description), 1-based
+fn findings(text: &str) -> Vec<(usize, usize, String)> {
+    let mut findings = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let leading = &line[..line.len() - line.trim_start_matches([' ', '\t']).len()];
+        if leading.contains('\t') && leading.contains(' ') && leading.len() < line.len() {
+            findings.push((number + 1, 1, "indentation mixes tabs and spaces".to_string()));
+        }
+        for (index, c) in line.chars().enumerate() {
+            let name = match c {
+                ' ' | '\t' => None,
+                c if c.is_control() => Some("control character"),
+                c => special_character_name(c),
+            };
+            if let Some(name) = name {
+                findings.push((number + 1, index + 1, format!("U+{:04X} {}", c as u32, name)));
+            }
+        }
+        let content = line.trim_end_matches([' ', '\t']);
+        if content.len() < line.len() {
+            findings.push((number + 1, content.chars().count() + 1, format!("trailing whitespace ({} character{})", line.len() - content.len(), if line.len() - content.len() == 1 { "" } else { "s" })));
+        }
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_target_and_detect_width() {
+        assert_eq!(parse_target("spaces:2").unwrap(), Indent::Spaces(2));
+        assert_eq!(parse_target("TABS").unwrap(), Indent::Tabs);
+        assert!(parse_target("spaces:0").is_err());
+        assert!(parse_target("2").is_err());
+        assert_eq!(detect_width("a:\n    b:\n        c: 1\n    d: 2\n"), Some(4));
+        assert_eq!(detect_width("fn x() {\n  if y {\n    z();\n  }\n}\n   aligned\n"), Some(2));
+        assert_eq!(detect_width("\tx\nno indent\n"), None);
+    }
+
+    #[test]
+    fn test_reindent() {
+        let text = "a:\n    b:\n        c: 1\n\n      d\n";
+        assert_eq!(reindent(text, 4, Indent::Spaces(2)), ("a:\n  b:\n    c: 1\n\n    d\n".to_string(), 3));
+        assert_eq!(reindent(text, 4, Indent::Tabs).0, "a:\n\tb:\n\t\tc: 1\n\n\t  d\n");
+        assert_eq!(reindent("x\r\n\ty\r\n", 4, Indent::Spaces(4)), ("x\r\n    y\r\n".to_string(), 1));
+        assert_eq!(reindent("x\n  y\n", 2, Indent::Spaces(2)).1, 0);
+    }
+
+    #[test]
+    fn test_render_and_findings() {
+        let text = "key:\tvalue  \r\nname:\u{00A0}x\u{200B}\n \tmixed\n";
+        assert_eq!(render(text, false), "key:→   value··␍\nname:⍽x<U+200B>\n →  mixed\n");
+        assert_eq!(
+            findings(text),
+            vec![
+                (1, 11, "trailing whitespace (2 characters)".to_string()),
+                (2, 6, "U+00A0 NO-BREAK SPACE".to_string()),
+                (2, 8, "U+200B ZERO WIDTH SPACE".to_string()),
+                (3, 1, "indentation mixes tabs and spaces".to_string()),
+            ]
+        );
+    }
+}
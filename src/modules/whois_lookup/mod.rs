@@ -0,0 +1,254 @@
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use colored::*;
+use std::error::Error;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+pub struct WhoisLookupModule;
+
+// IANA knows which server is authoritative for every top-level domain
+const ROOT_SERVER: &str = "whois.iana.org";
+const MAX_REFERRALS: usize = 3;
+
+impl ToolModule for WhoisLookupModule {
+    fn name(&self) -> &'static str {
+        "whois-lookup"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("whois")
+                .long("whois")
+                .value_name("DOMAIN")
+                .help("Look up a domain's registrar, creation and expiry dates and name servers")
+                .long_help("Query WHOIS for DOMAIN, starting at whois.iana.org and following referrals to the registry and registrar servers, then summarize the registrar, creation, update and expiry dates, status and name servers. URLs are accepted and reduced to their host. Use --whois-raw to print the full responses instead.")
+        )
+        .arg(
+            Arg::new("whois-raw")
+                .long("whois-raw")
+                .help("Print the raw --whois responses from every server queried")
+                .action(ArgAction::SetTrue)
+                .requires("whois")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(input) = matches.get_one::<String>("whois") {
+            let domain = normalize_domain(input)?;
+            let responses = lookup(&domain)?;
+            if matches.get_flag("whois-raw") {
+                for (server, response) in &responses {
+                    println!("{}\n{}", format!("# {}", server).bold(), response.trim_end());
+                    println!();
+                }
+                return Ok(());
+            }
+            // The most specific server answers last; fall back to earlier ones for missing fields.
+            // IANA's own answer describes the top-level domain, so it only counts when nothing else answered.
+            let skip = usize::from(responses.len() > 1);
+            let summary = responses.iter().skip(skip).rev().fold(Summary::default(), |summary, (_, response)| summary.merge(summarize(response)));
+            if summary.is_empty() {
+                let (server, response) = responses.last().ok_or("No WHOIS response")?;
+                return Err(format!("{} returned no registration data for {}:\n{}", server, domain, response.trim()).into());
+            }
+            print_summary(&domain, responses.last().map(|(server, _)| server.as_str()).unwrap_or(ROOT_SERVER), &summary);
+        }
+        Ok(())
+    }
+}
+
+// "https://www.Example.com/path" -> "www.example.com"
+fn normalize_domain(input: &str) -> Result<String, String> {
+    let host = input.trim().split("://").last().unwrap_or("");
+    let host = host.split(['/', '?', '#']).next().unwrap_or("");
+    let host = host.rsplit('@').next().unwrap_or("");
+    let host = host.split(':').next().unwrap_or("").trim_end_matches('.').to_lowercase();
+    if host.is_empty() || !host.contains('.') || host.chars().any(|c| c.is_whitespace()) {
+        return Err(format!("'{}' is not a domain name", input));
+    }
+    Ok(host)
+}
+
+fn query(server: &str, domain: &str) -> Result<String, String> {
+    let address = (server, 43)
+        .to_socket_addrs()
+        .map_err(|e| format!("Failed to resolve {}: {}", server, e))?
+        .next()
+        .ok_or_else(|| format!("Failed to resolve {}", server))?;
+    let mut stream = TcpStream::connect_timeout(&address, Duration::from_secs(10)).map_err(|e| format!("Failed to connect to {}: {}", server, e))?;
+    stream.set_read_timeout(Some(Duration::from_secs(15))).map_err(|e| e.to_string())?;
+    stream.write_all(format!("{}\r\n", domain).as_bytes()).map_err(|e| format!("Failed to query {}: {}", server, e))?;
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).map_err(|e| format!("Failed to read the response from {}: {}", server, e))?;
+    Ok(String::from_utf8_lossy(&response).into_owned())
+}
+
+// Every (server, response) queried, from IANA down to the registrar
+fn lookup(domain: &str) -> Result<Vec<(String, String)>, String> {
+    let mut responses = Vec::new();
+    let mut server = ROOT_SERVER.to_string();
+    for _ in 0..=MAX_REFERRALS {
+        let response = query(&server, domain)?;
+        let next = referral(&response).filter(|next| !responses.iter().any(|(seen, _): &(String, String)| seen.eq_ignore_ascii_case(next)) && !next.eq_ignore_ascii_case(&server));
+        responses.push((server, response));
+        match next {
+            Some(next) => server = next,
+            None => break,
+        }
+    }
+    Ok(responses)
+}
+
+// "refer:" from IANA, "Registrar WHOIS Server:" from thin registries such as .com
+fn referral(response: &str) -> Option<String> {
+    fields(response)
+        .find(|(key, _)| matches!(key.as_str(), "refer" | "whois" | "registrar whois server"))
+        .map(|(_, value)| value.trim_start_matches("whois://").trim_end_matches('/').to_string())
+        .filter(|server| !server.is_empty() && !server.contains(' '))
+}
+
+// "Key: value" lines with lowercase keys; comments and notices are skipped
+fn fields(response: &str) -> impl Iterator<Item = (String, String)> + '_ {
+    response.lines().filter_map(|line| {
+        let line = line.trim();
+        if line.starts_with('%') || line.starts_with('#') || line.starts_with(">>>") {
+            return None;
+        }
+        let (key, value) = line.split_once(':')?;
+        let value = value.trim();
+        (!value.is_empty()).then(|| (key.trim().to_lowercase(), value.to_string()))
+    })
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Summary {
+    registrar: Option<String>,
+    created: Option<String>,
+    updated: Option<String>,
+    expires: Option<String>,
+    status: Vec<String>,
+    name_servers: Vec<String>,
+}
+
+impl Summary {
+    fn is_empty(&self) -> bool {
+        *self == Summary::default()
+    }
+
+    fn merge(self, other: Summary) -> Summary {
+        Summary {
+            registrar: self.registrar.or(other.registrar),
+            created: self.created.or(other.created),
+            updated: self.updated.or(other.updated),
+            expires: self.expires.or(other.expires),
+            status: if self.status.is_empty() { other.status } else { self.status },
+            name_servers: if self.name_servers.is_empty() { other.name_servers } else { self.name_servers },
+        }
+    }
+}
+
+// Field names differ between registries; these cover the common gTLD and ccTLD spellings
+fn summarize(response: &str) -> Summary {
+    let mut summary = Summary::default();
+    for (key, value) in fields(response) {
+        match key.as_str() {
+            "registrar" | "registrar name" | "sponsoring registrar" => {
+                summary.registrar.get_or_insert(value);
+            }
+            "creation date" | "created" | "created on" | "registered on" | "registration time" | "domain registration date" => {
+                summary.created.get_or_insert(value);
+            }
+            "updated date" | "last updated" | "last-update" | "changed" | "last modified" => {
+                summary.updated.get_or_insert(value);
+            }
+            "registry expiry date" | "registrar registration expiration date" | "expiry date" | "expiration date" | "expires" | "expires on" | "paid-till" | "expiration time" => {
+                summary.expires.get_or_insert(value);
+            }
+            "domain status" | "status" | "state" => {
+                // "clientTransferProhibited https://icann.org/epp#clientTransferProhibited"
+                let status = value.split_whitespace().next().unwrap_or("").to_string();
+                if !summary.status.contains(&status) {
+                    summary.status.push(status);
+                }
+            }
+            "name server" | "nserver" | "nameserver" | "name servers" => {
+                let server = value.split_whitespace().next().unwrap_or("").trim_end_matches('.').to_lowercase();
+                if !summary.name_servers.contains(&server) {
+                    summary.name_servers.push(server);
+                }
+            }
+            _ => {}
+        }
+    }
+    summary
+}
+
+fn print_summary(domain: &str, server: &str, summary: &Summary) {
+    println!("{} {}", domain.bold(), format!("(from {})", server).dimmed());
+    let field = |label: &str, value: &Option<String>| {
+        if let Some(value) = value {
+            println!("  {:<13}{}", format!("{}:", label).cyan(), value);
+        }
+    };
+    field("Registrar", &summary.registrar);
+    field("Created", &summary.created);
+    field("Updated", &summary.updated);
+    field("Expires", &summary.expires);
+    if !summary.status.is_empty() {
+        println!("  {:<13}{}", "Status:".cyan(), summary.status.join(", "));
+    }
+    if !summary.name_servers.is_empty() {
+        println!("  {}", "Name servers:".cyan());
+        for server in &summary.name_servers {
+            println!("    {}", server);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IANA: &str = "% IANA WHOIS server\n\nrefer:        whois.verisign-grs.com\n\ndomain:       COM\nstatus:       ACTIVE\ncreated:      1985-01-01\n";
+
+    const VERISIGN: &str = "   Domain Name: EXAMPLE.COM\n   Registry Domain ID: 2336799_DOMAIN_COM-VRSN\n   Registrar WHOIS Server: whois.iana.org\n   Updated Date: 2024-08-14T07:01:34Z\n   Creation Date: 1995-08-14T04:00:00Z\n   Registry Expiry Date: 2025-08-13T04:00:00Z\n   Registrar: RESERVED-Internet Assigned Numbers Authority\n   Domain Status: clientDeleteProhibited https://icann.org/epp#clientDeleteProhibited\n   Domain Status: clientTransferProhibited https://icann.org/epp#clientTransferProhibited\n   Name Server: A.IANA-SERVERS.NET\n   Name Server: B.IANA-SERVERS.NET\n>>> Last update of whois database: 2024-09-01T10:00:00Z <<<\n";
+
+    #[test]
+    fn test_normalize_domain() {
+        assert_eq!(normalize_domain("https://www.Example.com/path?q=1").unwrap(), "www.example.com");
+        assert_eq!(normalize_domain("example.co.uk.").unwrap(), "example.co.uk");
+        assert_eq!(normalize_domain("user@example.org:8080").unwrap(), "example.org");
+        assert!(normalize_domain("localhost").is_err());
+        assert!(normalize_domain("").is_err());
+    }
+
+    #[test]
+    fn test_referral() {
+        assert_eq!(referral(IANA).as_deref(), Some("whois.verisign-grs.com"));
+        assert_eq!(referral(VERISIGN).as_deref(), Some("whois.iana.org"));
+        assert_eq!(referral("% No match\n"), None);
+    }
+
+    #[test]
+    fn test_summarize() {
+        let summary = summarize(VERISIGN);
+        assert_eq!(summary.registrar.as_deref(), Some("RESERVED-Internet Assigned Numbers Authority"));
+        assert_eq!(summary.created.as_deref(), Some("1995-08-14T04:00:00Z"));
+        assert_eq!(summary.updated.as_deref(), Some("2024-08-14T07:01:34Z"));
+        assert_eq!(summary.expires.as_deref(), Some("2025-08-13T04:00:00Z"));
+        assert_eq!(summary.status, ["clientDeleteProhibited", "clientTransferProhibited"]);
+        assert_eq!(summary.name_servers, ["a.iana-servers.net", "b.iana-servers.net"]);
+    }
+
+    #[test]
+    fn test_merge_prefers_specific_server() {
+        let registrar = Summary { registrar: Some("Example Registrar, Inc.".to_string()), ..Summary::default() };
+        let merged = registrar.merge(summarize(VERISIGN));
+        assert_eq!(merged.registrar.as_deref(), Some("Example Registrar, Inc."));
+        assert_eq!(merged.expires.as_deref(), Some("2025-08-13T04:00:00Z"));
+        assert!(summarize(IANA).name_servers.is_empty());
+        assert!(summarize("% No entries found\n").is_empty());
+    }
+}
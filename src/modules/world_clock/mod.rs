@@ -0,0 +1,239 @@
+use crate::config::load_config;
+use crate::date_calc::parse_timezone;
+use crate::tool_module::ToolModule;
+use chrono::{DateTime, Duration, Timelike, Utc};
+use chrono_tz::Tz;
+use clap::{Arg, ArgMatches, Command};
+use colored::*;
+use std::error::Error;
+use std::io::{self, IsTerminal};
+
+pub struct WorldClockModule;
+
+type Style = fn(&str) -> ColoredString;
+
+// Overlaps are searched over the next day in steps of this many minutes
+const STEP_MINUTES: i64 = 15;
+const GRID_HOURS: i64 = 24;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Hours {
+    // Minutes after local midnight; start < end
+    start: u32,
+    end: u32,
+}
+
+impl Hours {
+    // 9-18, 9:30-17:30 or 09:00-18:00
+    fn parse(text: &str) -> Result<Self, String> {
+        let invalid = || format!("Invalid --overlap hours '{}'; use e.g. 9-18 or 8:30-17:00", text);
+        let (start, end) = text.split_once('-').ok_or_else(invalid)?;
+        let minutes = |part: &str| -> Option<u32> {
+            let (hours, minutes) = part.trim().split_once(':').unwrap_or((part.trim(), "0"));
+            let (hours, minutes): (u32, u32) = (hours.parse().ok()?, minutes.parse().ok()?);
+            (hours <= 24 && minutes < 60 && hours * 60 + minutes <= 24 * 60).then_some(hours * 60 + minutes)
+        };
+        let hours = Hours { start: minutes(start).ok_or_else(invalid)?, end: minutes(end).ok_or_else(invalid)? };
+        if hours.start >= hours.end {
+            return Err(format!("--overlap hours must end after they start, e.g. 9-18 rather than {}", text));
+        }
+        Ok(hours)
+    }
+
+    fn contains(&self, time: &DateTime<Tz>) -> bool {
+        let minute = time.hour() * 60 + time.minute();
+        (self.start..self.end).contains(&minute)
+    }
+}
+
+impl ToolModule for WorldClockModule {
+    fn name(&self) -> &'static str {
+        "world-clock"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("worldclock")
+                .long("worldclock")
+                .value_name("ZONES")
+                .num_args(0..=1)
+                .default_missing_value("")
+                .help("Show the time now in several zones with day offsets (comma-separated or from config)")
+                .long_help("Show the current time in several IANA time zones side by side, with each zone's UTC offset and whether it is already tomorrow or still yesterday compared to here. Pass the zones comma-separated, or list them in the config file, the same list --tz-now uses:\n\n[date]\ntimezones = [\"Europe/Istanbul\", \"America/New_York\", \"Asia/Tokyo\"]\n\nAdd --overlap 9-18 to find meeting times inside everyone's working hours.")
+        )
+        .arg(
+            Arg::new("overlap")
+                .long("overlap")
+                .value_name("HOURS")
+                .help("Working hours for --worldclock, e.g. 9-18; shows when they overlap across all zones in the next 24 hours")
+                .requires("worldclock")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if let Some(zones) = matches.get_one::<String>("worldclock") {
+            let names: Vec<String> = if zones.is_empty() {
+                let configured = load_config()?.date.timezones;
+                if configured.is_empty() { vec!["local".to_string(), "UTC".to_string()] } else { configured }
+            } else {
+                zones.split(',').map(|zone| zone.trim().to_string()).filter(|zone| !zone.is_empty()).collect()
+            };
+            let mut zones = Vec::new();
+            for name in &names {
+                zones.push(parse_timezone(name)?);
+            }
+            let here = parse_timezone("local")?;
+            let now = Utc::now();
+            let color = io::stdout().is_terminal();
+            print!("{}", render_clock(&zones, here, now, color));
+            if let Some(hours) = matches.get_one::<String>("overlap") {
+                let hours = Hours::parse(hours)?;
+                print!("{}", render_overlap(&zones, hours, now, color));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn paint(text: &str, style: Style, color: bool) -> String {
+    if color {
+        style(text).to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+// "+1 day", "-1 day" or "" against the date here
+fn day_offset(time: &DateTime<Tz>, here: &DateTime<Tz>) -> String {
+    match (time.date_naive() - here.date_naive()).num_days() {
+        0 => String::new(),
+        days => format!("{:+} day{}", days, if days.abs() == 1 { "" } else { "s" }),
+    }
+}
+
+fn render_clock(zones: &[Tz], here: Tz, now: DateTime<Utc>, color: bool) -> String {
+    let local = now.with_timezone(&here);
+    let width = zones.iter().map(|zone| zone.name().len()).max().unwrap_or(0);
+    let mut output = String::new();
+    for zone in zones {
+        let time = now.with_timezone(zone);
+        let marker = if *zone == here { " (here)" } else { "" };
+        let line = format!(
+            "{:<width$}  {} {}  UTC{}  {}{}",
+            zone.name(),
+            time.format("%a"),
+            paint(&time.format("%H:%M").to_string(), |t| t.cyan().bold(), color),
+            time.format("%:z"),
+            paint(&day_offset(&time, &local), |t| t.yellow(), color),
+            marker,
+            width = width
+        );
+        output.push_str(line.trim_end());
+        output.push('\n');
+    }
+    output
+}
+
+// Consecutive stretches of the next day when every zone is inside its working hours
+fn overlaps(zones: &[Tz], hours: Hours, now: DateTime<Utc>) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    let start = now - Duration::minutes(now.minute() as i64 % STEP_MINUTES) - Duration::seconds(now.second() as i64);
+    let mut ranges: Vec<(DateTime<Utc>, DateTime<Utc>)> = Vec::new();
+    for step in 0..GRID_HOURS * 60 / STEP_MINUTES {
+        let time = start + Duration::minutes(step * STEP_MINUTES);
+        if !zones.iter().all(|zone| hours.contains(&time.with_timezone(zone))) {
+            continue;
+        }
+        let end = time + Duration::minutes(STEP_MINUTES);
+        match ranges.last_mut() {
+            Some(last) if last.1 == time => last.1 = end,
+            _ => ranges.push((time, end)),
+        }
+    }
+    ranges
+}
+
+fn render_overlap(zones: &[Tz], hours: Hours, now: DateTime<Utc>, color: bool) -> String {
+    let mut output = String::from("\n");
+    let width = zones.iter().map(|zone| zone.name().len()).max().unwrap_or(0);
+    // One column per hour from now, working hours green and shared ones bold
+    let first = now - Duration::minutes(now.minute() as i64) - Duration::seconds(now.second() as i64);
+    let shared: Vec<bool> = (0..GRID_HOURS).map(|hour| {
+        let time = first + Duration::hours(hour);
+        zones.iter().all(|zone| hours.contains(&time.with_timezone(zone)))
+    }).collect();
+    for zone in zones {
+        let mut row = format!("{:<width$} ", zone.name(), width = width);
+        for (hour, shared) in shared.iter().enumerate() {
+            let time = (first + Duration::hours(hour as i64)).with_timezone(zone);
+            let cell = format!("{:>3}", time.hour());
+            row.push_str(&match (hours.contains(&time), shared) {
+                (_, true) => paint(&cell, |t| t.black().on_green().bold(), color),
+                (true, false) => paint(&cell, |t| t.green(), color),
+                (false, false) => paint(&cell, |t| t.dimmed(), color),
+            });
+        }
+        output.push_str(&row);
+        output.push('\n');
+    }
+    if !color {
+        let marks: String = shared.iter().map(|shared| if *shared { "  ^" } else { "   " }).collect();
+        output.push_str(&format!("{:<width$} {}\n", "", marks.trim_end(), width = width));
+    }
+    output.push('\n');
+    let ranges = overlaps(zones, hours, now);
+    if ranges.is_empty() {
+        output.push_str(&format!("No time in the next 24 hours falls within {:02}:{:02}-{:02}:{:02} everywhere\n", hours.start / 60, hours.start % 60, hours.end / 60, hours.end % 60));
+        return output;
+    }
+    for (start, end) in ranges {
+        let minutes = (end - start).num_minutes();
+        output.push_str(&format!("{} ({}h{:02}m):\n", paint("Overlap", |t| t.green().bold(), color), minutes / 60, minutes % 60));
+        for zone in zones {
+            let (local_start, local_end) = (start.with_timezone(zone), end.with_timezone(zone));
+            output.push_str(&format!("  {:<width$}  {} {}-{}\n", zone.name(), local_start.format("%a"), local_start.format("%H:%M"), local_end.format("%H:%M"), width = width));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn zones(names: &[&str]) -> Vec<Tz> {
+        names.iter().map(|name| name.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_hours_parse() {
+        assert_eq!(Hours::parse("9-18").unwrap(), Hours { start: 540, end: 1080 });
+        assert_eq!(Hours::parse("8:30-17:15").unwrap(), Hours { start: 510, end: 1035 });
+        assert_eq!(Hours::parse("0-24").unwrap(), Hours { start: 0, end: 1440 });
+        assert!(Hours::parse("18-9").is_err());
+        assert!(Hours::parse("9").is_err());
+        assert!(Hours::parse("9:75-18").is_err());
+    }
+
+    #[test]
+    fn test_clock_and_day_offsets() {
+        let now = Utc.with_ymd_and_hms(2024, 5, 1, 22, 30, 0).unwrap();
+        let zones = zones(&["Europe/Istanbul", "America/New_York", "Asia/Tokyo"]);
+        let here: Tz = "America/New_York".parse().unwrap();
+        assert_eq!(
+            render_clock(&zones, here, now, false),
+            "Europe/Istanbul   Thu 01:30  UTC+03:00  +1 day\nAmerica/New_York  Wed 18:30  UTC-04:00   (here)\nAsia/Tokyo        Thu 07:30  UTC+09:00  +1 day\n"
+        );
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let now = Utc.with_ymd_and_hms(2024, 5, 1, 0, 7, 0).unwrap();
+        let hours = Hours::parse("9-18").unwrap();
+        // Istanbul is UTC+3 and New York UTC-4 in May: 13:00-15:00 UTC works for both
+        let ranges = overlaps(&zones(&["Europe/Istanbul", "America/New_York"]), hours, now);
+        assert_eq!(ranges, [(Utc.with_ymd_and_hms(2024, 5, 1, 13, 0, 0).unwrap(), Utc.with_ymd_and_hms(2024, 5, 1, 15, 0, 0).unwrap())]);
+        assert!(overlaps(&zones(&["Australia/Sydney", "America/New_York"]), hours, now).is_empty());
+        assert!(render_overlap(&zones(&["Australia/Sydney", "America/New_York"]), hours, now, false).contains("No time in the next 24 hours"));
+    }
+}
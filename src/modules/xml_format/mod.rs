@@ -0,0 +1,270 @@
+use crate::tool_module::ToolModule;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::{Reader, Writer};
+use serde_json::{Map, Value};
+use std::error::Error;
+use std::fs;
+use std::io::{self, Read};
+
+pub struct XmlFormatModule;
+
+impl ToolModule for XmlFormatModule {
+    fn name(&self) -> &'static str {
+        "xml-format"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("xml-pretty")
+                .long("xml-pretty")
+                .value_name("XML|FILE")
+                .help("Pretty print XML with 2-space indentation (or read from stdin)")
+                .long_help("Indent XML by two spaces per level. Whitespace between tags is replaced; text, comments, CDATA and processing instructions are kept. Takes XML text (starting with <), a file path, or reads from stdin when neither is given.")
+                .num_args(0..=1)
+                .conflicts_with_all(["xml-minify", "xml-to-json"])
+        )
+        .arg(
+            Arg::new("xml-minify")
+                .long("xml-minify")
+                .value_name("XML|FILE")
+                .help("Minify XML by removing whitespace between tags (or read from stdin)")
+                .num_args(0..=1)
+                .conflicts_with("xml-to-json")
+        )
+        .arg(
+            Arg::new("xml-to-json")
+                .long("xml-to-json")
+                .value_name("XML|FILE")
+                .help("Convert XML to JSON (or read from stdin)")
+                .long_help("Convert XML to JSON. Each element becomes a key holding its text, or an object when it has attributes or children. Attributes are prefixed with @ (including xmlns declarations), text next to attributes or children goes under #text, and repeated elements become arrays. Prefixed names such as soap:Envelope are kept unless --strip-namespaces is given.")
+                .num_args(0..=1)
+        )
+        .arg(
+            Arg::new("strip-namespaces")
+                .long("strip-namespaces")
+                .help("Drop namespace prefixes and xmlns attributes in --xml-to-json output")
+                .action(ArgAction::SetTrue)
+                .requires("xml-to-json")
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if matches.contains_id("xml-pretty") {
+            let input = read_input(matches.get_one::<String>("xml-pretty"))?;
+            println!("{}", reformat_xml(&input, Some(2))?);
+        } else if matches.contains_id("xml-minify") {
+            let input = read_input(matches.get_one::<String>("xml-minify"))?;
+            println!("{}", reformat_xml(&input, None)?);
+        } else if matches.contains_id("xml-to-json") {
+            let input = read_input(matches.get_one::<String>("xml-to-json"))?;
+            let json = xml_to_json(&input, matches.get_flag("strip-namespaces"))?;
+            println!("{}", serde_json::to_string_pretty(&json)?);
+        }
+        Ok(())
+    }
+}
+
+// Inline XML, a file path, or stdin when neither (or "-") is given
+fn read_input(value: Option<&String>) -> Result<String, Box<dyn Error>> {
+    match value.map(String::as_str) {
+        Some(text) if text.trim_start().starts_with('<') => Ok(text.to_string()),
+        Some(path) if path != "-" => fs::read_to_string(path).map_err(|e| format!("Failed to read '{}': {}", path, e).into()),
+        _ => {
+            let mut buffer = String::new();
+            io::stdin().read_to_string(&mut buffer)?;
+            Ok(buffer)
+        }
+    }
+}
+
+// "Invalid XML at line 3, column 7: ..." from a byte offset into the input
+fn describe_error(input: &str, position: u64, error: impl std::fmt::Display) -> String {
+    let before = &input[..(position as usize).min(input.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+    format!("Invalid XML at line {}, column {}: {}", line, column, error)
+}
+
+// Re-emits every event, dropping whitespace-only text between tags. With an
+// indent each element starts on its own line; without one the output is minified.
+fn reformat_xml(input: &str, indent: Option<usize>) -> Result<String, String> {
+    let mut reader = Reader::from_str(input);
+    let mut writer = match indent {
+        Some(size) => Writer::new_with_indent(Vec::new(), b' ', size),
+        None => Writer::new(Vec::new()),
+    };
+    let mut open = Vec::new();
+    loop {
+        let event = reader.read_event().map_err(|e| describe_error(input, reader.error_position(), e))?;
+        match &event {
+            Event::Eof => break,
+            Event::Text(text) if text.iter().all(u8::is_ascii_whitespace) => continue,
+            Event::Start(start) => open.push(String::from_utf8_lossy(start.name().as_ref()).into_owned()),
+            Event::End(_) => {
+                open.pop();
+            }
+            _ => {}
+        }
+        writer.write_event(event).map_err(|e| e.to_string())?;
+    }
+    if let Some(name) = open.pop() {
+        return Err(describe_error(input, input.len() as u64, format!("<{}> is never closed", name)));
+    }
+    String::from_utf8(writer.into_inner()).map_err(|e| e.to_string())
+}
+
+// An element while its children are being read
+struct Element {
+    name: String,
+    fields: Map<String, Value>,
+    text: String,
+}
+
+impl Element {
+    fn start(start: &BytesStart, strip_namespaces: bool) -> Result<Self, String> {
+        let mut fields = Map::new();
+        for attribute in start.attributes() {
+            let attribute = attribute.map_err(|e| e.to_string())?;
+            let key = attribute.key;
+            let is_namespace = key.as_namespace_binding().is_some();
+            if strip_namespaces && is_namespace {
+                continue;
+            }
+            let name = if strip_namespaces { key.local_name().into_inner() } else { key.as_ref() };
+            let value = attribute.unescape_value().map_err(|e| e.to_string())?;
+            fields.insert(format!("@{}", String::from_utf8_lossy(name)), Value::String(value.into_owned()));
+        }
+        let name = if strip_namespaces { start.local_name().into_inner() } else { start.name().into_inner() };
+        Ok(Element { name: String::from_utf8_lossy(name).into_owned(), fields, text: String::new() })
+    }
+
+    // Text alone becomes a string; with attributes or children it goes under #text
+    fn into_value(mut self) -> Value {
+        let text = self.text.trim();
+        if self.fields.is_empty() {
+            return if text.is_empty() { Value::Null } else { Value::String(text.to_string()) };
+        }
+        if !text.is_empty() {
+            self.fields.insert("#text".to_string(), Value::String(text.to_string()));
+        }
+        Value::Object(self.fields)
+    }
+
+    // Repeated child names collect into an array
+    fn add_child(&mut self, name: String, value: Value) {
+        match self.fields.get_mut(&name) {
+            Some(Value::Array(items)) => items.push(value),
+            Some(existing) => *existing = Value::Array(vec![existing.take(), value]),
+            None => {
+                self.fields.insert(name, value);
+            }
+        }
+    }
+}
+
+fn xml_to_json(input: &str, strip_namespaces: bool) -> Result<Value, String> {
+    let mut reader = Reader::from_str(input);
+    let mut stack: Vec<Element> = Vec::new();
+    let mut root: Option<Value> = None;
+    loop {
+        let event = reader.read_event().map_err(|e| describe_error(input, reader.error_position(), e))?;
+        let finished = match event {
+            Event::Eof => break,
+            Event::Start(start) => {
+                stack.push(Element::start(&start, strip_namespaces)?);
+                None
+            }
+            Event::Empty(start) => Some(Element::start(&start, strip_namespaces)?),
+            Event::End(_) => stack.pop(),
+            Event::Text(text) => {
+                if let Some(element) = stack.last_mut() {
+                    element.text.push_str(&text.unescape().map_err(|e| describe_error(input, reader.buffer_position(), e))?);
+                }
+                None
+            }
+            Event::CData(data) => {
+                if let Some(element) = stack.last_mut() {
+                    element.text.push_str(&String::from_utf8_lossy(&data));
+                }
+                None
+            }
+            _ => None,
+        };
+        if let Some(element) = finished {
+            let name = element.name.clone();
+            match stack.last_mut() {
+                Some(parent) => parent.add_child(name, element.into_value()),
+                None => {
+                    let mut document = Map::new();
+                    document.insert(name, element.into_value());
+                    root = Some(Value::Object(document));
+                }
+            }
+        }
+    }
+    if let Some(element) = stack.pop() {
+        return Err(describe_error(input, input.len() as u64, format!("<{}> is never closed", element.name)));
+    }
+    root.ok_or_else(|| "The input has no root element".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const FEED: &str = "<?xml version=\"1.0\"?>\n<rss version=\"2.0\">\n<channel>  <title>News &amp; more</title>\n<item><title>One</title></item><item><title>Two</title><guid isPermaLink=\"false\">2</guid></item>\n<!-- end --></channel></rss>";
+
+    #[test]
+    fn test_pretty_and_minify() {
+        assert_eq!(
+            reformat_xml(FEED, Some(2)).unwrap(),
+            "<?xml version=\"1.0\"?>\n<rss version=\"2.0\">\n  <channel>\n    <title>News &amp; more</title>\n    <item>\n      <title>One</title>\n    </item>\n    <item>\n      <title>Two</title>\n      <guid isPermaLink=\"false\">2</guid>\n    </item>\n    <!-- end -->\n  </channel>\n</rss>"
+        );
+        assert_eq!(
+            reformat_xml(FEED, None).unwrap(),
+            "<?xml version=\"1.0\"?><rss version=\"2.0\"><channel><title>News &amp; more</title><item><title>One</title></item><item><title>Two</title><guid isPermaLink=\"false\">2</guid></item><!-- end --></channel></rss>"
+        );
+    }
+
+    #[test]
+    fn test_xml_to_json() {
+        assert_eq!(xml_to_json(FEED, false).unwrap(), json!({
+            "rss": {
+                "@version": "2.0",
+                "channel": {
+                    "title": "News & more",
+                    "item": [
+                        { "title": "One" },
+                        { "title": "Two", "guid": { "@isPermaLink": "false", "#text": "2" } }
+                    ]
+                }
+            }
+        }));
+        assert_eq!(xml_to_json("<a><b/><![CDATA[<raw>]]></a>", false).unwrap(), json!({ "a": { "b": null, "#text": "<raw>" } }));
+    }
+
+    #[test]
+    fn test_xml_to_json_namespaces() {
+        let soap = "<soap:Envelope xmlns:soap=\"http://schemas.xmlsoap.org/soap/envelope/\"><soap:Body><m:Price xmlns:m=\"urn:prices\" m:currency=\"EUR\">9.99</m:Price></soap:Body></soap:Envelope>";
+        assert_eq!(xml_to_json(soap, false).unwrap(), json!({
+            "soap:Envelope": {
+                "@xmlns:soap": "http://schemas.xmlsoap.org/soap/envelope/",
+                "soap:Body": { "m:Price": { "@xmlns:m": "urn:prices", "@m:currency": "EUR", "#text": "9.99" } }
+            }
+        }));
+        assert_eq!(xml_to_json(soap, true).unwrap(), json!({
+            "Envelope": { "Body": { "Price": { "@currency": "EUR", "#text": "9.99" } } }
+        }));
+    }
+
+    #[test]
+    fn test_invalid_xml() {
+        let mismatched = reformat_xml("<a>\n  <b></c>\n</a>", Some(2)).unwrap_err();
+        assert!(mismatched.starts_with("Invalid XML at line 2"), "{}", mismatched);
+        assert!(reformat_xml("<a><b></b>", None).unwrap_err().contains("<a> is never closed"));
+        assert!(xml_to_json("<a><b></a>", false).is_err());
+        assert!(xml_to_json("just text", false).is_err());
+    }
+}
@@ -0,0 +1,143 @@
+use crate::tool_module::{read_input, ToolModule};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use serde::Deserialize;
+use serde_yaml::{Mapping, Value};
+use std::cmp::Ordering;
+use std::error::Error;
+
+pub struct YamlFormatModule;
+
+impl ToolModule for YamlFormatModule {
+    fn name(&self) -> &'static str {
+        "yaml-format"
+    }
+
+    fn configure_args(&self, cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("yaml-format")
+                .long("yaml-format")
+                .value_name("FILE")
+                .help("Reformat YAML with consistent 2-space indentation (or read from stdin)")
+                .long_help("Parse a YAML file and print it back with consistent 2-space indentation and quoting. Multi-document files keep their --- separators. Comments are not preserved. Use --sort-keys to sort mapping keys. If no file (or -) is given, reads from stdin.")
+                .num_args(0..=1)
+                .conflicts_with("yaml-validate")
+        )
+        .arg(
+            Arg::new("yaml-validate")
+                .long("yaml-validate")
+                .value_name("FILE")
+                .help("Check YAML syntax and report errors with line numbers (or read from stdin)")
+                .long_help("Parse a YAML file and report the first syntax error with its line and column, including duplicate keys. If no file (or -) is given, reads from stdin.")
+                .num_args(0..=1)
+        )
+        .arg(
+            Arg::new("sort-keys")
+                .long("sort-keys")
+                .help("Sort keys when formatting with --yaml-format or --toml-format")
+                .action(ArgAction::SetTrue)
+        )
+    }
+
+    fn execute(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        if matches.contains_id("yaml-format") {
+            let input = read_input(matches.get_one::<String>("yaml-format"))?;
+            let mut documents = parse_documents(&input)?;
+            if matches.get_flag("sort-keys") {
+                documents.iter_mut().for_each(sort_keys);
+            }
+            print!("{}", format_documents(&documents)?);
+        } else if matches.contains_id("yaml-validate") {
+            let input = read_input(matches.get_one::<String>("yaml-validate"))?;
+            let documents = parse_documents(&input)?;
+            match documents.len() {
+                1 => println!("✅ Valid YAML"),
+                n => println!("✅ Valid YAML ({} documents)", n),
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_documents(input: &str) -> Result<Vec<Value>, String> {
+    serde_yaml::Deserializer::from_str(input)
+        .map(|document| Value::deserialize(document).map_err(|e| describe_error(input, &e)))
+        .collect()
+}
+
+// The parser's message followed by the offending line and a caret under the column
+pub fn describe_error(input: &str, error: &serde_yaml::Error) -> String {
+    let Some(location) = error.location() else {
+        return format!("Invalid YAML: {}", error);
+    };
+    let line = input.lines().nth(location.line().saturating_sub(1)).unwrap_or("");
+    let gutter = format!("{} | ", location.line());
+    format!(
+        "Invalid YAML: {}\n{}{}\n{}^",
+        error,
+        gutter,
+        line,
+        " ".repeat(gutter.len() + location.column().saturating_sub(1))
+    )
+}
+
+fn format_documents(documents: &[Value]) -> Result<String, serde_yaml::Error> {
+    let documents = documents.iter().map(serde_yaml::to_string).collect::<Result<Vec<_>, _>>()?;
+    Ok(documents.join("---\n"))
+}
+
+fn sort_keys(value: &mut Value) {
+    match value {
+        Value::Mapping(mapping) => {
+            let mut entries: Vec<(Value, Value)> = std::mem::take(mapping).into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+            *mapping = entries.into_iter()
+                .map(|(key, mut value)| {
+                    sort_keys(&mut value);
+                    (key, value)
+                })
+                .collect::<Mapping>();
+        }
+        Value::Sequence(items) => items.iter_mut().for_each(sort_keys),
+        Value::Tagged(tagged) => sort_keys(&mut tagged.value),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_normalizes_indentation() {
+        let input = "server:\n    host: localhost\n    ports:\n        - 80\n        - 443\nname:   'app'\n";
+        let documents = parse_documents(input).unwrap();
+        assert_eq!(
+            format_documents(&documents).unwrap(),
+            "server:\n  host: localhost\n  ports:\n  - 80\n  - 443\nname: app\n"
+        );
+    }
+
+    #[test]
+    fn test_sort_keys() {
+        let mut documents = parse_documents("b: 1\na:\n  z: true\n  c: [x, y]\n").unwrap();
+        documents.iter_mut().for_each(sort_keys);
+        assert_eq!(format_documents(&documents).unwrap(), "a:\n  c:\n  - x\n  - y\n  z: true\nb: 1\n");
+    }
+
+    #[test]
+    fn test_multiple_documents() {
+        let documents = parse_documents("a: 1\n---\nb: 2\n").unwrap();
+        assert_eq!(documents.len(), 2);
+        assert_eq!(format_documents(&documents).unwrap(), "a: 1\n---\nb: 2\n");
+    }
+
+    #[test]
+    fn test_errors_report_line() {
+        let error = parse_documents("a: 1\nb: [1, 2\nc: 3\n").unwrap_err();
+        assert!(error.contains("line"), "{}", error);
+        assert!(error.contains(" | "), "{}", error);
+
+        let duplicate = parse_documents("a: 1\na: 2\n").unwrap_err();
+        assert!(duplicate.contains("duplicate"), "{}", duplicate);
+    }
+}
@@ -1,5 +1,11 @@
 use clap::{ArgMatches, Command};
 use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::future::Future;
+use std::io::{self, Read};
+use std::sync::OnceLock;
+use tokio::runtime::Runtime;
 
 pub trait ToolModule {
     fn name(&self) -> &'static str;
@@ -8,3 +14,89 @@ pub trait ToolModule {
 }
 
 pub type ToolModuleBox = Box<dyn ToolModule>;
+
+// Runs async work from a module's execute on one runtime shared by the whole
+// process, started the first time a module needs it
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| Runtime::new().expect("failed to start the async runtime")).block_on(future)
+}
+
+// A file path, or stdin when no path (or "-") is given
+pub fn read_input(path: Option<&String>) -> Result<String, Box<dyn Error>> {
+    match path.map(String::as_str) {
+        Some(path) if path != "-" => fs::read_to_string(path).map_err(|e| format!("Failed to read '{}': {}", path, e).into()),
+        _ => {
+            let mut buffer = String::new();
+            io::stdin().read_to_string(&mut buffer)?;
+            Ok(buffer)
+        }
+    }
+}
+
+// Like read_input, for input that need not be text
+pub fn read_input_bytes(path: Option<&String>) -> Result<Vec<u8>, Box<dyn Error>> {
+    match path.map(String::as_str) {
+        Some(path) if path != "-" => fs::read(path).map_err(|e| format!("Failed to read '{}': {}", path, e).into()),
+        _ => {
+            let mut buffer = Vec::new();
+            io::stdin().read_to_end(&mut buffer)?;
+            Ok(buffer)
+        }
+    }
+}
+
+// The value itself, or stdin for "-" minus the newline that echo and heredocs add
+pub fn read_text(value: &str) -> Result<String, Box<dyn Error>> {
+    if value != "-" {
+        return Ok(value.to_string());
+    }
+    let mut buffer = String::new();
+    io::stdin().read_to_string(&mut buffer)?;
+    let trimmed = buffer.strip_suffix('\n').map(|text| text.strip_suffix('\r').unwrap_or(text));
+    Ok(trimmed.map(str::to_string).unwrap_or(buffer))
+}
+
+// Whole numbers without a fraction (and -0 as 0), others to at most max_decimals
+// with trailing zeros dropped; NaN and infinity print as NaN, inf and -inf
+pub fn format_number(value: f64, max_decimals: usize) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        return format!("{}", value as i64);
+    }
+    let text = format!("{:.*}", max_decimals, value);
+    if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        text
+    }
+}
+
+// Error that asks main to exit with a specific code instead of the generic 1,
+// e.g. to pass through a child process's exit status. Silent errors only set
+// the exit code; the module has already reported what happened.
+#[derive(Debug)]
+pub struct ExitCodeError {
+    pub code: i32,
+    pub message: Option<String>,
+}
+
+impl ExitCodeError {
+    pub fn new(code: i32, message: impl Into<String>) -> Self {
+        Self { code, message: Some(message.into()) }
+    }
+
+    pub fn silent(code: i32) -> Self {
+        Self { code, message: None }
+    }
+}
+
+impl fmt::Display for ExitCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.message {
+            Some(message) => write!(f, "{}", message),
+            None => write!(f, "exited with code {}", self.code),
+        }
+    }
+}
+
+impl Error for ExitCodeError {}
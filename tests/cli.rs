@@ -0,0 +1,102 @@
+// End-to-end checks of the exit codes scripts and CI jobs rely on
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn micro_swiss(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_micro-swiss")).args(args).output().expect("failed to run micro-swiss")
+}
+
+fn temp_file(name: &str, content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("ms-cli-{}-{}", std::process::id(), name));
+    fs::write(&path, content).unwrap();
+    path
+}
+
+#[test]
+fn test_run_exit_codes() {
+    let ok = temp_file("ok.sh", "echo hi\n");
+    let failing = temp_file("fail.sh", "exit 4\n");
+    assert_eq!(micro_swiss(&["--run", ok.to_str().unwrap()]).status.code(), Some(0));
+    assert_eq!(micro_swiss(&["--run", failing.to_str().unwrap()]).status.code(), Some(4));
+    let missing = micro_swiss(&["--run", "definitely-missing.sh"]);
+    assert_eq!(missing.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&missing.stderr).contains("'definitely-missing.sh' does not exist"));
+    fs::remove_file(ok).unwrap();
+    fs::remove_file(failing).unwrap();
+}
+
+#[test]
+fn test_no_command_exits_nonzero() {
+    assert_eq!(micro_swiss(&[]).status.code(), Some(1));
+    assert_eq!(micro_swiss(&["--encode", "hi"]).status.code(), Some(0));
+}
+
+#[test]
+fn test_scan_secrets_exit_codes() {
+    let dir = std::env::temp_dir().join(format!("ms-cli-{}-secrets", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("config.py"), "DEBUG = True\n").unwrap();
+    let clean = micro_swiss(&["--scan-secrets", dir.to_str().unwrap()]);
+    assert_eq!(clean.status.code(), Some(0));
+    // Assembled at runtime so this file does not trip the scanner itself
+    fs::write(dir.join(".env"), format!("aws_access_key_id = AKIA{}\n", "IOSFODNN7EXAMPLE")).unwrap();
+    let found = micro_swiss(&["--scan-secrets", dir.to_str().unwrap()]);
+    assert_eq!(found.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&found.stdout).contains(".env"));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_semver_satisfies_exit_codes() {
+    assert_eq!(micro_swiss(&["--semver-satisfies", "^1.2.0", "1.4.2"]).status.code(), Some(0));
+    assert_eq!(micro_swiss(&["--semver-satisfies", "^1.2.0", "2.0.0"]).status.code(), Some(1));
+}
+
+#[test]
+fn test_cidr_contains_exit_codes() {
+    assert_eq!(micro_swiss(&["--cidr-contains", "10.0.0.0/8", "10.1.2.3"]).status.code(), Some(0));
+    assert_eq!(micro_swiss(&["--cidr-contains", "10.0.0.0/8", "192.168.1.1"]).status.code(), Some(1));
+}
+
+#[test]
+fn test_diff_exit_codes() {
+    let a = temp_file("a.txt", "one\ntwo\n");
+    let b = temp_file("b.txt", "one\nthree\n");
+    assert_eq!(micro_swiss(&["--diff", a.to_str().unwrap(), a.to_str().unwrap()]).status.code(), Some(0));
+    let different = micro_swiss(&["--diff", a.to_str().unwrap(), b.to_str().unwrap()]);
+    assert_eq!(different.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&different.stdout).contains("+three"));
+    assert_eq!(micro_swiss(&["--diff", "same", "same", "--format", "json"]).status.code(), Some(0));
+    fs::remove_file(a).unwrap();
+    fs::remove_file(b).unwrap();
+}
+
+#[test]
+fn test_password_check_empty_value() {
+    let output = micro_swiss(&["--password-check", ""]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No password given"));
+}
+
+#[test]
+fn test_watch_flags_are_not_mixed() {
+    let command_to_watch = micro_swiss(&["--watch", "echo hi"]);
+    assert_eq!(command_to_watch.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&command_to_watch.stderr).contains("--run <FILE>"));
+    let both = micro_swiss(&["--watch-cmd", "echo hi", "--run", "app.py"]);
+    assert_eq!(both.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&both.stderr).contains("cannot be used with"));
+}
+
+#[test]
+fn test_stats_needs_run() {
+    let numbers = temp_file("numbers.txt", "1\n2\n3\n");
+    let summary = micro_swiss(&["--num-stats", numbers.to_str().unwrap()]);
+    assert_eq!(summary.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&summary.stdout).contains("mean"));
+    let bare = micro_swiss(&["--stats", numbers.to_str().unwrap()]);
+    assert_eq!(bare.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&bare.stderr).contains("--run <FILE>"));
+    fs::remove_file(numbers).unwrap();
+}